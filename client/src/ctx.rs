@@ -271,6 +271,8 @@ impl SessionContext {
             chunked: Some(chunked),
             target_partitions,
             stream_trigger_interval,
+            params: None,
+            dialect: None,
         };
 
         // let param = &[("db", &self.session_config.database)];
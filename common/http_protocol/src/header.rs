@@ -12,18 +12,24 @@ pub const APPLICATION_TSV: &str = "application/tsv";
 pub const APPLICATION_JSON: &str = "application/json";
 pub const APPLICATION_NDJSON: &str = "application/nd-json";
 pub const APPLICATION_TABLE: &str = "application/table";
+pub const APPLICATION_ARROW_STREAM: &str = "application/vnd.apache.arrow.stream";
+pub const APPLICATION_PARQUET: &str = "application/vnd.apache.parquet";
+pub const APPLICATION_PROTOBUF: &str = "application/x-protobuf";
 pub const APPLICATION_STAR: &str = "application/*";
 pub const STAR_STAR: &str = "*/*";
 
 // basic auth
 pub const BASIC_PREFIX: &str = "Basic ";
 pub const BEARER_PREFIX: &str = "Bearer ";
+// influxdb v2 token auth, the token is `username:password`
+pub const TOKEN_PREFIX: &str = "Token ";
 
 // parameters
 pub const TENANT: &str = "tenant";
 pub const DB: &str = "db";
 pub const TARGET_PARTITIONS: &str = "target_partitions";
 pub const STREAM_TRIGGER_INTERVAL: &str = "stream_trigger_interval";
+pub const DIALECT: &str = "dialect";
 
 // encoding
 pub const GZIP: &str = "gzip";
@@ -9,6 +9,21 @@ pub struct SqlParam {
     // Number of partitions for query execution. Increasing partitions can increase concurrency.
     pub target_partitions: Option<usize>,
     pub stream_trigger_interval: Option<String>,
+    // Values of the placeholders in the sql, a json array for `$1, $2...`
+    // or a json object for `$name`.
+    pub params: Option<String>,
+    // Language of the query, `sql` (default) or `influxql`.
+    pub dialect: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PreparedStatementParam {
+    pub tenant: Option<String>,
+    // Handle returned by preparing the statement.
+    pub handle: String,
+    // Values of the placeholders in the statement, same as `SqlParam::params`.
+    pub params: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -19,6 +34,76 @@ pub struct WriteParam {
     pub db: Option<String>,
 }
 
+// Parameters of the influxdb v2 write api, org is the tenant and bucket is the database.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WriteV2Param {
+    pub org: Option<String>,
+    // `database` or `database/retention_policy`, the retention policy is ignored.
+    pub bucket: String,
+    // One of `ns`, `us`, `ms` and `s`, defaults to `ns`.
+    pub precision: Option<String>,
+}
+
+// Parameters of the influxdb v1 query api, passed in the url or in a form body.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct InfluxQueryParam {
+    pub tenant: Option<String>,
+    pub db: Option<String>,
+    // InfluxQL statements separated by `;`.
+    pub q: Option<String>,
+    // One of `ns`, `u`, `ms`, `s`, `m` and `h`, timestamps of the results are integers
+    // in the unit, or rfc3339 strings if it's absent.
+    pub epoch: Option<String>,
+    // User and password, used if there is no authorization header.
+    pub u: Option<String>,
+    pub p: Option<String>,
+}
+
+// Parameters of the prometheus query api, passed in the url or in a form body.
+// Times are unix timestamps in seconds or rfc3339 times, step is seconds or a duration.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PromQueryParam {
+    pub tenant: Option<String>,
+    pub db: Option<String>,
+    pub query: Option<String>,
+    // Evaluation time of an instant query, defaults to now.
+    pub time: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub step: Option<String>,
+}
+
+// Parameters of the prometheus series, labels and label values apis,
+// `match[]` may be repeated so they are collected from the key-value pairs.
+#[derive(Debug, Default)]
+pub struct PromSeriesParam {
+    pub tenant: Option<String>,
+    pub db: Option<String>,
+    pub matchers: Vec<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+impl PromSeriesParam {
+    pub fn from_pairs(pairs: Vec<(String, String)>) -> Self {
+        let mut param = Self::default();
+        for (key, value) in pairs {
+            match key.as_str() {
+                "tenant" => param.tenant = Some(value),
+                "db" => param.db = Some(value),
+                "match[]" => param.matchers.push(value),
+                "start" => param.start = Some(value),
+                "end" => param.end = Some(value),
+                _ => {}
+            }
+        }
+        param
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct DumpParam {
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 use std::fmt::Display;
+use std::time::Duration;
 
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
@@ -33,6 +34,10 @@ impl User {
         &self.desc
     }
 
+    pub fn privileges(&self) -> &HashSet<Privilege<Oid>> {
+        &self.privileges
+    }
+
     pub fn check_privilege(&self, privilege: &Privilege<Oid>) -> bool {
         self.privileges.iter().any(|e| e.check_privilege(privilege))
     }
@@ -132,6 +137,12 @@ pub struct UserOptions {
     comment: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     granted_admin: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_concurrent_queries: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_execution_time: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query_queue_timeout: Option<Duration>,
 }
 
 impl UserOptions {
@@ -150,6 +161,21 @@ impl UserOptions {
     pub fn granted_admin(&self) -> Option<bool> {
         self.granted_admin
     }
+    pub fn max_concurrent_queries(&self) -> Option<u32> {
+        self.max_concurrent_queries
+    }
+    pub fn max_execution_time(&self) -> Option<Duration> {
+        self.max_execution_time
+    }
+    pub fn query_queue_timeout(&self) -> Option<Duration> {
+        self.query_queue_timeout
+    }
+    /// Whether any limit of the queries is set
+    pub fn has_query_quota(&self) -> bool {
+        self.max_concurrent_queries.is_some()
+            || self.max_execution_time.is_some()
+            || self.query_queue_timeout.is_some()
+    }
 
     pub fn merge(self, other: Self) -> Self {
         Self {
@@ -158,6 +184,9 @@ impl UserOptions {
             rsa_public_key: self.rsa_public_key.or(other.rsa_public_key),
             comment: self.comment.or(other.comment),
             granted_admin: self.granted_admin.or(other.granted_admin),
+            max_concurrent_queries: self.max_concurrent_queries.or(other.max_concurrent_queries),
+            max_execution_time: self.max_execution_time.or(other.max_execution_time),
+            query_queue_timeout: self.query_queue_timeout.or(other.query_queue_timeout),
         }
     }
     pub fn hidden_password(&mut self) {
@@ -191,6 +220,18 @@ impl Display for UserOptions {
             write!(f, "granted_admin={},", e)?;
         }
 
+        if let Some(ref e) = self.max_concurrent_queries {
+            write!(f, "max_concurrent_queries={},", e)?;
+        }
+
+        if let Some(ref e) = self.max_execution_time {
+            write!(f, "max_execution_time={:?},", e)?;
+        }
+
+        if let Some(ref e) = self.query_queue_timeout {
+            write!(f, "query_queue_timeout={:?},", e)?;
+        }
+
         Ok(())
     }
 }
@@ -13,14 +13,16 @@ pub const BIGINT_CODEC: [Encoding; 4] = [
 pub const TIMESTAMP_CODEC: [Encoding; 4] = BIGINT_CODEC;
 pub const UNSIGNED_BIGINT_CODEC: [Encoding; 4] = BIGINT_CODEC;
 
-pub const DOUBLE_CODEC: [Encoding; 4] = [
+pub const DOUBLE_CODEC: [Encoding; 6] = [
     Encoding::Default,
     Encoding::Null,
     Encoding::Gorilla,
     Encoding::Quantile,
+    Encoding::Chimp,
+    Encoding::Alp,
 ];
 
-pub const STRING_CODEC: [Encoding; 7] = [
+pub const STRING_CODEC: [Encoding; 8] = [
     Encoding::Default,
     Encoding::Null,
     Encoding::Gzip,
@@ -28,8 +30,11 @@ pub const STRING_CODEC: [Encoding; 7] = [
     Encoding::Zstd,
     Encoding::Snappy,
     Encoding::Zlib,
+    Encoding::Dictionary,
 ];
 
+pub const DECIMAL_CODEC: [Encoding; 3] = [Encoding::Default, Encoding::Null, Encoding::Delta];
+
 pub const BOOLEAN_CODEC: [Encoding; 3] = [Encoding::Default, Encoding::Null, Encoding::BitPack];
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Hash, Default)]
@@ -46,6 +51,12 @@ pub enum Encoding {
     Zstd = 8,
     Zlib = 9,
     BitPack = 10,
+    /// Dictionary encoding for low-cardinality strings.
+    Dictionary = 11,
+    /// Chimp128 XOR encoding for floats.
+    Chimp = 12,
+    /// Adaptive lossless floating-point encoding for decimal-origin floats.
+    Alp = 13,
     Unknown = 15,
 }
 
@@ -74,6 +85,10 @@ impl Encoding {
         BOOLEAN_CODEC.contains(self)
     }
 
+    pub fn is_decimal_encoding(&self) -> bool {
+        DECIMAL_CODEC.contains(self)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Encoding::Default => "DEFAULT",
@@ -87,6 +102,9 @@ impl Encoding {
             Encoding::Zstd => "ZSTD",
             Encoding::Zlib => "ZLIB",
             Encoding::BitPack => "BITPACK",
+            Encoding::Dictionary => "DICTIONARY",
+            Encoding::Chimp => "CHIMP",
+            Encoding::Alp => "ALP",
             Encoding::Unknown => "UNKNOWN",
         }
     }
@@ -107,6 +125,9 @@ impl FromStr for Encoding {
             "ZSTD" => Ok(Self::Zstd),
             "ZLIB" => Ok(Self::Zlib),
             "BITPACK" => Ok(Self::BitPack),
+            "DICTIONARY" => Ok(Self::Dictionary),
+            "CHIMP" => Ok(Self::Chimp),
+            "ALP" => Ok(Self::Alp),
             _ => Err(s.to_string()),
         }
     }
@@ -126,6 +147,9 @@ impl From<u8> for Encoding {
             8 => Encoding::Zstd,
             9 => Encoding::Zlib,
             10 => Encoding::BitPack,
+            11 => Encoding::Dictionary,
+            12 => Encoding::Chimp,
+            13 => Encoding::Alp,
             _ => Encoding::Unknown,
         }
     }
@@ -7,7 +7,10 @@ use parking_lot::RwLock;
 pub use record_batch::*;
 pub use series_info::SeriesKey;
 pub use tag::Tag;
-pub use value_type::{PhysicalDType, ValueType};
+pub use value_type::{
+    decimal_to_string, parse_decimal, PhysicalDType, ValueType, DECIMAL128_MAX_PRECISION,
+    DECIMAL_DEFAULT_SCALE,
+};
 
 pub mod codec;
 pub mod consistency_level;
@@ -7,7 +7,7 @@ use crate::auth::role::{CustomTenantRole, TenantRoleIdentifier};
 use crate::node_info::NodeStatus;
 use crate::oid::Oid;
 use crate::predicate::domain::TimeRange;
-use crate::schema::{DatabaseSchema, TableSchema};
+use crate::schema::{DatabaseSchema, SqlFunction, TableSchema};
 
 pub type VnodeId = u32;
 pub type NodeId = u64;
@@ -224,6 +224,9 @@ pub struct TenantMetaData {
     pub dbs: HashMap<String, DatabaseInfo>,
     pub roles: HashMap<String, CustomTenantRole<Oid>>,
     pub members: HashMap<String, TenantRoleIdentifier>,
+    // function_name -> sql function
+    #[serde(default)]
+    pub functions: HashMap<String, SqlFunction>,
 }
 
 impl TenantMetaData {
@@ -233,6 +236,7 @@ impl TenantMetaData {
             dbs: HashMap::new(),
             roles: HashMap::new(),
             members: HashMap::new(),
+            functions: HashMap::new(),
         }
     }
 
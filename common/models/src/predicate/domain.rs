@@ -20,9 +20,7 @@ use serde::{Deserialize, Serialize};
 use super::transformation::RowExpressionToDomainsVisitor;
 use super::utils::filter_to_time_ranges;
 use super::PlacedSplit;
-use crate::schema::{
-    ColumnType, ScalarValueForkDF, TableColumn, TskvTableSchema, TskvTableSchemaRef,
-};
+use crate::schema::{ColumnType, ScalarValueForkDF, TskvTableSchema, TskvTableSchemaRef};
 use crate::{Error, Result, Timestamp};
 
 pub type PredicateRef = Arc<Predicate>;
@@ -1159,6 +1157,35 @@ impl Domain {
             entries,
         })
     }
+    /// Returns the values if the domain only contains single values,
+    /// e.g. `col = 'a' OR col = 'b'`, otherwise returns None.
+    pub fn equal_values(&self) -> Option<Vec<&ScalarValue>> {
+        match self {
+            Self::Range(range_set) => range_set
+                .low_indexed_ranges
+                .values()
+                .map(|range| match (&range.low, &range.high) {
+                    (
+                        Marker {
+                            value: Some(low),
+                            bound: Bound::Exactly,
+                            ..
+                        },
+                        Marker {
+                            value: Some(high),
+                            bound: Bound::Exactly,
+                            ..
+                        },
+                    ) if low == high => Some(low),
+                    _ => None,
+                })
+                .collect(),
+            Self::Equtable(value_set) if value_set.white_list => {
+                Some(value_set.entries.iter().map(|e| &e.value).collect())
+            }
+            _ => None,
+        }
+    }
     /// Calculates the intersection of two ranges, and returns None if the intersection does not exist
     ///
     /// This method returns the new value without changing the old value
@@ -1717,6 +1744,7 @@ pub struct QueryExpr {
     pub split: PlacedSplit,
     pub df_schema: Schema,
     pub table_schema: TskvTableSchemaRef,
+    pub aggregate_grouping: Option<PushedAggregateGrouping>,
 }
 
 impl QueryExpr {
@@ -1735,7 +1763,7 @@ impl QueryExpr {
     }
 }
 
-pub fn encode_agg(agg: &Option<Vec<TableColumn>>) -> Result<Vec<u8>> {
+pub fn encode_agg(agg: &Option<Vec<PushedAggregateFunction>>) -> Result<Vec<u8>> {
     let d = bincode::serialize(agg).map_err(|err| Error::InvalidSerdeMessage {
         err: err.to_string(),
     })?;
@@ -1743,19 +1771,66 @@ pub fn encode_agg(agg: &Option<Vec<TableColumn>>) -> Result<Vec<u8>> {
     Ok(d)
 }
 
-pub fn decode_agg(buf: &[u8]) -> Result<Option<Vec<TableColumn>>> {
-    let args = bincode::deserialize::<Option<Vec<TableColumn>>>(buf).map_err(|err| {
-        Error::InvalidSerdeMessage {
-            err: err.to_string(),
-        }
-    })?;
+pub fn decode_agg(buf: &[u8]) -> Result<Option<Vec<PushedAggregateFunction>>> {
+    let args =
+        bincode::deserialize::<Option<Vec<PushedAggregateFunction>>>(buf).map_err(|err| {
+            Error::InvalidSerdeMessage {
+                err: err.to_string(),
+            }
+        })?;
 
     Ok(args)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Aggregate functions pushed down to the storage, with name of the column to aggregate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PushedAggregateFunction {
     Count(String),
+    Max(String),
+    Min(String),
+    Sum(String),
+    /// Value of the minimum timestamp.
+    First(String),
+    /// Value of the maximum timestamp.
+    Last(String),
+}
+
+impl PushedAggregateFunction {
+    pub fn column_name(&self) -> &str {
+        match self {
+            Self::Count(column)
+            | Self::Max(column)
+            | Self::Min(column)
+            | Self::Sum(column)
+            | Self::First(column)
+            | Self::Last(column) => column,
+        }
+    }
+}
+
+/// Grouping of the aggregates pushed down to the storage, values of each series are
+/// grouped by time buckets `[origin + k * stride, origin + (k + 1) * stride)`,
+/// as `date_bin(stride, time, origin)` does.
+///
+/// The storage returns a row for each bucket of each series, the row is made up of
+/// the start of the bucket, values of the tags, and partial states of the aggregates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PushedAggregateGrouping {
+    /// Width of the time buckets, in the time unit of the table.
+    pub stride: i64,
+    /// Timestamp at the start of a time bucket, in the time unit of the table.
+    pub origin: i64,
+    /// Names of the tag columns to group by.
+    pub tags: Vec<String>,
+}
+
+impl PushedAggregateGrouping {
+    /// Returns start of the time bucket which the timestamp belongs to,
+    /// the start of the first bucket is clamped to `i64::MIN`.
+    pub fn time_bucket(&self, ts: Timestamp) -> Timestamp {
+        let bucket = (ts as i128 - self.origin as i128).div_euclid(self.stride as i128);
+        (self.origin as i128 + bucket * self.stride as i128).max(i64::MIN as i128) as Timestamp
+    }
 }
 
 #[cfg(test)]
@@ -2059,4 +2134,42 @@ mod tests {
             }
         };
     }
+
+    #[test]
+    fn test_equal_values() {
+        let a = ScalarValue::Utf8(Some("a".to_string()));
+        let b = ScalarValue::Utf8(Some("b".to_string()));
+
+        let domain = Domain::of_ranges(&[
+            Range::eq(&DataType::Utf8, &a),
+            Range::eq(&DataType::Utf8, &b),
+        ])
+        .unwrap();
+        assert_eq!(domain.equal_values(), Some(vec![&a, &b]));
+
+        let domain = Domain::of_values(&DataType::Utf8, true, &[&a]);
+        assert_eq!(domain.equal_values(), Some(vec![&a]));
+
+        let domain = Domain::of_ranges(&[Range::ge(&DataType::Utf8, &a)]).unwrap();
+        assert_eq!(domain.equal_values(), None);
+        let domain = Domain::of_values(&DataType::Utf8, false, &[&a]);
+        assert_eq!(domain.equal_values(), None);
+        assert_eq!(Domain::All.equal_values(), None);
+    }
+
+    #[test]
+    fn test_aggregate_grouping_time_bucket() {
+        let grouping = PushedAggregateGrouping {
+            stride: 10,
+            origin: 3,
+            tags: vec![],
+        };
+        assert_eq!(grouping.time_bucket(3), 3);
+        assert_eq!(grouping.time_bucket(12), 3);
+        assert_eq!(grouping.time_bucket(13), 13);
+        assert_eq!(grouping.time_bucket(2), -7);
+        assert_eq!(grouping.time_bucket(-7), -7);
+        assert_eq!(grouping.time_bucket(-8), -17);
+        assert_eq!(grouping.time_bucket(i64::MIN), i64::MIN);
+    }
 }
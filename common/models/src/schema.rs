@@ -33,6 +33,7 @@ use datafusion::scalar::ScalarValue;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
+use crate::auth::user::UserDesc;
 use crate::codec::Encoding;
 use crate::gis::data_type::Geometry;
 use crate::meta_data::ReplicationSet;
@@ -42,7 +43,10 @@ use crate::utils::{
     MINUTES_MICROS, MINUTES_MILLS, MINUTES_NANOS,
 };
 use crate::value_type::ValueType;
-use crate::{ColumnId, Error, PhysicalDType, SchemaId, Timestamp};
+use crate::{
+    ColumnId, Error, PhysicalDType, SchemaId, Timestamp, DECIMAL128_MAX_PRECISION,
+    DECIMAL_DEFAULT_SCALE,
+};
 
 pub type TskvTableSchemaRef = Arc<TskvTableSchema>;
 
@@ -202,11 +206,107 @@ impl ResourceInfo {
     }
 }
 
+pub const ROLLUP_AGGREGATE_FUNCTIONS: [&str; 7] =
+    ["avg", "sum", "min", "max", "count", "first", "last"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RollupAggregate {
+    pub field: String,
+    pub function: String,
+    /// Column of the target table, the aggregated field by default.
+    pub target: String,
+}
+
+impl RollupAggregate {
+    pub fn new(field: String, function: String, target: Option<String>) -> Self {
+        let target = target.unwrap_or_else(|| field.clone());
+        Self {
+            field,
+            function: function.to_lowercase(),
+            target,
+        }
+    }
+
+    pub fn is_valid_function(function: &str) -> bool {
+        ROLLUP_AGGREGATE_FUNCTIONS.contains(&function.to_lowercase().as_str())
+    }
+}
+
+/// Progress of a rollup policy, all timestamps are in nanoseconds.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RollupProgress {
+    /// Data before this time has been rolled up.
+    pub watermark: Option<i64>,
+    pub last_run_time: Option<i64>,
+    pub run_count: u64,
+    pub error_count: u64,
+    pub last_error: String,
+}
+
+/// Aggregates a source table into a target table on a schedule.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RollupPolicy {
+    pub tenant: String,
+    pub database: String,
+    pub name: String,
+    pub source_table: String,
+    pub target_table: String,
+    // time bucket of the target table and the schedule interval
+    pub interval: Duration,
+    pub aggregates: Vec<RollupAggregate>,
+    // data of target table older than it will be deleted
+    pub ttl: Option<Duration>,
+    pub owner: UserDesc,
+    pub progress: RollupProgress,
+}
+
+impl RollupPolicy {
+    /// Returns the time range `[start, end)` in nanoseconds to roll up at `now`,
+    /// only windows that are closed will be rolled up.
+    pub fn next_time_range(&self, now: i64) -> Option<(Option<i64>, i64)> {
+        let interval = self.interval.to_nanoseconds();
+        if interval <= 0 {
+            return None;
+        }
+        let end = now - now.rem_euclid(interval);
+        match self.progress.watermark {
+            Some(start) if start >= end => None,
+            start => Some((start, end)),
+        }
+    }
+}
+
+/// A scalar function of a tenant defined by a sql expression, e.g.
+/// `CREATE FUNCTION c_to_f(c DOUBLE) RETURNS DOUBLE AS c * 1.8 + 32`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SqlFunction {
+    pub tenant: String,
+    pub name: String,
+    pub args: Vec<(String, DataType)>,
+    pub return_type: DataType,
+    /// The sql text of the expression computing the result from the arguments.
+    pub body: String,
+}
+
+impl SqlFunction {
+    /// e.g. `c_to_f(c DOUBLE) RETURNS DOUBLE`
+    pub fn signature(&self) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|(name, data_type)| format!("{name} {data_type}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({}) RETURNS {}", self.name, args, self.return_type)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum TableSchema {
     TsKvTableSchema(TskvTableSchemaRef),
     ExternalTableSchema(Arc<ExternalTableSchema>),
     StreamTableSchema(Arc<StreamTable>),
+    ViewTableSchema(Arc<ViewTableSchema>),
 }
 
 impl TableSchema {
@@ -215,6 +315,7 @@ impl TableSchema {
             TableSchema::TsKvTableSchema(schema) => schema.name.as_str(),
             TableSchema::ExternalTableSchema(schema) => schema.name.as_str(),
             TableSchema::StreamTableSchema(schema) => schema.name(),
+            TableSchema::ViewTableSchema(schema) => schema.name.as_str(),
         }
     }
 
@@ -223,6 +324,42 @@ impl TableSchema {
             TableSchema::TsKvTableSchema(schema) => schema.db.as_str(),
             TableSchema::ExternalTableSchema(schema) => schema.db.as_str(),
             TableSchema::StreamTableSchema(schema) => schema.db(),
+            TableSchema::ViewTableSchema(schema) => schema.db.as_str(),
+        }
+    }
+
+    /// Copy the table into another database, e.g. when the database is restored from a backup.
+    pub fn with_database(&self, tenant: &str, db: &str) -> Self {
+        match self {
+            TableSchema::TsKvTableSchema(schema) => {
+                let mut schema = schema.as_ref().clone();
+                schema.tenant = tenant.to_string();
+                schema.db = db.to_string();
+                TableSchema::TsKvTableSchema(Arc::new(schema))
+            }
+            TableSchema::ExternalTableSchema(schema) => {
+                let mut schema = schema.as_ref().clone();
+                schema.tenant = tenant.to_string();
+                schema.db = db.to_string();
+                TableSchema::ExternalTableSchema(Arc::new(schema))
+            }
+            TableSchema::StreamTableSchema(schema) => {
+                TableSchema::StreamTableSchema(Arc::new(StreamTable::new(
+                    tenant,
+                    db,
+                    schema.name(),
+                    schema.schema(),
+                    schema.stream_type(),
+                    schema.watermark().clone(),
+                    schema.extra_options().clone(),
+                )))
+            }
+            TableSchema::ViewTableSchema(schema) => {
+                let mut schema = schema.as_ref().clone();
+                schema.tenant = tenant.to_string();
+                schema.db = db.to_string();
+                TableSchema::ViewTableSchema(Arc::new(schema))
+            }
         }
     }
 
@@ -231,6 +368,7 @@ impl TableSchema {
             TableSchema::TsKvTableSchema(_) => "TSKV",
             TableSchema::ExternalTableSchema(_) => "EXTERNAL",
             TableSchema::StreamTableSchema(_) => "STREAM",
+            TableSchema::ViewTableSchema(_) => "VIEW",
         }
     }
 
@@ -239,10 +377,23 @@ impl TableSchema {
             Self::ExternalTableSchema(e) => Arc::new(e.schema.clone()),
             Self::TsKvTableSchema(e) => e.to_arrow_schema(),
             Self::StreamTableSchema(e) => e.schema(),
+            Self::ViewTableSchema(e) => Arc::new(e.schema.clone()),
         }
     }
 }
 
+/// A view persisted in meta, the query is planned again every time the view is referenced
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ViewTableSchema {
+    pub tenant: String,
+    pub db: String,
+    pub name: String,
+    /// The sql text of the view definition, e.g. `SELECT * FROM t WHERE a > 1`
+    pub query: String,
+    /// The output schema of the query when the view was created
+    pub schema: Schema,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ExternalTableSchema {
     pub tenant: String,
@@ -545,7 +696,15 @@ impl From<&TableColumn> for ArrowField {
         }
 
         let nullable = column.nullable();
-        let mut f = ArrowField::new(&column.name, column.column_type.clone().into(), nullable);
+        let data_type = if column.is_dictionary_encoded() {
+            ArrowDataType::Dictionary(
+                Box::new(ArrowDataType::Int32),
+                Box::new(ArrowDataType::Utf8),
+            )
+        } else {
+            column.column_type.clone().into()
+        };
+        let mut f = ArrowField::new(&column.name, data_type, nullable);
         f.set_metadata(map);
         f
     }
@@ -604,6 +763,13 @@ impl TableColumn {
         !matches!(self.column_type, ColumnType::Time(_))
     }
 
+    /// Returns true if the column is a string field in dictionary encoding,
+    /// whose values are read as arrow dictionary arrays.
+    pub fn is_dictionary_encoded(&self) -> bool {
+        self.encoding == Encoding::Dictionary
+            && matches!(self.column_type, ColumnType::Field(ValueType::String))
+    }
+
     pub fn encode(&self) -> crate::errors::Result<Vec<u8>> {
         let buf = bincode::serialize(&self)
             .map_err(|e| Error::InvalidSerdeMessage { err: e.to_string() })?;
@@ -629,6 +795,14 @@ impl TableColumn {
             return self.encoding.is_unsigned_encoding();
         } else if let ColumnType::Field(ValueType::String) = self.column_type {
             return self.encoding.is_string_encoding();
+        } else if let ColumnType::Field(ValueType::Int32) = self.column_type {
+            return self.encoding.is_bigint_encoding();
+        } else if let ColumnType::Field(ValueType::Float32) = self.column_type {
+            return self.encoding.is_double_encoding();
+        } else if let ColumnType::Field(ValueType::Binary) = self.column_type {
+            return self.encoding.is_string_encoding();
+        } else if let ColumnType::Field(ValueType::Decimal128(..)) = self.column_type {
+            return self.encoding.is_decimal_encoding();
         } else if let ColumnType::Time(_) = self.column_type {
             return self.encoding.is_timestamp_encoding();
         } else if let ColumnType::Tag = self.column_type {
@@ -650,6 +824,12 @@ impl From<ColumnType> for ArrowDataType {
             ColumnType::Field(ValueType::String) => ArrowDataType::Utf8,
             ColumnType::Field(ValueType::Boolean) => ArrowDataType::Boolean,
             ColumnType::Field(ValueType::Geometry(_)) => ArrowDataType::Utf8,
+            ColumnType::Field(ValueType::Int32) => ArrowDataType::Int32,
+            ColumnType::Field(ValueType::Float32) => ArrowDataType::Float32,
+            ColumnType::Field(ValueType::Binary) => ArrowDataType::Binary,
+            ColumnType::Field(ValueType::Decimal128(precision, scale)) => {
+                ArrowDataType::Decimal128(precision, scale)
+            }
             _ => ArrowDataType::Null,
         }
     }
@@ -678,6 +858,10 @@ impl ColumnType {
             Self::Field(ValueType::Boolean) => "BOOL",
             Self::Field(ValueType::String) => "STRING",
             Self::Field(ValueType::Geometry(..)) => "GEOMETRY",
+            Self::Field(ValueType::Int32) => "I32",
+            Self::Field(ValueType::Float32) => "F32",
+            Self::Field(ValueType::Binary) => "BINARY",
+            Self::Field(ValueType::Decimal128(..)) => "DECIMAL",
             _ => "Error filed type not supported",
         }
     }
@@ -697,6 +881,10 @@ impl ColumnType {
             Self::Field(ValueType::Unsigned) => 2,
             Self::Field(ValueType::Boolean) => 3,
             Self::Field(ValueType::String) | Self::Field(ValueType::Geometry(_)) => 4,
+            Self::Field(ValueType::Int32) => 5,
+            Self::Field(ValueType::Float32) => 6,
+            Self::Field(ValueType::Decimal128(..)) => 7,
+            Self::Field(ValueType::Binary) => 8,
             _ => 0,
         }
     }
@@ -708,6 +896,13 @@ impl ColumnType {
             2 => Self::Field(ValueType::Unsigned),
             3 => Self::Field(ValueType::Boolean),
             4 => Self::Field(ValueType::String),
+            5 => Self::Field(ValueType::Int32),
+            6 => Self::Field(ValueType::Float32),
+            7 => Self::Field(ValueType::Decimal128(
+                DECIMAL128_MAX_PRECISION,
+                DECIMAL_DEFAULT_SCALE,
+            )),
+            8 => Self::Field(ValueType::Binary),
             _ => Self::Field(ValueType::Unknown),
         }
     }
@@ -729,6 +924,10 @@ impl ColumnType {
                 ValueType::Boolean => "BOOLEAN".into(),
                 ValueType::Unknown => "UNKNOWN".into(),
                 ValueType::Geometry(geo) => geo.to_string().into(),
+                ValueType::Int32
+                | ValueType::Float32
+                | ValueType::Binary
+                | ValueType::Decimal128(..) => value_type.to_sql_type_str(),
             },
         }
     }
@@ -770,6 +969,9 @@ impl ColumnType {
         self.eq(other)
             || (matches!(self, ColumnType::Field(ValueType::Geometry(..)))
                 && matches!(other, ColumnType::Field(ValueType::String)))
+            // Decimals are written as text, and converted with the scale of the column.
+            || (matches!(self, ColumnType::Field(ValueType::Decimal128(..)))
+                && matches!(other, ColumnType::Field(ValueType::Decimal128(..))))
     }
 }
 
@@ -849,6 +1051,23 @@ impl DatabaseSchema {
         };
         now - ttl
     }
+
+    // return the max timestamp value of data that should be moved to the cold storage,
+    // None if database has no cold duration.
+    pub fn time_to_cold(&self) -> Option<i64> {
+        let cold_duration = self
+            .config
+            .cold_duration()
+            .as_ref()
+            .filter(|d| d.unit != DurationUnit::Inf)?;
+        let precision = *self.config.precision_or_default();
+        let now = match precision {
+            Precision::MS => crate::utils::now_timestamp_millis(),
+            Precision::US => crate::utils::now_timestamp_micros(),
+            Precision::NS => crate::utils::now_timestamp_nanos(),
+        };
+        Some(now.saturating_sub(cold_duration.to_precision(precision)))
+    }
 }
 
 pub fn make_owner(tenant_name: &str, database_name: &str) -> String {
@@ -879,6 +1098,15 @@ pub struct DatabaseOptions {
     replica: Option<u64>,
     // timestamp precision
     precision: Option<Precision>,
+    // data older than it will be moved to the cold storage
+    #[serde(default)]
+    cold_duration: Option<Duration>,
+    // strategy to pick files for compaction
+    #[serde(default)]
+    compaction_strategy: Option<CompactionStrategy>,
+    // time window of the time window compaction strategy
+    #[serde(default)]
+    compaction_window: Option<Duration>,
 
     db_is_hidden: bool,
 }
@@ -895,6 +1123,11 @@ impl DatabaseOptions {
         unit: DurationUnit::Day,
     };
     pub const DEFAULT_PRECISION: Precision = Precision::NS;
+    pub const DEFAULT_COMPACTION_STRATEGY: CompactionStrategy = CompactionStrategy::Level;
+    pub const DEFAULT_COMPACTION_WINDOW: Duration = Duration {
+        time_num: 1,
+        unit: DurationUnit::Day,
+    };
 
     pub fn new(
         ttl: Option<Duration>,
@@ -909,6 +1142,9 @@ impl DatabaseOptions {
             vnode_duration,
             replica,
             precision,
+            cold_duration: None,
+            compaction_strategy: None,
+            compaction_window: None,
             db_is_hidden: false,
         }
     }
@@ -977,6 +1213,42 @@ impl DatabaseOptions {
         self.precision = Some(precision)
     }
 
+    pub fn cold_duration(&self) -> &Option<Duration> {
+        &self.cold_duration
+    }
+
+    pub fn with_cold_duration(&mut self, cold_duration: Duration) {
+        self.cold_duration = Some(cold_duration);
+    }
+
+    pub fn compaction_strategy(&self) -> &Option<CompactionStrategy> {
+        &self.compaction_strategy
+    }
+
+    pub fn compaction_strategy_or_default(&self) -> &CompactionStrategy {
+        self.compaction_strategy
+            .as_ref()
+            .unwrap_or(&DatabaseOptions::DEFAULT_COMPACTION_STRATEGY)
+    }
+
+    pub fn with_compaction_strategy(&mut self, compaction_strategy: CompactionStrategy) {
+        self.compaction_strategy = Some(compaction_strategy);
+    }
+
+    pub fn compaction_window(&self) -> &Option<Duration> {
+        &self.compaction_window
+    }
+
+    pub fn compaction_window_or_default(&self) -> &Duration {
+        self.compaction_window
+            .as_ref()
+            .unwrap_or(&DatabaseOptions::DEFAULT_COMPACTION_WINDOW)
+    }
+
+    pub fn with_compaction_window(&mut self, compaction_window: Duration) {
+        self.compaction_window = Some(compaction_window);
+    }
+
     pub fn get_db_is_hidden(&self) -> bool {
         self.db_is_hidden
     }
@@ -1043,6 +1315,33 @@ impl Precision {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompactionStrategy {
+    /// Compact files level by level.
+    Level,
+    /// Only compact files in the same time window.
+    TimeWindow,
+}
+
+impl CompactionStrategy {
+    pub fn new(text: &str) -> Option<Self> {
+        match text.to_uppercase().as_str() {
+            "LEVEL" => Some(CompactionStrategy::Level),
+            "TIME_WINDOW" => Some(CompactionStrategy::TimeWindow),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CompactionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompactionStrategy::Level => f.write_str("LEVEL"),
+            CompactionStrategy::TimeWindow => f.write_str("TIME_WINDOW"),
+        }
+    }
+}
+
 pub fn timestamp_convert(from: Precision, to: Precision, ts: Timestamp) -> Option<Timestamp> {
     match (from, to) {
         (Precision::NS, Precision::US) | (Precision::US, Precision::MS) => Some(ts / 1_000),
@@ -1210,6 +1509,13 @@ pub struct TenantOptions {
     pub limiter_config: Option<TenantLimiterConfig>,
     drop_after: Option<Duration>, // None means now
     tenant_is_hidden: bool,
+    /// Max number of the queries of the tenant running at the same time
+    pub max_concurrent_queries: Option<u32>,
+    /// Queries of the tenant running longer than it are cancelled
+    pub max_execution_time: Option<StdDuration>,
+    /// How long a query waits for a free slot when the concurrent queries are
+    /// limited, it's rejected at once if not set
+    pub query_queue_timeout: Option<StdDuration>,
 }
 
 impl From<TenantOptions> for TenantOptionsBuilder {
@@ -1224,6 +1530,15 @@ impl From<TenantOptions> for TenantOptionsBuilder {
         if let Some(drop_after) = value.get_drop_after() {
             builder.drop_after(drop_after);
         }
+        if let Some(max_concurrent_queries) = value.max_concurrent_queries {
+            builder.max_concurrent_queries(max_concurrent_queries);
+        }
+        if let Some(max_execution_time) = value.max_execution_time {
+            builder.max_execution_time(max_execution_time);
+        }
+        if let Some(query_queue_timeout) = value.query_queue_timeout {
+            builder.query_queue_timeout(query_queue_timeout);
+        }
         builder.tenant_is_hidden(false);
         builder
     }
@@ -1239,6 +1554,15 @@ impl TenantOptionsBuilder {
     pub fn unset_drop_after(&mut self) {
         self.drop_after = None;
     }
+    pub fn unset_max_concurrent_queries(&mut self) {
+        self.max_concurrent_queries = None;
+    }
+    pub fn unset_max_execution_time(&mut self) {
+        self.max_execution_time = None;
+    }
+    pub fn unset_query_queue_timeout(&mut self) {
+        self.query_queue_timeout = None;
+    }
 }
 
 impl TenantOptions {
@@ -1281,6 +1605,18 @@ impl Display for TenantOptions {
             write!(f, "limiter=None,")?;
         }
 
+        if let Some(e) = self.max_concurrent_queries {
+            write!(f, "max_concurrent_queries={e},")?;
+        }
+
+        if let Some(e) = self.max_execution_time {
+            write!(f, "max_execution_time={e:?},")?;
+        }
+
+        if let Some(e) = self.query_queue_timeout {
+            write!(f, "query_queue_timeout={e:?},")?;
+        }
+
         Ok(())
     }
 }
@@ -8,7 +8,7 @@ use crate::auth::user::UserDesc;
 use crate::oid::{Identifier, Oid};
 use crate::schema::{
     ColumnType, DatabaseSchema, DurationUnit, ExternalTableSchema, StreamTable, TableSchema,
-    Tenant, TskvTableSchema,
+    Tenant, TskvTableSchema, ViewTableSchema,
 };
 use crate::{Error, SqlParserValue};
 
@@ -237,11 +237,13 @@ pub fn create_table_sqls(tables: &[TableSchema], if_not_exists: bool) -> Result<
     let mut ts_table = vec![];
     let mut ex_table = vec![];
     let mut stream_table = vec![];
+    let mut view_table = vec![];
     for table in tables {
         match table {
             TableSchema::TsKvTableSchema(t) => ts_table.push(t),
             TableSchema::ExternalTableSchema(t) => ex_table.push(t),
             TableSchema::StreamTableSchema(s) => stream_table.push(s),
+            TableSchema::ViewTableSchema(v) => view_table.push(v),
         }
     }
 
@@ -257,6 +259,11 @@ pub fn create_table_sqls(tables: &[TableSchema], if_not_exists: bool) -> Result<
         res.push(stream.to_ddl_sql(if_not_exists)?)
     }
 
+    // views depend on the tables above, so create them last
+    for view in view_table.into_iter() {
+        res.push(view.to_ddl_sql(if_not_exists)?)
+    }
+
     Ok(res)
 }
 
@@ -267,10 +274,30 @@ impl ToDDLSql for TableSchema {
             TableSchema::TsKvTableSchema(t) => t.to_ddl_sql(if_not_exists),
             TableSchema::ExternalTableSchema(t) => t.to_ddl_sql(if_not_exists),
             TableSchema::StreamTableSchema(t) => t.to_ddl_sql(if_not_exists),
+            TableSchema::ViewTableSchema(t) => t.to_ddl_sql(if_not_exists),
         }
     }
 }
 
+// CREATE VIEW
+impl ToDDLSql for ViewTableSchema {
+    fn to_ddl_sql(&self, if_not_exists: bool) -> Result<String> {
+        // a view has no 'if not exists' clause, replace it instead
+        let sql = if if_not_exists {
+            format!(
+                "create or replace view \"{}\".\"{}\" as {};",
+                self.db, self.name, self.query
+            )
+        } else {
+            format!(
+                "create view \"{}\".\"{}\" as {};",
+                self.db, self.name, self.query
+            )
+        };
+        Ok(sql)
+    }
+}
+
 // CREATE TS TABLE
 impl ToDDLSql for TskvTableSchema {
     fn to_ddl_sql(&self, if_not_exists: bool) -> Result<String> {
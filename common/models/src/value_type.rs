@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
 use serde::{Deserialize, Serialize};
@@ -13,8 +14,19 @@ pub enum ValueType {
     Boolean,
     String,
     Geometry(Geometry),
+    Int32,
+    Float32,
+    /// Stored as `PhysicalDType::String`.
+    Binary,
+    /// Decimal128 with precision and scale.
+    Decimal128(u8, i8),
 }
 
+/// Max precision of `ValueType::Decimal128`.
+pub const DECIMAL128_MAX_PRECISION: u8 = 38;
+/// Scale of decimal fields created by writes, which don't declare the precision and scale.
+pub const DECIMAL_DEFAULT_SCALE: i8 = 10;
+
 /// data type for tskv
 #[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Eq, Hash)]
 pub enum PhysicalDType {
@@ -24,6 +36,9 @@ pub enum PhysicalDType {
     Unsigned,
     Boolean,
     String,
+    Decimal,
+    Integer32,
+    Float32,
 }
 
 impl ValueType {
@@ -36,18 +51,26 @@ impl ValueType {
             Self::Boolean => PhysicalDType::Boolean,
             Self::String => PhysicalDType::String,
             Self::Geometry(_) => PhysicalDType::String,
+            Self::Int32 => PhysicalDType::Integer32,
+            Self::Float32 => PhysicalDType::Float32,
+            Self::Binary => PhysicalDType::String,
+            Self::Decimal128(..) => PhysicalDType::Decimal,
         }
     }
 
-    pub fn to_sql_type_str(&self) -> &'static str {
+    pub fn to_sql_type_str(&self) -> Cow<'static, str> {
         match self {
-            Self::Unknown => "UNKNOWN",
-            Self::Float => "DOUBLE",
-            Self::Integer => "BIGINT",
-            Self::Unsigned => "BIGINT UNSIGNED",
-            Self::Boolean => "BOOLEAN",
-            Self::String => "STRING",
-            Self::Geometry(_) => "GEOMETRY",
+            Self::Unknown => "UNKNOWN".into(),
+            Self::Float => "DOUBLE".into(),
+            Self::Integer => "BIGINT".into(),
+            Self::Unsigned => "BIGINT UNSIGNED".into(),
+            Self::Boolean => "BOOLEAN".into(),
+            Self::String => "STRING".into(),
+            Self::Geometry(_) => "GEOMETRY".into(),
+            Self::Int32 => "INT".into(),
+            Self::Float32 => "FLOAT".into(),
+            Self::Binary => "BINARY".into(),
+            Self::Decimal128(precision, scale) => format!("DECIMAL({precision}, {scale})").into(),
         }
     }
 }
@@ -61,6 +84,9 @@ impl Display for PhysicalDType {
             PhysicalDType::Unsigned => f.write_str("Unsigned"),
             PhysicalDType::Boolean => f.write_str("Boolean"),
             PhysicalDType::String => f.write_str("String"),
+            PhysicalDType::Decimal => f.write_str("Decimal"),
+            PhysicalDType::Integer32 => f.write_str("Integer32"),
+            PhysicalDType::Float32 => f.write_str("Float32"),
         }
     }
 }
@@ -73,6 +99,9 @@ impl From<u8> for PhysicalDType {
             2 => Self::Boolean,
             3 => Self::String,
             4 => Self::Unsigned,
+            6 => Self::Decimal,
+            7 => Self::Integer32,
+            8 => Self::Float32,
             _ => Self::Unknown,
         }
     }
@@ -87,6 +116,9 @@ impl From<PhysicalDType> for u8 {
             PhysicalDType::String => 3,
             PhysicalDType::Unsigned => 4,
             PhysicalDType::Unknown => 5,
+            PhysicalDType::Decimal => 6,
+            PhysicalDType::Integer32 => 7,
+            PhysicalDType::Float32 => 8,
         }
     }
 }
@@ -99,7 +131,107 @@ impl From<protos::models::FieldType> for PhysicalDType {
             protos::models::FieldType::Unsigned => PhysicalDType::Unsigned,
             protos::models::FieldType::Boolean => PhysicalDType::Boolean,
             protos::models::FieldType::String => PhysicalDType::String,
+            protos::models::FieldType::Int32 => PhysicalDType::Integer32,
+            protos::models::FieldType::Float32 => PhysicalDType::Float32,
+            protos::models::FieldType::Decimal => PhysicalDType::Decimal,
+            protos::models::FieldType::Binary => PhysicalDType::String,
             _ => PhysicalDType::Unknown,
         }
     }
 }
+
+/// Formats the unscaled value of a decimal with the scale, e.g. `(12345, 2)` to "123.45".
+pub fn decimal_to_string(value: i128, scale: i8) -> String {
+    if scale <= 0 {
+        let mut s = value.to_string();
+        if value != 0 {
+            s.extend(std::iter::repeat('0').take(scale.unsigned_abs() as usize));
+        }
+        return s;
+    }
+
+    let scale = scale as usize;
+    let digits = value.unsigned_abs().to_string();
+    let digits = if digits.len() <= scale {
+        format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits)
+    } else {
+        digits
+    };
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{sign}{integer}.{fraction}")
+}
+
+/// Parses decimal text into the unscaled value with the scale, the fraction digits
+/// beyond the scale are rounded half away from zero.
+pub fn parse_decimal(s: &str, precision: u8, scale: i8) -> Result<i128, String> {
+    let invalid = || format!("invalid decimal '{s}'");
+    let (negative, unsigned) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if integer.is_empty() && fraction.is_empty()
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    // Digits kept in the unscaled value, and the first dropped digit used to round.
+    let kept_len = integer.len() as i64 + scale as i64;
+    let digits = integer.bytes().chain(fraction.bytes()).collect::<Vec<_>>();
+    let mut value: i128 = 0;
+    for i in 0..kept_len.max(0) as usize {
+        let digit = digits.get(i).map(|d| d - b'0').unwrap_or(0);
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(digit as i128))
+            .ok_or_else(|| format!("decimal '{s}' overflows"))?;
+    }
+    if kept_len >= 0 && digits.get(kept_len as usize).map_or(false, |d| *d >= b'5') {
+        value = value
+            .checked_add(1)
+            .ok_or_else(|| format!("decimal '{s}' overflows"))?;
+    }
+
+    let max = 10_i128
+        .checked_pow(precision as u32)
+        .ok_or_else(|| format!("invalid decimal precision {precision}"))?;
+    if value >= max {
+        return Err(format!(
+            "decimal '{s}' is out of range of precision {precision} and scale {scale}"
+        ));
+    }
+    Ok(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decimal_to_string, parse_decimal};
+
+    #[test]
+    fn test_decimal_string() {
+        assert_eq!(decimal_to_string(12345, 2), "123.45");
+        assert_eq!(decimal_to_string(-5, 3), "-0.005");
+        assert_eq!(decimal_to_string(0, 2), "0.00");
+        assert_eq!(decimal_to_string(12, 0), "12");
+        assert_eq!(decimal_to_string(12, -2), "1200");
+
+        assert_eq!(parse_decimal("123.45", 10, 2), Ok(12345));
+        assert_eq!(parse_decimal("-0.005", 10, 3), Ok(-5));
+        assert_eq!(parse_decimal("1.5", 10, 3), Ok(1500));
+        assert_eq!(parse_decimal("+7", 10, 2), Ok(700));
+        assert_eq!(parse_decimal(".25", 10, 1), Ok(3));
+        assert_eq!(parse_decimal("-1.24", 10, 1), Ok(-12));
+        assert_eq!(parse_decimal("1250", 10, -2), Ok(13));
+        assert_eq!(parse_decimal("99.99", 38, 10), Ok(999_900_000_000));
+        assert!(parse_decimal("123.45", 4, 2).is_err());
+        assert!(parse_decimal("1.2.3", 10, 2).is_err());
+        assert!(parse_decimal("abc", 10, 2).is_err());
+        assert!(parse_decimal("-", 10, 2).is_err());
+    }
+}
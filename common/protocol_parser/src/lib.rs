@@ -17,6 +17,7 @@ type NextTagRes<'a> = Result<Option<(Vec<(&'a str, &'a str)>, usize)>>;
 pub mod line_protocol;
 pub mod lines_convert;
 pub mod open_tsdb;
+pub mod otlp;
 
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
@@ -382,6 +383,7 @@ fn parse_field_value(buf: &str) -> Result<FieldValue> {
             't' | 'T' => parse_boolean_field(buf, true),
             'f' | 'F' => parse_boolean_field(buf, false),
             '"' => parse_string_field(buf),
+            'b' | 'B' => parse_binary_field(buf),
             _ => Err(Error::Parse {
                 pos: 0,
                 content: buf.to_string(),
@@ -399,7 +401,42 @@ fn parse_numeric_field(buf: &str, positive: bool) -> Result<FieldValue> {
             content: buf.to_string(),
         });
     }
+    let parse_err = || Error::Parse {
+        pos: 0,
+        content: buf.to_string(),
+    };
+    let suffix_start = buf.len().saturating_sub(3);
+    if let Some(suffix) = buf.get(suffix_start..) {
+        if suffix.eq_ignore_ascii_case("i32") {
+            let v = buf[..suffix_start]
+                .parse::<i64>()
+                .map_err(|_e| parse_err())?;
+            let v = i32::try_from(if positive { v } else { -v }).map_err(|_e| parse_err())?;
+            return Ok(FieldValue::I32(v));
+        }
+        if suffix.eq_ignore_ascii_case("f32") {
+            let v = buf[..suffix_start]
+                .parse::<f32>()
+                .map_err(|_e| parse_err())?;
+            return Ok(FieldValue::F32(if positive { v } else { -v }));
+        }
+    }
+
     let field_val = match &buf[buf.len() - 1..] {
+        "d" | "D" => {
+            let digits = &buf[..buf.len() - 1];
+            let mut parts = digits.splitn(2, '.');
+            let int_part = parts.next().unwrap_or_default();
+            let frac_part = parts.next().unwrap_or_default();
+            if (int_part.is_empty() && frac_part.is_empty())
+                || !int_part.bytes().all(|b| b.is_ascii_digit())
+                || !frac_part.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(parse_err());
+            }
+            let sign = if positive { "" } else { "-" };
+            FieldValue::Decimal(format!("{sign}{digits}"))
+        }
         "i" | "I" => {
             let v = buf[..buf.len() - 1]
                 .parse::<i64>()
@@ -492,6 +529,21 @@ fn parse_string_field(buf: &str) -> Result<FieldValue> {
     }
 }
 
+/// Binary field values are written as base64 text, e.g. `b"aGVsbG8="`.
+fn parse_binary_field(buf: &str) -> Result<FieldValue> {
+    let parse_err = || Error::Parse {
+        pos: 0,
+        content: buf.to_string(),
+    };
+    let encoded = buf
+        .strip_prefix(['b', 'B'])
+        .and_then(|s| s.strip_prefix('"'))
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(parse_err)?;
+    let bytes = base64::decode(encoded).map_err(|_e| parse_err())?;
+    Ok(FieldValue::Binary(bytes))
+}
+
 fn next_value(buf: &str) -> Option<(&str, usize)> {
     let mut exists_timestamp = false;
     let (mut tok_begin, mut tok_end) = (0, buf.len());
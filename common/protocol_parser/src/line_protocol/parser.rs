@@ -286,4 +286,33 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_extended_field_types() {
+        let parser = Parser::new(-1);
+        let lp = parser
+            .parse("m,t=a f1=-12i32,f2=1.5f32,f3=-123.45d,f4=b\"aGVsbG8=\",f5=7i 1")
+            .unwrap();
+        assert_eq!(lp.len(), 1);
+        assert_eq!(
+            lp[0].fields,
+            vec![
+                ("f1", FieldValue::I32(-12)),
+                ("f2", FieldValue::F32(1.5)),
+                ("f3", FieldValue::Decimal("-123.45".to_string())),
+                ("f4", FieldValue::Binary(b"hello".to_vec())),
+                ("f5", FieldValue::I64(7)),
+            ]
+        );
+
+        for line in [
+            "m,t=a f=3000000000i32 1",
+            "m,t=a f=1.2.3d 1",
+            "m,t=a f=b\"not base64!\" 1",
+            "m f=bé 1",
+            "m f=b\"é 1",
+        ] {
+            assert!(parser.parse(line).is_err(), "{line}");
+        }
+    }
 }
@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 
 use datafusion::arrow::array::{
-    Array, ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray,
-    TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray, UInt64Array,
+    Array, ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Float32Array, Float64Array,
+    Int32Array, Int64Array, StringArray, TimestampMicrosecondArray, TimestampMillisecondArray,
+    TimestampNanosecondArray, UInt64Array,
 };
-use datafusion::arrow::datatypes::{SchemaRef, TimeUnit};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{DataType, SchemaRef, TimeUnit};
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
-use models::schema::{PhysicalCType as ColumnType, TskvTableSchemaRef};
-use models::PhysicalDType as ValueType;
+use models::schema::{ColumnType, TskvTableSchemaRef};
+use models::{decimal_to_string, ValueType, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE};
 use protos::models::{
     Column as FbColumn, ColumnBuilder, ColumnType as FbColumnType, FieldType, PointsBuilder,
     TableBuilder, ValuesBuilder,
@@ -84,6 +86,26 @@ impl MutableBatch {
                             value.append(&mut vec![false; self.row_count - value.len()]);
                         }
                     }
+                    ColumnData::I32(ref mut value) => {
+                        if !value.is_empty() {
+                            value.append(&mut vec![0; self.row_count - value.len()]);
+                        }
+                    }
+                    ColumnData::F32(ref mut value) => {
+                        if !value.is_empty() {
+                            value.append(&mut vec![0.0; self.row_count - value.len()]);
+                        }
+                    }
+                    ColumnData::Decimal(ref mut value) => {
+                        if !value.is_empty() {
+                            value.append(&mut vec![String::new(); self.row_count - value.len()]);
+                        }
+                    }
+                    ColumnData::Binary(ref mut value) => {
+                        if !value.is_empty() {
+                            value.append(&mut vec![Vec::new(); self.row_count - value.len()]);
+                        }
+                    }
                 }
             }
         }
@@ -102,7 +124,7 @@ impl Column {
         let mut valid = BitSet::new();
         valid.append_unset(row_count);
 
-        let data = match column_type {
+        let data = match &column_type {
             ColumnType::Tag => ColumnData::String(vec![String::new(); row_count]),
             ColumnType::Time(_) => ColumnData::I64(vec![0; row_count]),
             ColumnType::Field(field_type) => match field_type {
@@ -115,7 +137,13 @@ impl Column {
                 ValueType::Integer => ColumnData::I64(vec![0; row_count]),
                 ValueType::Unsigned => ColumnData::U64(vec![0; row_count]),
                 ValueType::Boolean => ColumnData::Bool(vec![false; row_count]),
-                ValueType::String => ColumnData::String(vec![String::new(); row_count]),
+                ValueType::String | ValueType::Geometry(_) => {
+                    ColumnData::String(vec![String::new(); row_count])
+                }
+                ValueType::Int32 => ColumnData::I32(vec![0; row_count]),
+                ValueType::Float32 => ColumnData::F32(vec![0.0; row_count]),
+                ValueType::Binary => ColumnData::Binary(vec![Vec::new(); row_count]),
+                ValueType::Decimal128(_, _) => ColumnData::Decimal(vec![String::new(); row_count]),
             },
         };
         Ok(Self {
@@ -133,6 +161,11 @@ pub enum ColumnData {
     U64(Vec<u64>),
     String(Vec<String>),
     Bool(Vec<bool>),
+    I32(Vec<i32>),
+    F32(Vec<f32>),
+    /// Decimal text, parsed with the precision and scale of the column by tskv.
+    Decimal(Vec<String>),
+    Binary(Vec<Vec<u8>>),
 }
 
 pub fn line_to_batches(lines: &[Line]) -> Result<HashMap<String, MutableBatch>> {
@@ -243,11 +276,81 @@ pub fn line_to_batches(lines: &[Line]) -> Result<HashMap<String, MutableBatch>>
                         }
                     }
                 }
+                FieldValue::I32(value) => {
+                    let col = batch.column_mut(field_key, ColumnType::Field(ValueType::Int32))?;
+                    match &mut col.data {
+                        ColumnData::I32(data) => {
+                            data.resize(row_count + 1, 0);
+                            data[row_count] = *value;
+                            col.valid.append_unset(row_count - col.valid.len());
+                            col.valid.append_set(1);
+                        }
+                        _ => {
+                            return Err(Error::Common {
+                                content: "Expected i32 column".to_string(),
+                            });
+                        }
+                    }
+                }
+                FieldValue::F32(value) => {
+                    let col = batch.column_mut(field_key, ColumnType::Field(ValueType::Float32))?;
+                    match &mut col.data {
+                        ColumnData::F32(data) => {
+                            data.resize(row_count + 1, 0.0);
+                            data[row_count] = *value;
+                            col.valid.append_unset(row_count - col.valid.len());
+                            col.valid.append_set(1);
+                        }
+                        _ => {
+                            return Err(Error::Common {
+                                content: "Expected f32 column".to_string(),
+                            });
+                        }
+                    }
+                }
+                FieldValue::Decimal(value) => {
+                    let col = batch.column_mut(
+                        field_key,
+                        ColumnType::Field(ValueType::Decimal128(
+                            DECIMAL128_MAX_PRECISION,
+                            DECIMAL_DEFAULT_SCALE,
+                        )),
+                    )?;
+                    match &mut col.data {
+                        ColumnData::Decimal(data) => {
+                            data.resize(row_count + 1, String::new());
+                            data[row_count] = value.clone();
+                            col.valid.append_unset(row_count - col.valid.len());
+                            col.valid.append_set(1);
+                        }
+                        _ => {
+                            return Err(Error::Common {
+                                content: "Expected decimal column".to_string(),
+                            });
+                        }
+                    }
+                }
+                FieldValue::Binary(value) => {
+                    let col = batch.column_mut(field_key, ColumnType::Field(ValueType::Binary))?;
+                    match &mut col.data {
+                        ColumnData::Binary(data) => {
+                            data.resize(row_count + 1, Vec::new());
+                            data[row_count] = value.clone();
+                            col.valid.append_unset(row_count - col.valid.len());
+                            col.valid.append_set(1);
+                        }
+                        _ => {
+                            return Err(Error::Common {
+                                content: "Expected binary column".to_string(),
+                            });
+                        }
+                    }
+                }
             }
         }
 
         let time = line.timestamp;
-        let col = batch.column_mut("time", ColumnType::default_time())?;
+        let col = batch.column_mut("time", ColumnType::Time(TimeUnit::Nanosecond))?;
         match col.data {
             ColumnData::I64(ref mut data) => {
                 data.resize(row_count + 1, 0);
@@ -323,6 +426,38 @@ pub fn mutable_batches_to_point(db: &str, batches: HashMap<String, MutableBatch>
                     values_builder.add_bool_value(values);
                     (FieldType::Boolean, values_builder.finish())
                 }
+                ColumnData::I32(ref values) => {
+                    let values = fbb.create_vector(values);
+                    let mut values_builder = ValuesBuilder::new(fbb);
+                    values_builder.add_int32_value(values);
+                    (FieldType::Int32, values_builder.finish())
+                }
+                ColumnData::F32(ref values) => {
+                    let values = fbb.create_vector(values);
+                    let mut values_builder = ValuesBuilder::new(fbb);
+                    values_builder.add_float32_value(values);
+                    (FieldType::Float32, values_builder.finish())
+                }
+                ColumnData::Decimal(ref values) => {
+                    let values = values
+                        .iter()
+                        .map(|s| fbb.create_string(s))
+                        .collect::<Vec<_>>();
+                    let values = fbb.create_vector(&values);
+                    let mut values_builder = ValuesBuilder::new(fbb);
+                    values_builder.add_string_value(values);
+                    (FieldType::Decimal, values_builder.finish())
+                }
+                ColumnData::Binary(ref values) => {
+                    let (bytes, offsets) =
+                        concat_binary_values(values.iter().map(|v| v.as_slice()));
+                    let bytes = fbb.create_vector(&bytes);
+                    let offsets = fbb.create_vector(&offsets);
+                    let mut values_builder = ValuesBuilder::new(fbb);
+                    values_builder.add_binary_value(bytes);
+                    values_builder.add_binary_offsets(offsets);
+                    (FieldType::Binary, values_builder.finish())
+                }
             };
             let column_name = fbb.create_string(field_name);
             let nullbits = fbb.create_vector(column.valid.bytes());
@@ -368,9 +503,9 @@ pub fn arrow_array_to_points(
         let column_schema = table_schema.column(col_name).ok_or_else(|| Error::Common {
             content: format!("column {} not found in table {}", col_name, table_name),
         })?;
-        let fb_column = match column_schema.column_type.to_physical_type() {
+        let fb_column = match &column_schema.column_type {
             ColumnType::Tag => build_string_column(column, col_name, FbColumnType::Tag, &mut fbb)?,
-            ColumnType::Time(ref time_unit) => {
+            ColumnType::Time(time_unit) => {
                 build_timestamp_column(column, col_name, time_unit, &mut fbb)?
             }
             ColumnType::Field(value_type) => match value_type {
@@ -383,9 +518,13 @@ pub fn arrow_array_to_points(
                 ValueType::Integer => build_i64_column(column, col_name, &mut fbb)?,
                 ValueType::Unsigned => build_u64_column(column, col_name, &mut fbb)?,
                 ValueType::Boolean => build_bool_column(column, col_name, &mut fbb)?,
-                ValueType::String => {
+                ValueType::String | ValueType::Geometry(_) => {
                     build_string_column(column, col_name, FbColumnType::Field, &mut fbb)?
                 }
+                ValueType::Int32 => build_i32_column(column, col_name, &mut fbb)?,
+                ValueType::Float32 => build_f32_column(column, col_name, &mut fbb)?,
+                ValueType::Binary => build_binary_column(column, col_name, &mut fbb)?,
+                ValueType::Decimal128(_, _) => build_decimal_column(column, col_name, &mut fbb)?,
             },
         };
         fb_columns.push(fb_column);
@@ -415,6 +554,13 @@ pub fn build_string_column<'a>(
     fbb: &mut FlatBufferBuilder<'a>,
 ) -> Result<WIPOffset<FbColumn<'a>>> {
     let name = fbb.create_string(col_name);
+    // Dictionary-encoded strings are written as strings.
+    let column = match column.data_type() {
+        DataType::Dictionary(_, _) => cast(column, &DataType::Utf8).map_err(|e| Error::Common {
+            content: format!("column {} is not string: {}", col_name, e),
+        })?,
+        _ => column.clone(),
+    };
     let values = column
         .as_any()
         .downcast_ref::<StringArray>()
@@ -681,3 +827,164 @@ pub fn build_bool_column<'a>(
     column_builder.add_col_values(values);
     Ok(column_builder.finish())
 }
+
+/// Concatenates binary values and returns the bytes with the end offset of each value.
+fn concat_binary_values<'b>(values: impl Iterator<Item = &'b [u8]>) -> (Vec<u8>, Vec<u32>) {
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::new();
+    for value in values {
+        bytes.extend_from_slice(value);
+        offsets.push(bytes.len() as u32);
+    }
+    (bytes, offsets)
+}
+
+pub fn build_i32_column<'a>(
+    column: &ArrayRef,
+    col_name: &str,
+    fbb: &mut FlatBufferBuilder<'a>,
+) -> Result<WIPOffset<FbColumn<'a>>> {
+    let name = fbb.create_string(col_name);
+    let values = column
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .ok_or(Error::Common {
+            content: format!("column {} is not int32", col_name),
+        })?;
+    let mut nullbits = BitSet::new();
+    let mut col_values = Vec::with_capacity(values.len());
+    values.iter().for_each(|value| {
+        if let Some(value) = value {
+            nullbits.append_set(1);
+            col_values.push(value);
+        } else {
+            nullbits.append_unset(1);
+            col_values.push(0);
+        }
+    });
+    let nullbits = fbb.create_vector(nullbits.bytes());
+    let values = fbb.create_vector(&col_values);
+    let mut values_builder = ValuesBuilder::new(fbb);
+    values_builder.add_int32_value(values);
+    let values = values_builder.finish();
+    let mut column_builder = ColumnBuilder::new(fbb);
+    column_builder.add_name(name);
+    column_builder.add_column_type(FbColumnType::Field);
+    column_builder.add_field_type(FieldType::Int32);
+    column_builder.add_nullbits(nullbits);
+    column_builder.add_col_values(values);
+    Ok(column_builder.finish())
+}
+
+pub fn build_f32_column<'a>(
+    column: &ArrayRef,
+    col_name: &str,
+    fbb: &mut FlatBufferBuilder<'a>,
+) -> Result<WIPOffset<FbColumn<'a>>> {
+    let name = fbb.create_string(col_name);
+    let values = column
+        .as_any()
+        .downcast_ref::<Float32Array>()
+        .ok_or(Error::Common {
+            content: format!("column {} is not float32", col_name),
+        })?;
+    let mut nullbits = BitSet::new();
+    let mut col_values = Vec::with_capacity(values.len());
+    values.iter().for_each(|value| {
+        if let Some(value) = value {
+            nullbits.append_set(1);
+            col_values.push(value);
+        } else {
+            nullbits.append_unset(1);
+            col_values.push(0.0);
+        }
+    });
+    let nullbits = fbb.create_vector(nullbits.bytes());
+    let values = fbb.create_vector(&col_values);
+    let mut values_builder = ValuesBuilder::new(fbb);
+    values_builder.add_float32_value(values);
+    let values = values_builder.finish();
+    let mut column_builder = ColumnBuilder::new(fbb);
+    column_builder.add_name(name);
+    column_builder.add_column_type(FbColumnType::Field);
+    column_builder.add_field_type(FieldType::Float32);
+    column_builder.add_nullbits(nullbits);
+    column_builder.add_col_values(values);
+    Ok(column_builder.finish())
+}
+
+pub fn build_decimal_column<'a>(
+    column: &ArrayRef,
+    col_name: &str,
+    fbb: &mut FlatBufferBuilder<'a>,
+) -> Result<WIPOffset<FbColumn<'a>>> {
+    let name = fbb.create_string(col_name);
+    let values = column
+        .as_any()
+        .downcast_ref::<Decimal128Array>()
+        .ok_or(Error::Common {
+            content: format!("column {} is not decimal128", col_name),
+        })?;
+    let scale = values.scale();
+    let mut nullbits = BitSet::new();
+    let mut col_values = Vec::with_capacity(values.len());
+    values.iter().for_each(|value| {
+        if let Some(value) = value {
+            nullbits.append_set(1);
+            col_values.push(fbb.create_string(&decimal_to_string(value, scale)));
+        } else {
+            nullbits.append_unset(1);
+            col_values.push(fbb.create_string(""));
+        }
+    });
+    let nullbits = fbb.create_vector(nullbits.bytes());
+    let values = fbb.create_vector(&col_values);
+    let mut values_builder = ValuesBuilder::new(fbb);
+    values_builder.add_string_value(values);
+    let values = values_builder.finish();
+    let mut column_builder = ColumnBuilder::new(fbb);
+    column_builder.add_name(name);
+    column_builder.add_column_type(FbColumnType::Field);
+    column_builder.add_field_type(FieldType::Decimal);
+    column_builder.add_nullbits(nullbits);
+    column_builder.add_col_values(values);
+    Ok(column_builder.finish())
+}
+
+pub fn build_binary_column<'a>(
+    column: &ArrayRef,
+    col_name: &str,
+    fbb: &mut FlatBufferBuilder<'a>,
+) -> Result<WIPOffset<FbColumn<'a>>> {
+    let name = fbb.create_string(col_name);
+    let values = column
+        .as_any()
+        .downcast_ref::<BinaryArray>()
+        .ok_or(Error::Common {
+            content: format!("column {} is not binary", col_name),
+        })?;
+    let mut nullbits = BitSet::new();
+    let (bytes, offsets) = concat_binary_values(values.iter().map(|value| {
+        if let Some(value) = value {
+            nullbits.append_set(1);
+            value
+        } else {
+            nullbits.append_unset(1);
+            Default::default()
+        }
+    }));
+    let nullbits = fbb.create_vector(nullbits.bytes());
+    let bytes = fbb.create_vector(&bytes);
+    let offsets = fbb.create_vector(&offsets);
+    let mut values_builder = ValuesBuilder::new(fbb);
+    values_builder.add_binary_value(bytes);
+    values_builder.add_binary_offsets(offsets);
+    let values = values_builder.finish();
+    let mut column_builder = ColumnBuilder::new(fbb);
+    column_builder.add_name(name);
+    column_builder.add_column_type(FbColumnType::Field);
+    column_builder.add_field_type(FieldType::Binary);
+    column_builder.add_nullbits(nullbits);
+    column_builder.add_col_values(values);
+    Ok(column_builder.finish())
+}
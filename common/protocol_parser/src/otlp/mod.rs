@@ -0,0 +1,527 @@
+//! Conversion of OTLP metrics to lines.
+//!
+//! Metrics are written like prometheus remote write, so they can be queried by PromQL:
+//! every data point is a row of the table named by the metric, with the attributes of
+//! the resource, the scope and the data point as tags, and the sample in the field `value`.
+//!
+//! - Gauge and sum: one row of the metric.
+//! - Histogram and exponential histogram: rows of `<name>_bucket` with the cumulative count
+//!   of every upper bound as the tag `le`, `<name>_count` and `<name>_sum`.
+//! - Summary: rows of the metric with the tag `quantile`, `<name>_count` and `<name>_sum`.
+
+use std::collections::BTreeMap;
+
+use protos::otlp::any_value::Value as AnyValueKind;
+use protos::otlp::metric::Data;
+use protos::otlp::number_data_point::Value as NumberValue;
+use protos::otlp::{
+    AnyValue, DataPointFlags, ExponentialHistogramDataPoint, ExportMetricsServiceRequest,
+    HistogramDataPoint, KeyValue, NumberDataPoint, SummaryDataPoint,
+};
+use protos::FieldValue;
+
+use crate::Line;
+
+pub const OTLP_SAMPLE_FIELD_NAME: &str = "value";
+pub const OTLP_SCOPE_NAME_TAG: &str = "otel_scope_name";
+pub const OTLP_SCOPE_VERSION_TAG: &str = "otel_scope_version";
+const BUCKET_LABEL: &str = "le";
+const QUANTILE_LABEL: &str = "quantile";
+
+/// A data point converted from the OTLP metrics, owns the names of the table and tags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpPoint {
+    pub table: String,
+    pub tags: Vec<(String, String)>,
+    pub value: f64,
+    pub timestamp: i64,
+}
+
+impl OtlpPoint {
+    pub fn to_line(&self) -> Line<'_> {
+        let tags = self
+            .tags
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        let fields = vec![(OTLP_SAMPLE_FIELD_NAME, FieldValue::F64(self.value))];
+        Line::new(&self.table, tags, fields, self.timestamp)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OtlpMetrics {
+    pub points: Vec<OtlpPoint>,
+    /// Number of the data points without a value.
+    pub rejected: i64,
+}
+
+impl OtlpMetrics {
+    pub fn lines(&self) -> Vec<Line<'_>> {
+        self.points.iter().map(|p| p.to_line()).collect()
+    }
+}
+
+type Tags = BTreeMap<String, String>;
+
+pub fn otlp_metrics_to_points(req: &ExportMetricsServiceRequest) -> OtlpMetrics {
+    let mut metrics = OtlpMetrics::default();
+    for resource_metrics in &req.resource_metrics {
+        let mut resource_tags = Tags::new();
+        if let Some(resource) = &resource_metrics.resource {
+            extend_tags(&mut resource_tags, &resource.attributes);
+        }
+
+        for scope_metrics in &resource_metrics.scope_metrics {
+            let mut scope_tags = resource_tags.clone();
+            if let Some(scope) = &scope_metrics.scope {
+                insert_tag(&mut scope_tags, OTLP_SCOPE_NAME_TAG, scope.name.clone());
+                insert_tag(
+                    &mut scope_tags,
+                    OTLP_SCOPE_VERSION_TAG,
+                    scope.version.clone(),
+                );
+                extend_tags(&mut scope_tags, &scope.attributes);
+            }
+
+            for metric in &scope_metrics.metrics {
+                let name = sanitize_name(&metric.name);
+                match &metric.data {
+                    Some(Data::Gauge(gauge)) => {
+                        for point in &gauge.data_points {
+                            metrics.push_number(&name, &scope_tags, point);
+                        }
+                    }
+                    Some(Data::Sum(sum)) => {
+                        for point in &sum.data_points {
+                            metrics.push_number(&name, &scope_tags, point);
+                        }
+                    }
+                    Some(Data::Histogram(histogram)) => {
+                        for point in &histogram.data_points {
+                            metrics.push_histogram(&name, &scope_tags, point);
+                        }
+                    }
+                    Some(Data::ExponentialHistogram(histogram)) => {
+                        for point in &histogram.data_points {
+                            metrics.push_exponential_histogram(&name, &scope_tags, point);
+                        }
+                    }
+                    Some(Data::Summary(summary)) => {
+                        for point in &summary.data_points {
+                            metrics.push_summary(&name, &scope_tags, point);
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    metrics
+}
+
+impl OtlpMetrics {
+    fn push(&mut self, table: String, tags: &Tags, value: f64, timestamp: u64) {
+        self.points.push(OtlpPoint {
+            table,
+            tags: tags.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            value,
+            timestamp: timestamp as i64,
+        });
+    }
+
+    fn push_number(&mut self, name: &str, scope_tags: &Tags, point: &NumberDataPoint) {
+        if no_recorded_value(point.flags) {
+            return;
+        }
+        let value = match point.value {
+            Some(NumberValue::AsDouble(v)) => v,
+            Some(NumberValue::AsInt(v)) => v as f64,
+            None => {
+                self.rejected += 1;
+                return;
+            }
+        };
+        let tags = point_tags(scope_tags, &point.attributes);
+        self.push(name.to_string(), &tags, value, point.time_unix_nano);
+    }
+
+    fn push_histogram(&mut self, name: &str, scope_tags: &Tags, point: &HistogramDataPoint) {
+        if no_recorded_value(point.flags) {
+            return;
+        }
+        let mut tags = point_tags(scope_tags, &point.attributes);
+        let time = point.time_unix_nano;
+
+        // the bucket counts of prometheus are cumulative
+        let bucket = format!("{name}_bucket");
+        let mut cumulative = 0_u64;
+        for (idx, count) in point.bucket_counts.iter().enumerate() {
+            cumulative += count;
+            let le = point
+                .explicit_bounds
+                .get(idx)
+                .copied()
+                .unwrap_or(f64::INFINITY);
+            tags.insert(BUCKET_LABEL.to_string(), format_float(le));
+            self.push(bucket.clone(), &tags, cumulative as f64, time);
+        }
+        if point.bucket_counts.len() <= point.explicit_bounds.len() {
+            tags.insert(BUCKET_LABEL.to_string(), format_float(f64::INFINITY));
+            self.push(bucket, &tags, point.count as f64, time);
+        }
+        tags.remove(BUCKET_LABEL);
+
+        self.push(format!("{name}_count"), &tags, point.count as f64, time);
+        if let Some(sum) = point.sum {
+            self.push(format!("{name}_sum"), &tags, sum, time);
+        }
+    }
+
+    fn push_exponential_histogram(
+        &mut self,
+        name: &str,
+        scope_tags: &Tags,
+        point: &ExponentialHistogramDataPoint,
+    ) {
+        if no_recorded_value(point.flags) {
+            return;
+        }
+        let mut tags = point_tags(scope_tags, &point.attributes);
+        let time = point.time_unix_nano;
+
+        // bucket `index` contains the values in (base^index, base^(index+1)],
+        // converted to the cumulative buckets of the upper bounds
+        let base = 2_f64.powf(2_f64.powi(-point.scale));
+        let mut bounds = Vec::new();
+        if let Some(negative) = &point.negative {
+            // the bucket of the negative values is in [-base^(index+1), -base^index)
+            for (i, count) in negative.bucket_counts.iter().enumerate().rev() {
+                let index = negative.offset + i as i32;
+                bounds.push((-base.powi(index), *count));
+            }
+        }
+        if point.zero_count > 0 || point.positive.is_none() {
+            bounds.push((point.zero_threshold, point.zero_count));
+        }
+        if let Some(positive) = &point.positive {
+            for (i, count) in positive.bucket_counts.iter().enumerate() {
+                let index = positive.offset + i as i32;
+                bounds.push((base.powi(index + 1), *count));
+            }
+        }
+
+        let bucket = format!("{name}_bucket");
+        let mut cumulative = 0_u64;
+        for (le, count) in bounds {
+            cumulative += count;
+            tags.insert(BUCKET_LABEL.to_string(), format_float(le));
+            self.push(bucket.clone(), &tags, cumulative as f64, time);
+        }
+        tags.insert(BUCKET_LABEL.to_string(), format_float(f64::INFINITY));
+        self.push(bucket, &tags, point.count as f64, time);
+        tags.remove(BUCKET_LABEL);
+
+        self.push(format!("{name}_count"), &tags, point.count as f64, time);
+        if let Some(sum) = point.sum {
+            self.push(format!("{name}_sum"), &tags, sum, time);
+        }
+    }
+
+    fn push_summary(&mut self, name: &str, scope_tags: &Tags, point: &SummaryDataPoint) {
+        if no_recorded_value(point.flags) {
+            return;
+        }
+        let mut tags = point_tags(scope_tags, &point.attributes);
+        let time = point.time_unix_nano;
+
+        for quantile in &point.quantile_values {
+            tags.insert(QUANTILE_LABEL.to_string(), format_float(quantile.quantile));
+            self.push(name.to_string(), &tags, quantile.value, time);
+        }
+        tags.remove(QUANTILE_LABEL);
+
+        self.push(format!("{name}_count"), &tags, point.count as f64, time);
+        self.push(format!("{name}_sum"), &tags, point.sum, time);
+    }
+}
+
+fn no_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::NoRecordedValueMask as u32 != 0
+}
+
+fn point_tags(scope_tags: &Tags, attributes: &[KeyValue]) -> Tags {
+    let mut tags = scope_tags.clone();
+    extend_tags(&mut tags, attributes);
+    tags
+}
+
+/// Attributes overwrite the ones with the same name of the outer level.
+fn extend_tags(tags: &mut Tags, attributes: &[KeyValue]) {
+    for attr in attributes {
+        let value = attr.value.as_ref().map(any_value_to_string);
+        insert_tag(tags, &attr.key, value.unwrap_or_default());
+    }
+}
+
+/// Empty values are not written as tags.
+fn insert_tag(tags: &mut Tags, key: &str, value: String) {
+    let key = sanitize_name(key);
+    if value.is_empty() {
+        tags.remove(&key);
+    } else {
+        tags.insert(key, value);
+    }
+}
+
+fn any_value_to_string(value: &AnyValue) -> String {
+    match &value.value {
+        Some(AnyValueKind::StringValue(v)) => v.clone(),
+        Some(AnyValueKind::BoolValue(v)) => v.to_string(),
+        Some(AnyValueKind::IntValue(v)) => v.to_string(),
+        Some(AnyValueKind::DoubleValue(v)) => v.to_string(),
+        Some(AnyValueKind::ArrayValue(array)) => {
+            let values = array
+                .values
+                .iter()
+                .map(any_value_to_string)
+                .collect::<Vec<_>>();
+            format!("[{}]", values.join(","))
+        }
+        Some(AnyValueKind::KvlistValue(list)) => {
+            let values = list
+                .values
+                .iter()
+                .map(|kv| {
+                    let value = kv.value.as_ref().map(any_value_to_string);
+                    format!("{}={}", kv.key, value.unwrap_or_default())
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", values.join(","))
+        }
+        Some(AnyValueKind::BytesValue(v)) => v.iter().map(|b| format!("{b:02x}")).collect(),
+        None => String::new(),
+    }
+}
+
+/// Names of metrics and labels of prometheus match `[a-zA-Z_:][a-zA-Z0-9_:]*`,
+/// other characters are replaced by `_`.
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn format_float(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protos::otlp::any_value::Value as AnyValueKind;
+    use protos::otlp::exponential_histogram_data_point::Buckets;
+    use protos::otlp::metric::Data;
+    use protos::otlp::number_data_point::Value as NumberValue;
+    use protos::otlp::{
+        AnyValue, ExponentialHistogram, ExponentialHistogramDataPoint, ExportMetricsServiceRequest,
+        Gauge, Histogram, HistogramDataPoint, InstrumentationScope, KeyValue, Metric,
+        NumberDataPoint, Resource, ResourceMetrics, ScopeMetrics,
+    };
+
+    use super::{otlp_metrics_to_points, sanitize_name, OtlpPoint};
+
+    fn attr(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(AnyValueKind::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn request(metrics: Vec<Metric>) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![attr("service.name", "api"), attr("host", "h1")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "meter".to_string(),
+                        ..Default::default()
+                    }),
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    fn point(table: &str, tags: &[(&str, &str)], value: f64) -> OtlpPoint {
+        OtlpPoint {
+            table: table.to_string(),
+            tags: tags
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            value,
+            timestamp: 10,
+        }
+    }
+
+    #[test]
+    fn test_gauge() {
+        let req = request(vec![Metric {
+            name: "http.requests".to_string(),
+            data: Some(Data::Gauge(Gauge {
+                data_points: vec![
+                    NumberDataPoint {
+                        attributes: vec![attr("host", "h2")],
+                        time_unix_nano: 10,
+                        value: Some(NumberValue::AsInt(3)),
+                        ..Default::default()
+                    },
+                    NumberDataPoint {
+                        time_unix_nano: 10,
+                        ..Default::default()
+                    },
+                ],
+            })),
+            ..Default::default()
+        }]);
+
+        let metrics = otlp_metrics_to_points(&req);
+        assert_eq!(metrics.rejected, 1);
+        assert_eq!(
+            metrics.points,
+            vec![point(
+                "http_requests",
+                &[
+                    ("host", "h2"),
+                    ("otel_scope_name", "meter"),
+                    ("service_name", "api")
+                ],
+                3.0
+            )]
+        );
+        let lines = metrics.lines();
+        assert_eq!(lines[0].table, "http_requests");
+        assert_eq!(lines[0].tags.len(), 3);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let req = request(vec![Metric {
+            name: "latency".to_string(),
+            data: Some(Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    time_unix_nano: 10,
+                    count: 6,
+                    sum: Some(12.5),
+                    bucket_counts: vec![1, 2, 3],
+                    explicit_bounds: vec![0.5, 1.0],
+                    ..Default::default()
+                }],
+                aggregation_temporality: 2,
+            })),
+            ..Default::default()
+        }]);
+
+        let metrics = otlp_metrics_to_points(&req);
+        let tags = [
+            ("host", "h1"),
+            ("otel_scope_name", "meter"),
+            ("service_name", "api"),
+        ];
+        let bucket = |le: &str| {
+            let mut tags = tags.to_vec();
+            tags.push(("le", le));
+            tags.sort();
+            tags
+        };
+        assert_eq!(
+            metrics.points,
+            vec![
+                point("latency_bucket", &bucket("0.5"), 1.0),
+                point("latency_bucket", &bucket("1"), 3.0),
+                point("latency_bucket", &bucket("+Inf"), 6.0),
+                point("latency_count", &tags, 6.0),
+                point("latency_sum", &tags, 12.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exponential_histogram() {
+        let req = request(vec![Metric {
+            name: "size".to_string(),
+            data: Some(Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![ExponentialHistogramDataPoint {
+                    time_unix_nano: 10,
+                    count: 4,
+                    scale: 0,
+                    zero_count: 1,
+                    positive: Some(Buckets {
+                        offset: 1,
+                        bucket_counts: vec![1, 2],
+                    }),
+                    ..Default::default()
+                }],
+                aggregation_temporality: 2,
+            })),
+            ..Default::default()
+        }]);
+
+        let metrics = otlp_metrics_to_points(&req);
+        let buckets = metrics
+            .points
+            .iter()
+            .filter(|p| p.table == "size_bucket")
+            .map(|p| {
+                let le = p.tags.iter().find(|(k, _)| k == "le").unwrap();
+                (le.1.clone(), p.value)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            buckets,
+            vec![
+                ("0".to_string(), 1.0),
+                ("4".to_string(), 2.0),
+                ("8".to_string(), 4.0),
+                ("+Inf".to_string(), 4.0),
+            ]
+        );
+        // no sum is recorded
+        assert_eq!(metrics.points.len(), 5);
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(
+            sanitize_name("http.server.duration"),
+            "http_server_duration"
+        );
+        assert_eq!(sanitize_name("a:b_c1"), "a:b_c1");
+        assert_eq!(sanitize_name("1xx"), "_1xx");
+    }
+}
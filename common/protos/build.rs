@@ -27,11 +27,14 @@ mod flatbuffers_generated;
             proto_files_dir.join("kv_service.proto"),
             proto_files_dir.join("vector_event.proto"),
             proto_files_dir.join("raft_service.proto"),
+            proto_files_dir.join("otlp_metrics.proto"),
         ];
+        // (<rust_mod_name>, <proto_package_name>)
         let rust_mod_names = &[
-            "kv_service".to_string(),
-            "vector".to_string(),
-            "raft_service".to_string(),
+            ("kv_service", "kv_service"),
+            ("vector", "vector"),
+            ("raft_service", "raft_service"),
+            ("otlp", "opentelemetry.proto.collector.metrics.v1"),
         ];
 
         // src/generated/protobuf_generated/
@@ -55,7 +58,12 @@ mod flatbuffers_generated;
 
         // src/generated/protobuf_generated/mod.rs
         let mut protobuf_generated_mod_rs_file = fs::File::create(output_dir_final.join("mod.rs"))?;
-        for mod_name in rust_mod_names.iter() {
+        for (mod_name, package_name) in rust_mod_names.iter() {
+            // <proto_package_name>.rs is generated for each package
+            if mod_name != package_name {
+                protobuf_generated_mod_rs_file
+                    .write_all(format!("#[path = \"{package_name}.rs\"]\n").as_bytes())?;
+            }
             protobuf_generated_mod_rs_file.write_all(b"pub mod ")?;
             protobuf_generated_mod_rs_file.write_all(mod_name.as_bytes())?;
             protobuf_generated_mod_rs_file.write_all(b";\n")?;
@@ -21,16 +21,20 @@ pub mod models {
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MIN_FIELD_TYPE: i32 = -1;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
-pub const ENUM_MAX_FIELD_TYPE: i32 = 4;
+pub const ENUM_MAX_FIELD_TYPE: i32 = 8;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 #[allow(non_camel_case_types)]
-pub const ENUM_VALUES_FIELD_TYPE: [FieldType; 6] = [
+pub const ENUM_VALUES_FIELD_TYPE: [FieldType; 10] = [
   FieldType::Unknown,
   FieldType::Float,
   FieldType::Integer,
   FieldType::Unsigned,
   FieldType::Boolean,
   FieldType::String,
+  FieldType::Int32,
+  FieldType::Float32,
+  FieldType::Decimal,
+  FieldType::Binary,
 ];
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -44,9 +48,13 @@ impl FieldType {
   pub const Unsigned: Self = Self(2);
   pub const Boolean: Self = Self(3);
   pub const String: Self = Self(4);
+  pub const Int32: Self = Self(5);
+  pub const Float32: Self = Self(6);
+  pub const Decimal: Self = Self(7);
+  pub const Binary: Self = Self(8);
 
   pub const ENUM_MIN: i32 = -1;
-  pub const ENUM_MAX: i32 = 4;
+  pub const ENUM_MAX: i32 = 8;
   pub const ENUM_VALUES: &'static [Self] = &[
     Self::Unknown,
     Self::Float,
@@ -54,6 +62,10 @@ impl FieldType {
     Self::Unsigned,
     Self::Boolean,
     Self::String,
+    Self::Int32,
+    Self::Float32,
+    Self::Decimal,
+    Self::Binary,
   ];
   /// Returns the variant's name or "" if unknown.
   pub fn variant_name(self) -> Option<&'static str> {
@@ -64,6 +76,10 @@ impl FieldType {
       Self::Unsigned => Some("Unsigned"),
       Self::Boolean => Some("Boolean"),
       Self::String => Some("String"),
+      Self::Int32 => Some("Int32"),
+      Self::Float32 => Some("Float32"),
+      Self::Decimal => Some("Decimal"),
+      Self::Binary => Some("Binary"),
       _ => None,
     }
   }
@@ -334,6 +350,10 @@ impl<'a> Values<'a> {
   pub const VT_UINT_VALUE: flatbuffers::VOffsetT = 8;
   pub const VT_BOOL_VALUE: flatbuffers::VOffsetT = 10;
   pub const VT_STRING_VALUE: flatbuffers::VOffsetT = 12;
+  pub const VT_BINARY_VALUE: flatbuffers::VOffsetT = 14;
+  pub const VT_BINARY_OFFSETS: flatbuffers::VOffsetT = 16;
+  pub const VT_INT32_VALUE: flatbuffers::VOffsetT = 18;
+  pub const VT_FLOAT32_VALUE: flatbuffers::VOffsetT = 20;
 
   pub const fn get_fully_qualified_name() -> &'static str {
     "models.Values"
@@ -349,6 +369,10 @@ impl<'a> Values<'a> {
     args: &'args ValuesArgs<'args>
   ) -> flatbuffers::WIPOffset<Values<'bldr>> {
     let mut builder = ValuesBuilder::new(_fbb);
+    if let Some(x) = args.float32_value { builder.add_float32_value(x); }
+    if let Some(x) = args.int32_value { builder.add_int32_value(x); }
+    if let Some(x) = args.binary_offsets { builder.add_binary_offsets(x); }
+    if let Some(x) = args.binary_value { builder.add_binary_value(x); }
     if let Some(x) = args.string_value { builder.add_string_value(x); }
     if let Some(x) = args.bool_value { builder.add_bool_value(x); }
     if let Some(x) = args.uint_value { builder.add_uint_value(x); }
@@ -393,6 +417,34 @@ impl<'a> Values<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(Values::VT_STRING_VALUE, None)}
   }
+  #[inline]
+  pub fn binary_value(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Values::VT_BINARY_VALUE, None)}
+  }
+  #[inline]
+  pub fn binary_offsets(&self) -> Option<flatbuffers::Vector<'a, u32>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u32>>>(Values::VT_BINARY_OFFSETS, None)}
+  }
+  #[inline]
+  pub fn int32_value(&self) -> Option<flatbuffers::Vector<'a, i32>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, i32>>>(Values::VT_INT32_VALUE, None)}
+  }
+  #[inline]
+  pub fn float32_value(&self) -> Option<flatbuffers::Vector<'a, f32>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, f32>>>(Values::VT_FLOAT32_VALUE, None)}
+  }
 }
 
 impl flatbuffers::Verifiable for Values<'_> {
@@ -407,6 +459,10 @@ impl flatbuffers::Verifiable for Values<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u64>>>("uint_value", Self::VT_UINT_VALUE, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, bool>>>("bool_value", Self::VT_BOOL_VALUE, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("string_value", Self::VT_STRING_VALUE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("binary_value", Self::VT_BINARY_VALUE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("binary_offsets", Self::VT_BINARY_OFFSETS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, i32>>>("int32_value", Self::VT_INT32_VALUE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, f32>>>("float32_value", Self::VT_FLOAT32_VALUE, false)?
      .finish();
     Ok(())
   }
@@ -417,6 +473,10 @@ pub struct ValuesArgs<'a> {
     pub uint_value: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u64>>>,
     pub bool_value: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, bool>>>,
     pub string_value: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
+    pub binary_value: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub binary_offsets: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
+    pub int32_value: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, i32>>>,
+    pub float32_value: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, f32>>>,
 }
 impl<'a> Default for ValuesArgs<'a> {
   #[inline]
@@ -427,6 +487,10 @@ impl<'a> Default for ValuesArgs<'a> {
       uint_value: None,
       bool_value: None,
       string_value: None,
+      binary_value: None,
+      binary_offsets: None,
+      int32_value: None,
+      float32_value: None,
     }
   }
 }
@@ -457,6 +521,22 @@ impl<'a: 'b, 'b> ValuesBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Values::VT_STRING_VALUE, string_value);
   }
   #[inline]
+  pub fn add_binary_value(&mut self, binary_value: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Values::VT_BINARY_VALUE, binary_value);
+  }
+  #[inline]
+  pub fn add_binary_offsets(&mut self, binary_offsets: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Values::VT_BINARY_OFFSETS, binary_offsets);
+  }
+  #[inline]
+  pub fn add_int32_value(&mut self, int32_value: flatbuffers::WIPOffset<flatbuffers::Vector<'b , i32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Values::VT_INT32_VALUE, int32_value);
+  }
+  #[inline]
+  pub fn add_float32_value(&mut self, float32_value: flatbuffers::WIPOffset<flatbuffers::Vector<'b , f32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Values::VT_FLOAT32_VALUE, float32_value);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ValuesBuilder<'a, 'b> {
     let start = _fbb.start_table();
     ValuesBuilder {
@@ -479,6 +559,10 @@ impl core::fmt::Debug for Values<'_> {
       ds.field("uint_value", &self.uint_value());
       ds.field("bool_value", &self.bool_value());
       ds.field("string_value", &self.string_value());
+      ds.field("binary_value", &self.binary_value());
+      ds.field("binary_offsets", &self.binary_offsets());
+      ds.field("int32_value", &self.int32_value());
+      ds.field("float32_value", &self.float32_value());
       ds.finish()
   }
 }
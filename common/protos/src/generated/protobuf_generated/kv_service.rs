@@ -193,12 +193,24 @@ pub struct DestoryRaftGroupRequest {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestoreVnodeRequest {
+    #[prost(string, tag = "1")]
+    pub db: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub vnode_id: u32,
+    #[prost(bytes = "vec", tag = "3")]
+    pub location: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub backup: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AdminCommandRequest {
     #[prost(string, tag = "1")]
     pub tenant: ::prost::alloc::string::String,
     #[prost(
         oneof = "admin_command_request::Command",
-        tags = "2, 3, 4, 5, 6, 7, 8, 12, 13, 14, 15"
+        tags = "2, 3, 4, 5, 6, 7, 8, 12, 13, 14, 15, 16"
     )]
     pub command: ::core::option::Option<admin_command_request::Command>,
 }
@@ -229,6 +241,8 @@ pub mod admin_command_request {
         RemoveRaftNode(super::RemoveRaftNodeRequest),
         #[prost(message, tag = "15")]
         DestoryRaftGroup(super::DestoryRaftGroupRequest),
+        #[prost(message, tag = "16")]
+        RestoreVnode(super::RestoreVnodeRequest),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -239,10 +253,24 @@ pub struct FetchVnodeChecksumRequest {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BackupVnodeRequest {
+    #[prost(string, tag = "1")]
+    pub db: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub vnode_id: u32,
+    #[prost(string, tag = "3")]
+    pub backup_id: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub location: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, repeated, tag = "5")]
+    pub uploaded_files: ::prost::alloc::vec::Vec<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AdminFetchCommandRequest {
     #[prost(string, tag = "1")]
     pub tenant: ::prost::alloc::string::String,
-    #[prost(oneof = "admin_fetch_command_request::Command", tags = "8")]
+    #[prost(oneof = "admin_fetch_command_request::Command", tags = "8, 9")]
     pub command: ::core::option::Option<admin_fetch_command_request::Command>,
 }
 /// Nested message and enum types in `AdminFetchCommandRequest`.
@@ -252,6 +280,8 @@ pub mod admin_fetch_command_request {
     pub enum Command {
         #[prost(message, tag = "8")]
         FetchVnodeChecksum(super::FetchVnodeChecksumRequest),
+        #[prost(message, tag = "9")]
+        BackupVnode(super::BackupVnodeRequest),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1,3 +1,5 @@
 pub mod kv_service;
 pub mod vector;
 pub mod raft_service;
+#[path = "opentelemetry.proto.collector.metrics.v1.rs"]
+pub mod otlp;
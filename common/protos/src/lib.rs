@@ -59,6 +59,11 @@ pub enum FieldValue {
     Str(Vec<u8>),
     F64(f64),
     Bool(bool),
+    I32(i32),
+    F32(f32),
+    /// Decimal text, converted with the scale of the column when written.
+    Decimal(String),
+    Binary(Vec<u8>),
 }
 
 impl<'a> Points<'a> {
@@ -175,6 +180,44 @@ impl<'a> Column<'a> {
         Ok(values)
     }
 
+    pub fn int32_values_len(&self) -> PointsResult<usize> {
+        let len = self
+            .col_values()
+            .ok_or(PointsError::ColumnMissingValues)?
+            .int32_value()
+            .map(|v| v.len())
+            .unwrap_or(0);
+        Ok(len)
+    }
+
+    pub fn int32_values(&self) -> PointsResult<Vector<i32>> {
+        let values = self
+            .col_values()
+            .ok_or(PointsError::ColumnMissingValues)?
+            .int32_value()
+            .unwrap_or_default();
+        Ok(values)
+    }
+
+    pub fn float32_values_len(&self) -> PointsResult<usize> {
+        let len = self
+            .col_values()
+            .ok_or(PointsError::ColumnMissingValues)?
+            .float32_value()
+            .map(|v| v.len())
+            .unwrap_or(0);
+        Ok(len)
+    }
+
+    pub fn float32_values(&self) -> PointsResult<Vector<f32>> {
+        let values = self
+            .col_values()
+            .ok_or(PointsError::ColumnMissingValues)?
+            .float32_value()
+            .unwrap_or_default();
+        Ok(values)
+    }
+
     pub fn uint_values_len(&self) -> PointsResult<usize> {
         let len = self
             .col_values()
@@ -193,6 +236,38 @@ impl<'a> Column<'a> {
             .unwrap_or_default();
         Ok(values)
     }
+
+    pub fn binary_values_len(&self) -> PointsResult<usize> {
+        let len = self
+            .col_values()
+            .ok_or(PointsError::ColumnMissingValues)?
+            .binary_offsets()
+            .map(|v| v.len())
+            .unwrap_or(0);
+        Ok(len)
+    }
+
+    /// Returns the binary value at `index`, values are stored continuously in
+    /// `binary_value` and split by the end offsets in `binary_offsets`.
+    pub fn binary_value(&self, index: usize) -> PointsResult<&'a [u8]> {
+        let values = self.col_values().ok_or(PointsError::ColumnMissingValues)?;
+        let offsets = values.binary_offsets().unwrap_or_default();
+        let bytes = values.binary_value().unwrap_or_default().bytes();
+        if index >= offsets.len() {
+            return Err(PointsError::Points {
+                msg: format!("binary value index {} out of bounds", index),
+            });
+        }
+        let start = if index == 0 {
+            0
+        } else {
+            offsets.get(index - 1) as usize
+        };
+        let end = offsets.get(index) as usize;
+        bytes.get(start..end).ok_or_else(|| PointsError::Points {
+            msg: format!("invalid binary value offsets {}..{}", start, end),
+        })
+    }
 }
 
 impl<'a> Display for Points<'a> {
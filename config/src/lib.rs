@@ -379,6 +379,9 @@ grpc_enable_gzip = false
 flight_rpc_listen_port = 8904
 tcp_listen_port = 8905
 vector_listen_port = 8906
+otlp_listen_port = 8907
+postgres_listen_port = 8908
+mysql_listen_port = 8909
 reporting_disabled = false
 
 
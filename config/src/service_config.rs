@@ -20,6 +20,12 @@ pub struct ServiceConfig {
     pub tcp_listen_port: Option<u16>,
     #[serde(default = "ServiceConfig::default_vector_listen_port")]
     pub vector_listen_port: Option<u16>,
+    #[serde(default = "ServiceConfig::default_otlp_listen_port")]
+    pub otlp_listen_port: Option<u16>,
+    #[serde(default = "ServiceConfig::default_postgres_listen_port")]
+    pub postgres_listen_port: Option<u16>,
+    #[serde(default = "ServiceConfig::default_mysql_listen_port")]
+    pub mysql_listen_port: Option<u16>,
     #[serde(default = "ServiceConfig::default_enable_report")]
     pub enable_report: bool,
 }
@@ -49,6 +55,18 @@ impl ServiceConfig {
         None
     }
 
+    fn default_otlp_listen_port() -> Option<u16> {
+        None
+    }
+
+    fn default_postgres_listen_port() -> Option<u16> {
+        None
+    }
+
+    fn default_mysql_listen_port() -> Option<u16> {
+        None
+    }
+
     fn default_enable_report() -> bool {
         true
     }
@@ -63,6 +81,9 @@ impl Default for ServiceConfig {
             flight_rpc_listen_port: ServiceConfig::default_flight_rpc_listen_port(),
             tcp_listen_port: ServiceConfig::default_tcp_listen_port(),
             vector_listen_port: ServiceConfig::default_vector_listen_port(),
+            otlp_listen_port: ServiceConfig::default_otlp_listen_port(),
+            postgres_listen_port: ServiceConfig::default_postgres_listen_port(),
+            mysql_listen_port: ServiceConfig::default_mysql_listen_port(),
             enable_report: ServiceConfig::default_enable_report(),
         }
     }
@@ -91,6 +112,18 @@ impl OverrideByEnv for ServiceConfig {
             &mut self.vector_listen_port,
             "CNOSDB_SERVICE_VECTOR_LISTEN_PORT",
         );
+        entry_override_option(
+            &mut self.otlp_listen_port,
+            "CNOSDB_SERVICE_OTLP_LISTEN_PORT",
+        );
+        entry_override_option(
+            &mut self.postgres_listen_port,
+            "CNOSDB_SERVICE_POSTGRES_LISTEN_PORT",
+        );
+        entry_override_option(
+            &mut self.mysql_listen_port,
+            "CNOSDB_SERVICE_MYSQL_LISTEN_PORT",
+        );
         entry_override(&mut self.enable_report, "CNOSDB_SERVICE_ENABLE_REPORT");
     }
 }
@@ -178,13 +211,46 @@ impl CheckConfig for ServiceConfig {
             let default_vector_addr = format!("{}:{}", &config.global.host, port);
             if let Err(e) = default_vector_addr.to_socket_addrs() {
                 ret.add_error(CheckConfigItemResult {
-                    config: config_name,
+                    config: config_name.clone(),
                     item: default_vector_addr,
                     message: format!("Cannot resolve 'vector_listen_addr': {}", e),
                 });
             }
         }
 
+        if let Some(port) = self.otlp_listen_port {
+            let default_otlp_addr = format!("{}:{}", &config.global.host, port);
+            if let Err(e) = default_otlp_addr.to_socket_addrs() {
+                ret.add_error(CheckConfigItemResult {
+                    config: config_name.clone(),
+                    item: default_otlp_addr,
+                    message: format!("Cannot resolve 'otlp_listen_addr': {}", e),
+                });
+            }
+        }
+
+        if let Some(port) = self.postgres_listen_port {
+            let default_postgres_addr = format!("{}:{}", &config.global.host, port);
+            if let Err(e) = default_postgres_addr.to_socket_addrs() {
+                ret.add_error(CheckConfigItemResult {
+                    config: config_name.clone(),
+                    item: default_postgres_addr,
+                    message: format!("Cannot resolve 'postgres_listen_addr': {}", e),
+                });
+            }
+        }
+
+        if let Some(port) = self.mysql_listen_port {
+            let default_mysql_addr = format!("{}:{}", &config.global.host, port);
+            if let Err(e) = default_mysql_addr.to_socket_addrs() {
+                ret.add_error(CheckConfigItemResult {
+                    config: config_name,
+                    item: default_mysql_addr,
+                    message: format!("Cannot resolve 'mysql_listen_addr': {}", e),
+                });
+            }
+        }
+
         if ret.is_empty() {
             None
         } else {
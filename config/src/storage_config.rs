@@ -5,7 +5,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::check::{CheckConfig, CheckConfigItemResult, CheckConfigResult};
 use crate::codec::{bytes_num, duration};
-use crate::override_by_env::{entry_override, entry_override_to_duration, OverrideByEnv};
+use crate::override_by_env::{
+    entry_override, entry_override_option, entry_override_to_duration, OverrideByEnv,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct StorageConfig {
@@ -53,6 +55,9 @@ pub struct StorageConfig {
 
     #[serde(with = "bytes_num", default = "StorageConfig::default_reserve_space")]
     pub reserve_space: u64,
+
+    #[serde(default)]
+    pub cold_storage: Option<ColdStorageConfig>,
 }
 
 impl StorageConfig {
@@ -145,6 +150,7 @@ impl OverrideByEnv for StorageConfig {
             "CNOSDB_STORAGE_MAX_CONCURRENT_COMPACTION",
         );
         entry_override(&mut self.strict_write, "CNOSDB_STORAGE_STRICT_WRITE");
+        self.cold_storage.override_by_env();
     }
 }
 
@@ -163,12 +169,13 @@ impl Default for StorageConfig {
             max_concurrent_compaction: Self::default_max_concurrent_compaction(),
             strict_write: Self::default_strict_write(),
             reserve_space: Self::default_reserve_space(),
+            cold_storage: None,
         }
     }
 }
 
 impl CheckConfig for StorageConfig {
-    fn check(&self, _: &crate::Config) -> Option<CheckConfigResult> {
+    fn check(&self, all_config: &crate::Config) -> Option<CheckConfigResult> {
         let config_name = Arc::new("storage".to_string());
         let mut ret = CheckConfigResult::default();
 
@@ -222,6 +229,187 @@ impl CheckConfig for StorageConfig {
                 message: "'max_compact_size' maybe too small(less than 1M)".to_string(),
             });
         }
+        if let Some(ref cold_storage) = self.cold_storage {
+            if let Some(r) = cold_storage.check(all_config) {
+                ret.add_all(r);
+            }
+        }
+
+        if ret.is_empty() {
+            None
+        } else {
+            Some(ret)
+        }
+    }
+}
+
+/// Object store to keep cold tsm files, the credentials are the same as
+/// the connection options of `COPY INTO` external locations.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ColdStorageConfig {
+    /// One of `local`, `s3`, `gcs` and `azblob`.
+    #[serde(default = "ColdStorageConfig::default_kind")]
+    pub kind: String,
+
+    /// Root directory for `local`, or the key prefix of objects in the bucket.
+    #[serde(default = "ColdStorageConfig::default_path")]
+    pub path: String,
+
+    /// Bucket of S3 and GCS, or container of Azure Blob.
+    #[serde(default)]
+    pub bucket: Option<String>,
+
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    #[serde(default)]
+    pub security_token: Option<String>,
+    #[serde(default = "ColdStorageConfig::default_virtual_hosted_style_request")]
+    pub virtual_hosted_style_request: bool,
+
+    /// Path of the `service-account-********.json`-file of GCS.
+    #[serde(default)]
+    pub service_account_path: Option<String>,
+
+    #[serde(default)]
+    pub account_name: Option<String>,
+    #[serde(default)]
+    pub access_key: Option<String>,
+
+    /// Interval to check for tsm files older than the `COLD_DURATION` of database.
+    #[serde(
+        with = "duration",
+        default = "ColdStorageConfig::default_check_interval"
+    )]
+    pub check_interval: Duration,
+}
+
+impl ColdStorageConfig {
+    fn default_kind() -> String {
+        "local".to_string()
+    }
+
+    fn default_path() -> String {
+        "/var/lib/cnosdb/cold".to_string()
+    }
+
+    fn default_virtual_hosted_style_request() -> bool {
+        true
+    }
+
+    fn default_check_interval() -> Duration {
+        Duration::from_secs(10 * 60)
+    }
+}
+
+impl Default for ColdStorageConfig {
+    fn default() -> Self {
+        Self {
+            kind: Self::default_kind(),
+            path: Self::default_path(),
+            bucket: None,
+            region: None,
+            endpoint_url: None,
+            access_key_id: None,
+            secret_access_key: None,
+            security_token: None,
+            virtual_hosted_style_request: Self::default_virtual_hosted_style_request(),
+            service_account_path: None,
+            account_name: None,
+            access_key: None,
+            check_interval: Self::default_check_interval(),
+        }
+    }
+}
+
+impl OverrideByEnv for Option<ColdStorageConfig> {
+    fn override_by_env(&mut self) {
+        let is_some = self.is_some();
+        let mut config = self.take().unwrap_or_default();
+        let overridden = [
+            entry_override(&mut config.kind, "CNOSDB_STORAGE_COLD_STORAGE_KIND"),
+            entry_override(&mut config.path, "CNOSDB_STORAGE_COLD_STORAGE_PATH"),
+            entry_override_option(&mut config.bucket, "CNOSDB_STORAGE_COLD_STORAGE_BUCKET"),
+            entry_override_option(&mut config.region, "CNOSDB_STORAGE_COLD_STORAGE_REGION"),
+            entry_override_option(
+                &mut config.endpoint_url,
+                "CNOSDB_STORAGE_COLD_STORAGE_ENDPOINT_URL",
+            ),
+            entry_override_option(
+                &mut config.access_key_id,
+                "CNOSDB_STORAGE_COLD_STORAGE_ACCESS_KEY_ID",
+            ),
+            entry_override_option(
+                &mut config.secret_access_key,
+                "CNOSDB_STORAGE_COLD_STORAGE_SECRET_ACCESS_KEY",
+            ),
+            entry_override_option(
+                &mut config.account_name,
+                "CNOSDB_STORAGE_COLD_STORAGE_ACCOUNT_NAME",
+            ),
+            entry_override_option(
+                &mut config.access_key,
+                "CNOSDB_STORAGE_COLD_STORAGE_ACCESS_KEY",
+            ),
+            entry_override_to_duration(
+                &mut config.check_interval,
+                "CNOSDB_STORAGE_COLD_STORAGE_CHECK_INTERVAL",
+            ),
+        ]
+        .into_iter()
+        .any(|b| b);
+        *self = if is_some || overridden {
+            Some(config)
+        } else {
+            None
+        };
+    }
+}
+
+impl CheckConfig for ColdStorageConfig {
+    fn check(&self, _: &crate::Config) -> Option<CheckConfigResult> {
+        let config_name = Arc::new("storage.cold_storage".to_string());
+        let mut ret = CheckConfigResult::default();
+
+        match self.kind.as_str() {
+            "local" => {
+                if self.path.is_empty() {
+                    ret.add_error(CheckConfigItemResult {
+                        config: config_name.clone(),
+                        item: "path".to_string(),
+                        message: "'path' is empty".to_string(),
+                    });
+                }
+            }
+            "s3" | "gcs" | "azblob" => {
+                if self.bucket.is_none() {
+                    ret.add_error(CheckConfigItemResult {
+                        config: config_name.clone(),
+                        item: "bucket".to_string(),
+                        message: format!("'bucket' is required by kind '{}'", self.kind),
+                    });
+                }
+            }
+            _ => {
+                ret.add_error(CheckConfigItemResult {
+                    config: config_name.clone(),
+                    item: "kind".to_string(),
+                    message: "'kind' should be one of 'local', 's3', 'gcs', 'azblob'".to_string(),
+                });
+            }
+        }
+        if self.check_interval.as_secs() < 1 {
+            ret.add_warn(CheckConfigItemResult {
+                config: config_name,
+                item: "check_interval".to_string(),
+                message: "'check_interval' maybe too small(less than 1 second)".to_string(),
+            });
+        }
 
         if ret.is_empty() {
             None
@@ -0,0 +1,62 @@
+use std::collections::{HashMap, HashSet};
+
+use models::meta_data::VnodeId;
+use models::schema::{DatabaseSchema, TableSchema};
+use models::Timestamp;
+use serde::{Deserialize, Serialize};
+use tskv::backup::{BackupStore, VnodeBackup, BACKUP_MANIFEST_FILE};
+use tskv::ColumnFileId;
+
+use crate::errors::{CoordinatorError, CoordinatorResult};
+
+/// Manifest of the latest backup in a backup location, it is all that
+/// needed to restore the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub backup_id: String,
+    /// Id of the backup that this incremental backup is based on.
+    pub base_backup_id: Option<String>,
+    pub tenant: String,
+    pub database: DatabaseSchema,
+    pub tables: Vec<TableSchema>,
+    pub buckets: Vec<BucketBackup>,
+}
+
+/// Backup of a bucket, one vnode is backed up for each replication set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BucketBackup {
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub shard_group: Vec<VnodeBackup>,
+}
+
+impl BackupManifest {
+    pub async fn load(store: &BackupStore) -> CoordinatorResult<Option<Self>> {
+        match store.get(BACKUP_MANIFEST_FILE).await? {
+            Some(data) => serde_json::from_slice(&data)
+                .map(Some)
+                .map_err(|e| CoordinatorError::InvalidSerdeMsg { err: e.to_string() }),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save(&self, store: &BackupStore) -> CoordinatorResult<()> {
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| CoordinatorError::InvalidSerdeMsg { err: e.to_string() })?;
+        store.put(BACKUP_MANIFEST_FILE, data.into()).await?;
+        Ok(())
+    }
+
+    /// Data files in this backup grouped by vnode, an incremental backup
+    /// based on this one doesn't upload them again.
+    pub fn uploaded_files(&self) -> HashMap<VnodeId, HashSet<ColumnFileId>> {
+        let mut files: HashMap<VnodeId, HashSet<ColumnFileId>> = HashMap::new();
+        for vnode in self.buckets.iter().flat_map(|b| b.shard_group.iter()) {
+            files
+                .entry(vnode.vnode_id)
+                .or_default()
+                .extend(vnode.file_ids());
+        }
+        files
+    }
+}
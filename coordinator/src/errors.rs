@@ -226,6 +226,10 @@ pub enum CoordinatorError {
     InvalidInitialConfig {
         msg: String,
     },
+
+    #[error_code(code = 32)]
+    #[snafu(display("The scan has been cancelled"))]
+    Cancelled,
 }
 
 impl From<PointsError> for CoordinatorError {
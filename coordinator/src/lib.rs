@@ -18,12 +18,15 @@ use protocol_parser::Line;
 use protos::kv_service::{AdminCommandRequest, RaftWriteCommand, UpdateSetValue};
 use raft::manager::RaftNodesManager;
 use trace::SpanContext;
+use tskv::backup::BackupLocation;
 use tskv::reader::QueryOption;
 use tskv::EngineRef;
 
+use crate::backup::{BackupManifest, BucketBackup};
 use crate::errors::CoordinatorResult;
 use crate::service::CoordServiceMetrics;
 
+pub mod backup;
 pub mod errors;
 pub mod file_info;
 pub mod hh_queue;
@@ -171,6 +174,27 @@ pub trait Coordinator: Send + Sync {
     ) -> CoordinatorResult<()>;
 
     fn using_raft_replication(&self) -> bool;
+
+    /// Backup a vnode of each replication set of the database to the location,
+    /// data files uploaded by the base backup are not uploaded again.
+    async fn backup_database(
+        &self,
+        tenant: &str,
+        db: &str,
+        location: &BackupLocation,
+        backup_id: &str,
+        base: Option<&BackupManifest>,
+    ) -> CoordinatorResult<Vec<BucketBackup>>;
+
+    /// Create buckets of the database for the backups of buckets,
+    /// then restore all vnodes of the buckets from the location.
+    async fn restore_database(
+        &self,
+        tenant: &str,
+        db: &str,
+        location: &BackupLocation,
+        buckets: &[BucketBackup],
+    ) -> CoordinatorResult<()>;
 }
 
 pub fn status_response_to_result(
@@ -14,6 +14,7 @@ use futures::{ready, FutureExt, Stream, StreamExt, TryFutureExt};
 use meta::model::MetaRef;
 use metrics::count::U64Counter;
 use models::meta_data::{VnodeId, VnodeInfo, VnodeStatus};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
 use tracing::warn;
 use tskv::reader::QueryOption;
 
@@ -195,6 +196,52 @@ impl<O: VnodeOpener> Stream for CheckedCoordinatorRecordBatchStream<O> {
     }
 }
 
+/// Stops reading the inner stream once the token is cancelled.
+///
+/// The inner stream is dropped on cancellation, which aborts the scan rpc of the remote vnode.
+pub struct CancellableCoordinatorRecordBatchStream {
+    inner: Option<SendableCoordinatorRecordBatchStream>,
+    cancelled: Pin<Box<WaitForCancellationFutureOwned>>,
+}
+
+impl CancellableCoordinatorRecordBatchStream {
+    pub fn new(inner: SendableCoordinatorRecordBatchStream, token: CancellationToken) -> Self {
+        Self {
+            inner: Some(inner),
+            cancelled: Box::pin(token.cancelled_owned()),
+        }
+    }
+
+    /// Wraps the stream if there is a cancellation token.
+    pub fn wrap(
+        inner: SendableCoordinatorRecordBatchStream,
+        token: Option<&CancellationToken>,
+    ) -> SendableCoordinatorRecordBatchStream {
+        match token {
+            Some(token) => Box::pin(Self::new(inner, token.clone())),
+            None => inner,
+        }
+    }
+}
+
+impl Stream for CancellableCoordinatorRecordBatchStream {
+    type Item = Result<RecordBatch, CoordinatorError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.inner.is_none() {
+            return Poll::Ready(None);
+        }
+        if self.cancelled.poll_unpin(cx).is_ready() {
+            self.inner = None;
+            return Poll::Ready(Some(Err(CoordinatorError::Cancelled)));
+        }
+        match self.inner.as_mut() {
+            Some(inner) => inner.poll_next_unpin(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
+
 pub async fn change_vnode_to_broken(
     tenant: String,
     vnode_id: VnodeId,
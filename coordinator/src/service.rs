@@ -51,9 +51,11 @@ use tokio::sync::mpsc;
 use tonic::transport::Channel;
 use tower::timeout::Timeout;
 use trace::{debug, error, info, SpanContext, SpanExt, SpanRecorder};
+use tskv::backup::{BackupLocation, VnodeBackup};
 use tskv::{EngineRef, Error};
 use utils::BkdrHasher;
 
+use crate::backup::{BackupManifest, BucketBackup};
 use crate::errors::*;
 use crate::hh_queue::HintedOffManager;
 use crate::metrics::LPReporter;
@@ -397,6 +399,43 @@ impl CoordService {
         }
     }
 
+    async fn exec_admin_fetch_bytes_on_node(
+        &self,
+        node_id: u64,
+        req: AdminFetchCommandRequest,
+    ) -> CoordinatorResult<Vec<u8>> {
+        let channel = self.meta.get_node_conn(node_id).await?;
+
+        let request = tonic::Request::new(req);
+        let mut client = tskv_service_time_out_client(
+            channel,
+            Duration::from_secs(60 * 60),
+            DEFAULT_GRPC_SERVER_MESSAGE_LEN,
+            self.grpc_enable_gzip,
+        );
+        let response = client
+            .exec_admin_fetch_command(request)
+            .await
+            .map_err(tskv::Error::from)?
+            .into_inner();
+        if response.code == SUCCESS_RESPONSE_CODE {
+            Ok(response.data)
+        } else {
+            Err(CoordinatorError::GRPCRequest {
+                msg: String::from_utf8_lossy(&response.data).to_string(),
+            })
+        }
+    }
+
+    async fn backup_vnode_on_node(
+        &self,
+        node_id: u64,
+        req: AdminFetchCommandRequest,
+    ) -> CoordinatorResult<VnodeBackup> {
+        let data = self.exec_admin_fetch_bytes_on_node(node_id, req).await?;
+        Ok(bincode::deserialize(&data)?)
+    }
+
     #[allow(clippy::type_complexity)]
     fn multi_write_vnodes<'a>(
         &'a self,
@@ -1229,6 +1268,139 @@ impl Coordinator for CoordService {
 
         Ok(())
     }
+
+    async fn backup_database(
+        &self,
+        tenant: &str,
+        db: &str,
+        location: &BackupLocation,
+        backup_id: &str,
+        base: Option<&BackupManifest>,
+    ) -> CoordinatorResult<Vec<BucketBackup>> {
+        let meta = self
+            .meta
+            .tenant_meta(tenant)
+            .await
+            .ok_or(CoordinatorError::TenantNotFound {
+                name: tenant.to_string(),
+            })?;
+        let db_info = meta
+            .get_db_info(db)?
+            .ok_or_else(|| CoordinatorError::CommonError {
+                msg: format!("Database not found: {db}"),
+            })?;
+        let location = bincode::serialize(location)?;
+        let uploaded_files = base.map(|m| m.uploaded_files()).unwrap_or_default();
+
+        let mut buckets = Vec::with_capacity(db_info.buckets.len());
+        for bucket in db_info.buckets.iter() {
+            let mut requests = Vec::with_capacity(bucket.shard_group.len());
+            for replica in bucket.shard_group.iter() {
+                let vnode = replica
+                    .vnode(replica.leader_vnode_id)
+                    .or_else(|| replica.vnodes.first().cloned())
+                    .ok_or(CoordinatorError::NoValidReplica { id: replica.id })?;
+                let req = AdminFetchCommandRequest {
+                    tenant: tenant.to_string(),
+                    command: Some(admin_fetch_command_request::Command::BackupVnode(
+                        BackupVnodeRequest {
+                            db: db.to_string(),
+                            vnode_id: vnode.id,
+                            backup_id: backup_id.to_string(),
+                            location: location.clone(),
+                            uploaded_files: uploaded_files
+                                .get(&vnode.id)
+                                .map(|files| files.iter().copied().collect())
+                                .unwrap_or_default(),
+                        },
+                    )),
+                };
+                requests.push(self.backup_vnode_on_node(vnode.node_id, req));
+            }
+
+            let mut shard_group = Vec::with_capacity(requests.len());
+            for res in futures::future::join_all(requests).await {
+                shard_group.push(res?);
+            }
+            buckets.push(BucketBackup {
+                start_time: bucket.start_time,
+                end_time: bucket.end_time,
+                shard_group,
+            });
+        }
+
+        Ok(buckets)
+    }
+
+    async fn restore_database(
+        &self,
+        tenant: &str,
+        db: &str,
+        location: &BackupLocation,
+        buckets: &[BucketBackup],
+    ) -> CoordinatorResult<()> {
+        let meta = self
+            .meta
+            .tenant_meta(tenant)
+            .await
+            .ok_or(CoordinatorError::TenantNotFound {
+                name: tenant.to_string(),
+            })?;
+        let expired_time = meta
+            .get_db_info(db)?
+            .ok_or_else(|| CoordinatorError::CommonError {
+                msg: format!("Database not found: {db}"),
+            })?
+            .schema
+            .time_to_expired();
+        let location = bincode::serialize(location)?;
+
+        for bucket in buckets {
+            // data of expired buckets will be deleted, no need to restore
+            if bucket.end_time <= expired_time {
+                continue;
+            }
+            let bucket_info = meta
+                .create_bucket(db, bucket.start_time.max(expired_time))
+                .await?;
+            if bucket_info.shard_group.len() != bucket.shard_group.len() {
+                return Err(CoordinatorError::CommonError {
+                    msg: format!(
+                        "Bucket starts at {} has {} replication sets, but {} in the backup",
+                        bucket.start_time,
+                        bucket_info.shard_group.len(),
+                        bucket.shard_group.len()
+                    ),
+                });
+            }
+
+            let mut requests = Vec::new();
+            for (replica, backup) in bucket_info
+                .shard_group
+                .iter()
+                .zip(bucket.shard_group.iter())
+            {
+                let backup = bincode::serialize(backup)?;
+                for vnode in replica.vnodes.iter() {
+                    let req = AdminCommandRequest {
+                        tenant: tenant.to_string(),
+                        command: Some(RestoreVnode(RestoreVnodeRequest {
+                            db: db.to_string(),
+                            vnode_id: vnode.id,
+                            location: location.clone(),
+                            backup: backup.clone(),
+                        })),
+                    };
+                    requests.push(self.exec_admin_command_on_node(vnode.node_id, req));
+                }
+            }
+            for res in futures::future::join_all(requests).await {
+                res?
+            }
+        }
+
+        Ok(())
+    }
 }
 
 struct VnodeLines<'a> {
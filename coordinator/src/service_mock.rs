@@ -15,10 +15,12 @@ use models::schema::{Precision, TskvTableSchemaRef};
 use protocol_parser::Line;
 use protos::kv_service::{AdminCommandRequest, RaftWriteCommand, UpdateSetValue};
 use trace::SpanContext;
+use tskv::backup::BackupLocation;
 use tskv::engine_mock::MockEngine;
 use tskv::reader::QueryOption;
 use tskv::EngineRef;
 
+use crate::backup::{BackupManifest, BucketBackup};
 use crate::errors::CoordinatorResult;
 use crate::raft::manager::RaftNodesManager;
 use crate::service::CoordServiceMetrics;
@@ -252,4 +254,25 @@ impl Coordinator for MockCoordinator {
     ) -> CoordinatorResult<()> {
         todo!()
     }
+
+    async fn backup_database(
+        &self,
+        tenant: &str,
+        db: &str,
+        location: &BackupLocation,
+        backup_id: &str,
+        base: Option<&BackupManifest>,
+    ) -> CoordinatorResult<Vec<BucketBackup>> {
+        Ok(vec![])
+    }
+
+    async fn restore_database(
+        &self,
+        tenant: &str,
+        db: &str,
+        location: &BackupLocation,
+        buckets: &[BucketBackup],
+    ) -> CoordinatorResult<()> {
+        Ok(())
+    }
 }
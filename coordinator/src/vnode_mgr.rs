@@ -97,6 +97,8 @@ impl VnodeManager {
             return Err(err);
         }
 
+        // Tsm files in the cold storage are not downloaded, they are copied to the
+        // location of the new vnode when the summary is applied.
         let ve = self.fetch_vnode_summary(&all_info, &mut client).await?;
         self.kv_inst
             .apply_vnode_summary(tenant, &all_info.db_name, new_id, ve)
@@ -0,0 +1,146 @@
+#[cfg(test)]
+pub mod test {
+    use std::path::PathBuf;
+
+    use http_protocol::status_code;
+
+    use crate::utils::{clean_env, start_singleton, Client};
+
+    const URL: &str = "http://127.0.0.1:8902/api/v1/sql?db=dict_db";
+
+    fn execute(client: &Client, sql: &str) -> Vec<String> {
+        let resp = client.post(URL, sql).unwrap();
+        let status = resp.status();
+        let text = resp.text().unwrap();
+        assert_eq!(status, status_code::OK, "{sql}: {text}");
+        text.split_terminator('\n').map(|l| l.to_string()).collect()
+    }
+
+    /// Flush and compact all vnodes of the database, the data written so far
+    /// is moved into tsm blocks.
+    fn compact(client: &Client) {
+        let dir = PathBuf::from("/tmp/cnosdb/1001/db/data/cnosdb.dict_db");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let entry = entry.unwrap();
+            if let Ok(vnode_id) = entry.file_name().to_string_lossy().parse::<u32>() {
+                execute(client, &format!("compact vnode {vnode_id};"));
+            }
+        }
+    }
+
+    /// Rows of a string field are in blocks written before and after the field
+    /// is switched to the dictionary codec, and in the cache.
+    #[test]
+    fn test_dictionary_codec_mixed_blocks() {
+        println!("Test begin test_dictionary_codec_mixed_blocks");
+        clean_env();
+        let _data = start_singleton(None::<PathBuf>, "config_8902.toml", "127.0.0.1:8902");
+
+        let client = Client::new("root".to_string(), Some(String::new()));
+
+        execute(
+            &client,
+            "CREATE DATABASE dict_db WITH TTL '1000000d' SHARD 1 VNODE_DURATION '1000d' REPLICA 1",
+        );
+        execute(&client, "CREATE TABLE air (status STRING, TAGS(station))");
+        execute(
+            &client,
+            "INSERT INTO air (time, station, status) VALUES
+                ('2023-01-01 00:00:00', 'XiaoMaiDao', 'ok'),
+                ('2023-01-01 00:00:01', 'XiaoMaiDao', 'error'),
+                ('2023-01-01 00:00:02', 'LianYunGang', 'ok')",
+        );
+        compact(&client);
+
+        execute(
+            &client,
+            "ALTER TABLE air ALTER status SET CODEC(DICTIONARY)",
+        );
+        execute(
+            &client,
+            "INSERT INTO air (time, station, status) VALUES
+                ('2023-01-01 00:00:03', 'XiaoMaiDao', 'ok'),
+                ('2023-01-01 00:00:04', 'XiaoMaiDao', 'warn'),
+                ('2023-01-01 00:00:05', 'LianYunGang', 'error')",
+        );
+        compact(&client);
+
+        execute(
+            &client,
+            "INSERT INTO air (time, station, status) VALUES
+                ('2023-01-01 00:00:06', 'XiaoMaiDao', 'ok'),
+                ('2023-01-01 00:00:07', 'LianYunGang', 'warn')",
+        );
+
+        assert_eq!(
+            execute(
+                &client,
+                "SELECT time, station, status FROM air ORDER BY station, time"
+            ),
+            vec![
+                "time,station,status",
+                "2023-01-01T00:00:02.000000000,LianYunGang,ok",
+                "2023-01-01T00:00:05.000000000,LianYunGang,error",
+                "2023-01-01T00:00:07.000000000,LianYunGang,warn",
+                "2023-01-01T00:00:00.000000000,XiaoMaiDao,ok",
+                "2023-01-01T00:00:01.000000000,XiaoMaiDao,error",
+                "2023-01-01T00:00:03.000000000,XiaoMaiDao,ok",
+                "2023-01-01T00:00:04.000000000,XiaoMaiDao,warn",
+                "2023-01-01T00:00:06.000000000,XiaoMaiDao,ok",
+            ]
+        );
+        assert_eq!(
+            execute(
+                &client,
+                "SELECT time, station, status FROM air WHERE status = 'ok' ORDER BY station, time"
+            ),
+            vec![
+                "time,station,status",
+                "2023-01-01T00:00:02.000000000,LianYunGang,ok",
+                "2023-01-01T00:00:00.000000000,XiaoMaiDao,ok",
+                "2023-01-01T00:00:03.000000000,XiaoMaiDao,ok",
+                "2023-01-01T00:00:06.000000000,XiaoMaiDao,ok",
+            ]
+        );
+        assert_eq!(
+            execute(
+                &client,
+                "SELECT time, station FROM air WHERE status = 'error' ORDER BY station, time"
+            ),
+            vec![
+                "time,station",
+                "2023-01-01T00:00:05.000000000,LianYunGang",
+                "2023-01-01T00:00:01.000000000,XiaoMaiDao",
+            ]
+        );
+        assert_eq!(
+            execute(
+                &client,
+                "SELECT count(*) AS cnt FROM air WHERE status = 'missing'"
+            ),
+            vec!["cnt", "0"]
+        );
+
+        // Overwrite a value in a dictionary block by a value in the cache.
+        execute(
+            &client,
+            "INSERT INTO air (time, station, status) VALUES
+                ('2023-01-01 00:00:03', 'XiaoMaiDao', 'error')",
+        );
+        assert_eq!(
+            execute(
+                &client,
+                "SELECT time, station, status FROM air WHERE status = 'ok' ORDER BY station, time"
+            ),
+            vec![
+                "time,station,status",
+                "2023-01-01T00:00:02.000000000,LianYunGang,ok",
+                "2023-01-01T00:00:00.000000000,XiaoMaiDao,ok",
+                "2023-01-01T00:00:06.000000000,XiaoMaiDao,ok",
+            ]
+        );
+
+        clean_env();
+        println!("Test complete test_dictionary_codec_mixed_blocks");
+    }
+}
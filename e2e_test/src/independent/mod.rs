@@ -5,6 +5,7 @@ mod chaos_tests;
 #[cfg(feature = "coordinator_e2e_test")]
 #[cfg(test)]
 mod coordinator_tests;
+mod dictionary_codec_tests;
 mod dump;
 mod https_api_tests;
 mod restart_tests;
@@ -0,0 +1,205 @@
+use std::pin::Pin;
+
+use arrow_flight::flight_descriptor::DescriptorType;
+use arrow_flight::flight_service_server::FlightService;
+use arrow_flight::sql::{
+    Any, CommandPreparedStatementQuery, CommandPreparedStatementUpdate,
+    CommandStatementSubstraitPlan, CommandStatementUpdate, DoPutUpdateResult,
+};
+use arrow_flight::{
+    Action, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo, HandshakeRequest,
+    HandshakeResponse, PutResult, SchemaResult, Ticket,
+};
+use futures::{Stream, StreamExt};
+use prost::Message;
+use tonic::{Request, Response, Status, Streaming};
+
+use super::auth_middleware::CallHeaderAuthenticator;
+use super::flight_sql_server::{FlightDataStream, FlightSqlServiceImpl};
+use super::ingest::CommandStatementIngest;
+use crate::status;
+
+type InnerService<T> = FlightSqlServiceImpl<T>;
+
+/// Flight service of flight sql.
+///
+/// All requests except do_put are served by the [`FlightService`] of [`FlightSqlServiceImpl`].
+/// The do_put of it consumes the first message before dispatching by the command,
+/// which is the schema of the parameters or the record batches,
+/// and rejects the commands unknown to arrow-flight like [`CommandStatementIngest`].
+///
+/// The do_put of a path descriptor `tenant.db.table` writes the record batches into the table.
+pub struct FlightServiceImpl<T> {
+    inner: InnerService<T>,
+}
+
+impl<T> FlightServiceImpl<T> {
+    pub fn new(inner: InnerService<T>) -> Self {
+        Self { inner }
+    }
+}
+
+#[tonic::async_trait]
+impl<T> FlightService for FlightServiceImpl<T>
+where
+    T: CallHeaderAuthenticator + Send + Sync + 'static,
+{
+    type HandshakeStream = <InnerService<T> as FlightService>::HandshakeStream;
+    type ListFlightsStream = <InnerService<T> as FlightService>::ListFlightsStream;
+    type DoGetStream = <InnerService<T> as FlightService>::DoGetStream;
+    type DoPutStream = <InnerService<T> as FlightService>::DoPutStream;
+    type DoActionStream = <InnerService<T> as FlightService>::DoActionStream;
+    type ListActionsStream = <InnerService<T> as FlightService>::ListActionsStream;
+    type DoExchangeStream = <InnerService<T> as FlightService>::DoExchangeStream;
+
+    async fn handshake(
+        &self,
+        request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        FlightService::handshake(&self.inner, request).await
+    }
+
+    async fn list_flights(
+        &self,
+        request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        FlightService::list_flights(&self.inner, request).await
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        FlightService::get_flight_info(&self.inner, request).await
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        FlightService::get_schema(&self.inner, request).await
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        FlightService::do_get(&self.inner, request).await
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let (metadata, extensions, mut stream) = request.into_parts();
+
+        let first = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("do_put: no flight data"))?;
+        let descriptor = first
+            .flight_descriptor
+            .clone()
+            .ok_or_else(|| Status::invalid_argument("do_put: no flight descriptor"))?;
+        // put back the first message, it is the schema of the record batches
+        let data: FlightDataStream =
+            Box::pin(futures::stream::once(async { Ok(first) }).chain(stream));
+
+        if descriptor.r#type() == DescriptorType::Path {
+            let output = self
+                .inner
+                .put_table(&descriptor, &metadata, &extensions, data)
+                .await?;
+            return Ok(Response::new(output));
+        }
+
+        let any =
+            Any::decode(&*descriptor.cmd).map_err(|e| status!("Unable to decode command", e))?;
+
+        if let Some(cmd) = any
+            .unpack::<CommandStatementUpdate>()
+            .map_err(|e| status!("Unable to unpack command", e))?
+        {
+            let record_count = self
+                .inner
+                .put_statement_update(cmd, &metadata, &extensions)
+                .await?;
+            return Ok(update_result(record_count));
+        }
+        if let Some(cmd) = any
+            .unpack::<CommandPreparedStatementQuery>()
+            .map_err(|e| status!("Unable to unpack command", e))?
+        {
+            self.inner
+                .put_prepared_statement_query(cmd, &extensions, data)
+                .await?;
+            return Ok(Response::new(Box::pin(futures::stream::empty())));
+        }
+        if let Some(cmd) = any
+            .unpack::<CommandPreparedStatementUpdate>()
+            .map_err(|e| status!("Unable to unpack command", e))?
+        {
+            let record_count = self
+                .inner
+                .put_prepared_statement_update(cmd, &extensions, data)
+                .await?;
+            return Ok(update_result(record_count));
+        }
+        if let Some(cmd) = any
+            .unpack::<CommandStatementIngest>()
+            .map_err(|e| status!("Unable to unpack command", e))?
+        {
+            let record_count = self
+                .inner
+                .put_statement_ingest(cmd, &metadata, &extensions, data)
+                .await?;
+            return Ok(update_result(record_count));
+        }
+        if let Some(cmd) = any
+            .unpack::<CommandStatementSubstraitPlan>()
+            .map_err(|e| status!("Unable to unpack command", e))?
+        {
+            let record_count = self
+                .inner
+                .put_substrait_plan(cmd, &metadata, &extensions)
+                .await?;
+            return Ok(update_result(record_count));
+        }
+
+        Err(Status::invalid_argument(format!(
+            "do_put: The defined request is invalid: {}",
+            any.type_url
+        )))
+    }
+
+    async fn do_action(
+        &self,
+        request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        FlightService::do_action(&self.inner, request).await
+    }
+
+    async fn list_actions(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        FlightService::list_actions(&self.inner, request).await
+    }
+
+    async fn do_exchange(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        FlightService::do_exchange(&self.inner, request).await
+    }
+}
+
+fn update_result(
+    record_count: i64,
+) -> Response<Pin<Box<dyn Stream<Item = Result<PutResult, Status>> + Send>>> {
+    let result = DoPutUpdateResult { record_count };
+    let output = futures::stream::iter(vec![Ok(PutResult {
+        app_metadata: result.encode_to_vec().into(),
+    })]);
+    Response::new(Box::pin(output))
+}
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
+use arrow_flight::decode::FlightRecordBatchStream;
+use arrow_flight::error::FlightError;
 use arrow_flight::flight_service_server::FlightService;
 use arrow_flight::sql::server::FlightSqlService;
 use arrow_flight::sql::{
@@ -9,62 +12,107 @@ use arrow_flight::sql::{
     ActionBeginTransactionResult, ActionCancelQueryRequest, ActionCancelQueryResult,
     ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest,
     ActionCreatePreparedStatementResult, ActionCreatePreparedSubstraitPlanRequest,
-    ActionEndSavepointRequest, ActionEndTransactionRequest, CommandGetCatalogs,
+    ActionEndSavepointRequest, ActionEndTransactionRequest, Any, CancelResult, CommandGetCatalogs,
     CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys, CommandGetImportedKeys,
     CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes, CommandGetTables,
     CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandPreparedStatementUpdate,
-    CommandStatementQuery, CommandStatementSubstraitPlan, CommandStatementUpdate, ProstMessageExt,
-    SqlInfo, TicketStatementQuery,
+    CommandStatementQuery, CommandStatementSubstraitPlan, CommandStatementUpdate,
+    DoPutUpdateResult, ProstMessageExt, SqlInfo, SubstraitPlan, TicketStatementQuery,
 };
 use arrow_flight::{
     utils as flight_utils, Action, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
-    HandshakeRequest, HandshakeResponse, IpcMessage, Ticket,
+    HandshakeRequest, HandshakeResponse, IpcMessage, PutResult, Ticket,
 };
-use datafusion::arrow::datatypes::{Schema, SchemaRef, ToByteSlice};
-use futures::Stream;
-use http_protocol::header::{DB, STREAM_TRIGGER_INTERVAL, TARGET_PARTITIONS, TENANT};
+use coordinator::service::CoordinatorRef;
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef, ToByteSlice};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::scalar::ScalarValue;
+use futures::{Stream, TryStreamExt};
+use http_protocol::header::{DB, DIALECT, STREAM_TRIGGER_INTERVAL, TARGET_PARTITIONS, TENANT};
+use models::auth::privilege::{DatabasePrivilege, Privilege, TenantObjectPrivilege};
 use models::auth::user::User;
 use models::oid::UuidGenerator;
+use models::schema::TskvTableSchemaRef;
 use moka::sync::Cache;
+use parking_lot::Mutex;
 use prost::bytes::Bytes;
 use prost::Message;
-use spi::query::config::StreamTriggerInterval;
+use query::sql::param::rewrite_question_mark_placeholders;
+use spi::query::config::{SqlDialect, StreamTriggerInterval};
 use spi::query::execution::{Output, QueryStateMachineRef};
 use spi::query::logical_planner::Plan;
 use spi::server::dbms::DBMSRef;
-use spi::service::protocol::{Context, ContextBuilder, Query, QueryHandle};
+use spi::service::protocol::{Context, ContextBuilder, Query, QueryHandle, QueryId};
 use tonic::metadata::MetadataMap;
 use tonic::{Extensions, Request, Response, Status, Streaming};
 use trace::{debug, SpanContext, SpanExt, SpanRecorder};
 
 use super::auth_middleware::CallHeaderAuthenticator;
 use crate::flight_sql::auth_middleware::AuthResult;
+use crate::flight_sql::ingest::{parse_table_path, write_record_batch, CommandStatementIngest};
 use crate::flight_sql::utils;
 use crate::status;
 
 const UNKNOWN_AFFECTED_ROWS_COUNT: i64 = -1;
 
+pub type FlightDataStream = Pin<Box<dyn Stream<Item = Result<FlightData, Status>> + Send>>;
+
+/// Query state machines of the running executions of each prepared statement.
+type Executions = Arc<Mutex<HashMap<Vec<u8>, Vec<QueryStateMachineRef>>>>;
+
+/// A running execution of a prepared statement,
+/// it's removed from the running executions when dropped.
+struct ExecutionGuard {
+    executions: Executions,
+    statement_handle: Vec<u8>,
+    query_id: QueryId,
+}
+
+impl Drop for ExecutionGuard {
+    fn drop(&mut self) {
+        let mut executions = self.executions.lock();
+        if let Some(running) = executions.get_mut(&self.statement_handle) {
+            running.retain(|e| e.query_id != self.query_id);
+            if running.is_empty() {
+                executions.remove(&self.statement_handle);
+            }
+        }
+    }
+}
+
 pub struct FlightSqlServiceImpl<T> {
     instance: DBMSRef,
+    coord: CoordinatorRef,
     authenticator: T,
     id_generator: UuidGenerator,
     result_cache: Cache<Vec<u8>, (Option<Plan>, QueryStateMachineRef)>,
+    /// Parameters bound to the prepared statements by do_put
+    param_cache: Cache<Vec<u8>, Vec<ScalarValue>>,
+    executions: Executions,
 }
 
 impl<T> FlightSqlServiceImpl<T> {
-    pub fn new(instance: DBMSRef, authenticator: T) -> Self {
+    pub fn new(instance: DBMSRef, coord: CoordinatorRef, authenticator: T) -> Self {
         let result_cache = Cache::builder()
             .thread_pool_enabled(false)
             // Time to live (TTL): 2 minutes
             // The query results are only cached for 2 minutes and expire after 2 minutes
             .time_to_live(Duration::from_secs(2 * 60))
             .build();
+        let param_cache = Cache::builder()
+            .thread_pool_enabled(false)
+            .time_to_live(Duration::from_secs(2 * 60))
+            .build();
 
         Self {
             instance,
+            coord,
             authenticator,
             id_generator: Default::default(),
             result_cache,
+            param_cache,
+            executions: Default::default(),
         }
     }
 }
@@ -79,6 +127,50 @@ where
         req_headers: &MetadataMap,
         span_ctx: Option<&SpanContext>,
     ) -> Result<(Option<Plan>, QueryStateMachineRef), Status> {
+        let query_state_machine = self
+            .authenticate_and_build_query_state_machine(sql, req_headers, span_ctx)
+            .await?;
+
+        // build logical plan
+        let logical_plan = self.build_logical_plan(query_state_machine.clone()).await?;
+
+        Ok((logical_plan, query_state_machine))
+    }
+
+    async fn pre_precess_substrait_plan_req(
+        &self,
+        plan: Option<SubstraitPlan>,
+        req_headers: &MetadataMap,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<(Option<Plan>, QueryStateMachineRef), Status> {
+        let SubstraitPlan { plan, version } =
+            plan.ok_or_else(|| Status::invalid_argument("Substrait plan is required"))?;
+
+        // the substrait plan takes the place of the sql
+        let query_state_machine = self
+            .authenticate_and_build_query_state_machine(
+                format!("substrait plan (version {})", version),
+                req_headers,
+                span_ctx,
+            )
+            .await?;
+
+        // build logical plan
+        let logical_plan = self
+            .instance
+            .build_substrait_logical_plan(query_state_machine.clone(), &plan)
+            .await
+            .map_err(|e| status!("Build logical plan", e))?;
+
+        Ok((Some(logical_plan), query_state_machine))
+    }
+
+    async fn authenticate_and_build_query_state_machine(
+        &self,
+        sql: impl Into<String>,
+        req_headers: &MetadataMap,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<QueryStateMachineRef, Status> {
         // auth request
         let auth_result = {
             let _span_recorder = SpanRecorder::new(span_ctx.child_span("authenticate"));
@@ -100,10 +192,7 @@ where
                 .await?
         };
 
-        // build logical plan
-        let logical_plan = self.build_logical_plan(query_state_machine.clone()).await?;
-
-        Ok((logical_plan, query_state_machine))
+        Ok(query_state_machine)
     }
 
     async fn pre_precess_statement_query_req_and_save(
@@ -121,6 +210,16 @@ where
             .map(|e| e.schema())
             .unwrap_or(Arc::new(Schema::empty()));
 
+        let result_ident = self.save_statement(logical_plan, query_state_machine);
+
+        Ok((result_ident, schema))
+    }
+
+    fn save_statement(
+        &self,
+        logical_plan: Option<Plan>,
+        query_state_machine: QueryStateMachineRef,
+    ) -> Vec<u8> {
         // generate result identifier
         let result_ident = self.id_generator.next_id().to_le_bytes().to_vec();
 
@@ -128,7 +227,7 @@ where
         self.result_cache
             .insert(result_ident.clone(), (logical_plan, query_state_machine));
 
-        Ok((result_ident, schema))
+        result_ident
     }
 
     async fn precess_flight_info_req(
@@ -141,6 +240,15 @@ where
             .pre_precess_statement_query_req_and_save(sql, request.metadata(), span_ctx)
             .await?;
 
+        self.construct_statement_flight_info(result_ident, schema, request)
+    }
+
+    fn construct_statement_flight_info(
+        &self,
+        result_ident: Vec<u8>,
+        schema: SchemaRef,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
         let ticket = TicketStatementQuery {
             statement_handle: result_ident.into(),
         };
@@ -206,11 +314,18 @@ where
                         STREAM_TRIGGER_INTERVAL, e
                     ))
                 })?;
+        let dialect = utils::get_value_from_header(metadata, DIALECT, "")
+            .map(|e| e.parse::<SqlDialect>())
+            .transpose()
+            .map_err(|e| {
+                Status::invalid_argument(format!("parse {} failed, error: {}", DIALECT, e))
+            })?;
         let ctx = ContextBuilder::new(user_info)
             .with_tenant(tenant)
             .with_database(db)
             .with_target_partitions(target_partitions)
             .with_stream_trigger_interval(stream_trigger_interval)
+            .with_dialect(dialect)
             .build();
 
         Ok(ctx)
@@ -281,14 +396,49 @@ where
         Ok((logical_plan, query_state_machine))
     }
 
-    async fn execute_and_fetch_result_set(
+    /// Start an execution of the prepared statement.
+    ///
+    /// Every execution is a new query with its own query id and cancellation token,
+    /// so that it's tracked, killed and limited by the quotas as one query.
+    async fn start_execution(
         &self,
         statement_handle: &[u8],
+        query_state_machine: &QueryStateMachineRef,
         span_ctx: Option<&SpanContext>,
-    ) -> Result<<Self as FlightService>::DoGetStream, Status> {
-        let (logical_plan, query_state_machine) =
-            self.get_plan_and_qsm(statement_handle, span_ctx.cloned())?;
+    ) -> Result<(QueryStateMachineRef, ExecutionGuard), Status> {
+        let query_state_machine = self
+            .instance
+            .build_query_state_machine(query_state_machine.query.clone(), span_ctx)
+            .await
+            .map_err(|e| status!("Build query state machine", e))?;
 
+        self.executions
+            .lock()
+            .entry(statement_handle.to_vec())
+            .or_default()
+            .push(query_state_machine.clone());
+        let guard = ExecutionGuard {
+            executions: self.executions.clone(),
+            statement_handle: statement_handle.to_vec(),
+            query_id: query_state_machine.query_id,
+        };
+
+        Ok((query_state_machine, guard))
+    }
+
+    fn running_executions(&self, statement_handle: &[u8]) -> Vec<QueryStateMachineRef> {
+        self.executions
+            .lock()
+            .get(statement_handle)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    async fn execute_and_fetch_result_set(
+        &self,
+        logical_plan: Option<Plan>,
+        query_state_machine: QueryStateMachineRef,
+    ) -> Result<<Self as FlightService>::DoGetStream, Status> {
         // execute plan
         let query_result = self
             .execute_logical_plan(logical_plan, query_state_machine)
@@ -309,6 +459,360 @@ where
             Box::pin(futures::stream::iter(flight_data));
         Ok(stream)
     }
+
+    /// Replace the placeholders of the plan with the parameters bound to the prepared statement.
+    fn bind_parameters(
+        &self,
+        statement_handle: &[u8],
+        logical_plan: Option<Plan>,
+    ) -> Result<Option<Plan>, Status> {
+        match (logical_plan, self.param_cache.get(statement_handle)) {
+            (Some(plan), Some(params)) => plan
+                .with_param_values(params)
+                .map(Some)
+                .map_err(|e| status!("Bind parameters", e)),
+            (logical_plan, _) => Ok(logical_plan),
+        }
+    }
+
+    pub(super) async fn put_statement_update(
+        &self,
+        ticket: CommandStatementUpdate,
+        req_headers: &MetadataMap,
+        extensions: &Extensions,
+    ) -> Result<i64, Status> {
+        let span_recorder = get_span_recorder(extensions, "flight sql do_put_statement_update");
+        let span_ctx = span_recorder.span_ctx();
+        // ignore transaction_id
+        let CommandStatementUpdate { query, .. } = ticket;
+
+        let (logical_plan, query_state_machine) = self
+            .pre_precess_statement_query_req(query, req_headers, span_ctx)
+            .await?;
+
+        // execute plan
+        let query_result = self
+            .execute_logical_plan(logical_plan, query_state_machine)
+            .await?;
+
+        let affected_rows = query_result.result().affected_rows().await;
+
+        Ok(affected_rows)
+    }
+
+    pub(super) async fn put_substrait_plan(
+        &self,
+        ticket: CommandStatementSubstraitPlan,
+        req_headers: &MetadataMap,
+        extensions: &Extensions,
+    ) -> Result<i64, Status> {
+        let span_recorder = get_span_recorder(extensions, "flight sql do_put_substrait_plan");
+        // ignore transaction_id
+        let CommandStatementSubstraitPlan { plan, .. } = ticket;
+
+        let (logical_plan, query_state_machine) = self
+            .pre_precess_substrait_plan_req(plan, req_headers, span_recorder.span_ctx())
+            .await?;
+
+        // execute plan
+        let query_result = self
+            .execute_logical_plan(logical_plan, query_state_machine)
+            .await?;
+
+        let affected_rows = query_result.result().affected_rows().await;
+
+        Ok(affected_rows)
+    }
+
+    /// Bind the parameters to the prepared statement,
+    /// they are used by the following executions of the prepared statement.
+    pub(super) async fn put_prepared_statement_query(
+        &self,
+        query: CommandPreparedStatementQuery,
+        extensions: &Extensions,
+        data: FlightDataStream,
+    ) -> Result<(), Status> {
+        let span_recorder =
+            get_span_recorder(extensions, "flight sql do_put_prepared_statement_query");
+        let statement_handle = query.prepared_statement_handle.to_vec();
+
+        let (plan, _) =
+            self.get_plan_and_qsm(&statement_handle, span_recorder.span_ctx().cloned())?;
+        let mut rows = decode_parameters(plan.as_ref(), data).await?;
+        if rows.len() > 1 {
+            return Err(Status::invalid_argument(format!(
+                "A query accepts only one row of parameters, but got {}",
+                rows.len()
+            )));
+        }
+
+        match rows.pop() {
+            Some(params) => self.param_cache.insert(statement_handle, params),
+            None => self.param_cache.invalidate(&statement_handle),
+        }
+
+        Ok(())
+    }
+
+    /// Execute the prepared statement once for each row of the parameters,
+    /// return the total number of affected rows.
+    pub(super) async fn put_prepared_statement_update(
+        &self,
+        query: CommandPreparedStatementUpdate,
+        extensions: &Extensions,
+        data: FlightDataStream,
+    ) -> Result<i64, Status> {
+        let prepared_statement_ident = query.prepared_statement_handle.to_byte_slice();
+        debug!(
+            "do_put_prepared_statement_update query: {:?}",
+            prepared_statement_ident
+        );
+        let span_recorder =
+            get_span_recorder(extensions, "flight sql do_put_prepared_statement_update");
+        let span_ctx = span_recorder.span_ctx();
+
+        let (plan, query_machine) =
+            self.get_plan_and_qsm(prepared_statement_ident, span_ctx.cloned())?;
+        let rows = decode_parameters(plan.as_ref(), data).await?;
+        if rows.is_empty() {
+            let (query_machine, _execution) = self
+                .start_execution(prepared_statement_ident, &query_machine, span_ctx)
+                .await?;
+            // execute plan
+            let query_result = self.execute_logical_plan(plan, query_machine).await?;
+            let output = query_result.result();
+            return Ok(output.affected_rows().await);
+        }
+
+        let mut affected_rows = 0;
+        for params in rows {
+            let plan = plan
+                .clone()
+                .map(|p| p.with_param_values(params))
+                .transpose()
+                .map_err(|e| status!("Bind parameters", e))?;
+            let (query_machine, _execution) = self
+                .start_execution(prepared_statement_ident, &query_machine, span_ctx)
+                .await?;
+            let query_result = self.execute_logical_plan(plan, query_machine).await?;
+            affected_rows += query_result.result().affected_rows().await;
+        }
+
+        Ok(affected_rows)
+    }
+
+    /// Write the record batches into the table of the command,
+    /// return the number of rows written.
+    pub(super) async fn put_statement_ingest(
+        &self,
+        cmd: CommandStatementIngest,
+        req_headers: &MetadataMap,
+        extensions: &Extensions,
+        data: FlightDataStream,
+    ) -> Result<i64, Status> {
+        debug!("do_put_statement_ingest: cmd: {:?}", cmd);
+
+        let span_recorder = get_span_recorder(extensions, "flight sql do_put_statement_ingest");
+        let span_ctx = span_recorder.span_ctx();
+
+        cmd.check_options()?;
+        let table_schema = self
+            .table_to_write(req_headers, cmd.catalog, cmd.schema, &cmd.table, span_ctx)
+            .await?;
+
+        let mut batches =
+            FlightRecordBatchStream::new_from_flight_data(data.map_err(FlightError::Tonic));
+        let mut rows = 0;
+        while let Some(batch) = batches
+            .try_next()
+            .await
+            .map_err(|e| status!("Decode record batch", e))?
+        {
+            rows += write_record_batch(&self.coord, &table_schema, batch, span_ctx).await?;
+        }
+
+        Ok(rows as i64)
+    }
+
+    /// Write the record batches into the table of the path descriptor `tenant.db.table`,
+    /// the result of each record batch is returned once it is written.
+    pub(super) async fn put_table(
+        &self,
+        descriptor: &FlightDescriptor,
+        req_headers: &MetadataMap,
+        extensions: &Extensions,
+        data: FlightDataStream,
+    ) -> Result<<Self as FlightService>::DoPutStream, Status> {
+        debug!("do_put table: descriptor: {:?}", descriptor);
+
+        let span_recorder = get_span_recorder(extensions, "flight do_put table");
+
+        let (tenant, db, table) = parse_table_path(&descriptor.path)?;
+        let table_schema = self
+            .table_to_write(req_headers, tenant, db, &table, span_recorder.span_ctx())
+            .await?;
+
+        let coord = self.coord.clone();
+        let output = async_stream::try_stream! {
+            let mut batches =
+                FlightRecordBatchStream::new_from_flight_data(data.map_err(FlightError::Tonic));
+            while let Some(batch) = batches
+                .try_next()
+                .await
+                .map_err(|e| status!("Decode record batch", e))?
+            {
+                let rows =
+                    write_record_batch(&coord, &table_schema, batch, span_recorder.span_ctx())
+                        .await?;
+                let result = DoPutUpdateResult {
+                    record_count: rows as i64,
+                };
+                yield PutResult {
+                    app_metadata: result.encode_to_vec().into(),
+                };
+            }
+        };
+
+        Ok(Box::pin(output))
+    }
+
+    /// Authenticate the request and get the schema of the table to write,
+    /// the user must have the write privilege of the database.
+    ///
+    /// The tenant and the database of the request are used if absent.
+    pub(super) async fn table_to_write(
+        &self,
+        req_headers: &MetadataMap,
+        tenant: Option<String>,
+        db: Option<String>,
+        table: &str,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<TskvTableSchemaRef, Status> {
+        // auth request
+        let auth_result = {
+            let _span_recorder = SpanRecorder::new(span_ctx.child_span("authenticate"));
+            self.authenticator.authenticate(req_headers).await?
+        };
+        let ctx = self.construct_context(auth_result.identity(), req_headers)?;
+        let tenant = tenant.as_deref().unwrap_or(ctx.tenant());
+        let db = db.as_deref().unwrap_or(ctx.database());
+
+        let meta = self
+            .coord
+            .tenant_meta(tenant)
+            .await
+            .ok_or_else(|| Status::not_found(format!("Tenant {} not found", tenant)))?;
+
+        let privilege = Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Write, Some(db.to_string())),
+            Some(*meta.tenant().id()),
+        );
+        if !ctx.user_info().check_privilege(&privilege) {
+            return Err(Status::permission_denied(format!(
+                "Insufficient privileges, expected [{}]",
+                privilege
+            )));
+        }
+
+        meta.get_tskv_table_schema(db, table)
+            .map_err(|e| status!("Get table schema", e))?
+            .ok_or_else(|| Status::not_found(format!("Table {}.{} not found", db, table)))
+    }
+}
+
+/// Decode the parameters from the record batches of do_put, a row of parameters for an execution.
+///
+/// The columns are the parameters in position order,
+/// they are casted to the types inferred by the planner.
+async fn decode_parameters(
+    plan: Option<&Plan>,
+    data: FlightDataStream,
+) -> Result<Vec<Vec<ScalarValue>>, Status> {
+    let batches: Vec<RecordBatch> =
+        FlightRecordBatchStream::new_from_flight_data(data.map_err(FlightError::Tonic))
+            .try_collect()
+            .await
+            .map_err(|e| status!("Decode parameters", e))?;
+    let param_types = match plan {
+        Some(plan) => plan
+            .parameter_types()
+            .map_err(|e| status!("Get parameter types", e))?,
+        None => HashMap::new(),
+    };
+
+    let mut rows = vec![];
+    for batch in batches {
+        let columns = batch
+            .columns()
+            .iter()
+            .enumerate()
+            .map(
+                |(idx, column)| match param_types.get(&format!("${}", idx + 1)) {
+                    Some(Some(data_type)) if data_type != column.data_type() => {
+                        cast(column, data_type).map_err(|e| status!("Cast parameter", e))
+                    }
+                    _ => Ok(column.clone()),
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+        for row in 0..batch.num_rows() {
+            let params = columns
+                .iter()
+                .map(|column| ScalarValue::try_from_array(column, row))
+                .collect::<datafusion::error::Result<Vec<_>>>()
+                .map_err(|e| status!("Decode parameters", e))?;
+            rows.push(params);
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Schema of the parameters `$1, $2...` of the plan, the type of a parameter is null
+/// if it can't be inferred.
+fn parameter_schema(plan: Option<&Plan>) -> Result<Schema, Status> {
+    let param_types = match plan {
+        Some(plan) => plan
+            .parameter_types()
+            .map_err(|e| status!("Get parameter types", e))?,
+        None => HashMap::new(),
+    };
+
+    let mut params = param_types
+        .into_iter()
+        .filter_map(|(name, data_type)| {
+            let position = name.strip_prefix('$')?.parse::<usize>().ok()?;
+            Some((position, name, data_type))
+        })
+        .collect::<Vec<_>>();
+    params.sort_by_key(|(position, ..)| *position);
+
+    let fields = params
+        .into_iter()
+        .map(|(_, name, data_type)| Field::new(name, data_type.unwrap_or(DataType::Null), true))
+        .collect::<Vec<_>>();
+
+    Ok(Schema::new(fields))
+}
+
+/// Statement handle of the ticket of [`TicketStatementQuery`] or [`CommandPreparedStatementQuery`]
+fn statement_handle_of_ticket(ticket: &Ticket) -> Result<Option<Vec<u8>>, Status> {
+    let any = Any::decode(&*ticket.ticket).map_err(|e| status!("Unable to decode ticket", e))?;
+
+    if let Some(ticket) = any
+        .unpack::<TicketStatementQuery>()
+        .map_err(|e| status!("Unable to unpack ticket", e))?
+    {
+        return Ok(Some(ticket.statement_handle.to_vec()));
+    }
+    if let Some(query) = any
+        .unpack::<CommandPreparedStatementQuery>()
+        .map_err(|e| status!("Unable to unpack ticket", e))?
+    {
+        return Ok(Some(query.prepared_statement_handle.to_vec()));
+    }
+
+    Ok(None)
 }
 
 /// use jdbc to execute statement query:
@@ -704,8 +1208,10 @@ where
 
         let TicketStatementQuery { statement_handle } = ticket;
 
+        let (logical_plan, query_state_machine) =
+            self.get_plan_and_qsm(&statement_handle, span_recorder.span_ctx().cloned())?;
         let output = self
-            .execute_and_fetch_result_set(&statement_handle, span_recorder.span_ctx())
+            .execute_and_fetch_result_set(logical_plan, query_state_machine)
             .await?;
 
         // clear cache of this query
@@ -733,14 +1239,18 @@ where
 
         let prepared_statement_handle = query.prepared_statement_handle.to_byte_slice();
 
+        // the prepared statement is reused until it is closed
+        let span_ctx = span_recorder.span_ctx();
+        let (logical_plan, query_state_machine) =
+            self.get_plan_and_qsm(prepared_statement_handle, span_ctx.cloned())?;
+        let logical_plan = self.bind_parameters(prepared_statement_handle, logical_plan)?;
+        let (query_state_machine, _execution) = self
+            .start_execution(prepared_statement_handle, &query_state_machine, span_ctx)
+            .await?;
         let output = self
-            .execute_and_fetch_result_set(prepared_statement_handle, span_recorder.span_ctx())
+            .execute_and_fetch_result_set(logical_plan, query_state_machine)
             .await?;
 
-        // clear cache of this query
-        self.result_cache
-            .invalidate(prepared_statement_handle.to_byte_slice());
-
         Ok(Response::new(output))
     }
 
@@ -875,28 +1385,17 @@ where
             ticket, request
         );
 
-        let span_recorder =
-            get_span_recorder(request.extensions(), "flight sql do_put_statement_update");
-        let span_ctx = span_recorder.span_ctx();
-        // ignore transaction_id
-        let CommandStatementUpdate { query, .. } = ticket;
-        let req_headers = request.metadata();
-
-        let (logical_plan, query_state_machine) = self
-            .pre_precess_statement_query_req(query, req_headers, span_ctx)
-            .await?;
-
-        // execute plan
-        let query_result = self
-            .execute_logical_plan(logical_plan, query_state_machine)
-            .await?;
-
-        let affected_rows = query_result.result().affected_rows().await;
-
-        Ok(affected_rows)
+        let (metadata, extensions, _) = request.into_parts();
+        self.put_statement_update(ticket, &metadata, &extensions)
+            .await
     }
 
-    /// not support
+    /// Bind the parameters to the prepared statement.
+    ///
+    /// The schema message of the parameters has been consumed by the do_put of
+    /// [`FlightSqlService`], [`FlightServiceImpl`] serves do_put with the whole stream.
+    ///
+    /// [`FlightServiceImpl`]: crate::flight_sql::flight_service::FlightServiceImpl
     async fn do_put_prepared_statement_query(
         &self,
         query: CommandPreparedStatementQuery,
@@ -907,41 +1406,35 @@ where
             query, request
         );
 
-        Err(Status::unimplemented(
-            "do_put_prepared_statement_query not implemented",
-        ))
+        let (_, extensions, data) = request.into_parts();
+        self.put_prepared_statement_query(query, &extensions, Box::pin(data))
+            .await?;
+
+        Ok(Response::new(Box::pin(futures::stream::empty())))
     }
 
-    /// Execute the query and return the number of affected rows.
+    /// Execute the prepared statement for each row of the bound parameters
+    /// and return the number of affected rows.
     /// The prepared statement can be reused afterwards.
     ///
-    /// Prepared statement is not supported,
-    /// because ad-hoc statement of flight jdbc needs to call this interface, so it is simple to implement
+    /// The schema message of the parameters has been consumed by the do_put of
+    /// [`FlightSqlService`], [`FlightServiceImpl`] serves do_put with the whole stream.
+    ///
+    /// [`FlightServiceImpl`]: crate::flight_sql::flight_service::FlightServiceImpl
     async fn do_put_prepared_statement_update(
         &self,
         query: CommandPreparedStatementUpdate,
         request: Request<Streaming<FlightData>>,
     ) -> Result<i64, Status> {
-        let prepared_statement_ident = query.prepared_statement_handle.to_byte_slice();
-        debug!(
-            "do_put_prepared_statement_update query: {:?}",
-            prepared_statement_ident
-        );
-        let span_recorder = get_span_recorder(
-            request.extensions(),
-            "flight sql do_put_prepared_statement_update",
-        );
-        let (plan, query_machine) =
-            self.get_plan_and_qsm(prepared_statement_ident, span_recorder.span_ctx().cloned())?;
-        // execute plan
-        let query_result = self.execute_logical_plan(plan, query_machine).await?;
-        let output = query_result.result();
-        Ok(output.affected_rows().await)
+        let (_, extensions, data) = request.into_parts();
+        self.put_prepared_statement_update(query, &extensions, Box::pin(data))
+            .await
     }
 
-    /// Prepared statement is not supported,
-    /// because ad-hoc statement of flight jdbc needs to call this interface,
-    /// so directly return the sql as the result.
+    /// Plan the statement and return the handle of the prepared statement,
+    /// with the schema of the result set and the schema of the parameters.
+    ///
+    /// The anonymous placeholders `?` of jdbc are rewritten to `$1, $2...`.
     async fn do_action_create_prepared_statement(
         &self,
         query: ActionCreatePreparedStatementRequest,
@@ -958,24 +1451,32 @@ where
         );
         // ignore transaction_id
         let ActionCreatePreparedStatementRequest { query: sql, .. } = query;
+        let sql = rewrite_question_mark_placeholders(&sql)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
 
-        let (result_ident, schema) = self
-            .pre_precess_statement_query_req_and_save(
-                sql,
-                request.metadata(),
-                span_recorder.span_ctx(),
-            )
+        let (logical_plan, query_state_machine) = self
+            .pre_precess_statement_query_req(sql, request.metadata(), span_recorder.span_ctx())
             .await?;
 
+        let schema = logical_plan
+            .as_ref()
+            .map(|e| e.schema())
+            .unwrap_or(Arc::new(Schema::empty()));
+        let parameter_schema = parameter_schema(logical_plan.as_ref())?;
+
+        let result_ident = self.save_statement(logical_plan, query_state_machine);
+
         let IpcMessage(dataset_schema) = utils::schema_to_ipc_message(schema.as_ref())
             .map_err(|e| status!("Schema to ipc message", e))?;
+        let IpcMessage(parameter_schema) = utils::schema_to_ipc_message(&parameter_schema)
+            .map_err(|e| status!("Schema to ipc message", e))?;
         // JDBC:
         //    - schema.getFields().isEmpty() ? StatementType.UPDATE : StatementType.SELECT;
         //    - long updateCount = statementType.equals(StatementType.UPDATE) ? preparedStatement.executeUpdate() : -1L;
         let result = ActionCreatePreparedStatementResult {
             prepared_statement_handle: result_ident.into(),
             dataset_schema,
-            ..Default::default()
+            parameter_schema,
         };
 
         Ok(result)
@@ -983,7 +1484,7 @@ where
 
     /// Close a previously created prepared statement.
     ///
-    /// Empty logic, because we not save created prepared statement.
+    /// Remove the prepared statement and its bound parameters.
     async fn do_action_close_prepared_statement(
         &self,
         query: ActionClosePreparedStatementRequest,
@@ -994,6 +1495,10 @@ where
             query, request
         );
 
+        let prepared_statement_handle = query.prepared_statement_handle.to_byte_slice();
+        self.result_cache.invalidate(prepared_statement_handle);
+        self.param_cache.invalidate(prepared_statement_handle);
+
         Ok(())
     }
 
@@ -1002,14 +1507,44 @@ where
         debug!("register_sql_info: _id: {:?}, request: {:?}", _id, _result);
     }
 
+    /// Plan the substrait plan and return the handle of the prepared statement,
+    /// with the schema of the result set.
     async fn do_action_create_prepared_substrait_plan(
         &self,
-        _query: ActionCreatePreparedSubstraitPlanRequest,
-        _request: Request<Action>,
+        query: ActionCreatePreparedSubstraitPlanRequest,
+        request: Request<Action>,
     ) -> Result<ActionCreatePreparedStatementResult, Status> {
-        Err(Status::unimplemented(
-            "Implement do_action_create_prepared_substrait_plan",
-        ))
+        debug!(
+            "do_action_create_prepared_substrait_plan: query: {:?}, request: {:?}",
+            query, request
+        );
+
+        let span_recorder = get_span_recorder(
+            request.extensions(),
+            "flight sql do_action_create_prepared_substrait_plan",
+        );
+        // ignore transaction_id
+        let ActionCreatePreparedSubstraitPlanRequest { plan, .. } = query;
+
+        let (logical_plan, query_state_machine) = self
+            .pre_precess_substrait_plan_req(plan, request.metadata(), span_recorder.span_ctx())
+            .await?;
+        let schema = logical_plan
+            .as_ref()
+            .map(|e| e.schema())
+            .unwrap_or(Arc::new(Schema::empty()));
+
+        let result_ident = self.save_statement(logical_plan, query_state_machine);
+
+        let IpcMessage(dataset_schema) = utils::schema_to_ipc_message(schema.as_ref())
+            .map_err(|e| status!("Schema to ipc message", e))?;
+        let result = ActionCreatePreparedStatementResult {
+            prepared_statement_handle: result_ident.into(),
+            dataset_schema,
+            ..Default::default()
+        };
+
+        Ok(result)
     }
 
     async fn do_action_begin_transaction(
@@ -1046,32 +1581,119 @@ where
         Err(Status::unimplemented("Implement do_action_end_savepoint"))
     }
 
+    /// Cancel the query of the [`FlightInfo`] returned by [`Self::get_flight_info_statement`]
+    /// or [`Self::get_flight_info_prepared_statement`]
     async fn do_action_cancel_query(
         &self,
-        _query: ActionCancelQueryRequest,
-        _request: Request<Action>,
+        query: ActionCancelQueryRequest,
+        request: Request<Action>,
     ) -> Result<ActionCancelQueryResult, Status> {
-        Err(Status::unimplemented("Implement do_action_cancel_query"))
+        debug!(
+            "do_action_cancel_query: query: {:?}, request: {:?}",
+            query, request
+        );
+
+        let user = self
+            .authenticator
+            .authenticate(request.metadata())
+            .await?
+            .identity();
+
+        let flight_info = FlightInfo::decode(query.info)
+            .map_err(|e| status!("Unable to decode FlightInfo", e))?;
+
+        let mut result = CancelResult::NotCancellable;
+        for endpoint in flight_info.endpoint {
+            let handle = match endpoint.ticket {
+                Some(ticket) => statement_handle_of_ticket(&ticket)?,
+                None => None,
+            };
+            let (handle, (_, query_state_machine)) = match handle.and_then(|handle| {
+                let cached = self.result_cache.get(handle.as_slice())?;
+                Some((handle, cached))
+            }) {
+                Some(cached) => cached,
+                None => continue,
+            };
+
+            let owner = query_state_machine.session.user();
+            let tenant_id = *query_state_machine.session.tenant_id();
+            if owner.desc().id() != user.desc().id()
+                && !user.desc().is_admin()
+                && !user.can_access_system(tenant_id)
+            {
+                return Err(Status::permission_denied(format!(
+                    "user {} can't cancel the query {:?}",
+                    user.desc().name(),
+                    query_state_machine.query_id
+                )));
+            }
+
+            // the statement query may be running, or will fail as soon as it's fetched,
+            // the prepared statement is executed by new queries, cancel the running ones
+            self.instance.cancel(&query_state_machine.query_id);
+            query_state_machine.cancel();
+            for execution in self.running_executions(&handle) {
+                self.instance.cancel(&execution.query_id);
+                execution.cancel();
+            }
+            result = CancelResult::Cancelled;
+        }
+
+        Ok(ActionCancelQueryResult {
+            result: result.into(),
+        })
     }
 
+    /// Execute a substrait plan and return the number of affected rows.
     async fn do_put_substrait_plan(
         &self,
-        _ticket: CommandStatementSubstraitPlan,
-        _request: Request<Streaming<FlightData>>,
+        ticket: CommandStatementSubstraitPlan,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<i64, Status> {
-        Err(Status::unimplemented(
-            "do_put_substrait_plan not implemented",
-        ))
+        debug!(
+            "do_put_substrait_plan: query: {:?}, request: {:?}",
+            ticket, request
+        );
+
+        let (metadata, extensions, _) = request.into_parts();
+        self.put_substrait_plan(ticket, &metadata, &extensions)
+            .await
     }
 
+    /// Execute a substrait plan.
+    ///
+    /// Return the address of the result set,
+    /// waiting to call [`Self::do_get_statement`] to get the result set.
     async fn get_flight_info_substrait_plan(
         &self,
-        _query: CommandStatementSubstraitPlan,
-        _request: Request<FlightDescriptor>,
+        query: CommandStatementSubstraitPlan,
+        request: Request<FlightDescriptor>,
     ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_substrait_plan not implemented",
-        ))
+        debug!(
+            "get_flight_info_substrait_plan: query: {:?}, request: {:?}",
+            query, request
+        );
+
+        let span_recorder = get_span_recorder(
+            request.extensions(),
+            "flight sql get_flight_info_substrait_plan",
+        );
+
+        // ignore transaction_id
+        let CommandStatementSubstraitPlan { plan, .. } = query;
+
+        let (logical_plan, query_state_machine) = self
+            .pre_precess_substrait_plan_req(plan, request.metadata(), span_recorder.span_ctx())
+            .await?;
+        let schema = logical_plan
+            .as_ref()
+            .map(|e| e.schema())
+            .unwrap_or(Arc::new(Schema::empty()));
+
+        let result_ident = self.save_statement(logical_plan, query_state_machine);
+
+        self.construct_statement_flight_info(result_ident, schema, request)
     }
 
     async fn get_flight_info_xdbc_type_info(
@@ -1111,19 +1733,23 @@ mod test {
     use arrow_flight::sql::{Any, CommandStatementQuery};
     use arrow_flight::utils::flight_data_to_batches;
     use arrow_flight::{FlightDescriptor, HandshakeRequest, IpcMessage};
+    use coordinator::service_mock::MockCoordinator;
     use datafusion::arrow::buffer::Buffer;
     use datafusion::arrow::datatypes::Schema;
     use datafusion::arrow::{self, ipc};
     use futures::{StreamExt, TryStreamExt};
     use http_protocol::header::AUTHORIZATION;
+    use models::auth::user::UserInfo;
     use prost::Message;
-    use spi::server::dbms::DatabaseManagerSystemMock;
+    use spi::server::dbms::{DatabaseManagerSystem, DatabaseManagerSystemMock};
+    use spi::service::protocol::{ContextBuilder, Query};
     use tonic::metadata::MetadataValue;
     use tonic::transport::{Channel, Endpoint, Server};
     use tonic::Request;
 
     use crate::flight_sql::auth_middleware::basic_call_header_authenticator::BasicCallHeaderAuthenticator;
     use crate::flight_sql::auth_middleware::generated_bearer_token_authenticator::GeneratedBearerTokenAuthenticator;
+    use crate::flight_sql::flight_service::FlightServiceImpl;
     use crate::flight_sql::flight_sql_server::FlightSqlServiceImpl;
     use crate::flight_sql::utils;
 
@@ -1135,7 +1761,11 @@ mod test {
             BasicCallHeaderAuthenticator::new(instance.clone()),
         );
 
-        let svc = FlightServiceServer::new(FlightSqlServiceImpl::new(instance, authenticator));
+        let svc = FlightServiceServer::new(FlightServiceImpl::new(FlightSqlServiceImpl::new(
+            instance,
+            Arc::new(MockCoordinator::default()),
+            authenticator,
+        )));
 
         println!("Listening on {:?}", addr);
 
@@ -1263,4 +1893,64 @@ mod test {
             };
         }
     }
+
+    #[tokio::test]
+    async fn test_prepared_statement_executions() {
+        let instance = Arc::new(DatabaseManagerSystemMock {});
+        let authenticator = GeneratedBearerTokenAuthenticator::new(
+            BasicCallHeaderAuthenticator::new(instance.clone()),
+        );
+        let server = FlightSqlServiceImpl::new(
+            instance.clone(),
+            Arc::new(MockCoordinator::default()),
+            authenticator,
+        );
+
+        let user_info = UserInfo {
+            user: "root".to_string(),
+            password: "".to_string(),
+            private_key: None,
+        };
+        let user = instance.authenticate(&user_info, None).await.unwrap();
+        let query = Query::new(ContextBuilder::new(user).build(), "select 1".to_string());
+        let statement = instance
+            .build_query_state_machine(query, None)
+            .await
+            .unwrap();
+        let handle = server.save_statement(None, statement.clone());
+
+        // every execution is a new query
+        let (first, first_guard) = server
+            .start_execution(&handle, &statement, None)
+            .await
+            .unwrap();
+        let (second, second_guard) = server
+            .start_execution(&handle, &statement, None)
+            .await
+            .unwrap();
+        assert_ne!(first.query_id, statement.query_id);
+        assert_ne!(second.query_id, statement.query_id);
+        assert_ne!(first.query_id, second.query_id);
+        assert_eq!(server.running_executions(&handle).len(), 2);
+
+        // cancelling an execution doesn't cancel the others
+        first.cancel();
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+        assert!(!statement.is_cancelled());
+        drop(first_guard);
+        let (third, third_guard) = server
+            .start_execution(&handle, &statement, None)
+            .await
+            .unwrap();
+        assert!(!third.is_cancelled());
+
+        let running = server.running_executions(&handle);
+        assert_eq!(running.len(), 2);
+        assert!(running.iter().all(|e| e.query_id != first.query_id));
+
+        drop(second_guard);
+        drop(third_guard);
+        assert!(server.running_executions(&handle).is_empty());
+    }
 }
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow_flight::sql::{Any, ProstMessageExt};
+use coordinator::service::CoordinatorRef;
+use datafusion::arrow::compute::{cast_with_options, CastOptions};
+use datafusion::arrow::datatypes::{Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use models::schema::{TskvTableSchema, TskvTableSchemaRef};
+use prost::Message;
+use tonic::Status;
+use trace::{SpanContext, SpanExt, SpanRecorder};
+
+use crate::status;
+
+/// TableExistsOption.TABLE_EXISTS_OPTION_FAIL
+pub const TABLE_EXISTS_OPTION_FAIL: i32 = 1;
+/// TableExistsOption.TABLE_EXISTS_OPTION_REPLACE
+pub const TABLE_EXISTS_OPTION_REPLACE: i32 = 3;
+
+/// Command of flight sql to ingest the record batches of do_put into a table.
+///
+/// Defined by the flight sql protocol but not provided by arrow-flight yet.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandStatementIngest {
+    #[prost(message, optional, tag = "1")]
+    pub table_definition_options: Option<TableDefinitionOptions>,
+    #[prost(string, tag = "2")]
+    pub table: String,
+    /// The database of the table, the database of the request if absent.
+    #[prost(string, optional, tag = "3")]
+    pub schema: Option<String>,
+    /// The tenant of the table, the tenant of the request if absent.
+    #[prost(string, optional, tag = "4")]
+    pub catalog: Option<String>,
+    #[prost(bool, tag = "5")]
+    pub temporary: bool,
+    #[prost(bytes = "vec", optional, tag = "6")]
+    pub transaction_id: Option<Vec<u8>>,
+    #[prost(map = "string, string", tag = "1000")]
+    pub options: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TableDefinitionOptions {
+    #[prost(int32, tag = "1")]
+    pub if_not_exist: i32,
+    #[prost(int32, tag = "2")]
+    pub if_exists: i32,
+}
+
+impl ProstMessageExt for CommandStatementIngest {
+    fn type_url() -> &'static str {
+        "type.googleapis.com/arrow.flight.protocol.sql.CommandStatementIngest"
+    }
+
+    fn as_any(&self) -> Any {
+        Any {
+            type_url: Self::type_url().to_string(),
+            value: self.encode_to_vec().into(),
+        }
+    }
+}
+
+impl CommandStatementIngest {
+    /// Tables are never created or replaced by ingestion, only appended to.
+    pub fn check_options(&self) -> Result<(), Status> {
+        if self.temporary {
+            return Err(Status::invalid_argument(
+                "Ingest into temporary table is not supported",
+            ));
+        }
+        if self.transaction_id.is_some() {
+            return Err(Status::invalid_argument("Transaction is not supported"));
+        }
+        if let Some(options) = &self.table_definition_options {
+            if matches!(
+                options.if_exists,
+                TABLE_EXISTS_OPTION_FAIL | TABLE_EXISTS_OPTION_REPLACE
+            ) {
+                return Err(Status::invalid_argument(
+                    "Ingest only supports appending to the existing table",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse the path `[tenant.][db.]table` of the flight descriptor to the tenant,
+/// the database and the table, the path may also be split into segments.
+pub fn parse_table_path(
+    path: &[String],
+) -> Result<(Option<String>, Option<String>, String), Status> {
+    let names = path
+        .iter()
+        .flat_map(|p| p.split('.'))
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    if names.iter().any(|n| n.is_empty()) {
+        return Err(invalid_table_path(path));
+    }
+
+    match names.as_slice() {
+        [table] => Ok((None, None, table.clone())),
+        [db, table] => Ok((None, Some(db.clone()), table.clone())),
+        [tenant, db, table] => Ok((Some(tenant.clone()), Some(db.clone()), table.clone())),
+        _ => Err(invalid_table_path(path)),
+    }
+}
+
+fn invalid_table_path(path: &[String]) -> Status {
+    Status::invalid_argument(format!(
+        "Invalid table path {}, expected tenant.db.table",
+        path.join(".")
+    ))
+}
+
+/// Write the record batch into the table, return the number of rows written.
+pub async fn write_record_batch(
+    coord: &CoordinatorRef,
+    table_schema: &TskvTableSchemaRef,
+    batch: RecordBatch,
+    span_ctx: Option<&SpanContext>,
+) -> Result<usize, Status> {
+    let batch = cast_to_table_schema(&batch, table_schema)?;
+    let rows = batch.num_rows();
+    let record_batch_size = batch.get_array_memory_size() as u64;
+
+    let span_recorder = SpanRecorder::new(span_ctx.child_span("write record batch"));
+    coord
+        .write_record_batch(table_schema.clone(), batch, span_recorder.span_ctx())
+        .await
+        .map_err(|e| status!("Write record batch", e))?;
+    coord
+        .metrics()
+        .sql_data_in(table_schema.tenant.as_str(), table_schema.db.as_str())
+        .inc(record_batch_size);
+
+    Ok(rows)
+}
+
+/// Cast the columns of the record batch to the types of the table columns,
+/// the record batch must have the time column, and all of its columns must exist in the table.
+/// Values that can't be cast are rejected rather than written as nulls.
+pub fn cast_to_table_schema(
+    batch: &RecordBatch,
+    table_schema: &TskvTableSchema,
+) -> Result<RecordBatch, Status> {
+    let cast_options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    let mut fields = Vec::with_capacity(batch.num_columns());
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    let mut has_time = false;
+    for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+        let column = table_schema.column(field.name()).ok_or_else(|| {
+            Status::invalid_argument(format!(
+                "Column {} not found in table {}",
+                field.name(),
+                table_schema.name
+            ))
+        })?;
+        if column.column_type.is_time() {
+            if array.null_count() > 0 {
+                return Err(Status::invalid_argument(format!(
+                    "Time column {} can't be null",
+                    column.name
+                )));
+            }
+            has_time = true;
+        }
+
+        let field = Field::from(column);
+        let array = cast_with_options(array, field.data_type(), &cast_options).map_err(|e| {
+            Status::invalid_argument(format!(
+                "Cast column {} to {}: {}",
+                column.name,
+                field.data_type(),
+                e
+            ))
+        })?;
+        fields.push(field);
+        columns.push(array);
+    }
+    if !has_time {
+        return Err(Status::invalid_argument(format!(
+            "Time column not found in the record batch of table {}",
+            table_schema.name
+        )));
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+        .map_err(|e| status!("Build record batch", e))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow_flight::sql::{Any, ProstMessageExt};
+    use datafusion::arrow::array::{Float64Array, Int64Array, StringArray};
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::arrow::record_batch::RecordBatch;
+    use models::schema::{ColumnType, TableColumn, TskvTableSchema};
+    use models::ValueType;
+    use tonic::Code;
+
+    use super::{cast_to_table_schema, parse_table_path, CommandStatementIngest};
+
+    fn table_schema() -> TskvTableSchema {
+        TskvTableSchema::new(
+            "cnosdb".into(),
+            "public".into(),
+            "air".into(),
+            vec![
+                TableColumn::new_time_column(0, TimeUnit::Nanosecond),
+                TableColumn::new_tag_column(1, "station".into()),
+                TableColumn::new(
+                    2,
+                    "temperature".into(),
+                    ColumnType::Field(ValueType::Float),
+                    Default::default(),
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_pack_command_statement_ingest() {
+        let cmd = CommandStatementIngest {
+            table: "air".to_string(),
+            schema: Some("public".to_string()),
+            ..Default::default()
+        };
+        let any = Any::pack(&cmd).unwrap();
+        assert_eq!(any.type_url, CommandStatementIngest::type_url());
+        assert_eq!(any.unpack::<CommandStatementIngest>().unwrap(), Some(cmd));
+    }
+
+    #[test]
+    fn test_parse_table_path() {
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            parse_table_path(&path(&["cnosdb.public.air"])).unwrap(),
+            (
+                Some("cnosdb".to_string()),
+                Some("public".to_string()),
+                "air".to_string()
+            )
+        );
+        assert_eq!(
+            parse_table_path(&path(&["public", "air"])).unwrap(),
+            (None, Some("public".to_string()), "air".to_string())
+        );
+        assert_eq!(
+            parse_table_path(&path(&["air"])).unwrap(),
+            (None, None, "air".to_string())
+        );
+        assert!(parse_table_path(&path(&[])).is_err());
+        assert!(parse_table_path(&path(&["public..air"])).is_err());
+        assert!(parse_table_path(&path(&["a.b.c.d"])).is_err());
+    }
+
+    #[test]
+    fn test_cast_to_table_schema() {
+        let table_schema = table_schema();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("time", DataType::Int64, false),
+            Field::new("station", DataType::Utf8, true),
+            Field::new("temperature", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(Int64Array::from(vec![10, 20])),
+            ],
+        )
+        .unwrap();
+        let batch = cast_to_table_schema(&batch, &table_schema).unwrap();
+        assert_eq!(
+            batch.schema().field(0).data_type(),
+            &DataType::Timestamp(TimeUnit::Nanosecond, None)
+        );
+        assert_eq!(
+            batch
+                .column(2)
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap(),
+            &Float64Array::from(vec![10.0, 20.0])
+        );
+
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "station",
+            DataType::Utf8,
+            true,
+        )]));
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(StringArray::from(vec!["a"]))]).unwrap();
+        assert!(cast_to_table_schema(&batch, &table_schema).is_err());
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("time", DataType::Int64, false),
+            Field::new("humidity", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1])),
+                Arc::new(Float64Array::from(vec![1.0])),
+            ],
+        )
+        .unwrap();
+        assert!(cast_to_table_schema(&batch, &table_schema).is_err());
+
+        // values that can't be cast are rejected, nulls are kept
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("time", DataType::Int64, false),
+            Field::new("temperature", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("12.5"), Some("warm")])),
+            ],
+        )
+        .unwrap();
+        let err = cast_to_table_schema(&batch, &table_schema).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("temperature"));
+
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("12.5"), None])),
+            ],
+        )
+        .unwrap();
+        let batch = cast_to_table_schema(&batch, &table_schema).unwrap();
+        assert_eq!(
+            batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap(),
+            &Float64Array::from(vec![Some(12.5), None])
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use arrow_flight::flight_service_server::FlightServiceServer;
 use config::TLSConfig;
+use coordinator::service::CoordinatorRef;
 use spi::server::dbms::DBMSRef;
 use tokio::sync::oneshot;
 use tonic::transport::{Identity, Server, ServerTlsConfig};
@@ -10,6 +11,7 @@ use trace::info;
 use trace_http::ctx::SpanContextExtractor;
 use trace_http::tower_layer::TraceLayer;
 
+use self::flight_service::FlightServiceImpl;
 use self::flight_sql_server::FlightSqlServiceImpl;
 use crate::flight_sql::auth_middleware::basic_call_header_authenticator::BasicCallHeaderAuthenticator;
 use crate::flight_sql::auth_middleware::generated_bearer_token_authenticator::GeneratedBearerTokenAuthenticator;
@@ -17,11 +19,14 @@ use crate::server::ServiceHandle;
 use crate::spi::service::Service;
 
 mod auth_middleware;
+pub mod flight_service;
 pub mod flight_sql_server;
+mod ingest;
 mod utils;
 
 pub struct FlightSqlServiceAdapter {
     dbms: DBMSRef,
+    coord: CoordinatorRef,
 
     addr: SocketAddr,
     tls_config: Option<TLSConfig>,
@@ -32,12 +37,14 @@ pub struct FlightSqlServiceAdapter {
 impl FlightSqlServiceAdapter {
     pub fn new(
         dbms: DBMSRef,
+        coord: CoordinatorRef,
         addr: SocketAddr,
         tls_config: Option<TLSConfig>,
         span_context_extractor: Arc<SpanContextExtractor>,
     ) -> Self {
         Self {
             dbms,
+            coord,
             addr,
             tls_config,
             span_context_extractor,
@@ -71,8 +78,11 @@ impl Service for FlightSqlServiceAdapter {
         let authenticator = GeneratedBearerTokenAuthenticator::new(
             BasicCallHeaderAuthenticator::new(self.dbms.clone()),
         );
-        let svc =
-            FlightServiceServer::new(FlightSqlServiceImpl::new(self.dbms.clone(), authenticator));
+        let svc = FlightServiceServer::new(FlightServiceImpl::new(FlightSqlServiceImpl::new(
+            self.dbms.clone(),
+            self.coord.clone(),
+            authenticator,
+        )));
 
         let server = server
             .layer(trace_layer)
@@ -7,9 +7,16 @@ pub enum HttpApiType {
     ApiV1OpenTsDBWrite,
     ApiV1OpenTsDBPut,
     ApiV1PromWrite,
+    ApiV2Write,
+    ApiV1OtlpWrite,
 
     ApiV1Sql,
+    ApiV1InfluxQuery,
     ApiV1PromRead,
+    ApiV1PromQuery,
+    ApiV1PromSeries,
+    ApiV1PromLabels,
+    ApiV1PromLabelValues,
 }
 
 impl Display for HttpApiType {
@@ -27,12 +34,33 @@ impl Display for HttpApiType {
             HttpApiType::ApiV1PromWrite => {
                 write!(f, "api/v1/prom/write")
             }
+            HttpApiType::ApiV2Write => {
+                write!(f, "api/v2/write")
+            }
+            HttpApiType::ApiV1OtlpWrite => {
+                write!(f, "api/v1/otlp/v1/metrics")
+            }
             HttpApiType::ApiV1Sql => {
                 write!(f, "api/v1/sql")
             }
+            HttpApiType::ApiV1InfluxQuery => {
+                write!(f, "query")
+            }
             HttpApiType::ApiV1PromRead => {
                 write!(f, "api/v1/prom/read")
             }
+            HttpApiType::ApiV1PromQuery => {
+                write!(f, "api/v1/query")
+            }
+            HttpApiType::ApiV1PromSeries => {
+                write!(f, "api/v1/series")
+            }
+            HttpApiType::ApiV1PromLabels => {
+                write!(f, "api/v1/labels")
+            }
+            HttpApiType::ApiV1PromLabelValues => {
+                write!(f, "api/v1/label/values")
+            }
         }
     }
 }
@@ -44,7 +72,14 @@ pub fn metrics_record_db(api: &HttpApiType) -> bool {
         | HttpApiType::ApiV1OpenTsDBPut
         | HttpApiType::ApiV1OpenTsDBWrite
         | HttpApiType::ApiV1PromWrite
-        | HttpApiType::ApiV1PromRead => true,
+        | HttpApiType::ApiV2Write
+        | HttpApiType::ApiV1OtlpWrite
+        | HttpApiType::ApiV1InfluxQuery
+        | HttpApiType::ApiV1PromRead
+        | HttpApiType::ApiV1PromQuery
+        | HttpApiType::ApiV1PromSeries
+        | HttpApiType::ApiV1PromLabels
+        | HttpApiType::ApiV1PromLabelValues => true,
         HttpApiType::ApiV1Sql => false,
     }
 }
@@ -1,4 +1,4 @@
-use http_protocol::header::{APPLICATION_CSV, BASIC_PREFIX};
+use http_protocol::header::{APPLICATION_CSV, BASIC_PREFIX, TOKEN_PREFIX};
 use models::auth::user::UserInfo;
 use warp::http::header::{HeaderName, HeaderValue};
 
@@ -11,6 +11,7 @@ pub struct Header {
     content_encoding: Option<String>,
     authorization: String,
     private_key: Option<String>,
+    content_type: Option<String>,
 }
 
 impl Header {
@@ -26,6 +27,7 @@ impl Header {
             content_encoding,
             authorization,
             private_key: None,
+            content_type: None,
         }
     }
 
@@ -42,9 +44,15 @@ impl Header {
             content_encoding,
             authorization,
             private_key,
+            content_type: None,
         }
     }
 
+    pub fn with_content_type(mut self, content_type: Option<String>) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
     pub fn get_accept(&self) -> &str {
         self.accept.as_deref().unwrap_or(APPLICATION_CSV)
     }
@@ -57,6 +65,10 @@ impl Header {
         self.content_encoding.as_deref()
     }
 
+    pub fn get_content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
     pub fn try_get_basic_auth(&self) -> Result<UserInfo, HttpError> {
         let private_key = self
             .private_key
@@ -79,6 +91,18 @@ impl Header {
             })
         };
 
+        // influxdb v2 clients send `Token username:password`
+        if let Some(token) = auth.strip_prefix(TOKEN_PREFIX) {
+            return match token.split_once(':') {
+                Some((user, password)) => Ok(UserInfo {
+                    user: user.to_string(),
+                    password: password.to_string(),
+                    private_key,
+                }),
+                None => get_err(),
+            };
+        }
+
         if auth.len() < BASIC_PREFIX.len() {
             return get_err();
         }
@@ -194,5 +218,13 @@ mod tests {
         let auth = base64::encode("xx");
         let header = Header::with(None, None, None, auth);
         assert!(header.try_get_basic_auth().is_err());
+
+        let header = Header::with(None, None, None, format!("{}xx:yy", TOKEN_PREFIX));
+        let user_info = header.try_get_basic_auth().unwrap();
+        assert_eq!(&user_info.user, "xx");
+        assert_eq!(&user_info.password, "yy");
+
+        let header = Header::with(None, None, None, format!("{}xx", TOKEN_PREFIX));
+        assert!(header.try_get_basic_auth().is_err());
     }
 }
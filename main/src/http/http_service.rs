@@ -11,11 +11,18 @@ use std::time::Instant;
 use chrono::Local;
 use config::TLSConfig;
 use coordinator::service::CoordinatorRef;
+use datafusion::scalar::ScalarValue;
 use fly_accept_encoding::Encoding;
 use http_protocol::encoding::EncodingExt;
-use http_protocol::header::{ACCEPT, AUTHORIZATION, PRIVATE_KEY};
-use http_protocol::parameter::{DebugParam, DumpParam, SqlParam, WriteParam};
+use http_protocol::header::{
+    ACCEPT, APPLICATION_JSON, APPLICATION_PROTOBUF, AUTHORIZATION, CONTENT_TYPE, PRIVATE_KEY,
+};
+use http_protocol::parameter::{
+    DebugParam, DumpParam, InfluxQueryParam, PreparedStatementParam, PromQueryParam,
+    PromSeriesParam, SqlParam, WriteParam, WriteV2Param,
+};
 use http_protocol::response::ErrorResponse;
+use http_protocol::status_code::OK;
 use meta::error::{MetaError, MetaResult};
 use meta::limiter::RequestLimiter;
 use meta::model::MetaRef;
@@ -24,22 +31,32 @@ use metrics::gather_metrics;
 use metrics::metric_register::MetricsRegister;
 use metrics::prom_reporter::PromReporter;
 use models::auth::privilege::{DatabasePrivilege, Privilege, TenantObjectPrivilege};
+use models::auth::user::UserInfo;
 use models::error_code::UnknownCodeWithMessage;
 use models::oid::{Identifier, Oid};
 use models::schema::{Precision, DEFAULT_CATALOG};
+use prost::Message;
 use protocol_parser::line_protocol::line_protocol_to_lines;
 use protocol_parser::open_tsdb::open_tsdb_to_lines;
+use protocol_parser::otlp::otlp_metrics_to_points;
 use protocol_parser::{DataPoint, Line};
+use protos::otlp::{
+    ExportMetricsPartialSuccess, ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use query::prom::promql;
 use query::prom::remote_server::PromRemoteSqlServer;
+use query::sql::influxql::{translate_influxql, InfluxQLStatement};
 use reqwest::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
 use snafu::ResultExt;
-use spi::query::config::StreamTriggerInterval;
+use spi::query::config::{SqlDialect, StreamTriggerInterval};
+use spi::query::execution::Output;
+use spi::query::logical_planner::Plan;
 use spi::server::dbms::DBMSRef;
-use spi::server::prom::PromRemoteServerRef;
-use spi::service::protocol::{Context, ContextBuilder, Query};
+use spi::server::prom::{PromQueryRequest, PromRemoteServerRef, PromSeriesRequest};
+use spi::service::protocol::{Context, ContextBuilder, Query, QueryHandle};
 use spi::QueryError;
 use tokio::sync::oneshot;
-use trace::{debug, error, info, SpanContext, SpanExt, SpanRecorder};
+use trace::{debug, error, info, SpanContext, SpanExt, SpanRecorder, SpanRecorderExt};
 use trace_http::ctx::{SpanContextExtractor, DEFAULT_TRACE_HEADER_NAME};
 use utils::backtrace;
 use warp::hyper::body::Bytes;
@@ -52,14 +69,35 @@ use super::header::Header;
 use super::Error as HttpError;
 use crate::http::api_type::{metrics_record_db, HttpApiType};
 use crate::http::encoding::{get_accept_encoding_from_header, get_content_encoding_from_header};
+use crate::http::influx_query::{
+    batches_to_series, Epoch, InfluxQueryResponse, Series, StatementResult,
+};
 use crate::http::metrics::HttpMetrics;
 use crate::http::response::{HttpResponse, ResponseBuilder};
 use crate::http::result_format::{get_result_format_from_header, ResultFormat};
+use crate::http::sql_param::{PrepareResponse, PreparedStatement, PreparedStatements, SqlBody};
 use crate::http::{meta_err_to_reject, QuerySnafu};
 use crate::server::ServiceHandle;
 use crate::spi::service::Service;
 use crate::{server, VERSION};
 
+/// Metadata apis of prometheus.
+enum PromMetadataApi {
+    Series,
+    Labels,
+    LabelValues(String),
+}
+
+impl PromMetadataApi {
+    fn api_type(&self) -> HttpApiType {
+        match self {
+            PromMetadataApi::Series => HttpApiType::ApiV1PromSeries,
+            PromMetadataApi::Labels => HttpApiType::ApiV1PromLabels,
+            PromMetadataApi::LabelValues(_) => HttpApiType::ApiV1PromLabelValues,
+        }
+    }
+}
+
 pub enum ServerMode {
     Store,
     Query,
@@ -95,6 +133,7 @@ pub struct HttpService {
     metrics_register: Arc<MetricsRegister>,
     http_metrics: Arc<HttpMetrics>,
     span_context_extractor: Arc<SpanContextExtractor>,
+    prepared_statements: PreparedStatements,
 }
 
 impl HttpService {
@@ -126,6 +165,7 @@ impl HttpService {
             metrics_register,
             http_metrics,
             span_context_extractor,
+            prepared_statements: PreparedStatements::default(),
         }
     }
 
@@ -140,15 +180,22 @@ impl HttpService {
             .and(header::optional::<String>(CONTENT_ENCODING.as_str()))
             .and(header::<String>(AUTHORIZATION.as_str()))
             .and(header::optional::<String>(PRIVATE_KEY))
+            .and(header::optional::<String>(CONTENT_TYPE.as_str()))
             .and_then(
-                |accept, accept_encoding, content_encoding, authorization, private_key| async move {
+                |accept,
+                 accept_encoding,
+                 content_encoding,
+                 authorization,
+                 private_key,
+                 content_type| async move {
                     let res: Result<Header, warp::Rejection> = Ok(Header::with_private_key(
                         accept,
                         accept_encoding,
                         content_encoding,
                         authorization,
                         private_key,
-                    ));
+                    )
+                    .with_content_type(content_type));
                     res
                 },
             )
@@ -206,6 +253,13 @@ impl HttpService {
         warp::any().map(move || metric.clone())
     }
 
+    fn with_prepared_statements(
+        &self,
+    ) -> impl Filter<Extract = (PreparedStatements,), Error = Infallible> + Clone {
+        let prepared_statements = self.prepared_statements.clone();
+        warp::any().map(move || prepared_statements.clone())
+    }
+
     fn with_meta(&self) -> impl Filter<Extract = (MetaRef,), Error = Infallible> + Clone {
         let meta = self.coord.meta_manager();
         warp::any().map(move || meta.clone())
@@ -216,19 +270,27 @@ impl HttpService {
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
         self.ping()
             .or(self.query())
+            .or(self.prepare_sql())
+            .or(self.execute_prepared_sql())
+            .or(self.deallocate_prepared_sql())
             .or(self.metrics())
             .or(self.print_meta())
             .or(self.meta_leader_addr())
             .or(self.debug_pprof())
             .or(self.debug_jeprof())
             .or(self.prom_remote_read())
+            .or(self.prom_query())
+            .or(self.influx_query())
+            .or(self.prom_metadata())
             .or(self.backtrace())
             .or(self.print_raft())
             .or(self.dump_ddl_sql())
             .or(self.prom_remote_write())
             .or(self.write_open_tsdb())
             .or(self.put_open_tsdb())
+            .or(self.write_otlp_metrics())
             .or(self.write_line_protocol())
+            .or(self.write_line_protocol_v2())
     }
 
     fn routes_store(
@@ -307,16 +369,16 @@ impl HttpService {
                             reject::custom(HttpError::DecodeRequest { source: e })
                         })?;
                     }
-                    let query = {
+                    let (query, params) = {
                         let mut span_recorder =
                             SpanRecorder::new(span_context.child_span("authenticate"));
 
                         // Parse req、header and param to construct query request
-                        let query = construct_query(req, &header, param, dbms.clone())
+                        let (query, params) = construct_query(req, &header, param, dbms.clone())
                             .await
                             .map_err(reject::custom)?;
 
-                        span_recorder.record(query)
+                        (span_recorder.record(query), params)
                     };
 
                     let result_fmt = get_result_format_from_header(&header)?;
@@ -345,6 +407,8 @@ impl HttpService {
                         );
                         sql_handle(
                             &query,
+                            None,
+                            &params,
                             &dbms,
                             result_fmt,
                             result_encoding,
@@ -374,6 +438,188 @@ impl HttpService {
             )
     }
 
+    fn prepare_sql(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "sql" / "prepare")
+            .and(warp::post())
+            .and(warp::body::content_length_limit(self.query_body_limit))
+            .and(warp::body::bytes())
+            .and(self.handle_header())
+            .and(warp::query::<SqlParam>())
+            .and(self.with_dbms())
+            .and(self.with_meta())
+            .and(self.with_prepared_statements())
+            .and(self.handle_span_header())
+            .and_then(
+                |req: Bytes,
+                 header: Header,
+                 param: SqlParam,
+                 dbms: DBMSRef,
+                 meta: MetaRef,
+                 prepared_statements: PreparedStatements,
+                 parent_span_ctx: Option<SpanContext>| async move {
+                    debug!(
+                        "Receive http sql prepare request, header: {:?}, param: {:?}",
+                        header, param
+                    );
+                    let mut span_recorder =
+                        SpanRecorder::new(parent_span_ctx.child_span("rest sql prepare"));
+                    let req = decode_request(req, &header)?;
+                    prepare_statement(
+                        req,
+                        &header,
+                        param,
+                        &dbms,
+                        &meta,
+                        &prepared_statements,
+                        span_recorder.span_ctx(),
+                    )
+                    .await
+                    .map(|resp| warp::reply::json(&resp))
+                    .map_err(|e| {
+                        span_recorder.error(e.to_string());
+                        trace::error!("Failed to prepare http sql request, err: {}", e);
+                        reject::custom(e)
+                    })
+                },
+            )
+    }
+
+    fn execute_prepared_sql(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "sql" / "execute")
+            .and(warp::post())
+            .and(warp::body::content_length_limit(self.query_body_limit))
+            .and(warp::body::bytes())
+            .and(self.handle_header())
+            .and(warp::query::<PreparedStatementParam>())
+            .and(self.with_dbms())
+            .and(self.with_meta())
+            .and(self.with_http_metrics())
+            .and(self.with_hostaddr())
+            .and(self.with_prepared_statements())
+            .and(self.handle_span_header())
+            .and_then(
+                |req: Bytes,
+                 header: Header,
+                 param: PreparedStatementParam,
+                 dbms: DBMSRef,
+                 meta: MetaRef,
+                 metrics: Arc<HttpMetrics>,
+                 addr: String,
+                 prepared_statements: PreparedStatements,
+                 parent_span_ctx: Option<SpanContext>| async move {
+                    let start = Instant::now();
+                    debug!(
+                        "Receive http sql execute request, header: {:?}, param: {:?}",
+                        header, param
+                    );
+                    let span_recorder =
+                        SpanRecorder::new(parent_span_ctx.child_span("rest sql execute"));
+                    let span_context = span_recorder.span_ctx();
+                    let req_len = req.len();
+                    let req = decode_request(req, &header)?;
+                    let (statement, params) = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("authenticate"));
+                        let (statement, params) = construct_prepared_query(
+                            req,
+                            &header,
+                            param,
+                            &dbms,
+                            &meta,
+                            &prepared_statements,
+                            span_recorder.span_ctx(),
+                        )
+                        .await
+                        .map_err(reject::custom)?;
+                        statement.query.record(&mut span_recorder);
+                        (statement, params)
+                    };
+                    let query = &statement.query;
+
+                    let result_fmt = get_result_format_from_header(&header)?;
+                    let result_encoding = get_accept_encoding_from_header(&header)?;
+                    http_limiter_check_query(&meta, query.context().tenant(), req_len)
+                        .await
+                        .map_err(reject::custom)?;
+
+                    let tenant = query.context().tenant();
+                    let user = query.context().user_info().desc().name();
+
+                    let result = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("sql handle"));
+                        let limiter = meta
+                            .limiter(query.context().tenant())
+                            .await
+                            .map_err(meta_err_to_reject)?;
+                        let http_data_out = metrics.http_data_out(
+                            tenant,
+                            user,
+                            None,
+                            addr.as_str(),
+                            HttpApiType::ApiV1Sql,
+                        );
+                        sql_handle(
+                            query,
+                            statement.plan.as_ref(),
+                            &params,
+                            &dbms,
+                            result_fmt,
+                            result_encoding,
+                            span_recorder.span_ctx(),
+                            limiter,
+                            http_data_out,
+                        )
+                        .await
+                        .map_err(|e| {
+                            span_recorder.error(e.to_string());
+                            trace::error!("Failed to execute prepared http sql, err: {}", e);
+                            reject::custom(e)
+                        })
+                    };
+
+                    http_record_query_metrics(
+                        &metrics,
+                        query.context(),
+                        &addr,
+                        req_len,
+                        start,
+                        HttpApiType::ApiV1Sql,
+                    );
+                    result
+                },
+            )
+    }
+
+    fn deallocate_prepared_sql(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "sql" / "deallocate")
+            .and(warp::post())
+            .and(self.handle_header())
+            .and(warp::query::<PreparedStatementParam>())
+            .and(self.with_dbms())
+            .and(self.with_prepared_statements())
+            .and_then(
+                |header: Header,
+                 param: PreparedStatementParam,
+                 dbms: DBMSRef,
+                 prepared_statements: PreparedStatements| async move {
+                    let context = construct_prepared_statement_context(&header, &param, &dbms)
+                        .await
+                        .map_err(reject::custom)?;
+                    prepared_statements
+                        .remove(&param.handle, &context)
+                        .map(|_| ResponseBuilder::ok())
+                        .map_err(reject::custom)
+                },
+            )
+    }
+
     fn write_line_protocol(
         &self,
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
@@ -459,6 +705,100 @@ impl HttpService {
             )
     }
 
+    fn write_line_protocol_v2(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v2" / "write")
+            .and(warp::post())
+            .and(warp::body::content_length_limit(self.write_body_limit))
+            .and(warp::body::bytes())
+            .and(self.handle_header())
+            .and(warp::query::<WriteV2Param>())
+            .and(self.with_dbms())
+            .and(self.with_coord())
+            .and(self.with_http_metrics())
+            .and(self.with_hostaddr())
+            .and(self.handle_span_header())
+            .and_then(
+                |req: Bytes,
+                 header: Header,
+                 param: WriteV2Param,
+                 dbms: DBMSRef,
+                 coord: CoordinatorRef,
+                 metrics: Arc<HttpMetrics>,
+                 addr: String,
+                 parent_span_ctx: Option<SpanContext>| async move {
+                    let start = Instant::now();
+                    let span_recorder = SpanRecorder::new(
+                        parent_span_ctx.child_span("rest line protocol v2 write"),
+                    );
+                    let span_context = span_recorder.span_ctx();
+
+                    let req_len = req.len();
+                    let req = decode_request(req, &header)?;
+
+                    // influxdb v2 supports second precision, which is written as milliseconds
+                    let (param, seconds) = v2_write_param(param).map_err(reject::custom)?;
+                    let ctx = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("construct write context"));
+                        let ctx = construct_write_context_and_check_privilege(
+                            header,
+                            param,
+                            dbms,
+                            coord.clone(),
+                        )
+                        .await
+                        .map_err(reject::custom)?;
+                        span_recorder.record(ctx)
+                    };
+
+                    http_limiter_check_write(&coord.meta_manager(), ctx.tenant(), req_len).await?;
+
+                    let precision = Precision::new(ctx.precision()).unwrap_or(Precision::NS);
+
+                    let write_points_lines = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("try parse req to lines"));
+                        span_recorder.set_metadata("bytes", req.len());
+                        if seconds {
+                            let default_time = Local::now().timestamp();
+                            let mut lines =
+                                try_parse_req_to_lines_with_default_time(&req, default_time)
+                                    .map_err(reject::custom)?;
+                            for line in lines.iter_mut() {
+                                line.timestamp = line.timestamp.saturating_mul(1000);
+                            }
+                            lines
+                        } else {
+                            try_parse_req_to_lines(&req).map_err(reject::custom)?
+                        }
+                    };
+
+                    let resp = coord_write_points_with_span_recorder(
+                        &coord,
+                        ctx.tenant(),
+                        ctx.database(),
+                        precision,
+                        write_points_lines,
+                        span_context,
+                    )
+                    .await;
+
+                    http_record_write_metrics(
+                        &metrics,
+                        &ctx,
+                        &addr,
+                        req_len,
+                        start,
+                        HttpApiType::ApiV2Write,
+                    );
+                    resp.map(|_| ResponseBuilder::no_content())
+                        .map_err(reject::custom)
+                },
+            )
+    }
+
     fn write_open_tsdb(
         &self,
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
@@ -633,6 +973,107 @@ impl HttpService {
             )
     }
 
+    /// OTLP/HTTP metrics receiver, exporters use `/api/v1/otlp` as the endpoint.
+    fn write_otlp_metrics(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "otlp" / "v1" / "metrics")
+            .and(warp::post())
+            .and(warp::body::content_length_limit(self.write_body_limit))
+            .and(warp::body::bytes())
+            .and(self.handle_header())
+            .and(warp::query::<WriteParam>())
+            .and(self.with_dbms())
+            .and(self.with_coord())
+            .and(self.with_http_metrics())
+            .and(self.with_hostaddr())
+            .and(self.handle_span_header())
+            .and_then(
+                |req: Bytes,
+                 header: Header,
+                 param: WriteParam,
+                 dbms: DBMSRef,
+                 coord: CoordinatorRef,
+                 metrics: Arc<HttpMetrics>,
+                 addr: String,
+                 parent_span_ctx: Option<SpanContext>| async move {
+                    let start = Instant::now();
+                    let span_recorder =
+                        SpanRecorder::new(parent_span_ctx.child_span("rest otlp metrics write"));
+                    let span_context = span_recorder.span_ctx();
+
+                    let req_len = req.len();
+                    let req = decode_request(req, &header)?;
+
+                    let ctx = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("construct write context"));
+                        let ctx = construct_write_context_and_check_privilege(
+                            header,
+                            param,
+                            dbms,
+                            coord.clone(),
+                        )
+                        .await
+                        .map_err(reject::custom)?;
+                        span_recorder.record(ctx)
+                    };
+
+                    http_limiter_check_write(&coord.meta_manager(), ctx.tenant(), req_len)
+                        .await
+                        .map_err(reject::custom)?;
+
+                    let otlp_metrics = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("decode otlp metrics"));
+                        span_recorder.set_metadata("bytes", req.len());
+                        let export_req =
+                            ExportMetricsServiceRequest::decode(req).map_err(|source| {
+                                reject::custom(HttpError::ParseOtlpProtocol { source })
+                            })?;
+                        otlp_metrics_to_points(&export_req)
+                    };
+
+                    let lines = otlp_metrics.lines();
+                    let resp = if lines.is_empty() {
+                        Ok(0)
+                    } else {
+                        coord_write_points_with_span_recorder(
+                            &coord,
+                            ctx.tenant(),
+                            ctx.database(),
+                            Precision::NS,
+                            lines,
+                            span_context,
+                        )
+                        .await
+                    };
+
+                    http_record_write_metrics(
+                        &metrics,
+                        &ctx,
+                        &addr,
+                        req_len,
+                        start,
+                        HttpApiType::ApiV1OtlpWrite,
+                    );
+
+                    let partial_success =
+                        (otlp_metrics.rejected > 0).then(|| ExportMetricsPartialSuccess {
+                            rejected_data_points: otlp_metrics.rejected,
+                            error_message: "data points without value are rejected".to_string(),
+                        });
+                    let body = ExportMetricsServiceResponse { partial_success }.encode_to_vec();
+                    resp.map(|_| {
+                        ResponseBuilder::new(OK)
+                            .insert_header((CONTENT_TYPE, APPLICATION_PROTOBUF))
+                            .build(body)
+                    })
+                    .map_err(reject::custom)
+                },
+            )
+    }
+
     fn meta_leader_addr(
         &self,
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
@@ -705,48 +1146,378 @@ impl HttpService {
         })
     }
 
-    fn debug_jeprof(
+    fn debug_jeprof(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("debug" / "jeprof").and_then(|| async move {
+            #[cfg(unix)]
+            {
+                let res = utils::pprof_tools::gernate_jeprof().await;
+                info!("debug jeprof: {:?}", res);
+                match res {
+                    Ok(v) => Ok(v),
+                    Err(e) => Err(reject::custom(HttpError::PProf { reason: e })),
+                }
+            }
+            #[cfg(not(unix))]
+            {
+                Err::<String, _>(reject::not_found())
+            }
+        })
+    }
+
+    fn metrics(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("metrics")
+            .and(self.with_metrics_register())
+            .map(|register: Arc<MetricsRegister>| {
+                let mut buffer = gather_metrics();
+                let mut prom_reporter = PromReporter::new(&mut buffer);
+                register.report(&mut prom_reporter);
+                Response::new(Body::from(buffer))
+            })
+    }
+
+    fn prom_remote_read(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "prom" / "read")
+            .and(warp::post())
+            .and(warp::body::content_length_limit(self.query_body_limit))
+            .and(warp::body::bytes())
+            .and(self.handle_header())
+            .and(warp::query::<SqlParam>())
+            .and(self.with_dbms())
+            .and(self.with_meta())
+            .and(self.with_http_metrics())
+            .and(self.with_prom_remote_server())
+            .and(self.with_hostaddr())
+            .and(self.handle_span_header())
+            .and_then(
+                |req: Bytes,
+                 header: Header,
+                 param: SqlParam,
+                 dbms: DBMSRef,
+                 meta: MetaRef,
+                 metrics: Arc<HttpMetrics>,
+                 prs: PromRemoteServerRef,
+                 addr: String,
+                 parent_span_ctx: Option<SpanContext>| async move {
+                    let start = Instant::now();
+                    debug!(
+                        "Receive rest prom remote read request, header: {:?}, param: {:?}",
+                        header, param
+                    );
+                    let span_recorder =
+                        SpanRecorder::new(parent_span_ctx.child_span("rest prom remote read"));
+                    let span_context = span_recorder.span_ctx();
+
+                    // Parse req、header and param to construct query request
+                    let context = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("construct context"));
+                        span_recorder.set_metadata("bytes", req.len());
+                        let ctx = construct_read_context(&header, param, dbms)
+                            .await
+                            .map_err(reject::custom)?;
+                        span_recorder.record(ctx)
+                    };
+                    let req_len = req.len();
+
+                    http_limiter_check_query(&meta, context.tenant(), req_len)
+                        .await
+                        .map_err(reject::custom)?;
+
+                    let tenant_name = context.tenant();
+                    let username = context.user_info().desc().name();
+                    let database_name = context.database();
+
+                    let http_query_data_out = metrics.http_data_out(
+                        tenant_name,
+                        username,
+                        Some(database_name),
+                        addr.as_str(),
+                        HttpApiType::ApiV1PromRead,
+                    );
+
+                    let result = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("remote read"));
+                        prs.remote_read(&context, req, span_recorder.span_ctx())
+                            .await
+                            .map_err(|e| {
+                                span_recorder.error(e.to_string());
+                                trace::error!(
+                                    "Failed to handle prom remote read request, err: {}",
+                                    e
+                                );
+                                reject::custom(HttpError::from(e))
+                            })
+                            .map(|b| {
+                                http_query_data_out.inc(b.len() as u64);
+                                b
+                            })
+                    };
+
+                    http_record_query_metrics(
+                        &metrics,
+                        &context,
+                        &addr,
+                        req_len,
+                        start,
+                        HttpApiType::ApiV1PromRead,
+                    );
+                    result
+                },
+            )
+    }
+
+    fn prom_query(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let is_range = warp::path!("api" / "v1" / "query")
+            .map(|| false)
+            .or(warp::path!("api" / "v1" / "query_range").map(|| true))
+            .unify();
+        // parameters of a post request are in the form body, and maybe in the url
+        let param = warp::get()
+            .and(warp::query::<PromQueryParam>())
+            .or(warp::post()
+                .and(warp::query::<PromQueryParam>())
+                .and(warp::body::content_length_limit(self.query_body_limit))
+                .and(warp::body::form::<PromQueryParam>())
+                .map(merge_prom_query_param))
+            .unify();
+
+        is_range
+            .and(param)
+            .and(self.handle_header())
+            .and(self.with_dbms())
+            .and(self.with_meta())
+            .and(self.with_http_metrics())
+            .and(self.with_prom_remote_server())
+            .and(self.with_hostaddr())
+            .and(self.handle_span_header())
+            .and_then(
+                |is_range: bool,
+                 param: PromQueryParam,
+                 header: Header,
+                 dbms: DBMSRef,
+                 meta: MetaRef,
+                 metrics: Arc<HttpMetrics>,
+                 prs: PromRemoteServerRef,
+                 addr: String,
+                 parent_span_ctx: Option<SpanContext>| async move {
+                    let start = Instant::now();
+                    debug!(
+                        "Receive rest prom query request, header: {:?}, param: {:?}",
+                        header, param
+                    );
+                    let span_recorder =
+                        SpanRecorder::new(parent_span_ctx.child_span("rest prom query"));
+                    let span_context = span_recorder.span_ctx();
+
+                    let sql_param = SqlParam {
+                        tenant: param.tenant.clone(),
+                        db: param.db.clone(),
+                        chunked: None,
+                        target_partitions: None,
+                        stream_trigger_interval: None,
+                        params: None,
+                        dialect: None,
+                    };
+                    let context = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("construct context"));
+                        let ctx = construct_read_context(&header, sql_param, dbms)
+                            .await
+                            .map_err(reject::custom)?;
+                        span_recorder.record(ctx)
+                    };
+                    let req = construct_prom_query_request(param, is_range)
+                        .map_err(|e| reject::custom(HttpError::from(e)))?;
+                    let req_len = req.query.len();
+
+                    http_limiter_check_query(&meta, context.tenant(), req_len)
+                        .await
+                        .map_err(reject::custom)?;
+
+                    let result = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("prom query"));
+                        prs.query(&context, req, span_recorder.span_ctx())
+                            .await
+                            .map(|data| {
+                                warp::reply::json(&serde_json::json!({
+                                    "status": "success",
+                                    "data": data,
+                                }))
+                            })
+                            .map_err(|e| {
+                                span_recorder.error(e.to_string());
+                                trace::error!("Failed to handle prom query request, err: {}", e);
+                                reject::custom(HttpError::from(e))
+                            })
+                    };
+
+                    http_record_query_metrics(
+                        &metrics,
+                        &context,
+                        &addr,
+                        req_len,
+                        start,
+                        HttpApiType::ApiV1PromQuery,
+                    );
+                    result
+                },
+            )
+    }
+
+    /// The query api of influxdb v1, used by grafana and other clients of influxdb.
+    fn influx_query(
         &self,
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-        warp::path!("debug" / "jeprof").and_then(|| async move {
-            #[cfg(unix)]
-            {
-                let res = utils::pprof_tools::gernate_jeprof().await;
-                info!("debug jeprof: {:?}", res);
-                match res {
-                    Ok(v) => Ok(v),
-                    Err(e) => Err(reject::custom(HttpError::PProf { reason: e })),
-                }
-            }
-            #[cfg(not(unix))]
-            {
-                Err::<String, _>(reject::not_found())
-            }
-        })
-    }
+        // parameters of a post request are in the form body, and maybe in the url
+        let param = warp::get()
+            .and(warp::query::<InfluxQueryParam>())
+            .or(warp::post()
+                .and(warp::query::<InfluxQueryParam>())
+                .and(warp::body::content_length_limit(self.query_body_limit))
+                .and(warp::body::form::<InfluxQueryParam>())
+                .map(merge_influx_query_param))
+            .unify();
+        // the user and password may be in the parameters instead of the authorization header
+        let header = self
+            .handle_header()
+            .map(Some)
+            .or(warp::any().map(|| None::<Header>))
+            .unify();
+
+        warp::path!("query")
+            .and(param)
+            .and(header)
+            .and(self.with_dbms())
+            .and(self.with_meta())
+            .and(self.with_http_metrics())
+            .and(self.with_hostaddr())
+            .and(self.handle_span_header())
+            .and_then(
+                |param: InfluxQueryParam,
+                 header: Option<Header>,
+                 dbms: DBMSRef,
+                 meta: MetaRef,
+                 metrics: Arc<HttpMetrics>,
+                 addr: String,
+                 parent_span_ctx: Option<SpanContext>| async move {
+                    let start = Instant::now();
+                    // the parameters may have the password
+                    debug!(
+                        "Receive rest influx query request, db: {:?}, q: {:?}",
+                        param.db, param.q
+                    );
+                    let span_recorder =
+                        SpanRecorder::new(parent_span_ctx.child_span("rest influx query"));
+                    let span_context = span_recorder.span_ctx();
 
-    fn metrics(
-        &self,
-    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-        warp::path!("metrics")
-            .and(self.with_metrics_register())
-            .map(|register: Arc<MetricsRegister>| {
-                let mut buffer = gather_metrics();
-                let mut prom_reporter = PromReporter::new(&mut buffer);
-                register.report(&mut prom_reporter);
-                Response::new(Body::from(buffer))
-            })
+                    let epoch = param
+                        .epoch
+                        .as_deref()
+                        .map(str::parse::<Epoch>)
+                        .transpose()
+                        .map_err(reject::custom)?;
+                    let influxql = param.q.clone().ok_or_else(|| {
+                        reject::custom(HttpError::InvalidHeader {
+                            reason: "missing parameter \"q\"".to_string(),
+                        })
+                    })?;
+                    let statements = translate_influxql(&influxql)
+                        .map_err(|e| reject::custom(HttpError::from(QueryError::from(e))))?;
+                    let context = {
+                        let mut span_recorder =
+                            SpanRecorder::new(span_context.child_span("construct context"));
+                        let ctx =
+                            construct_influx_query_context(header.as_ref(), param, dbms.clone())
+                                .await
+                                .map_err(reject::custom)?;
+                        span_recorder.record(ctx)
+                    };
+                    let req_len = influxql.len();
+
+                    http_limiter_check_query(&meta, context.tenant(), req_len)
+                        .await
+                        .map_err(reject::custom)?;
+
+                    let response = {
+                        let span_recorder =
+                            SpanRecorder::new(span_context.child_span("influx query"));
+                        execute_influxql_statements(
+                            &context,
+                            &statements,
+                            epoch,
+                            &dbms,
+                            span_recorder.span_ctx(),
+                        )
+                        .await
+                    };
+                    let body = serde_json::to_vec(&response).map_err(|e| {
+                        reject::custom(HttpError::FetchResult {
+                            reason: e.to_string(),
+                        })
+                    })?;
+
+                    metrics
+                        .http_data_out(
+                            context.tenant(),
+                            context.user_info().desc().name(),
+                            None,
+                            addr.as_str(),
+                            HttpApiType::ApiV1InfluxQuery,
+                        )
+                        .inc(body.len() as u64);
+                    http_record_query_metrics(
+                        &metrics,
+                        &context,
+                        &addr,
+                        req_len,
+                        start,
+                        HttpApiType::ApiV1InfluxQuery,
+                    );
+                    Ok::<_, Rejection>(
+                        ResponseBuilder::new(OK)
+                            .insert_header((CONTENT_TYPE, APPLICATION_JSON))
+                            .build(body),
+                    )
+                },
+            )
     }
 
-    fn prom_remote_read(
+    fn prom_metadata(
         &self,
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-        warp::path!("api" / "v1" / "prom" / "read")
-            .and(warp::post())
-            .and(warp::body::content_length_limit(self.query_body_limit))
-            .and(warp::body::bytes())
+        let api = warp::path!("api" / "v1" / "series")
+            .map(|| PromMetadataApi::Series)
+            .or(warp::path!("api" / "v1" / "labels").map(|| PromMetadataApi::Labels))
+            .unify()
+            .or(warp::path!("api" / "v1" / "label" / String / "values")
+                .map(PromMetadataApi::LabelValues))
+            .unify();
+        // parameters of a post request are in the form body, and maybe in the url
+        let param = warp::get()
+            .and(warp::query::<Vec<(String, String)>>())
+            .map(PromSeriesParam::from_pairs)
+            .or(warp::post()
+                .and(warp::query::<Vec<(String, String)>>())
+                .and(warp::body::content_length_limit(self.query_body_limit))
+                .and(warp::body::form::<Vec<(String, String)>>())
+                .map(|mut url: Vec<(String, String)>, form| {
+                    url.extend(form);
+                    PromSeriesParam::from_pairs(url)
+                }))
+            .unify();
+
+        api.and(param)
             .and(self.handle_header())
-            .and(warp::query::<SqlParam>())
             .and(self.with_dbms())
             .and(self.with_meta())
             .and(self.with_http_metrics())
@@ -754,9 +1525,9 @@ impl HttpService {
             .and(self.with_hostaddr())
             .and(self.handle_span_header())
             .and_then(
-                |req: Bytes,
+                |api: PromMetadataApi,
+                 param: PromSeriesParam,
                  header: Header,
-                 param: SqlParam,
                  dbms: DBMSRef,
                  meta: MetaRef,
                  metrics: Arc<HttpMetrics>,
@@ -765,68 +1536,73 @@ impl HttpService {
                  parent_span_ctx: Option<SpanContext>| async move {
                     let start = Instant::now();
                     debug!(
-                        "Receive rest prom remote read request, header: {:?}, param: {:?}",
-                        header, param
+                        "Receive rest prom {} request, header: {:?}, param: {:?}",
+                        api.api_type(),
+                        header,
+                        param
+                    );
+                    let span_recorder = SpanRecorder::new(
+                        parent_span_ctx.child_span(format!("rest prom {}", api.api_type())),
                     );
-                    let span_recorder =
-                        SpanRecorder::new(parent_span_ctx.child_span("rest prom remote read"));
                     let span_context = span_recorder.span_ctx();
 
-                    // Parse req、header and param to construct query request
+                    let sql_param = SqlParam {
+                        tenant: param.tenant.clone(),
+                        db: param.db.clone(),
+                        chunked: None,
+                        target_partitions: None,
+                        stream_trigger_interval: None,
+                        params: None,
+                        dialect: None,
+                    };
                     let context = {
                         let mut span_recorder =
                             SpanRecorder::new(span_context.child_span("construct context"));
-                        span_recorder.set_metadata("bytes", req.len());
-                        let ctx = construct_read_context(&header, param, dbms)
+                        let ctx = construct_read_context(&header, sql_param, dbms)
                             .await
                             .map_err(reject::custom)?;
                         span_recorder.record(ctx)
                     };
-                    let req_len = req.len();
+                    let req = construct_prom_series_request(param)
+                        .map_err(|e| reject::custom(HttpError::from(e)))?;
+                    let req_len = req.matchers.iter().map(|m| m.len()).sum();
 
                     http_limiter_check_query(&meta, context.tenant(), req_len)
                         .await
                         .map_err(reject::custom)?;
 
-                    let tenant_name = context.tenant();
-                    let username = context.user_info().desc().name();
-                    let database_name = context.database();
-
-                    let http_query_data_out = metrics.http_data_out(
-                        tenant_name,
-                        username,
-                        Some(database_name),
-                        addr.as_str(),
-                        HttpApiType::ApiV1PromRead,
-                    );
-
+                    let api_type = api.api_type();
                     let result = {
                         let mut span_recorder =
-                            SpanRecorder::new(span_context.child_span("remote read"));
-                        prs.remote_read(&context, req, span_recorder.span_ctx())
-                            .await
-                            .map_err(|e| {
-                                span_recorder.error(e.to_string());
-                                trace::error!(
-                                    "Failed to handle prom remote read request, err: {}",
-                                    e
-                                );
-                                reject::custom(HttpError::from(e))
-                            })
-                            .map(|b| {
-                                http_query_data_out.inc(b.len() as u64);
-                                b
-                            })
+                            SpanRecorder::new(span_context.child_span(api_type.to_string()));
+                        let data = match &api {
+                            PromMetadataApi::Series => prs
+                                .series(&context, req, span_recorder.span_ctx())
+                                .await
+                                .map(|data| serde_json::json!(data)),
+                            PromMetadataApi::Labels => prs
+                                .label_names(&context, req, span_recorder.span_ctx())
+                                .await
+                                .map(|data| serde_json::json!(data)),
+                            PromMetadataApi::LabelValues(name) => prs
+                                .label_values(&context, name, req, span_recorder.span_ctx())
+                                .await
+                                .map(|data| serde_json::json!(data)),
+                        };
+                        data.map(|data| {
+                            warp::reply::json(&serde_json::json!({
+                                "status": "success",
+                                "data": data,
+                            }))
+                        })
+                        .map_err(|e| {
+                            span_recorder.error(e.to_string());
+                            trace::error!("Failed to handle prom {} request, err: {}", api_type, e);
+                            reject::custom(HttpError::from(e))
+                        })
                     };
 
-                    http_record_query_metrics(
-                        &metrics,
-                        &context,
-                        &addr,
-                        req_len,
-                        start,
-                        HttpApiType::ApiV1PromRead,
-                    );
+                    http_record_query_metrics(&metrics, &context, &addr, req_len, start, api_type);
                     result
                 },
             )
@@ -1055,24 +1831,278 @@ impl Service for HttpService {
 async fn construct_query(
     req: Bytes,
     header: &Header,
-    param: SqlParam,
+    mut param: SqlParam,
     dbms: DBMSRef,
-) -> Result<Query, HttpError> {
+) -> Result<(Query, Vec<ScalarValue>), HttpError> {
+    let mut body = SqlBody::parse(&req, header)?;
+    let param_values = body.param_values(param.params.take().as_deref())?;
     let context = construct_read_context(header, param, dbms).await?;
+    let query = Query::new(context, body.sql);
+
+    // the sql is planned as is without parameters
+    if param_values.is_empty() {
+        return Ok((query, vec![]));
+    }
+    let (query, param_names) = PreparedStatements::rewrite_query(query)?;
+    let params = param_values.into_positional(&param_names)?;
+
+    Ok((query, params))
+}
+
+async fn prepare_statement(
+    req: Bytes,
+    header: &Header,
+    param: SqlParam,
+    dbms: &DBMSRef,
+    meta: &MetaRef,
+    prepared_statements: &PreparedStatements,
+    span_ctx: Option<&SpanContext>,
+) -> Result<PrepareResponse, HttpError> {
+    let body = SqlBody::parse(&req, header)?;
+    let context = construct_read_context(header, param, dbms.clone()).await?;
+    let (query, param_names) = PreparedStatements::rewrite_query(Query::new(context, body.sql))?;
+
+    let statement = plan_prepared_statement(query, param_names, dbms, meta, span_ctx).await?;
+    let parameters = statement.parameters()?;
+    let handle = prepared_statements.insert(statement);
 
-    Ok(Query::new(
-        context,
-        String::from_utf8_lossy(req.as_ref()).to_string(),
+    Ok(PrepareResponse { handle, parameters })
+}
+
+/// Plan the statement, the privileges of the user of the query are checked by the planner.
+async fn plan_prepared_statement(
+    query: Query,
+    param_names: Vec<String>,
+    dbms: &DBMSRef,
+    meta: &MetaRef,
+    span_ctx: Option<&SpanContext>,
+) -> Result<PreparedStatement, HttpError> {
+    // read the version before planning, a change during planning makes the plan outdated
+    let meta_version = tenant_meta_version(meta, query.context().tenant()).await;
+    let query_state_machine = dbms
+        .build_query_state_machine(query.clone(), span_ctx)
+        .await?;
+    let plan = dbms.build_logical_plan(query_state_machine).await?;
+
+    Ok(PreparedStatement::new(
+        query,
+        plan,
+        param_names,
+        meta_version,
     ))
 }
 
+async fn construct_prepared_statement_context(
+    header: &Header,
+    param: &PreparedStatementParam,
+    dbms: &DBMSRef,
+) -> Result<Context, HttpError> {
+    let user_info = header.try_get_basic_auth()?;
+    let tenant = param.tenant.clone();
+    let user = dbms
+        .authenticate(&user_info, tenant.as_deref())
+        .await
+        .context(QuerySnafu)?;
+
+    Ok(ContextBuilder::new(user).with_tenant(tenant).build())
+}
+
+async fn tenant_meta_version(meta: &MetaRef, tenant: &str) -> u64 {
+    match meta.tenant_meta(tenant).await {
+        Some(client) => client.version().await,
+        None => 0,
+    }
+}
+
+/// Get the prepared statement and the values of its placeholders,
+/// the statement is planned again if its plan is outdated.
+async fn construct_prepared_query(
+    req: Bytes,
+    header: &Header,
+    param: PreparedStatementParam,
+    dbms: &DBMSRef,
+    meta: &MetaRef,
+    prepared_statements: &PreparedStatements,
+    span_ctx: Option<&SpanContext>,
+) -> Result<(Arc<PreparedStatement>, Vec<ScalarValue>), HttpError> {
+    let mut body = SqlBody::parse(&req, header)?;
+    let param_values = body.param_values(param.params.as_deref())?;
+    let context = construct_prepared_statement_context(header, &param, dbms).await?;
+
+    let mut statement = prepared_statements.get(&param.handle, &context)?;
+    let meta_version = tenant_meta_version(meta, context.tenant()).await;
+    if !statement.is_plan_valid(&context, meta_version) {
+        let query = Query::new(context, statement.query.content().to_string());
+        let planned =
+            plan_prepared_statement(query, statement.param_names.clone(), dbms, meta, span_ctx)
+                .await?;
+        statement = prepared_statements.update(&param.handle, planned);
+    }
+    let params = param_values.into_positional(&statement.param_names)?;
+
+    Ok((statement, params))
+}
+
+fn decode_request(req: Bytes, header: &Header) -> Result<Bytes, Rejection> {
+    match get_content_encoding_from_header(header)? {
+        Some(encoding) => encoding.decode(req).map_err(|e| {
+            trace::error!("Failed to decode request, err: {}", e);
+            reject::custom(HttpError::DecodeRequest { source: e })
+        }),
+        None => Ok(req),
+    }
+}
+
+fn merge_prom_query_param(url: PromQueryParam, form: PromQueryParam) -> PromQueryParam {
+    PromQueryParam {
+        tenant: form.tenant.or(url.tenant),
+        db: form.db.or(url.db),
+        query: form.query.or(url.query),
+        time: form.time.or(url.time),
+        start: form.start.or(url.start),
+        end: form.end.or(url.end),
+        step: form.step.or(url.step),
+    }
+}
+
+fn merge_influx_query_param(url: InfluxQueryParam, form: InfluxQueryParam) -> InfluxQueryParam {
+    InfluxQueryParam {
+        tenant: form.tenant.or(url.tenant),
+        db: form.db.or(url.db),
+        q: form.q.or(url.q),
+        epoch: form.epoch.or(url.epoch),
+        u: form.u.or(url.u),
+        p: form.p.or(url.p),
+    }
+}
+
+/// The user of the parameters `u` and `p`, or else the one of the authorization header.
+async fn construct_influx_query_context(
+    header: Option<&Header>,
+    param: InfluxQueryParam,
+    dbms: DBMSRef,
+) -> Result<Context, HttpError> {
+    let user_info = match (param.u, param.p, header) {
+        (Some(user), password, _) => UserInfo {
+            user,
+            password: password.unwrap_or_default(),
+            private_key: None,
+        },
+        (None, _, Some(header)) => header.try_get_basic_auth()?,
+        (None, _, None) => {
+            return Err(HttpError::ParseAuth {
+                reason: "missing authorization header or parameter u".to_string(),
+            })
+        }
+    };
+    let sql_param = SqlParam {
+        tenant: param.tenant,
+        db: param.db,
+        chunked: None,
+        target_partitions: None,
+        stream_trigger_interval: None,
+        params: None,
+        dialect: None,
+    };
+
+    construct_read_context_with_user_info(user_info, sql_param, dbms).await
+}
+
+/// Execute the statements one by one, the ones after a failed statement are not executed.
+async fn execute_influxql_statements(
+    context: &Context,
+    statements: &[InfluxQLStatement],
+    epoch: Option<Epoch>,
+    dbms: &DBMSRef,
+    span_ctx: Option<&SpanContext>,
+) -> InfluxQueryResponse {
+    let mut results = vec![];
+    for (statement_id, statement) in statements.iter().enumerate() {
+        let query = Query::new(context.clone(), statement.sql.clone());
+        match execute_influxql_statement(&query, statement, epoch, dbms, span_ctx).await {
+            Ok(series) => results.push(StatementResult::series(statement_id, series)),
+            Err(e) => {
+                trace::error!("Failed to execute influxql statement, err: {}", e);
+                results.push(StatementResult::error(statement_id, e.to_string()));
+                break;
+            }
+        }
+    }
+
+    InfluxQueryResponse { results }
+}
+
+async fn execute_influxql_statement(
+    query: &Query,
+    statement: &InfluxQLStatement,
+    epoch: Option<Epoch>,
+    dbms: &DBMSRef,
+    span_ctx: Option<&SpanContext>,
+) -> Result<Vec<Series>, HttpError> {
+    let output = dbms.execute(query, span_ctx).await?.result();
+    let schema = output.schema();
+    let batches = output.chunk_result().await?;
+
+    batches_to_series(statement, schema, &batches, epoch).map_err(|e| HttpError::FetchResult {
+        reason: e.to_string(),
+    })
+}
+
+fn construct_prom_query_request(
+    param: PromQueryParam,
+    is_range: bool,
+) -> Result<PromQueryRequest, QueryError> {
+    let missing = |name: &str| QueryError::InvalidPromQL {
+        reason: format!("missing parameter \"{name}\""),
+    };
+    let query = param.query.ok_or_else(|| missing("query"))?;
+
+    if !is_range {
+        let time = match param.time {
+            Some(time) => promql::parse_time(&time)?,
+            None => Local::now().timestamp_nanos(),
+        };
+        return Ok(PromQueryRequest {
+            query,
+            start: time,
+            end: time,
+            step: None,
+        });
+    }
+
+    let start = promql::parse_time(&param.start.ok_or_else(|| missing("start"))?)?;
+    let end = promql::parse_time(&param.end.ok_or_else(|| missing("end"))?)?;
+    let step = promql::parse_step(&param.step.ok_or_else(|| missing("step"))?)?;
+    Ok(PromQueryRequest {
+        query,
+        start,
+        end,
+        step: Some(step),
+    })
+}
+
+fn construct_prom_series_request(param: PromSeriesParam) -> Result<PromSeriesRequest, QueryError> {
+    Ok(PromSeriesRequest {
+        matchers: param.matchers,
+        start: param.start.as_deref().map(promql::parse_time).transpose()?,
+        end: param.end.as_deref().map(promql::parse_time).transpose()?,
+    })
+}
+
 async fn construct_read_context(
     header: &Header,
     param: SqlParam,
     dbms: DBMSRef,
 ) -> Result<Context, HttpError> {
     let user_info = header.try_get_basic_auth()?;
+    construct_read_context_with_user_info(user_info, param, dbms).await
+}
 
+async fn construct_read_context_with_user_info(
+    user_info: UserInfo,
+    param: SqlParam,
+    dbms: DBMSRef,
+) -> Result<Context, HttpError> {
     let tenant = param.tenant;
     let user = dbms
         .authenticate(&user_info, tenant.as_deref())
@@ -1093,6 +2123,15 @@ async fn construct_read_context(
                 })
                 .transpose()?,
         )
+        .with_dialect(
+            param
+                .dialect
+                .map(|ref e| {
+                    e.parse::<SqlDialect>()
+                        .map_err(|reason| HttpError::InvalidHeader { reason })
+                })
+                .transpose()?,
+        )
         .build();
 
     Ok(context)
@@ -1162,13 +2201,49 @@ async fn construct_write_context_and_check_privilege(
 }
 
 fn try_parse_req_to_lines(req: &Bytes) -> Result<Vec<Line>, HttpError> {
+    try_parse_req_to_lines_with_default_time(req, Local::now().timestamp_nanos())
+}
+
+fn try_parse_req_to_lines_with_default_time(
+    req: &Bytes,
+    default_time: i64,
+) -> Result<Vec<Line>, HttpError> {
     let lines = unsafe { std::str::from_utf8_unchecked(req.as_ref()) };
-    let line_protocol_lines = line_protocol_to_lines(lines, Local::now().timestamp_nanos())
+    let line_protocol_lines = line_protocol_to_lines(lines, default_time)
         .map_err(|e| HttpError::ParseLineProtocol { source: e })?;
 
     Ok(line_protocol_lines)
 }
 
+/// Convert the parameters of influxdb v2 write api, returns whether the precision is second.
+fn v2_write_param(param: WriteV2Param) -> Result<(WriteParam, bool), HttpError> {
+    let (precision, seconds) = match param.precision.as_deref().unwrap_or("ns") {
+        "ns" => ("ns", false),
+        "us" => ("us", false),
+        "ms" => ("ms", false),
+        "s" => ("ms", true),
+        other => {
+            return Err(HttpError::InvalidHeader {
+                reason: format!("precision not support: {}", other),
+            })
+        }
+    };
+    // the retention policy in `database/retention_policy` is ignored
+    let db = match param.bucket.split_once('/') {
+        Some((db, _)) => db.to_string(),
+        None => param.bucket,
+    };
+
+    Ok((
+        WriteParam {
+            precision: Some(precision.to_string()),
+            tenant: param.org,
+            db: Some(db),
+        },
+        seconds,
+    ))
+}
+
 fn construct_write_tsdb_points_request(req: &Bytes) -> Result<Vec<Line>, HttpError> {
     let lines = unsafe { std::str::from_utf8_unchecked(req.as_ref()) };
 
@@ -1221,8 +2296,42 @@ async fn coord_write_points_with_span_recorder(
         })
 }
 
+/// Execute the query, or the plan of a prepared statement, with the values of its placeholders.
+async fn execute_query(
+    query: &Query,
+    plan: Option<&Plan>,
+    params: &[ScalarValue],
+    dbms: &DBMSRef,
+    span_ctx: Option<&SpanContext>,
+) -> Result<QueryHandle, QueryError> {
+    if plan.is_none() && params.is_empty() {
+        return dbms.execute(query, span_ctx).await;
+    }
+
+    let query_state_machine = dbms
+        .build_query_state_machine(query.clone(), span_ctx)
+        .await?;
+    let plan = match plan {
+        Some(plan) => Some(plan.clone()),
+        None => dbms.build_logical_plan(query_state_machine.clone()).await?,
+    };
+    match plan {
+        Some(plan) => {
+            let plan = plan.with_param_values(params.to_vec())?;
+            dbms.execute_logical_plan(plan, query_state_machine).await
+        }
+        None => Ok(QueryHandle::new(
+            query_state_machine.query_id,
+            query_state_machine.query.clone(),
+            Output::Nil(()),
+        )),
+    }
+}
+
 async fn sql_handle(
     query: &Query,
+    plan: Option<&Plan>,
+    params: &[ScalarValue],
     dbms: &DBMSRef,
     fmt: ResultFormat,
     encoding: Option<Encoding>,
@@ -1233,7 +2342,7 @@ async fn sql_handle(
     // debug!("prepare to execute: {:?}", query.content());
     let handle = {
         let mut execute_span_recorder = SpanRecorder::new(span_ctx.child_span("execute"));
-        dbms.execute(query, execute_span_recorder.span_ctx())
+        execute_query(query, plan, params, dbms, execute_span_recorder.span_ctx())
             .await
             .map_err(|err| {
                 execute_span_recorder.error(err.to_string());
@@ -1252,7 +2361,8 @@ async fn sql_handle(
     );
 
     let _response_span_recorder = SpanRecorder::new(span_ctx.child_span("build response"));
-    if !query.context().chunked() {
+    // binary formats are always streamed batch by batch
+    if !query.context().chunked() && !fmt.is_binary() {
         let result = resp.wrap_batches_to_response().await;
         if let Err(err) = &result {
             if tskv::Error::vnode_broken_code(err.error_code().code()) {
@@ -1260,7 +2370,7 @@ async fn sql_handle(
                 let handle = {
                     let mut execute_span_recorder =
                         SpanRecorder::new(span_ctx.child_span("retry execute"));
-                    dbms.execute(query, execute_span_recorder.span_ctx())
+                    execute_query(query, plan, params, dbms, execute_span_recorder.span_ctx())
                         .await
                         .map_err(|err| {
                             execute_span_recorder.error(err.to_string());
@@ -1382,8 +2492,43 @@ async fn handle_rejection(err: Rejection) -> Result<impl Reply, std::convert::In
 /**************** bottom *****************/
 #[cfg(test)]
 mod test {
+    use http_protocol::parameter::WriteV2Param;
     use tokio::time;
 
+    use super::v2_write_param;
+
+    #[test]
+    fn test_v2_write_param() {
+        let (param, seconds) = v2_write_param(WriteV2Param {
+            org: Some("cnosdb".to_string()),
+            bucket: "public/autogen".to_string(),
+            precision: Some("s".to_string()),
+        })
+        .unwrap();
+        assert!(seconds);
+        assert_eq!(param.tenant.as_deref(), Some("cnosdb"));
+        assert_eq!(param.db.as_deref(), Some("public"));
+        assert_eq!(param.precision.as_deref(), Some("ms"));
+
+        let (param, seconds) = v2_write_param(WriteV2Param {
+            org: None,
+            bucket: "public".to_string(),
+            precision: None,
+        })
+        .unwrap();
+        assert!(!seconds);
+        assert_eq!(param.tenant, None);
+        assert_eq!(param.db.as_deref(), Some("public"));
+        assert_eq!(param.precision.as_deref(), Some("ns"));
+
+        assert!(v2_write_param(WriteV2Param {
+            org: None,
+            bucket: "public".to_string(),
+            precision: Some("h".to_string()),
+        })
+        .is_err());
+    }
+
     #[tokio::test]
     async fn test1() {
         // use futures_util::future::TryFutureExt;
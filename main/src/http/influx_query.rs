@@ -0,0 +1,272 @@
+//! Result of the InfluxDB v1 compatible query api.
+//!
+//! Each statement has a result of series, rows are split into series by the values of
+//! the tags of `GROUP BY`, the tags are not columns of the series. Timestamps are rfc3339
+//! strings, or integers in the unit of the `epoch` parameter.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use chrono::{SecondsFormat, TimeZone, Utc};
+use datafusion::arrow::array::{Array, Int64Array};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{DataType, SchemaRef, TimeUnit};
+use datafusion::arrow::error::{ArrowError, Result as ArrowResult};
+use datafusion::arrow::json::ArrayWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+use query::sql::influxql::InfluxQLStatement;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::Error as HttpError;
+
+const TIME: &str = "time";
+
+/// Unit of the returned timestamps, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Epoch(i64);
+
+impl FromStr for Epoch {
+    type Err = HttpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nanos = match s {
+            "ns" | "n" => 1,
+            "u" | "µ" | "us" => 1_000,
+            "ms" => 1_000_000,
+            "s" => 1_000_000_000,
+            "m" => 60_000_000_000,
+            "h" => 3_600_000_000_000,
+            other => {
+                return Err(HttpError::InvalidHeader {
+                    reason: format!("epoch not support: {}", other),
+                })
+            }
+        };
+        Ok(Self(nanos))
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct InfluxQueryResponse {
+    pub results: Vec<StatementResult>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct StatementResult {
+    pub statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub series: Vec<Series>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl StatementResult {
+    pub fn series(statement_id: usize, series: Vec<Series>) -> Self {
+        Self {
+            statement_id,
+            series,
+            error: None,
+        }
+    }
+
+    pub fn error(statement_id: usize, error: String) -> Self {
+        Self {
+            statement_id,
+            series: vec![],
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Series {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    pub columns: Vec<String>,
+    pub values: Vec<Vec<Value>>,
+}
+
+/// Split the result of the statement into series, the rows of a series are adjacent
+/// since the translated sql is ordered by the tags.
+pub fn batches_to_series(
+    statement: &InfluxQLStatement,
+    schema: SchemaRef,
+    batches: &[RecordBatch],
+    epoch: Option<Epoch>,
+) -> ArrowResult<Vec<Series>> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .filter(|name| !statement.tags.contains(name))
+        .collect::<Vec<_>>();
+    let times = times(&schema, batches)?;
+
+    let mut series: Vec<Series> = vec![];
+    for (i, mut row) in json_rows(batches)?.into_iter().enumerate() {
+        if let Some(times) = &times {
+            let time = times[i].map(|t| format_time(t, epoch)).unwrap_or_default();
+            row.insert(TIME.to_string(), time);
+        }
+        let tags = statement
+            .tags
+            .iter()
+            .map(|tag| {
+                let value = match row.remove(tag) {
+                    None | Some(Value::Null) => String::new(),
+                    Some(Value::String(s)) => s,
+                    Some(other) => other.to_string(),
+                };
+                (tag.clone(), value)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let values = columns
+            .iter()
+            .map(|c| row.remove(c).unwrap_or(Value::Null))
+            .collect();
+
+        match series.last_mut() {
+            Some(last) if last.tags == tags => last.values.push(values),
+            _ => series.push(Series {
+                name: statement.name.clone(),
+                tags,
+                columns: columns.clone(),
+                values: vec![values],
+            }),
+        }
+    }
+
+    Ok(series)
+}
+
+fn json_rows(batches: &[RecordBatch]) -> ArrowResult<Vec<Map<String, Value>>> {
+    if batches.iter().all(|b| b.num_rows() == 0) {
+        return Ok(vec![]);
+    }
+    let mut bytes = vec![];
+    {
+        let mut writer = ArrayWriter::new(&mut bytes);
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+    }
+    serde_json::from_slice(&bytes).map_err(|e| ArrowError::JsonError(e.to_string()))
+}
+
+/// Nanoseconds of the time column of all rows, if there is a time column.
+fn times(schema: &SchemaRef, batches: &[RecordBatch]) -> ArrowResult<Option<Vec<Option<i64>>>> {
+    let index = match schema.index_of(TIME) {
+        Ok(index) if matches!(schema.field(index).data_type(), DataType::Timestamp(..)) => index,
+        _ => return Ok(None),
+    };
+
+    let mut times = vec![];
+    for batch in batches {
+        let array = cast(
+            batch.column(index),
+            &DataType::Timestamp(TimeUnit::Nanosecond, None),
+        )?;
+        let array = cast(&array, &DataType::Int64)?;
+        let array = array
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| ArrowError::CastError("time is not a timestamp".to_string()))?;
+        times.extend(array.iter());
+    }
+    Ok(Some(times))
+}
+
+fn format_time(nanos: i64, epoch: Option<Epoch>) -> Value {
+    match epoch {
+        Some(Epoch(unit)) => Value::from(nanos / unit),
+        None => Value::from(
+            Utc.timestamp_nanos(nanos)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use datafusion::arrow::array::{Float64Array, StringArray, TimestampNanosecondArray};
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::arrow::record_batch::RecordBatch;
+    use query::sql::influxql::InfluxQLStatement;
+    use serde_json::json;
+
+    use super::{batches_to_series, Epoch, Series};
+
+    #[test]
+    fn test_batches_to_series() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
+            Field::new("host", DataType::Utf8, true),
+            Field::new("v", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(TimestampNanosecondArray::from(vec![0, 1_000_000_000, 0])),
+                Arc::new(StringArray::from(vec![Some("a"), Some("a"), None])),
+                Arc::new(Float64Array::from(vec![Some(1.5), None, Some(3.0)])),
+            ],
+        )
+        .unwrap();
+        let statement = InfluxQLStatement {
+            sql: String::new(),
+            name: Some("m".to_string()),
+            tags: vec!["host".to_string()],
+        };
+
+        let series = batches_to_series(&statement, schema.clone(), &[batch.clone()], None).unwrap();
+        assert_eq!(
+            series,
+            vec![
+                Series {
+                    name: Some("m".to_string()),
+                    tags: BTreeMap::from([("host".to_string(), "a".to_string())]),
+                    columns: vec!["time".to_string(), "v".to_string()],
+                    values: vec![
+                        vec![json!("1970-01-01T00:00:00Z"), json!(1.5)],
+                        vec![json!("1970-01-01T00:00:01Z"), json!(null)],
+                    ],
+                },
+                Series {
+                    name: Some("m".to_string()),
+                    tags: BTreeMap::from([("host".to_string(), "".to_string())]),
+                    columns: vec!["time".to_string(), "v".to_string()],
+                    values: vec![vec![json!("1970-01-01T00:00:00Z"), json!(3.0)]],
+                },
+            ]
+        );
+
+        let statement = InfluxQLStatement {
+            tags: vec![],
+            ..statement
+        };
+        let epoch = "ms".parse::<Epoch>().unwrap();
+        let series = batches_to_series(&statement, schema.clone(), &[batch], Some(epoch)).unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].columns, vec!["time", "host", "v"]);
+        assert_eq!(
+            series[0].values[1],
+            vec![json!(1000), json!("a"), json!(null)]
+        );
+
+        assert!(batches_to_series(&statement, schema, &[], None)
+            .unwrap()
+            .is_empty());
+        assert!("d".parse::<Epoch>().is_err());
+    }
+}
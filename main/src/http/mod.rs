@@ -14,9 +14,11 @@ mod api_type;
 mod encoding;
 pub mod header;
 pub mod http_service;
+mod influx_query;
 mod metrics;
 mod response;
 mod result_format;
+mod sql_param;
 
 #[derive(Debug, Snafu, ErrorCoder)]
 #[error_code(mod_code = "04")]
@@ -103,6 +105,24 @@ pub enum Error {
     EncodeResponse {
         source: std::io::Error,
     },
+
+    #[snafu(display("Invalid sql parameters: {}", reason))]
+    #[error_code(code = 15)]
+    InvalidSqlParam {
+        reason: String,
+    },
+
+    #[snafu(display("Prepared statement {} does not exist or has expired", handle))]
+    #[error_code(code = 16)]
+    PreparedStatementNotFound {
+        handle: String,
+    },
+
+    #[snafu(display("Error parsing otlp message: {}", source))]
+    #[error_code(code = 17)]
+    ParseOtlpProtocol {
+        source: prost::DecodeError,
+    },
 }
 
 impl From<tskv::Error> for Error {
@@ -166,6 +186,7 @@ impl From<&Error> for Response {
             | Error::Coordinator { .. }
             | Error::Meta { .. }
             | Error::NotFoundTenant { .. }
+            | Error::PreparedStatementNotFound { .. }
             | Error::EncodeResponse { .. } => {
                 ResponseBuilder::new(UNPROCESSABLE_ENTITY).json(&error_resp)
             }
@@ -174,7 +195,9 @@ impl From<&Error> for Response {
             | Error::TraceHttp { .. }
             | Error::DecodeRequest { .. }
             | Error::ParseOpentsdbProtocol { .. }
-            | Error::ParseOpentsdbJsonProtocol { .. } => ResponseBuilder::bad_request(&error_resp),
+            | Error::ParseOpentsdbJsonProtocol { .. }
+            | Error::ParseOtlpProtocol { .. }
+            | Error::InvalidSqlParam { .. } => ResponseBuilder::bad_request(&error_resp),
             _ => ResponseBuilder::internal_server_error(),
         }
     }
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use std::task::Poll;
 
 use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::error::ArrowError;
 use datafusion::arrow::record_batch::RecordBatch;
 use fly_accept_encoding::Encoding;
 use futures::future::BoxFuture;
@@ -12,7 +13,8 @@ use http_protocol::encoding::EncodingExt;
 use http_protocol::header::{APPLICATION_JSON, CONTENT_TYPE};
 use http_protocol::response::ErrorResponse;
 use http_protocol::status_code::{
-    BAD_REQUEST, INTERNAL_SERVER_ERROR, METHOD_NOT_ALLOWED, NOT_FOUND, OK, PAYLOAD_TOO_LARGE,
+    BAD_REQUEST, INTERNAL_SERVER_ERROR, METHOD_NOT_ALLOWED, NOT_FOUND, NO_CONTENT, OK,
+    PAYLOAD_TOO_LARGE,
 };
 use meta::limiter::RequestLimiter;
 use metrics::count::U64Counter;
@@ -26,7 +28,7 @@ use warp::reply::Response;
 use warp::{hyper, Reply};
 
 use super::header::IntoHeaderPair;
-use super::result_format::ResultFormat;
+use super::result_format::{BinaryResultWriter, ResultFormat};
 use super::Error as HttpError;
 
 #[derive(Default)]
@@ -92,6 +94,10 @@ impl ResponseBuilder {
         OK.into_response()
     }
 
+    pub fn no_content() -> Response {
+        NO_CONTENT.into_response()
+    }
+
     pub fn bad_request<T>(error_info: &T) -> Response
     where
         T: Serialize,
@@ -128,6 +134,7 @@ pub struct HttpResponse {
     format: ResultFormat,
     encoding: Option<Encoding>,
     schema: Option<SchemaRef>,
+    binary_writer: Option<BinaryResultWriter>,
     http_query_data_out: U64Counter,
     limiter: Arc<dyn RequestLimiter>,
     stream_state: HttpResponseStreamState,
@@ -147,6 +154,7 @@ impl HttpResponse {
             format,
             encoding,
             schema: Some(schema),
+            binary_writer: None,
             limiter,
             stream_state: HttpResponseStreamState::PollNext,
             http_query_data_out,
@@ -154,8 +162,10 @@ impl HttpResponse {
     }
 
     pub async fn wrap_batches_to_response(self) -> Result<Response, HttpError> {
+        let schema = self.result.schema();
         let actual = self.result.chunk_result().await?;
         self.format.wrap_batches_to_response(
+            schema,
             &actual,
             true,
             self.http_query_data_out.clone(),
@@ -176,17 +186,21 @@ impl HttpResponse {
         &mut self,
         opt_result_batch: Option<Result<RecordBatch, QueryError>>,
     ) -> Result<HttpResponseStreamState, HttpError> {
+        if self.format.is_binary() {
+            return self.handle_opt_binary_record_batch(opt_result_batch);
+        }
+
         match opt_result_batch {
             None => {
                 if let Some(schema) = self.schema.take() {
                     let has_headers = !schema.fields().is_empty();
-                    let rb = RecordBatch::new_empty(schema);
-                    let mut buffer =
-                        self.format
-                            .format_batches(&[rb], has_headers)
-                            .map_err(|e| HttpError::FetchResult {
-                                reason: format!("{}", e),
-                            })?;
+                    let rb = RecordBatch::new_empty(schema.clone());
+                    let mut buffer = self
+                        .format
+                        .format_batches(schema, &[rb], has_headers)
+                        .map_err(|e| HttpError::FetchResult {
+                            reason: format!("{}", e),
+                        })?;
                     if let Some(encoding) = self.encoding {
                         buffer = encoding
                             .encode(buffer)
@@ -215,7 +229,7 @@ impl HttpResponse {
                 if rb.num_rows() > 0 {
                     let mut buffer = self
                         .format
-                        .format_batches(&[rb], self.schema.is_some())
+                        .format_batches(rb.schema(), &[rb], self.schema.is_some())
                         .map_err(|e| HttpError::FetchResult {
                             reason: format!("{}", e),
                         })?;
@@ -254,6 +268,66 @@ impl HttpResponse {
     }
 }
 
+impl HttpResponse {
+    /// Binary formats write the schema only once, so batches are written by
+    /// one writer for the whole result instead of being formatted one by one.
+    fn handle_opt_binary_record_batch(
+        &mut self,
+        opt_result_batch: Option<Result<RecordBatch, QueryError>>,
+    ) -> Result<HttpResponseStreamState, HttpError> {
+        let fetch_result_error = |e: ArrowError| HttpError::FetchResult {
+            reason: format!("{}", e),
+        };
+        let mut writer = match self.binary_writer.take() {
+            Some(writer) => writer,
+            None => BinaryResultWriter::try_new(&self.format, self.result.schema())
+                .map_err(fetch_result_error)?,
+        };
+        let (buffer, is_finish) = match opt_result_batch {
+            None => (writer.finish().map_err(fetch_result_error)?, true),
+            Some(Ok(rb)) => {
+                let buffer = if rb.num_rows() > 0 {
+                    writer.write(&rb).map_err(fetch_result_error)?
+                } else {
+                    vec![]
+                };
+                self.binary_writer = Some(writer);
+                (buffer, false)
+            }
+            Some(Err(e)) => {
+                return Err(HttpError::FetchResult {
+                    reason: e.to_string(),
+                })
+            }
+        };
+        if buffer.is_empty() && !is_finish {
+            return Ok(HttpResponseStreamState::PollNext);
+        }
+
+        let mut buffer = buffer;
+        if let Some(encoding) = self.encoding.as_ref() {
+            buffer = encoding
+                .encode(buffer)
+                .map_err(|e| HttpError::EncodeResponse { source: e })?;
+        }
+        self.http_query_data_out.inc(buffer.len() as u64);
+
+        let limiter = self.limiter.clone();
+        let buffer_len = buffer.len();
+        let future = async move {
+            limiter
+                .check_http_data_out(buffer_len)
+                .await
+                .map_err(HttpError::from)
+        };
+        Ok(HttpResponseStreamState::CheckLimiter(
+            Box::pin(future),
+            buffer,
+            is_finish,
+        ))
+    }
+}
+
 impl Stream for HttpResponse {
     type Item = Result<Vec<u8>, HttpError>;
 
@@ -1,18 +1,27 @@
+use std::io::{self, Write};
+use std::mem;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use datafusion::arrow::csv::writer::WriterBuilder;
-use datafusion::arrow::error::Result as ArrowResult;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::error::{ArrowError, Result as ArrowResult};
+use datafusion::arrow::ipc::writer::StreamWriter;
 use datafusion::arrow::json::{ArrayWriter, LineDelimitedWriter};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::arrow::util::pretty::pretty_format_batches;
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::parquet::errors::ParquetError;
 use fly_accept_encoding::Encoding;
 use http_protocol::encoding::EncodingExt;
 use http_protocol::header::{
-    APPLICATION_CSV, APPLICATION_JSON, APPLICATION_NDJSON, APPLICATION_PREFIX, APPLICATION_STAR,
-    APPLICATION_TABLE, APPLICATION_TSV, CONTENT_TYPE, STAR_STAR,
+    APPLICATION_ARROW_STREAM, APPLICATION_CSV, APPLICATION_JSON, APPLICATION_NDJSON,
+    APPLICATION_PARQUET, APPLICATION_PREFIX, APPLICATION_STAR, APPLICATION_TABLE, APPLICATION_TSV,
+    CONTENT_TYPE, STAR_STAR,
 };
 use http_protocol::status_code::OK;
 use metrics::count::U64Counter;
+use parking_lot::Mutex;
 use reqwest::header::CONTENT_ENCODING;
 use warp::reply::Response;
 use warp::{reject, Rejection};
@@ -53,6 +62,20 @@ fn batches_with_sep(
     Ok(bytes)
 }
 
+fn batches_with_binary_writer(
+    schema: SchemaRef,
+    batches: &[RecordBatch],
+    format: &ResultFormat,
+) -> ArrowResult<Vec<u8>> {
+    let mut writer = BinaryResultWriter::try_new(format, schema)?;
+    let mut bytes = vec![];
+    for batch in batches {
+        bytes.append(&mut writer.write(batch)?);
+    }
+    bytes.append(&mut writer.finish()?);
+    Ok(bytes)
+}
+
 /// Allow records to be printed in different formats
 #[derive(Debug, PartialEq, Eq, clap::ValueEnum, Clone)]
 pub enum ResultFormat {
@@ -61,6 +84,11 @@ pub enum ResultFormat {
     Json,
     NdJson,
     Table,
+    /// Arrow IPC streaming format
+    #[value(name = "vnd.apache.arrow.stream", alias = "arrow")]
+    ArrowStream,
+    #[value(name = "vnd.apache.parquet", alias = "parquet")]
+    Parquet,
 }
 
 impl ResultFormat {
@@ -71,18 +99,27 @@ impl ResultFormat {
             Self::Json => APPLICATION_JSON,
             Self::NdJson => APPLICATION_NDJSON,
             Self::Table => APPLICATION_TABLE,
+            Self::ArrowStream => APPLICATION_ARROW_STREAM,
+            Self::Parquet => APPLICATION_PARQUET,
         }
     }
 
+    /// Binary formats can not be formatted batch by batch independently,
+    /// they are written by a [`BinaryResultWriter`].
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::ArrowStream | Self::Parquet)
+    }
+
+    /// Binary formats write the `schema` even if there are no batches.
     pub fn format_batches(
         &self,
+        schema: SchemaRef,
         batches: &[RecordBatch],
         has_headers: bool,
     ) -> ArrowResult<Vec<u8>> {
-        if batches.is_empty() {
-            return Ok(Vec::new());
-        }
         match self {
+            Self::ArrowStream | Self::Parquet => batches_with_binary_writer(schema, batches, self),
+            _ if batches.is_empty() => Ok(Vec::new()),
             Self::Csv => batches_with_sep(batches, b',', has_headers),
             Self::Tsv => batches_with_sep(batches, b'\t', has_headers),
             Self::Json => batches_to_json!(ArrayWriter, batches),
@@ -95,16 +132,17 @@ impl ResultFormat {
 
     pub fn wrap_batches_to_response(
         &self,
+        schema: SchemaRef,
         batches: &[RecordBatch],
         has_headers: bool,
         http_query_data_out: U64Counter,
         result_encoding: Option<Encoding>,
     ) -> Result<Response, HttpError> {
-        let mut result =
-            self.format_batches(batches, has_headers)
-                .map_err(|e| HttpError::FetchResult {
-                    reason: format!("{}", e),
-                })?;
+        let mut result = self
+            .format_batches(schema, batches, has_headers)
+            .map_err(|e| HttpError::FetchResult {
+                reason: format!("{}", e),
+            })?;
 
         let mut builder =
             ResponseBuilder::new(OK).insert_header((CONTENT_TYPE, self.get_http_content_type()));
@@ -122,6 +160,86 @@ impl ResultFormat {
     }
 }
 
+/// A buffer that can be drained while the writer owning it is still alive.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        mem::take(&mut *self.0.lock())
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+enum BinaryWriter {
+    ArrowStream(StreamWriter<SharedBuffer>),
+    Parquet(ArrowWriter<SharedBuffer>),
+}
+
+/// Writes the record batches of a result in a binary format one by one,
+/// the bytes returned by each call can be sent to the client immediately.
+pub struct BinaryResultWriter {
+    buffer: SharedBuffer,
+    writer: BinaryWriter,
+}
+
+impl BinaryResultWriter {
+    pub fn try_new(format: &ResultFormat, schema: SchemaRef) -> ArrowResult<Self> {
+        let buffer = SharedBuffer::default();
+        let writer = match format {
+            ResultFormat::ArrowStream => {
+                BinaryWriter::ArrowStream(StreamWriter::try_new(buffer.clone(), &schema)?)
+            }
+            ResultFormat::Parquet => BinaryWriter::Parquet(
+                ArrowWriter::try_new(buffer.clone(), schema, None).map_err(parquet_error)?,
+            ),
+            _ => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "{:?} is not a binary result format",
+                    format
+                )))
+            }
+        };
+        Ok(Self { buffer, writer })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> ArrowResult<Vec<u8>> {
+        match &mut self.writer {
+            BinaryWriter::ArrowStream(writer) => writer.write(batch)?,
+            BinaryWriter::Parquet(writer) => {
+                writer.write(batch).map_err(parquet_error)?;
+                // close the row group so the batch can be sent
+                writer.flush().map_err(parquet_error)?;
+            }
+        }
+        Ok(self.buffer.take())
+    }
+
+    pub fn finish(self) -> ArrowResult<Vec<u8>> {
+        match self.writer {
+            BinaryWriter::ArrowStream(mut writer) => writer.finish()?,
+            BinaryWriter::Parquet(writer) => {
+                writer.close().map_err(parquet_error)?;
+            }
+        }
+        Ok(self.buffer.take())
+    }
+}
+
+fn parquet_error(e: ParquetError) -> ArrowError {
+    ArrowError::ExternalError(Box::new(e))
+}
+
 impl TryFrom<&str> for ResultFormat {
     type Error = HttpError;
 
@@ -232,4 +350,87 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_binary_result_format_from_accept() {
+        assert_eq!(
+            ResultFormat::try_from(APPLICATION_ARROW_STREAM).unwrap(),
+            ResultFormat::ArrowStream
+        );
+        assert_eq!(
+            ResultFormat::try_from(APPLICATION_PARQUET).unwrap(),
+            ResultFormat::Parquet
+        );
+        assert_eq!(
+            ResultFormat::try_from("application/arrow").unwrap(),
+            ResultFormat::ArrowStream
+        );
+        assert!(ResultFormat::Parquet.is_binary());
+        assert!(!ResultFormat::Csv.is_binary());
+    }
+
+    #[test]
+    fn test_binary_result_writer() {
+        use datafusion::arrow::ipc::reader::StreamReader;
+        use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let batches = vec![
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![1, 2])),
+                    Arc::new(Int32Array::from(vec![3, 4])),
+                ],
+            )
+            .unwrap(),
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![5])),
+                    Arc::new(Int32Array::from(vec![6])),
+                ],
+            )
+            .unwrap(),
+        ];
+
+        // every batch is sent as soon as it is written
+        let mut writer =
+            BinaryResultWriter::try_new(&ResultFormat::ArrowStream, schema.clone()).unwrap();
+        let mut bytes = vec![];
+        for batch in batches.iter() {
+            let mut buf = writer.write(batch).unwrap();
+            assert!(!buf.is_empty());
+            bytes.append(&mut buf);
+        }
+        bytes.append(&mut writer.finish().unwrap());
+        let reader = StreamReader::try_new(bytes.as_slice(), None).unwrap();
+        let actual = reader.collect::<ArrowResult<Vec<_>>>().unwrap();
+        assert_eq!(actual, batches);
+
+        let bytes = ResultFormat::Parquet
+            .format_batches(schema.clone(), &batches, true)
+            .unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes))
+            .unwrap()
+            .build()
+            .unwrap();
+        let actual = reader.collect::<ArrowResult<Vec<_>>>().unwrap();
+        assert_eq!(actual, batches);
+
+        // an empty result still carries the schema
+        let bytes = ResultFormat::ArrowStream
+            .format_batches(schema.clone(), &[], true)
+            .unwrap();
+        let reader = StreamReader::try_new(bytes.as_slice(), None).unwrap();
+        assert_eq!(reader.schema(), schema);
+        assert_eq!(reader.count(), 0);
+        assert!(ResultFormat::Csv
+            .format_batches(schema, &[], true)
+            .unwrap()
+            .is_empty());
+    }
 }
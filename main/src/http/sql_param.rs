@@ -0,0 +1,374 @@
+//! Parameters and prepared statements of the http sql api.
+//!
+//! Values of placeholders are passed as json, a json array binds `$1, $2...`
+//! and a json object binds `$name`. A value is either a plain json value whose
+//! type follows the json type, or `{"type": "...", "value": ...}` with an explicit type.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use datafusion::arrow::datatypes::{DataType, TimeUnit};
+use datafusion::scalar::ScalarValue;
+use http_protocol::header::APPLICATION_JSON;
+use models::oid::UuidGenerator;
+use moka::sync::Cache;
+use query::sql::param::{rewrite_named_placeholders, ParamValues};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use spi::query::logical_planner::Plan;
+use spi::service::protocol::{Context, Query};
+use warp::hyper::body::Bytes;
+
+use super::header::Header;
+use super::Error as HttpError;
+
+/// Json body of the sql api, used when the content type is `application/json`.
+#[derive(Debug, Default, Deserialize)]
+pub struct SqlBody {
+    #[serde(default)]
+    pub sql: String,
+    pub params: Option<Value>,
+}
+
+impl SqlBody {
+    /// Parse the request body, a non-json body is the sql itself.
+    pub fn parse(req: &Bytes, header: &Header) -> Result<Self, HttpError> {
+        let is_json = header
+            .get_content_type()
+            .map(|t| t.starts_with(APPLICATION_JSON))
+            .unwrap_or(false);
+        if !is_json {
+            return Ok(Self {
+                sql: String::from_utf8_lossy(req.as_ref()).to_string(),
+                params: None,
+            });
+        }
+        if req.is_empty() {
+            return Ok(Self::default());
+        }
+
+        serde_json::from_slice(req.as_ref()).map_err(|e| HttpError::InvalidSqlParam {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Values of placeholders in the body, or else the ones in the url query.
+    pub fn param_values(&mut self, query_params: Option<&str>) -> Result<ParamValues, HttpError> {
+        let value = match (self.params.take(), query_params) {
+            (Some(value), _) => value,
+            (None, Some(params)) => {
+                serde_json::from_str(params).map_err(|e| HttpError::InvalidSqlParam {
+                    reason: e.to_string(),
+                })?
+            }
+            (None, None) => return Ok(ParamValues::default()),
+        };
+
+        parse_param_values(value)
+    }
+}
+
+pub fn parse_param_values(value: Value) -> Result<ParamValues, HttpError> {
+    match value {
+        Value::Null => Ok(ParamValues::default()),
+        Value::Array(values) => Ok(ParamValues::Positional(
+            values
+                .into_iter()
+                .map(json_to_scalar)
+                .collect::<Result<_, _>>()?,
+        )),
+        Value::Object(values) => Ok(ParamValues::Named(
+            values
+                .into_iter()
+                .map(|(name, value)| {
+                    let name = name.strip_prefix('$').unwrap_or(&name).to_string();
+                    Ok((name, json_to_scalar(value)?))
+                })
+                .collect::<Result<_, HttpError>>()?,
+        )),
+        other => Err(HttpError::InvalidSqlParam {
+            reason: format!("expect a json array or object, but found {other}"),
+        }),
+    }
+}
+
+fn json_to_scalar(value: Value) -> Result<ScalarValue, HttpError> {
+    match value {
+        Value::Null => Ok(ScalarValue::Null),
+        Value::Bool(b) => Ok(ScalarValue::Boolean(Some(b))),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Ok(ScalarValue::Int64(Some(i)))
+            } else if let Some(u) = n.as_u64() {
+                Ok(ScalarValue::UInt64(Some(u)))
+            } else {
+                Ok(ScalarValue::Float64(n.as_f64()))
+            }
+        }
+        Value::String(s) => Ok(ScalarValue::Utf8(Some(s))),
+        Value::Object(typed) => typed_json_to_scalar(typed),
+        Value::Array(_) => Err(HttpError::InvalidSqlParam {
+            reason: "array is not supported as a parameter value".to_string(),
+        }),
+    }
+}
+
+/// Convert `{"type": "...", "value": ...}` to the value of the type.
+fn typed_json_to_scalar(mut typed: Map<String, Value>) -> Result<ScalarValue, HttpError> {
+    let data_type = match typed.get("type") {
+        Some(Value::String(t)) => parse_data_type(t)?,
+        _ => {
+            return Err(HttpError::InvalidSqlParam {
+                reason: "expect a string field 'type' in typed parameter".to_string(),
+            })
+        }
+    };
+    let value = match typed.remove("value") {
+        None | Some(Value::Null) => return ScalarValue::try_from(&data_type).map_err(invalid),
+        Some(Value::String(s)) => s,
+        Some(v @ (Value::Bool(_) | Value::Number(_))) => v.to_string(),
+        Some(v) => {
+            return Err(HttpError::InvalidSqlParam {
+                reason: format!("invalid value of typed parameter: {v}"),
+            })
+        }
+    };
+
+    ScalarValue::try_from_string(value, &data_type).map_err(invalid)
+}
+
+fn parse_data_type(name: &str) -> Result<DataType, HttpError> {
+    let data_type = match name.to_ascii_lowercase().as_str() {
+        "bigint" | "int64" => DataType::Int64,
+        "bigint unsigned" | "uint64" => DataType::UInt64,
+        "double" | "float64" => DataType::Float64,
+        "boolean" | "bool" => DataType::Boolean,
+        "string" | "varchar" | "utf8" => DataType::Utf8,
+        "timestamp" => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        _ => {
+            return Err(HttpError::InvalidSqlParam {
+                reason: format!("unsupported parameter type '{name}'"),
+            })
+        }
+    };
+    Ok(data_type)
+}
+
+fn invalid(e: impl ToString) -> HttpError {
+    HttpError::InvalidSqlParam {
+        reason: e.to_string(),
+    }
+}
+
+/// A statement planned by `prepare` and executed by its handle.
+pub struct PreparedStatement {
+    /// Query of the `prepare` request, named placeholders are rewritten to positional ones.
+    pub query: Query,
+    pub plan: Option<Plan>,
+    /// Names of the named placeholders in position order.
+    pub param_names: Vec<String>,
+    /// Version of the tenant's meta data when the statement was planned.
+    meta_version: u64,
+}
+
+impl PreparedStatement {
+    pub fn new(
+        query: Query,
+        plan: Option<Plan>,
+        param_names: Vec<String>,
+        meta_version: u64,
+    ) -> Self {
+        Self {
+            query,
+            plan,
+            param_names,
+            meta_version,
+        }
+    }
+
+    /// The plan can be reused if neither the meta data of the tenant (schemas, roles...)
+    /// nor the privileges of the user have changed since it was planned and checked,
+    /// otherwise the statement must be planned again.
+    pub fn is_plan_valid(&self, context: &Context, meta_version: u64) -> bool {
+        self.meta_version == meta_version
+            && self.query.context().user_info().privileges() == context.user_info().privileges()
+    }
+
+    /// A prepared statement can only be used by the user who prepared it.
+    pub fn is_owned_by(&self, context: &Context) -> bool {
+        let ctx = self.query.context();
+        ctx.tenant() == context.tenant()
+            && ctx.user_info().desc().name() == context.user_info().desc().name()
+    }
+
+    pub fn parameters(&self) -> Result<Vec<PreparedParameter>, HttpError> {
+        let types = match &self.plan {
+            Some(plan) => plan.parameter_types()?,
+            None => Default::default(),
+        };
+        let mut parameters = types
+            .into_iter()
+            .map(|(id, data_type)| {
+                let position = id[1..].parse::<usize>().unwrap_or_default();
+                let name = self
+                    .param_names
+                    .get(position.wrapping_sub(1))
+                    .cloned()
+                    .unwrap_or(id);
+                (
+                    position,
+                    PreparedParameter {
+                        name,
+                        data_type: data_type.map(|t| t.to_string()),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        parameters.sort_by_key(|(position, _)| *position);
+
+        Ok(parameters.into_iter().map(|(_, p)| p).collect())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreparedParameter {
+    pub name: String,
+    pub data_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrepareResponse {
+    pub handle: String,
+    pub parameters: Vec<PreparedParameter>,
+}
+
+/// Plan cache of prepared statements, an entry expires after it's idle for 30 minutes.
+#[derive(Clone)]
+pub struct PreparedStatements {
+    id_generator: Arc<UuidGenerator>,
+    cache: Cache<String, Arc<PreparedStatement>>,
+}
+
+impl Default for PreparedStatements {
+    fn default() -> Self {
+        let cache = Cache::builder()
+            .thread_pool_enabled(false)
+            .max_capacity(10_000)
+            .time_to_idle(Duration::from_secs(30 * 60))
+            .build();
+
+        Self {
+            id_generator: Default::default(),
+            cache,
+        }
+    }
+}
+
+impl PreparedStatements {
+    /// Rewrite named placeholders of the sql, returns the rewritten query and the names.
+    pub fn rewrite_query(query: Query) -> Result<(Query, Vec<String>), HttpError> {
+        let (sql, names) = rewrite_named_placeholders(query.content())?;
+        Ok((Query::new(query.context().clone(), sql), names))
+    }
+
+    pub fn insert(&self, statement: PreparedStatement) -> String {
+        let handle = format!("{:032x}", self.id_generator.next_id());
+        self.cache.insert(handle.clone(), Arc::new(statement));
+        handle
+    }
+
+    /// Get the statement prepared by the user of the context.
+    pub fn get(
+        &self,
+        handle: &str,
+        context: &Context,
+    ) -> Result<Arc<PreparedStatement>, HttpError> {
+        match self.cache.get(handle) {
+            Some(statement) if statement.is_owned_by(context) => Ok(statement),
+            _ => Err(HttpError::PreparedStatementNotFound {
+                handle: handle.to_string(),
+            }),
+        }
+    }
+
+    /// Replace the statement of the handle by the one planned again.
+    pub fn update(&self, handle: &str, statement: PreparedStatement) -> Arc<PreparedStatement> {
+        let statement = Arc::new(statement);
+        self.cache.insert(handle.to_string(), statement.clone());
+        statement
+    }
+
+    pub fn remove(&self, handle: &str, context: &Context) -> Result<(), HttpError> {
+        self.get(handle, context)?;
+        self.cache.invalidate(handle);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use datafusion::scalar::ScalarValue;
+    use models::auth::role::UserRole;
+    use models::auth::user::{User, UserDesc, UserOptions};
+    use models::oid::Oid;
+    use query::sql::param::ParamValues;
+    use serde_json::json;
+    use spi::service::protocol::{Context, ContextBuilder, Query};
+
+    use super::{parse_param_values, PreparedStatement};
+
+    #[test]
+    fn test_parse_param_values() {
+        let values = parse_param_values(json!([1, -2.5, "a", true, null])).unwrap();
+        assert_eq!(
+            values,
+            ParamValues::Positional(vec![
+                ScalarValue::Int64(Some(1)),
+                ScalarValue::Float64(Some(-2.5)),
+                ScalarValue::Utf8(Some("a".to_string())),
+                ScalarValue::Boolean(Some(true)),
+                ScalarValue::Null,
+            ])
+        );
+
+        let values = parse_param_values(json!({
+            "$host": "a",
+            "start": {"type": "timestamp", "value": "1970-01-01T00:00:01Z"},
+            "count": {"type": "bigint unsigned", "value": 3},
+        }))
+        .unwrap();
+        assert_eq!(
+            values,
+            ParamValues::Named(HashMap::from([
+                ("host".to_string(), ScalarValue::Utf8(Some("a".to_string()))),
+                (
+                    "start".to_string(),
+                    ScalarValue::TimestampNanosecond(Some(1_000_000_000), None)
+                ),
+                ("count".to_string(), ScalarValue::UInt64(Some(3))),
+            ]))
+        );
+
+        assert!(parse_param_values(json!("a")).is_err());
+        assert!(parse_param_values(json!([[1]])).is_err());
+        assert!(parse_param_values(json!([{"type": "unknown", "value": 1}])).is_err());
+        assert!(parse_param_values(json!([{"type": "bigint", "value": "a"}])).is_err());
+    }
+
+    fn context(role: UserRole<Oid>) -> Context {
+        let desc = UserDesc::new(1, "u".to_string(), UserOptions::default(), false);
+        ContextBuilder::new(User::new(desc, role.to_privileges())).build()
+    }
+
+    #[test]
+    fn test_prepared_statement_plan_valid() {
+        let query = Query::new(context(UserRole::Dba), "select 1".to_string());
+        let statement = PreparedStatement::new(query, None, vec![], 1);
+
+        assert!(statement.is_plan_valid(&context(UserRole::Dba), 1));
+        assert!(!statement.is_plan_valid(&context(UserRole::Dba), 2));
+        assert!(!statement.is_plan_valid(&context(UserRole::Public(HashMap::new())), 1));
+    }
+}
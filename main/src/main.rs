@@ -22,12 +22,16 @@ use crate::report::ReportService;
 
 mod flight_sql;
 mod http;
+mod mysql;
+mod otlp;
+mod postgres;
 mod report;
 mod rpc;
 mod server;
 mod signal;
 mod spi;
 mod tcp;
+mod tls;
 mod vector;
 
 static VERSION: Lazy<String> = Lazy::new(|| {
@@ -0,0 +1,476 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use coordinator::service::CoordinatorRef;
+use futures::StreamExt;
+use models::auth::user::{User, UserInfo};
+use models::schema::{DEFAULT_CATALOG, DEFAULT_DATABASE};
+use spi::query::execution::Output;
+use spi::server::dbms::DBMSRef;
+use spi::service::protocol::{ContextBuilder, Query};
+use spi::QueryError;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsAcceptor;
+use trace::debug;
+
+use super::packet::{
+    self, command, put_lenenc_str, HandshakeResponse, CACHING_SHA2_PASSWORD_PLUGIN,
+    CLEAR_PASSWORD_PLUGIN, MAX_PACKET_LEN, MAX_PAYLOAD_LEN, PERFORM_FULL_AUTHENTICATION,
+};
+use super::sql::{system_variable, LocalStatement, Variable};
+use super::{types, Error, Result, SERVER_VERSION};
+use crate::tls::MaybeTlsStream;
+
+/// Size of the buffered packets to be written to the client before flushing.
+const WRITE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// A connection of the mysql client, serves the text protocol.
+pub struct Connection {
+    stream: BufStream<MaybeTlsStream>,
+    write_buf: BytesMut,
+    /// The connection must be encrypted before the authentication if tls is enabled.
+    tls_acceptor: Option<TlsAcceptor>,
+    /// Sequence id of the next packet, reset by each command of the client.
+    sequence_id: u8,
+    dbms: DBMSRef,
+    coord: CoordinatorRef,
+    connection_id: u32,
+}
+
+struct Session {
+    user: User,
+    tenant: String,
+    database: String,
+}
+
+impl Connection {
+    pub fn new(
+        stream: TcpStream,
+        dbms: DBMSRef,
+        coord: CoordinatorRef,
+        connection_id: u32,
+        tls_acceptor: Option<TlsAcceptor>,
+    ) -> Self {
+        Self {
+            stream: BufStream::new(MaybeTlsStream::Plain(stream)),
+            write_buf: BytesMut::with_capacity(WRITE_BUFFER_SIZE),
+            tls_acceptor,
+            sequence_id: 0,
+            dbms,
+            coord,
+            connection_id,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        let auth_plugin_data = auth_plugin_data();
+        let mut response = self.start_handshake(&auth_plugin_data).await;
+        if let (true, Some(acceptor)) = (
+            matches!(&response, Ok(Some(payload)) if HandshakeResponse::is_ssl_request(payload)),
+            self.tls_acceptor.clone(),
+        ) {
+            let stream = self
+                .stream
+                .into_inner()
+                .upgrade(&acceptor)
+                .await
+                .map_err(|e| Error::Io { source: e })?;
+            self.stream = BufStream::new(stream);
+            response = self.read_packet().await;
+        }
+
+        let session = match response {
+            Ok(Some(payload)) => self.handshake(&payload, &auth_plugin_data).await,
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        };
+        match session {
+            Ok(Some(mut session)) => self.serve(&mut session).await,
+            Ok(None) => Ok(()),
+            Err(e) => self.report_fatal(e).await,
+        }
+    }
+
+    /// Serve the commands after the handshake until the client quits.
+    async fn serve(&mut self, session: &mut Session) -> Result<()> {
+        loop {
+            let payload = match self.read_packet().await {
+                Ok(Some(payload)) => payload,
+                Ok(None) => return Ok(()),
+                Err(e) => return self.report_fatal(e).await,
+            };
+            let (command, body) = match payload.split_first() {
+                Some((command, body)) => (*command, body),
+                None => {
+                    let e = Error::Protocol {
+                        reason: "empty command packet".to_string(),
+                    };
+                    return self.report_fatal(e).await;
+                }
+            };
+
+            let result = match command {
+                command::COM_QUIT => return Ok(()),
+                command::COM_INIT_DB => match utf8(body) {
+                    Ok(database) => self.use_database(session, database).await,
+                    Err(e) => Err(e),
+                },
+                command::COM_QUERY => match utf8(body) {
+                    Ok(sql) => self.query(session, &sql).await,
+                    Err(e) => Err(e),
+                },
+                command::COM_PING => {
+                    self.send(&packet::ok_packet(0));
+                    Ok(())
+                }
+                _ => Err(Error::Unsupported {
+                    reason: format!("command {:#04x}", command),
+                }),
+            };
+
+            if let Err(e) = result {
+                if e.is_fatal() {
+                    return self.report_fatal(e).await;
+                }
+                self.report_error(e)?;
+            }
+            self.flush().await?;
+        }
+    }
+
+    /// Send the handshake of the server and read the response of the client,
+    /// returns `None` if the client closes the connection.
+    async fn start_handshake(&mut self, auth_plugin_data: &[u8; 20]) -> Result<Option<Bytes>> {
+        self.send(&packet::handshake(
+            SERVER_VERSION,
+            self.connection_id,
+            auth_plugin_data,
+            self.tls_acceptor.is_some(),
+        ));
+        self.flush().await?;
+        self.read_packet().await
+    }
+
+    /// Authenticate the user by the handshake response,
+    /// returns `None` if the client closes the connection.
+    async fn handshake(
+        &mut self,
+        payload: &[u8],
+        auth_plugin_data: &[u8; 20],
+    ) -> Result<Option<Session>> {
+        let response = HandshakeResponse::decode(payload)?;
+        let secure = self.stream.get_ref().is_tls();
+        if self.tls_acceptor.is_some() && !secure {
+            return Err(Error::SslRequired);
+        }
+
+        // the password is required in clear text to be verified against the stored hash
+        let password = match response.auth_plugin.as_deref() {
+            Some(CLEAR_PASSWORD_PLUGIN) => response.auth_response.clone(),
+            // the full authentication of caching_sha2_password, which is
+            // the default of the clients, sends the password over tls
+            Some(plugin) if secure => {
+                if plugin != CACHING_SHA2_PASSWORD_PLUGIN {
+                    self.send(&packet::auth_switch_request(
+                        CACHING_SHA2_PASSWORD_PLUGIN,
+                        auth_plugin_data,
+                    ));
+                    self.flush().await?;
+                    // the scramble of the client can't be verified against the stored hash
+                    if self.read_packet().await?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                self.send(&packet::auth_more_data(PERFORM_FULL_AUTHENTICATION));
+                self.flush().await?;
+                match self.read_packet().await? {
+                    Some(payload) => payload.to_vec(),
+                    None => return Ok(None),
+                }
+            }
+            Some(_) => {
+                self.send(&packet::auth_switch_request(
+                    CLEAR_PASSWORD_PLUGIN,
+                    auth_plugin_data,
+                ));
+                self.flush().await?;
+                match self.read_packet().await? {
+                    Some(payload) => payload.to_vec(),
+                    None => return Ok(None),
+                }
+            }
+            None => {
+                return Err(Error::Protocol {
+                    reason: "client doesn't support pluggable authentication".to_string(),
+                })
+            }
+        };
+        let password = utf8(&password)?.trim_end_matches('\0').to_string();
+
+        // the tenant is set by the connection attribute, like `connectionAttributes=tenant:cnosdb` of jdbc
+        let tenant = response
+            .attributes
+            .get("tenant")
+            .filter(|e| !e.is_empty())
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_CATALOG.to_string());
+        let database = response
+            .database
+            .clone()
+            .unwrap_or_else(|| DEFAULT_DATABASE.to_string());
+        let private_key = response
+            .attributes
+            .get("private_key")
+            .map(|e| {
+                base64::decode(e)
+                    .ok()
+                    .and_then(|e| String::from_utf8(e).ok())
+                    .ok_or_else(|| Error::Protocol {
+                        reason: format!("can not parse private_key with base64: {}", e),
+                    })
+            })
+            .transpose()?;
+
+        let user_info = UserInfo {
+            user: response.user,
+            password,
+            private_key,
+        };
+        let user = self
+            .dbms
+            .authenticate(&user_info, Some(&tenant))
+            .await
+            .map_err(|e| Error::Authentication { source: e })?;
+        debug!(
+            "mysql connection {} authenticated, user: {}, tenant: {}",
+            self.connection_id, user_info.user, tenant
+        );
+        self.check_database(&tenant, &database).await?;
+
+        self.send(&packet::ok_packet(0));
+        self.flush().await?;
+
+        Ok(Some(Session {
+            user,
+            tenant,
+            database,
+        }))
+    }
+
+    async fn query(&mut self, session: &mut Session, sql: &str) -> Result<()> {
+        debug!("mysql connection {} query: {}", self.connection_id, sql);
+
+        if let Some(statement) = LocalStatement::parse(sql) {
+            match statement {
+                LocalStatement::Use(database) => {
+                    self.use_database(session, database).await?;
+                }
+                LocalStatement::SelectVariables(columns) => {
+                    self.send_variables(session, columns);
+                }
+                LocalStatement::Empty | LocalStatement::Set | LocalStatement::Transaction => {
+                    self.send(&packet::ok_packet(0));
+                }
+            }
+            return Ok(());
+        }
+
+        let context = ContextBuilder::new(session.user.clone())
+            .with_tenant(Some(session.tenant.clone()))
+            .with_database(Some(session.database.clone()))
+            .build();
+        let query = Query::new(context, sql.to_string());
+        let output = self
+            .dbms
+            .execute(&query, None)
+            .await
+            .map_err(|e| Error::Query { source: e })?
+            .result();
+
+        self.send_output(session, output).await
+    }
+
+    /// Switch the database of the session, the database must exist.
+    async fn use_database(&mut self, session: &mut Session, database: String) -> Result<()> {
+        self.check_database(&session.tenant, &database).await?;
+        session.database = database;
+        self.send(&packet::ok_packet(0));
+        Ok(())
+    }
+
+    async fn check_database(&self, tenant: &str, database: &str) -> Result<()> {
+        let schema = match self.coord.tenant_meta(tenant).await {
+            Some(meta) => meta.get_db_schema(database).map_err(|e| Error::Query {
+                source: QueryError::Meta { source: e },
+            })?,
+            None => None,
+        };
+        if schema.is_none() {
+            return Err(Error::Query {
+                source: QueryError::DatabaseNotFound {
+                    name: database.to_string(),
+                },
+            });
+        }
+        Ok(())
+    }
+
+    /// Send the output as a text result set, or an OK packet if the output has no columns.
+    async fn send_output(&mut self, session: &Session, mut output: Output) -> Result<()> {
+        let schema = output.schema();
+        if schema.fields().is_empty() {
+            self.send(&packet::ok_packet(0));
+            return Ok(());
+        }
+
+        self.send(&packet::column_count(schema.fields().len()));
+        for field in schema.fields() {
+            self.send(&types::column_definition(&session.database, field).encode());
+        }
+        self.send(&packet::eof_packet());
+
+        let mut row_buf = BytesMut::new();
+        while let Some(batch) = output.next().await {
+            let batch = batch.map_err(|e| Error::Query { source: e })?;
+            for row in 0..batch.num_rows() {
+                row_buf.clear();
+                types::encode_text_row(&batch, row, &mut row_buf)?;
+                self.send(&row_buf);
+                if self.write_buf.len() >= WRITE_BUFFER_SIZE {
+                    self.flush().await?;
+                }
+            }
+        }
+        self.send(&packet::eof_packet());
+
+        Ok(())
+    }
+
+    /// Send the values of the variables as a result set of one row.
+    fn send_variables(&mut self, session: &Session, columns: Vec<(String, Variable)>) {
+        self.send(&packet::column_count(columns.len()));
+        for (name, _) in &columns {
+            self.send(&types::string_column_definition(&session.database, name).encode());
+        }
+        self.send(&packet::eof_packet());
+
+        let mut row = BytesMut::new();
+        for (_, variable) in &columns {
+            let value = match variable {
+                Variable::System(name) => system_variable(name),
+                Variable::Database => Some(session.database.as_str()),
+            };
+            match value {
+                Some(value) => put_lenenc_str(&mut row, value.as_bytes()),
+                None => row.put_u8(0xfb),
+            }
+        }
+        self.send(&row);
+        self.send(&packet::eof_packet());
+    }
+
+    /// Write the payload as packets, the payload longer than the max length is split.
+    fn send(&mut self, payload: &[u8]) {
+        let mut rest = payload;
+        loop {
+            let len = rest.len().min(MAX_PAYLOAD_LEN);
+            self.write_buf.put_uint_le(len as u64, 3);
+            self.write_buf.put_u8(self.sequence_id);
+            self.sequence_id = self.sequence_id.wrapping_add(1);
+            self.write_buf.put_slice(&rest[..len]);
+            rest = &rest[len..];
+            // the payload of exactly the max length is followed by an empty packet
+            if len < MAX_PAYLOAD_LEN {
+                return;
+            }
+        }
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.stream
+            .write_all(&self.write_buf)
+            .await
+            .map_err(|e| Error::Io { source: e })?;
+        self.write_buf.clear();
+        self.stream
+            .flush()
+            .await
+            .map_err(|e| Error::Io { source: e })
+    }
+
+    fn report_error(&mut self, e: Error) -> Result<()> {
+        if let Error::Io { .. } = e {
+            return Err(e);
+        }
+        debug!("mysql connection {} error: {}", self.connection_id, e);
+        self.send(&e.to_err_packet());
+        Ok(())
+    }
+
+    /// Report the error to the client and close the connection.
+    async fn report_fatal(&mut self, e: Error) -> Result<()> {
+        self.report_error(e)?;
+        self.flush().await
+    }
+
+    /// Read the payload of a packet, the payloads split into multiple packets are joined.
+    /// Returns `None` if the connection is closed.
+    async fn read_packet(&mut self) -> Result<Option<Bytes>> {
+        let mut payload = BytesMut::new();
+        loop {
+            let mut header = [0_u8; 4];
+            match self.stream.read_exact(&mut header).await {
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && payload.is_empty() => {
+                    return Ok(None)
+                }
+                Err(e) => return Err(Error::Io { source: e }),
+            }
+            let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+            self.sequence_id = header[3].wrapping_add(1);
+            if payload.len() + len > MAX_PACKET_LEN {
+                return Err(Error::Protocol {
+                    reason: format!("packet is longer than {} bytes", MAX_PACKET_LEN),
+                });
+            }
+
+            let start = payload.len();
+            payload.resize(start + len, 0);
+            self.stream
+                .read_exact(&mut payload[start..])
+                .await
+                .map_err(|e| Error::Io { source: e })?;
+            if len < MAX_PAYLOAD_LEN {
+                return Ok(Some(payload.freeze()));
+            }
+        }
+    }
+}
+
+fn utf8(value: &[u8]) -> Result<String> {
+    String::from_utf8(value.to_vec()).map_err(|e| Error::Protocol {
+        reason: format!("string is not utf8: {}", e),
+    })
+}
+
+/// Random printable bytes for the scramble of the authentication.
+fn auth_plugin_data() -> [u8; 20] {
+    let mut data = [0; 20];
+    for chunk in data.chunks_mut(8) {
+        let random = models::oid::uuid_u64().to_le_bytes();
+        for (b, r) in chunk.iter_mut().zip(random) {
+            *b = r % 94 + 33;
+        }
+    }
+    data
+}
+
+#[cfg(test)]
+mod test {
+    use super::auth_plugin_data;
+
+    #[test]
+    fn test_auth_plugin_data() {
+        let data = auth_plugin_data();
+        assert!(data.iter().all(|b| b.is_ascii_graphic()));
+    }
+}
@@ -0,0 +1,247 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use bytes::BytesMut;
+use config::TLSConfig;
+use coordinator::service::CoordinatorRef;
+use models::error_code::{ErrorCode, ErrorCoder};
+use snafu::Snafu;
+use spi::server::dbms::DBMSRef;
+use spi::QueryError;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use trace::{debug, info, warn};
+
+use self::connection::Connection;
+use crate::server;
+use crate::server::ServiceHandle;
+use crate::spi::service::Service;
+use crate::tls::build_tls_acceptor;
+
+mod connection;
+mod packet;
+mod sql;
+mod types;
+
+/// Version reported to the clients, drivers check the major version to decide the behaviours.
+pub const SERVER_VERSION: &str = "8.0.26-CnosDB";
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Snafu, ErrorCoder)]
+#[error_code(mod_code = "08")]
+#[snafu(visibility(pub))]
+pub enum Error {
+    Query {
+        source: QueryError,
+    },
+
+    #[snafu(display("Authentication failed: {}", source))]
+    #[error_code(code = 1)]
+    Authentication {
+        source: QueryError,
+    },
+
+    #[snafu(display("IO error: {}", source))]
+    #[error_code(code = 2)]
+    Io {
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Protocol violation: {}", reason))]
+    #[error_code(code = 3)]
+    Protocol {
+        reason: String,
+    },
+
+    #[snafu(display("Encode value: {}", reason))]
+    #[error_code(code = 4)]
+    Encode {
+        reason: String,
+    },
+
+    #[snafu(display("Not supported: {}", reason))]
+    #[error_code(code = 5)]
+    Unsupported {
+        reason: String,
+    },
+
+    #[snafu(display("SSL connection is required"))]
+    #[error_code(code = 6)]
+    SslRequired,
+}
+
+impl Error {
+    pub fn error_code(&self) -> &dyn ErrorCode {
+        match self {
+            Error::Query { source } => source.error_code(),
+            _ => self,
+        }
+    }
+
+    /// The connection is closed after the fatal errors.
+    fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Error::Io { .. }
+                | Error::Protocol { .. }
+                | Error::Authentication { .. }
+                | Error::SslRequired
+        )
+    }
+
+    /// The mysql error number and SQLSTATE of the error,
+    /// see https://dev.mysql.com/doc/mysql-errors/8.0/en/server-error-reference.html
+    fn mysql_error(&self) -> (u16, &'static str) {
+        match self {
+            Error::Query { source } => match source {
+                // ER_PARSE_ERROR
+                QueryError::Parser { .. } | QueryError::MultiStatement { .. } => (1064, "42000"),
+                // ER_SPECIFIC_ACCESS_DENIED_ERROR
+                QueryError::InsufficientPrivileges { .. } => (1227, "42000"),
+                // ER_ACCESS_DENIED_ERROR
+                QueryError::Auth { .. } => (1045, "28000"),
+                // ER_NOT_SUPPORTED_YET
+                QueryError::NotImplemented { .. } | QueryError::Unimplement { .. } => {
+                    (1235, "42000")
+                }
+                // ER_BAD_DB_ERROR
+                QueryError::DatabaseNotFound { .. } => (1049, "42000"),
+                // ER_QUERY_TIMEOUT
+                QueryError::QueryTimeout { .. } => (3024, "HY000"),
+                // ER_UNKNOWN_ERROR
+                _ => (1105, "HY000"),
+            },
+            Error::Authentication { .. } => (1045, "28000"),
+            // ER_NET_READ_ERROR
+            Error::Io { .. } => (1158, "08S01"),
+            // ER_HANDSHAKE_ERROR
+            Error::Protocol { .. } => (1043, "08S01"),
+            Error::Encode { .. } => (1105, "HY000"),
+            // ER_UNKNOWN_COM_ERROR
+            Error::Unsupported { .. } => (1047, "08S01"),
+            // ER_SECURE_TRANSPORT_REQUIRED
+            Error::SslRequired => (3159, "HY000"),
+        }
+    }
+
+    fn to_err_packet(&self) -> BytesMut {
+        let (code, sql_state) = self.mysql_error();
+        packet::err_packet(code, sql_state, &self.error_code().message())
+    }
+}
+
+/// Service of the [MySQL client/server protocol](https://dev.mysql.com/doc/dev/mysql-server/latest/PAGE_PROTOCOL.html),
+/// the text protocol of `COM_QUERY` is supported.
+///
+/// The password is required in clear text to be verified. If the tls of the server is configured, the
+/// connection must be encrypted and the password is sent by the full authentication of the
+/// `caching_sha2_password` plugin, otherwise it is sent by the `mysql_clear_password` plugin.
+/// The tenant is set by the connection attribute `tenant`, and the database is set by the connection
+/// or `USE db`.
+pub struct MysqlService {
+    dbms: DBMSRef,
+    coord: CoordinatorRef,
+    addr: String,
+    tls_config: Option<TLSConfig>,
+    handle: Option<ServiceHandle<()>>,
+}
+
+impl MysqlService {
+    pub fn new(
+        dbms: DBMSRef,
+        coord: CoordinatorRef,
+        addr: String,
+        tls_config: Option<TLSConfig>,
+    ) -> Self {
+        Self {
+            dbms,
+            coord,
+            addr,
+            tls_config,
+            handle: None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Service for MysqlService {
+    fn start(&mut self) -> server::Result<()> {
+        let (shutdown, mut rx) = oneshot::channel();
+        let dbms = self.dbms.clone();
+        let coord = self.coord.clone();
+        let addr = self.addr.clone();
+        let tls_acceptor = self
+            .tls_config
+            .as_ref()
+            .map(build_tls_acceptor)
+            .transpose()
+            .map_err(|e| server::Error::Common {
+                reason: format!("build tls of mysql service: {}", e),
+            })?;
+        let listener = std::net::TcpListener::bind(&addr)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(listener)
+            })
+            .map_err(|e| server::Error::Common {
+                reason: format!("bind mysql service to {}: {}", addr, e),
+            })?;
+
+        let join_handle = tokio::spawn(async move {
+            let listener = match TcpListener::from_std(listener) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    warn!("mysql service listen on {}: {}", addr, e);
+                    return;
+                }
+            };
+            let connection_id = AtomicU32::new(1);
+
+            loop {
+                let stream = tokio::select! {
+                    _ = &mut rx => {
+                        info!("mysql server graceful shutdown!");
+                        return;
+                    }
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            warn!("mysql service accept connection: {}", e);
+                            continue;
+                        }
+                    },
+                };
+                let _ = stream.set_nodelay(true);
+
+                let connection_id = connection_id.fetch_add(1, Ordering::Relaxed);
+                let connection = Connection::new(
+                    stream,
+                    dbms.clone(),
+                    coord.clone(),
+                    connection_id,
+                    tls_acceptor.clone(),
+                );
+                tokio::spawn(async move {
+                    if let Err(e) = connection.run().await {
+                        debug!("mysql connection {} closed: {}", connection_id, e);
+                    }
+                });
+            }
+        });
+        self.handle = Some(ServiceHandle::new(
+            "mysql service".to_string(),
+            join_handle,
+            shutdown,
+        ));
+
+        info!("mysql server start addr: {}", self.addr);
+
+        Ok(())
+    }
+
+    async fn stop(&mut self, force: bool) {
+        if let Some(stop) = self.handle.take() {
+            stop.shutdown(force).await
+        };
+    }
+}
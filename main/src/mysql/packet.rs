@@ -0,0 +1,452 @@
+//! Packets of the [MySQL client/server protocol](https://dev.mysql.com/doc/dev/mysql-server/latest/PAGE_PROTOCOL.html).
+
+use std::collections::HashMap;
+
+use bytes::{Buf, BufMut, BytesMut};
+
+use super::{Error, Result};
+
+/// Max length of the payload of a packet, longer payloads are split into multiple packets.
+pub const MAX_PAYLOAD_LEN: usize = 0xff_ffff;
+/// Max length of the joined payloads, the `max_allowed_packet` of the server.
+pub const MAX_PACKET_LEN: usize = 64 * 1024 * 1024;
+
+pub const PROTOCOL_VERSION: u8 = 10;
+/// utf8mb4_general_ci
+pub const UTF8MB4_CHARSET: u16 = 45;
+/// binary
+pub const BINARY_CHARSET: u16 = 63;
+
+pub const CLEAR_PASSWORD_PLUGIN: &str = "mysql_clear_password";
+pub const CACHING_SHA2_PASSWORD_PLUGIN: &str = "caching_sha2_password";
+/// AuthMoreData of `caching_sha2_password`, asks the client to send the password,
+/// which is sent in clear text over a secure connection.
+pub const PERFORM_FULL_AUTHENTICATION: u8 = 0x04;
+
+pub mod capability {
+    pub const CLIENT_LONG_PASSWORD: u32 = 0x0000_0001;
+    pub const CLIENT_FOUND_ROWS: u32 = 0x0000_0002;
+    pub const CLIENT_LONG_FLAG: u32 = 0x0000_0004;
+    pub const CLIENT_CONNECT_WITH_DB: u32 = 0x0000_0008;
+    pub const CLIENT_PROTOCOL_41: u32 = 0x0000_0200;
+    pub const CLIENT_SSL: u32 = 0x0000_0800;
+    pub const CLIENT_TRANSACTIONS: u32 = 0x0000_2000;
+    pub const CLIENT_SECURE_CONNECTION: u32 = 0x0000_8000;
+    pub const CLIENT_PLUGIN_AUTH: u32 = 0x0008_0000;
+    pub const CLIENT_CONNECT_ATTRS: u32 = 0x0010_0000;
+    pub const CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA: u32 = 0x0020_0000;
+
+    /// Capabilities of the server.
+    pub const SERVER_CAPABILITIES: u32 = CLIENT_LONG_PASSWORD
+        | CLIENT_FOUND_ROWS
+        | CLIENT_LONG_FLAG
+        | CLIENT_CONNECT_WITH_DB
+        | CLIENT_PROTOCOL_41
+        | CLIENT_TRANSACTIONS
+        | CLIENT_SECURE_CONNECTION
+        | CLIENT_PLUGIN_AUTH
+        | CLIENT_CONNECT_ATTRS
+        | CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA;
+}
+
+/// SERVER_STATUS_AUTOCOMMIT, transactions are not supported.
+pub const SERVER_STATUS: u16 = 0x0002;
+
+pub mod command {
+    pub const COM_QUIT: u8 = 0x01;
+    pub const COM_INIT_DB: u8 = 0x02;
+    pub const COM_QUERY: u8 = 0x03;
+    pub const COM_PING: u8 = 0x0e;
+}
+
+/// Protocol::HandshakeResponse41 of the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandshakeResponse {
+    pub user: String,
+    pub auth_response: Vec<u8>,
+    pub database: Option<String>,
+    pub auth_plugin: Option<String>,
+    pub attributes: HashMap<String, String>,
+}
+
+impl HandshakeResponse {
+    /// Protocol::SSLRequest, the head of the handshake response
+    /// sent before the TLS handshake.
+    pub fn is_ssl_request(payload: &[u8]) -> bool {
+        let mut buf = payload;
+        payload.len() == 32 && get_u32(&mut buf).map_or(false, |e| e & capability::CLIENT_SSL != 0)
+    }
+
+    pub fn decode(mut payload: &[u8]) -> Result<Self> {
+        let capabilities = get_u32(&mut payload)?;
+        if capabilities & capability::CLIENT_PROTOCOL_41 == 0 {
+            return Err(Error::Protocol {
+                reason: "client protocol older than 4.1 is not supported".to_string(),
+            });
+        }
+        // max packet size, character set and the reserved bytes
+        skip(&mut payload, 4 + 1 + 23)?;
+
+        let user = get_null_str(&mut payload)?;
+        let auth_response = if capabilities & capability::CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA != 0
+        {
+            let len = get_lenenc_int(&mut payload)? as usize;
+            get_bytes(&mut payload, len)?
+        } else if capabilities & capability::CLIENT_SECURE_CONNECTION != 0 {
+            let len = get_u8(&mut payload)? as usize;
+            get_bytes(&mut payload, len)?
+        } else {
+            get_null_str(&mut payload)?.into_bytes()
+        };
+        let database = if capabilities & capability::CLIENT_CONNECT_WITH_DB != 0 {
+            Some(get_null_str(&mut payload)?).filter(|e| !e.is_empty())
+        } else {
+            None
+        };
+        let auth_plugin = if capabilities & capability::CLIENT_PLUGIN_AUTH != 0 {
+            Some(get_null_str(&mut payload)?)
+        } else {
+            None
+        };
+        let mut attributes = HashMap::new();
+        if capabilities & capability::CLIENT_CONNECT_ATTRS != 0 && payload.has_remaining() {
+            let len = get_lenenc_int(&mut payload)? as usize;
+            let mut attrs = &get_bytes(&mut payload, len)?[..];
+            while attrs.has_remaining() {
+                let key = get_lenenc_str(&mut attrs)?;
+                let value = get_lenenc_str(&mut attrs)?;
+                attributes.insert(key, value);
+            }
+        }
+
+        Ok(Self {
+            user,
+            auth_response,
+            database,
+            auth_plugin,
+            attributes,
+        })
+    }
+}
+
+/// Protocol::HandshakeV10 of the server, clients authenticate by `caching_sha2_password`
+/// over TLS if `tls` is set, otherwise by `mysql_clear_password`.
+pub fn handshake(
+    server_version: &str,
+    connection_id: u32,
+    auth_plugin_data: &[u8; 20],
+    tls: bool,
+) -> BytesMut {
+    let (capabilities, auth_plugin) = if tls {
+        (
+            capability::SERVER_CAPABILITIES | capability::CLIENT_SSL,
+            CACHING_SHA2_PASSWORD_PLUGIN,
+        )
+    } else {
+        (capability::SERVER_CAPABILITIES, CLEAR_PASSWORD_PLUGIN)
+    };
+    let mut buf = BytesMut::new();
+    buf.put_u8(PROTOCOL_VERSION);
+    put_null_str(&mut buf, server_version);
+    buf.put_u32_le(connection_id);
+    buf.put_slice(&auth_plugin_data[..8]);
+    buf.put_u8(0);
+    buf.put_u16_le(capabilities as u16);
+    buf.put_u8(UTF8MB4_CHARSET as u8);
+    buf.put_u16_le(SERVER_STATUS);
+    buf.put_u16_le((capabilities >> 16) as u16);
+    buf.put_u8(auth_plugin_data.len() as u8 + 1);
+    buf.put_slice(&[0; 10]);
+    buf.put_slice(&auth_plugin_data[8..]);
+    buf.put_u8(0);
+    put_null_str(&mut buf, auth_plugin);
+    buf
+}
+
+/// Protocol::AuthSwitchRequest, asks the client to authenticate by the plugin.
+pub fn auth_switch_request(plugin: &str, auth_plugin_data: &[u8]) -> BytesMut {
+    let mut buf = BytesMut::new();
+    buf.put_u8(0xfe);
+    put_null_str(&mut buf, plugin);
+    buf.put_slice(auth_plugin_data);
+    buf.put_u8(0);
+    buf
+}
+
+/// Protocol::AuthMoreData, the extra data of the authentication method.
+pub fn auth_more_data(data: u8) -> BytesMut {
+    let mut buf = BytesMut::new();
+    buf.put_u8(0x01);
+    buf.put_u8(data);
+    buf
+}
+
+pub fn ok_packet(affected_rows: u64) -> BytesMut {
+    let mut buf = BytesMut::new();
+    buf.put_u8(0x00);
+    put_lenenc_int(&mut buf, affected_rows);
+    // last insert id
+    put_lenenc_int(&mut buf, 0);
+    buf.put_u16_le(SERVER_STATUS);
+    // warnings
+    buf.put_u16_le(0);
+    buf
+}
+
+pub fn err_packet(code: u16, sql_state: &str, message: &str) -> BytesMut {
+    let mut buf = BytesMut::new();
+    buf.put_u8(0xff);
+    buf.put_u16_le(code);
+    buf.put_u8(b'#');
+    buf.put_slice(sql_state.as_bytes());
+    buf.put_slice(message.as_bytes());
+    buf
+}
+
+pub fn eof_packet() -> BytesMut {
+    let mut buf = BytesMut::new();
+    buf.put_u8(0xfe);
+    // warnings
+    buf.put_u16_le(0);
+    buf.put_u16_le(SERVER_STATUS);
+    buf
+}
+
+pub fn column_count(num: usize) -> BytesMut {
+    let mut buf = BytesMut::new();
+    put_lenenc_int(&mut buf, num as u64);
+    buf
+}
+
+/// Protocol::ColumnDefinition41
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDefinition {
+    pub schema: String,
+    pub name: String,
+    pub charset: u16,
+    pub column_length: u32,
+    pub column_type: u8,
+    pub flags: u16,
+    pub decimals: u8,
+}
+
+impl ColumnDefinition {
+    pub fn encode(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        put_lenenc_str(&mut buf, b"def");
+        put_lenenc_str(&mut buf, self.schema.as_bytes());
+        // table and original table
+        put_lenenc_str(&mut buf, b"");
+        put_lenenc_str(&mut buf, b"");
+        put_lenenc_str(&mut buf, self.name.as_bytes());
+        put_lenenc_str(&mut buf, self.name.as_bytes());
+        // length of the fixed length fields
+        put_lenenc_int(&mut buf, 0x0c);
+        buf.put_u16_le(self.charset);
+        buf.put_u32_le(self.column_length);
+        buf.put_u8(self.column_type);
+        buf.put_u16_le(self.flags);
+        buf.put_u8(self.decimals);
+        buf.put_u16_le(0);
+        buf
+    }
+}
+
+pub fn put_lenenc_int(buf: &mut BytesMut, value: u64) {
+    match value {
+        0..=250 => buf.put_u8(value as u8),
+        251..=0xffff => {
+            buf.put_u8(0xfc);
+            buf.put_u16_le(value as u16);
+        }
+        0x1_0000..=0xff_ffff => {
+            buf.put_u8(0xfd);
+            buf.put_uint_le(value, 3);
+        }
+        _ => {
+            buf.put_u8(0xfe);
+            buf.put_u64_le(value);
+        }
+    }
+}
+
+pub fn put_lenenc_str(buf: &mut BytesMut, value: &[u8]) {
+    put_lenenc_int(buf, value.len() as u64);
+    buf.put_slice(value);
+}
+
+fn put_null_str(buf: &mut BytesMut, value: &str) {
+    buf.put_slice(value.as_bytes());
+    buf.put_u8(0);
+}
+
+fn ensure_remaining(buf: &[u8], len: usize) -> Result<()> {
+    if buf.remaining() < len {
+        return Err(Error::Protocol {
+            reason: "packet is shorter than its content".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn skip(buf: &mut &[u8], len: usize) -> Result<()> {
+    ensure_remaining(buf, len)?;
+    buf.advance(len);
+    Ok(())
+}
+
+fn get_u8(buf: &mut &[u8]) -> Result<u8> {
+    ensure_remaining(buf, 1)?;
+    Ok(buf.get_u8())
+}
+
+fn get_u32(buf: &mut &[u8]) -> Result<u32> {
+    ensure_remaining(buf, 4)?;
+    Ok(buf.get_u32_le())
+}
+
+fn get_bytes(buf: &mut &[u8], len: usize) -> Result<Vec<u8>> {
+    ensure_remaining(buf, len)?;
+    let bytes = buf[..len].to_vec();
+    buf.advance(len);
+    Ok(bytes)
+}
+
+fn get_lenenc_int(buf: &mut &[u8]) -> Result<u64> {
+    let len = match get_u8(buf)? {
+        value @ 0..=250 => return Ok(value as u64),
+        0xfc => 2,
+        0xfd => 3,
+        0xfe => 8,
+        value => {
+            return Err(Error::Protocol {
+                reason: format!("invalid length-encoded integer prefix {:#x}", value),
+            })
+        }
+    };
+    ensure_remaining(buf, len)?;
+    Ok(buf.get_uint_le(len))
+}
+
+fn get_lenenc_str(buf: &mut &[u8]) -> Result<String> {
+    let len = get_lenenc_int(buf)? as usize;
+    let bytes = get_bytes(buf, len)?;
+    String::from_utf8(bytes).map_err(|e| Error::Protocol {
+        reason: format!("string is not utf8: {}", e),
+    })
+}
+
+fn get_null_str(buf: &mut &[u8]) -> Result<String> {
+    let end = buf
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or_else(|| Error::Protocol {
+            reason: "string is not terminated by null".to_string(),
+        })?;
+    let s = String::from_utf8(buf[..end].to_vec()).map_err(|e| Error::Protocol {
+        reason: format!("string is not utf8: {}", e),
+    })?;
+    buf.advance(end + 1);
+    Ok(s)
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::{BufMut, BytesMut};
+
+    use super::{
+        capability, get_lenenc_int, handshake, put_lenenc_int, put_lenenc_str, HandshakeResponse,
+    };
+
+    #[test]
+    fn test_lenenc_int() {
+        for value in [
+            0,
+            250,
+            251,
+            0xffff,
+            0x1_0000,
+            0xff_ffff,
+            0x100_0000,
+            u64::MAX,
+        ] {
+            let mut buf = BytesMut::new();
+            put_lenenc_int(&mut buf, value);
+            let mut slice = &buf[..];
+            assert_eq!(get_lenenc_int(&mut slice).unwrap(), value);
+            assert!(slice.is_empty());
+        }
+        assert!(get_lenenc_int(&mut &[0xfc, 0x01][..]).is_err());
+        assert!(get_lenenc_int(&mut &[0xff][..]).is_err());
+    }
+
+    #[test]
+    fn test_decode_handshake_response() {
+        let capabilities = capability::CLIENT_PROTOCOL_41
+            | capability::CLIENT_SECURE_CONNECTION
+            | capability::CLIENT_CONNECT_WITH_DB
+            | capability::CLIENT_PLUGIN_AUTH
+            | capability::CLIENT_CONNECT_ATTRS;
+        let mut buf = BytesMut::new();
+        buf.put_u32_le(capabilities);
+        buf.put_u32_le(1 << 24);
+        buf.put_u8(45);
+        buf.put_slice(&[0; 23]);
+        buf.put_slice(b"root\0");
+        buf.put_u8(4);
+        buf.put_slice(b"pass");
+        buf.put_slice(b"air\0");
+        buf.put_slice(b"mysql_clear_password\0");
+        let mut attrs = BytesMut::new();
+        put_lenenc_str(&mut attrs, b"tenant");
+        put_lenenc_str(&mut attrs, b"cnosdb");
+        put_lenenc_str(&mut buf, &attrs);
+
+        let response = HandshakeResponse::decode(&buf).unwrap();
+        assert_eq!(response.user, "root");
+        assert_eq!(response.auth_response, b"pass");
+        assert_eq!(response.database.as_deref(), Some("air"));
+        assert_eq!(
+            response.auth_plugin.as_deref(),
+            Some("mysql_clear_password")
+        );
+        assert_eq!(response.attributes.get("tenant").unwrap(), "cnosdb");
+
+        // truncated
+        assert!(HandshakeResponse::decode(&buf[..40]).is_err());
+    }
+
+    #[test]
+    fn test_handshake() {
+        let buf = handshake("8.0.26", 7, &[b'a'; 20], false);
+        assert_eq!(buf[0], 10);
+        assert_eq!(&buf[1..8], b"8.0.26\0");
+        assert_eq!(&buf[8..12], &7_u32.to_le_bytes());
+        assert_eq!(
+            u16::from_le_bytes([buf[21], buf[22]]) as u32 & capability::CLIENT_SSL,
+            0
+        );
+        assert!(buf.ends_with(b"mysql_clear_password\0"));
+
+        let buf = handshake("8.0.26", 7, &[b'a'; 20], true);
+        assert_ne!(
+            u16::from_le_bytes([buf[21], buf[22]]) as u32 & capability::CLIENT_SSL,
+            0
+        );
+        assert!(buf.ends_with(b"caching_sha2_password\0"));
+    }
+
+    #[test]
+    fn test_ssl_request() {
+        let mut buf = BytesMut::new();
+        buf.put_u32_le(capability::CLIENT_PROTOCOL_41 | capability::CLIENT_SSL);
+        buf.put_u32_le(1 << 24);
+        buf.put_u8(45);
+        buf.put_slice(&[0; 23]);
+        assert!(HandshakeResponse::is_ssl_request(&buf));
+
+        buf.put_slice(b"root\0");
+        assert!(!HandshakeResponse::is_ssl_request(&buf));
+        assert!(!HandshakeResponse::is_ssl_request(&[0; 32]));
+    }
+}
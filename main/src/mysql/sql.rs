@@ -0,0 +1,213 @@
+//! Statements of the mysql clients that are not sent to the query engine.
+
+/// Statements handled by the connection itself, drivers and tools send them
+/// when connecting, but the query engine doesn't support them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalStatement {
+    Empty,
+    /// `USE db`, changes the database of the session.
+    Use(String),
+    /// `SET ...`, they are ignored.
+    Set,
+    /// Transactions are not supported, the statements are ignored.
+    Transaction,
+    /// `SELECT @@version_comment, DATABASE() ...`, the columns and their values.
+    SelectVariables(Vec<(String, Variable)>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Variable {
+    /// The system variable `@@[session. | global.]name`.
+    System(String),
+    /// `DATABASE()`, the database of the session.
+    Database,
+}
+
+impl LocalStatement {
+    pub fn parse(sql: &str) -> Option<Self> {
+        let sql = strip_leading_comments(sql).trim_end_matches(';').trim();
+        let lowercase = sql.to_ascii_lowercase();
+        let words = lowercase.split_whitespace().collect::<Vec<_>>();
+
+        let statement = match words.as_slice() {
+            [] => Self::Empty,
+            // the lowercase has the same length, keep the case of the database
+            ["use", _] => Self::Use(unquote(sql["use".len()..].trim()).to_string()),
+            ["set", ..] => Self::Set,
+            ["begin"] | ["start", "transaction", ..] | ["commit", ..] | ["rollback", ..] => {
+                Self::Transaction
+            }
+            ["select", ..] => Self::SelectVariables(select_variables(&sql["select".len()..])?),
+            _ => return None,
+        };
+
+        Some(statement)
+    }
+}
+
+/// The columns of `SELECT item [AS alias], ... [LIMIT n]` if all the items are variables.
+fn select_variables(select_list: &str) -> Option<Vec<(String, Variable)>> {
+    let mut select_list = select_list.trim();
+    let lowercase = select_list.to_ascii_lowercase();
+    if let Some(idx) = lowercase.rfind(" limit ") {
+        let limit = lowercase[idx + " limit ".len()..].trim();
+        if !limit.is_empty() && limit.chars().all(|c| c.is_ascii_digit()) {
+            select_list = select_list[..idx].trim_end();
+        }
+    }
+
+    select_list
+        .split(',')
+        .map(|item| {
+            let item = item.trim();
+            let lowercase = item.to_ascii_lowercase();
+            let (expr, alias) = match lowercase.find(" as ") {
+                Some(idx) => (item[..idx].trim(), Some(unquote(item[idx + 4..].trim()))),
+                None => (item, None),
+            };
+
+            let lowercase_expr = expr.to_ascii_lowercase();
+            let variable = if lowercase_expr.replace(' ', "") == "database()" {
+                Variable::Database
+            } else {
+                let name = lowercase_expr.strip_prefix("@@")?;
+                let name = name
+                    .strip_prefix("session.")
+                    .or_else(|| name.strip_prefix("global."))
+                    .or_else(|| name.strip_prefix("local."))
+                    .unwrap_or(name);
+                if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    return None;
+                }
+                Variable::System(name.to_string())
+            };
+
+            Some((alias.unwrap_or(expr).to_string(), variable))
+        })
+        .collect()
+}
+
+/// The value of the system variable, `None` for the unknown variables.
+pub fn system_variable(name: &str) -> Option<&'static str> {
+    let value = match name {
+        "version" => super::SERVER_VERSION,
+        "version_comment" => "CnosDB",
+        "auto_increment_increment" => "1",
+        "autocommit" => "1",
+        "character_set_client"
+        | "character_set_connection"
+        | "character_set_database"
+        | "character_set_results"
+        | "character_set_server" => "utf8mb4",
+        "collation_connection" | "collation_database" | "collation_server" => "utf8mb4_general_ci",
+        "init_connect" => "",
+        "interactive_timeout" | "wait_timeout" => "28800",
+        "license" => "AGPL-3.0",
+        "lower_case_table_names" => "0",
+        "max_allowed_packet" => "67108864",
+        "net_buffer_length" => "16384",
+        "net_write_timeout" => "60",
+        "performance_schema" => "0",
+        "query_cache_size" => "0",
+        "query_cache_type" => "OFF",
+        "sql_mode" => "",
+        "system_time_zone" => "UTC",
+        "time_zone" => "+00:00",
+        "transaction_isolation" | "tx_isolation" => "READ-COMMITTED",
+        "transaction_read_only" | "tx_read_only" => "0",
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Skip the comments like `/* mysql-connector-java-8.0.30 */` before the statement.
+fn strip_leading_comments(sql: &str) -> &str {
+    let mut sql = sql.trim_start();
+    while let Some(rest) = sql.strip_prefix("/*") {
+        match rest.find("*/") {
+            Some(end) => sql = rest[end + 2..].trim_start(),
+            None => return "",
+        }
+    }
+    sql
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['`', '\'', '"'] {
+        if let Some(value) = value
+            .strip_prefix(quote)
+            .and_then(|e| e.strip_suffix(quote))
+        {
+            return value;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod test {
+    use super::{system_variable, LocalStatement, Variable};
+
+    #[test]
+    fn test_parse_local_statement() {
+        assert_eq!(LocalStatement::parse(" ; "), Some(LocalStatement::Empty));
+        assert_eq!(
+            LocalStatement::parse("USE `Air`;"),
+            Some(LocalStatement::Use("Air".to_string()))
+        );
+        assert_eq!(
+            LocalStatement::parse("use public"),
+            Some(LocalStatement::Use("public".to_string()))
+        );
+        assert_eq!(
+            LocalStatement::parse("SET NAMES utf8mb4"),
+            Some(LocalStatement::Set)
+        );
+        assert_eq!(
+            LocalStatement::parse("COMMIT"),
+            Some(LocalStatement::Transaction)
+        );
+        assert_eq!(LocalStatement::parse("SELECT * FROM air"), None);
+        assert_eq!(LocalStatement::parse("SELECT 1"), None);
+        assert_eq!(LocalStatement::parse("USE"), None);
+    }
+
+    #[test]
+    fn test_select_variables() {
+        assert_eq!(
+            LocalStatement::parse("select @@version_comment limit 1"),
+            Some(LocalStatement::SelectVariables(vec![(
+                "@@version_comment".to_string(),
+                Variable::System("version_comment".to_string())
+            )]))
+        );
+        assert_eq!(
+            LocalStatement::parse(
+                "/* mysql-connector-java-8.0.30 */SELECT @@session.auto_increment_increment AS \
+                 auto_increment_increment, @@character_set_client AS character_set_client, \
+                 DATABASE()"
+            ),
+            Some(LocalStatement::SelectVariables(vec![
+                (
+                    "auto_increment_increment".to_string(),
+                    Variable::System("auto_increment_increment".to_string())
+                ),
+                (
+                    "character_set_client".to_string(),
+                    Variable::System("character_set_client".to_string())
+                ),
+                ("DATABASE()".to_string(), Variable::Database),
+            ]))
+        );
+        assert_eq!(
+            LocalStatement::parse("SELECT @@version, time FROM air"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_system_variable() {
+        assert_eq!(system_variable("version_comment"), Some("CnosDB"));
+        assert_eq!(system_variable("unknown"), None);
+    }
+}
@@ -0,0 +1,234 @@
+//! Mapping from the arrow types to the mysql column types, and the text format of the values.
+
+use bytes::{BufMut, BytesMut};
+use chrono::NaiveDateTime;
+use datafusion::arrow::array::{Array, ArrayRef, AsArray};
+use datafusion::arrow::datatypes::{
+    DataType, Field, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType,
+};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::array_value_to_string;
+
+use super::packet::{put_lenenc_str, ColumnDefinition, BINARY_CHARSET, UTF8MB4_CHARSET};
+use super::{Error, Result};
+
+/// Types of the columns, defined in `enum_field_types` of mysql.
+pub mod column_type {
+    pub const MYSQL_TYPE_TINY: u8 = 0x01;
+    pub const MYSQL_TYPE_SHORT: u8 = 0x02;
+    pub const MYSQL_TYPE_LONG: u8 = 0x03;
+    pub const MYSQL_TYPE_FLOAT: u8 = 0x04;
+    pub const MYSQL_TYPE_DOUBLE: u8 = 0x05;
+    pub const MYSQL_TYPE_LONGLONG: u8 = 0x08;
+    pub const MYSQL_TYPE_DATE: u8 = 0x0a;
+    pub const MYSQL_TYPE_TIME: u8 = 0x0b;
+    pub const MYSQL_TYPE_DATETIME: u8 = 0x0c;
+    pub const MYSQL_TYPE_NEWDECIMAL: u8 = 0xf6;
+    pub const MYSQL_TYPE_BLOB: u8 = 0xfc;
+    pub const MYSQL_TYPE_VAR_STRING: u8 = 0xfd;
+}
+
+pub mod column_flag {
+    pub const NOT_NULL_FLAG: u16 = 0x0001;
+    pub const UNSIGNED_FLAG: u16 = 0x0020;
+    pub const BINARY_FLAG: u16 = 0x0080;
+}
+
+/// Digits of the fractional seconds of the datetime values.
+const DATETIME_DECIMALS: u8 = 6;
+/// The decimals of the floating point columns without fixed decimals.
+const FLOATING_DECIMALS: u8 = 0x1f;
+
+pub fn column_definition(schema: &str, field: &Field) -> ColumnDefinition {
+    use column_type::*;
+
+    let (column_type, column_length, decimals) = match field.data_type() {
+        DataType::Boolean => (MYSQL_TYPE_TINY, 1, 0),
+        DataType::Int8 | DataType::UInt8 => (MYSQL_TYPE_TINY, 4, 0),
+        DataType::Int16 | DataType::UInt16 => (MYSQL_TYPE_SHORT, 6, 0),
+        DataType::Int32 | DataType::UInt32 => (MYSQL_TYPE_LONG, 11, 0),
+        DataType::Int64 | DataType::UInt64 => (MYSQL_TYPE_LONGLONG, 20, 0),
+        DataType::Float16 | DataType::Float32 => (MYSQL_TYPE_FLOAT, 12, FLOATING_DECIMALS),
+        DataType::Float64 => (MYSQL_TYPE_DOUBLE, 22, FLOATING_DECIMALS),
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => (
+            MYSQL_TYPE_NEWDECIMAL,
+            *precision as u32 + 2,
+            (*scale).max(0) as u8,
+        ),
+        DataType::Timestamp(_, _) => (MYSQL_TYPE_DATETIME, 26, DATETIME_DECIMALS),
+        DataType::Date32 | DataType::Date64 => (MYSQL_TYPE_DATE, 10, 0),
+        DataType::Time32(_) | DataType::Time64(_) => (MYSQL_TYPE_TIME, 17, DATETIME_DECIMALS),
+        DataType::Binary | DataType::LargeBinary => (MYSQL_TYPE_BLOB, 0xffff, 0),
+        _ => (MYSQL_TYPE_VAR_STRING, 0xffff * 4, 0),
+    };
+
+    let mut flags = 0;
+    if !field.is_nullable() {
+        flags |= column_flag::NOT_NULL_FLAG;
+    }
+    if matches!(
+        field.data_type(),
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64
+    ) {
+        flags |= column_flag::UNSIGNED_FLAG;
+    }
+    let charset = match column_type {
+        MYSQL_TYPE_VAR_STRING => UTF8MB4_CHARSET,
+        MYSQL_TYPE_BLOB => {
+            flags |= column_flag::BINARY_FLAG;
+            BINARY_CHARSET
+        }
+        _ => BINARY_CHARSET,
+    };
+
+    ColumnDefinition {
+        schema: schema.to_string(),
+        name: field.name().clone(),
+        charset,
+        column_length,
+        column_type,
+        flags,
+        decimals,
+    }
+}
+
+/// Column definition of the string values, like the system variables.
+pub fn string_column_definition(schema: &str, name: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        schema: schema.to_string(),
+        name: name.to_string(),
+        charset: UTF8MB4_CHARSET,
+        column_length: 0xffff * 4,
+        column_type: column_type::MYSQL_TYPE_VAR_STRING,
+        flags: 0,
+        decimals: 0,
+    }
+}
+
+/// Encode the row of the batch into the payload of a ProtocolText::ResultsetRow.
+pub fn encode_text_row(batch: &RecordBatch, row: usize, buf: &mut BytesMut) -> Result<()> {
+    for column in batch.columns() {
+        if column.is_null(row) {
+            buf.put_u8(0xfb);
+            continue;
+        }
+        match column.data_type() {
+            DataType::Binary => put_lenenc_str(buf, column.as_binary::<i32>().value(row)),
+            DataType::LargeBinary => put_lenenc_str(buf, column.as_binary::<i64>().value(row)),
+            _ => put_lenenc_str(buf, text_value(column, row)?.as_bytes()),
+        }
+    }
+    Ok(())
+}
+
+fn text_value(array: &ArrayRef, row: usize) -> Result<String> {
+    let text = match array.data_type() {
+        DataType::Boolean => (array.as_boolean().value(row) as u8).to_string(),
+        DataType::Utf8 => array.as_string::<i32>().value(row).to_string(),
+        DataType::LargeUtf8 => array.as_string::<i64>().value(row).to_string(),
+        DataType::Timestamp(unit, _) => timestamp_value(array, unit, row)?
+            .format("%Y-%m-%d %H:%M:%S%.6f")
+            .to_string(),
+        _ => array_value_to_string(array, row).map_err(|e| Error::Encode {
+            reason: e.to_string(),
+        })?,
+    };
+    Ok(text)
+}
+
+fn timestamp_value(array: &ArrayRef, unit: &TimeUnit, row: usize) -> Result<NaiveDateTime> {
+    let datetime = match unit {
+        TimeUnit::Second => array
+            .as_primitive::<TimestampSecondType>()
+            .value_as_datetime(row),
+        TimeUnit::Millisecond => array
+            .as_primitive::<TimestampMillisecondType>()
+            .value_as_datetime(row),
+        TimeUnit::Microsecond => array
+            .as_primitive::<TimestampMicrosecondType>()
+            .value_as_datetime(row),
+        TimeUnit::Nanosecond => array
+            .as_primitive::<TimestampNanosecondType>()
+            .value_as_datetime(row),
+    };
+    datetime.ok_or_else(|| Error::Encode {
+        reason: format!(
+            "timestamp {} out of range",
+            array_value_to_string(array, row).unwrap_or_default()
+        ),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use bytes::BytesMut;
+    use datafusion::arrow::array::{
+        BinaryArray, BooleanArray, Float64Array, StringArray, TimestampNanosecondArray, UInt32Array,
+    };
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::arrow::record_batch::RecordBatch;
+
+    use super::{column_definition, column_flag, column_type, encode_text_row};
+
+    #[test]
+    fn test_column_definition() {
+        let field = Field::new(
+            "time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        );
+        let column = column_definition("public", &field);
+        assert_eq!(column.column_type, column_type::MYSQL_TYPE_DATETIME);
+        assert_eq!(column.flags, column_flag::NOT_NULL_FLAG);
+        assert_eq!(column.decimals, 6);
+
+        let field = Field::new("value", DataType::UInt32, true);
+        let column = column_definition("public", &field);
+        assert_eq!(column.column_type, column_type::MYSQL_TYPE_LONG);
+        assert_eq!(column.flags, column_flag::UNSIGNED_FLAG);
+
+        let field = Field::new("tag", DataType::Utf8, true);
+        let column = column_definition("public", &field);
+        assert_eq!(column.column_type, column_type::MYSQL_TYPE_VAR_STRING);
+        assert_eq!(column.charset, 45);
+    }
+
+    #[test]
+    fn test_encode_text_row() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
+            Field::new("tag", DataType::Utf8, true),
+            Field::new("value", DataType::Float64, true),
+            Field::new("flag", DataType::Boolean, true),
+            Field::new("count", DataType::UInt32, true),
+            Field::new("data", DataType::Binary, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(TimestampNanosecondArray::from(vec![1_500_000_000])),
+                Arc::new(StringArray::from(vec![None::<&str>])),
+                Arc::new(Float64Array::from(vec![1.5])),
+                Arc::new(BooleanArray::from(vec![true])),
+                Arc::new(UInt32Array::from(vec![7])),
+                Arc::new(BinaryArray::from(vec![&[0_u8, 1][..]])),
+            ],
+        )
+        .unwrap();
+
+        let mut buf = BytesMut::new();
+        encode_text_row(&batch, 0, &mut buf).unwrap();
+        let mut expected = vec![26];
+        expected.extend_from_slice(b"1970-01-01 00:00:01.500000");
+        expected.push(0xfb);
+        expected.extend_from_slice(b"\x031.5\x011\x017\x02\x00\x01");
+        assert_eq!(&buf[..], &expected[..]);
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use coordinator::service::CoordinatorRef;
+use http_protocol::header::{AUTHORIZATION, DB, TENANT};
+use metrics::count::U64Counter;
+use metrics::duration::{DurationHistogram, DurationHistogramOptions};
+use metrics::metric::Metric;
+use metrics::metric_register::MetricsRegister;
+use models::auth::privilege::{DatabasePrivilege, Privilege, TenantObjectPrivilege};
+use models::auth::user::{UserInfo, ROOT, ROOT_PWD};
+use models::oid::Identifier;
+use models::schema::{Precision, DEFAULT_CATALOG, DEFAULT_DATABASE};
+use prost::Message;
+use protocol_parser::otlp::otlp_metrics_to_points;
+use protos::otlp::metrics_service_server::MetricsService;
+use protos::otlp::{
+    ExportMetricsPartialSuccess, ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use spi::server::dbms::DBMSRef;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status};
+
+use crate::http::header::Header;
+
+pub struct OtlpService {
+    pub coord: CoordinatorRef,
+    pub dbms: DBMSRef,
+    metrics: OtlpMetrics,
+}
+
+impl OtlpService {
+    pub fn new(
+        coord: CoordinatorRef,
+        dbms: DBMSRef,
+        metrics_register: &Arc<MetricsRegister>,
+    ) -> Self {
+        Self {
+            coord,
+            dbms,
+            metrics: OtlpMetrics::new(metrics_register),
+        }
+    }
+
+    /// The tenant and database are read from the metadata `tenant` and `db`,
+    /// the user from the basic authorization.
+    pub async fn get_tenant_db_and_check_privilege(
+        &self,
+        metadata: &MetadataMap,
+    ) -> Result<(String, String, String), Status> {
+        let get = |key: &str| {
+            metadata
+                .get(key)
+                .map(|v| {
+                    v.to_str()
+                        .map_err(|_| Status::invalid_argument(format!("invalid metadata {key}")))
+                })
+                .transpose()
+        };
+        let tenant = get(TENANT)?.unwrap_or(DEFAULT_CATALOG).to_string();
+        let db = get(DB)?.unwrap_or(DEFAULT_DATABASE).to_string();
+        let user_info = match get(AUTHORIZATION.as_str())? {
+            Some(auth) => Header::with(None, None, None, auth.to_string())
+                .try_get_basic_auth()
+                .map_err(|e| Status::unauthenticated(e.to_string()))?,
+            None => UserInfo {
+                user: ROOT.to_string(),
+                password: ROOT_PWD.to_string(),
+                private_key: None,
+            },
+        };
+
+        self.privilege_check(&tenant, &db, &user_info).await?;
+        Ok((tenant, db, user_info.user))
+    }
+
+    async fn privilege_check(
+        &self,
+        tenant: &str,
+        db: &str,
+        user_info: &UserInfo,
+    ) -> Result<(), Status> {
+        let tenant_id = *self
+            .coord
+            .tenant_meta(tenant)
+            .await
+            .ok_or(Status::invalid_argument("invalid tenant"))?
+            .tenant()
+            .id();
+        let privilege = Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Write, Some(db.to_string())),
+            Some(tenant_id),
+        );
+        let user = self
+            .dbms
+            .authenticate(user_info, Some(tenant))
+            .await
+            .map_err(|e| Status::permission_denied(e.to_string()))?;
+        if !user.check_privilege(&privilege) {
+            return Err(Status::permission_denied(format!(
+                "user {} has no privilege {:?}",
+                user_info.user, privilege
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for OtlpService {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let start = Instant::now();
+        let (tenant, db, user) = self
+            .get_tenant_db_and_check_privilege(request.metadata())
+            .await?;
+
+        let req_len = request.get_ref().encoded_len();
+        let metrics = otlp_metrics_to_points(request.get_ref());
+        let lines = metrics.lines();
+        let resp = if lines.is_empty() {
+            Ok(0)
+        } else {
+            self.coord
+                .write_lines(&tenant, &db, Precision::NS, lines, None)
+                .await
+        };
+        self.metrics
+            .record_write(&tenant, &user, &db, req_len, start);
+        resp.map_err(|e| Status::internal(format!("failed to write lines to database {}", e)))?;
+
+        let partial_success = (metrics.rejected > 0).then(|| ExportMetricsPartialSuccess {
+            rejected_data_points: metrics.rejected,
+            error_message: "data points without value are rejected".to_string(),
+        });
+        Ok(Response::new(ExportMetricsServiceResponse {
+            partial_success,
+        }))
+    }
+}
+
+struct OtlpMetrics {
+    grpc_otlp_writes: Metric<U64Counter>,
+    grpc_otlp_data_in: Metric<U64Counter>,
+    grpc_otlp_write_duration: Metric<DurationHistogram>,
+}
+
+impl OtlpMetrics {
+    fn new(register: &Arc<MetricsRegister>) -> Self {
+        Self {
+            grpc_otlp_writes: register.metric(
+                "grpc_otlp_writes",
+                "the number of otlp export requests received by the user",
+            ),
+            grpc_otlp_data_in: register
+                .metric("grpc_otlp_data_in", "Count the body of otlp export request"),
+            grpc_otlp_write_duration: register.register_metric(
+                "grpc_otlp_write_duration",
+                "Duration of the otlp export handle",
+                DurationHistogramOptions::default(),
+            ),
+        }
+    }
+
+    fn record_write(&self, tenant: &str, user: &str, db: &str, req_len: usize, start: Instant) {
+        let labels = [("tenant", tenant), ("user", user), ("database", db)];
+        self.grpc_otlp_writes.recorder(labels).inc_one();
+        self.grpc_otlp_data_in.recorder(labels).inc(req_len as u64);
+        self.grpc_otlp_write_duration
+            .recorder(labels)
+            .record(start.elapsed());
+    }
+}
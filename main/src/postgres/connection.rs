@@ -0,0 +1,900 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use datafusion::arrow::datatypes::{DataType, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use futures::StreamExt;
+use models::auth::user::{User, UserInfo};
+use models::schema::{DEFAULT_CATALOG, DEFAULT_DATABASE};
+use parking_lot::Mutex;
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::Plan;
+use spi::server::dbms::DBMSRef;
+use spi::service::protocol::{ContextBuilder, Query, QueryId};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsAcceptor;
+use trace::debug;
+
+use super::message::{
+    BackendMessage, FrontendMessage, StartupMessage, Target, MAX_MESSAGE_LEN,
+    MAX_STARTUP_MESSAGE_LEN,
+};
+use super::sql::{split_statements, LocalStatement};
+use super::types::{self, Format};
+use super::{BackendKey, BackendKeys, Error, Result};
+use crate::tls::MaybeTlsStream;
+
+/// Size of the buffered messages to be written to the client before flushing.
+const WRITE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Parameters reported to the client after the authentication,
+/// drivers and tools check them to decide the behaviours.
+const SERVER_PARAMETERS: &[(&str, &str)] = &[
+    ("server_version", "14.0"),
+    ("server_encoding", "UTF8"),
+    ("client_encoding", "UTF8"),
+    ("DateStyle", "ISO, MDY"),
+    ("TimeZone", "UTC"),
+    ("integer_datetimes", "on"),
+    ("standard_conforming_strings", "on"),
+];
+
+/// A connection of the postgres client, serves the simple and the extended query protocols.
+pub struct Connection {
+    stream: BufStream<MaybeTlsStream>,
+    write_buf: BytesMut,
+    /// The connection must be encrypted before the authentication if tls is enabled.
+    tls_acceptor: Option<TlsAcceptor>,
+    dbms: DBMSRef,
+    backend_keys: BackendKeys,
+    process_id: i32,
+    running_query: Arc<Mutex<Option<QueryId>>>,
+}
+
+struct Session {
+    user: User,
+    tenant: String,
+    database: String,
+    statements: HashMap<String, Statement>,
+    portals: HashMap<String, Portal>,
+}
+
+#[derive(Clone)]
+enum Statement {
+    /// Statements handled by the connection instead of the query engine.
+    Local(LocalStatement),
+    Query {
+        sql: String,
+        plan: Option<Plan>,
+        /// The postgres type and the arrow type of the parameters `$1, $2...`.
+        param_types: Vec<(u32, DataType)>,
+    },
+}
+
+struct Portal {
+    schema: SchemaRef,
+    formats: Vec<Format>,
+    state: PortalState,
+}
+
+enum PortalState {
+    Local(LocalStatement),
+    Bound {
+        sql: String,
+        plan: Option<Plan>,
+    },
+    Running {
+        output: Output,
+        batch: Option<(RecordBatch, usize)>,
+        rows: usize,
+    },
+    Complete,
+}
+
+impl Connection {
+    pub fn new(
+        stream: TcpStream,
+        dbms: DBMSRef,
+        backend_keys: BackendKeys,
+        process_id: i32,
+        tls_acceptor: Option<TlsAcceptor>,
+    ) -> Self {
+        Self {
+            stream: BufStream::new(MaybeTlsStream::Plain(stream)),
+            write_buf: BytesMut::with_capacity(WRITE_BUFFER_SIZE),
+            tls_acceptor,
+            dbms,
+            backend_keys,
+            process_id,
+            running_query: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        let mut message = self.read_startup_message().await;
+        if let (true, Some(acceptor)) = (
+            matches!(message, Ok(StartupMessage::SslRequest)),
+            self.tls_acceptor.clone(),
+        ) {
+            let stream = self
+                .stream
+                .into_inner()
+                .upgrade(&acceptor)
+                .await
+                .map_err(|e| Error::Io { source: e })?;
+            self.stream = BufStream::new(stream);
+            message = self.read_startup_message().await;
+        }
+
+        let result = match message {
+            Ok(message) => match self.startup(message).await {
+                Ok(Some(mut session)) => self.serve(&mut session).await,
+                Ok(None) => Ok(()),
+                Err(e) => self.report_fatal(e).await,
+            },
+            Err(e) => self.report_fatal(e).await,
+        };
+        self.backend_keys.remove(&self.process_id);
+        result
+    }
+
+    /// Serve the messages after the startup until the client terminates the connection.
+    async fn serve(&mut self, session: &mut Session) -> Result<()> {
+        // the messages of the extended query protocol are ignored after an error until Sync
+        let mut ignore_till_sync = false;
+
+        loop {
+            let message = match self.read_message().await {
+                Ok(Some(message)) => message,
+                Ok(None) => return Ok(()),
+                Err(e) => return self.report_fatal(e).await,
+            };
+            debug!(
+                "postgres connection {} receive: {:?}",
+                self.process_id, message
+            );
+
+            let result = match message {
+                FrontendMessage::Query(sql) => {
+                    if let Err(e) = self.simple_query(session, &sql).await {
+                        self.report_error(e)?;
+                    }
+                    ignore_till_sync = false;
+                    self.send(BackendMessage::ReadyForQuery);
+                    self.flush().await
+                }
+                FrontendMessage::Sync => {
+                    ignore_till_sync = false;
+                    self.send(BackendMessage::ReadyForQuery);
+                    self.flush().await
+                }
+                FrontendMessage::Flush => self.flush().await,
+                FrontendMessage::Terminate => return Ok(()),
+                _ if ignore_till_sync => Ok(()),
+                FrontendMessage::Parse {
+                    statement,
+                    query,
+                    param_types,
+                } => self.parse(session, statement, query, param_types).await,
+                FrontendMessage::Bind {
+                    portal,
+                    statement,
+                    param_formats,
+                    params,
+                    result_formats,
+                } => self.bind(
+                    session,
+                    portal,
+                    &statement,
+                    &param_formats,
+                    params,
+                    &result_formats,
+                ),
+                FrontendMessage::Describe { target, name } => self.describe(session, target, &name),
+                FrontendMessage::Execute { portal, max_rows } => {
+                    self.execute(session, &portal, max_rows).await
+                }
+                FrontendMessage::Close { target, name } => {
+                    match target {
+                        Target::Statement => {
+                            session.statements.remove(&name);
+                        }
+                        Target::Portal => {
+                            session.portals.remove(&name);
+                        }
+                    }
+                    self.send(BackendMessage::CloseComplete);
+                    Ok(())
+                }
+                FrontendMessage::Password(_) => Err(Error::Protocol {
+                    reason: "unexpected password message".to_string(),
+                }),
+            };
+
+            if let Err(e) = result {
+                if e.is_fatal() {
+                    return self.report_fatal(e).await;
+                }
+                self.report_error(e)?;
+                ignore_till_sync = true;
+            }
+        }
+    }
+
+    /// Read the first message which is not a refused encryption request. The SSL request
+    /// is accepted with `S` and returned if tls is enabled, the client starts the TLS
+    /// handshake then, otherwise the client continues with the plain connection.
+    async fn read_startup_message(&mut self) -> Result<StartupMessage> {
+        loop {
+            let len = self
+                .stream
+                .read_i32()
+                .await
+                .map_err(|e| Error::Io { source: e })?;
+            let body = self.read_body(len, MAX_STARTUP_MESSAGE_LEN).await?;
+            let message = StartupMessage::decode(body)?;
+            let accept_ssl = self.tls_acceptor.is_some() && !self.stream.get_ref().is_tls();
+            let reply = match message {
+                StartupMessage::SslRequest if accept_ssl => b"S",
+                StartupMessage::SslRequest | StartupMessage::GssEncRequest => b"N",
+                _ => return Ok(message),
+            };
+            self.stream
+                .write_all(reply)
+                .await
+                .map_err(|e| Error::Io { source: e })?;
+            self.stream
+                .flush()
+                .await
+                .map_err(|e| Error::Io { source: e })?;
+            if reply == b"S" {
+                return Ok(message);
+            }
+        }
+    }
+
+    /// Negotiate the protocol and authenticate the user,
+    /// returns `None` if the connection only requests to cancel a query.
+    async fn startup(&mut self, message: StartupMessage) -> Result<Option<Session>> {
+        let mut params = match message {
+            StartupMessage::CancelRequest {
+                process_id,
+                secret_key,
+            } => {
+                self.cancel(process_id, secret_key);
+                return Ok(None);
+            }
+            StartupMessage::Startup { params } => params,
+            StartupMessage::SslRequest | StartupMessage::GssEncRequest => {
+                return Err(Error::Protocol {
+                    reason: "unexpected encryption request".to_string(),
+                })
+            }
+        };
+        // the password is sent in clear text, so it must be encrypted by tls if enabled
+        if self.tls_acceptor.is_some() && !self.stream.get_ref().is_tls() {
+            return Err(Error::SslRequired);
+        }
+        parse_options(&mut params);
+
+        let user_name = params.remove("user").ok_or_else(|| Error::Protocol {
+            reason: "user is required by the startup message".to_string(),
+        })?;
+        // the database of postgres is the tenant, and the schema is the database
+        let tenant = params
+            .remove("database")
+            .filter(|e| !e.is_empty())
+            .unwrap_or_else(|| DEFAULT_CATALOG.to_string());
+        let database = params
+            .get("search_path")
+            .and_then(|e| search_path_database(e))
+            .unwrap_or_else(|| DEFAULT_DATABASE.to_string());
+        let private_key = params
+            .get("private_key")
+            .map(|e| {
+                base64::decode(e)
+                    .ok()
+                    .and_then(|e| String::from_utf8(e).ok())
+                    .ok_or_else(|| Error::Protocol {
+                        reason: format!("can not parse private_key with base64: {}", e),
+                    })
+            })
+            .transpose()?;
+
+        self.send(BackendMessage::AuthenticationCleartextPassword);
+        self.flush().await?;
+        let password = match self
+            .read_message_with_limit(MAX_STARTUP_MESSAGE_LEN)
+            .await?
+        {
+            Some(FrontendMessage::Password(password)) => password,
+            Some(_) => {
+                return Err(Error::Protocol {
+                    reason: "expect password message".to_string(),
+                })
+            }
+            None => return Ok(None),
+        };
+
+        let user_info = UserInfo {
+            user: user_name,
+            password,
+            private_key,
+        };
+        let user = self
+            .dbms
+            .authenticate(&user_info, Some(&tenant))
+            .await
+            .map_err(|e| Error::Authentication { source: e })?;
+        debug!(
+            "postgres connection {} authenticated, user: {}, tenant: {}",
+            self.process_id, user_info.user, tenant
+        );
+
+        let secret_key = models::oid::uuid_u64() as i32;
+        self.backend_keys.insert(
+            self.process_id,
+            BackendKey {
+                secret_key,
+                running_query: self.running_query.clone(),
+            },
+        );
+
+        self.send(BackendMessage::AuthenticationOk);
+        for (name, value) in SERVER_PARAMETERS {
+            self.send(BackendMessage::ParameterStatus {
+                name: name.to_string(),
+                value: value.to_string(),
+            });
+        }
+        if let Some(application_name) = params.get("application_name") {
+            self.send(BackendMessage::ParameterStatus {
+                name: "application_name".to_string(),
+                value: application_name.clone(),
+            });
+        }
+        self.send(BackendMessage::BackendKeyData {
+            process_id: self.process_id,
+            secret_key,
+        });
+        self.send(BackendMessage::ReadyForQuery);
+        self.flush().await?;
+
+        Ok(Some(Session {
+            user,
+            tenant,
+            database,
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+        }))
+    }
+
+    fn cancel(&self, process_id: i32, secret_key: i32) {
+        let query_id = match self.backend_keys.get(&process_id) {
+            Some(key) if key.secret_key == secret_key => *key.running_query.lock(),
+            _ => None,
+        };
+        if let Some(query_id) = query_id {
+            debug!(
+                "postgres cancel query {} of connection {}",
+                query_id, process_id
+            );
+            self.dbms.cancel(&query_id);
+        }
+    }
+
+    /// Execute the statements of the simple query one by one, stop at the first error.
+    async fn simple_query(&mut self, session: &mut Session, sql: &str) -> Result<()> {
+        let statements = split_statements(sql);
+        if statements.is_empty() {
+            self.send(BackendMessage::EmptyQueryResponse);
+            return Ok(());
+        }
+
+        for sql in statements {
+            let statement = self.prepare(session, sql, &[]).await?;
+            let mut portal = bind_portal(statement, vec![], &[])?;
+            self.execute_portal(session, &mut portal, 0, true).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn parse(
+        &mut self,
+        session: &mut Session,
+        name: String,
+        sql: String,
+        param_types: Vec<u32>,
+    ) -> Result<()> {
+        let statement = self.prepare(session, sql, &param_types).await?;
+        session.statements.insert(name, statement);
+        self.send(BackendMessage::ParseComplete);
+        Ok(())
+    }
+
+    async fn prepare(
+        &self,
+        session: &Session,
+        sql: String,
+        param_types: &[u32],
+    ) -> Result<Statement> {
+        if let Some(statement) = LocalStatement::parse(&sql) {
+            return Ok(Statement::Local(statement));
+        }
+
+        let query_state_machine = self.build_query_state_machine(session, &sql).await?;
+        let plan = self
+            .dbms
+            .build_logical_plan(query_state_machine)
+            .await
+            .map_err(|e| Error::Query { source: e })?;
+
+        let inferred_types = match &plan {
+            Some(plan) => plan
+                .parameter_types()
+                .map_err(|e| Error::Query { source: e })?,
+            None => HashMap::new(),
+        };
+        let num_params = inferred_types
+            .keys()
+            .filter_map(|e| e.trim_start_matches('$').parse::<usize>().ok())
+            .max()
+            .unwrap_or_default()
+            .max(param_types.len());
+        let param_types = (0..num_params)
+            .map(|idx| {
+                let type_oid = param_types
+                    .get(idx)
+                    .copied()
+                    .unwrap_or(types::oid::UNSPECIFIED);
+                let data_type = inferred_types
+                    .get(&format!("${}", idx + 1))
+                    .cloned()
+                    .flatten()
+                    .or_else(|| types::data_type_of(type_oid))
+                    .unwrap_or(DataType::Utf8);
+                let type_oid = match type_oid {
+                    types::oid::UNSPECIFIED => types::pg_type(&data_type),
+                    _ => type_oid,
+                };
+                (type_oid, data_type)
+            })
+            .collect();
+
+        Ok(Statement::Query {
+            sql,
+            plan,
+            param_types,
+        })
+    }
+
+    /// Every execution of a statement is a new query, which has its own query id.
+    async fn build_query_state_machine(
+        &self,
+        session: &Session,
+        sql: &str,
+    ) -> Result<QueryStateMachineRef> {
+        let ctx = ContextBuilder::new(session.user.clone())
+            .with_tenant(Some(session.tenant.clone()))
+            .with_database(Some(session.database.clone()))
+            .build();
+        let query = Query::new(ctx, sql.to_string());
+        self.dbms
+            .build_query_state_machine(query, None)
+            .await
+            .map_err(|e| Error::Query { source: e })
+    }
+
+    fn bind(
+        &mut self,
+        session: &mut Session,
+        portal: String,
+        statement: &str,
+        param_formats: &[i16],
+        params: Vec<Option<Bytes>>,
+        result_formats: &[i16],
+    ) -> Result<()> {
+        let statement =
+            session
+                .statements
+                .get(statement)
+                .cloned()
+                .ok_or_else(|| Error::StatementNotFound {
+                    name: statement.to_string(),
+                })?;
+        let param_formats = types::formats(param_formats, params.len())?;
+        let params = params
+            .iter()
+            .zip(param_formats)
+            .map(|(value, format)| (value.as_deref(), format))
+            .collect::<Vec<_>>();
+        let bound = bind_portal(statement, params, result_formats)?;
+        session.portals.insert(portal, bound);
+        self.send(BackendMessage::BindComplete);
+        Ok(())
+    }
+
+    fn describe(&mut self, session: &Session, target: Target, name: &str) -> Result<()> {
+        let (schema, formats) = match target {
+            Target::Statement => {
+                let statement =
+                    session
+                        .statements
+                        .get(name)
+                        .ok_or_else(|| Error::StatementNotFound {
+                            name: name.to_string(),
+                        })?;
+                let (param_types, schema) = match statement {
+                    Statement::Local(_) => (vec![], Arc::new(Schema::empty())),
+                    Statement::Query {
+                        plan, param_types, ..
+                    } => (
+                        param_types.iter().map(|(oid, _)| *oid).collect(),
+                        plan_schema(plan.as_ref()),
+                    ),
+                };
+                self.send(BackendMessage::ParameterDescription(param_types));
+                // the formats of the results are unknown until bind
+                let formats = vec![Format::Text; schema.fields().len()];
+                (schema, formats)
+            }
+            Target::Portal => {
+                let portal = session
+                    .portals
+                    .get(name)
+                    .ok_or_else(|| Error::PortalNotFound {
+                        name: name.to_string(),
+                    })?;
+                (portal.schema.clone(), portal.formats.clone())
+            }
+        };
+
+        if schema.fields().is_empty() {
+            self.send(BackendMessage::NoData);
+        } else {
+            self.send(BackendMessage::RowDescription(types::field_descriptions(
+                &schema, &formats,
+            )));
+        }
+        Ok(())
+    }
+
+    async fn execute(&mut self, session: &mut Session, name: &str, max_rows: i32) -> Result<()> {
+        let mut portal = session
+            .portals
+            .remove(name)
+            .ok_or_else(|| Error::PortalNotFound {
+                name: name.to_string(),
+            })?;
+        let result = self
+            .execute_portal(session, &mut portal, max_rows, false)
+            .await;
+        session.portals.insert(name.to_string(), portal);
+        result
+    }
+
+    /// Execute the portal and send at most `max_rows` rows, 0 means no limit.
+    ///
+    /// The result set is described before the rows if `describe` is set,
+    /// by the simple query protocol which has no Describe message.
+    async fn execute_portal(
+        &mut self,
+        session: &mut Session,
+        portal: &mut Portal,
+        max_rows: i32,
+        describe: bool,
+    ) -> Result<()> {
+        let state = std::mem::replace(&mut portal.state, PortalState::Complete);
+        let (mut output, mut batch, mut rows) = match state {
+            PortalState::Local(statement) => {
+                if let LocalStatement::SetSearchPath(path) = &statement {
+                    if let Some(database) = search_path_database(path) {
+                        session.database = database;
+                    }
+                }
+                match statement.command_tag() {
+                    Some(tag) => self.send(BackendMessage::CommandComplete(tag)),
+                    None => self.send(BackendMessage::EmptyQueryResponse),
+                }
+                return Ok(());
+            }
+            PortalState::Bound { sql, plan } => {
+                let plan = match plan {
+                    Some(plan) => plan,
+                    None => {
+                        self.send(BackendMessage::EmptyQueryResponse);
+                        return Ok(());
+                    }
+                };
+                let query_state_machine = self.build_query_state_machine(session, &sql).await?;
+                *self.running_query.lock() = Some(query_state_machine.query_id);
+                let output = self
+                    .dbms
+                    .execute_logical_plan(plan, query_state_machine)
+                    .await
+                    .map(|e| e.result());
+                let output = match output {
+                    Ok(output) => output,
+                    Err(e) => {
+                        *self.running_query.lock() = None;
+                        return Err(Error::Query { source: e });
+                    }
+                };
+                let schema = output.schema();
+                if schema.fields().is_empty() {
+                    *self.running_query.lock() = None;
+                    self.send(BackendMessage::CommandComplete(command_tag(&sql)));
+                    return Ok(());
+                }
+                if describe {
+                    portal.formats = vec![Format::Text; schema.fields().len()];
+                    self.send(BackendMessage::RowDescription(types::field_descriptions(
+                        &schema,
+                        &portal.formats,
+                    )));
+                }
+                (output, None, 0)
+            }
+            PortalState::Running {
+                output,
+                batch,
+                rows,
+            } => (output, batch, rows),
+            PortalState::Complete => {
+                return Err(Error::Protocol {
+                    reason: "portal has been executed".to_string(),
+                })
+            }
+        };
+
+        let mut sent = 0;
+        let result = loop {
+            let (record_batch, row) = match batch.take() {
+                Some((record_batch, row)) if row < record_batch.num_rows() => (record_batch, row),
+                _ => match output.next().await {
+                    Some(Ok(record_batch)) => {
+                        batch = Some((record_batch, 0));
+                        continue;
+                    }
+                    Some(Err(e)) => break Err(Error::Query { source: e }),
+                    None => {
+                        self.send(BackendMessage::CommandComplete(format!("SELECT {}", rows)));
+                        break Ok(());
+                    }
+                },
+            };
+
+            if max_rows > 0 && sent == max_rows {
+                batch = Some((record_batch, row));
+                portal.state = PortalState::Running {
+                    output,
+                    batch,
+                    rows,
+                };
+                self.send(BackendMessage::PortalSuspended);
+                return Ok(());
+            }
+
+            if let Err(e) = types::encode_data_row(
+                &mut self.write_buf,
+                record_batch.columns(),
+                row,
+                &portal.formats,
+            ) {
+                break Err(e);
+            }
+            batch = Some((record_batch, row + 1));
+            rows += 1;
+            sent += 1;
+            if self.write_buf.len() >= WRITE_BUFFER_SIZE {
+                if let Err(e) = self.flush().await {
+                    break Err(e);
+                }
+            }
+        };
+        *self.running_query.lock() = None;
+
+        result
+    }
+
+    fn send(&mut self, message: BackendMessage) {
+        message.encode(&mut self.write_buf);
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.stream
+            .write_all(&self.write_buf)
+            .await
+            .map_err(|e| Error::Io { source: e })?;
+        self.write_buf.clear();
+        self.stream
+            .flush()
+            .await
+            .map_err(|e| Error::Io { source: e })
+    }
+
+    fn report_error(&mut self, e: Error) -> Result<()> {
+        if let Error::Io { .. } = e {
+            return Err(e);
+        }
+        debug!("postgres connection {} error: {}", self.process_id, e);
+        self.send(e.to_error_response());
+        Ok(())
+    }
+
+    /// Report the error to the client and close the connection.
+    async fn report_fatal(&mut self, e: Error) -> Result<()> {
+        self.report_error(e)?;
+        self.flush().await
+    }
+
+    /// Read a message of the client, returns `None` if the connection is closed.
+    async fn read_message(&mut self) -> Result<Option<FrontendMessage>> {
+        self.read_message_with_limit(MAX_MESSAGE_LEN).await
+    }
+
+    async fn read_message_with_limit(&mut self, max_len: usize) -> Result<Option<FrontendMessage>> {
+        let tag = match self.stream.read_u8().await {
+            Ok(tag) => tag,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(Error::Io { source: e }),
+        };
+        let len = self
+            .stream
+            .read_i32()
+            .await
+            .map_err(|e| Error::Io { source: e })?;
+        let body = self.read_body(len, max_len).await?;
+        FrontendMessage::decode(tag, body).map(Some)
+    }
+
+    /// Read the body of a message, the length includes itself.
+    async fn read_body(&mut self, len: i32, max_len: usize) -> Result<Bytes> {
+        if len < 4 || len as usize > max_len {
+            return Err(Error::Protocol {
+                reason: format!("invalid message length {}", len),
+            });
+        }
+        let mut body = vec![0; len as usize - 4];
+        self.stream
+            .read_exact(&mut body)
+            .await
+            .map_err(|e| Error::Io { source: e })?;
+        Ok(body.into())
+    }
+}
+
+/// Bind the parameters to the statement, and decide the formats of the results.
+fn bind_portal(
+    statement: Statement,
+    params: Vec<(Option<&[u8]>, Format)>,
+    result_formats: &[i16],
+) -> Result<Portal> {
+    let (schema, state) = match statement {
+        Statement::Local(statement) => (Arc::new(Schema::empty()), PortalState::Local(statement)),
+        Statement::Query {
+            sql,
+            plan,
+            param_types,
+        } => {
+            if params.len() != param_types.len() {
+                return Err(Error::InvalidParameter {
+                    reason: format!(
+                        "statement requires {} parameters, but got {}",
+                        param_types.len(),
+                        params.len()
+                    ),
+                });
+            }
+            let param_values = params
+                .into_iter()
+                .zip(param_types)
+                .map(|((value, format), (type_oid, data_type))| {
+                    types::decode_param(value, format, type_oid, &data_type)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let plan = plan
+                .map(|e| e.with_param_values(param_values))
+                .transpose()
+                .map_err(|e| Error::Query { source: e })?;
+            let schema = plan_schema(plan.as_ref());
+            (schema, PortalState::Bound { sql, plan })
+        }
+    };
+    let formats = types::formats(result_formats, schema.fields().len())?;
+
+    Ok(Portal {
+        schema,
+        formats,
+        state,
+    })
+}
+
+fn plan_schema(plan: Option<&Plan>) -> SchemaRef {
+    plan.map(|e| e.schema())
+        .unwrap_or_else(|| Arc::new(Schema::empty()))
+}
+
+/// Tag of the CommandComplete of the statements without result set,
+/// the first keyword, or the first two keywords of CREATE, DROP and ALTER.
+fn command_tag(sql: &str) -> String {
+    let mut words = sql.split_whitespace().map(|e| e.to_uppercase());
+    match words.next() {
+        Some(first) if matches!(first.as_str(), "CREATE" | "DROP" | "ALTER") => {
+            match words.next() {
+                Some(second) => format!("{} {}", first, second),
+                None => first,
+            }
+        }
+        Some(first) => first,
+        None => String::new(),
+    }
+}
+
+/// The database of the search path, the first schema of it.
+fn search_path_database(search_path: &str) -> Option<String> {
+    search_path
+        .split(',')
+        .map(|e| e.trim().trim_matches(|c| c == '"' || c == '\''))
+        .find(|e| !e.is_empty())
+        .map(|e| e.to_string())
+}
+
+/// Merge the run-time parameters `-c name=value` and `--name=value` of
+/// the `options` startup parameter into the startup parameters.
+fn parse_options(params: &mut HashMap<String, String>) {
+    let options = match params.remove("options") {
+        Some(options) => options,
+        None => return,
+    };
+    let mut args = options.split_whitespace();
+    while let Some(arg) = args.next() {
+        let option = match arg.strip_prefix("--") {
+            Some(option) => Some(option),
+            None if arg == "-c" => args.next(),
+            None => arg.strip_prefix("-c"),
+        };
+        if let Some((name, value)) = option.and_then(|e| e.split_once('=')) {
+            params.insert(name.replace('-', "_"), value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{command_tag, parse_options, search_path_database};
+
+    #[test]
+    fn test_parse_options() {
+        let mut params = HashMap::from([(
+            "options".to_string(),
+            "-c search_path=air --private-key=a2V5 -csome=1".to_string(),
+        )]);
+        parse_options(&mut params);
+        assert_eq!(params.get("search_path").unwrap(), "air");
+        assert_eq!(params.get("private_key").unwrap(), "a2V5");
+        assert_eq!(params.get("some").unwrap(), "1");
+        assert!(!params.contains_key("options"));
+    }
+
+    #[test]
+    fn test_search_path_database() {
+        assert_eq!(
+            search_path_database("\"air\", public").as_deref(),
+            Some("air")
+        );
+        assert_eq!(search_path_database(" , ").as_deref(), None);
+    }
+
+    #[test]
+    fn test_command_tag() {
+        assert_eq!(command_tag("create table air (a bigint)"), "CREATE TABLE");
+        assert_eq!(command_tag("drop\n database db"), "DROP DATABASE");
+        assert_eq!(command_tag("delete from air"), "DELETE");
+    }
+}
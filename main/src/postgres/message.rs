@@ -0,0 +1,436 @@
+//! Messages of the [PostgreSQL frontend/backend protocol](https://www.postgresql.org/docs/current/protocol-message-formats.html) version 3.0.
+
+use std::collections::HashMap;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::{Error, Result};
+
+pub const PROTOCOL_VERSION_3: i32 = 196608;
+pub const SSL_REQUEST_CODE: i32 = 80877103;
+pub const GSSENC_REQUEST_CODE: i32 = 80877104;
+pub const CANCEL_REQUEST_CODE: i32 = 80877102;
+
+/// Max length of a message, messages longer than it are rejected.
+pub const MAX_MESSAGE_LEN: usize = 1 << 30;
+/// Max length of the messages before the authentication, the startup and the password
+/// messages, same as the `MAX_STARTUP_PACKET_LENGTH` of postgres.
+pub const MAX_STARTUP_MESSAGE_LEN: usize = 10000;
+
+/// The first message of a connection, it has no tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupMessage {
+    SslRequest,
+    GssEncRequest,
+    CancelRequest { process_id: i32, secret_key: i32 },
+    Startup { params: HashMap<String, String> },
+}
+
+impl StartupMessage {
+    /// Decode the body of the startup message, the length is excluded.
+    pub fn decode(mut body: Bytes) -> Result<Self> {
+        let code = get_i32(&mut body)?;
+        match code {
+            SSL_REQUEST_CODE => Ok(Self::SslRequest),
+            GSSENC_REQUEST_CODE => Ok(Self::GssEncRequest),
+            CANCEL_REQUEST_CODE => {
+                let process_id = get_i32(&mut body)?;
+                let secret_key = get_i32(&mut body)?;
+                Ok(Self::CancelRequest {
+                    process_id,
+                    secret_key,
+                })
+            }
+            PROTOCOL_VERSION_3 => {
+                let mut params = HashMap::new();
+                loop {
+                    let name = get_cstr(&mut body)?;
+                    if name.is_empty() {
+                        break;
+                    }
+                    let value = get_cstr(&mut body)?;
+                    params.insert(name, value);
+                }
+                Ok(Self::Startup { params })
+            }
+            _ => Err(Error::Protocol {
+                reason: format!(
+                    "unsupported protocol version {}.{}",
+                    code >> 16,
+                    code & 0xffff
+                ),
+            }),
+        }
+    }
+}
+
+/// Messages sent by the client after the startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrontendMessage {
+    Password(String),
+    Query(String),
+    Parse {
+        statement: String,
+        query: String,
+        param_types: Vec<u32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Bytes>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        target: Target,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        target: Target,
+        name: String,
+    },
+    Sync,
+    Flush,
+    Terminate,
+}
+
+/// The target of Describe and Close, a prepared statement or a portal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Statement,
+    Portal,
+}
+
+impl FrontendMessage {
+    pub fn decode(tag: u8, mut body: Bytes) -> Result<Self> {
+        let message = match tag {
+            b'p' => Self::Password(get_cstr(&mut body)?),
+            b'Q' => Self::Query(get_cstr(&mut body)?),
+            b'P' => {
+                let statement = get_cstr(&mut body)?;
+                let query = get_cstr(&mut body)?;
+                let num = get_i16(&mut body)?;
+                let param_types = (0..num)
+                    .map(|_| get_i32(&mut body).map(|e| e as u32))
+                    .collect::<Result<Vec<_>>>()?;
+                Self::Parse {
+                    statement,
+                    query,
+                    param_types,
+                }
+            }
+            b'B' => {
+                let portal = get_cstr(&mut body)?;
+                let statement = get_cstr(&mut body)?;
+                let param_formats = get_i16_array(&mut body)?;
+                let num = get_i16(&mut body)?;
+                let params = (0..num)
+                    .map(|_| {
+                        let len = get_i32(&mut body)?;
+                        if len < 0 {
+                            return Ok(None);
+                        }
+                        let len = len as usize;
+                        ensure_remaining(&body, len)?;
+                        Ok(Some(body.split_to(len)))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let result_formats = get_i16_array(&mut body)?;
+                Self::Bind {
+                    portal,
+                    statement,
+                    param_formats,
+                    params,
+                    result_formats,
+                }
+            }
+            b'D' => Self::Describe {
+                target: get_target(&mut body)?,
+                name: get_cstr(&mut body)?,
+            },
+            b'E' => Self::Execute {
+                portal: get_cstr(&mut body)?,
+                max_rows: get_i32(&mut body)?,
+            },
+            b'C' => Self::Close {
+                target: get_target(&mut body)?,
+                name: get_cstr(&mut body)?,
+            },
+            b'S' => Self::Sync,
+            b'H' => Self::Flush,
+            b'X' => Self::Terminate,
+            _ => {
+                return Err(Error::Protocol {
+                    reason: format!("unsupported message type '{}'", tag as char),
+                })
+            }
+        };
+
+        Ok(message)
+    }
+}
+
+/// Messages sent by the server, except DataRow which is encoded by [`super::types::encode_data_row`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackendMessage {
+    AuthenticationOk,
+    AuthenticationCleartextPassword,
+    ParameterStatus {
+        name: String,
+        value: String,
+    },
+    BackendKeyData {
+        process_id: i32,
+        secret_key: i32,
+    },
+    ReadyForQuery,
+    RowDescription(Vec<FieldDescription>),
+    ParameterDescription(Vec<u32>),
+    NoData,
+    CommandComplete(String),
+    EmptyQueryResponse,
+    PortalSuspended,
+    ParseComplete,
+    BindComplete,
+    CloseComplete,
+    ErrorResponse {
+        code: String,
+        message: String,
+        detail: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDescription {
+    pub name: String,
+    pub type_oid: u32,
+    pub type_len: i16,
+    pub format: i16,
+}
+
+impl BackendMessage {
+    pub fn encode(&self, buf: &mut BytesMut) {
+        match self {
+            Self::AuthenticationOk => write_message(buf, b'R', |buf| buf.put_i32(0)),
+            Self::AuthenticationCleartextPassword => write_message(buf, b'R', |buf| buf.put_i32(3)),
+            Self::ParameterStatus { name, value } => write_message(buf, b'S', |buf| {
+                put_cstr(buf, name);
+                put_cstr(buf, value);
+            }),
+            Self::BackendKeyData {
+                process_id,
+                secret_key,
+            } => write_message(buf, b'K', |buf| {
+                buf.put_i32(*process_id);
+                buf.put_i32(*secret_key);
+            }),
+            // transactions are not supported, the status is always idle
+            Self::ReadyForQuery => write_message(buf, b'Z', |buf| buf.put_u8(b'I')),
+            Self::RowDescription(fields) => write_message(buf, b'T', |buf| {
+                buf.put_i16(fields.len() as i16);
+                for field in fields {
+                    put_cstr(buf, &field.name);
+                    // table oid and column attribute number
+                    buf.put_i32(0);
+                    buf.put_i16(0);
+                    buf.put_u32(field.type_oid);
+                    buf.put_i16(field.type_len);
+                    // type modifier
+                    buf.put_i32(-1);
+                    buf.put_i16(field.format);
+                }
+            }),
+            Self::ParameterDescription(types) => write_message(buf, b't', |buf| {
+                buf.put_i16(types.len() as i16);
+                for oid in types {
+                    buf.put_u32(*oid);
+                }
+            }),
+            Self::NoData => write_message(buf, b'n', |_| {}),
+            Self::CommandComplete(tag) => write_message(buf, b'C', |buf| put_cstr(buf, tag)),
+            Self::EmptyQueryResponse => write_message(buf, b'I', |_| {}),
+            Self::PortalSuspended => write_message(buf, b's', |_| {}),
+            Self::ParseComplete => write_message(buf, b'1', |_| {}),
+            Self::BindComplete => write_message(buf, b'2', |_| {}),
+            Self::CloseComplete => write_message(buf, b'3', |_| {}),
+            Self::ErrorResponse {
+                code,
+                message,
+                detail,
+            } => write_message(buf, b'E', |buf| {
+                buf.put_u8(b'S');
+                put_cstr(buf, "ERROR");
+                buf.put_u8(b'V');
+                put_cstr(buf, "ERROR");
+                buf.put_u8(b'C');
+                put_cstr(buf, code);
+                buf.put_u8(b'M');
+                put_cstr(buf, message);
+                if let Some(detail) = detail {
+                    buf.put_u8(b'D');
+                    put_cstr(buf, detail);
+                }
+                buf.put_u8(0);
+            }),
+        }
+    }
+}
+
+/// Write a message with the tag, the length is filled after the body is written.
+pub fn write_message(buf: &mut BytesMut, tag: u8, body: impl FnOnce(&mut BytesMut)) {
+    buf.put_u8(tag);
+    let start = buf.len();
+    buf.put_i32(0);
+    body(buf);
+    let len = (buf.len() - start) as i32;
+    buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+}
+
+fn put_cstr(buf: &mut BytesMut, s: &str) {
+    buf.put_slice(s.as_bytes());
+    buf.put_u8(0);
+}
+
+fn ensure_remaining(buf: &Bytes, len: usize) -> Result<()> {
+    if buf.remaining() < len {
+        return Err(Error::Protocol {
+            reason: "message is shorter than its content".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn get_i16(buf: &mut Bytes) -> Result<i16> {
+    ensure_remaining(buf, 2)?;
+    Ok(buf.get_i16())
+}
+
+fn get_i32(buf: &mut Bytes) -> Result<i32> {
+    ensure_remaining(buf, 4)?;
+    Ok(buf.get_i32())
+}
+
+fn get_i16_array(buf: &mut Bytes) -> Result<Vec<i16>> {
+    let num = get_i16(buf)?;
+    (0..num).map(|_| get_i16(buf)).collect()
+}
+
+fn get_cstr(buf: &mut Bytes) -> Result<String> {
+    let end = buf
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or_else(|| Error::Protocol {
+            reason: "string is not terminated by null".to_string(),
+        })?;
+    let s = buf.split_to(end);
+    buf.advance(1);
+    String::from_utf8(s.to_vec()).map_err(|e| Error::Protocol {
+        reason: format!("string is not utf8: {}", e),
+    })
+}
+
+fn get_target(buf: &mut Bytes) -> Result<Target> {
+    ensure_remaining(buf, 1)?;
+    match buf.get_u8() {
+        b'S' => Ok(Target::Statement),
+        b'P' => Ok(Target::Portal),
+        t => Err(Error::Protocol {
+            reason: format!("invalid target '{}' of describe or close", t as char),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::{BufMut, Bytes, BytesMut};
+
+    use super::{BackendMessage, FrontendMessage, StartupMessage, Target, PROTOCOL_VERSION_3};
+
+    #[test]
+    fn test_decode_startup() {
+        let mut buf = BytesMut::new();
+        buf.put_i32(PROTOCOL_VERSION_3);
+        buf.put_slice(b"user\0root\0database\0cnosdb\0\0");
+        let params = match StartupMessage::decode(buf.freeze()).unwrap() {
+            StartupMessage::Startup { params } => params,
+            m => panic!("expect startup message, but got {:?}", m),
+        };
+        assert_eq!(params.get("user").unwrap(), "root");
+        assert_eq!(params.get("database").unwrap(), "cnosdb");
+
+        let mut buf = BytesMut::new();
+        buf.put_i32(80877103);
+        assert_eq!(
+            StartupMessage::decode(buf.freeze()).unwrap(),
+            StartupMessage::SslRequest
+        );
+
+        let mut buf = BytesMut::new();
+        buf.put_i32(2 << 16);
+        assert!(StartupMessage::decode(buf.freeze()).is_err());
+    }
+
+    #[test]
+    fn test_decode_bind() {
+        let mut buf = BytesMut::new();
+        buf.put_slice(b"p1\0s1\0");
+        buf.put_i16(1);
+        buf.put_i16(1);
+        buf.put_i16(2);
+        buf.put_i32(4);
+        buf.put_i32(42);
+        buf.put_i32(-1);
+        buf.put_i16(0);
+        assert_eq!(
+            FrontendMessage::decode(b'B', buf.freeze()).unwrap(),
+            FrontendMessage::Bind {
+                portal: "p1".to_string(),
+                statement: "s1".to_string(),
+                param_formats: vec![1],
+                params: vec![Some(Bytes::from(42_i32.to_be_bytes().to_vec())), None],
+                result_formats: vec![],
+            }
+        );
+
+        // truncated parameter
+        let mut buf = BytesMut::new();
+        buf.put_slice(b"\0\0");
+        buf.put_i16(0);
+        buf.put_i16(1);
+        buf.put_i32(8);
+        buf.put_i32(42);
+        assert!(FrontendMessage::decode(b'B', buf.freeze()).is_err());
+    }
+
+    #[test]
+    fn test_decode_frontend_message() {
+        assert_eq!(
+            FrontendMessage::decode(b'Q', Bytes::from_static(b"select 1\0")).unwrap(),
+            FrontendMessage::Query("select 1".to_string())
+        );
+        assert_eq!(
+            FrontendMessage::decode(b'D', Bytes::from_static(b"Ss1\0")).unwrap(),
+            FrontendMessage::Describe {
+                target: Target::Statement,
+                name: "s1".to_string()
+            }
+        );
+        assert!(FrontendMessage::decode(b'Q', Bytes::from_static(b"select 1")).is_err());
+        assert!(FrontendMessage::decode(b'?', Bytes::new()).is_err());
+    }
+
+    #[test]
+    fn test_encode_backend_message() {
+        let mut buf = BytesMut::new();
+        BackendMessage::CommandComplete("SELECT 1".to_string()).encode(&mut buf);
+        assert_eq!(&buf[..], b"C\0\0\0\x0dSELECT 1\0");
+
+        let mut buf = BytesMut::new();
+        BackendMessage::ReadyForQuery.encode(&mut buf);
+        assert_eq!(&buf[..], b"Z\0\0\0\x05I");
+    }
+}
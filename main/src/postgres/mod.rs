@@ -0,0 +1,260 @@
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+use config::TLSConfig;
+use dashmap::DashMap;
+use models::error_code::{ErrorCode, ErrorCoder};
+use parking_lot::Mutex;
+use snafu::Snafu;
+use spi::server::dbms::DBMSRef;
+use spi::service::protocol::QueryId;
+use spi::QueryError;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use trace::{debug, info, warn};
+
+use self::connection::Connection;
+use self::message::BackendMessage;
+use crate::server;
+use crate::server::ServiceHandle;
+use crate::spi::service::Service;
+use crate::tls::build_tls_acceptor;
+
+mod connection;
+mod message;
+mod sql;
+mod types;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Snafu, ErrorCoder)]
+#[error_code(mod_code = "07")]
+#[snafu(visibility(pub))]
+pub enum Error {
+    Query {
+        source: QueryError,
+    },
+
+    #[snafu(display("Authentication failed: {}", source))]
+    #[error_code(code = 1)]
+    Authentication {
+        source: QueryError,
+    },
+
+    #[snafu(display("IO error: {}", source))]
+    #[error_code(code = 2)]
+    Io {
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Protocol violation: {}", reason))]
+    #[error_code(code = 3)]
+    Protocol {
+        reason: String,
+    },
+
+    #[snafu(display("Invalid parameter: {}", reason))]
+    #[error_code(code = 4)]
+    InvalidParameter {
+        reason: String,
+    },
+
+    #[snafu(display("Encode value: {}", reason))]
+    #[error_code(code = 5)]
+    Encode {
+        reason: String,
+    },
+
+    #[snafu(display("Not supported: {}", reason))]
+    #[error_code(code = 6)]
+    Unsupported {
+        reason: String,
+    },
+
+    #[snafu(display("Prepared statement \"{}\" does not exist", name))]
+    #[error_code(code = 7)]
+    StatementNotFound {
+        name: String,
+    },
+
+    #[snafu(display("Portal \"{}\" does not exist", name))]
+    #[error_code(code = 8)]
+    PortalNotFound {
+        name: String,
+    },
+
+    #[snafu(display("SSL connection is required"))]
+    #[error_code(code = 9)]
+    SslRequired,
+}
+
+impl Error {
+    pub fn error_code(&self) -> &dyn ErrorCode {
+        match self {
+            Error::Query { source } => source.error_code(),
+            _ => self,
+        }
+    }
+
+    /// The connection is closed after the fatal errors.
+    fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Error::Io { .. }
+                | Error::Protocol { .. }
+                | Error::Authentication { .. }
+                | Error::SslRequired
+        )
+    }
+
+    /// The SQLSTATE of the error, see https://www.postgresql.org/docs/current/errcodes-appendix.html
+    fn sqlstate(&self) -> &'static str {
+        match self {
+            Error::Query { source } => match source {
+                QueryError::Parser { .. } | QueryError::MultiStatement { .. } => "42601",
+                QueryError::InsufficientPrivileges { .. } => "42501",
+                QueryError::Auth { .. } => "28000",
+                QueryError::NotImplemented { .. } | QueryError::Unimplement { .. } => "0A000",
+                QueryError::Analyzer { .. } | QueryError::Semantic { .. } => "42000",
+                QueryError::DatabaseNotFound { .. } => "3F000",
+                QueryError::QueryTimeout { .. } => "57014",
+                _ => "XX000",
+            },
+            Error::Authentication { .. } => "28P01",
+            Error::Io { .. } => "08006",
+            Error::Protocol { .. } => "08P01",
+            Error::InvalidParameter { .. } => "22023",
+            Error::Encode { .. } => "22000",
+            Error::Unsupported { .. } => "0A000",
+            Error::StatementNotFound { .. } => "26000",
+            Error::PortalNotFound { .. } => "34000",
+            Error::SslRequired => "28000",
+        }
+    }
+
+    fn to_error_response(&self) -> BackendMessage {
+        let error_code = self.error_code();
+        BackendMessage::ErrorResponse {
+            code: self.sqlstate().to_string(),
+            message: error_code.message(),
+            detail: Some(format!("error code: {}", error_code.code())),
+        }
+    }
+}
+
+/// Key of a connection for the clients to cancel the running query of it.
+pub struct BackendKey {
+    secret_key: i32,
+    running_query: Arc<Mutex<Option<QueryId>>>,
+}
+
+/// Keys of the connections by the process ids.
+pub type BackendKeys = Arc<DashMap<i32, BackendKey>>;
+
+/// Service of the [PostgreSQL frontend/backend protocol](https://www.postgresql.org/docs/current/protocol.html),
+/// the simple and the extended query protocols are supported.
+///
+/// The database of the client is the tenant, and the search path is the database.
+///
+/// The password is sent in clear text, the clients must encrypt the connection by SSL
+/// before the authentication if the tls of the server is configured.
+pub struct PostgresService {
+    dbms: DBMSRef,
+    addr: String,
+    tls_config: Option<TLSConfig>,
+    handle: Option<ServiceHandle<()>>,
+}
+
+impl PostgresService {
+    pub fn new(dbms: DBMSRef, addr: String, tls_config: Option<TLSConfig>) -> Self {
+        Self {
+            dbms,
+            addr,
+            tls_config,
+            handle: None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Service for PostgresService {
+    fn start(&mut self) -> server::Result<()> {
+        let (shutdown, mut rx) = oneshot::channel();
+        let dbms = self.dbms.clone();
+        let addr = self.addr.clone();
+        let tls_acceptor = self
+            .tls_config
+            .as_ref()
+            .map(build_tls_acceptor)
+            .transpose()
+            .map_err(|e| server::Error::Common {
+                reason: format!("build tls of postgres service: {}", e),
+            })?;
+        let listener = std::net::TcpListener::bind(&addr)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(listener)
+            })
+            .map_err(|e| server::Error::Common {
+                reason: format!("bind postgres service to {}: {}", addr, e),
+            })?;
+
+        let join_handle = tokio::spawn(async move {
+            let listener = match TcpListener::from_std(listener) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    warn!("postgres service listen on {}: {}", addr, e);
+                    return;
+                }
+            };
+            let backend_keys = BackendKeys::default();
+            let process_id = AtomicI32::new(1);
+
+            loop {
+                let stream = tokio::select! {
+                    _ = &mut rx => {
+                        info!("postgres server graceful shutdown!");
+                        return;
+                    }
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            warn!("postgres service accept connection: {}", e);
+                            continue;
+                        }
+                    },
+                };
+                let _ = stream.set_nodelay(true);
+
+                let process_id = process_id.fetch_add(1, Ordering::Relaxed);
+                let connection = Connection::new(
+                    stream,
+                    dbms.clone(),
+                    backend_keys.clone(),
+                    process_id,
+                    tls_acceptor.clone(),
+                );
+                tokio::spawn(async move {
+                    if let Err(e) = connection.run().await {
+                        debug!("postgres connection {} closed: {}", process_id, e);
+                    }
+                });
+            }
+        });
+        self.handle = Some(ServiceHandle::new(
+            "postgres service".to_string(),
+            join_handle,
+            shutdown,
+        ));
+
+        info!("postgres server start addr: {}", self.addr);
+
+        Ok(())
+    }
+
+    async fn stop(&mut self, force: bool) {
+        if let Some(stop) = self.handle.take() {
+            stop.shutdown(force).await
+        };
+    }
+}
@@ -0,0 +1,159 @@
+//! Statements of the postgres clients that are not sent to the query engine.
+
+/// Statements handled by the connection itself, drivers and tools send them
+/// when connecting, but the query engine doesn't support them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalStatement {
+    Empty,
+    /// `SET search_path TO ...`, changes the database of the session.
+    SetSearchPath(String),
+    /// Other `SET` statements, they are ignored.
+    Set,
+    /// Transactions are not supported, the statements are ignored.
+    Transaction(&'static str),
+}
+
+impl LocalStatement {
+    pub fn parse(sql: &str) -> Option<Self> {
+        let sql = sql.trim().trim_end_matches(';').trim();
+        let lowercase = sql.to_ascii_lowercase();
+        let words = lowercase.split_whitespace().collect::<Vec<_>>();
+
+        let statement = match words.as_slice() {
+            [] => Self::Empty,
+            ["set", ..] => match search_path_value(sql) {
+                Some(value) => Self::SetSearchPath(value),
+                None => Self::Set,
+            },
+            ["begin", ..] => Self::Transaction("BEGIN"),
+            ["start", "transaction", ..] => Self::Transaction("START TRANSACTION"),
+            ["commit" | "end", ..] => Self::Transaction("COMMIT"),
+            ["rollback" | "abort", ..] => Self::Transaction("ROLLBACK"),
+            _ => return None,
+        };
+
+        Some(statement)
+    }
+
+    /// Tag of the CommandComplete, `None` means EmptyQueryResponse.
+    pub fn command_tag(&self) -> Option<String> {
+        match self {
+            Self::Empty => None,
+            Self::SetSearchPath(_) | Self::Set => Some("SET".to_string()),
+            Self::Transaction(tag) => Some(tag.to_string()),
+        }
+    }
+}
+
+/// The value of `SET [SESSION | LOCAL] search_path {TO | =} value`.
+fn search_path_value(sql: &str) -> Option<String> {
+    let lowercase = sql.to_ascii_lowercase();
+    let rest = lowercase.strip_prefix("set")?.trim_start();
+    let rest = rest
+        .strip_prefix("session ")
+        .or_else(|| rest.strip_prefix("local "))
+        .unwrap_or(rest)
+        .trim_start();
+    let rest = rest.strip_prefix("search_path")?.trim_start();
+    let rest = rest
+        .strip_prefix("to ")
+        .or_else(|| rest.strip_prefix('='))?
+        .trim_start();
+    // the lowercase has the same length, keep the case of the value
+    Some(sql[sql.len() - rest.len()..].to_string())
+}
+
+/// Split the sql of a simple query into statements by the semicolons
+/// outside the quotes and the comments, the empty statements are skipped.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    enum State {
+        Normal,
+        SingleQuoted,
+        DoubleQuoted,
+        LineComment,
+        BlockComment,
+    }
+
+    let mut statements = vec![];
+    let mut state = State::Normal;
+    let mut start = 0;
+    let mut chars = sql.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match state {
+            State::Normal => match c {
+                '\'' => state = State::SingleQuoted,
+                '"' => state = State::DoubleQuoted,
+                '-' if matches!(chars.peek(), Some((_, '-'))) => {
+                    chars.next();
+                    state = State::LineComment;
+                }
+                '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                    chars.next();
+                    state = State::BlockComment;
+                }
+                ';' => {
+                    statements.push(&sql[start..idx]);
+                    start = idx + 1;
+                }
+                _ => {}
+            },
+            // the escaped quote '' is handled as two adjacent quoted strings
+            State::SingleQuoted if c == '\'' => state = State::Normal,
+            State::DoubleQuoted if c == '"' => state = State::Normal,
+            State::LineComment if c == '\n' => state = State::Normal,
+            State::BlockComment if c == '*' && matches!(chars.peek(), Some((_, '/'))) => {
+                chars.next();
+                state = State::Normal;
+            }
+            _ => {}
+        }
+    }
+    statements.push(&sql[start..]);
+
+    statements
+        .into_iter()
+        .map(|e| e.trim())
+        .filter(|e| !e.is_empty())
+        .map(|e| e.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{split_statements, LocalStatement};
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            split_statements("select 1; select 'a;b', \"c;\" -- d;\n from t;; "),
+            vec!["select 1", "select 'a;b', \"c;\" -- d;\n from t"]
+        );
+        assert_eq!(
+            split_statements("select 'it''s;' /* ; */"),
+            vec!["select 'it''s;' /* ; */"]
+        );
+        assert!(split_statements(" ; ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_local_statement() {
+        assert_eq!(
+            LocalStatement::parse("SET search_path TO \"Air\""),
+            Some(LocalStatement::SetSearchPath("\"Air\"".to_string()))
+        );
+        assert_eq!(
+            LocalStatement::parse("set session search_path = air;"),
+            Some(LocalStatement::SetSearchPath("air".to_string()))
+        );
+        assert_eq!(
+            LocalStatement::parse("SET extra_float_digits = 3"),
+            Some(LocalStatement::Set)
+        );
+        assert_eq!(
+            LocalStatement::parse("start transaction read only"),
+            Some(LocalStatement::Transaction("START TRANSACTION"))
+        );
+        assert_eq!(LocalStatement::parse(" "), Some(LocalStatement::Empty));
+        assert_eq!(LocalStatement::parse("select 1"), None);
+    }
+}
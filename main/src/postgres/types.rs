@@ -0,0 +1,560 @@
+//! Mapping between the arrow types and the postgres types,
+//! and the text and binary formats of the values.
+
+use bytes::{BufMut, BytesMut};
+use chrono::NaiveDateTime;
+use datafusion::arrow::array::{Array, ArrayRef, AsArray, FixedSizeBinaryArray};
+use datafusion::arrow::datatypes::{
+    DataType, Date32Type, Float16Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+    Int8Type, Schema, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt8Type,
+};
+use datafusion::arrow::util::display::array_value_to_string;
+use datafusion::scalar::ScalarValue;
+
+use super::message::{write_message, FieldDescription};
+use super::{Error, Result};
+
+/// Oids of the postgres types, defined in `pg_type.dat` of postgres.
+pub mod oid {
+    pub const UNSPECIFIED: u32 = 0;
+    pub const BOOL: u32 = 16;
+    pub const BYTEA: u32 = 17;
+    pub const INT8: u32 = 20;
+    pub const INT2: u32 = 21;
+    pub const INT4: u32 = 23;
+    pub const TEXT: u32 = 25;
+    pub const FLOAT4: u32 = 700;
+    pub const FLOAT8: u32 = 701;
+    pub const UNKNOWN: u32 = 705;
+    pub const VARCHAR: u32 = 1043;
+    pub const DATE: u32 = 1082;
+    pub const TIME: u32 = 1083;
+    pub const TIMESTAMP: u32 = 1114;
+    pub const TIMESTAMPTZ: u32 = 1184;
+    pub const INTERVAL: u32 = 1186;
+    pub const NUMERIC: u32 = 1700;
+}
+
+/// Microseconds from the unix epoch to the postgres epoch 2000-01-01.
+const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+/// Days from the unix epoch to the postgres epoch 2000-01-01.
+const PG_EPOCH_DAYS: i32 = 10_957;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Binary,
+}
+
+impl Format {
+    pub fn code(&self) -> i16 {
+        match self {
+            Self::Text => 0,
+            Self::Binary => 1,
+        }
+    }
+
+    fn from_code(code: i16) -> Result<Self> {
+        match code {
+            0 => Ok(Self::Text),
+            1 => Ok(Self::Binary),
+            _ => Err(Error::Protocol {
+                reason: format!("invalid format code {}", code),
+            }),
+        }
+    }
+}
+
+/// The formats of `num` values, no format code means all values are text,
+/// one format code is applied to all values.
+pub fn formats(codes: &[i16], num: usize) -> Result<Vec<Format>> {
+    match codes {
+        [] => Ok(vec![Format::Text; num]),
+        [code] => Ok(vec![Format::from_code(*code)?; num]),
+        _ if codes.len() == num => codes.iter().map(|e| Format::from_code(*e)).collect(),
+        _ => Err(Error::Protocol {
+            reason: format!("expect {} format codes, but got {}", num, codes.len()),
+        }),
+    }
+}
+
+/// The postgres type of the arrow type, the values of the types without
+/// a corresponding postgres type are sent as text.
+pub fn pg_type(data_type: &DataType) -> u32 {
+    match data_type {
+        DataType::Boolean => oid::BOOL,
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => oid::INT2,
+        DataType::Int32 | DataType::UInt16 => oid::INT4,
+        DataType::Int64 | DataType::UInt32 => oid::INT8,
+        DataType::UInt64 | DataType::Decimal128(..) | DataType::Decimal256(..) => oid::NUMERIC,
+        DataType::Float16 | DataType::Float32 => oid::FLOAT4,
+        DataType::Float64 => oid::FLOAT8,
+        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => oid::BYTEA,
+        DataType::Timestamp(_, None) => oid::TIMESTAMP,
+        DataType::Timestamp(_, Some(_)) => oid::TIMESTAMPTZ,
+        DataType::Date32 | DataType::Date64 => oid::DATE,
+        DataType::Time32(_) | DataType::Time64(_) => oid::TIME,
+        DataType::Interval(_) | DataType::Duration(_) => oid::INTERVAL,
+        _ => oid::TEXT,
+    }
+}
+
+/// The arrow type of the postgres type specified by the client for a parameter.
+pub fn data_type_of(oid: u32) -> Option<DataType> {
+    let data_type = match oid {
+        oid::BOOL => DataType::Boolean,
+        oid::INT2 => DataType::Int16,
+        oid::INT4 => DataType::Int32,
+        oid::INT8 => DataType::Int64,
+        oid::FLOAT4 => DataType::Float32,
+        oid::FLOAT8 => DataType::Float64,
+        oid::TEXT | oid::VARCHAR => DataType::Utf8,
+        oid::BYTEA => DataType::Binary,
+        oid::TIMESTAMP => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        oid::TIMESTAMPTZ => DataType::Timestamp(TimeUnit::Nanosecond, Some("+00:00".into())),
+        oid::DATE => DataType::Date32,
+        _ => return None,
+    };
+    Some(data_type)
+}
+
+/// Size of the postgres type, -1 means a variable-length type.
+fn type_len(oid: u32) -> i16 {
+    match oid {
+        oid::BOOL => 1,
+        oid::INT2 => 2,
+        oid::INT4 | oid::FLOAT4 | oid::DATE => 4,
+        oid::INT8 | oid::FLOAT8 | oid::TIMESTAMP | oid::TIMESTAMPTZ | oid::TIME => 8,
+        oid::INTERVAL => 16,
+        _ => -1,
+    }
+}
+
+pub fn field_descriptions(schema: &Schema, formats: &[Format]) -> Vec<FieldDescription> {
+    schema
+        .fields()
+        .iter()
+        .zip(formats)
+        .map(|(field, format)| {
+            let type_oid = pg_type(field.data_type());
+            FieldDescription {
+                name: field.name().clone(),
+                type_oid,
+                type_len: type_len(type_oid),
+                format: format.code(),
+            }
+        })
+        .collect()
+}
+
+/// Encode the row of the columns to a DataRow message.
+pub fn encode_data_row(
+    buf: &mut BytesMut,
+    columns: &[ArrayRef],
+    row: usize,
+    formats: &[Format],
+) -> Result<()> {
+    let message_start = buf.len();
+    let mut result = Ok(());
+    write_message(buf, b'D', |buf| {
+        buf.put_i16(columns.len() as i16);
+        for (array, format) in columns.iter().zip(formats) {
+            if array.is_null(row) {
+                buf.put_i32(-1);
+                continue;
+            }
+
+            let start = buf.len();
+            buf.put_i32(0);
+            let encoded = match format {
+                Format::Text => encode_text(array, row, buf),
+                Format::Binary => encode_binary(array, row, buf),
+            };
+            if let Err(e) = encoded {
+                result = Err(e);
+                return;
+            }
+            let len = (buf.len() - start - 4) as i32;
+            buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+        }
+    });
+    if result.is_err() {
+        // discard the incomplete message
+        buf.truncate(message_start);
+    }
+    result
+}
+
+macro_rules! float_text {
+    ($value: expr) => {{
+        let value = $value;
+        if value.is_nan() {
+            "NaN".to_string()
+        } else if value.is_infinite() {
+            if value.is_sign_positive() {
+                "Infinity".to_string()
+            } else {
+                "-Infinity".to_string()
+            }
+        } else {
+            value.to_string()
+        }
+    }};
+}
+
+fn encode_text(array: &ArrayRef, row: usize, buf: &mut BytesMut) -> Result<()> {
+    let text = match array.data_type() {
+        DataType::Boolean => {
+            let value = if array.as_boolean().value(row) {
+                "t"
+            } else {
+                "f"
+            };
+            value.to_string()
+        }
+        DataType::Float16 => float_text!(array.as_primitive::<Float16Type>().value(row).to_f32()),
+        DataType::Float32 => float_text!(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => float_text!(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Binary => bytea_text(array.as_binary::<i32>().value(row)),
+        DataType::LargeBinary => bytea_text(array.as_binary::<i64>().value(row)),
+        DataType::FixedSizeBinary(_) => bytea_text(fixed_size_binary_value(array, row)?),
+        DataType::Timestamp(unit, tz) => {
+            let datetime = timestamp_value(array, unit, row)?;
+            let text = datetime.format("%Y-%m-%d %H:%M:%S%.f").to_string();
+            // the timestamps with time zone are always in UTC
+            match tz {
+                Some(_) => format!("{}+00", text),
+                None => text,
+            }
+        }
+        _ => array_value_to_string(array, row).map_err(|e| Error::Encode {
+            reason: e.to_string(),
+        })?,
+    };
+    buf.put_slice(text.as_bytes());
+    Ok(())
+}
+
+fn bytea_text(value: &[u8]) -> String {
+    let mut text = String::with_capacity(2 + value.len() * 2);
+    text.push_str("\\x");
+    for b in value {
+        text.push_str(&format!("{:02x}", b));
+    }
+    text
+}
+
+fn fixed_size_binary_value(array: &ArrayRef, row: usize) -> Result<&[u8]> {
+    array
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .map(|e| e.value(row))
+        .ok_or_else(|| Error::Encode {
+            reason: format!("expect FixedSizeBinaryArray, but got {}", array.data_type()),
+        })
+}
+
+fn timestamp_value(array: &ArrayRef, unit: &TimeUnit, row: usize) -> Result<NaiveDateTime> {
+    let (secs, nanos) = match unit {
+        TimeUnit::Second => (array.as_primitive::<TimestampSecondType>().value(row), 0),
+        TimeUnit::Millisecond => {
+            let value = array.as_primitive::<TimestampMillisecondType>().value(row);
+            (value.div_euclid(1_000), value.rem_euclid(1_000) * 1_000_000)
+        }
+        TimeUnit::Microsecond => {
+            let value = array.as_primitive::<TimestampMicrosecondType>().value(row);
+            (
+                value.div_euclid(1_000_000),
+                value.rem_euclid(1_000_000) * 1_000,
+            )
+        }
+        TimeUnit::Nanosecond => {
+            let value = array.as_primitive::<TimestampNanosecondType>().value(row);
+            (
+                value.div_euclid(1_000_000_000),
+                value.rem_euclid(1_000_000_000),
+            )
+        }
+    };
+    NaiveDateTime::from_timestamp_opt(secs, nanos as u32).ok_or_else(|| Error::Encode {
+        reason: format!("timestamp {}s {}ns out of range", secs, nanos),
+    })
+}
+
+fn encode_binary(array: &ArrayRef, row: usize, buf: &mut BytesMut) -> Result<()> {
+    match array.data_type() {
+        DataType::Boolean => buf.put_u8(array.as_boolean().value(row) as u8),
+        DataType::Int8 => buf.put_i16(array.as_primitive::<Int8Type>().value(row) as i16),
+        DataType::Int16 => buf.put_i16(array.as_primitive::<Int16Type>().value(row)),
+        DataType::UInt8 => buf.put_i16(array.as_primitive::<UInt8Type>().value(row) as i16),
+        DataType::Int32 => buf.put_i32(array.as_primitive::<Int32Type>().value(row)),
+        DataType::UInt16 => buf.put_i32(array.as_primitive::<UInt16Type>().value(row) as i32),
+        DataType::Int64 => buf.put_i64(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt32 => buf.put_i64(array.as_primitive::<UInt32Type>().value(row) as i64),
+        DataType::Float16 => buf.put_f32(array.as_primitive::<Float16Type>().value(row).to_f32()),
+        DataType::Float32 => buf.put_f32(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => buf.put_f64(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => buf.put_slice(array.as_string::<i32>().value(row).as_bytes()),
+        DataType::LargeUtf8 => buf.put_slice(array.as_string::<i64>().value(row).as_bytes()),
+        DataType::Binary => buf.put_slice(array.as_binary::<i32>().value(row)),
+        DataType::LargeBinary => buf.put_slice(array.as_binary::<i64>().value(row)),
+        DataType::FixedSizeBinary(_) => buf.put_slice(fixed_size_binary_value(array, row)?),
+        DataType::Timestamp(unit, _) => {
+            let datetime = timestamp_value(array, unit, row)?;
+            buf.put_i64(datetime.timestamp_micros() - PG_EPOCH_MICROS)
+        }
+        DataType::Date32 => {
+            buf.put_i32(array.as_primitive::<Date32Type>().value(row) - PG_EPOCH_DAYS)
+        }
+        data_type => {
+            return Err(Error::Unsupported {
+                reason: format!("binary format of {}", data_type),
+            })
+        }
+    }
+    Ok(())
+}
+
+/// Decode the value of a parameter to the type inferred from the statement.
+pub fn decode_param(
+    value: Option<&[u8]>,
+    format: Format,
+    type_oid: u32,
+    data_type: &DataType,
+) -> Result<ScalarValue> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            return ScalarValue::try_from(data_type).map_err(|e| Error::InvalidParameter {
+                reason: e.to_string(),
+            })
+        }
+    };
+
+    let scalar = match format {
+        Format::Text => {
+            let text = std::str::from_utf8(value).map_err(|e| Error::InvalidParameter {
+                reason: format!("parameter is not utf8: {}", e),
+            })?;
+            ScalarValue::try_from_string(text.to_string(), data_type)
+        }
+        Format::Binary => decode_binary(value, type_oid)?.cast_to(data_type),
+    };
+
+    scalar.map_err(|e| Error::InvalidParameter {
+        reason: e.to_string(),
+    })
+}
+
+fn decode_binary(value: &[u8], type_oid: u32) -> Result<ScalarValue> {
+    let invalid_len = || Error::InvalidParameter {
+        reason: format!(
+            "invalid length {} of the binary parameter of type {}",
+            value.len(),
+            type_oid
+        ),
+    };
+    let scalar = match type_oid {
+        oid::BOOL => match value {
+            [b] => ScalarValue::Boolean(Some(*b != 0)),
+            _ => return Err(invalid_len()),
+        },
+        oid::INT2 => ScalarValue::Int16(Some(i16::from_be_bytes(
+            value.try_into().map_err(|_| invalid_len())?,
+        ))),
+        oid::INT4 => ScalarValue::Int32(Some(i32::from_be_bytes(
+            value.try_into().map_err(|_| invalid_len())?,
+        ))),
+        oid::INT8 => ScalarValue::Int64(Some(i64::from_be_bytes(
+            value.try_into().map_err(|_| invalid_len())?,
+        ))),
+        oid::FLOAT4 => ScalarValue::Float32(Some(f32::from_be_bytes(
+            value.try_into().map_err(|_| invalid_len())?,
+        ))),
+        oid::FLOAT8 => ScalarValue::Float64(Some(f64::from_be_bytes(
+            value.try_into().map_err(|_| invalid_len())?,
+        ))),
+        oid::TEXT | oid::VARCHAR | oid::UNKNOWN | oid::UNSPECIFIED => {
+            let text = std::str::from_utf8(value).map_err(|e| Error::InvalidParameter {
+                reason: format!("parameter is not utf8: {}", e),
+            })?;
+            ScalarValue::Utf8(Some(text.to_string()))
+        }
+        oid::BYTEA => ScalarValue::Binary(Some(value.to_vec())),
+        oid::TIMESTAMP | oid::TIMESTAMPTZ => {
+            let micros = i64::from_be_bytes(value.try_into().map_err(|_| invalid_len())?);
+            let tz = (type_oid == oid::TIMESTAMPTZ).then(|| "+00:00".into());
+            ScalarValue::TimestampMicrosecond(Some(micros + PG_EPOCH_MICROS), tz)
+        }
+        oid::DATE => {
+            let days = i32::from_be_bytes(value.try_into().map_err(|_| invalid_len())?);
+            ScalarValue::Date32(Some(days + PG_EPOCH_DAYS))
+        }
+        _ => {
+            return Err(Error::Unsupported {
+                reason: format!("binary format of the parameter of type {}", type_oid),
+            })
+        }
+    };
+    Ok(scalar)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use bytes::BytesMut;
+    use datafusion::arrow::array::{
+        ArrayRef, BinaryArray, BooleanArray, Float64Array, Int64Array, StringArray,
+        TimestampNanosecondArray, UInt64Array,
+    };
+    use datafusion::arrow::datatypes::{DataType, TimeUnit};
+    use datafusion::scalar::ScalarValue;
+
+    use super::{decode_param, encode_data_row, formats, oid, pg_type, Format};
+
+    /// Values of the DataRow message, the tag and the length are skipped.
+    fn data_row_values(buf: &[u8]) -> Vec<Option<Vec<u8>>> {
+        assert_eq!(buf[0], b'D');
+        assert_eq!(
+            i32::from_be_bytes(buf[1..5].try_into().unwrap()) as usize,
+            buf.len() - 1
+        );
+        let num = i16::from_be_bytes(buf[5..7].try_into().unwrap());
+        let mut pos = 7;
+        let mut values = vec![];
+        for _ in 0..num {
+            let len = i32::from_be_bytes(buf[pos..pos + 4].try_into().unwrap());
+            pos += 4;
+            if len < 0 {
+                values.push(None);
+            } else {
+                values.push(Some(buf[pos..pos + len as usize].to_vec()));
+                pos += len as usize;
+            }
+        }
+        values
+    }
+
+    #[test]
+    fn test_pg_type() {
+        assert_eq!(pg_type(&DataType::Int64), oid::INT8);
+        assert_eq!(pg_type(&DataType::UInt64), oid::NUMERIC);
+        assert_eq!(pg_type(&DataType::Utf8), oid::TEXT);
+        assert_eq!(
+            pg_type(&DataType::Timestamp(TimeUnit::Nanosecond, None)),
+            oid::TIMESTAMP
+        );
+        assert_eq!(
+            pg_type(&DataType::Timestamp(
+                TimeUnit::Nanosecond,
+                Some("+00:00".into())
+            )),
+            oid::TIMESTAMPTZ
+        );
+    }
+
+    #[test]
+    fn test_formats() {
+        assert_eq!(formats(&[], 2).unwrap(), vec![Format::Text; 2]);
+        assert_eq!(formats(&[1], 2).unwrap(), vec![Format::Binary; 2]);
+        assert_eq!(
+            formats(&[0, 1], 2).unwrap(),
+            vec![Format::Text, Format::Binary]
+        );
+        assert!(formats(&[0, 1], 3).is_err());
+        assert!(formats(&[2], 1).is_err());
+    }
+
+    #[test]
+    fn test_encode_text() {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(TimestampNanosecondArray::from(vec![
+                1_672_531_200_123_456_789,
+            ])),
+            Arc::new(BooleanArray::from(vec![true])),
+            Arc::new(Float64Array::from(vec![f64::NEG_INFINITY])),
+            Arc::new(BinaryArray::from(vec![&b"\x01\xab"[..]])),
+            Arc::new(UInt64Array::from(vec![u64::MAX])),
+            Arc::new(StringArray::from(vec![None::<&str>])),
+        ];
+        let mut buf = BytesMut::new();
+        encode_data_row(&mut buf, &columns, 0, &[Format::Text; 6]).unwrap();
+        assert_eq!(
+            data_row_values(&buf),
+            vec![
+                Some(b"2023-01-01 00:00:00.123456789".to_vec()),
+                Some(b"t".to_vec()),
+                Some(b"-Infinity".to_vec()),
+                Some(b"\\x01ab".to_vec()),
+                Some(b"18446744073709551615".to_vec()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_binary() {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(TimestampNanosecondArray::from(vec![
+                946_684_801_000_000_000,
+            ])),
+            Arc::new(Int64Array::from(vec![-2])),
+        ];
+        let mut buf = BytesMut::new();
+        encode_data_row(&mut buf, &columns, 0, &[Format::Binary; 2]).unwrap();
+        assert_eq!(
+            data_row_values(&buf),
+            vec![
+                Some(1_000_000_i64.to_be_bytes().to_vec()),
+                Some((-2_i64).to_be_bytes().to_vec()),
+            ]
+        );
+
+        let columns: Vec<ArrayRef> = vec![Arc::new(UInt64Array::from(vec![1]))];
+        let mut buf = BytesMut::new();
+        assert!(encode_data_row(&mut buf, &columns, 0, &[Format::Binary]).is_err());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_decode_param() {
+        assert_eq!(
+            decode_param(
+                Some(b"42"),
+                Format::Text,
+                oid::UNSPECIFIED,
+                &DataType::Int64
+            )
+            .unwrap(),
+            ScalarValue::Int64(Some(42))
+        );
+        assert_eq!(
+            decode_param(
+                Some(&42_i32.to_be_bytes()),
+                Format::Binary,
+                oid::INT4,
+                &DataType::Float64
+            )
+            .unwrap(),
+            ScalarValue::Float64(Some(42.0))
+        );
+        assert_eq!(
+            decode_param(
+                Some(&1_000_000_i64.to_be_bytes()),
+                Format::Binary,
+                oid::TIMESTAMP,
+                &DataType::Timestamp(TimeUnit::Nanosecond, None)
+            )
+            .unwrap(),
+            ScalarValue::TimestampNanosecond(Some(946_684_801_000_000_000), None)
+        );
+        assert_eq!(
+            decode_param(None, Format::Text, oid::TEXT, &DataType::Utf8).unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert!(decode_param(Some(b"x"), Format::Text, oid::TEXT, &DataType::Int64).is_err());
+        assert!(decode_param(Some(&[0, 1]), Format::Binary, oid::INT4, &DataType::Int32).is_err());
+    }
+}
@@ -9,8 +9,10 @@ use futures::{Stream, StreamExt, TryStreamExt};
 use meta::model::MetaRef;
 use metrics::metric_register::MetricsRegister;
 use models::meta_data::VnodeInfo;
-use models::predicate::domain::{self, QueryArgs, QueryExpr, ResolvedPredicate};
-use models::schema::{Precision, TableColumn};
+use models::predicate::domain::{
+    self, PushedAggregateFunction, QueryArgs, QueryExpr, ResolvedPredicate,
+};
+use models::schema::Precision;
 use models::{record_batch_encode, SeriesKey};
 use protos::kv_service::tskv_service_server::TskvService;
 use protos::kv_service::*;
@@ -21,6 +23,7 @@ use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Extensions, Request, Response, Status};
 use trace::{debug, error, info, SpanContext, SpanExt, SpanRecorder};
+use tskv::backup::{backup_vnode, restore_vnode, BackupLocation, BackupStore, VnodeBackup};
 use tskv::error::{Error as TskvError, Result as TskvResult};
 use tskv::reader::query_executor::QueryExecutor;
 use tskv::reader::serialize::TonicRecordBatchEncoder;
@@ -221,6 +224,64 @@ impl TskvServiceImpl {
             Err(_) => self.bytes_response(FAILED_RESPONSE_CODE, vec![]),
         }
     }
+    async fn admin_backup_vnode(
+        &self,
+        tenant: &str,
+        request: &BackupVnodeRequest,
+    ) -> Result<tonic::Response<BatchBytesResponse>, tonic::Status> {
+        let result = async {
+            let location = bincode::deserialize::<BackupLocation>(&request.location)
+                .map_err(|e| TskvError::Decode { source: e })?;
+            let store = BackupStore::new(&location)?;
+            let vnode = self
+                .kv_inst
+                .open_tsfamily(tenant, &request.db, request.vnode_id)
+                .await?;
+            let uploaded_files = request.uploaded_files.iter().copied().collect();
+            let backup = backup_vnode(&vnode, &store, &request.backup_id, &uploaded_files).await?;
+            bincode::serialize(&backup).map_err(|e| TskvError::Encode { source: e })
+        }
+        .await;
+
+        match result {
+            Ok(bytes) => self.bytes_response(SUCCESS_RESPONSE_CODE, bytes),
+            Err(err) => {
+                error!("backup vnode {} failed: {}", request.vnode_id, err);
+                self.bytes_response(FAILED_RESPONSE_CODE, err.to_string().into_bytes())
+            }
+        }
+    }
+
+    async fn admin_restore_vnode(
+        &self,
+        tenant: &str,
+        request: &RestoreVnodeRequest,
+    ) -> Result<tonic::Response<StatusResponse>, tonic::Status> {
+        let result = async {
+            let location = bincode::deserialize::<BackupLocation>(&request.location)
+                .map_err(|e| TskvError::Decode { source: e })?;
+            let backup = bincode::deserialize::<VnodeBackup>(&request.backup)
+                .map_err(|e| TskvError::Decode { source: e })?;
+            let store = BackupStore::new(&location)?;
+            restore_vnode(
+                &self.kv_inst,
+                tenant,
+                &request.db,
+                request.vnode_id,
+                &store,
+                backup,
+            )
+            .await
+        }
+        .await;
+
+        if let Err(err) = result {
+            self.status_response(FAILED_RESPONSE_CODE, err.to_string())
+        } else {
+            self.status_response(SUCCESS_RESPONSE_CODE, "".to_string())
+        }
+    }
+
     async fn admin_add_raft_follower(
         &self,
         tenant: &str,
@@ -283,7 +344,7 @@ impl TskvServiceImpl {
         self,
         args: QueryArgs,
         expr: QueryExpr,
-        aggs: Option<Vec<TableColumn>>,
+        aggs: Option<Vec<PushedAggregateFunction>>,
         span_ctx: Option<&SpanContext>,
     ) -> TskvResult<SendableTskvRecordBatchStream> {
         let option = QueryOption::new(
@@ -292,7 +353,8 @@ impl TskvServiceImpl {
             aggs,
             Arc::new(expr.df_schema),
             expr.table_schema,
-        );
+        )
+        .with_aggregate_grouping(expr.aggregate_grouping);
 
         let meta = self.coord.meta_manager();
         let node_id = meta.node_id();
@@ -547,6 +609,9 @@ impl TskvService for TskvServiceImpl {
                 admin_command_request::Command::DestoryRaftGroup(command) => {
                     self.admin_destory_raft_group(&inner.tenant, command).await
                 }
+                admin_command_request::Command::RestoreVnode(command) => {
+                    self.admin_restore_vnode(&inner.tenant, command).await
+                }
             };
 
             info!("admin command: {:?}, result: {:?}", command, resp);
@@ -568,6 +633,9 @@ impl TskvService for TskvServiceImpl {
                     self.admin_fetch_vnode_checksum(&inner.tenant, command)
                         .await
                 }
+                admin_fetch_command_request::Command::BackupVnode(command) => {
+                    self.admin_backup_vnode(&inner.tenant, command).await
+                }
             }
         } else {
             self.bytes_response(FAILED_RESPONSE_CODE, vec![])
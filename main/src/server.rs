@@ -21,6 +21,9 @@ use tskv::{EngineRef, TsKv};
 
 use crate::flight_sql::FlightSqlServiceAdapter;
 use crate::http::http_service::{HttpService, ServerMode};
+use crate::mysql::MysqlService;
+use crate::otlp::otlp_grpc_service::OtlpGrpcService;
+use crate::postgres::PostgresService;
 use crate::rpc::grpc_service::GrpcService;
 use crate::spi::service::ServiceRef;
 use crate::tcp::tcp_service::TcpService;
@@ -170,6 +173,10 @@ impl ServiceBuilder {
             server.add_service(Box::new(vector_service));
         }
 
+        if let Some(otlp_service) = self.create_otlp_grpc_if_enabled(coord.clone(), dbms.clone()) {
+            server.add_service(Box::new(otlp_service));
+        }
+
         Some(kv_inst)
     }
 
@@ -188,10 +195,20 @@ impl ServiceBuilder {
             server.add_service(Box::new(http_service));
         }
 
-        if let Some(flight_sql_service) = self.create_flight_sql_if_enabled(dbms.clone()) {
+        if let Some(flight_sql_service) =
+            self.create_flight_sql_if_enabled(dbms.clone(), coord.clone())
+        {
             server.add_service(Box::new(flight_sql_service));
         }
 
+        if let Some(postgres_service) = self.create_postgres_if_enabled(dbms.clone()) {
+            server.add_service(Box::new(postgres_service));
+        }
+
+        if let Some(mysql_service) = self.create_mysql_if_enabled(dbms.clone(), coord.clone()) {
+            server.add_service(Box::new(mysql_service));
+        }
+
         None
     }
 
@@ -223,10 +240,20 @@ impl ServiceBuilder {
             server.add_service(Box::new(grpc_service));
         }
 
-        if let Some(flight_sql_service) = self.create_flight_sql_if_enabled(dbms.clone()) {
+        if let Some(flight_sql_service) =
+            self.create_flight_sql_if_enabled(dbms.clone(), coord.clone())
+        {
             server.add_service(Box::new(flight_sql_service));
         }
 
+        if let Some(postgres_service) = self.create_postgres_if_enabled(dbms.clone()) {
+            server.add_service(Box::new(postgres_service));
+        }
+
+        if let Some(mysql_service) = self.create_mysql_if_enabled(dbms.clone(), coord.clone()) {
+            server.add_service(Box::new(mysql_service));
+        }
+
         if let Some(tcp_service) = self.create_tcp_if_enabled(coord.clone()) {
             server.add_service(Box::new(tcp_service));
         }
@@ -237,6 +264,10 @@ impl ServiceBuilder {
             server.add_service(Box::new(vector_service));
         }
 
+        if let Some(otlp_service) = self.create_otlp_grpc_if_enabled(coord.clone(), dbms.clone()) {
+            server.add_service(Box::new(otlp_service));
+        }
+
         Some(kv_inst)
     }
 
@@ -419,6 +450,40 @@ impl ServiceBuilder {
         ))
     }
 
+    fn create_otlp_grpc_if_enabled(
+        &self,
+        coord: CoordinatorRef,
+        dbms: DBMSRef,
+    ) -> Option<OtlpGrpcService> {
+        let default_otlp_grpc_addr = match self.config.service.otlp_listen_port {
+            Some(port) => build_default_address(port),
+            None => return None,
+        };
+
+        let addr = default_otlp_grpc_addr
+            .to_socket_addrs()
+            .map_err(|e| {
+                format!(
+                    "Cannot resolve otlp_grpc_listen_addr '{}': {}",
+                    default_otlp_grpc_addr, e
+                )
+            })
+            .unwrap()
+            .collect::<Vec<SocketAddr>>()
+            .first()
+            .copied()
+            .expect("Config otlp_grpc_listen_addr cannot be empty.");
+
+        Some(OtlpGrpcService::new(
+            coord,
+            dbms,
+            addr,
+            self.config.security.tls_config.clone(),
+            self.metrics_register.clone(),
+            self.span_context_extractor.clone(),
+        ))
+    }
+
     fn create_tcp_if_enabled(&self, coord: CoordinatorRef) -> Option<TcpService> {
         let default_tcp_addr = match self.config.service.tcp_listen_port {
             Some(port) => build_default_address(port),
@@ -428,7 +493,42 @@ impl ServiceBuilder {
         Some(TcpService::new(coord, default_tcp_addr))
     }
 
-    fn create_flight_sql_if_enabled(&self, dbms: DBMSRef) -> Option<FlightSqlServiceAdapter> {
+    fn create_postgres_if_enabled(&self, dbms: DBMSRef) -> Option<PostgresService> {
+        let default_postgres_addr = match self.config.service.postgres_listen_port {
+            Some(port) => build_default_address(port),
+            None => return None,
+        };
+
+        Some(PostgresService::new(
+            dbms,
+            default_postgres_addr,
+            self.config.security.tls_config.clone(),
+        ))
+    }
+
+    fn create_mysql_if_enabled(
+        &self,
+        dbms: DBMSRef,
+        coord: CoordinatorRef,
+    ) -> Option<MysqlService> {
+        let default_mysql_addr = match self.config.service.mysql_listen_port {
+            Some(port) => build_default_address(port),
+            None => return None,
+        };
+
+        Some(MysqlService::new(
+            dbms,
+            coord,
+            default_mysql_addr,
+            self.config.security.tls_config.clone(),
+        ))
+    }
+
+    fn create_flight_sql_if_enabled(
+        &self,
+        dbms: DBMSRef,
+        coord: CoordinatorRef,
+    ) -> Option<FlightSqlServiceAdapter> {
         let default_flight_sql_addr = match self.config.service.flight_rpc_listen_port {
             Some(port) => build_default_address(port),
             None => return None,
@@ -451,6 +551,7 @@ impl ServiceBuilder {
 
         Some(FlightSqlServiceAdapter::new(
             dbms,
+            coord,
             addr,
             tls_config,
             self.span_context_extractor.clone(),
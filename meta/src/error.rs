@@ -177,6 +177,22 @@ pub enum MetaError {
     #[error_code(code = 55)]
     #[snafu(display("resourceinfo mark is lock by: {node_id}"))]
     ResourceInfosMarkIsLock { node_id: u64 },
+
+    #[error_code(code = 56)]
+    #[snafu(display("The rollup policy {} already exists", name))]
+    RollupPolicyAlreadyExists { name: String },
+
+    #[error_code(code = 57)]
+    #[snafu(display("The rollup policy {} not found", name))]
+    RollupPolicyNotFound { name: String },
+
+    #[error_code(code = 58)]
+    #[snafu(display("The function {} already exists", name))]
+    FunctionAlreadyExists { name: String },
+
+    #[error_code(code = 59)]
+    #[snafu(display("The function {} not found", name))]
+    FunctionNotFound { name: String },
 }
 
 impl MetaError {
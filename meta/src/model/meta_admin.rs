@@ -11,7 +11,7 @@ use models::meta_data::*;
 use models::node_info::NodeStatus;
 use models::oid::{Identifier, Oid, UuidGenerator};
 use models::schema::{ResourceInfo, Tenant, TenantOptions};
-use models::utils::{build_address_with_optional_addr, now_timestamp_secs};
+use models::utils::{build_address_with_optional_addr, now_timestamp_nanos, now_timestamp_secs};
 use parking_lot::RwLock;
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tonic::transport::{Channel, Endpoint};
@@ -760,4 +760,13 @@ impl AdminMeta {
 
         self.client.read::<(NodeId, bool)>(&req).await
     }
+
+    /// Acquire or renew the lease of scheduling rollup policies for `ttl` nanoseconds,
+    /// returns false if another node holds the lease.
+    pub async fn acquire_rollup_lease(&self, node_id: NodeId, ttl: i64) -> MetaResult<bool> {
+        let now = now_timestamp_nanos();
+        let req = command::WriteCommand::AcquireRollupLease(self.cluster(), node_id, now, ttl);
+
+        self.client.write::<bool>(&req).await
+    }
 }
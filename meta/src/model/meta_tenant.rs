@@ -12,7 +12,8 @@ use models::auth::user::UserDesc;
 use models::meta_data::*;
 use models::oid::{Identifier, Oid};
 use models::schema::{
-    DatabaseSchema, ExternalTableSchema, ResourceInfo, TableSchema, Tenant, TskvTableSchemaRef,
+    DatabaseSchema, ExternalTableSchema, ResourceInfo, RollupPolicy, RollupProgress, SqlFunction,
+    TableSchema, Tenant, TskvTableSchemaRef,
 };
 use parking_lot::RwLock;
 use store::command;
@@ -362,6 +363,91 @@ impl TenantMeta {
     }
     // tenant role end
 
+    // tenant rollup policy start
+
+    pub async fn create_rollup_policy(&self, policy: RollupPolicy) -> MetaResult<()> {
+        let req = command::WriteCommand::CreateRollupPolicy(
+            self.cluster.clone(),
+            self.tenant_name(),
+            policy,
+        );
+
+        self.client.write::<()>(&req).await
+    }
+
+    pub async fn rollup_policies(&self) -> MetaResult<Vec<RollupPolicy>> {
+        let req = command::ReadCommand::RollupPolicies(self.cluster.clone(), self.tenant_name());
+
+        self.client.read::<Vec<RollupPolicy>>(&req).await
+    }
+
+    pub async fn update_rollup_progress(
+        &self,
+        database: &str,
+        name: &str,
+        progress: RollupProgress,
+    ) -> MetaResult<()> {
+        let req = command::WriteCommand::UpdateRollupProgress(
+            self.cluster.clone(),
+            self.tenant_name(),
+            database.to_string(),
+            name.to_string(),
+            progress,
+        );
+
+        self.client.write::<()>(&req).await
+    }
+
+    pub async fn drop_rollup_policy(&self, database: &str, name: &str) -> MetaResult<bool> {
+        let req = command::WriteCommand::DropRollupPolicy(
+            self.cluster.clone(),
+            self.tenant_name(),
+            database.to_string(),
+            name.to_string(),
+        );
+
+        let rsp = self.client.write::<bool>(&req).await;
+        if let Err(MetaError::RollupPolicyNotFound { name: _ }) = rsp {
+            Ok(false)
+        } else {
+            rsp
+        }
+    }
+
+    // tenant rollup policy end
+
+    // tenant function start
+
+    pub async fn create_function(&self, function: SqlFunction) -> MetaResult<()> {
+        let req = command::WriteCommand::CreateFunction(
+            self.cluster.clone(),
+            self.tenant_name(),
+            function,
+        );
+
+        self.write_with_data(&req).await
+    }
+
+    pub fn functions(&self) -> Vec<SqlFunction> {
+        self.data.read().functions.values().cloned().collect()
+    }
+
+    pub async fn drop_function(&self, name: &str) -> MetaResult<bool> {
+        let req = command::WriteCommand::DropFunction(
+            self.cluster.clone(),
+            self.tenant_name(),
+            name.to_string(),
+        );
+
+        match self.write_with_data(&req).await {
+            Ok(_) => Ok(true),
+            Err(MetaError::FunctionNotFound { name: _ }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // tenant function end
+
     async fn write_with_data(&self, req: &command::WriteCommand) -> MetaResult<()> {
         let rsp = self.client.write::<TenantMetaData>(req).await?;
 
@@ -811,6 +897,7 @@ impl TenantMeta {
 
     // **[6]    /cluster_name/tenants/tenant/roles/name -> [CustomTenantRole<Oid>]
     // **[6]    /cluster_name/tenants/tenant/members/oid -> [TenantRoleIdentifier]
+    // **[6]    /cluster_name/tenants/tenant/functions/name -> [SqlFunction]
     pub async fn process_watch_log(&self, entry: &EntryLog) -> MetaResult<()> {
         let mut cache = self.data.write();
         if cache.version >= entry.ver {
@@ -892,6 +979,15 @@ impl TenantMeta {
             } else if entry.tye == command::ENTRY_LOG_TYPE_DEL {
                 cache.roles.remove(key);
             }
+        } else if len == 6 && strs[4] == key_path::FUNCTIONS && strs[2] == key_path::TENANTS {
+            let key = strs[5];
+            if entry.tye == command::ENTRY_LOG_TYPE_SET {
+                if let Ok(info) = serde_json::from_str::<SqlFunction>(&entry.val) {
+                    cache.functions.insert(key.to_owned(), info);
+                }
+            } else if entry.tye == command::ENTRY_LOG_TYPE_DEL {
+                cache.functions.remove(key);
+            }
         }
 
         Ok(())
@@ -7,7 +7,10 @@ use models::auth::role::{SystemTenantRole, TenantRoleIdentifier};
 use models::auth::user::{UserDesc, UserOptions};
 use models::meta_data::*;
 use models::oid::Oid;
-use models::schema::{DatabaseSchema, ResourceInfo, TableSchema, Tenant, TenantOptions};
+use models::schema::{
+    DatabaseSchema, ResourceInfo, RollupPolicy, RollupProgress, SqlFunction, TableSchema, Tenant,
+    TenantOptions,
+};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
@@ -140,6 +143,20 @@ pub enum WriteCommand {
     ResourceInfo(String, String, ResourceInfo),
     // cluster, node_id, is_lock
     ResourceInfosMark(String, NodeId, bool),
+
+    // cluster, tenant_name, policy
+    CreateRollupPolicy(String, String, RollupPolicy),
+    // cluster, tenant_name, db_name, policy name, progress
+    UpdateRollupProgress(String, String, String, String, RollupProgress),
+    // cluster, tenant_name, db_name, policy name
+    DropRollupPolicy(String, String, String, String),
+    // cluster, node_id, now, ttl in nanoseconds
+    AcquireRollupLease(String, NodeId, i64, i64),
+
+    // cluster, tenant_name, function
+    CreateFunction(String, String, SqlFunction),
+    // cluster, tenant_name, function name
+    DropFunction(String, String, String),
 }
 
 /******************* read command *************************/
@@ -172,6 +189,8 @@ pub enum ReadCommand {
     ResourceInfos(String),
     // cluster
     ResourceInfosMark(String),
+    // cluster, tenant_name
+    RollupPolicies(String, String),
 }
 
 pub const ENTRY_LOG_TYPE_SET: i32 = 1;
@@ -6,12 +6,15 @@ use models::oid::Oid;
 // **    /cluster_name/tenants/tenant/roles/roles ->
 // **    /cluster_name/tenants/tenant/members/user_id ->
 // **    /cluster_name/tenants/tenant/limiter ->
+// **    /cluster_name/tenants/tenant/functions/name -> [SqlFunction]
+// **    /cluster_name/rollup_lease -> (node_id, expire) 调度rollup policy的节点
 // **    /cluster_name/auto_incr_id -> id
 // **    /cluster_name/data_nodes/node_id -> [NodeInfo] 集群、数据节点等信息
 
 // **    /cluster_name/tenant_name/dbs/db_name -> [DatabaseInfo] db相关信息、保留策略等
 // **    /cluster_name/tenant_name/dbs/db_name/buckets/id -> [BucketInfo] bucket相关信息
 // **    /cluster_name/tenant_name/dbs/db_name/schemas/name -> [TskvTableSchema] schema相关信息
+// **    /cluster_name/tenant_name/dbs/db_name/rollup_policies/name -> [RollupPolicy]
 
 pub const DBS: &str = "dbs";
 pub const USERS: &str = "users";
@@ -21,6 +24,8 @@ pub const SCHEMAS: &str = "schemas";
 pub const TENANTS: &str = "tenants";
 pub const MEMBERS: &str = "members";
 pub const LIMITER: &str = "limiter";
+pub const ROLLUP_POLICIES: &str = "rollup_policies";
+pub const FUNCTIONS: &str = "functions";
 pub const DATA_NODES: &str = "data_nodes";
 pub const AUTO_INCR_ID: &str = "auto_incr_id";
 pub const DATA_NODES_METRICS: &str = "data_nodes_metrics";
@@ -131,6 +136,26 @@ impl KeyPath {
         format!("/{cluster}/tenants/{tenant_name}/limiter")
     }
 
+    pub fn rollup_policies(cluster: &str, tenant_name: &str, db: &str) -> String {
+        format!("/{cluster}/tenants/{tenant_name}/dbs/{db}/rollup_policies")
+    }
+
+    pub fn rollup_policy(cluster: &str, tenant_name: &str, db: &str, name: &str) -> String {
+        format!("/{cluster}/tenants/{tenant_name}/dbs/{db}/rollup_policies/{name}")
+    }
+
+    pub fn rollup_lease(cluster: &str) -> String {
+        format!("/{cluster}/rollup_lease")
+    }
+
+    pub fn functions(cluster: &str, tenant_name: &str) -> String {
+        format!("/{cluster}/tenants/{tenant_name}/functions")
+    }
+
+    pub fn function(cluster: &str, tenant_name: &str, name: &str) -> String {
+        format!("/{cluster}/tenants/{tenant_name}/functions/{name}")
+    }
+
     pub fn resourceinfos(cluster: &str, name: &str) -> String {
         format!("/{}/resourceinfos/{}", cluster, name)
     }
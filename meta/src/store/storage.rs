@@ -9,7 +9,10 @@ use models::auth::role::{CustomTenantRole, SystemTenantRole, TenantRoleIdentifie
 use models::auth::user::{UserDesc, UserOptions};
 use models::meta_data::*;
 use models::oid::{Identifier, Oid, UuidGenerator};
-use models::schema::{DatabaseSchema, ResourceInfo, TableSchema, Tenant, TenantOptions};
+use models::schema::{
+    DatabaseSchema, ResourceInfo, RollupPolicy, RollupProgress, SqlFunction, TableSchema, Tenant,
+    TenantOptions,
+};
 use replication::errors::ReplicationResult;
 use replication::{ApplyContext, ApplyStorage, Request, Response};
 use serde::{Deserialize, Serialize};
@@ -344,6 +347,7 @@ impl StateMachine {
             self.children_data::<CustomTenantRole<Oid>>(&KeyPath::roles(cluster, tenant))?;
         meta.members =
             self.children_data::<TenantRoleIdentifier>(&KeyPath::members(cluster, tenant))?;
+        meta.functions = self.children_data::<SqlFunction>(&KeyPath::functions(cluster, tenant))?;
         let db_schemas =
             self.children_data::<DatabaseSchema>(&KeyPath::tenant_dbs(cluster, tenant))?;
 
@@ -410,6 +414,9 @@ impl StateMachine {
             ReadCommand::ResourceInfosMark(cluster) => {
                 response_encode(self.process_read_resourceinfos_mark(cluster))
             }
+            ReadCommand::RollupPolicies(cluster, tenant_name) => {
+                response_encode(self.process_read_rollup_policies(cluster, tenant_name))
+            }
         }
     }
 
@@ -515,6 +522,22 @@ impl StateMachine {
         Ok(resourceinfos)
     }
 
+    pub fn process_read_rollup_policies(
+        &self,
+        cluster: &str,
+        tenant_name: &str,
+    ) -> MetaResult<Vec<RollupPolicy>> {
+        let mut policies = vec![];
+        let dbs =
+            self.children_data::<DatabaseSchema>(&KeyPath::tenant_dbs(cluster, tenant_name))?;
+        for db_name in dbs.keys() {
+            let path = KeyPath::rollup_policies(cluster, tenant_name, db_name);
+            policies.extend(self.children_data::<RollupPolicy>(&path)?.into_values());
+        }
+
+        Ok(policies)
+    }
+
     pub fn process_read_resourceinfos_mark(&self, cluster: &str) -> MetaResult<(NodeId, bool)> {
         let path = KeyPath::resourceinfosmark(cluster);
         match self.get_struct::<(NodeId, bool)>(&path)? {
@@ -657,6 +680,30 @@ impl StateMachine {
             WriteCommand::ResourceInfosMark(cluster, node_id, is_lock) => {
                 response_encode(self.process_write_resourceinfos_mark(cluster, *node_id, *is_lock))
             }
+            WriteCommand::CreateRollupPolicy(cluster, tenant_name, policy) => {
+                response_encode(self.process_create_rollup_policy(cluster, tenant_name, policy))
+            }
+            WriteCommand::UpdateRollupProgress(cluster, tenant_name, db_name, name, progress) => {
+                response_encode(self.process_update_rollup_progress(
+                    cluster,
+                    tenant_name,
+                    db_name,
+                    name,
+                    progress,
+                ))
+            }
+            WriteCommand::DropRollupPolicy(cluster, tenant_name, db_name, name) => response_encode(
+                self.process_drop_rollup_policy(cluster, tenant_name, db_name, name),
+            ),
+            WriteCommand::AcquireRollupLease(cluster, node_id, now, ttl) => {
+                response_encode(self.process_acquire_rollup_lease(cluster, *node_id, *now, *ttl))
+            }
+            WriteCommand::CreateFunction(cluster, tenant_name, function) => {
+                response_encode(self.process_create_function(cluster, tenant_name, function))
+            }
+            WriteCommand::DropFunction(cluster, tenant_name, name) => {
+                response_encode(self.process_drop_function(cluster, tenant_name, name))
+            }
         }
     }
 
@@ -825,6 +872,11 @@ impl StateMachine {
             let _ = self.remove(it);
         }
 
+        let policies_path = KeyPath::rollup_policies(cluster, tenant, db_name);
+        for it in self.children_fullpath(&policies_path)?.iter() {
+            let _ = self.remove(it);
+        }
+
         Ok(())
     }
 
@@ -955,6 +1007,8 @@ impl StateMachine {
                         });
                     }
                 }
+                // CREATE OR REPLACE VIEW
+                (TableSchema::ViewTableSchema(_), TableSchema::ViewTableSchema(_)) => {}
                 _ => {
                     return Err(MetaError::NotSupport {
                         msg: "update external table".to_string(),
@@ -1245,6 +1299,21 @@ impl StateMachine {
             self.process_drop_role(cluster, role.name(), name)?;
         }
 
+        // drop sql functions and rollup policies in the tenant, so that a tenant
+        // created later with the same name doesn't inherit them
+        let mut paths = vec![KeyPath::functions(cluster, name)];
+        for db_name in self
+            .children_data::<DatabaseSchema>(&KeyPath::tenant_dbs(cluster, name))?
+            .keys()
+        {
+            paths.push(KeyPath::rollup_policies(cluster, name, db_name));
+        }
+        for path in paths {
+            for key in self.children_fullpath(&path)? {
+                self.remove(&key)?;
+            }
+        }
+
         // drop tenant meta
         let key = KeyPath::tenant(cluster, name);
         let limiter_key = KeyPath::limiter(cluster, name);
@@ -1458,6 +1527,116 @@ impl StateMachine {
         let key = KeyPath::resourceinfosmark(cluster);
         self.insert(&key, &value_encode(&(node_id, is_lock))?)
     }
+
+    /// Grant the lease of scheduling rollup policies to the node if it is free,
+    /// expired or held by the node, returns whether the node holds the lease.
+    fn process_acquire_rollup_lease(
+        &self,
+        cluster: &str,
+        node_id: NodeId,
+        now: i64,
+        ttl: i64,
+    ) -> MetaResult<bool> {
+        let key = KeyPath::rollup_lease(cluster);
+        if let Some((holder, expire)) = self.get_struct::<(NodeId, i64)>(&key)? {
+            if holder != node_id && expire > now {
+                return Ok(false);
+            }
+        }
+
+        self.insert(&key, &value_encode(&(node_id, now.saturating_add(ttl)))?)?;
+        Ok(true)
+    }
+
+    fn process_create_rollup_policy(
+        &self,
+        cluster: &str,
+        tenant_name: &str,
+        policy: &RollupPolicy,
+    ) -> MetaResult<()> {
+        let key = KeyPath::rollup_policy(cluster, tenant_name, &policy.database, &policy.name);
+        if self.contains_key(&key)? {
+            return Err(MetaError::RollupPolicyAlreadyExists {
+                name: policy.name.clone(),
+            });
+        }
+
+        self.insert(&key, &value_encode(policy)?)
+    }
+
+    fn process_update_rollup_progress(
+        &self,
+        cluster: &str,
+        tenant_name: &str,
+        db_name: &str,
+        name: &str,
+        progress: &RollupProgress,
+    ) -> MetaResult<()> {
+        let key = KeyPath::rollup_policy(cluster, tenant_name, db_name, name);
+        match self.get_struct::<RollupPolicy>(&key)? {
+            Some(mut policy) => {
+                policy.progress = progress.clone();
+                self.insert(&key, &value_encode(&policy)?)
+            }
+            None => Err(MetaError::RollupPolicyNotFound {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    fn process_drop_rollup_policy(
+        &self,
+        cluster: &str,
+        tenant_name: &str,
+        db_name: &str,
+        name: &str,
+    ) -> MetaResult<bool> {
+        let key = KeyPath::rollup_policy(cluster, tenant_name, db_name, name);
+        if !self.contains_key(&key)? {
+            return Err(MetaError::RollupPolicyNotFound {
+                name: name.to_string(),
+            });
+        }
+
+        self.remove(&key)?;
+        Ok(true)
+    }
+
+    fn process_create_function(
+        &self,
+        cluster: &str,
+        tenant_name: &str,
+        function: &SqlFunction,
+    ) -> MetaResult<TenantMetaData> {
+        let key = KeyPath::function(cluster, tenant_name, &function.name);
+        if self.contains_key(&key)? {
+            return Err(MetaError::FunctionAlreadyExists {
+                name: function.name.clone(),
+            });
+        }
+
+        self.insert(&key, &value_encode(function)?)?;
+
+        self.to_tenant_meta_data(cluster, tenant_name)
+    }
+
+    fn process_drop_function(
+        &self,
+        cluster: &str,
+        tenant_name: &str,
+        name: &str,
+    ) -> MetaResult<TenantMetaData> {
+        let key = KeyPath::function(cluster, tenant_name, name);
+        if !self.contains_key(&key)? {
+            return Err(MetaError::FunctionNotFound {
+                name: name.to_string(),
+            });
+        }
+
+        self.remove(&key)?;
+
+        self.to_tenant_meta_data(cluster, tenant_name)
+    }
 }
 
 fn check_node_enough(need: u64, node_list: &[NodeInfo]) -> MetaResult<()> {
@@ -1477,6 +1656,29 @@ mod test {
 
     use serde::{Deserialize, Serialize};
 
+    use super::StateMachine;
+
+    #[test]
+    fn test_rollup_lease() {
+        let dir = "/tmp/test/meta/rollup_lease";
+        let _ = std::fs::remove_dir_all(dir);
+        let storage = StateMachine::open(dir).unwrap();
+
+        assert!(storage.process_acquire_rollup_lease("c", 1, 0, 10).unwrap());
+        assert!(!storage.process_acquire_rollup_lease("c", 2, 5, 10).unwrap());
+        // renew the lease until 15
+        assert!(storage.process_acquire_rollup_lease("c", 1, 5, 10).unwrap());
+        assert!(!storage
+            .process_acquire_rollup_lease("c", 2, 14, 10)
+            .unwrap());
+        assert!(storage
+            .process_acquire_rollup_lease("c", 2, 15, 10)
+            .unwrap());
+        assert!(!storage
+            .process_acquire_rollup_lease("c", 1, 16, 10)
+            .unwrap());
+    }
+
     #[test]
     fn test_btree_map() {
         let mut map = BTreeMap::new();
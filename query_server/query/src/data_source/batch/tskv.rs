@@ -4,24 +4,31 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use coordinator::service::CoordinatorRef;
-use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{IntervalDayTimeType, IntervalMonthDayNanoType, SchemaRef};
 use datafusion::datasource::{TableProvider, TableType};
 use datafusion::error::{DataFusionError, Result};
 use datafusion::execution::context::SessionState;
-use datafusion::logical_expr::expr::AggregateFunction;
+use datafusion::logical_expr::expr::{AggregateFunction, ScalarFunction};
 use datafusion::logical_expr::logical_plan::AggWithGrouping;
 use datafusion::logical_expr::{
-    aggregate_function, Expr, TableProviderAggregationPushDown, TableProviderFilterPushDown,
+    aggregate_function, BuiltinScalarFunction, Expr, TableProviderAggregationPushDown,
+    TableProviderFilterPushDown,
 };
 use datafusion::optimizer::utils::split_conjunction;
 use datafusion::physical_expr::PhysicalExpr;
 use datafusion::physical_plan::empty::EmptyExec;
 use datafusion::physical_plan::{project_schema, ExecutionPlan};
 use datafusion::prelude::Column;
+use datafusion::scalar::ScalarValue;
 use meta::error::MetaError;
 use meta::model::MetaClientRef;
-use models::predicate::domain::{Predicate, PredicateRef, PushedAggregateFunction};
-use models::schema::{TskvTableSchema, TskvTableSchemaRef};
+use models::predicate::domain::{
+    Predicate, PredicateRef, PushedAggregateFunction, PushedAggregateGrouping,
+};
+use models::schema::{
+    timestamp_convert, ColumnType, Precision, TskvTableSchema, TskvTableSchemaRef,
+};
+use models::ValueType;
 use trace::debug;
 
 use crate::data_source::sink::tskv::TskvRecordBatchSinkProvider;
@@ -82,11 +89,24 @@ impl ClusterTable {
         agg_with_grouping: &AggWithGrouping,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let AggWithGrouping {
-            group_expr: _,
+            group_expr,
             agg_expr,
             schema,
         } = agg_with_grouping;
         let proj_schema = SchemaRef::from(schema.deref());
+        let grouping = if group_expr.is_empty() {
+            None
+        } else {
+            let (grouping, _) = self
+                .pushed_aggregate_grouping(group_expr, agg_expr)
+                .ok_or_else(|| {
+                    DataFusionError::Plan(
+                        "Invalid plan, pushed group by expressions contains unsupported"
+                            .to_string(),
+                    )
+                })?;
+            Some(grouping)
+        };
 
         let table_layout = TableLayoutHandle {
             table: self.schema.clone(),
@@ -136,22 +156,31 @@ impl ClusterTable {
                 .collect::<Result<Vec<_>>>()
                 .and_then(|columns| {
                     // Convert pushdown aggregate functions to intermediate structures
+                    let column = columns
+                        .first()
+                        .ok_or_else(|| {
+                            DataFusionError::Internal(
+                                "Pushed aggregate functions's args is none.".to_string(),
+                            )
+                        })?
+                        .name
+                        .to_owned();
                     match fun {
                         aggregate_function::AggregateFunction::Count => {
-                            let column = columns
-                                .first()
-                                .ok_or_else(|| {
-                                    DataFusionError::Internal(
-                                        "Pushed aggregate functions's args is none.".to_string(),
-                                    )
-                                })?;
-                            Ok(PushedAggregateFunction::Count(column.name.to_owned()))
+                            Ok(PushedAggregateFunction::Count(column))
+                        }
+                        aggregate_function::AggregateFunction::Max => {
+                            Ok(PushedAggregateFunction::Max(column))
+                        }
+                        aggregate_function::AggregateFunction::Min => {
+                            Ok(PushedAggregateFunction::Min(column))
                         }
-                        // aggregate_function::AggregateFunction::Max => {},
-                        // aggregate_function::AggregateFunction::Min => {},
-                        _ => Err(DataFusionError::Internal(
-                            "Pushed aggregate functions's args is none.".to_string(),
-                        )),
+                        aggregate_function::AggregateFunction::Sum => {
+                            Ok(PushedAggregateFunction::Sum(column))
+                        }
+                        _ => Err(DataFusionError::Internal(format!(
+                            "Unsupported pushed aggregate function: {fun}."
+                        ))),
                     }
                 })
         })
@@ -162,6 +191,7 @@ impl ClusterTable {
             proj_schema,
             self.schema.clone(),
             pushed_aggs,
+            grouping,
             filter,
             splits,
         )))
@@ -221,6 +251,140 @@ impl ClusterTable {
         self.schema.clone()
     }
 
+    /// If the grouped aggregates can be pushed down to tskv, returns grouping of them
+    /// and the group by expressions in order of the grouping, the time bucket goes first:
+    ///
+    /// 1. Aggregates are count, max, min or sum of field columns.
+    /// 2. Group by a `date_bin` of the time column, and optionally the tag columns.
+    pub fn pushed_aggregate_grouping(
+        &self,
+        group_expr: &[Expr],
+        aggr_expr: &[Expr],
+    ) -> Option<(PushedAggregateGrouping, Vec<Expr>)> {
+        let support_aggr = aggr_expr.iter().all(|e| match e {
+            Expr::AggregateFunction(AggregateFunction {
+                fun,
+                args,
+                distinct,
+                filter,
+                order_by,
+            }) => {
+                matches!(
+                    fun,
+                    aggregate_function::AggregateFunction::Count
+                        | aggregate_function::AggregateFunction::Max
+                        | aggregate_function::AggregateFunction::Min
+                        | aggregate_function::AggregateFunction::Sum
+                ) && args.len() == 1
+                    && self.is_field_column(&args[0])
+                    && !*distinct
+                    && filter.is_none()
+                    && order_by.is_none()
+            }
+            _ => false,
+        });
+        if !support_aggr {
+            return None;
+        }
+
+        let mut time_bucket = None;
+        let mut tags = Vec::new();
+        let mut tag_exprs = Vec::new();
+        for expr in group_expr {
+            match expr {
+                Expr::Column(c) if self.is_tag_column(expr) => {
+                    tags.push(c.name.clone());
+                    tag_exprs.push(expr.clone());
+                }
+                _ if time_bucket.is_none() => {
+                    time_bucket = Some((self.time_bucket(expr)?, expr.clone()));
+                }
+                _ => return None,
+            }
+        }
+        let ((stride, origin), time_bucket_expr) = time_bucket?;
+
+        let mut exprs = Vec::with_capacity(tag_exprs.len() + 1);
+        exprs.push(time_bucket_expr);
+        exprs.extend(tag_exprs);
+        Some((
+            PushedAggregateGrouping {
+                stride,
+                origin,
+                tags,
+            },
+            exprs,
+        ))
+    }
+
+    /// Returns stride and origin of `date_bin(<stride>, <time column>[, <origin>])`,
+    /// in the time unit of the table.
+    fn time_bucket(&self, expr: &Expr) -> Option<(i64, i64)> {
+        let args = match expr {
+            Expr::ScalarFunction(ScalarFunction {
+                fun: BuiltinScalarFunction::DateBin,
+                args,
+            }) => args,
+            _ => return None,
+        };
+        let stride = match args.get(0)? {
+            Expr::Literal(value) => interval_nanos(value)?,
+            _ => return None,
+        };
+        match args.get(1)? {
+            Expr::Column(c) if c.name == self.schema.time_column().name => {}
+            _ => return None,
+        }
+        let origin = match args.get(2) {
+            Some(Expr::Literal(value)) => timestamp_nanos(value)?,
+            Some(_) => return None,
+            None => 0,
+        };
+
+        let precision = self.schema.time_column_precision();
+        let stride = nanos_to_precision(stride, precision)?;
+        let origin = nanos_to_precision(origin, precision)?;
+        (stride > 0).then_some((stride, origin))
+    }
+
+    /// Returns true if the expression is a field column which can be aggregated in tskv,
+    /// dictionary-encoded strings are not, as they are aggregated to dictionaries.
+    /// Neither are the fields whose arrow type differs from the arrow type of the stored
+    /// values, such as INT, FLOAT, BINARY and DECIMAL.
+    fn is_field_column(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Column(c) => self
+                .schema
+                .column(&c.name)
+                .map(|col| {
+                    col.column_type.is_field()
+                        && !col.is_dictionary_encoded()
+                        && !matches!(
+                            col.column_type,
+                            ColumnType::Field(
+                                ValueType::Int32
+                                    | ValueType::Float32
+                                    | ValueType::Binary
+                                    | ValueType::Decimal128(_, _)
+                            )
+                        )
+                })
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    fn is_tag_column(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Column(c) => self
+                .schema
+                .column(&c.name)
+                .map(|col| col.column_type.is_tag())
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
     // Check and return the projected schema
     fn project_schema(&self, projection: Option<&Vec<usize>>) -> Result<SchemaRef> {
         valid_project(&self.schema, projection)
@@ -306,18 +470,22 @@ impl TableProvider for ClusterTable {
                     filter,
                     order_by,
                 }) => {
-                    let support_agg_func = matches!(
-                        fun,
-                        aggregate_function::AggregateFunction::Count // TODO
-                                                                     // | aggregate_function::AggregateFunction::Max
-                                                                     // | aggregate_function::AggregateFunction::Min
-                                                                     // | aggregate_function::AggregateFunction::Sum
-                    );
-
-                    support_agg_func
-                        && args.len() == 1
-                        // count(*) | count(1) | count(col)
-                        && (matches!(args[0], Expr::Column(_)) || matches!(args[0], Expr::Literal(_)))
+                    let support_agg_args = args.len() == 1
+                        && match fun {
+                            // count(*) | count(1) | count(col)
+                            aggregate_function::AggregateFunction::Count => {
+                                matches!(args[0], Expr::Column(_) | Expr::Literal(_))
+                            }
+                            // max(field) | min(field) | sum(field)
+                            aggregate_function::AggregateFunction::Max
+                            | aggregate_function::AggregateFunction::Min
+                            | aggregate_function::AggregateFunction::Sum => {
+                                self.is_field_column(&args[0])
+                            }
+                            _ => false,
+                        };
+
+                    support_agg_args
                         // not distinct
                         && !*distinct
                         && filter.is_none()
@@ -405,6 +573,45 @@ impl UpdateExecExt for ClusterTable {
     }
 }
 
+/// Returns nanoseconds of an interval without months.
+fn interval_nanos(value: &ScalarValue) -> Option<i64> {
+    match value {
+        ScalarValue::IntervalDayTime(Some(v)) => {
+            let (days, millis) = IntervalDayTimeType::to_parts(*v);
+            (days as i64)
+                .checked_mul(86_400_000_000_000)?
+                .checked_add(millis as i64 * 1_000_000)
+        }
+        ScalarValue::IntervalMonthDayNano(Some(v)) => {
+            let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(*v);
+            if months != 0 {
+                return None;
+            }
+            (days as i64)
+                .checked_mul(86_400_000_000_000)?
+                .checked_add(nanos)
+        }
+        _ => None,
+    }
+}
+
+/// Returns nanoseconds of a timestamp.
+fn timestamp_nanos(value: &ScalarValue) -> Option<i64> {
+    match value {
+        ScalarValue::TimestampSecond(Some(v), _) => v.checked_mul(1_000_000_000),
+        ScalarValue::TimestampMillisecond(Some(v), _) => v.checked_mul(1_000_000),
+        ScalarValue::TimestampMicrosecond(Some(v), _) => v.checked_mul(1_000),
+        ScalarValue::TimestampNanosecond(Some(v), _) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Convert nanoseconds to the precision, returns None if it is not exact.
+fn nanos_to_precision(nanos: i64, precision: Precision) -> Option<i64> {
+    let value = timestamp_convert(Precision::NS, precision, nanos)?;
+    (timestamp_convert(precision, Precision::NS, value)? == nanos).then_some(value)
+}
+
 /// Check the validity of the projection
 ///
 /// 1. If the projection contains the time column, it must contain the field column, otherwise an error will be reported
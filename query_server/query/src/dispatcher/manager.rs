@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use coordinator::service::CoordinatorRef;
@@ -6,26 +8,36 @@ use memory_pool::MemoryPoolRef;
 use meta::error::MetaError;
 use meta::model::MetaClientRef;
 use models::auth::user::admin_user;
-use models::oid::Oid;
+use models::oid::{Identifier, Oid};
+use parking_lot::RwLock;
 use spi::query::ast::ExtStatement;
+use spi::query::config::SqlDialect;
 use spi::query::datasource::stream::StreamProviderManagerRef;
 use spi::query::dispatcher::{QueryDispatcher, QueryInfo, QueryStatus};
 use spi::query::execution::{Output, QueryStateMachine};
-use spi::query::function::FuncMetaManagerRef;
+use spi::query::function::{FuncMetaManagerRef, FunctionMetadataManager};
 use spi::query::logical_planner::{LogicalPlanner, Plan};
 use spi::query::parser::Parser;
 use spi::query::session::{SessionCtx, SessionCtxFactory};
 use spi::service::protocol::{ContextBuilder, Query, QueryId};
 use spi::{QueryError, Result};
-use trace::{info, SpanContext, SpanExt, SpanRecorder, TraceExporter};
+use trace::{info, warn, SpanContext, SpanExt, SpanRecorder, TraceExporter};
 
-use super::query_tracker::QueryTracker;
+use super::query_tracker::{QueryQuota, QueryTracker};
+use super::rollup::RollupScheduler;
 use crate::data_source::split::SplitManagerRef;
 use crate::execution::factory::QueryExecutionFactoryRef;
+use crate::function::simple_func_manager::SimpleFunctionMetadataManagerRef;
+use crate::function::sql_function::{create_sql_udf, FunctionContextProvider};
 use crate::metadata::{
     BaseTableProvider, ContextProviderExtension, MetadataProvider, TableHandleProviderRef,
 };
 use crate::sql::logical::planner::DefaultLogicalPlanner;
+use crate::sql::substrait::substrait_to_logical_plan;
+
+/// Function managers with the sql functions of the tenants compiled, by tenant name,
+/// along with the version of the tenant's meta data they were compiled from.
+type TenantFuncManagers = Arc<RwLock<HashMap<String, (u64, FuncMetaManagerRef)>>>;
 
 #[derive(Clone)]
 pub struct SimpleQueryDispatcher {
@@ -40,9 +52,12 @@ pub struct SimpleQueryDispatcher {
     query_tracker: Arc<QueryTracker>,
     // parser
     parser: Arc<dyn Parser + Send + Sync>,
+    influxql_parser: Arc<dyn Parser + Send + Sync>,
     // get query execution factory
     query_execution_factory: QueryExecutionFactoryRef,
-    func_manager: FuncMetaManagerRef,
+    // built-in functions
+    func_manager: SimpleFunctionMetadataManagerRef,
+    tenant_func_managers: TenantFuncManagers,
     stream_provider_manager: StreamProviderManagerRef,
     trace_collector: Option<Arc<dyn TraceExporter>>,
 }
@@ -80,6 +95,10 @@ impl QueryDispatcher for SimpleQueryDispatcher {
             }
         }
 
+        // schedule rollup policies
+        let rollup_scheduler = RollupScheduler::new(self.coord.clone(), self.clone());
+        tokio::spawn(rollup_scheduler.run());
+
         Ok(())
     }
 
@@ -131,7 +150,11 @@ impl QueryDispatcher for SimpleQueryDispatcher {
         let logical_planner = DefaultLogicalPlanner::new(&scheme_provider);
 
         let span_recorder = session.get_child_span_recorder("parse sql");
-        let statements = self.parser.parse(query.content())?;
+        let parser = match query.context().dialect() {
+            SqlDialect::Sql => &self.parser,
+            SqlDialect::InfluxQL => &self.influxql_parser,
+        };
+        let statements = parser.parse(query.content())?;
 
         // not allow multi statement
         if statements.len() > 1 {
@@ -154,6 +177,25 @@ impl QueryDispatcher for SimpleQueryDispatcher {
         Ok(Some(logical_plan))
     }
 
+    async fn build_substrait_logical_plan(
+        &self,
+        query_state_machine: Arc<QueryStateMachine>,
+        substrait_plan: &[u8],
+    ) -> Result<Plan> {
+        let session = &query_state_machine.session;
+        let scheme_provider = Arc::new(self.build_scheme_provider(session).await?);
+
+        // begin analyze
+        query_state_machine.begin_analyze();
+        let logical_plan = {
+            let _span_recorder = session.get_child_span_recorder("substrait to logical plan");
+            substrait_to_logical_plan(scheme_provider, substrait_plan, session).await?
+        };
+        query_state_machine.end_analyze();
+
+        Ok(logical_plan)
+    }
+
     async fn execute_logical_plan(
         &self,
         logical_plan: Plan,
@@ -230,28 +272,65 @@ impl SimpleQueryDispatcher {
         logical_plan: Plan,
         query_state_machine: Arc<QueryStateMachine>,
     ) -> Result<Output> {
+        // ddl and system tasks, e.g. kill query, are not limited
+        let quota = match logical_plan {
+            Plan::Query(_) => self.query_quota(&query_state_machine.session).await?,
+            _ => QueryQuota::default(),
+        };
+
         let execution = self
             .query_execution_factory
             .create_query_execution(logical_plan, query_state_machine.clone())?;
 
         // TrackedQuery.drop() is called implicitly when the value goes out of scope,
         self.query_tracker
-            .try_track_query(query_state_machine.query_id, execution)
+            .try_track_query_with_quota(query_state_machine.query_id, execution, quota)
             .await?
             .start()
             .await
     }
 
+    /// Quota of the queries of the session, the stricter one of the tenant's and the user's
+    async fn query_quota(&self, session: &SessionCtx) -> Result<QueryQuota> {
+        let meta_client = self.build_current_session_meta_client(session).await?;
+        let tenant_options = meta_client.tenant().options();
+        let user = session.user().desc();
+        let user_options = user.options();
+
+        let stricter = |a: Option<Duration>, b: Option<Duration>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        Ok(QueryQuota {
+            tenant_id: *session.tenant_id(),
+            tenant_name: session.tenant().to_string(),
+            tenant_max_concurrent_queries: tenant_options.max_concurrent_queries,
+            user_id: *user.id(),
+            user_name: user.name().to_string(),
+            user_max_concurrent_queries: user_options.max_concurrent_queries(),
+            max_execution_time: stricter(
+                tenant_options.max_execution_time,
+                user_options.max_execution_time(),
+            ),
+            queue_timeout: stricter(
+                tenant_options.query_queue_timeout,
+                user_options.query_queue_timeout(),
+            ),
+        })
+    }
+
     async fn build_scheme_provider(&self, session: &SessionCtx) -> Result<MetadataProvider> {
         let meta_client = self.build_current_session_meta_client(session).await?;
         let current_session_table_provider =
             self.build_table_handle_provider(meta_client.clone())?;
+        let func_manager = self.build_func_manager(&meta_client).await;
         let metadata_provider = MetadataProvider::new(
             self.coord.clone(),
             meta_client,
             current_session_table_provider,
             self.default_table_provider.clone(),
-            self.func_manager.clone(),
+            func_manager,
             self.query_tracker.clone(),
             session.clone(),
         );
@@ -259,6 +338,71 @@ impl SimpleQueryDispatcher {
         Ok(metadata_provider)
     }
 
+    /// The built-in functions with the sql functions of the tenant registered,
+    /// the functions are compiled again only after the meta data of the tenant changed.
+    async fn build_func_manager(&self, meta_client: &MetaClientRef) -> FuncMetaManagerRef {
+        let tenant = meta_client.tenant_name();
+        // read the version before the functions, a change in between compiles them again
+        let version = meta_client.version().await;
+        if let Some((compiled_version, func_manager)) =
+            self.tenant_func_managers.read().get(&tenant)
+        {
+            if *compiled_version == version {
+                return func_manager.clone();
+            }
+        }
+
+        let func_manager = self.compile_func_manager(meta_client);
+        self.tenant_func_managers
+            .write()
+            .insert(tenant, (version, func_manager.clone()));
+        func_manager
+    }
+
+    fn compile_func_manager(&self, meta_client: &MetaClientRef) -> FuncMetaManagerRef {
+        let mut pending = meta_client.functions();
+        if pending.is_empty() {
+            return self.func_manager.clone();
+        }
+
+        let mut func_manager = self.func_manager.as_ref().clone();
+        // a function may call the functions created before it, register
+        // them until no more function can be registered
+        loop {
+            let pending_num = pending.len();
+            pending.retain(|function| {
+                if self.func_manager.udf(&function.name).is_ok() {
+                    warn!(
+                        "Sql function {} conflicts with a built-in function",
+                        function.name
+                    );
+                    return false;
+                }
+                let udf = create_sql_udf(function, &FunctionContextProvider::new(&func_manager));
+                match udf {
+                    Ok(udf) => {
+                        let _ = func_manager.register_udf(udf);
+                        false
+                    }
+                    Err(_) => true,
+                }
+            });
+            if pending.is_empty() || pending.len() == pending_num {
+                break;
+            }
+        }
+
+        for function in pending {
+            warn!(
+                "Failed to register sql function {} of tenant {}",
+                function.signature(),
+                function.tenant
+            );
+        }
+
+        Arc::new(func_manager)
+    }
+
     async fn build_current_session_meta_client(
         &self,
         session: &SessionCtx,
@@ -297,12 +441,13 @@ pub struct SimpleQueryDispatcherBuilder {
     split_manager: Option<SplitManagerRef>,
     session_factory: Option<Arc<SessionCtxFactory>>,
     parser: Option<Arc<dyn Parser + Send + Sync>>,
+    influxql_parser: Option<Arc<dyn Parser + Send + Sync>>,
 
     query_execution_factory: Option<QueryExecutionFactoryRef>,
     query_tracker: Option<Arc<QueryTracker>>,
     memory_pool: Option<MemoryPoolRef>, // memory
 
-    func_manager: Option<FuncMetaManagerRef>,
+    func_manager: Option<SimpleFunctionMetadataManagerRef>,
     stream_provider_manager: Option<StreamProviderManagerRef>,
     trace_collector: Option<Arc<dyn TraceExporter>>,
 }
@@ -336,6 +481,11 @@ impl SimpleQueryDispatcherBuilder {
         self
     }
 
+    pub fn with_influxql_parser(mut self, parser: Arc<dyn Parser + Send + Sync>) -> Self {
+        self.influxql_parser = Some(parser);
+        self
+    }
+
     pub fn with_query_execution_factory(
         mut self,
         query_execution_factory: QueryExecutionFactoryRef,
@@ -354,7 +504,7 @@ impl SimpleQueryDispatcherBuilder {
         self
     }
 
-    pub fn with_func_manager(mut self, func_manager: FuncMetaManagerRef) -> Self {
+    pub fn with_func_manager(mut self, func_manager: SimpleFunctionMetadataManagerRef) -> Self {
         self.func_manager = Some(func_manager);
         self
     }
@@ -395,6 +545,12 @@ impl SimpleQueryDispatcherBuilder {
                 err: "lost of parser".to_string(),
             })?;
 
+        let influxql_parser =
+            self.influxql_parser
+                .ok_or_else(|| QueryError::BuildQueryDispatcher {
+                    err: "lost of influxql_parser".to_string(),
+                })?;
+
         let query_execution_factory =
             self.query_execution_factory
                 .ok_or_else(|| QueryError::BuildQueryDispatcher {
@@ -440,9 +596,11 @@ impl SimpleQueryDispatcherBuilder {
             session_factory,
             memory_pool,
             parser,
+            influxql_parser,
             query_execution_factory,
             query_tracker,
             func_manager,
+            tenant_func_managers: Default::default(),
             stream_provider_manager,
             trace_collector,
         })
@@ -6,6 +6,7 @@ use spi::Result;
 pub mod manager;
 pub mod persister;
 pub mod query_tracker;
+pub mod rollup;
 
 #[async_trait]
 pub trait QueryPersister {
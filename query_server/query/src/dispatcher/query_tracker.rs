@@ -3,26 +3,104 @@ use std::ops::Deref;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::SchemaRef;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::common::Result as DFResult;
+use datafusion::error::DataFusionError;
 use datafusion::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
-use futures::{Stream, StreamExt};
-use parking_lot::RwLock;
+use futures::{FutureExt, Stream, StreamExt};
+use models::oid::Oid;
+use parking_lot::{Mutex, RwLock};
 use spi::query::dispatcher::{QueryInfo, QueryStatus};
 use spi::query::execution::{Output, QueryExecution, QueryExecutionRef, QueryType};
 use spi::service::protocol::QueryId;
 use spi::{QueryError, Result};
+use tokio::sync::Notify;
+use tokio::time::{Instant, Sleep};
 use trace::{debug, warn};
 
 use super::persister::QueryPersisterRef;
 
+/// Limits of a query, resolved from the options of its tenant and user
+#[derive(Debug, Default, Clone)]
+pub struct QueryQuota {
+    pub tenant_id: Oid,
+    pub tenant_name: String,
+    pub tenant_max_concurrent_queries: Option<u32>,
+    pub user_id: Oid,
+    pub user_name: String,
+    pub user_max_concurrent_queries: Option<u32>,
+    pub max_execution_time: Option<Duration>,
+    /// Wait for a free slot instead of being rejected when the concurrent queries exceed the limit
+    pub queue_timeout: Option<Duration>,
+}
+
+/// Running queries of the tenants and the users
+#[derive(Default)]
+struct QuerySlots {
+    tenants: HashMap<Oid, u32>,
+    users: HashMap<Oid, u32>,
+    owners: HashMap<QueryId, (Oid, Oid)>,
+}
+
+impl QuerySlots {
+    fn try_acquire(&mut self, query_id: QueryId, quota: &QueryQuota) -> Result<()> {
+        let tenant_running = self.tenants.get(&quota.tenant_id).copied().unwrap_or(0);
+        if let Some(limit) = quota.tenant_max_concurrent_queries {
+            if tenant_running >= limit {
+                return Err(QueryError::QueryQuotaExceeded {
+                    owner: format!("tenant '{}'", quota.tenant_name),
+                    limit,
+                });
+            }
+        }
+        let user_running = self.users.get(&quota.user_id).copied().unwrap_or(0);
+        if let Some(limit) = quota.user_max_concurrent_queries {
+            if user_running >= limit {
+                return Err(QueryError::QueryQuotaExceeded {
+                    owner: format!("user '{}'", quota.user_name),
+                    limit,
+                });
+            }
+        }
+
+        self.tenants.insert(quota.tenant_id, tenant_running + 1);
+        self.users.insert(quota.user_id, user_running + 1);
+        self.owners
+            .insert(query_id, (quota.tenant_id, quota.user_id));
+        Ok(())
+    }
+
+    fn release(&mut self, query_id: &QueryId) -> bool {
+        fn decrease(running: &mut HashMap<Oid, u32>, id: Oid) {
+            if let Some(n) = running.get_mut(&id) {
+                *n -= 1;
+                if *n == 0 {
+                    running.remove(&id);
+                }
+            }
+        }
+
+        match self.owners.remove(query_id) {
+            Some((tenant_id, user_id)) => {
+                decrease(&mut self.tenants, tenant_id);
+                decrease(&mut self.users, user_id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 pub struct QueryTracker {
     queries: RwLock<HashMap<QueryId, Arc<dyn QueryExecution>>>,
     query_limit: usize,
     query_persister: QueryPersisterRef,
+    slots: Mutex<QuerySlots>,
+    slot_released: Notify,
 }
 
 impl QueryTracker {
@@ -31,6 +109,8 @@ impl QueryTracker {
             queries: RwLock::new(HashMap::new()),
             query_limit,
             query_persister,
+            slots: Mutex::new(QuerySlots::default()),
+            slot_released: Notify::new(),
         }
     }
 }
@@ -46,6 +126,21 @@ impl QueryTracker {
         self: &Arc<Self>,
         query_id: QueryId,
         query: Arc<dyn QueryExecution>,
+    ) -> Result<TrackedQuery> {
+        self.try_track_query_with_quota(query_id, query, QueryQuota::default())
+            .await
+    }
+
+    /// track a query limited by the quota
+    ///
+    /// The concurrent queries are limited when the query is started, it waits for a free slot
+    /// if [`QueryQuota::queue_timeout`] is set, otherwise [`QueryError::QueryQuotaExceeded`] is returned.
+    /// The query running longer than [`QueryQuota::max_execution_time`] is cancelled.
+    pub async fn try_track_query_with_quota(
+        self: &Arc<Self>,
+        query_id: QueryId,
+        query: Arc<dyn QueryExecution>,
+        quota: QueryQuota,
     ) -> Result<TrackedQuery> {
         debug!(
             "total query count: {}, status {:?}",
@@ -68,6 +163,7 @@ impl QueryTracker {
                 inner: query,
                 query_id,
                 tracker: self.clone(),
+                quota,
             }),
             QueryType::Stream => {
                 // 流任务是常驻任务，需要手动kill，不需要在这里做代理
@@ -104,6 +200,10 @@ impl QueryTracker {
     }
 
     pub fn expire_query(&self, id: &QueryId) -> Option<Arc<dyn QueryExecution>> {
+        if self.slots.lock().release(id) {
+            self.slot_released.notify_waiters();
+        }
+
         self.queries.write().remove(id).map(|q| {
             if q.need_persist() {
                 let _ = self.query_persister.remove(id).map_err(|err| {
@@ -114,6 +214,36 @@ impl QueryTracker {
         })
     }
 
+    /// Take a slot of the concurrent queries of the tenant and the user, released by [`QueryTracker::expire_query`]
+    async fn acquire_slot(
+        &self,
+        query_id: QueryId,
+        quota: &QueryQuota,
+        on_queued: impl FnOnce(),
+    ) -> Result<()> {
+        let deadline = quota.queue_timeout.map(|timeout| Instant::now() + timeout);
+        let mut on_queued = Some(on_queued);
+        loop {
+            // created before checking the slots, so that a slot released in between is not missed
+            let released = self.slot_released.notified();
+            let err = match self.slots.lock().try_acquire(query_id, quota) {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+            let deadline = match deadline {
+                Some(deadline) => deadline,
+                None => return Err(err),
+            };
+            if let Some(on_queued) = on_queued.take() {
+                debug!("Query {:?} is queued: {}", query_id, err);
+                on_queued();
+            }
+            if tokio::time::timeout_at(deadline, released).await.is_err() {
+                return Err(err);
+            }
+        }
+    }
+
     async fn save_query(&self, query_id: QueryId, query: Arc<dyn QueryExecution>) -> Result<()> {
         if self.queries.read().len() >= self.query_limit {
             warn!("simultaneous request limit exceeded - dropping request");
@@ -158,6 +288,7 @@ pub struct QueryExecutionTrackedProxy {
     inner: QueryExecutionRef,
     query_id: QueryId,
     tracker: Arc<QueryTracker>,
+    quota: QueryQuota,
 }
 
 #[async_trait]
@@ -167,13 +298,49 @@ impl QueryExecution for QueryExecutionTrackedProxy {
     }
 
     async fn start(&self) -> Result<Output> {
-        match self.inner.start().await {
+        // the future is dropped if the client is gone before the query is started
+        let mut guard = CancelOnDrop {
+            query_id: self.query_id,
+            tracker: self.tracker.clone(),
+            armed: true,
+        };
+        if let Err(err) = self
+            .tracker
+            .acquire_slot(self.query_id, &self.quota, || self.inner.queued())
+            .await
+        {
+            guard.armed = false;
+            let _ = self.tracker.expire_query(&self.query_id);
+            return Err(err);
+        }
+
+        let deadline = self
+            .quota
+            .max_execution_time
+            .map(|timeout| (Instant::now() + timeout, timeout));
+        let result = match deadline {
+            Some((deadline, timeout)) => {
+                match tokio::time::timeout_at(deadline, self.inner.start()).await {
+                    Ok(result) => result,
+                    // cancelled by the guard
+                    Err(_) => return Err(QueryError::QueryTimeout { timeout }),
+                }
+            }
+            None => self.inner.start().await,
+        };
+        guard.armed = false;
+
+        match result {
             Ok(Output::StreamData(stream)) => {
                 debug!("Track RecordBatchStream: {:?}", self.query_id);
                 Ok(Output::StreamData(Box::pin(TrackedRecordBatchStream {
                     inner: stream,
                     query_id: self.query_id,
                     tracker: self.tracker.clone(),
+                    finished: false,
+                    deadline: deadline.map(|(deadline, timeout)| {
+                        (Box::pin(tokio::time::sleep_until(deadline)), timeout)
+                    }),
                 })))
             }
             Ok(nil @ Output::Nil(_)) => {
@@ -197,6 +364,10 @@ impl QueryExecution for QueryExecutionTrackedProxy {
         self.inner.cancel()
     }
 
+    fn queued(&self) {
+        self.inner.queued()
+    }
+
     fn info(&self) -> QueryInfo {
         self.inner.info()
     }
@@ -210,10 +381,38 @@ impl QueryExecution for QueryExecutionTrackedProxy {
     }
 }
 
+/// Cancels the query which is dropped before it's done, e.g. the client disconnected.
+fn expire_and_cancel(tracker: &QueryTracker, query_id: &QueryId) {
+    if let Some(query) = tracker.expire_query(query_id) {
+        debug!("Cancel unfinished query: {:?}", query_id);
+        if let Err(err) = query.cancel() {
+            warn!("Cancel query {:?} failed: {:?}", query_id, err);
+        }
+    }
+}
+
+struct CancelOnDrop {
+    query_id: QueryId,
+    tracker: Arc<QueryTracker>,
+    armed: bool,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if self.armed {
+            expire_and_cancel(&self.tracker, &self.query_id);
+        }
+    }
+}
+
 pub struct TrackedRecordBatchStream {
     inner: SendableRecordBatchStream,
     query_id: QueryId,
     tracker: Arc<QueryTracker>,
+    /// The stream is exhausted or failed
+    finished: bool,
+    /// The query is cancelled when it runs out of the max execution time
+    deadline: Option<(Pin<Box<Sleep>>, Duration)>,
 }
 
 impl RecordBatchStream for TrackedRecordBatchStream {
@@ -226,35 +425,63 @@ impl Stream for TrackedRecordBatchStream {
     type Item = DFResult<RecordBatch>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.inner.poll_next_unpin(cx)
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        if let Some((sleep, timeout)) = self.deadline.as_mut() {
+            if sleep.poll_unpin(cx).is_ready() {
+                let timeout = *timeout;
+                self.deadline = None;
+                self.finished = true;
+                expire_and_cancel(&self.tracker, &self.query_id);
+                return Poll::Ready(Some(Err(DataFusionError::External(Box::new(
+                    QueryError::QueryTimeout { timeout },
+                )))));
+            }
+        }
+
+        let poll = self.inner.poll_next_unpin(cx);
+        if let Poll::Ready(None | Some(Err(_))) = &poll {
+            self.finished = true;
+        }
+        poll
     }
 }
 
 impl Drop for TrackedRecordBatchStream {
     fn drop(&mut self) {
         debug!("Query drop: {:?}", self.query_id);
-        let _ = self.tracker.expire_query(&self.query_id);
+        if self.finished {
+            let _ = self.tracker.expire_query(&self.query_id);
+        } else {
+            expire_and_cancel(&self.tracker, &self.query_id);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::Arc;
     use std::time::Duration;
 
     use async_trait::async_trait;
     use datafusion::arrow::datatypes::Schema;
     use datafusion::physical_plan::EmptyRecordBatchStream;
+    use futures::StreamExt;
     use models::auth::user::{UserDesc, UserOptions};
     use spi::query::dispatcher::{QueryInfo, QueryStatus};
     use spi::query::execution::{Output, QueryExecution, QueryState, RUNNING};
     use spi::service::protocol::QueryId;
     use spi::QueryError;
 
-    use super::QueryTracker;
+    use super::{QueryQuota, QueryTracker};
     use crate::dispatcher::persister::LocalQueryPersister;
 
-    struct QueryExecutionMock {}
+    #[derive(Default)]
+    struct QueryExecutionMock {
+        cancelled: AtomicBool,
+    }
 
     #[async_trait]
     impl QueryExecution for QueryExecutionMock {
@@ -264,6 +491,7 @@ mod tests {
             ))))
         }
         fn cancel(&self) -> std::result::Result<(), QueryError> {
+            self.cancelled.store(true, Ordering::Relaxed);
             Ok(())
         }
         fn info(&self) -> QueryInfo {
@@ -296,7 +524,7 @@ mod tests {
     #[tokio::test]
     async fn test_track_and_drop() {
         let query_id = QueryId::next_id();
-        let query = Arc::new(QueryExecutionMock {});
+        let query = Arc::new(QueryExecutionMock::default());
         let tracker = Arc::new(new_query_tracker(10));
 
         let _tq = tracker
@@ -333,7 +561,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_track_stream_result_and_drop() {
-        let query = Arc::new(QueryExecutionMock {});
+        let query = Arc::new(QueryExecutionMock::default());
         let tracker = Arc::new(new_query_tracker(10));
 
         let output = {
@@ -350,10 +578,116 @@ mod tests {
         assert_eq!(tracker._running_query_count(), 0);
     }
 
+    #[tokio::test]
+    async fn test_cancel_unfinished_stream_on_drop() {
+        let query = Arc::new(QueryExecutionMock::default());
+        let tracker = Arc::new(new_query_tracker(10));
+
+        let tq = tracker
+            .try_track_query(QueryId::next_id(), query.clone())
+            .await
+            .unwrap();
+        // the client is gone before the result is read
+        drop(tq.start().await.unwrap());
+        assert_eq!(tracker._running_query_count(), 0);
+        assert!(query.cancelled.load(Ordering::Relaxed));
+
+        let query = Arc::new(QueryExecutionMock::default());
+        let tq = tracker
+            .try_track_query(QueryId::next_id(), query.clone())
+            .await
+            .unwrap();
+        let mut output = tq.start().await.unwrap();
+        while output.next().await.is_some() {}
+        drop(output);
+        assert_eq!(tracker._running_query_count(), 0);
+        assert!(!query.cancelled.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_exceed_query_quota() {
+        let tracker = Arc::new(new_query_tracker(10));
+        let quota = QueryQuota {
+            user_max_concurrent_queries: Some(1),
+            ..Default::default()
+        };
+
+        let tq = tracker
+            .try_track_query_with_quota(
+                QueryId::next_id(),
+                Arc::new(QueryExecutionMock::default()),
+                quota.clone(),
+            )
+            .await
+            .unwrap();
+        let output = tq.start().await.unwrap();
+
+        let tq = tracker
+            .try_track_query_with_quota(
+                QueryId::next_id(),
+                Arc::new(QueryExecutionMock::default()),
+                quota.clone(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            tq.start().await,
+            Err(QueryError::QueryQuotaExceeded { limit: 1, .. })
+        ));
+        assert_eq!(tracker._running_query_count(), 1);
+
+        // the slot is released with the result
+        drop(output);
+        let tq = tracker
+            .try_track_query_with_quota(
+                QueryId::next_id(),
+                Arc::new(QueryExecutionMock::default()),
+                quota,
+            )
+            .await
+            .unwrap();
+        assert!(tq.start().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_query_quota() {
+        let tracker = Arc::new(new_query_tracker(10));
+        let quota = QueryQuota {
+            tenant_max_concurrent_queries: Some(1),
+            queue_timeout: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+
+        let tq = tracker
+            .try_track_query_with_quota(
+                QueryId::next_id(),
+                Arc::new(QueryExecutionMock::default()),
+                quota.clone(),
+            )
+            .await
+            .unwrap();
+        let output = tq.start().await.unwrap();
+
+        let queued = tracker
+            .try_track_query_with_quota(
+                QueryId::next_id(),
+                Arc::new(QueryExecutionMock::default()),
+                quota,
+            )
+            .await
+            .unwrap();
+        let waiting = tokio::spawn(async move { queued.start().await.map(|_| ()) });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        drop(output);
+        waiting.await.unwrap().unwrap();
+    }
+
     #[tokio::test]
     async fn test_exceed_query_limit() {
         let query_id = QueryId::next_id();
-        let query = Arc::new(QueryExecutionMock {});
+        let query = Arc::new(QueryExecutionMock::default());
         let tracker = Arc::new(new_query_tracker(2));
 
         let _tq = tracker
@@ -376,7 +710,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_query_info() {
         let query_id = QueryId::next_id();
-        let query = Arc::new(QueryExecutionMock {});
+        let query = Arc::new(QueryExecutionMock::default());
         let tracker = Arc::new(new_query_tracker(2));
 
         let _tq = tracker
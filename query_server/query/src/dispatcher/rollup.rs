@@ -0,0 +1,410 @@
+//! Scheduler of rollup policies.
+//!
+//! Each policy is scheduled by a trigger executor at its interval. A run aggregates the
+//! closed time windows after the watermark of the policy from the source table into the
+//! target table, then deletes the data of the target table older than the ttl. Points of
+//! a window rolled up twice overwrite each other, so re-running a window is harmless.
+//!
+//! Only the query node holding the rollup lease in meta schedules the policies, the
+//! lease is renewed at every sync, other nodes cancel their jobs.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{SecondsFormat, TimeZone, Utc};
+use coordinator::service::CoordinatorRef;
+use meta::error::MetaError;
+use meta::model::MetaClientRef;
+use models::auth::user::User;
+use models::oid::Identifier;
+use models::runtime::executor::{DedicatedExecutor, Job};
+use models::schema::RollupPolicy;
+use spi::query::config::StreamTriggerInterval;
+use spi::query::dispatcher::QueryDispatcher;
+use spi::service::protocol::{ContextBuilder, Query, QueryId};
+use spi::Result;
+use trace::{debug, info, warn};
+
+use super::manager::SimpleQueryDispatcher;
+use crate::execution::stream::trigger::executor::TriggerExecutorFactory;
+
+/// Interval of loading rollup policies from meta.
+const SYNC_POLICIES_INTERVAL: Duration = Duration::from_secs(10);
+/// Time to live of the rollup lease, a node that failed to renew the lease holds it
+/// for up to this long.
+const ROLLUP_LEASE_TTL: Duration = Duration::from_secs(30);
+
+pub struct RollupScheduler {
+    coord: CoordinatorRef,
+    dispatcher: SimpleQueryDispatcher,
+    runtime: Arc<DedicatedExecutor>,
+    trigger_executor_factory: TriggerExecutorFactory,
+    // (tenant, database, policy name) -> (interval in nanoseconds, job)
+    jobs: HashMap<(String, String, String), (i64, Job<()>)>,
+}
+
+impl RollupScheduler {
+    pub fn new(coord: CoordinatorRef, dispatcher: SimpleQueryDispatcher) -> Self {
+        let runtime = Arc::new(DedicatedExecutor::new("rollup-trigger", 1));
+        let trigger_executor_factory = TriggerExecutorFactory::new(runtime.clone());
+        Self {
+            coord,
+            dispatcher,
+            runtime,
+            trigger_executor_factory,
+            jobs: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        let mut ticker = tokio::time::interval(SYNC_POLICIES_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match self.acquire_lease().await {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.sync_jobs(vec![]);
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Failed to acquire rollup lease: {}", e);
+                    self.sync_jobs(vec![]);
+                    continue;
+                }
+            }
+            match self.list_policies().await {
+                Ok(policies) => self.sync_jobs(policies),
+                Err(e) => warn!("Failed to load rollup policies: {}", e),
+            }
+        }
+    }
+
+    async fn acquire_lease(&self) -> Result<bool> {
+        let acquired = self
+            .coord
+            .meta_manager()
+            .acquire_rollup_lease(self.coord.node_id(), ROLLUP_LEASE_TTL.as_nanos() as i64)
+            .await?;
+        Ok(acquired)
+    }
+
+    async fn list_policies(&self) -> Result<Vec<RollupPolicy>> {
+        let meta = self.coord.meta_manager();
+        let mut policies = vec![];
+        for tenant in meta.tenants().await? {
+            if let Some(client) = meta.tenant_meta(tenant.name()).await {
+                policies.extend(client.rollup_policies().await?);
+            }
+        }
+        Ok(policies)
+    }
+
+    /// Schedule new policies and cancel the jobs of dropped ones.
+    fn sync_jobs(&mut self, policies: Vec<RollupPolicy>) {
+        let mut alive = HashSet::with_capacity(policies.len());
+        for policy in policies {
+            let key = (
+                policy.tenant.clone(),
+                policy.database.clone(),
+                policy.name.clone(),
+            );
+            let interval = policy.interval.to_nanoseconds();
+            alive.insert(key.clone());
+            if matches!(self.jobs.get(&key), Some((i, _)) if *i == interval) {
+                continue;
+            }
+
+            info!(
+                "Schedule rollup policy {} of database {}.{}",
+                policy.name, policy.tenant, policy.database
+            );
+            let trigger = StreamTriggerInterval::Interval(Duration::from_nanos(interval as u64));
+            let executor = self.trigger_executor_factory.create(&trigger);
+            let coord = self.coord.clone();
+            let dispatcher = self.dispatcher.clone();
+            let (tenant, database, name) = key.clone();
+            let job = executor.schedule(
+                move |_| {
+                    run_policy(
+                        coord.clone(),
+                        dispatcher.clone(),
+                        tenant.clone(),
+                        database.clone(),
+                        name.clone(),
+                    )
+                },
+                self.runtime.clone(),
+            );
+            self.jobs.insert(key, (interval, job));
+        }
+
+        self.jobs.retain(|key, _| {
+            let retain = alive.contains(key);
+            if !retain {
+                let (tenant, database, name) = key;
+                info!(
+                    "Cancel rollup policy {} of database {}.{}",
+                    name, tenant, database
+                );
+            }
+            retain
+        });
+    }
+}
+
+async fn run_policy(
+    coord: CoordinatorRef,
+    dispatcher: SimpleQueryDispatcher,
+    tenant: String,
+    database: String,
+    name: String,
+) -> Result<()> {
+    let meta = coord
+        .tenant_meta(&tenant)
+        .await
+        .ok_or_else(|| MetaError::TenantNotFound {
+            tenant: tenant.clone(),
+        })?;
+    // reload the policy for the latest progress
+    let policy = match meta
+        .rollup_policies()
+        .await?
+        .into_iter()
+        .find(|p| p.database == database && p.name == name)
+    {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+
+    let now = Utc::now().timestamp_nanos();
+    let (start, end) = match policy.next_time_range(now) {
+        Some(range) => range,
+        None => return Ok(()),
+    };
+
+    debug!(
+        "Run rollup policy {} of database {}.{}, range: [{:?}, {})",
+        name, tenant, database, start, end
+    );
+    let result = match owner(&coord, &policy).await {
+        Ok(owner) => roll_up(&dispatcher, &meta, &owner, &policy, start, end, now).await,
+        Err(e) => Err(e),
+    };
+
+    let mut progress = policy.progress;
+    progress.last_run_time = Some(now);
+    progress.run_count += 1;
+    match &result {
+        Ok(_) => {
+            progress.watermark = Some(end);
+            progress.last_error.clear();
+        }
+        Err(e) => {
+            warn!(
+                "Failed to run rollup policy {} of database {}.{}: {}",
+                name, tenant, database, e
+            );
+            progress.error_count += 1;
+            progress.last_error = e.to_string();
+        }
+    }
+    meta.update_rollup_progress(&database, &name, progress)
+        .await?;
+
+    result
+}
+
+/// The owner of the policy with the privileges granted to them now.
+async fn owner(coord: &CoordinatorRef, policy: &RollupPolicy) -> Result<User> {
+    let owner = coord
+        .meta_manager()
+        .user_with_privileges(policy.owner.name(), Some(&policy.tenant))
+        .await?;
+    // the owner was dropped, and another user was created with the same name
+    if owner.desc().id() != policy.owner.id() {
+        return Err(MetaError::UserNotFound {
+            user: policy.owner.name().to_string(),
+        }
+        .into());
+    }
+    Ok(owner)
+}
+
+async fn roll_up(
+    dispatcher: &SimpleQueryDispatcher,
+    meta: &MetaClientRef,
+    owner: &User,
+    policy: &RollupPolicy,
+    start: Option<i64>,
+    end: i64,
+    now: i64,
+) -> Result<()> {
+    let source_schema = meta
+        .get_tskv_table_schema(&policy.database, &policy.source_table)?
+        .ok_or_else(|| MetaError::TableNotFound {
+            table: policy.source_table.clone(),
+        })?;
+    let tags: Vec<&str> = source_schema
+        .columns()
+        .iter()
+        .filter(|c| c.column_type.is_tag())
+        .map(|c| c.name.as_str())
+        .collect();
+
+    let sql = rollup_sql(policy, &tags, start, end);
+    execute_sql(dispatcher, meta, owner, policy, sql).await?;
+
+    if let Some(ttl) = &policy.ttl {
+        let ttl = ttl.to_nanoseconds();
+        if ttl != i64::MAX {
+            let sql = format!(
+                "DELETE FROM {} WHERE \"time\" < '{}'",
+                quote_ident(&policy.target_table),
+                format_timestamp(now.saturating_sub(ttl))
+            );
+            execute_sql(dispatcher, meta, owner, policy, sql).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn execute_sql(
+    dispatcher: &SimpleQueryDispatcher,
+    meta: &MetaClientRef,
+    owner: &User,
+    policy: &RollupPolicy,
+    sql: String,
+) -> Result<()> {
+    let ctx = ContextBuilder::new(owner.clone())
+        .with_tenant(Some(policy.tenant.clone()))
+        .with_database(Some(policy.database.clone()))
+        .build();
+    let query = Query::new(ctx, sql);
+    let output = dispatcher
+        .execute_query(*meta.tenant().id(), QueryId::next_id(), &query, None)
+        .await?;
+    // drive the stream to write all rows
+    output.chunk_result().await?;
+
+    Ok(())
+}
+
+/// Build the statement that rolls up `[start, end)` of the source table into the target table.
+fn rollup_sql(policy: &RollupPolicy, tags: &[&str], start: Option<i64>, end: i64) -> String {
+    let time_bucket = format!(
+        "date_bin(INTERVAL '{} seconds', \"time\")",
+        policy.interval.to_nanoseconds() / 1_000_000_000
+    );
+    let tags: Vec<String> = tags.iter().map(|t| quote_ident(t)).collect();
+
+    let mut insert_columns = vec!["\"time\"".to_string()];
+    insert_columns.extend(tags.iter().cloned());
+    let mut select_exprs = vec![format!("{time_bucket} AS \"time\"")];
+    select_exprs.extend(tags.iter().cloned());
+    for aggregate in policy.aggregates.iter() {
+        let field = quote_ident(&aggregate.field);
+        let expr = match aggregate.function.as_str() {
+            "first" | "last" => format!("{}(\"time\", {field})", aggregate.function),
+            function => format!("{function}({field})"),
+        };
+        let target = quote_ident(&aggregate.target);
+        select_exprs.push(format!("{expr} AS {target}"));
+        insert_columns.push(target);
+    }
+
+    let mut filter = format!("\"time\" < '{}'", format_timestamp(end));
+    if let Some(start) = start {
+        filter = format!("\"time\" >= '{}' AND {filter}", format_timestamp(start));
+    }
+    let mut group_by = vec![time_bucket];
+    group_by.extend(tags);
+
+    format!(
+        "INSERT INTO {} ({}) SELECT {} FROM {} WHERE {} GROUP BY {}",
+        quote_ident(&policy.target_table),
+        insert_columns.join(", "),
+        select_exprs.join(", "),
+        quote_ident(&policy.source_table),
+        filter,
+        group_by.join(", ")
+    )
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn format_timestamp(nanos: i64) -> String {
+    Utc.timestamp_nanos(nanos)
+        .to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+#[cfg(test)]
+mod test {
+    use models::auth::user::{UserDesc, UserOptions};
+    use models::oid::Oid;
+    use models::schema::{Duration, RollupAggregate, RollupPolicy, RollupProgress};
+
+    use super::rollup_sql;
+
+    fn policy() -> RollupPolicy {
+        RollupPolicy {
+            tenant: "cnosdb".to_string(),
+            database: "public".to_string(),
+            name: "cpu_1m".to_string(),
+            source_table: "cpu".to_string(),
+            target_table: "cpu_1m".to_string(),
+            interval: Duration::new("1m").unwrap(),
+            aggregates: vec![
+                RollupAggregate::new("usage".to_string(), "AVG".to_string(), None),
+                RollupAggregate::new(
+                    "usage".to_string(),
+                    "last".to_string(),
+                    Some("usage_last".to_string()),
+                ),
+            ],
+            ttl: None,
+            owner: UserDesc::new(
+                Oid::default(),
+                "root".to_string(),
+                UserOptions::default(),
+                true,
+            ),
+            progress: RollupProgress::default(),
+        }
+    }
+
+    #[test]
+    fn test_next_time_range() {
+        let mut policy = policy();
+        let minute = 60_000_000_000_i64;
+
+        assert_eq!(
+            policy.next_time_range(minute * 3 + 5),
+            Some((None, minute * 3))
+        );
+        policy.progress.watermark = Some(minute * 3);
+        assert_eq!(policy.next_time_range(minute * 3 + 5), None);
+        assert_eq!(
+            policy.next_time_range(minute * 5),
+            Some((Some(minute * 3), minute * 5))
+        );
+    }
+
+    #[test]
+    fn test_rollup_sql() {
+        let sql = rollup_sql(&policy(), &["host"], Some(0), 60_000_000_000);
+        assert_eq!(
+            sql,
+            "INSERT INTO \"cpu_1m\" (\"time\", \"host\", \"usage\", \"usage_last\") \
+            SELECT date_bin(INTERVAL '60 seconds', \"time\") AS \"time\", \"host\", \
+            avg(\"usage\") AS \"usage\", last(\"time\", \"usage\") AS \"usage_last\" \
+            FROM \"cpu\" \
+            WHERE \"time\" >= '1970-01-01T00:00:00.000000000Z' \
+            AND \"time\" < '1970-01-01T00:01:00.000000000Z' \
+            GROUP BY date_bin(INTERVAL '60 seconds', \"time\"), \"host\""
+        );
+    }
+}
@@ -61,4 +61,13 @@ fn build_database_schema(database_options: &DatabaseOptions, config: &mut Databa
     if let Some(precision) = database_options.precision() {
         config.with_precision(*precision);
     }
+    if let Some(cold_duration) = database_options.cold_duration() {
+        config.with_cold_duration(cold_duration.clone());
+    }
+    if let Some(compaction_strategy) = database_options.compaction_strategy() {
+        config.with_compaction_strategy(*compaction_strategy);
+    }
+    if let Some(compaction_window) = database_options.compaction_window() {
+        config.with_compaction_window(compaction_window.clone());
+    }
 }
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use coordinator::backup::BackupManifest;
+use meta::error::MetaError;
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::BackupDatabase;
+use spi::{QueryError, Result};
+use trace::info;
+use tskv::backup::{BackupLocation, BackupStore};
+
+use super::DDLDefinitionTask;
+
+pub struct BackupDatabaseTask {
+    stmt: BackupDatabase,
+}
+
+impl BackupDatabaseTask {
+    #[inline(always)]
+    pub fn new(stmt: BackupDatabase) -> Self {
+        Self { stmt }
+    }
+}
+
+#[async_trait]
+impl DDLDefinitionTask for BackupDatabaseTask {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let BackupDatabase {
+            ref tenant_name,
+            ref db_name,
+            ref location,
+            incremental,
+        } = self.stmt;
+
+        let meta = query_state_machine
+            .meta
+            .tenant_meta(tenant_name)
+            .await
+            .ok_or_else(|| MetaError::TenantNotFound {
+                tenant: tenant_name.to_string(),
+            })?;
+        let db_info = meta
+            .get_db_info(db_name)?
+            .ok_or_else(|| MetaError::DatabaseNotFound {
+                database: db_name.to_string(),
+            })?;
+
+        check_local_location(&query_state_machine, location).await?;
+        let store = BackupStore::new(location)?;
+        let base = if incremental {
+            let base =
+                BackupManifest::load(&store)
+                    .await?
+                    .ok_or_else(|| QueryError::InvalidBackup {
+                        reason: "no backup to do incremental backup on".to_string(),
+                    })?;
+            if base.tenant != *tenant_name || base.database.database_name() != db_name {
+                return Err(QueryError::InvalidBackup {
+                    reason: format!(
+                        "the location is the backup of database {}.{}",
+                        base.tenant,
+                        base.database.database_name()
+                    ),
+                });
+            }
+            Some(base)
+        } else {
+            None
+        };
+
+        let backup_id = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f").to_string();
+        info!("backup database {tenant_name}.{db_name}, backup id: {backup_id}");
+
+        let buckets = query_state_machine
+            .coord
+            .backup_database(tenant_name, db_name, location, &backup_id, base.as_ref())
+            .await?;
+
+        let manifest = BackupManifest {
+            backup_id,
+            base_backup_id: base.map(|b| b.backup_id),
+            tenant: tenant_name.clone(),
+            database: db_info.schema,
+            tables: db_info.tables.into_values().collect(),
+            buckets,
+        };
+        manifest.save(&store).await?;
+
+        Ok(Output::Nil(()))
+    }
+}
+
+/// The vnodes are backed up on the data nodes and the manifest is written on this node,
+/// a local directory only holds the whole backup if this node is the only data node.
+pub(super) async fn check_local_location(
+    query_state_machine: &QueryStateMachineRef,
+    location: &BackupLocation,
+) -> Result<()> {
+    if !location.is_local() {
+        return Ok(());
+    }
+    let node_id = query_state_machine.coord.node_id();
+    let data_nodes = query_state_machine.meta.data_nodes().await;
+    if data_nodes.iter().any(|node| node.id != node_id) {
+        return Err(QueryError::InvalidBackup {
+            reason: "local directory is only supported on a single node, use an object store"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
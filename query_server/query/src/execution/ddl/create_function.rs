@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use meta::error::MetaError;
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::CreateFunction;
+use spi::{QueryError, Result};
+use trace::debug;
+
+use super::DDLDefinitionTask;
+
+pub struct CreateFunctionTask {
+    stmt: CreateFunction,
+}
+
+impl CreateFunctionTask {
+    pub fn new(stmt: CreateFunction) -> Self {
+        Self { stmt }
+    }
+}
+
+#[async_trait]
+impl DDLDefinitionTask for CreateFunctionTask {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let CreateFunction {
+            ref if_not_exists,
+            ref function,
+        } = self.stmt;
+
+        let meta = query_state_machine
+            .meta
+            .tenant_meta(&function.tenant)
+            .await
+            .ok_or_else(|| QueryError::Meta {
+                source: MetaError::TenantNotFound {
+                    tenant: function.tenant.to_string(),
+                },
+            })?;
+
+        debug!(
+            "Create function {} of tenant {}: {:?}",
+            function.name, function.tenant, function
+        );
+        match meta.create_function(function.clone()).await {
+            Ok(_) => Ok(Output::Nil(())),
+            Err(MetaError::FunctionAlreadyExists { .. }) if *if_not_exists => Ok(Output::Nil(())),
+            Err(e) => Err(QueryError::Meta { source: e }),
+        }
+    }
+}
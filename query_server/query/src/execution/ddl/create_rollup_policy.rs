@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use meta::error::MetaError;
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::CreateRollupPolicy;
+use spi::{QueryError, Result};
+use trace::debug;
+
+use super::DDLDefinitionTask;
+
+pub struct CreateRollupPolicyTask {
+    stmt: CreateRollupPolicy,
+}
+
+impl CreateRollupPolicyTask {
+    pub fn new(stmt: CreateRollupPolicy) -> Self {
+        Self { stmt }
+    }
+}
+
+#[async_trait]
+impl DDLDefinitionTask for CreateRollupPolicyTask {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let CreateRollupPolicy {
+            ref if_not_exists,
+            ref policy,
+        } = self.stmt;
+
+        let meta = query_state_machine
+            .meta
+            .tenant_meta(&policy.tenant)
+            .await
+            .ok_or_else(|| QueryError::Meta {
+                source: MetaError::TenantNotFound {
+                    tenant: policy.tenant.to_string(),
+                },
+            })?;
+
+        debug!(
+            "Create rollup policy {} of tenant {}: {:?}",
+            policy.name, policy.tenant, policy
+        );
+        match meta.create_rollup_policy(policy.clone()).await {
+            Ok(_) => Ok(Output::Nil(())),
+            Err(MetaError::RollupPolicyAlreadyExists { .. }) if *if_not_exists => {
+                Ok(Output::Nil(()))
+            }
+            Err(e) => Err(QueryError::Meta { source: e }),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use meta::error::MetaError;
+use models::schema::{TableSchema, ViewTableSchema};
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::CreateView;
+use spi::{QueryError, Result};
+use trace::debug;
+
+use super::DDLDefinitionTask;
+
+pub struct CreateViewTask {
+    stmt: CreateView,
+}
+
+impl CreateViewTask {
+    #[inline(always)]
+    pub fn new(stmt: CreateView) -> Self {
+        Self { stmt }
+    }
+}
+
+#[async_trait]
+impl DDLDefinitionTask for CreateViewTask {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let CreateView {
+            or_replace,
+            ref name,
+            ref query,
+            ref schema,
+        } = self.stmt;
+
+        let tenant = name.tenant();
+        let client = query_state_machine.meta.tenant_meta(tenant).await.ok_or(
+            MetaError::TenantNotFound {
+                tenant: tenant.to_string(),
+            },
+        )?;
+
+        let view = TableSchema::ViewTableSchema(Arc::new(ViewTableSchema {
+            tenant: tenant.to_string(),
+            db: name.database().to_string(),
+            name: name.table().to_string(),
+            query: query.clone(),
+            schema: schema.clone(),
+        }));
+
+        match client.get_table_schema(name.database(), name.table())? {
+            // replace the definition of the view
+            Some(TableSchema::ViewTableSchema(_)) if or_replace => {
+                debug!("Replace view {}", name);
+                client.update_table(&view).await?;
+            }
+            Some(TableSchema::ViewTableSchema(_)) => Err(MetaError::TableAlreadyExists {
+                table_name: name.to_string(),
+            })?,
+            // a view can't replace a table
+            Some(_) => {
+                return Err(QueryError::Semantic {
+                    err: format!("{} already exists and is not a view", name),
+                })
+            }
+            None => {
+                debug!("Create view {}", name);
+                client.create_table(&view).await?;
+            }
+        }
+
+        Ok(Output::Nil(()))
+    }
+}
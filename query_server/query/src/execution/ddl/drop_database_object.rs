@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use coordinator::resource_manager::ResourceManager;
 use meta::error::MetaError;
 use models::oid::Identifier;
-use models::schema::{ResourceInfo, ResourceOperator};
+use models::schema::{ResourceInfo, ResourceOperator, TableSchema};
 use spi::query::execution::{Output, QueryStateMachineRef};
 use spi::query::logical_planner::{DatabaseObjectType, DropDatabaseObject};
 use spi::{QueryError, Result};
@@ -41,19 +41,24 @@ impl DDLDefinitionTask for DropDatabaseObjectTask {
                     },
                 )?;
 
-                if client
-                    .get_table_schema(object_name.database(), object_name.table())
-                    .is_ok_and(|opt| opt.is_none())
-                {
-                    if *if_exist {
-                        return Ok(Output::Nil(()));
-                    } else {
-                        return Err(QueryError::Meta {
-                            source: MetaError::TableNotFound {
-                                table: object_name.table().to_string(),
-                            },
+                match client.get_table_schema(object_name.database(), object_name.table()) {
+                    Ok(None) => {
+                        if *if_exist {
+                            return Ok(Output::Nil(()));
+                        } else {
+                            return Err(QueryError::Meta {
+                                source: MetaError::TableNotFound {
+                                    table: object_name.table().to_string(),
+                                },
+                            });
+                        }
+                    }
+                    Ok(Some(TableSchema::ViewTableSchema(_))) => {
+                        return Err(QueryError::Semantic {
+                            err: format!("{} is a view, use DROP VIEW instead", object_name),
                         });
                     }
+                    _ => {}
                 }
 
                 let resourceinfo = ResourceInfo::new(
@@ -73,6 +78,37 @@ impl DDLDefinitionTask for DropDatabaseObjectTask {
                 ResourceManager::add_resource_task(query_state_machine.coord.clone(), resourceinfo)
                     .await?;
             }
+            DatabaseObjectType::View => {
+                info!("Drop view {}", object_name);
+                let tenant = object_name.tenant();
+                let client = query_state_machine.meta.tenant_meta(tenant).await.ok_or(
+                    MetaError::TenantNotFound {
+                        tenant: tenant.to_string(),
+                    },
+                )?;
+
+                match client.get_table_schema(object_name.database(), object_name.table())? {
+                    Some(TableSchema::ViewTableSchema(_)) => {
+                        client
+                            .drop_table(object_name.database(), object_name.table())
+                            .await?;
+                    }
+                    Some(_) => {
+                        return Err(QueryError::Semantic {
+                            err: format!("{} is not a view, use DROP TABLE instead", object_name),
+                        });
+                    }
+                    None => {
+                        if !*if_exist {
+                            return Err(QueryError::Meta {
+                                source: MetaError::TableNotFound {
+                                    table: object_name.table().to_string(),
+                                },
+                            });
+                        }
+                    }
+                }
+            }
         };
 
         Ok(Output::Nil(()))
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use meta::error::MetaError;
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::DropFunction;
+use spi::{QueryError, Result};
+use trace::debug;
+
+use super::DDLDefinitionTask;
+
+pub struct DropFunctionTask {
+    stmt: DropFunction,
+}
+
+impl DropFunctionTask {
+    pub fn new(stmt: DropFunction) -> Self {
+        Self { stmt }
+    }
+}
+
+#[async_trait]
+impl DDLDefinitionTask for DropFunctionTask {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let DropFunction {
+            ref tenant_name,
+            ref name,
+            ref if_exist,
+        } = self.stmt;
+
+        let meta = query_state_machine
+            .meta
+            .tenant_meta(tenant_name)
+            .await
+            .ok_or_else(|| QueryError::Meta {
+                source: MetaError::TenantNotFound {
+                    tenant: tenant_name.to_string(),
+                },
+            })?;
+
+        debug!("Drop function {} of tenant {}", name, tenant_name);
+        let success = meta.drop_function(name).await?;
+
+        if let (false, false) = (if_exist, success) {
+            return Err(QueryError::Meta {
+                source: MetaError::FunctionNotFound {
+                    name: name.to_string(),
+                },
+            });
+        }
+
+        Ok(Output::Nil(()))
+    }
+}
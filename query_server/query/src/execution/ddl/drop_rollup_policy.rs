@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use meta::error::MetaError;
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::DropRollupPolicy;
+use spi::{QueryError, Result};
+use trace::debug;
+
+use super::DDLDefinitionTask;
+
+pub struct DropRollupPolicyTask {
+    stmt: DropRollupPolicy,
+}
+
+impl DropRollupPolicyTask {
+    pub fn new(stmt: DropRollupPolicy) -> Self {
+        Self { stmt }
+    }
+}
+
+#[async_trait]
+impl DDLDefinitionTask for DropRollupPolicyTask {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let DropRollupPolicy {
+            ref tenant_name,
+            ref database,
+            ref name,
+            ref if_exist,
+        } = self.stmt;
+
+        let meta = query_state_machine
+            .meta
+            .tenant_meta(tenant_name)
+            .await
+            .ok_or_else(|| QueryError::Meta {
+                source: MetaError::TenantNotFound {
+                    tenant: tenant_name.to_string(),
+                },
+            })?;
+
+        debug!(
+            "Drop rollup policy {} of database {}.{}",
+            name, tenant_name, database
+        );
+        let success = meta.drop_rollup_policy(database, name).await?;
+
+        if let (false, false) = (if_exist, success) {
+            return Err(QueryError::Meta {
+                source: MetaError::RollupPolicyNotFound {
+                    name: name.to_string(),
+                },
+            });
+        }
+
+        Ok(Output::Nil(()))
+    }
+}
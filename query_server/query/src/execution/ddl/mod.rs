@@ -7,18 +7,25 @@ use spi::Result;
 
 use self::alter_tenant::AlterTenantTask;
 use self::alter_user::AlterUserTask;
+use self::backup_database::BackupDatabaseTask;
 use self::create_external_table::CreateExternalTableTask;
+use self::create_function::CreateFunctionTask;
 use self::create_role::CreateRoleTask;
+use self::create_rollup_policy::CreateRollupPolicyTask;
 use self::create_stream_table::CreateStreamTableTask;
 use self::create_table::CreateTableTask;
 use self::create_tenant::CreateTenantTask;
 use self::create_user::CreateUserTask;
+use self::create_view::CreateViewTask;
 use self::drop_database_object::DropDatabaseObjectTask;
+use self::drop_function::DropFunctionTask;
 use self::drop_global_object::DropGlobalObjectTask;
+use self::drop_rollup_policy::DropRollupPolicyTask;
 use self::drop_tenant_object::DropTenantObjectTask;
 use self::grant_revoke::GrantRevokeTask;
 use self::recover_database::RecoverDatabaseTask;
 use self::recover_tenant::RecoverTenantTask;
+use self::restore_database::RestoreDatabaseTask;
 use crate::execution::ddl::alter_database::AlterDatabaseTask;
 use crate::execution::ddl::alter_table::AlterTableTask;
 use crate::execution::ddl::checksum_group::ChecksumGroupTask;
@@ -32,24 +39,31 @@ mod alter_database;
 mod alter_table;
 mod alter_tenant;
 mod alter_user;
+mod backup_database;
 mod checksum_group;
 mod compact_vnode;
 mod copy_vnode;
 mod create_database;
 mod create_external_table;
+mod create_function;
 mod create_role;
+mod create_rollup_policy;
 mod create_stream_table;
 mod create_table;
 mod create_tenant;
 mod create_user;
+mod create_view;
 mod drop_database_object;
+mod drop_function;
 mod drop_global_object;
+mod drop_rollup_policy;
 mod drop_tenant_object;
 mod drop_vnode;
 mod grant_revoke;
 mod move_node;
 mod recover_database;
 mod recover_tenant;
+mod restore_database;
 
 /// Traits that DDL tasks should implement
 #[async_trait]
@@ -174,10 +188,27 @@ impl DDLDefinitionTaskFactory {
 
                 Box::new(CreateStreamTableTask::new(checker, sub_plan.clone()))
             }
+            DDLPlan::CreateView(sub_plan) => Box::new(CreateViewTask::new(sub_plan.clone())),
+            DDLPlan::CreateRollupPolicy(sub_plan) => {
+                Box::new(CreateRollupPolicyTask::new(sub_plan.clone()))
+            }
+            DDLPlan::DropRollupPolicy(sub_plan) => {
+                Box::new(DropRollupPolicyTask::new(sub_plan.clone()))
+            }
+            DDLPlan::CreateFunction(sub_plan) => {
+                Box::new(CreateFunctionTask::new(sub_plan.clone()))
+            }
+            DDLPlan::DropFunction(sub_plan) => Box::new(DropFunctionTask::new(sub_plan.clone())),
             DDLPlan::RecoverDatabase(sub_plan) => {
                 Box::new(RecoverDatabaseTask::new(sub_plan.clone()))
             }
             DDLPlan::RecoverTenant(sub_plan) => Box::new(RecoverTenantTask::new(sub_plan.clone())),
+            DDLPlan::BackupDatabase(sub_plan) => {
+                Box::new(BackupDatabaseTask::new(sub_plan.clone()))
+            }
+            DDLPlan::RestoreDatabase(sub_plan) => {
+                Box::new(RestoreDatabaseTask::new(sub_plan.clone()))
+            }
         }
     }
 }
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use coordinator::backup::BackupManifest;
+use coordinator::resource_manager::ResourceManager;
+use meta::error::MetaError;
+use meta::model::MetaClientRef;
+use models::oid::Identifier;
+use models::schema::{DatabaseSchema, ResourceInfo, ResourceOperator, TableSchema};
+use spi::query::execution::{Output, QueryStateMachineRef};
+use spi::query::logical_planner::RestoreDatabase;
+use spi::{QueryError, Result};
+use trace::{error, info};
+use tskv::backup::BackupStore;
+
+use super::backup_database::check_local_location;
+use super::DDLDefinitionTask;
+
+pub struct RestoreDatabaseTask {
+    stmt: RestoreDatabase,
+}
+
+impl RestoreDatabaseTask {
+    #[inline(always)]
+    pub fn new(stmt: RestoreDatabase) -> Self {
+        Self { stmt }
+    }
+}
+
+#[async_trait]
+impl DDLDefinitionTask for RestoreDatabaseTask {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let RestoreDatabase {
+            ref tenant_name,
+            ref db_name,
+            ref location,
+        } = self.stmt;
+
+        let meta = query_state_machine
+            .meta
+            .tenant_meta(tenant_name)
+            .await
+            .ok_or_else(|| MetaError::TenantNotFound {
+                tenant: tenant_name.to_string(),
+            })?;
+        if meta.get_db_info(db_name)?.is_some() {
+            return Err(MetaError::DatabaseAlreadyExists {
+                database: db_name.to_string(),
+            })?;
+        }
+
+        check_local_location(&query_state_machine, location).await?;
+        let store = BackupStore::new(location)?;
+        let manifest =
+            BackupManifest::load(&store)
+                .await?
+                .ok_or_else(|| QueryError::InvalidBackup {
+                    reason: "no backup found in the location".to_string(),
+                })?;
+        info!(
+            "restore database {tenant_name}.{db_name} from backup {} of {}.{}",
+            manifest.backup_id,
+            manifest.tenant,
+            manifest.database.database_name()
+        );
+
+        // buckets are created with the shard number of the database
+        let shard_num = manifest.database.config.shard_num_or_default() as usize;
+        if let Some(bucket) = manifest
+            .buckets
+            .iter()
+            .find(|b| b.shard_group.len() != shard_num)
+        {
+            return Err(QueryError::InvalidBackup {
+                reason: format!(
+                    "bucket starts at {} has {} shards, but the database has {shard_num}",
+                    bucket.start_time,
+                    bucket.shard_group.len()
+                ),
+            });
+        }
+
+        let mut database_schema = DatabaseSchema::new(tenant_name, db_name);
+        database_schema.config = manifest.database.config.clone();
+        meta.create_db(database_schema).await?;
+
+        let result = async {
+            // views are planned against tables, create them at last
+            let (views, tables): (Vec<_>, Vec<_>) = manifest
+                .tables
+                .iter()
+                .partition(|t| matches!(t, TableSchema::ViewTableSchema(_)));
+            for table in tables.into_iter().chain(views) {
+                meta.create_table(&table.with_database(tenant_name, db_name))
+                    .await?;
+            }
+
+            query_state_machine
+                .coord
+                .restore_database(tenant_name, db_name, location, &manifest.buckets)
+                .await?;
+            Ok::<_, QueryError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            // don't leave a partially restored database behind
+            if let Err(drop_err) = drop_database(&query_state_machine, &meta, db_name).await {
+                error!("failed to drop restored database {tenant_name}.{db_name}: {drop_err}");
+            }
+            return Err(e);
+        }
+
+        Ok(Output::Nil(()))
+    }
+}
+
+async fn drop_database(
+    query_state_machine: &QueryStateMachineRef,
+    meta: &MetaClientRef,
+    db_name: &str,
+) -> Result<()> {
+    let tenant_name = meta.tenant_name();
+    meta.set_db_is_hidden(&tenant_name, db_name, true).await?;
+    let resourceinfo = ResourceInfo::new(
+        (*meta.tenant().id(), db_name.to_string()),
+        tenant_name.clone() + "-" + db_name,
+        ResourceOperator::DropDatabase(tenant_name, db_name.to_string()),
+        &None,
+    );
+    ResourceManager::add_resource_task(query_state_machine.coord.clone(), resourceinfo).await?;
+    Ok(())
+}
@@ -1,7 +1,14 @@
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use async_trait::async_trait;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DFResult};
+use datafusion::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
 use futures::stream::AbortHandle;
+use futures::{FutureExt, Stream, StreamExt};
 use parking_lot::Mutex;
 use spi::query::dispatcher::{QueryInfo, QueryStatus};
 use spi::query::execution::{Output, QueryExecution, QueryStateMachineRef};
@@ -9,6 +16,7 @@ use spi::query::logical_planner::QueryPlan;
 use spi::query::optimizer::Optimizer;
 use spi::query::scheduler::SchedulerRef;
 use spi::{QueryError, Result};
+use tokio_util::sync::WaitForCancellationFutureOwned;
 use trace::debug;
 
 pub struct SqlQueryExecution {
@@ -59,7 +67,17 @@ impl SqlQueryExecution {
         debug!("Success build result stream.");
         self.query_state_machine.end_schedule();
 
-        Ok(Output::StreamData(stream))
+        let cancelled = self
+            .query_state_machine
+            .session
+            .cancellation_token()
+            .clone()
+            .cancelled_owned();
+        Ok(Output::StreamData(Box::pin(CancellableRecordBatchStream {
+            schema: stream.schema(),
+            inner: Some(stream),
+            cancelled: Box::pin(cancelled),
+        })))
     }
 }
 
@@ -99,6 +117,10 @@ impl QueryExecution for SqlQueryExecution {
         Ok(())
     }
 
+    fn queued(&self) {
+        self.query_state_machine.begin_queue();
+    }
+
     fn info(&self) -> QueryInfo {
         let qsm = &self.query_state_machine;
         QueryInfo::new(
@@ -118,3 +140,37 @@ impl QueryExecution for SqlQueryExecution {
         )
     }
 }
+
+/// Ends the result stream with [`QueryError::Cancel`] once the query is cancelled,
+/// the physical plan is released at the same time.
+struct CancellableRecordBatchStream {
+    schema: SchemaRef,
+    inner: Option<SendableRecordBatchStream>,
+    cancelled: Pin<Box<WaitForCancellationFutureOwned>>,
+}
+
+impl RecordBatchStream for CancellableRecordBatchStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for CancellableRecordBatchStream {
+    type Item = DFResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.inner.is_none() {
+            return Poll::Ready(None);
+        }
+        if self.cancelled.poll_unpin(cx).is_ready() {
+            self.inner = None;
+            return Poll::Ready(Some(Err(DataFusionError::External(Box::new(
+                QueryError::Cancel,
+            )))));
+        }
+        match self.inner.as_mut() {
+            Some(inner) => inner.poll_next_unpin(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use models::auth::privilege::{Privilege, TenantObjectPrivilege};
 use spi::query::execution::{Output, QueryStateMachineRef};
 use spi::service::protocol::QueryId;
 use spi::{QueryError, Result};
@@ -25,15 +26,32 @@ impl KillQueryTask {
 
 #[async_trait]
 impl SystemTask for KillQueryTask {
-    async fn execute(&self, _query_state_machine: QueryStateMachineRef) -> Result<Output> {
-        if let Some(q) = self.query_tracker.expire_query(&self.query_id) {
-            let _ = q.cancel();
-        } else {
-            return Err(QueryError::QueryNotFound {
+    async fn execute(&self, query_state_machine: QueryStateMachineRef) -> Result<Output> {
+        let q = self
+            .query_tracker
+            .query(&self.query_id)
+            .ok_or(QueryError::QueryNotFound {
                 query_id: self.query_id,
+            })?;
+
+        // users can kill their own queries, the others require the system privilege of the tenant
+        let info = q.info();
+        let user = query_state_machine.session.user();
+        let is_owner = info.user_id() == *user.desc().id();
+        if !is_owner && !user.desc().is_admin() && !user.can_access_system(info.tenant_id()) {
+            return Err(QueryError::InsufficientPrivileges {
+                privilege: Privilege::TenantObject(
+                    TenantObjectPrivilege::System,
+                    Some(info.tenant_id()),
+                )
+                .to_string(),
             });
         }
 
+        if let Some(q) = self.query_tracker.expire_query(&self.query_id) {
+            let _ = q.cancel();
+        }
+
         Ok(Output::Nil(()))
     }
 }
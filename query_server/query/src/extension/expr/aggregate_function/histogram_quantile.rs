@@ -0,0 +1,251 @@
+use std::sync::Arc;
+
+use datafusion::arrow::array::{ArrayRef, Float64Array, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field};
+use datafusion::common::cast::as_list_array;
+use datafusion::common::{downcast_value, DataFusionError, Result as DFResult};
+use datafusion::logical_expr::{
+    AccumulatorFactoryFunction, AggregateUDF, ReturnTypeFunction, Signature, StateTypeFunction,
+    TypeSignature, Volatility,
+};
+use datafusion::physical_plan::Accumulator;
+use datafusion::scalar::ScalarValue;
+use spi::query::function::FunctionMetadataManager;
+use spi::Result;
+
+use crate::extension::expr::aggregate_function::HISTOGRAM_QUANTILE_UDAF_NAME;
+
+pub fn register_udaf(func_manager: &mut dyn FunctionMetadataManager) -> Result<AggregateUDF> {
+    let udf = new();
+    func_manager.register_udaf(udf.clone())?;
+    Ok(udf)
+}
+
+fn new() -> AggregateUDF {
+    let return_type_func: ReturnTypeFunction = Arc::new(move |_| Ok(Arc::new(DataType::Float64)));
+
+    let state_type_func: StateTypeFunction = Arc::new(move |_, _| {
+        let list_dt = DataType::List(Arc::new(Field::new("item", DataType::Float64, true)));
+        Ok(Arc::new(vec![DataType::Float64, list_dt.clone(), list_dt]))
+    });
+
+    let accumulator: AccumulatorFactoryFunction =
+        Arc::new(|_, _| Ok(Box::<HistogramQuantileAccumulator>::default()));
+
+    // histogram_quantile(
+    //     quantile DOUBLE,
+    //     le STRING,
+    //     count DOUBLE
+    //   )
+    AggregateUDF::new(
+        HISTOGRAM_QUANTILE_UDAF_NAME,
+        &Signature::one_of(
+            vec![TypeSignature::Exact(vec![
+                DataType::Float64,
+                DataType::Utf8,
+                DataType::Float64,
+            ])],
+            Volatility::Immutable,
+        ),
+        &return_type_func,
+        &accumulator,
+        &state_type_func,
+    )
+}
+
+/// Quantile of the buckets of a prometheus histogram, `le` is the upper bound of a bucket
+/// and `count` is the cumulative count of it.
+#[derive(Debug, Default)]
+struct HistogramQuantileAccumulator {
+    quantile: Option<f64>,
+    // (upper bound, cumulative count)
+    buckets: Vec<(f64, f64)>,
+}
+
+impl HistogramQuantileAccumulator {
+    fn set_quantile(&mut self, quantile: Option<f64>) {
+        if self.quantile.is_none() {
+            self.quantile = quantile;
+        }
+    }
+}
+
+impl Accumulator for HistogramQuantileAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DFResult<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        debug_assert!(
+            values.len() == 3,
+            "histogram_quantile can only take 3 param, but found {}",
+            values.len()
+        );
+
+        let quantiles = downcast_value!(values[0], Float64Array);
+        let les = downcast_value!(values[1], StringArray);
+        let counts = downcast_value!(values[2], Float64Array);
+
+        for i in 0..les.len() {
+            if quantiles.is_valid(i) {
+                self.set_quantile(Some(quantiles.value(i)));
+            }
+            if les.is_null(i) || counts.is_null(i) {
+                continue;
+            }
+            let upper_bound = les.value(i).trim().parse::<f64>().map_err(|_| {
+                DataFusionError::Execution(format!(
+                    "Invalid upper bound of histogram bucket: {}",
+                    les.value(i)
+                ))
+            })?;
+            self.buckets.push((upper_bound, counts.value(i)));
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DFResult<ScalarValue> {
+        let quantile = match self.quantile {
+            Some(quantile) => quantile,
+            None => return Ok(ScalarValue::Float64(None)),
+        };
+        Ok(ScalarValue::Float64(Some(bucket_quantile(
+            quantile,
+            self.buckets.clone(),
+        ))))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.buckets.capacity() * std::mem::size_of::<(f64, f64)>()
+    }
+
+    fn state(&self) -> DFResult<Vec<ScalarValue>> {
+        let (upper_bounds, counts): (Vec<_>, Vec<_>) = self
+            .buckets
+            .iter()
+            .map(|(b, c)| (ScalarValue::from(*b), ScalarValue::from(*c)))
+            .unzip();
+
+        Ok(vec![
+            ScalarValue::Float64(self.quantile),
+            ScalarValue::new_list(Some(upper_bounds), DataType::Float64),
+            ScalarValue::new_list(Some(counts), DataType::Float64),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DFResult<()> {
+        let quantiles = downcast_value!(states[0], Float64Array);
+        let upper_bound_lists = as_list_array(states[1].as_ref())?;
+        let count_lists = as_list_array(states[2].as_ref())?;
+
+        for quantile in quantiles.iter().flatten() {
+            self.set_quantile(Some(quantile));
+        }
+        for (upper_bounds, counts) in upper_bound_lists
+            .iter()
+            .flatten()
+            .zip(count_lists.iter().flatten())
+        {
+            let upper_bounds = downcast_value!(upper_bounds, Float64Array);
+            let counts = downcast_value!(counts, Float64Array);
+            self.buckets.extend(
+                upper_bounds
+                    .values()
+                    .iter()
+                    .copied()
+                    .zip(counts.values().iter().copied()),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Same as `bucketQuantile` of prometheus: find the bucket of the rank of the quantile,
+/// then interpolate linearly in the bucket.
+fn bucket_quantile(quantile: f64, mut buckets: Vec<(f64, f64)>) -> f64 {
+    if quantile.is_nan() {
+        return f64::NAN;
+    }
+    if quantile < 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if quantile > 1.0 {
+        return f64::INFINITY;
+    }
+
+    buckets.sort_by(|a, b| a.0.total_cmp(&b.0));
+    // buckets of the same upper bound are summed up
+    buckets.dedup_by(|b, a| {
+        if a.0 == b.0 {
+            a.1 += b.1;
+            true
+        } else {
+            false
+        }
+    });
+    match buckets.last() {
+        Some((upper_bound, _)) if *upper_bound == f64::INFINITY => {}
+        _ => return f64::NAN,
+    }
+    if buckets.len() < 2 {
+        return f64::NAN;
+    }
+    // cumulative counts may be non-monotonic due to scrape races
+    let mut max = f64::NEG_INFINITY;
+    for bucket in buckets.iter_mut() {
+        max = max.max(bucket.1);
+        bucket.1 = max;
+    }
+
+    let observations = buckets[buckets.len() - 1].1;
+    if observations == 0.0 {
+        return f64::NAN;
+    }
+    let mut rank = quantile * observations;
+    let b = buckets.partition_point(|(_, count)| *count < rank);
+
+    if b == buckets.len() - 1 {
+        return buckets[buckets.len() - 2].0;
+    }
+    if b == 0 && buckets[0].0 <= 0.0 {
+        return buckets[0].0;
+    }
+
+    let mut bucket_start = 0.0;
+    let bucket_end = buckets[b].0;
+    let mut count = buckets[b].1;
+    if b > 0 {
+        bucket_start = buckets[b - 1].0;
+        count -= buckets[b - 1].1;
+        rank -= buckets[b - 1].1;
+    }
+    bucket_start + (bucket_end - bucket_start) * (rank / count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bucket_quantile;
+
+    #[test]
+    fn test_bucket_quantile() {
+        let buckets = vec![(0.1, 10.0), (0.5, 30.0), (1.0, 40.0), (f64::INFINITY, 40.0)];
+        assert!((bucket_quantile(0.5, buckets.clone()) - 0.3).abs() < 1e-9);
+        assert_eq!(bucket_quantile(0.0, buckets.clone()), 0.0);
+        assert_eq!(bucket_quantile(1.0, buckets.clone()), 1.0);
+        assert_eq!(bucket_quantile(2.0, buckets.clone()), f64::INFINITY);
+        // buckets of the same upper bound are summed up, the order doesn't matter
+        let buckets = vec![
+            (f64::INFINITY, 20.0),
+            (0.5, 15.0),
+            (0.1, 5.0),
+            (f64::INFINITY, 20.0),
+            (0.1, 5.0),
+            (0.5, 15.0),
+        ];
+        assert_eq!(bucket_quantile(0.25, buckets), 0.1);
+        // the +Inf bucket is required
+        assert!(bucket_quantile(0.5, vec![(0.1, 1.0), (0.5, 2.0)]).is_nan());
+    }
+}
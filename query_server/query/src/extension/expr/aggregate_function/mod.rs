@@ -2,6 +2,7 @@
 mod example;
 mod first;
 mod gauge;
+mod histogram_quantile;
 mod increase;
 mod last;
 mod mode;
@@ -26,6 +27,7 @@ pub const FIRST_UDAF_NAME: &str = "first";
 pub const LAST_UDAF_NAME: &str = "last";
 pub const MODE_UDAF_NAME: &str = "mode";
 pub const INCREASE_NAME: &str = "increase";
+pub const HISTOGRAM_QUANTILE_UDAF_NAME: &str = "histogram_quantile";
 pub use gauge::GaugeData;
 pub use state_agg::StateAggData;
 
@@ -40,6 +42,7 @@ pub fn register_udafs(func_manager: &mut dyn FunctionMetadataManager) -> Result<
     last::register_udaf(func_manager)?;
     mode::register_udaf(func_manager)?;
     increase::register_udaf(func_manager)?;
+    histogram_quantile::register_udaf(func_manager)?;
     Ok(())
 }
 
@@ -1,2 +1,3 @@
+pub mod push_down_time_bucket_aggregation;
 pub mod reject_cross_join;
 pub mod rewrite_tag_scan;
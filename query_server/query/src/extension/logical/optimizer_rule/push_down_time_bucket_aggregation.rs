@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::Field;
+use datafusion::datasource::source_as_provider;
+use datafusion::error::Result;
+use datafusion::logical_expr::expr::AggregateFunction;
+use datafusion::logical_expr::logical_plan::AggWithGrouping;
+use datafusion::logical_expr::{
+    aggregate_function, Aggregate, Expr, LogicalPlan, LogicalPlanBuilder, TableScan,
+};
+use datafusion::optimizer::optimizer::ApplyOrder;
+use datafusion::optimizer::{OptimizerConfig, OptimizerRule};
+
+use crate::data_source::batch::tskv::ClusterTable;
+
+/// Push down aggregates grouped by time buckets to tskv, tskv returns partial aggregates
+/// of each time bucket of each series, which are merged by a final aggregate.
+///
+/// Triggering conditions:
+/// 1. The input of the aggregate is a table scan of ClusterTable
+/// 2. Aggregates are count, max, min or sum of field columns
+/// 3. Group by a `date_bin` of the time column, and optionally the tag columns
+///
+/// ```text
+/// Aggregate: groupBy=[[host, date_bin(..., time)]], aggr=[[COUNT(value)]]
+///   TableScan: t projection=[time, host, value]
+/// ```
+///
+/// is rewritten to
+///
+/// ```text
+/// Projection: host, date_bin(..., time), SUM(COUNT(value)) AS COUNT(value)
+///   Aggregate: groupBy=[[host, date_bin(..., time)]], aggr=[[SUM(COUNT(value))]]
+///     TableScan: t, grouping=[date_bin(..., time), host], agg=[COUNT(value)]
+/// ```
+pub struct PushDownTimeBucketAggregation {}
+
+impl OptimizerRule for PushDownTimeBucketAggregation {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        _optimizer_config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        if let LogicalPlan::Aggregate(Aggregate {
+            input,
+            group_expr,
+            aggr_expr,
+            ..
+        }) = plan
+        {
+            if let LogicalPlan::TableScan(scan) = input.as_ref() {
+                if group_expr.is_empty() || scan.agg_with_grouping.is_some() {
+                    return Ok(None);
+                }
+                // Only handle the table of ClusterTable
+                if let Some(cluster_table) = source_as_provider(&scan.source)?
+                    .as_any()
+                    .downcast_ref::<ClusterTable>()
+                {
+                    return push_down_aggregate(
+                        plan,
+                        cluster_table,
+                        input,
+                        scan,
+                        group_expr,
+                        aggr_expr,
+                    );
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn name(&self) -> &str {
+        "push_down_time_bucket_aggregation"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+}
+
+fn push_down_aggregate(
+    plan: &LogicalPlan,
+    cluster_table: &ClusterTable,
+    input: &Arc<LogicalPlan>,
+    scan: &TableScan,
+    group_expr: &[Expr],
+    aggr_expr: &[Expr],
+) -> Result<Option<LogicalPlan>> {
+    let scan_group_expr = match cluster_table.pushed_aggregate_grouping(group_expr, aggr_expr) {
+        Some((_, exprs)) => exprs,
+        None => return Ok(None),
+    };
+
+    // Output of the scan: start of the time buckets, tags and partial aggregates.
+    let scan_schema =
+        Aggregate::try_new(input.clone(), scan_group_expr.clone(), aggr_expr.to_vec())?.schema;
+    // Tskv returns start of the time buckets in type of the time column.
+    let time_field: Field = (&cluster_table.table_schema().time_column()).into();
+    if scan_schema.field(0).data_type() != time_field.data_type() {
+        return Ok(None);
+    }
+
+    let mut final_group_expr = Vec::with_capacity(group_expr.len());
+    for expr in group_expr {
+        match scan_group_expr.iter().position(|e| e == expr) {
+            Some(i) => final_group_expr.push(Expr::Column(scan_schema.field(i).qualified_column())),
+            None => return Ok(None),
+        }
+    }
+    let mut final_aggr_expr = Vec::with_capacity(aggr_expr.len());
+    for (i, expr) in aggr_expr.iter().enumerate() {
+        let fun = match expr {
+            Expr::AggregateFunction(AggregateFunction { fun, .. }) => match fun {
+                aggregate_function::AggregateFunction::Count => {
+                    aggregate_function::AggregateFunction::Sum
+                }
+                _ => fun.clone(),
+            },
+            _ => return Ok(None),
+        };
+        let partial = scan_schema
+            .field(scan_group_expr.len() + i)
+            .qualified_column();
+        final_aggr_expr.push(Expr::AggregateFunction(AggregateFunction::new(
+            fun,
+            vec![Expr::Column(partial)],
+            false,
+            None,
+            None,
+        )));
+    }
+
+    let agg_with_grouping = AggWithGrouping {
+        group_expr: scan_group_expr,
+        agg_expr: aggr_expr.to_vec(),
+        schema: scan_schema.clone(),
+    };
+    let new_scan = LogicalPlan::TableScan(TableScan {
+        table_name: scan.table_name.clone(),
+        source: scan.source.clone(),
+        projection: None,
+        projected_schema: scan_schema,
+        filters: scan.filters.clone(),
+        fetch: scan.fetch,
+        agg_with_grouping: Some(agg_with_grouping),
+    });
+    let final_aggregate = LogicalPlanBuilder::from(new_scan)
+        .aggregate(final_group_expr, final_aggr_expr)?
+        .build()?;
+
+    // Keep names of the output columns.
+    let projection = final_aggregate
+        .schema()
+        .fields()
+        .iter()
+        .zip(plan.schema().fields())
+        .enumerate()
+        .map(|(i, (final_field, field))| {
+            let column = Expr::Column(final_field.qualified_column());
+            if i < group_expr.len() {
+                column
+            } else {
+                column.alias(field.name())
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Some(
+        LogicalPlanBuilder::from(final_aggregate)
+            .project(projection)?
+            .build()?,
+    ))
+}
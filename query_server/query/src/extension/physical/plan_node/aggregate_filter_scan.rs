@@ -4,6 +4,7 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
+use coordinator::reader::CancellableCoordinatorRecordBatchStream;
 use coordinator::service::CoordinatorRef;
 use datafusion::arrow::datatypes::SchemaRef;
 use datafusion::error::{DataFusionError, Result};
@@ -13,9 +14,10 @@ use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
 use datafusion::physical_plan::{
     DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
 };
-use models::predicate::domain::{PredicateRef, PushedAggregateFunction};
+use models::predicate::domain::{PredicateRef, PushedAggregateFunction, PushedAggregateGrouping};
 use models::predicate::PlacedSplit;
 use models::schema::TskvTableSchemaRef;
+use tokio_util::sync::CancellationToken;
 use trace::{debug, SpanContext, SpanExt, SpanRecorder};
 use tskv::reader::QueryOption;
 
@@ -28,6 +30,9 @@ pub struct AggregateFilterTskvExec {
     schema: SchemaRef,
     table_schema: TskvTableSchemaRef,
     pushed_aggs: Vec<PushedAggregateFunction>,
+    /// Grouping of the pushed aggregates, outputs are partial aggregates
+    /// of each time bucket of each series.
+    grouping: Option<PushedAggregateGrouping>,
     filter: PredicateRef,
     splits: Vec<PlacedSplit>,
     metrics: ExecutionPlanMetricsSet,
@@ -39,6 +44,7 @@ impl AggregateFilterTskvExec {
         schema: SchemaRef,
         table_schema: TskvTableSchemaRef,
         pushed_aggs: Vec<PushedAggregateFunction>,
+        grouping: Option<PushedAggregateGrouping>,
         filter: PredicateRef,
         splits: Vec<PlacedSplit>,
     ) -> Self {
@@ -47,6 +53,7 @@ impl AggregateFilterTskvExec {
             schema,
             table_schema,
             pushed_aggs,
+            grouping,
             filter,
             splits,
             metrics: ExecutionPlanMetricsSet::new(),
@@ -87,17 +94,6 @@ impl ExecutionPlan for AggregateFilterTskvExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        let mut agg_columns = Vec::with_capacity(self.pushed_aggs.len());
-        for agg in self.pushed_aggs.iter() {
-            match agg {
-                PushedAggregateFunction::Count(column) => {
-                    if let Some(col) = self.table_schema.column(column) {
-                        agg_columns.push(col.clone());
-                    }
-                }
-            }
-        }
-
         let split = unsafe {
             debug_assert!(partition < self.splits.len(), "Partition not exists");
             self.splits.get_unchecked(partition).clone()
@@ -108,12 +104,16 @@ impl ExecutionPlan for AggregateFilterTskvExec {
         let query_opt = QueryOption::new(
             100_usize,
             split,
-            Some(agg_columns),
+            Some(self.pushed_aggs.clone()),
             self.schema.clone(),
             self.table_schema.clone(),
-        );
+        )
+        .with_aggregate_grouping(self.grouping.clone());
 
         let span_ctx = context.session_config().get_extension::<SpanContext>();
+        let cancellation_token = context
+            .session_config()
+            .get_extension::<CancellationToken>();
         let span_recorder =
             SpanRecorder::new(span_ctx.child_span("TableScanStream of AggregateFilterTskvExec"));
 
@@ -121,6 +121,8 @@ impl ExecutionPlan for AggregateFilterTskvExec {
             .coord
             .table_scan(query_opt, span_recorder.span_ctx())
             .map_err(|e| DataFusionError::Internal(e.to_string()))?;
+        let iterator =
+            CancellableCoordinatorRecordBatchStream::wrap(iterator, cancellation_token.as_deref());
         let table_stream = TableScanStream::with_iterator(
             self.schema.clone(),
             100_usize,
@@ -143,11 +145,11 @@ impl ExecutionPlan for AggregateFilterTskvExec {
     }
 
     fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "AggregateFilterTskvExec: agg=[{:?}], filter=[{:?}]",
-            self.pushed_aggs, self.filter
-        )
+        write!(f, "AggregateFilterTskvExec: agg=[{:?}], ", self.pushed_aggs)?;
+        if let Some(grouping) = &self.grouping {
+            write!(f, "grouping=[{:?}], ", grouping)?;
+        }
+        write!(f, "filter=[{:?}]", self.filter)
     }
 }
 
@@ -157,6 +159,7 @@ impl Debug for AggregateFilterTskvExec {
             .field("schema", &self.schema)
             .field("table_schema", &self.table_schema)
             .field("pushed_aggs", &self.pushed_aggs)
+            .field("grouping", &self.grouping)
             .field("filter", &self.filter)
             .field("splits", &self.splits)
             .finish()
@@ -4,6 +4,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use coordinator::reader::CancellableCoordinatorRecordBatchStream;
 use coordinator::service::CoordinatorRef;
 use coordinator::SendableCoordinatorRecordBatchStream;
 use datafusion::arrow::array::ArrayRef;
@@ -22,6 +23,7 @@ use models::predicate::domain::PredicateRef;
 use models::predicate::PlacedSplit;
 use models::schema::{TskvTableSchema, TskvTableSchemaRef};
 use spi::QueryError;
+use tokio_util::sync::CancellationToken;
 use trace::{debug, SpanContext, SpanExt, SpanRecorder};
 use tskv::reader::QueryOption;
 
@@ -123,6 +125,9 @@ impl ExecutionPlan for TagScanExec {
 
         let metrics = TableScanMetrics::new(&self.metrics, partition);
         let span_ctx = context.session_config().get_extension::<SpanContext>();
+        let cancellation_token = context
+            .session_config()
+            .get_extension::<CancellationToken>();
 
         let tag_scan_stream = TagScanStream::new(
             self.table_schema.clone(),
@@ -132,6 +137,7 @@ impl ExecutionPlan for TagScanExec {
             batch_size,
             metrics,
             SpanRecorder::new(span_ctx.child_span(format!("TagScanStream ({partition})"))),
+            cancellation_token.as_deref(),
         )
         .map_err(|err| DataFusionError::External(Box::new(err)))?;
 
@@ -234,6 +240,7 @@ impl TagScanStream {
         batch_size: usize,
         metrics: TableScanMetrics,
         span_recorder: SpanRecorder,
+        cancellation_token: Option<&CancellationToken>,
     ) -> Result<Self, QueryError> {
         let mut proj_fileds = Vec::with_capacity(proj_schema.fields().len());
         for field_name in proj_schema.fields().iter().map(|f| f.name()) {
@@ -264,7 +271,10 @@ impl TagScanStream {
         );
 
         let span_ctx = span_recorder.span_ctx();
-        let stream = coord.tag_scan(option, span_ctx)?;
+        let stream = CancellableCoordinatorRecordBatchStream::wrap(
+            coord.tag_scan(option, span_ctx)?,
+            cancellation_token,
+        );
 
         Ok(Self {
             proj_schema,
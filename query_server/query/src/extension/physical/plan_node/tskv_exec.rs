@@ -3,6 +3,7 @@ use std::fmt::{self, Display, Formatter};
 use std::sync::Arc;
 use std::task::Poll;
 
+use coordinator::reader::CancellableCoordinatorRecordBatchStream;
 use coordinator::service::CoordinatorRef;
 use coordinator::SendableCoordinatorRecordBatchStream;
 use datafusion::arrow::datatypes::{SchemaRef, TimeUnit};
@@ -21,6 +22,7 @@ use models::predicate::domain::PredicateRef;
 use models::predicate::PlacedSplit;
 use models::schema::{ColumnType, TableColumn, TskvTableSchema, TskvTableSchemaRef, TIME_FIELD};
 use spi::{QueryError, Result};
+use tokio_util::sync::CancellationToken;
 use trace::{debug, SpanContext, SpanExt, SpanRecorder};
 use tskv::reader::QueryOption;
 
@@ -123,6 +125,9 @@ impl ExecutionPlan for TskvExec {
         let metrics = TableScanMetrics::new(&self.metrics, partition);
 
         let span_ctx = context.session_config().get_extension::<SpanContext>();
+        let cancellation_token = context
+            .session_config()
+            .get_extension::<CancellationToken>();
 
         let table_stream = TableScanStream::new(
             self.table_schema.clone(),
@@ -132,6 +137,7 @@ impl ExecutionPlan for TskvExec {
             batch_size,
             metrics,
             SpanRecorder::new(span_ctx.child_span(format!("TableScanStream ({partition})"))),
+            cancellation_token.as_deref(),
         )
         .map_err(|err| DataFusionError::External(Box::new(err)))?;
 
@@ -218,6 +224,7 @@ impl TableScanStream {
         batch_size: usize,
         metrics: TableScanMetrics,
         span_recorder: SpanRecorder,
+        cancellation_token: Option<&CancellationToken>,
     ) -> Result<Self> {
         let mut proj_fileds = Vec::with_capacity(proj_schema.fields().len());
         for item in proj_schema.fields().iter() {
@@ -266,7 +273,10 @@ impl TableScanStream {
         );
 
         let span_ctx = span_recorder.span_ctx();
-        let iterator = coord.table_scan(option, span_ctx)?;
+        let iterator = CancellableCoordinatorRecordBatchStream::wrap(
+            coord.table_scan(option, span_ctx)?,
+            cancellation_token,
+        );
 
         Ok(Self {
             proj_schema,
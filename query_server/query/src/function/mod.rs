@@ -1 +1,2 @@
 pub mod simple_func_manager;
+pub mod sql_function;
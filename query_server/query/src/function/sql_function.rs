@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datafusion::common::{DFSchema, Result as DFResult};
+use datafusion::config::ConfigOptions;
+use datafusion::error::DataFusionError;
+use datafusion::logical_expr::expr_rewriter::rewrite_preserving_name;
+use datafusion::logical_expr::{
+    cast, create_udf, AggregateUDF, ColumnarValue, ScalarUDF, TableSource, Volatility, WindowUDF,
+};
+use datafusion::optimizer::analyzer::type_coercion::TypeCoercionRewriter;
+use datafusion::physical_expr::create_physical_expr;
+use datafusion::physical_expr::execution_props::ExecutionProps;
+use datafusion::physical_plan::PhysicalExpr;
+use datafusion::scalar::ScalarValue;
+use datafusion::sql::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion::sql::sqlparser::dialect::GenericDialect;
+use datafusion::sql::sqlparser::parser::Parser;
+use datafusion::sql::TableReference;
+use models::schema::SqlFunction;
+use spi::query::function::FunctionMetadataManager;
+
+/// Compile the sql function into a scalar udf.
+///
+/// The body is planned once against the arguments of the function,
+/// the udf evaluates it on a batch made of the actual arguments.
+pub fn create_sql_udf<P: ContextProvider>(
+    function: &SqlFunction,
+    provider: &P,
+) -> DFResult<ScalarUDF> {
+    let schema: SchemaRef = Arc::new(Schema::new(
+        function
+            .args
+            .iter()
+            .map(|(name, data_type)| Field::new(name, data_type.clone(), true))
+            .collect::<Vec<_>>(),
+    ));
+    let df_schema = DFSchema::try_from(schema.as_ref().clone())?;
+
+    let body = Parser::new(&GenericDialect {})
+        .try_with_sql(&function.body)?
+        .parse_expr()?;
+    let expr = SqlToRel::new(provider).sql_to_expr(body, &df_schema, &mut PlannerContext::new())?;
+    let expr = cast(expr, function.return_type.clone());
+    let mut rewriter = TypeCoercionRewriter::new(Arc::new(df_schema.clone()));
+    let expr = rewrite_preserving_name(expr, &mut rewriter)?;
+    let physical_expr = create_physical_expr(&expr, &df_schema, &schema, &ExecutionProps::new())?;
+
+    let input_types = function.args.iter().map(|(_, t)| t.clone()).collect();
+    let fun = Arc::new(move |args: &[ColumnarValue]| {
+        evaluate_sql_function(&schema, physical_expr.as_ref(), args)
+    });
+
+    Ok(create_udf(
+        &function.name,
+        input_types,
+        Arc::new(function.return_type.clone()),
+        Volatility::Immutable,
+        fun,
+    ))
+}
+
+fn evaluate_sql_function(
+    schema: &SchemaRef,
+    body: &dyn PhysicalExpr,
+    args: &[ColumnarValue],
+) -> DFResult<ColumnarValue> {
+    let num_rows = args.iter().find_map(|arg| match arg {
+        ColumnarValue::Array(array) => Some(array.len()),
+        ColumnarValue::Scalar(_) => None,
+    });
+
+    let columns = args
+        .iter()
+        .map(|arg| arg.clone().into_array(num_rows.unwrap_or(1)))
+        .collect::<Vec<_>>();
+    let options = RecordBatchOptions::new().with_row_count(Some(num_rows.unwrap_or(1)));
+    let batch = RecordBatch::try_new_with_options(schema.clone(), columns, &options)?;
+
+    match (num_rows, body.evaluate(&batch)?) {
+        // all arguments are scalars, so is the result
+        (None, ColumnarValue::Array(array)) => Ok(ColumnarValue::Scalar(
+            ScalarValue::try_from_array(&array, 0)?,
+        )),
+        (_, result) => Ok(result),
+    }
+}
+
+/// Resolves the functions referenced by the body of a sql function,
+/// tables are not available in the body.
+pub struct FunctionContextProvider<'a> {
+    func_manager: &'a dyn FunctionMetadataManager,
+    options: ConfigOptions,
+}
+
+impl<'a> FunctionContextProvider<'a> {
+    pub fn new(func_manager: &'a dyn FunctionMetadataManager) -> Self {
+        Self {
+            func_manager,
+            options: ConfigOptions::default(),
+        }
+    }
+}
+
+impl ContextProvider for FunctionContextProvider<'_> {
+    fn get_table_provider(&self, name: TableReference) -> DFResult<Arc<dyn TableSource>> {
+        Err(DataFusionError::Plan(format!(
+            "Table {} can't be referenced in a function",
+            name
+        )))
+    }
+
+    fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.func_manager.udf(name).ok()
+    }
+
+    fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        self.func_manager.udaf(name).ok()
+    }
+
+    fn get_variable_type(&self, _variable_names: &[String]) -> Option<DataType> {
+        None
+    }
+
+    fn options(&self) -> &ConfigOptions {
+        &self.options
+    }
+
+    fn get_window_meta(&self, _name: &str) -> Option<Arc<WindowUDF>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::{Array, Float64Array};
+
+    use super::*;
+    use crate::function::simple_func_manager::SimpleFunctionMetadataManager;
+
+    fn c_to_f() -> SqlFunction {
+        SqlFunction {
+            tenant: "cnosdb".to_string(),
+            name: "c_to_f".to_string(),
+            args: vec![("c".to_string(), DataType::Float64)],
+            return_type: DataType::Float64,
+            body: "c * 1.8 + 32".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_sql_udf() {
+        let func_manager = SimpleFunctionMetadataManager::default();
+        let provider = FunctionContextProvider::new(&func_manager);
+        let udf = create_sql_udf(&c_to_f(), &provider).unwrap();
+
+        let args = vec![ColumnarValue::Array(Arc::new(Float64Array::from(vec![
+            Some(0.0),
+            None,
+            Some(100.0),
+        ])))];
+        let result = (udf.fun)(&args).unwrap().into_array(3);
+        let result = result.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(result.value(0), 32.0);
+        assert!(result.is_null(1));
+        assert_eq!(result.value(2), 212.0);
+
+        let args = vec![ColumnarValue::Scalar(ScalarValue::Float64(Some(10.0)))];
+        match (udf.fun)(&args).unwrap() {
+            ColumnarValue::Scalar(v) => assert_eq!(v, ScalarValue::Float64(Some(50.0))),
+            _ => panic!("expect scalar"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_sql_udf() {
+        let func_manager = SimpleFunctionMetadataManager::default();
+        let provider = FunctionContextProvider::new(&func_manager);
+
+        let mut function = c_to_f();
+        function.body = "unknown_column + 1".to_string();
+        assert!(create_sql_udf(&function, &provider).is_err());
+
+        function.body = "(select 1)".to_string();
+        assert!(create_sql_udf(&function, &provider).is_err());
+    }
+}
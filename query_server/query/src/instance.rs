@@ -35,6 +35,7 @@ use crate::extension::expr::load_all_functions;
 use crate::extension::variable::load_all_system_vars;
 use crate::function::simple_func_manager::SimpleFunctionMetadataManager;
 use crate::metadata::BaseTableProvider;
+use crate::sql::influxql::InfluxQLParser;
 use crate::sql::optimizer::CascadeOptimizerBuilder;
 use crate::sql::parser::DefaultParser;
 use crate::variable::simple_sys_var_manager::SimpleSystemVarManager;
@@ -110,6 +111,16 @@ where
         Ok(logical_plan)
     }
 
+    async fn build_substrait_logical_plan(
+        &self,
+        query_state_machine: QueryStateMachineRef,
+        substrait_plan: &[u8],
+    ) -> Result<Plan> {
+        self.query_dispatcher
+            .build_substrait_logical_plan(query_state_machine, substrait_plan)
+            .await
+    }
+
     async fn execute_logical_plan(
         &self,
         logical_plan: Plan,
@@ -181,6 +192,7 @@ pub async fn make_cnosdbms(
         query_dedicated_hidden_dir.clone(),
     ));
     let parser = Arc::new(DefaultParser::default());
+    let influxql_parser = Arc::new(InfluxQLParser::default());
     let optimizer = Arc::new(CascadeOptimizerBuilder::default().build());
     // TODO wrap, and num_threads configurable
     let scheduler = Arc::new(LocalScheduler {});
@@ -245,6 +257,7 @@ pub async fn make_cnosdbms(
         .with_session_factory(session_factory)
         .with_memory_pool(memory_pool)
         .with_parser(parser)
+        .with_influxql_parser(influxql_parser)
         .with_query_execution_factory(query_execution_factory)
         .with_query_tracker(query_tracker)
         .with_func_manager(Arc::new(func_manager))
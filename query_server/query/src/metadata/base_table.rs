@@ -64,6 +64,13 @@ impl TableHandleProvider for BaseTableProvider {
                     .create_provider(self.meta_client.clone(), table.as_ref())
                     .map_err(|e| DataFusionError::External(Box::new(e)))?
                     .into(),
+                TableSchema::ViewTableSchema(view) => {
+                    // views are planned by the MetadataProvider, see `MetadataProvider::build_view_table_handle`
+                    return Err(DataFusionError::Plan(format!(
+                        "View {}.{} can not be used as a base table",
+                        view.db, view.name,
+                    )));
+                }
             },
             None => {
                 return Err(DataFusionError::Plan(format!(
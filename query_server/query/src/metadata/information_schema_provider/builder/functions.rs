@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use datafusion::arrow::array::StringBuilder;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::DataFusionError;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    pub static ref FUNCTION_SCHEMA: SchemaRef = Arc::new(Schema::new(vec![
+        Field::new("tenant_name", DataType::Utf8, false),
+        Field::new("function_name", DataType::Utf8, false),
+        Field::new("arguments", DataType::Utf8, false),
+        Field::new("return_type", DataType::Utf8, false),
+        Field::new("body", DataType::Utf8, false),
+    ]));
+}
+
+/// Builds the `information_schema.FUNCTIONS` table row by row
+#[derive(Default)]
+pub struct InformationSchemaFunctionsBuilder {
+    tenant_names: StringBuilder,
+    function_names: StringBuilder,
+    arguments: StringBuilder,
+    return_types: StringBuilder,
+    bodies: StringBuilder,
+}
+
+impl InformationSchemaFunctionsBuilder {
+    pub fn append_row(
+        &mut self,
+        tenant_name: impl AsRef<str>,
+        function_name: impl AsRef<str>,
+        arguments: impl AsRef<str>,
+        return_type: impl AsRef<str>,
+        body: impl AsRef<str>,
+    ) {
+        // Note: append_value is actually infallable.
+        self.tenant_names.append_value(tenant_name.as_ref());
+        self.function_names.append_value(function_name.as_ref());
+        self.arguments.append_value(arguments.as_ref());
+        self.return_types.append_value(return_type.as_ref());
+        self.bodies.append_value(body.as_ref());
+    }
+}
+
+impl TryFrom<InformationSchemaFunctionsBuilder> for RecordBatch {
+    type Error = DataFusionError;
+
+    fn try_from(value: InformationSchemaFunctionsBuilder) -> Result<Self, Self::Error> {
+        let InformationSchemaFunctionsBuilder {
+            mut tenant_names,
+            mut function_names,
+            mut arguments,
+            mut return_types,
+            mut bodies,
+        } = value;
+
+        let batch = RecordBatch::try_new(
+            FUNCTION_SCHEMA.clone(),
+            vec![
+                Arc::new(tenant_names.finish()),
+                Arc::new(function_names.finish()),
+                Arc::new(arguments.finish()),
+                Arc::new(return_types.finish()),
+                Arc::new(bodies.finish()),
+            ],
+        )?;
+
+        Ok(batch)
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use datafusion::arrow::array::{StringBuilder, TimestampNanosecondBuilder, UInt64Builder};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::DataFusionError;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    pub static ref ROLLUP_POLICY_SCHEMA: SchemaRef = Arc::new(Schema::new(vec![
+        Field::new("tenant_name", DataType::Utf8, false),
+        Field::new("database_name", DataType::Utf8, false),
+        Field::new("policy_name", DataType::Utf8, false),
+        Field::new("source_table", DataType::Utf8, false),
+        Field::new("target_table", DataType::Utf8, false),
+        Field::new("interval", DataType::Utf8, false),
+        Field::new("aggregates", DataType::Utf8, false),
+        Field::new("ttl", DataType::Utf8, true),
+        Field::new(
+            "watermark",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true
+        ),
+        Field::new(
+            "last_run_time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true
+        ),
+        Field::new("run_count", DataType::UInt64, false),
+        Field::new("error_count", DataType::UInt64, false),
+        Field::new("last_error", DataType::Utf8, false),
+    ]));
+}
+
+/// Builds the `information_schema.ROLLUP_POLICIES` table row by row
+#[derive(Default)]
+pub struct InformationSchemaRollupPoliciesBuilder {
+    tenant_names: StringBuilder,
+    database_names: StringBuilder,
+    policy_names: StringBuilder,
+    source_tables: StringBuilder,
+    target_tables: StringBuilder,
+    intervals: StringBuilder,
+    aggregates: StringBuilder,
+    ttls: StringBuilder,
+    watermarks: TimestampNanosecondBuilder,
+    last_run_times: TimestampNanosecondBuilder,
+    run_counts: UInt64Builder,
+    error_counts: UInt64Builder,
+    last_errors: StringBuilder,
+}
+
+impl InformationSchemaRollupPoliciesBuilder {
+    #[allow(clippy::too_many_arguments)]
+    pub fn append_row(
+        &mut self,
+        tenant_name: impl AsRef<str>,
+        database_name: impl AsRef<str>,
+        policy_name: impl AsRef<str>,
+        source_table: impl AsRef<str>,
+        target_table: impl AsRef<str>,
+        interval: impl AsRef<str>,
+        aggregates: impl AsRef<str>,
+        ttl: Option<impl AsRef<str>>,
+        watermark: Option<i64>,
+        last_run_time: Option<i64>,
+        run_count: u64,
+        error_count: u64,
+        last_error: impl AsRef<str>,
+    ) {
+        // Note: append_value is actually infallable.
+        self.tenant_names.append_value(tenant_name.as_ref());
+        self.database_names.append_value(database_name.as_ref());
+        self.policy_names.append_value(policy_name.as_ref());
+        self.source_tables.append_value(source_table.as_ref());
+        self.target_tables.append_value(target_table.as_ref());
+        self.intervals.append_value(interval.as_ref());
+        self.aggregates.append_value(aggregates.as_ref());
+        self.ttls.append_option(ttl);
+        self.watermarks.append_option(watermark);
+        self.last_run_times.append_option(last_run_time);
+        self.run_counts.append_value(run_count);
+        self.error_counts.append_value(error_count);
+        self.last_errors.append_value(last_error.as_ref());
+    }
+}
+
+impl TryFrom<InformationSchemaRollupPoliciesBuilder> for RecordBatch {
+    type Error = DataFusionError;
+
+    fn try_from(value: InformationSchemaRollupPoliciesBuilder) -> Result<Self, Self::Error> {
+        let InformationSchemaRollupPoliciesBuilder {
+            mut tenant_names,
+            mut database_names,
+            mut policy_names,
+            mut source_tables,
+            mut target_tables,
+            mut intervals,
+            mut aggregates,
+            mut ttls,
+            mut watermarks,
+            mut last_run_times,
+            mut run_counts,
+            mut error_counts,
+            mut last_errors,
+        } = value;
+
+        let batch = RecordBatch::try_new(
+            ROLLUP_POLICY_SCHEMA.clone(),
+            vec![
+                Arc::new(tenant_names.finish()),
+                Arc::new(database_names.finish()),
+                Arc::new(policy_names.finish()),
+                Arc::new(source_tables.finish()),
+                Arc::new(target_tables.finish()),
+                Arc::new(intervals.finish()),
+                Arc::new(aggregates.finish()),
+                Arc::new(ttls.finish()),
+                Arc::new(watermarks.finish()),
+                Arc::new(last_run_times.finish()),
+                Arc::new(run_counts.finish()),
+                Arc::new(error_counts.finish()),
+                Arc::new(last_errors.finish()),
+            ],
+        )?;
+
+        Ok(batch)
+    }
+}
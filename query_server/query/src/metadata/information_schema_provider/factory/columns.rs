@@ -15,7 +15,7 @@ use meta::model::MetaClientRef;
 use models::auth::user::User;
 use models::oid::Identifier;
 use models::schema::{
-    ColumnType, ExternalTableSchema, StreamTable, TableSchema, TskvTableSchemaRef,
+    ColumnType, ExternalTableSchema, StreamTable, TableSchema, TskvTableSchemaRef, ViewTableSchema,
 };
 use models::ValueType;
 
@@ -113,6 +113,9 @@ impl TableProvider for InformationColumnsTable {
                         TableSchema::StreamTableSchema(t) => {
                             append_stream_table(tenant_name, &db, t.clone(), &mut builder);
                         }
+                        TableSchema::ViewTableSchema(t) => {
+                            append_view_table(tenant_name, &db, t.clone(), &mut builder);
+                        }
                     }
                 }
             }
@@ -194,3 +197,26 @@ fn append_stream_table(
         );
     }
 }
+
+fn append_view_table(
+    tenant_name: &str,
+    database_name: &str,
+    table: Arc<ViewTableSchema>,
+    builder: &mut InformationSchemaColumnsBuilder,
+) {
+    for (idx, col) in table.schema.all_fields().iter().enumerate() {
+        builder.append_row(
+            tenant_name,
+            database_name,
+            &table.name,
+            col.name(),
+            // The columns of the view are all type FIELD
+            ColumnType::Field(ValueType::Unknown).as_column_type_str(),
+            idx as u64,
+            "NULL",
+            col.is_nullable(),
+            col.data_type().to_string(),
+            None::<String>,
+        );
+    }
+}
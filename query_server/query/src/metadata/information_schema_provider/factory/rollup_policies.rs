@@ -0,0 +1,121 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::{DataFusionError, Result as DFResult};
+use datafusion::datasource::{TableProvider, TableType};
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::logical_plan::AggWithGrouping;
+use datafusion::physical_plan::memory::MemoryExec;
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::prelude::Expr;
+use meta::model::MetaClientRef;
+use models::auth::user::User;
+use models::oid::Identifier;
+
+use crate::dispatcher::query_tracker::QueryTracker;
+use crate::metadata::information_schema_provider::builder::rollup_policies::{
+    InformationSchemaRollupPoliciesBuilder, ROLLUP_POLICY_SCHEMA,
+};
+use crate::metadata::information_schema_provider::InformationSchemaTableFactory;
+
+pub const INFORMATION_SCHEMA_ROLLUP_POLICIES: &str = "ROLLUP_POLICIES";
+
+/// This view only displays rollup policies of databases for which the current user has Read permission or higher.
+pub struct RollupPoliciesFactory {}
+
+impl InformationSchemaTableFactory for RollupPoliciesFactory {
+    fn table_name(&self) -> &'static str {
+        INFORMATION_SCHEMA_ROLLUP_POLICIES
+    }
+
+    fn create(
+        &self,
+        user: &User,
+        metadata: MetaClientRef,
+        _query_tracker: Arc<QueryTracker>,
+    ) -> Arc<dyn TableProvider> {
+        Arc::new(InformationRollupPoliciesTable::new(metadata, user.clone()))
+    }
+}
+
+pub struct InformationRollupPoliciesTable {
+    user: User,
+    metadata: MetaClientRef,
+}
+
+impl InformationRollupPoliciesTable {
+    pub fn new(metadata: MetaClientRef, user: User) -> Self {
+        Self { user, metadata }
+    }
+}
+
+#[async_trait]
+impl TableProvider for InformationRollupPoliciesTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        ROLLUP_POLICY_SCHEMA.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _agg_with_grouping: Option<&AggWithGrouping>,
+        _limit: Option<usize>,
+    ) -> DFResult<Arc<dyn ExecutionPlan>> {
+        let mut builder = InformationSchemaRollupPoliciesBuilder::default();
+
+        let policies = self.metadata.rollup_policies().await.map_err(|e| {
+            DataFusionError::Internal(format!("Failed to list rollup policies: {}", e))
+        })?;
+        let tenant_id = self.metadata.tenant().id();
+
+        for policy in policies {
+            // Check if the current user has at least read permission on this db, skip if not
+            if !self.user.can_read_database(*tenant_id, &policy.database) {
+                continue;
+            }
+
+            let aggregates = policy
+                .aggregates
+                .iter()
+                .map(|e| format!("{}({}) AS {}", e.function, e.field, e.target))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let progress = &policy.progress;
+            builder.append_row(
+                &policy.tenant,
+                &policy.database,
+                &policy.name,
+                &policy.source_table,
+                &policy.target_table,
+                policy.interval.to_string(),
+                aggregates,
+                policy.ttl.as_ref().map(|e| e.to_string()),
+                progress.watermark,
+                progress.last_run_time,
+                progress.run_count,
+                progress.error_count,
+                &progress.last_error,
+            );
+        }
+        let rb: RecordBatch = builder.try_into()?;
+
+        Ok(Arc::new(MemoryExec::try_new(
+            &[vec![rb]],
+            self.schema(),
+            projection.cloned(),
+        )?))
+    }
+}
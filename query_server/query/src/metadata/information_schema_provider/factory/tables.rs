@@ -14,6 +14,7 @@ use datafusion::prelude::Expr;
 use meta::model::MetaClientRef;
 use models::auth::user::User;
 use models::oid::Identifier;
+use models::schema::TableSchema;
 
 use crate::dispatcher::query_tracker::QueryTracker;
 use crate::metadata::information_schema_provider::builder::tables;
@@ -98,11 +99,15 @@ impl TableProvider for InformationTable {
                 if let Some(table) = self.metadata.get_table_schema(&db, &table).map_err(|e| {
                     DataFusionError::Internal(format!("failed to get table schema {}", e))
                 })? {
+                    let table_type = match table {
+                        TableSchema::ViewTableSchema(_) => TableType::View,
+                        _ => TableType::Base,
+                    };
                     builder.append_row(
                         tenant_name,
                         &db,
                         table.name(),
-                        TableType::Base,
+                        table_type,
                         table.engine_name(),
                         "TODO",
                     );
@@ -27,10 +27,12 @@ use self::factory::columns::ColumnsFactory;
 use self::factory::database_privileges::DatabasePrivilegesFactory;
 use self::factory::databases::DatabasesFactory;
 use self::factory::enabled_roles::EnabledRolesFactory;
+use self::factory::functions::FunctionsFactory;
 use self::factory::members::MembersFactory;
 use self::factory::queries::QueriesFactory;
 use self::factory::resource_status::InformationSchemaResourceStatusFactory;
 use self::factory::roles::RolesFactory;
+use self::factory::rollup_policies::RollupPoliciesFactory;
 use super::INFORMATION_SCHEMA;
 use crate::dispatcher::query_tracker::QueryTracker;
 use crate::metadata::information_schema_provider::factory::tables::TablesFactory;
@@ -60,6 +62,8 @@ impl InformationSchemaProvider {
         provider.register_table_factory(Box::new(MembersFactory {}));
         provider.register_table_factory(Box::new(QueriesFactory {}));
         provider.register_table_factory(Box::new(InformationSchemaResourceStatusFactory {}));
+        provider.register_table_factory(Box::new(RollupPoliciesFactory {}));
+        provider.register_table_factory(Box::new(FunctionsFactory {}));
 
         provider
     }
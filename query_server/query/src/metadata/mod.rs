@@ -6,11 +6,13 @@ use coordinator::service::CoordinatorRef;
 use datafusion::arrow::datatypes::DataType;
 use datafusion::common::Result as DFResult;
 use datafusion::config::ConfigOptions;
-use datafusion::datasource::TableProvider;
+use datafusion::datasource::{TableProvider, ViewTable};
 use datafusion::error::DataFusionError;
-use datafusion::logical_expr::{AggregateUDF, ScalarUDF, TableSource, WindowUDF};
+use datafusion::logical_expr::{AggregateUDF, LogicalPlan, ScalarUDF, TableSource, WindowUDF};
 use datafusion::physical_expr::var_provider::is_system_variables;
-use datafusion::sql::planner::ContextProvider;
+use datafusion::sql::planner::{ContextProvider, SqlToRel};
+use datafusion::sql::sqlparser::dialect::GenericDialect;
+use datafusion::sql::sqlparser::parser::Parser;
 use datafusion::sql::TableReference;
 use datafusion::variable::{VarProvider, VarType};
 pub use information_schema_provider::{
@@ -26,7 +28,9 @@ use meta::error::MetaError;
 use meta::model::MetaClientRef;
 use models::auth::user::UserDesc;
 use models::object_reference::{Resolve, ResolvedTable};
-use models::schema::{Precision, Tenant, DEFAULT_CATALOG, DEFAULT_DATABASE};
+use models::schema::{
+    Precision, TableSchema, Tenant, ViewTableSchema, DEFAULT_CATALOG, DEFAULT_DATABASE,
+};
 use parking_lot::RwLock;
 use spi::query::function::FuncMetaManagerRef;
 use spi::query::session::SessionCtx;
@@ -57,6 +61,8 @@ pub trait ContextProviderExtension: ContextProvider {
     /// Clear the access record and return the content before clearing
     fn reset_access_databases(&self) -> DatabaseSet;
     fn get_db_precision(&self, name: &str) -> Result<Precision, MetaError>;
+    /// Functions of the session, including the sql functions of the tenant.
+    fn func_manager(&self) -> FuncMetaManagerRef;
     fn get_table_source(
         &self,
         name: TableReference,
@@ -87,6 +93,8 @@ pub struct MetadataProvider {
     cluster_schema_provider: ClusterSchemaProvider,
     usage_schema_provider: UsageSchemaProvider,
     access_databases: RwLock<DatabaseSet>,
+    // views that are being planned, used to detect recursive view definitions
+    resolving_views: RwLock<HashSet<String>>,
     // tskv/external
     current_session_table_provider: TableHandleProviderRef,
 }
@@ -114,6 +122,7 @@ impl MetadataProvider {
             cluster_schema_provider: ClusterSchemaProvider::new(),
             usage_schema_provider: UsageSchemaProvider::new(default_table_provider),
             access_databases: Default::default(),
+            resolving_views: Default::default(),
         }
     }
 
@@ -176,9 +185,98 @@ impl MetadataProvider {
             return Ok(source.into());
         }
 
+        if let Some(TableSchema::ViewTableSchema(view)) = self
+            .meta_client
+            .get_table_schema(database_name, table_name)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+        {
+            return self.build_view_table_handle(&view);
+        }
+
         self.current_session_table_provider
             .build_table_handle(database_name, table_name)
     }
+
+    /// Plan the query of the view with the privileges of the current user,
+    /// the tables referenced by the view are recorded in `access_databases`.
+    fn build_view_table_handle(
+        &self,
+        view: &ViewTableSchema,
+    ) -> datafusion::common::Result<TableHandle> {
+        let view_name = format!("{}.{}", view.db, view.name);
+        if !self.resolving_views.write().insert(view_name.clone()) {
+            return Err(DataFusionError::Plan(format!(
+                "Recursive definition of view {view_name}"
+            )));
+        }
+        let plan = self.plan_view_query(view);
+        self.resolving_views.write().remove(&view_name);
+
+        let view_table: Arc<dyn TableProvider> =
+            Arc::new(ViewTable::try_new(plan?, Some(view.query.clone()))?);
+        Ok(view_table.into())
+    }
+
+    fn plan_view_query(&self, view: &ViewTableSchema) -> datafusion::common::Result<LogicalPlan> {
+        let mut statements = Parser::parse_sql(&GenericDialect {}, &view.query)?;
+        if statements.len() != 1 {
+            return Err(DataFusionError::Plan(format!(
+                "The query of view {}.{} must be exactly one statement",
+                view.db, view.name
+            )));
+        }
+
+        let provider = ViewContextProvider::new(self, &view.db);
+        SqlToRel::new(&provider).sql_statement_to_plan(statements.remove(0))
+    }
+}
+
+/// Resolves the tables referenced by a view against the database of the view
+/// instead of the default database of the session.
+pub(crate) struct ViewContextProvider<'a, P> {
+    inner: &'a P,
+    database: &'a str,
+}
+
+impl<'a, P: ContextProvider> ViewContextProvider<'a, P> {
+    pub(crate) fn new(inner: &'a P, database: &'a str) -> Self {
+        Self { inner, database }
+    }
+}
+
+impl<P: ContextProvider> ContextProvider for ViewContextProvider<'_, P> {
+    fn get_table_provider(
+        &self,
+        name: TableReference,
+    ) -> datafusion::error::Result<Arc<dyn TableSource>> {
+        let name = match name {
+            TableReference::Bare { table } => {
+                TableReference::partial(self.database.to_string(), table.into_owned())
+            }
+            other => other.to_owned_reference(),
+        };
+        self.inner.get_table_provider(name)
+    }
+
+    fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.inner.get_function_meta(name)
+    }
+
+    fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        self.inner.get_aggregate_meta(name)
+    }
+
+    fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType> {
+        self.inner.get_variable_type(variable_names)
+    }
+
+    fn options(&self) -> &ConfigOptions {
+        self.inner.options()
+    }
+
+    fn get_window_meta(&self, name: &str) -> Option<Arc<WindowUDF>> {
+        self.inner.get_window_meta(name)
+    }
 }
 
 #[async_trait::async_trait]
@@ -222,6 +320,10 @@ impl ContextProviderExtension for MetadataProvider {
         Ok(precision)
     }
 
+    fn func_manager(&self) -> FuncMetaManagerRef {
+        self.func_manager.clone()
+    }
+
     fn get_table_source(
         &self,
         table_ref: TableReference,
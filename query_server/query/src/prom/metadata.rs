@@ -0,0 +1,241 @@
+//! Metadata apis of prometheus: series, label names and label values.
+//!
+//! Only the tag columns of the metric tables are selected, so the queries are rewritten to
+//! tag scans, which read the series index of the vnodes in the time range instead of the data.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use datafusion::arrow::array::Array;
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::cast::as_string_array;
+use spi::server::dbms::DBMSRef;
+use spi::server::prom::PromSeriesRequest;
+use spi::service::protocol::{Context, Query};
+use spi::{QueryError, Result};
+use trace::{debug, SpanRecorder};
+
+use super::promql::parser::{self, Expr, MatchOp, Matcher};
+use super::promql::planner::{MetricTable, TableResolver};
+use super::METRIC_NAME_LABEL;
+
+pub type Labels = BTreeMap<String, String>;
+
+/// Series matched by any of the selectors.
+pub async fn series(
+    db: &DBMSRef,
+    resolver: &dyn TableResolver,
+    ctx: &Context,
+    req: &PromSeriesRequest,
+    span_recorder: SpanRecorder,
+) -> Result<Vec<Labels>> {
+    if req.matchers.is_empty() {
+        return Err(invalid("no match[] parameter provided"));
+    }
+    let series = select_series(db, resolver, ctx, req, None, span_recorder).await?;
+    Ok(series.into_iter().collect())
+}
+
+/// Names of the labels of the selected series, sorted.
+pub async fn label_names(
+    db: &DBMSRef,
+    resolver: &dyn TableResolver,
+    ctx: &Context,
+    req: &PromSeriesRequest,
+    span_recorder: SpanRecorder,
+) -> Result<Vec<String>> {
+    let series = select_series(db, resolver, ctx, req, None, span_recorder).await?;
+    let names: BTreeSet<String> = series.into_iter().flat_map(|s| s.into_keys()).collect();
+    Ok(names.into_iter().collect())
+}
+
+/// Values of the label of the selected series, sorted.
+pub async fn label_values(
+    db: &DBMSRef,
+    resolver: &dyn TableResolver,
+    ctx: &Context,
+    name: &str,
+    req: &PromSeriesRequest,
+    span_recorder: SpanRecorder,
+) -> Result<Vec<String>> {
+    let series = select_series(db, resolver, ctx, req, Some(name), span_recorder).await?;
+    let values: BTreeSet<String> = series
+        .into_iter()
+        .filter_map(|mut s| s.remove(name))
+        .collect();
+    Ok(values.into_iter().collect())
+}
+
+/// Select the series of the selectors, or of all metrics if there is no selector.
+/// Only `label` is selected if specified, tables without the label are skipped.
+async fn select_series(
+    db: &DBMSRef,
+    resolver: &dyn TableResolver,
+    ctx: &Context,
+    req: &PromSeriesRequest,
+    label: Option<&str>,
+    span_recorder: SpanRecorder,
+) -> Result<BTreeSet<Labels>> {
+    let selectors = if req.matchers.is_empty() {
+        vec![vec![]]
+    } else {
+        req.matchers
+            .iter()
+            .map(|m| parse_selector(m))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut series = BTreeSet::new();
+    for (idx, mut matchers) in selectors.into_iter().enumerate() {
+        // a selector without the metric name matches all the metrics
+        if !matchers.iter().any(|m| m.name == METRIC_NAME_LABEL) {
+            matchers.insert(
+                0,
+                Matcher {
+                    name: METRIC_NAME_LABEL.to_string(),
+                    op: MatchOp::Re,
+                    value: ".+".to_string(),
+                },
+            );
+        }
+        let selection = resolver.resolve(&matchers)?;
+
+        for table in selection.tables {
+            // the other matchers are filters of the tags, a table without the
+            // matched labels has no series matched
+            let has_labels = matchers
+                .iter()
+                .all(|m| m.name == METRIC_NAME_LABEL || table.tags.contains(&m.name));
+            if !has_labels {
+                continue;
+            }
+            let tags = match label {
+                Some(METRIC_NAME_LABEL) => vec![],
+                Some(label) if table.tags.iter().any(|t| t == label) => vec![label.to_string()],
+                Some(_) => continue,
+                None => table.tags.clone(),
+            };
+
+            let sql = series_sql(&table, &tags, &selection.filters, req);
+            debug!("Prepare to select series as: {}", sql);
+            let query = Query::new(ctx.clone(), sql);
+            let batches = db
+                .execute(&query, span_recorder.child(idx.to_string()).span_ctx())
+                .await?
+                .result()
+                .chunk_result()
+                .await?;
+
+            for batch in batches {
+                let columns = batch
+                    .columns()
+                    .iter()
+                    .map(|c| cast(c, &DataType::Utf8))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let columns = columns
+                    .iter()
+                    .map(|c| as_string_array(c))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                for row in 0..batch.num_rows() {
+                    let mut labels = Labels::new();
+                    labels.insert(METRIC_NAME_LABEL.to_string(), table.name.clone());
+                    for (tag, array) in tags.iter().zip(&columns) {
+                        // empty labels are omitted
+                        if array.is_valid(row) && !array.value(row).is_empty() {
+                            labels.insert(tag.clone(), array.value(row).to_string());
+                        }
+                    }
+                    series.insert(labels);
+                }
+            }
+        }
+    }
+
+    Ok(series)
+}
+
+/// Sql of the distinct tags of the table, a table without tags has
+/// a series if there is any sample.
+fn series_sql(
+    table: &MetricTable,
+    tags: &[String],
+    filters: &[String],
+    req: &PromSeriesRequest,
+) -> String {
+    let mut filters = filters.to_vec();
+    if let Some(start) = req.start {
+        filters.push(format!("\"time\" >= CAST({start} AS TIMESTAMP)"));
+    }
+    if let Some(end) = req.end {
+        filters.push(format!("\"time\" <= CAST({end} AS TIMESTAMP)"));
+    }
+    let filter = if filters.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", filters.join(" AND "))
+    };
+
+    let table_name = quote_ident(&table.name);
+    if tags.is_empty() {
+        return format!("SELECT 1 FROM {table_name}{filter} LIMIT 1");
+    }
+    let tags = tags
+        .iter()
+        .map(|t| quote_ident(t))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("SELECT DISTINCT {tags} FROM {table_name}{filter}")
+}
+
+/// Parse a series selector, e.g. `up{job="prometheus"}`.
+fn parse_selector(selector: &str) -> Result<Vec<Matcher>> {
+    match parser::parse(selector)? {
+        Expr::Vector(selector) => Ok(selector.matchers),
+        _ => Err(invalid(format!("invalid series selector \"{selector}\""))),
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn invalid(reason: impl Into<String>) -> QueryError {
+    QueryError::InvalidPromQL {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use models::schema::Precision;
+    use spi::server::prom::PromSeriesRequest;
+
+    use super::{parse_selector, series_sql};
+    use crate::prom::promql::planner::MetricTable;
+
+    #[test]
+    fn test_series_sql() {
+        let table = MetricTable {
+            name: "up".to_string(),
+            tags: vec!["job".to_string(), "instance".to_string()],
+            precision: Precision::NS,
+        };
+        let req = PromSeriesRequest {
+            matchers: vec![],
+            start: Some(1),
+            end: None,
+        };
+        assert_eq!(
+            series_sql(&table, &table.tags, &["\"job\" = 'a'".to_string()], &req),
+            "SELECT DISTINCT \"job\", \"instance\" FROM \"up\" \
+            WHERE \"job\" = 'a' AND \"time\" >= CAST(1 AS TIMESTAMP)"
+        );
+        assert_eq!(
+            series_sql(&table, &[], &[], &PromSeriesRequest::default()),
+            "SELECT 1 FROM \"up\" LIMIT 1"
+        );
+
+        assert_eq!(parse_selector("up{job=\"a\"}").unwrap().len(), 2);
+        assert!(parse_selector("rate(up[5m])").is_err());
+    }
+}
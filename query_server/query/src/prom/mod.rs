@@ -1,3 +1,5 @@
+pub mod metadata;
+pub mod promql;
 pub mod remote_server;
 pub mod time_series;
 
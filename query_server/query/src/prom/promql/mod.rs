@@ -0,0 +1,326 @@
+//! PromQL queries of the prometheus http api.
+//!
+//! Expressions are translated to sql over the metric tables written by remote write,
+//! see [`planner`], and the results are converted to the series of prometheus.
+
+pub mod parser;
+pub mod planner;
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::DateTime;
+use datafusion::arrow::array::{Array, Float64Array, Int64Array, StringArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::record_batch::RecordBatch;
+use spi::server::dbms::DBMSRef;
+use spi::server::prom::{
+    PromInstantSeries, PromQueryRequest, PromQueryResult, PromRangeSeries, PromSample,
+};
+use spi::service::protocol::{Context, Query};
+use spi::{QueryError, Result};
+use trace::{debug, SpanRecorder};
+
+use self::planner::{Planner, TableResolver, Value};
+use super::{METRIC_NAME_LABEL, METRIC_SAMPLE_COLUMN_NAME};
+
+/// Max number of points of a series in the result of a range query, same as prometheus.
+const MAX_POINTS: i64 = 11_000;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Evaluate the PromQL query by executing the translated sql.
+pub async fn execute(
+    db: &DBMSRef,
+    resolver: &dyn TableResolver,
+    ctx: &Context,
+    req: PromQueryRequest,
+    span_recorder: SpanRecorder,
+) -> Result<PromQueryResult> {
+    if let Some(step) = req.step {
+        if step <= 0 {
+            return Err(invalid(
+                "zero or negative query resolution step widths are not accepted",
+            ));
+        }
+        if req.end < req.start {
+            return Err(invalid("end timestamp must not be before start time"));
+        }
+        if (req.end - req.start) / step >= MAX_POINTS {
+            return Err(invalid(format!(
+                "exceeded maximum resolution of {MAX_POINTS} points per timeseries"
+            )));
+        }
+    }
+
+    let expr = parser::parse(&req.query)?;
+    let value = Planner::new(resolver, req.start, req.end, req.step).plan(&expr)?;
+    let vector = match value {
+        Value::Scalar(n) => return Ok(scalar_result(&req, n)),
+        Value::Vector(vector) => vector,
+    };
+
+    debug!("Prepare to execute PromQL {} as: {}", req.query, vector.sql);
+    let query = Query::new(ctx.clone(), vector.sql);
+    let batches = db
+        .execute(&query, span_recorder.span_ctx())
+        .await?
+        .result()
+        .chunk_result()
+        .await?;
+    let series = collect_series(&batches)?;
+
+    let result = match req.step {
+        Some(_) => PromQueryResult::Matrix(
+            series
+                .into_iter()
+                .map(|(metric, points)| PromRangeSeries {
+                    metric,
+                    values: points.into_iter().map(to_sample).collect(),
+                })
+                .collect(),
+        ),
+        None => PromQueryResult::Vector(
+            series
+                .into_iter()
+                .filter_map(|(metric, points)| {
+                    points.last().map(|point| PromInstantSeries {
+                        metric,
+                        value: to_sample(*point),
+                    })
+                })
+                .collect(),
+        ),
+    };
+
+    Ok(result)
+}
+
+fn scalar_result(req: &PromQueryRequest, n: f64) -> PromQueryResult {
+    match req.step {
+        // a scalar of a range query is a series without labels
+        Some(step) => {
+            let values = (0..=(req.end - req.start) / step)
+                .map(|i| to_sample((req.start + i * step, n)))
+                .collect();
+            PromQueryResult::Matrix(vec![PromRangeSeries {
+                metric: BTreeMap::new(),
+                values,
+            }])
+        }
+        None => PromQueryResult::Scalar(to_sample((req.start, n))),
+    }
+}
+
+type Series = BTreeMap<BTreeMap<String, String>, Vec<(i64, f64)>>;
+
+/// Group the samples by series, samples of a series are ordered by time.
+fn collect_series(batches: &[RecordBatch]) -> Result<Series> {
+    let mut series: HashMap<BTreeMap<String, String>, Vec<(i64, f64)>> = HashMap::new();
+    for batch in batches {
+        let schema = batch.schema();
+        let column = |name: &str, data_type: &DataType| {
+            let idx = schema.index_of(name)?;
+            Ok::<_, QueryError>(cast(batch.column(idx), data_type)?)
+        };
+        let times = column("time", &DataType::Int64)?;
+        let times = times
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| internal("time column is not int64"))?;
+        let values = column(METRIC_SAMPLE_COLUMN_NAME, &DataType::Float64)?;
+        let values = values
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| internal("value column is not float64"))?;
+        let labels = schema
+            .fields()
+            .iter()
+            .filter(|f| f.name() != "time" && f.name() != METRIC_SAMPLE_COLUMN_NAME)
+            .map(|f| Ok((f.name().clone(), column(f.name(), &DataType::Utf8)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        for row in 0..batch.num_rows() {
+            if times.is_null(row) || values.is_null(row) {
+                continue;
+            }
+            // empty labels are omitted
+            let metric = labels
+                .iter()
+                .filter_map(|(name, array)| {
+                    let array = array.as_any().downcast_ref::<StringArray>()?;
+                    if array.is_null(row) || array.value(row).is_empty() {
+                        return None;
+                    }
+                    Some((name.clone(), array.value(row).to_string()))
+                })
+                .collect();
+            series
+                .entry(metric)
+                .or_default()
+                .push((times.value(row), values.value(row)));
+        }
+    }
+
+    Ok(series
+        .into_iter()
+        .map(|(metric, mut points)| {
+            points.sort_by_key(|(time, _)| *time);
+            (metric, points)
+        })
+        .collect())
+}
+
+fn to_sample((time, value): (i64, f64)) -> PromSample {
+    let value = if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    };
+    (time as f64 / NANOS_PER_SECOND as f64, value)
+}
+
+/// Parse a duration of PromQL like `1h30m`, returns nanoseconds.
+pub fn parse_duration(duration: &str) -> Result<i64> {
+    const UNITS: [(&str, i64); 7] = [
+        ("ms", 1_000_000),
+        ("s", NANOS_PER_SECOND),
+        ("m", 60 * NANOS_PER_SECOND),
+        ("h", 3600 * NANOS_PER_SECOND),
+        ("d", 86400 * NANOS_PER_SECOND),
+        ("w", 7 * 86400 * NANOS_PER_SECOND),
+        ("y", 365 * 86400 * NANOS_PER_SECOND),
+    ];
+
+    let err = || invalid(format!("invalid duration '{duration}'"));
+    let mut rest = duration;
+    let mut nanos = 0_i64;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).ok_or_else(err)?;
+        let n = rest[..digits].parse::<i64>().map_err(|_| err())?;
+        rest = &rest[digits..];
+        let (unit, unit_nanos) = UNITS
+            .iter()
+            .find(|(unit, _)| {
+                rest.starts_with(unit)
+                    // `m` of `ms`
+                    && !(*unit == "m" && rest.starts_with("ms"))
+            })
+            .ok_or_else(err)?;
+        rest = &rest[unit.len()..];
+        nanos = n
+            .checked_mul(*unit_nanos)
+            .and_then(|n| n.checked_add(nanos))
+            .ok_or_else(err)?;
+    }
+    if nanos == 0 && duration.is_empty() {
+        return Err(err());
+    }
+
+    Ok(nanos)
+}
+
+/// Parse a time of the http api, a unix timestamp in seconds or a rfc3339 time,
+/// returns nanoseconds.
+pub fn parse_time(time: &str) -> Result<i64> {
+    if let Ok(seconds) = time.parse::<f64>() {
+        return Ok((seconds * NANOS_PER_SECOND as f64).round() as i64);
+    }
+    DateTime::parse_from_rfc3339(time)
+        .map(|t| t.timestamp_nanos())
+        .map_err(|_| invalid(format!("cannot parse \"{time}\" to a valid timestamp")))
+}
+
+/// Parse a step of the http api, seconds or a duration, returns nanoseconds.
+pub fn parse_step(step: &str) -> Result<i64> {
+    if let Ok(seconds) = step.parse::<f64>() {
+        return Ok((seconds * NANOS_PER_SECOND as f64).round() as i64);
+    }
+    parse_duration(step)
+}
+
+fn invalid(reason: impl Into<String>) -> QueryError {
+    QueryError::InvalidPromQL {
+        reason: reason.into(),
+    }
+}
+
+fn internal(reason: &str) -> QueryError {
+    QueryError::Internal {
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use datafusion::arrow::array::{Float64Array, StringArray, TimestampNanosecondArray};
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::arrow::record_batch::RecordBatch;
+
+    use super::{collect_series, parse_duration, parse_step, parse_time, to_sample};
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("5m").unwrap(), 300_000_000_000);
+        assert_eq!(parse_duration("1h30m").unwrap(), 5_400_000_000_000);
+        assert_eq!(parse_duration("1s500ms").unwrap(), 1_500_000_000);
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5").is_err());
+        assert!(parse_duration("5x").is_err());
+
+        assert_eq!(parse_step("15").unwrap(), 15_000_000_000);
+        assert_eq!(parse_step("0.5").unwrap(), 500_000_000);
+        assert_eq!(parse_step("1m").unwrap(), 60_000_000_000);
+        assert_eq!(parse_time("1.5").unwrap(), 1_500_000_000);
+        assert_eq!(parse_time("1970-01-01T00:00:01.5Z").unwrap(), 1_500_000_000);
+        assert!(parse_time("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_collect_series() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                true,
+            ),
+            Field::new("job", DataType::Utf8, true),
+            Field::new("value", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(TimestampNanosecondArray::from(vec![
+                    2_000_000_000,
+                    1_000_000_000,
+                    1_000_000_000,
+                ])),
+                Arc::new(StringArray::from(vec![Some("a"), Some("a"), None])),
+                Arc::new(Float64Array::from(vec![2.0, 1.0, f64::INFINITY])),
+            ],
+        )
+        .unwrap();
+
+        let series = collect_series(&[batch]).unwrap();
+        let a = BTreeMap::from([("job".to_string(), "a".to_string())]);
+        assert_eq!(
+            series,
+            BTreeMap::from([
+                (BTreeMap::new(), vec![(1_000_000_000, f64::INFINITY)]),
+                (a, vec![(1_000_000_000, 1.0), (2_000_000_000, 2.0)]),
+            ])
+        );
+        assert_eq!(
+            to_sample((1_500_000_000, f64::INFINITY)),
+            (1.5, "+Inf".to_string())
+        );
+        assert_eq!(to_sample((0, 0.25)), (0.0, "0.25".to_string()));
+    }
+}
@@ -0,0 +1,765 @@
+//! Parser of PromQL expressions.
+
+use spi::{QueryError, Result};
+
+use super::{parse_duration, METRIC_NAME_LABEL};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    /// Instant vector selector, `metric{label="value"} offset 5m`.
+    Vector(VectorSelector),
+    /// Range vector selector, `metric{label="value"}[5m] offset 5m`.
+    Matrix {
+        selector: VectorSelector,
+        /// Range in nanoseconds.
+        range: i64,
+    },
+    Call {
+        func: String,
+        args: Vec<Expr>,
+    },
+    Aggregate {
+        op: String,
+        /// Parameter of `quantile`.
+        param: Option<Box<Expr>>,
+        expr: Box<Expr>,
+        grouping: Option<Grouping>,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+        /// `bool` modifier of comparison operators.
+        return_bool: bool,
+        matching: Option<Grouping>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorSelector {
+    pub matchers: Vec<Matcher>,
+    /// Offset in nanoseconds.
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matcher {
+    pub name: String,
+    pub op: MatchOp,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchOp {
+    Equal,
+    NotEqual,
+    Re,
+    NotRe,
+}
+
+/// Labels of `by`/`without` of aggregations, or `on`/`ignoring` of vector matching.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Grouping {
+    /// `by` or `on`
+    By(Vec<String>),
+    /// `without` or `ignoring`
+    Without(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Pow,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    And,
+    Or,
+    Unless,
+}
+
+impl BinaryOp {
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Self::Eq | Self::Ne | Self::Gt | Self::Lt | Self::Ge | Self::Le
+        )
+    }
+
+    pub fn is_set_operator(&self) -> bool {
+        matches!(self, Self::And | Self::Or | Self::Unless)
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Or => 1,
+            Self::And | Self::Unless => 2,
+            Self::Eq | Self::Ne | Self::Gt | Self::Lt | Self::Ge | Self::Le => 3,
+            Self::Add | Self::Sub => 4,
+            Self::Mul | Self::Div | Self::Mod => 5,
+            Self::Pow => 6,
+        }
+    }
+
+    /// Apply the operator to scalars, comparisons return 1 for true and 0 for false.
+    pub fn eval(&self, lhs: f64, rhs: f64) -> Option<f64> {
+        let bool_value = |b: bool| if b { 1.0 } else { 0.0 };
+        let value = match self {
+            Self::Add => lhs + rhs,
+            Self::Sub => lhs - rhs,
+            Self::Mul => lhs * rhs,
+            Self::Div => lhs / rhs,
+            Self::Mod => lhs % rhs,
+            Self::Pow => lhs.powf(rhs),
+            Self::Eq => bool_value(lhs == rhs),
+            Self::Ne => bool_value(lhs != rhs),
+            Self::Gt => bool_value(lhs > rhs),
+            Self::Lt => bool_value(lhs < rhs),
+            Self::Ge => bool_value(lhs >= rhs),
+            Self::Le => bool_value(lhs <= rhs),
+            Self::And | Self::Or | Self::Unless => return None,
+        };
+        Some(value)
+    }
+}
+
+const AGGREGATE_OPS: [&str; 9] = [
+    "sum", "avg", "min", "max", "count", "stddev", "stdvar", "group", "quantile",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    /// Duration in nanoseconds.
+    Duration(i64),
+    String(String),
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Comma,
+    Colon,
+    Assign,
+    Op(BinaryOp),
+    EqRegex,
+    NeqRegex,
+}
+
+pub fn parse(promql: &str) -> Result<Expr> {
+    let tokens = tokenize(promql)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.expr(0)?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(t) => Err(invalid(format!("unexpected {t:?} after expression"))),
+    }
+}
+
+fn tokenize(promql: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = promql.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '+' => Token::Op(BinaryOp::Add),
+            '-' => Token::Op(BinaryOp::Sub),
+            '*' => Token::Op(BinaryOp::Mul),
+            '/' => Token::Op(BinaryOp::Div),
+            '%' => Token::Op(BinaryOp::Mod),
+            '^' => Token::Op(BinaryOp::Pow),
+            '=' if next == Some('=') => {
+                i += 1;
+                Token::Op(BinaryOp::Eq)
+            }
+            '=' if next == Some('~') => {
+                i += 1;
+                Token::EqRegex
+            }
+            '=' => Token::Assign,
+            '!' if next == Some('=') => {
+                i += 1;
+                Token::Op(BinaryOp::Ne)
+            }
+            '!' if next == Some('~') => {
+                i += 1;
+                Token::NeqRegex
+            }
+            '>' if next == Some('=') => {
+                i += 1;
+                Token::Op(BinaryOp::Ge)
+            }
+            '>' => Token::Op(BinaryOp::Gt),
+            '<' if next == Some('=') => {
+                i += 1;
+                Token::Op(BinaryOp::Le)
+            }
+            '<' => Token::Op(BinaryOp::Lt),
+            '"' | '\'' | '`' => {
+                let (s, end) = read_string(&chars, i)?;
+                i = end;
+                tokens.push(Token::String(s));
+                continue;
+            }
+            c if c.is_ascii_digit() || (c == '.' && next.map_or(false, |n| n.is_ascii_digit())) => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                    // exponent of numbers like 1e-3
+                    if matches!(chars[i], 'e' | 'E')
+                        && matches!(chars.get(i + 1), Some('+' | '-'))
+                        && chars.get(i + 2).map_or(false, |c| c.is_ascii_digit())
+                    {
+                        i += 2;
+                    }
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                tokens.push(parse_number_or_duration(&literal)?);
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' || c == ':' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == ':')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let token = match word.to_lowercase().as_str() {
+                    "and" => Token::Op(BinaryOp::And),
+                    "or" => Token::Op(BinaryOp::Or),
+                    "unless" => Token::Op(BinaryOp::Unless),
+                    "inf" => Token::Number(f64::INFINITY),
+                    "nan" => Token::Number(f64::NAN),
+                    _ => Token::Ident(word),
+                };
+                tokens.push(token);
+                continue;
+            }
+            c => return Err(invalid(format!("unexpected character '{c}'"))),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+fn parse_number_or_duration(literal: &str) -> Result<Token> {
+    if let Ok(n) = literal.parse::<f64>() {
+        return Ok(Token::Number(n));
+    }
+    if let Some(hex) = literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))
+    {
+        if let Ok(n) = i64::from_str_radix(hex, 16) {
+            return Ok(Token::Number(n as f64));
+        }
+    }
+    parse_duration(literal).map(Token::Duration)
+}
+
+/// Read the string quoted by the char at `start`, returns it and the position after it.
+fn read_string(chars: &[char], start: usize) -> Result<(String, usize)> {
+    let quote = chars[start];
+    let mut s = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            // no escapes in raw strings
+            '\\' if quote != '`' && i + 1 < chars.len() => {
+                let escaped = match chars[i + 1] {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    c => c,
+                };
+                s.push(escaped);
+                i += 2;
+            }
+            c if c == quote => return Ok((s, i + 1)),
+            c => {
+                s.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err(invalid("unterminated quoted string"))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn consume(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<()> {
+        if self.consume(token) {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "expected {token:?}, found {:?}",
+                self.peek()
+            )))
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Binary expressions of operators whose precedence is higher than `min_precedence`.
+    fn expr(&mut self, min_precedence: u8) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(op)) if op.precedence() > min_precedence => *op,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+
+            let return_bool = self.consume_keyword("bool");
+            if return_bool && !op.is_comparison() {
+                return Err(invalid(
+                    "bool modifier can only be used on comparison operators",
+                ));
+            }
+            let matching = if self.consume_keyword("on") {
+                Some(Grouping::By(self.labels()?))
+            } else if self.consume_keyword("ignoring") {
+                Some(Grouping::Without(self.labels()?))
+            } else {
+                None
+            };
+            if self.consume_keyword("group_left") || self.consume_keyword("group_right") {
+                return Err(invalid("many-to-one matching is not supported"));
+            }
+
+            // `^` is right associative
+            let rhs = if op == BinaryOp::Pow {
+                self.expr(op.precedence() - 1)?
+            } else {
+                self.expr(op.precedence())?
+            };
+            lhs = match (lhs, rhs) {
+                (Expr::Number(l), Expr::Number(r)) if !op.is_set_operator() => {
+                    if op.is_comparison() && !return_bool {
+                        return Err(invalid(
+                            "comparisons between scalars must use bool modifier",
+                        ));
+                    }
+                    Expr::Number(op.eval(l, r).unwrap_or(f64::NAN))
+                }
+                (lhs, rhs) => Expr::Binary {
+                    op,
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                    return_bool,
+                    matching,
+                },
+            };
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        match self.peek() {
+            Some(Token::Op(BinaryOp::Sub)) => {
+                self.pos += 1;
+                // binds looser than `^`, `-2^2` is -4
+                let expr = self.expr(BinaryOp::Mul.precedence())?;
+                Ok(match expr {
+                    Expr::Number(n) => Expr::Number(-n),
+                    expr => Expr::Binary {
+                        op: BinaryOp::Mul,
+                        lhs: Box::new(Expr::Number(-1.0)),
+                        rhs: Box::new(expr),
+                        return_bool: false,
+                        matching: None,
+                    },
+                })
+            }
+            Some(Token::Op(BinaryOp::Add)) => {
+                self.pos += 1;
+                self.expr(BinaryOp::Mul.precedence())
+            }
+            _ => self.postfix(),
+        }
+    }
+
+    /// Primary expression followed by `[range]` and `offset`.
+    fn postfix(&mut self) -> Result<Expr> {
+        let mut expr = self.primary()?;
+        if self.consume(&Token::LBracket) {
+            let selector = match expr {
+                Expr::Vector(selector) => selector,
+                _ => return Err(invalid("ranges are only allowed for vector selectors")),
+            };
+            let range = self.duration()?;
+            if self.peek() == Some(&Token::Colon) {
+                return Err(invalid("subqueries are not supported"));
+            }
+            self.expect(&Token::RBracket)?;
+            expr = Expr::Matrix { selector, range };
+        }
+        if self.consume_keyword("offset") {
+            let offset = match self.peek() {
+                Some(Token::Op(BinaryOp::Sub)) => {
+                    self.pos += 1;
+                    -self.duration()?
+                }
+                _ => self.duration()?,
+            };
+            match &mut expr {
+                Expr::Vector(selector) | Expr::Matrix { selector, .. } => selector.offset = offset,
+                _ => return Err(invalid("offset is only allowed for selectors")),
+            }
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Duration(_)) => Err(invalid("unexpected duration")),
+            Some(Token::LParen) => {
+                let expr = self.expr(0)?;
+                self.expect(&Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::LBrace) => {
+                let matchers = self.matchers()?;
+                if !matchers.iter().any(|m| m.name == METRIC_NAME_LABEL) {
+                    return Err(invalid("vector selector must contain a metric name"));
+                }
+                Ok(Expr::Vector(VectorSelector {
+                    matchers,
+                    offset: 0,
+                }))
+            }
+            Some(Token::Ident(name)) => {
+                let lower = name.to_lowercase();
+                if AGGREGATE_OPS.contains(&lower.as_str()) {
+                    let is_call =
+                        matches!(self.peek(), Some(Token::LParen)) || self.peek_grouping();
+                    if is_call {
+                        return self.aggregate(lower);
+                    }
+                }
+                if self.consume(&Token::LParen) {
+                    let args = self.args()?;
+                    return Ok(Expr::Call { func: name, args });
+                }
+
+                let mut matchers = vec![Matcher {
+                    name: METRIC_NAME_LABEL.to_string(),
+                    op: MatchOp::Equal,
+                    value: name,
+                }];
+                if self.consume(&Token::LBrace) {
+                    matchers.extend(self.matchers()?);
+                }
+                Ok(Expr::Vector(VectorSelector {
+                    matchers,
+                    offset: 0,
+                }))
+            }
+            other => Err(invalid(format!("unexpected {other:?}"))),
+        }
+    }
+
+    fn peek_grouping(&self) -> bool {
+        matches!(self.peek(), Some(Token::Ident(w))
+            if w.eq_ignore_ascii_case("by") || w.eq_ignore_ascii_case("without"))
+    }
+
+    fn grouping(&mut self) -> Result<Option<Grouping>> {
+        if self.consume_keyword("by") {
+            Ok(Some(Grouping::By(self.labels()?)))
+        } else if self.consume_keyword("without") {
+            Ok(Some(Grouping::Without(self.labels()?)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// `sum by (labels) (expr)` or `sum (expr) by (labels)`
+    fn aggregate(&mut self, op: String) -> Result<Expr> {
+        let mut grouping = self.grouping()?;
+        self.expect(&Token::LParen)?;
+        let mut args = self.args()?;
+        if grouping.is_none() {
+            grouping = self.grouping()?;
+        }
+
+        let expected = if op == "quantile" { 2 } else { 1 };
+        if args.len() != expected {
+            return Err(invalid(format!(
+                "{op} expects {expected} argument(s), but found {}",
+                args.len()
+            )));
+        }
+        let expr = Box::new(args.pop().unwrap_or(Expr::Number(f64::NAN)));
+        Ok(Expr::Aggregate {
+            op,
+            param: args.pop().map(Box::new),
+            expr,
+            grouping,
+        })
+    }
+
+    /// Arguments of a call, after the left parenthesis.
+    fn args(&mut self) -> Result<Vec<Expr>> {
+        let mut args = vec![];
+        if self.consume(&Token::RParen) {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expr(0)?);
+            if !self.consume(&Token::Comma) {
+                break;
+            }
+        }
+        self.expect(&Token::RParen)?;
+        Ok(args)
+    }
+
+    /// `(label, ...)`
+    fn labels(&mut self) -> Result<Vec<String>> {
+        self.expect(&Token::LParen)?;
+        let mut labels = vec![];
+        loop {
+            match self.next() {
+                Some(Token::Ident(label)) => labels.push(label),
+                Some(Token::RParen) if labels.is_empty() => return Ok(labels),
+                other => return Err(invalid(format!("expected label, found {other:?}"))),
+            }
+            if !self.consume(&Token::Comma) {
+                break;
+            }
+        }
+        self.expect(&Token::RParen)?;
+        Ok(labels)
+    }
+
+    /// Label matchers, after the left brace.
+    fn matchers(&mut self) -> Result<Vec<Matcher>> {
+        let mut matchers = vec![];
+        loop {
+            let name = match self.next() {
+                Some(Token::Ident(name)) => name,
+                Some(Token::RBrace) => return Ok(matchers),
+                other => return Err(invalid(format!("expected label, found {other:?}"))),
+            };
+            let op = match self.next() {
+                Some(Token::Assign) => MatchOp::Equal,
+                Some(Token::Op(BinaryOp::Ne)) => MatchOp::NotEqual,
+                Some(Token::EqRegex) => MatchOp::Re,
+                Some(Token::NeqRegex) => MatchOp::NotRe,
+                other => {
+                    return Err(invalid(format!(
+                        "expected label matching operator, found {other:?}"
+                    )))
+                }
+            };
+            let value = match self.next() {
+                Some(Token::String(value)) => value,
+                other => return Err(invalid(format!("expected string, found {other:?}"))),
+            };
+            matchers.push(Matcher { name, op, value });
+            if !self.consume(&Token::Comma) {
+                self.expect(&Token::RBrace)?;
+                return Ok(matchers);
+            }
+        }
+    }
+
+    fn duration(&mut self) -> Result<i64> {
+        match self.next() {
+            Some(Token::Duration(d)) if d > 0 => Ok(d),
+            // durations like `[300]` are seconds
+            Some(Token::Number(n)) if n > 0.0 => Ok((n * 1e9) as i64),
+            other => Err(invalid(format!("expected duration, found {other:?}"))),
+        }
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> QueryError {
+    QueryError::InvalidPromQL {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, BinaryOp, Expr, Grouping, MatchOp, Matcher, VectorSelector};
+
+    fn selector(name: &str, matchers: Vec<(&str, MatchOp, &str)>) -> VectorSelector {
+        let mut all = vec![Matcher {
+            name: "__name__".to_string(),
+            op: MatchOp::Equal,
+            value: name.to_string(),
+        }];
+        all.extend(matchers.into_iter().map(|(n, op, v)| Matcher {
+            name: n.to_string(),
+            op,
+            value: v.to_string(),
+        }));
+        VectorSelector {
+            matchers: all,
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_selector() {
+        assert_eq!(
+            parse(r#"http_requests_total{job="api", code=~"5..", path!~'/a\'b'}"#).unwrap(),
+            Expr::Vector(selector(
+                "http_requests_total",
+                vec![
+                    ("job", MatchOp::Equal, "api"),
+                    ("code", MatchOp::Re, "5.."),
+                    ("path", MatchOp::NotRe, "/a'b"),
+                ]
+            ))
+        );
+
+        let mut s = selector("up", vec![]);
+        s.offset = 3_600_000_000_000;
+        assert_eq!(
+            parse("up[1m30s] offset 1h").unwrap(),
+            Expr::Matrix {
+                selector: s,
+                range: 90_000_000_000
+            }
+        );
+
+        assert!(parse(r#"{job="api"}"#).is_err());
+        assert!(parse("up[5m:1m]").is_err());
+        assert!(parse("rate(up)[5m]").is_err());
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        let rate = Expr::Call {
+            func: "rate".to_string(),
+            args: vec![Expr::Matrix {
+                selector: selector("req", vec![]),
+                range: 300_000_000_000,
+            }],
+        };
+        let expected = Expr::Aggregate {
+            op: "sum".to_string(),
+            param: None,
+            expr: Box::new(rate.clone()),
+            grouping: Some(Grouping::By(vec!["job".to_string()])),
+        };
+        assert_eq!(parse("sum by (job) (rate(req[5m]))").unwrap(), expected);
+        assert_eq!(parse("sum(rate(req[5m])) by (job)").unwrap(), expected);
+
+        assert_eq!(
+            parse("histogram_quantile(0.9, sum without (job) (rate(req[5m])))").unwrap(),
+            Expr::Call {
+                func: "histogram_quantile".to_string(),
+                args: vec![
+                    Expr::Number(0.9),
+                    Expr::Aggregate {
+                        op: "sum".to_string(),
+                        param: None,
+                        expr: Box::new(rate.clone()),
+                        grouping: Some(Grouping::Without(vec!["job".to_string()])),
+                    }
+                ]
+            }
+        );
+
+        // constants are folded and `*` binds tighter than `+`
+        assert_eq!(
+            parse("rate(req[5m]) * 2 ^ 3 + 1").unwrap(),
+            Expr::Binary {
+                op: BinaryOp::Add,
+                lhs: Box::new(Expr::Binary {
+                    op: BinaryOp::Mul,
+                    lhs: Box::new(rate),
+                    rhs: Box::new(Expr::Number(8.0)),
+                    return_bool: false,
+                    matching: None,
+                }),
+                rhs: Box::new(Expr::Number(1.0)),
+                return_bool: false,
+                matching: None,
+            }
+        );
+        assert_eq!(parse("-2 ^ 2").unwrap(), Expr::Number(-4.0));
+        assert_eq!(parse("2 ^ 3 ^ 2").unwrap(), Expr::Number(512.0));
+
+        assert!(matches!(
+            parse("a > bool on (job) b").unwrap(),
+            Expr::Binary {
+                op: BinaryOp::Gt,
+                return_bool: true,
+                matching: Some(Grouping::By(_)),
+                ..
+            }
+        ));
+        assert!(parse("1 > 2").is_err());
+        assert!(parse("a + bool b").is_err());
+        assert!(parse("sum(a, b)").is_err());
+    }
+}
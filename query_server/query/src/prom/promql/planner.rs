@@ -0,0 +1,714 @@
+//! Translate PromQL expressions to sql.
+//!
+//! An instant vector is a sql query of columns `time`, its labels, then `value`, which has a
+//! row for each series at each evaluation time. Range vectors are only used by functions like
+//! `rate`: samples of the range before each evaluation time are aggregated by `time_window`,
+//! whose windows slide by the step and end right after the evaluation times. An instant
+//! vector selector is the last sample in the lookback delta before each evaluation time.
+//! The windows are computed in the precision of the time column of each table, evaluation
+//! times are truncated to it.
+
+use std::collections::BTreeSet;
+
+use models::schema::Precision;
+use spi::{QueryError, Result};
+
+use super::parser::{BinaryOp, Expr, Grouping, Matcher, VectorSelector};
+use super::METRIC_NAME_LABEL;
+
+/// How far back a sample is looked for an instant vector selector.
+pub const LOOKBACK_DELTA: i64 = 5 * 60 * 1_000_000_000;
+
+/// A metric table and its tags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricTable {
+    pub name: String,
+    pub tags: Vec<String>,
+    /// Precision of the time column.
+    pub precision: Precision,
+}
+
+/// Tables matched by the metric name matchers of a selector,
+/// and the sql filters of the other matchers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selection {
+    pub tables: Vec<MetricTable>,
+    pub filters: Vec<String>,
+}
+
+pub trait TableResolver: Send + Sync {
+    fn resolve(&self, matchers: &[Matcher]) -> Result<Selection>;
+}
+
+/// Result of planning an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Scalar(f64),
+    Vector(VectorSql),
+}
+
+/// Sql of an instant vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorSql {
+    pub sql: String,
+    /// Label columns between `time` and `value`.
+    pub labels: Vec<String>,
+}
+
+pub struct Planner<'a> {
+    resolver: &'a dyn TableResolver,
+    /// Evaluation times in nanoseconds, `step` is `None` for an instant query.
+    start: i64,
+    end: i64,
+    step: Option<i64>,
+}
+
+impl<'a> Planner<'a> {
+    pub fn new(resolver: &'a dyn TableResolver, start: i64, end: i64, step: Option<i64>) -> Self {
+        Self {
+            resolver,
+            start,
+            end,
+            step,
+        }
+    }
+
+    pub fn plan(&self, expr: &Expr) -> Result<Value> {
+        match expr {
+            Expr::Number(n) => Ok(Value::Scalar(*n)),
+            Expr::Vector(selector) => self
+                .selector(selector, "last(\"time\", \"value\")", LOOKBACK_DELTA, true)
+                .map(Value::Vector),
+            Expr::Matrix { .. } => Err(invalid(
+                "range vector can only be used as the argument of functions",
+            )),
+            Expr::Call { func, args } => self.call(func, args),
+            Expr::Aggregate {
+                op,
+                param,
+                expr,
+                grouping,
+            } => self
+                .aggregate(op, param.as_deref(), expr, grouping.as_ref())
+                .map(Value::Vector),
+            Expr::Binary {
+                op,
+                lhs,
+                rhs,
+                return_bool,
+                matching,
+            } => self.binary(*op, lhs, rhs, *return_bool, matching.as_ref()),
+        }
+    }
+
+    fn plan_vector(&self, expr: &Expr) -> Result<VectorSql> {
+        match self.plan(expr)? {
+            Value::Vector(vector) => Ok(vector),
+            Value::Scalar(_) => Err(invalid("expected instant vector, found scalar")),
+        }
+    }
+
+    fn plan_scalar(&self, expr: &Expr) -> Result<f64> {
+        match self.plan(expr)? {
+            Value::Scalar(n) => Ok(n),
+            Value::Vector(_) => Err(invalid("expected scalar, found instant vector")),
+        }
+    }
+
+    /// Aggregate the samples of the range before each evaluation time of the series.
+    fn selector(
+        &self,
+        selector: &VectorSelector,
+        aggregate: &str,
+        range: i64,
+        keep_name: bool,
+    ) -> Result<VectorSql> {
+        let selection = self.resolver.resolve(&selector.matchers)?;
+
+        let mut labels = vec![];
+        if keep_name {
+            labels.push(METRIC_NAME_LABEL.to_string());
+        }
+        let tags: BTreeSet<&String> = selection.tables.iter().flat_map(|t| &t.tags).collect();
+        labels.extend(tags.into_iter().cloned());
+        if selection.tables.is_empty() {
+            return Ok(empty_vector(labels));
+        }
+
+        let offset = selector.offset;
+        let sqls: Vec<String> = selection
+            .tables
+            .iter()
+            .map(|table| {
+                self.table_sql(table, &labels, &selection.filters, aggregate, range, offset)
+            })
+            .collect();
+
+        Ok(VectorSql {
+            sql: sqls.join(" UNION ALL "),
+            labels,
+        })
+    }
+
+    /// Sql of the selector on a table, the window arithmetic is in the unit of the precision
+    /// of its time column.
+    fn table_sql(
+        &self,
+        table: &MetricTable,
+        labels: &[String],
+        filters: &[String],
+        aggregate: &str,
+        range: i64,
+        offset: i64,
+    ) -> String {
+        let tags: String = table
+            .tags
+            .iter()
+            .map(|t| format!(", {}", quote_ident(t)))
+            .collect();
+        let projections: String = labels
+            .iter()
+            .map(|label| {
+                if label == METRIC_NAME_LABEL {
+                    format!("{} AS {}, ", quote_string(&table.name), quote_ident(label))
+                } else if table.tags.contains(label) {
+                    format!("{}, ", quote_ident(label))
+                } else {
+                    format!("CAST(NULL AS STRING) AS {}, ", quote_ident(label))
+                }
+            })
+            .collect();
+
+        let unit = nanos_per_unit(table.precision);
+        let start = (self.start - offset).div_euclid(unit);
+        let end = (self.end - offset).div_euclid(unit);
+        let range = (range / unit).max(1);
+        let step = self.step.map_or(range, |step| (step / unit).max(1));
+
+        let mut filters = filters.to_vec();
+        filters.push(format!(
+            "\"time\" >= CAST({} AS TIMESTAMP)",
+            (start - range + 1) * unit
+        ));
+        filters.push(format!("\"time\" <= CAST({} AS TIMESTAMP)", end * unit));
+        // windows `[t - range + 1, t + 1)` of each evaluation time `t`
+        let (window, origin) = if range >= step {
+            (range, (start - range + 1).rem_euclid(step))
+        } else {
+            // windows of the step, filtered to the samples in the range
+            filters.push(format!(
+                "((({start} - CAST(\"time\" AS BIGINT)) % {step}) + {step}) % {step} < {range}"
+            ));
+            (step, (start + 1).rem_euclid(step))
+        };
+        let time_window = format!(
+            "time_window(\"time\", {}, {}, '{}')",
+            interval(window * unit),
+            interval(step * unit),
+            format_timestamp(origin * unit)
+        );
+
+        format!(
+            "SELECT CAST((CAST(\"window\".\"end\" AS BIGINT) - 1) * {unit} + {offset} AS TIMESTAMP) AS \"time\", \
+            {projections}\"value\" FROM (\
+            SELECT {time_window} AS \"window\"{tags}, {aggregate} AS \"value\" \
+            FROM {} WHERE {} GROUP BY \"window\"{tags}) \
+            WHERE CAST(\"window\".\"end\" AS BIGINT) BETWEEN {} AND {}",
+            quote_ident(&table.name),
+            filters.join(" AND "),
+            start + 1,
+            end + 1,
+        )
+    }
+
+    fn call(&self, func: &str, args: &[Expr]) -> Result<Value> {
+        let range_aggregate = match func {
+            "rate" => Some("increase(\"time\", \"value\") / {range_seconds}"),
+            "increase" => Some("increase(\"time\", \"value\")"),
+            "delta" => Some("last(\"time\", \"value\") - first(\"time\", \"value\")"),
+            "avg_over_time" => Some("avg(\"value\")"),
+            "sum_over_time" => Some("sum(\"value\")"),
+            "min_over_time" => Some("min(\"value\")"),
+            "max_over_time" => Some("max(\"value\")"),
+            "count_over_time" => Some("CAST(count(\"value\") AS DOUBLE)"),
+            "last_over_time" => Some("last(\"time\", \"value\")"),
+            "stddev_over_time" => Some("stddev_pop(\"value\")"),
+            "stdvar_over_time" => Some("var_pop(\"value\")"),
+            _ => None,
+        };
+        if let Some(aggregate) = range_aggregate {
+            return match args {
+                [Expr::Matrix { selector, range }] => {
+                    let range_seconds = float_sql(*range as f64 / 1e9);
+                    let aggregate = aggregate.replace("{range_seconds}", &range_seconds);
+                    self.selector(selector, &aggregate, *range, false)
+                        .map(Value::Vector)
+                }
+                _ => Err(invalid(format!("{func} expects a range vector argument"))),
+            };
+        }
+
+        match (func, args) {
+            ("histogram_quantile", [quantile, expr]) => {
+                let quantile = self.plan_scalar(quantile)?;
+                let vector = self.plan_vector(expr)?;
+                let labels = without_labels(&vector.labels, &["le"]);
+                if !vector.labels.iter().any(|l| l == "le") {
+                    return Ok(Value::Vector(empty_vector(labels)));
+                }
+                Ok(Value::Vector(group_by(
+                    &vector,
+                    labels,
+                    &format!(
+                        "histogram_quantile({}, \"le\", \"value\")",
+                        float_sql(quantile)
+                    ),
+                )))
+            }
+            (
+                "abs" | "ceil" | "floor" | "exp" | "ln" | "log2" | "log10" | "sqrt" | "round",
+                [expr],
+            ) => {
+                let vector = self.plan_vector(expr)?;
+                Ok(Value::Vector(map_value(
+                    &vector,
+                    &format!("{func}(\"value\")"),
+                )))
+            }
+            ("clamp_min" | "clamp_max", [expr, bound]) => {
+                let vector = self.plan_vector(expr)?;
+                let bound = float_sql(self.plan_scalar(bound)?);
+                let op = if func == "clamp_min" { "<" } else { ">" };
+                Ok(Value::Vector(map_value(
+                    &vector,
+                    &format!("CASE WHEN \"value\" {op} {bound} THEN {bound} ELSE \"value\" END"),
+                )))
+            }
+            _ => Err(invalid(format!(
+                "unsupported function {func} with {} argument(s)",
+                args.len()
+            ))),
+        }
+    }
+
+    fn aggregate(
+        &self,
+        op: &str,
+        param: Option<&Expr>,
+        expr: &Expr,
+        grouping: Option<&Grouping>,
+    ) -> Result<VectorSql> {
+        let vector = self.plan_vector(expr)?;
+        let labels = match grouping {
+            Some(Grouping::By(by)) => {
+                let mut labels = vec![];
+                for label in by {
+                    if vector.labels.contains(label) && !labels.contains(label) {
+                        labels.push(label.clone());
+                    }
+                }
+                labels
+            }
+            Some(Grouping::Without(without)) => without_labels(&vector.labels, without),
+            None => vec![],
+        };
+        let aggregate = match op {
+            "sum" | "avg" | "min" | "max" => format!("{op}(\"value\")"),
+            "count" => "CAST(count(\"value\") AS DOUBLE)".to_string(),
+            "stddev" => "stddev_pop(\"value\")".to_string(),
+            "stdvar" => "var_pop(\"value\")".to_string(),
+            "group" => "max(CAST(1 AS DOUBLE))".to_string(),
+            "quantile" => {
+                let quantile = match param {
+                    Some(param) => self.plan_scalar(param)?,
+                    None => return Err(invalid("quantile expects a parameter")),
+                };
+                format!("approx_percentile_cont(\"value\", {})", float_sql(quantile))
+            }
+            _ => return Err(invalid(format!("unsupported aggregation {op}"))),
+        };
+
+        Ok(group_by(&vector, labels, &aggregate))
+    }
+
+    fn binary(
+        &self,
+        op: BinaryOp,
+        lhs: &Expr,
+        rhs: &Expr,
+        return_bool: bool,
+        matching: Option<&Grouping>,
+    ) -> Result<Value> {
+        if op.is_set_operator() {
+            return Err(invalid(format!("unsupported operator {op:?}")));
+        }
+        let filter = op.is_comparison() && !return_bool;
+        let value = |l: &str, r: &str| {
+            let expr = binary_sql(op, l, r);
+            if op.is_comparison() {
+                format!("CAST({expr} AS DOUBLE)")
+            } else {
+                expr
+            }
+        };
+
+        let vector_scalar = |vector: VectorSql, l: &str, r: &str| -> Result<Value> {
+            if filter {
+                return Ok(Value::Vector(VectorSql {
+                    sql: format!(
+                        "SELECT * FROM ({}) WHERE {}",
+                        vector.sql,
+                        binary_sql(op, l, r)
+                    ),
+                    labels: vector.labels,
+                }));
+            }
+            Ok(Value::Vector(map_value(&vector, &value(l, r))))
+        };
+
+        match (self.plan(lhs)?, self.plan(rhs)?) {
+            (Value::Scalar(l), Value::Scalar(r)) => {
+                Ok(Value::Scalar(op.eval(l, r).unwrap_or(f64::NAN)))
+            }
+            (Value::Vector(vector), Value::Scalar(s)) => {
+                let s = float_sql(s);
+                vector_scalar(vector, "\"value\"", &s)
+            }
+            (Value::Scalar(s), Value::Vector(vector)) => {
+                let s = float_sql(s);
+                vector_scalar(vector, &s, "\"value\"")
+            }
+            (Value::Vector(left), Value::Vector(right)) => {
+                let matching_labels = match matching {
+                    Some(Grouping::By(on)) => on.clone(),
+                    Some(Grouping::Without(ignoring)) => without_labels(&left.labels, ignoring),
+                    None => without_labels(&left.labels, &[]),
+                };
+                let mut conditions = vec!["\"l\".\"time\" = \"r\".\"time\"".to_string()];
+                for label in matching_labels.iter() {
+                    let (in_left, in_right) =
+                        (left.labels.contains(label), right.labels.contains(label));
+                    let label = quote_ident(label);
+                    match (in_left, in_right) {
+                        (true, true) => conditions
+                            .push(format!("\"l\".{label} IS NOT DISTINCT FROM \"r\".{label}")),
+                        (true, false) => conditions.push(format!("\"l\".{label} IS NULL")),
+                        (false, true) => conditions.push(format!("\"r\".{label} IS NULL")),
+                        (false, false) => {}
+                    }
+                }
+                // labels of the right side not used to match must be empty
+                if !matches!(matching, Some(Grouping::By(_))) {
+                    let ignoring = match matching {
+                        Some(Grouping::Without(ignoring)) => ignoring.as_slice(),
+                        _ => &[],
+                    };
+                    for label in without_labels(&right.labels, ignoring) {
+                        if !matching_labels.contains(&label) {
+                            conditions.push(format!("\"r\".{} IS NULL", quote_ident(&label)));
+                        }
+                    }
+                }
+                let join = format!(
+                    "FROM ({}) AS \"l\" JOIN ({}) AS \"r\" ON {}",
+                    left.sql,
+                    right.sql,
+                    conditions.join(" AND ")
+                );
+
+                if filter {
+                    return Ok(Value::Vector(VectorSql {
+                        sql: format!(
+                            "SELECT \"l\".* {join} WHERE {}",
+                            binary_sql(op, "\"l\".\"value\"", "\"r\".\"value\"")
+                        ),
+                        labels: left.labels,
+                    }));
+                }
+                let labels: Vec<String> = matching_labels
+                    .into_iter()
+                    .filter(|l| left.labels.contains(l))
+                    .collect();
+                let projections: String = labels
+                    .iter()
+                    .map(|l| format!("\"l\".{0} AS {0}, ", quote_ident(l)))
+                    .collect();
+                Ok(Value::Vector(VectorSql {
+                    sql: format!(
+                        "SELECT \"l\".\"time\" AS \"time\", {projections}{} AS \"value\" {join}",
+                        value("\"l\".\"value\"", "\"r\".\"value\"")
+                    ),
+                    labels,
+                }))
+            }
+        }
+    }
+}
+
+/// Aggregate the vector by the labels at each evaluation time.
+fn group_by(vector: &VectorSql, labels: Vec<String>, aggregate: &str) -> VectorSql {
+    let columns: String = labels
+        .iter()
+        .map(|l| format!(", {}", quote_ident(l)))
+        .collect();
+    VectorSql {
+        sql: format!(
+            "SELECT \"time\"{columns}, {aggregate} AS \"value\" FROM ({}) GROUP BY \"time\"{columns}",
+            vector.sql
+        ),
+        labels,
+    }
+}
+
+/// Compute a new value of each sample, the metric name is dropped.
+fn map_value(vector: &VectorSql, value: &str) -> VectorSql {
+    let labels = without_labels(&vector.labels, &[]);
+    let columns: String = labels
+        .iter()
+        .map(|l| format!("{}, ", quote_ident(l)))
+        .collect();
+    VectorSql {
+        sql: format!(
+            "SELECT \"time\", {columns}{value} AS \"value\" FROM ({})",
+            vector.sql
+        ),
+        labels,
+    }
+}
+
+fn empty_vector(labels: Vec<String>) -> VectorSql {
+    let columns: String = labels
+        .iter()
+        .map(|l| format!("CAST(NULL AS STRING) AS {}, ", quote_ident(l)))
+        .collect();
+    VectorSql {
+        sql: format!(
+            "SELECT CAST(NULL AS TIMESTAMP) AS \"time\", {columns}CAST(NULL AS DOUBLE) AS \"value\" WHERE false"
+        ),
+        labels,
+    }
+}
+
+/// Labels except the excluded ones and the metric name.
+fn without_labels<S: AsRef<str>>(labels: &[String], excluded: &[S]) -> Vec<String> {
+    labels
+        .iter()
+        .filter(|l| *l != METRIC_NAME_LABEL && !excluded.iter().any(|e| e.as_ref() == *l))
+        .cloned()
+        .collect()
+}
+
+fn binary_sql(op: BinaryOp, l: &str, r: &str) -> String {
+    let op = match op {
+        BinaryOp::Pow => return format!("power({l}, {r})"),
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::Mod => "%",
+        BinaryOp::Eq => "=",
+        BinaryOp::Ne => "!=",
+        BinaryOp::Gt => ">",
+        BinaryOp::Lt => "<",
+        BinaryOp::Ge => ">=",
+        BinaryOp::Le => "<=",
+        BinaryOp::And => "AND",
+        BinaryOp::Or => "OR",
+        BinaryOp::Unless => "AND NOT",
+    };
+    format!("({l} {op} {r})")
+}
+
+fn float_sql(n: f64) -> String {
+    if n.is_finite() {
+        format!("{n:?}")
+    } else {
+        format!("CAST('{n}' AS DOUBLE)")
+    }
+}
+
+/// Nanoseconds of a unit of the time column of the precision.
+fn nanos_per_unit(precision: Precision) -> i64 {
+    match precision {
+        Precision::MS => 1_000_000,
+        Precision::US => 1_000,
+        Precision::NS => 1,
+    }
+}
+
+/// Interval literal of the nanoseconds in the largest exact unit.
+fn interval(nanos: i64) -> String {
+    const UNITS: [(&str, i64); 5] = [
+        ("hour", 3_600_000_000_000),
+        ("minute", 60_000_000_000),
+        ("second", 1_000_000_000),
+        ("millisecond", 1_000_000),
+        ("microsecond", 1_000),
+    ];
+    match UNITS.iter().find(|(_, n)| nanos % n == 0) {
+        Some((unit, n)) => format!("INTERVAL '{} {unit}'", nanos / n),
+        None => format!("INTERVAL '{nanos} nanosecond'"),
+    }
+}
+
+fn format_timestamp(nanos: i64) -> String {
+    use chrono::{SecondsFormat, TimeZone, Utc};
+
+    Utc.timestamp_nanos(nanos)
+        .to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn invalid(reason: impl Into<String>) -> QueryError {
+    QueryError::InvalidPromQL {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use models::schema::Precision;
+    use spi::Result;
+
+    use super::{MetricTable, Planner, Selection, TableResolver, Value};
+    use crate::prom::promql::parser::{parse, Matcher};
+
+    struct Resolver;
+
+    impl TableResolver for Resolver {
+        fn resolve(&self, matchers: &[Matcher]) -> Result<Selection> {
+            let name = &matchers[0].value;
+            let (tags, precision) = match name.as_str() {
+                "req" => (vec!["job".to_string(), "le".to_string()], Precision::NS),
+                "up" => (vec!["job".to_string()], Precision::NS),
+                "up_ms" => (vec!["job".to_string()], Precision::MS),
+                _ => return Ok(Selection::default()),
+            };
+            Ok(Selection {
+                tables: vec![MetricTable {
+                    name: name.clone(),
+                    tags,
+                    precision,
+                }],
+                filters: matchers[1..]
+                    .iter()
+                    .map(|m| format!("\"{}\" = '{}'", m.name, m.value))
+                    .collect(),
+            })
+        }
+    }
+
+    fn plan(promql: &str, step: Option<i64>) -> Result<Value> {
+        let minute = 60_000_000_000;
+        Planner::new(&Resolver, minute * 10, minute * 20, step).plan(&parse(promql)?)
+    }
+
+    fn vector(promql: &str, step: Option<i64>) -> (String, Vec<String>) {
+        match plan(promql, step).unwrap() {
+            Value::Vector(v) => (v.sql, v.labels),
+            Value::Scalar(n) => panic!("expect vector, found {n}"),
+        }
+    }
+
+    #[test]
+    fn test_plan_selector() {
+        let (sql, labels) = vector("up{job=\"a\"}", Some(60_000_000_000));
+        assert_eq!(labels, vec!["__name__".to_string(), "job".to_string()]);
+        assert_eq!(
+            sql,
+            "SELECT CAST((CAST(\"window\".\"end\" AS BIGINT) - 1) * 1 + 0 AS TIMESTAMP) AS \"time\", \
+            'up' AS \"__name__\", \"job\", \"value\" FROM (\
+            SELECT time_window(\"time\", INTERVAL '5 minute', INTERVAL '1 minute', \
+            '1970-01-01T00:00:00.000000001Z') AS \"window\", \"job\", \
+            last(\"time\", \"value\") AS \"value\" \
+            FROM \"up\" WHERE \"job\" = 'a' AND \"time\" >= CAST(300000000001 AS TIMESTAMP) \
+            AND \"time\" <= CAST(1200000000000 AS TIMESTAMP) GROUP BY \"window\", \"job\") \
+            WHERE CAST(\"window\".\"end\" AS BIGINT) BETWEEN 600000000001 AND 1200000000001"
+        );
+
+        // the range is shorter than the step
+        let (sql, _) = vector("rate(up[30s] offset 1m)", Some(60_000_000_000));
+        assert!(sql.contains(
+            "(((540000000000 - CAST(\"time\" AS BIGINT)) % 60000000000) + 60000000000) \
+            % 60000000000 < 30000000000"
+        ));
+        assert!(sql.contains(
+            "time_window(\"time\", INTERVAL '1 minute', INTERVAL '1 minute', \
+            '1970-01-01T00:00:00.000000001Z')"
+        ));
+        assert!(sql.contains("increase(\"time\", \"value\") / 30.0 AS \"value\""));
+        assert!(sql.contains("- 1) * 1 + 60000000000 AS TIMESTAMP"));
+
+        // the time column is in milliseconds
+        let (sql, _) = vector("up_ms{job=\"a\"}", Some(60_000_000_000));
+        assert_eq!(
+            sql,
+            "SELECT CAST((CAST(\"window\".\"end\" AS BIGINT) - 1) * 1000000 + 0 AS TIMESTAMP) AS \"time\", \
+            'up_ms' AS \"__name__\", \"job\", \"value\" FROM (\
+            SELECT time_window(\"time\", INTERVAL '5 minute', INTERVAL '1 minute', \
+            '1970-01-01T00:00:00.001000000Z') AS \"window\", \"job\", \
+            last(\"time\", \"value\") AS \"value\" \
+            FROM \"up_ms\" WHERE \"job\" = 'a' AND \"time\" >= CAST(300001000000 AS TIMESTAMP) \
+            AND \"time\" <= CAST(1200000000000 AS TIMESTAMP) GROUP BY \"window\", \"job\") \
+            WHERE CAST(\"window\".\"end\" AS BIGINT) BETWEEN 600001 AND 1200001"
+        );
+        let (sql, _) = vector("rate(up_ms[30s] offset 1m)", Some(60_000_000_000));
+        assert!(
+            sql.contains("(((540000 - CAST(\"time\" AS BIGINT)) % 60000) + 60000) % 60000 < 30000")
+        );
+        assert!(sql.contains(
+            "time_window(\"time\", INTERVAL '1 minute', INTERVAL '1 minute', \
+            '1970-01-01T00:00:00.001000000Z')"
+        ));
+        assert!(sql.contains("- 1) * 1000000 + 60000000000 AS TIMESTAMP"));
+
+        // unknown metrics are empty
+        let (sql, labels) = vector("unknown", None);
+        assert_eq!(labels, vec!["__name__".to_string()]);
+        assert!(sql.ends_with("WHERE false"));
+    }
+
+    #[test]
+    fn test_plan_expr() {
+        let (sql, labels) = vector(
+            "histogram_quantile(0.9, sum by (le) (rate(req[5m])))",
+            Some(60_000_000_000),
+        );
+        assert!(labels.is_empty());
+        assert!(sql.starts_with(
+            "SELECT \"time\", histogram_quantile(0.9, \"le\", \"value\") AS \"value\" \
+            FROM (SELECT \"time\", \"le\", sum(\"value\") AS \"value\" FROM (SELECT"
+        ));
+        assert!(sql.ends_with("GROUP BY \"time\", \"le\") GROUP BY \"time\""));
+
+        let (sql, labels) = vector("2 * up > 1", None);
+        assert_eq!(labels, vec!["job".to_string()]);
+        assert!(sql.starts_with(
+            "SELECT * FROM (SELECT \"time\", \"job\", (2.0 * \"value\") AS \"value\" FROM"
+        ));
+        assert!(sql.ends_with("WHERE (\"value\" > 1.0)"));
+
+        let (sql, labels) = vector("up / on (job) up", None);
+        assert_eq!(labels, vec!["job".to_string()]);
+        assert!(sql.starts_with(
+            "SELECT \"l\".\"time\" AS \"time\", \"l\".\"job\" AS \"job\", \
+            (\"l\".\"value\" / \"r\".\"value\") AS \"value\" FROM"
+        ));
+        assert!(sql.ends_with(
+            "ON \"l\".\"time\" = \"r\".\"time\" AND \"l\".\"job\" IS NOT DISTINCT FROM \"r\".\"job\""
+        ));
+
+        assert_eq!(plan("1 + 2 * 3", None).unwrap(), Value::Scalar(7.0));
+        assert!(plan("up[5m]", None).is_err());
+        assert!(plan("rate(up)", None).is_err());
+        assert!(plan("up and up", None).is_err());
+        assert!(plan("holt_winters(up[5m], 0.1, 0.1)", None).is_err());
+    }
+}
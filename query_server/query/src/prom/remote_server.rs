@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -18,13 +18,15 @@ use protos::prompb::types::TimeSeries;
 use protos::FieldValue;
 use regex::Regex;
 use spi::server::dbms::DBMSRef;
-use spi::server::prom::PromRemoteServer;
+use spi::server::prom::{PromQueryRequest, PromQueryResult, PromRemoteServer, PromSeriesRequest};
 use spi::service::protocol::{Context, Query, QueryHandle};
 use spi::{QueryError, Result};
 use trace::{debug, warn, SpanContext, SpanExt, SpanRecorder};
 
+use super::promql::parser::{MatchOp, Matcher};
+use super::promql::planner::{MetricTable, Selection, TableResolver};
 use super::time_series::writer::WriterBuilder;
-use super::{METRIC_NAME_LABEL, METRIC_SAMPLE_COLUMN_NAME};
+use super::{metadata, promql, METRIC_NAME_LABEL, METRIC_SAMPLE_COLUMN_NAME};
 use crate::prom::DEFAULT_PROM_TABLE_NAME;
 
 pub struct PromRemoteSqlServer {
@@ -69,6 +71,53 @@ impl PromRemoteServer for PromRemoteSqlServer {
         Ok(prom_write_request)
     }
 
+    async fn query(
+        &self,
+        ctx: &Context,
+        req: PromQueryRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<PromQueryResult> {
+        debug!("Received PromQL query request: {:?}", req);
+
+        let resolver = self.table_resolver(ctx).await?;
+        let span_recorder = SpanRecorder::new(span_ctx.child_span("process promql query"));
+        promql::execute(&self.db, &resolver, ctx, req, span_recorder).await
+    }
+
+    async fn series(
+        &self,
+        ctx: &Context,
+        req: PromSeriesRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<Vec<BTreeMap<String, String>>> {
+        let resolver = self.table_resolver(ctx).await?;
+        let span_recorder = SpanRecorder::new(span_ctx.child_span("process series request"));
+        metadata::series(&self.db, &resolver, ctx, &req, span_recorder).await
+    }
+
+    async fn label_names(
+        &self,
+        ctx: &Context,
+        req: PromSeriesRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<Vec<String>> {
+        let resolver = self.table_resolver(ctx).await?;
+        let span_recorder = SpanRecorder::new(span_ctx.child_span("process labels request"));
+        metadata::label_names(&self.db, &resolver, ctx, &req, span_recorder).await
+    }
+
+    async fn label_values(
+        &self,
+        ctx: &Context,
+        name: &str,
+        req: PromSeriesRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<Vec<String>> {
+        let resolver = self.table_resolver(ctx).await?;
+        let span_recorder = SpanRecorder::new(span_ctx.child_span("process label values request"));
+        metadata::label_values(&self.db, &resolver, ctx, name, &req, span_recorder).await
+    }
+
     fn prom_write_request_to_lines<'a>(&self, req: &'a WriteRequest) -> Result<Vec<Line<'a>>> {
         let mut lines = Vec::with_capacity(req.timeseries.len());
 
@@ -105,6 +154,18 @@ impl PromRemoteSqlServer {
         }
     }
 
+    async fn table_resolver<'a>(&self, ctx: &'a Context) -> Result<MetaTableResolver<'a>> {
+        let meta = self
+            .coord
+            .meta_manager()
+            .tenant_meta(ctx.tenant())
+            .await
+            .ok_or_else(|| MetaError::TenantNotFound {
+                tenant: ctx.tenant().to_string(),
+            })?;
+        Ok(MetaTableResolver { ctx, meta })
+    }
+
     async fn deserialize_read_request(&self, req: Bytes) -> Result<ReadRequest> {
         let mut decompressed = Vec::new();
         let compressed = req.to_byte_slice();
@@ -218,26 +279,58 @@ fn build_sql_with_table(
         special_fields: _,
     } = query;
 
+    let matchers = matchers
+        .iter()
+        .map(|m| {
+            let type_ = m
+                .type_
+                .enum_value()
+                .map_err(|e| QueryError::InvalidRemoteReadReq {
+                    source: format!("Unknown label matcher type: {e}").into(),
+                })?;
+            Ok((type_, m.name.as_str(), m.value.as_str()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let (tables, mut filters) = match_tables(ctx, meta, &matchers)?;
+
+    // Convert to ns timestamp
+    filters.push(format!("time >= {}", start_timestamp_ms * 1_000_000));
+    filters.push(format!("time <= {}", end_timestamp_ms * 1_000_000));
+
+    let result = tables
+        .into_iter()
+        .map(|table| SqlWithTable {
+            sql: format!(
+                "SELECT * FROM {} WHERE {}",
+                table.name,
+                filters.join(" AND ")
+            ),
+            table,
+        })
+        .collect();
+
+    Ok(result)
+}
+
+/// Get the tables matched by the matchers of the metric name,
+/// and the sql filters of the other matchers.
+fn match_tables(
+    ctx: &Context,
+    meta: &MetaClientRef,
+    matchers: &[(Type, &str, &str)],
+) -> Result<(Vec<TskvTableSchemaRef>, Vec<String>)> {
     let mut tables = Vec::new();
     let mut filters = Vec::with_capacity(matchers.len());
 
-    for m in matchers {
-        let type_ = m
-            .type_
-            .enum_value()
-            .map_err(|e| QueryError::InvalidRemoteReadReq {
-                source: format!("Unknown label matcher type: {e}").into(),
-            })?;
-
-        if METRIC_NAME_LABEL == m.name {
+    for (type_, name, value) in matchers {
+        if METRIC_NAME_LABEL == *name {
             match type_ {
                 Type::EQ => {
                     // Get schema of the specified table
-                    let table_name = &m.value;
                     let table = meta
-                        .get_tskv_table_schema(ctx.database(), table_name)?
+                        .get_tskv_table_schema(ctx.database(), value)?
                         .ok_or_else(|| MetaError::TableNotFound {
-                            table: table_name.to_string(),
+                            table: value.to_string(),
                         })?;
                     tables = vec![table];
                 }
@@ -245,7 +338,7 @@ fn build_sql_with_table(
                     // Filter table names through regular expressions,
                     // Get the schema of the remaining tables.
                     let pattern =
-                        Regex::new(&m.value).map_err(|err| QueryError::InvalidRemoteReadReq {
+                        Regex::new(value).map_err(|err| QueryError::InvalidRemoteReadReq {
                             source: Box::new(err),
                         })?;
 
@@ -274,38 +367,87 @@ fn build_sql_with_table(
             continue;
         }
 
+        let name = format!("\"{}\"", name.replace('"', "\"\""));
+        let value = format!("'{}'", value.replace('\'', "''"));
         match type_ {
             Type::EQ => {
-                filters.push(format!("{} = '{}'", m.name, m.value));
+                filters.push(format!("{name} = {value}"));
             }
             Type::NEQ => {
-                filters.push(format!("{} != '{}'", m.name, m.value));
+                filters.push(format!("{name} != {value}"));
             }
             Type::RE => {
-                filters.push(format!("{} ~ '{}'", m.name, m.value));
+                filters.push(format!("{name} ~ {value}"));
             }
             Type::NRE => {
-                filters.push(format!("{} !~ '{}'", m.name, m.value));
+                filters.push(format!("{name} !~ {value}"));
             }
         }
     }
-    // Convert to ns timestamp
-    filters.push(format!("time >= {}", start_timestamp_ms * 1_000_000));
-    filters.push(format!("time <= {}", end_timestamp_ms * 1_000_000));
 
-    let result = tables
-        .into_iter()
-        .map(|table| SqlWithTable {
-            sql: format!(
-                "SELECT * FROM {} WHERE {}",
-                table.name,
-                filters.join(" AND ")
-            ),
-            table,
-        })
-        .collect();
+    Ok((tables, filters))
+}
 
-    Ok(result)
+/// Resolve the tables of PromQL selectors from the meta of the tenant.
+struct MetaTableResolver<'a> {
+    ctx: &'a Context,
+    meta: MetaClientRef,
+}
+
+impl TableResolver for MetaTableResolver<'_> {
+    fn resolve(&self, matchers: &[Matcher]) -> Result<Selection> {
+        // regular expressions of PromQL are fully anchored
+        let values = matchers
+            .iter()
+            .map(|m| match m.op {
+                MatchOp::Re | MatchOp::NotRe => format!("^(?:{})$", m.value),
+                MatchOp::Equal | MatchOp::NotEqual => m.value.clone(),
+            })
+            .collect::<Vec<_>>();
+        let matchers = matchers
+            .iter()
+            .zip(&values)
+            .map(|(m, value)| {
+                let type_ = match m.op {
+                    MatchOp::Equal => Type::EQ,
+                    MatchOp::NotEqual => Type::NEQ,
+                    MatchOp::Re => Type::RE,
+                    MatchOp::NotRe => Type::NRE,
+                };
+                (type_, m.name.as_str(), value.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        let (tables, filters) = match match_tables(self.ctx, &self.meta, &matchers) {
+            Ok(matched) => matched,
+            // selector of an unknown metric is empty
+            Err(QueryError::Meta {
+                source: MetaError::TableNotFound { .. },
+            }) => return Ok(Selection::default()),
+            Err(QueryError::InvalidRemoteReadReq { source }) => {
+                return Err(QueryError::InvalidPromQL {
+                    reason: source.to_string(),
+                })
+            }
+            Err(err) => return Err(err),
+        };
+
+        let tables = tables
+            .into_iter()
+            .map(|table| MetricTable {
+                name: table.name.to_string(),
+                tags: table
+                    .columns()
+                    .iter()
+                    .filter(|c| c.column_type.is_tag())
+                    .map(|c| c.name.clone())
+                    .collect(),
+                precision: table.time_column_precision(),
+            })
+            .collect();
+
+        Ok(Selection { tables, filters })
+    }
 }
 
 /// Convert the execution result of query to TimeSeries list of prometheus
@@ -0,0 +1,899 @@
+//! InfluxQL front-end.
+//!
+//! InfluxQL statements are translated to the sql of CnosDB, then parsed by [`ExtParser`],
+//! so they produce the same [`ExtStatement`]s and logical plans as sql.
+//!
+//! `GROUP BY time(interval[, offset])` is translated to `time_window_gapfill`, and `fill()`
+//! to the gap-filling functions: `fill(previous)` is `locf`, `fill(linear)` is `interpolate`,
+//! `fill(<number>)` is `coalesce` and `fill(null)` (the default) leaves the gaps null.
+//! `fill(none)` doesn't fill gaps, windows are computed by `date_bin`. Since gap filling
+//! needs an upper time bound, `time <= now()` is added if the query doesn't have one,
+//! which is the implicit upper bound of InfluxQL.
+
+mod token;
+
+use std::collections::VecDeque;
+
+use chrono::{SecondsFormat, TimeZone, Utc};
+use datafusion::sql::sqlparser::parser::ParserError;
+use snafu::ResultExt;
+use spi::query::ast::ExtStatement;
+use spi::query::parser::Parser;
+use spi::ParserSnafu;
+
+use self::token::{tokenize, Token};
+use crate::sql::parser::ExtParser;
+
+type Result<T> = std::result::Result<T, ParserError>;
+
+#[derive(Default)]
+pub struct InfluxQLParser {}
+
+impl Parser for InfluxQLParser {
+    fn parse(&self, influxql: &str) -> spi::Result<VecDeque<ExtStatement>> {
+        let sql = influxql_to_sql(influxql).context(ParserSnafu)?;
+        ExtParser::parse_sql(&sql).context(ParserSnafu)
+    }
+}
+
+/// Translate InfluxQL statements to sql statements.
+pub fn influxql_to_sql(influxql: &str) -> Result<String> {
+    let statements = translate_influxql(influxql)?
+        .into_iter()
+        .map(|s| s.sql)
+        .collect::<Vec<_>>();
+    Ok(statements.join(";\n"))
+}
+
+/// A translated InfluxQL statement, with what is needed to build the InfluxDB style result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfluxQLStatement {
+    pub sql: String,
+    /// Name of the series in the result.
+    pub name: Option<String>,
+    /// Tags of `GROUP BY`, rows of the result are split into series by their values.
+    pub tags: Vec<String>,
+}
+
+impl InfluxQLStatement {
+    fn new(sql: String, name: Option<String>) -> Self {
+        Self {
+            sql,
+            name,
+            tags: vec![],
+        }
+    }
+}
+
+/// Translate InfluxQL statements to sql statements one by one.
+pub fn translate_influxql(influxql: &str) -> Result<Vec<InfluxQLStatement>> {
+    let mut translator = Translator {
+        tokens: tokenize(influxql)?,
+        pos: 0,
+        has_upper_time_bound: false,
+    };
+
+    let mut statements = vec![];
+    loop {
+        while translator.consume(&Token::SemiColon) {}
+        if translator.peek().is_none() {
+            break;
+        }
+        statements.push(translator.statement()?);
+        match translator.peek() {
+            None | Some(Token::SemiColon) => {}
+            Some(t) => return parser_err(format!("expected end of statement, found {t:?}")),
+        }
+    }
+
+    Ok(statements)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Fill {
+    Null,
+    None,
+    Previous,
+    Linear,
+    Value(String),
+}
+
+/// Aggregate functions of InfluxQL, gaps of their results are filled by `fill()`.
+const AGGREGATE_FUNCTIONS: [&str; 12] = [
+    "count",
+    "sum",
+    "mean",
+    "median",
+    "mode",
+    "spread",
+    "stddev",
+    "min",
+    "max",
+    "first",
+    "last",
+    "percentile",
+];
+
+struct Translator {
+    tokens: Vec<Token>,
+    pos: usize,
+    // whether the WHERE clause has a condition like `time < x`
+    has_upper_time_bound: bool,
+}
+
+impl Translator {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn consume(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<()> {
+        if self.consume(token) {
+            Ok(())
+        } else {
+            parser_err(format!("expected {token:?}, found {:?}", self.peek()))
+        }
+    }
+
+    fn parse_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().map(|t| t.is_keyword(keyword)).unwrap_or(false) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_keywords(&mut self, keywords: &[&str]) -> bool {
+        let matched = keywords.iter().enumerate().all(|(i, k)| {
+            self.tokens
+                .get(self.pos + i)
+                .map(|t| t.is_keyword(k))
+                .unwrap_or(false)
+        });
+        if matched {
+            self.pos += keywords.len();
+        }
+        matched
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.parse_keyword(keyword) {
+            Ok(())
+        } else {
+            parser_err(format!("expected {keyword}, found {:?}", self.peek()))
+        }
+    }
+
+    fn statement(&mut self) -> Result<InfluxQLStatement> {
+        if self.parse_keyword("SELECT") {
+            self.select()
+        } else if self.parse_keyword("SHOW") {
+            self.show()
+        } else {
+            parser_err(format!(
+                "unsupported InfluxQL statement starting with {:?}",
+                self.peek()
+            ))
+        }
+    }
+
+    fn show(&mut self) -> Result<InfluxQLStatement> {
+        if self.parse_keyword("DATABASES") {
+            Ok(InfluxQLStatement::new(
+                "SHOW DATABASES".to_string(),
+                Some("databases".to_string()),
+            ))
+        } else if self.parse_keyword("MEASUREMENTS") {
+            let mut sql = "SHOW TABLES".to_string();
+            if self.parse_keyword("ON") {
+                sql.push_str(&format!(" ON {}", self.identifier()?));
+            }
+            Ok(InfluxQLStatement::new(
+                sql,
+                Some("measurements".to_string()),
+            ))
+        } else if self.parse_keywords(&["FIELD", "KEYS"]) || self.parse_keywords(&["TAG", "KEYS"]) {
+            let database = if self.parse_keyword("ON") {
+                Some(self.identifier()?)
+            } else {
+                None
+            };
+            self.expect_keyword("FROM")?;
+            let name = self.raw_identifier()?;
+            let table = quote_ident(&name);
+            let sql = match database {
+                Some(db) => format!("DESCRIBE TABLE {db}.{table}"),
+                None => format!("DESCRIBE TABLE {table}"),
+            };
+            Ok(InfluxQLStatement::new(sql, Some(name)))
+        } else if self.parse_keywords(&["TAG", "VALUES"]) {
+            let sql = format!("SHOW TAG VALUES {}", self.rest_of_statement()?);
+            Ok(InfluxQLStatement::new(sql, None))
+        } else if self.parse_keyword("SERIES") {
+            let sql = format!("SHOW SERIES {}", self.rest_of_statement()?);
+            Ok(InfluxQLStatement::new(sql, None))
+        } else {
+            parser_err(format!(
+                "unsupported InfluxQL SHOW statement {:?}",
+                self.peek()
+            ))
+        }
+    }
+
+    /// Translate the remaining tokens of the statement one by one.
+    fn rest_of_statement(&mut self) -> Result<String> {
+        let mut parts = vec![];
+        while let Some(token) = self.peek() {
+            if token == &Token::SemiColon {
+                break;
+            }
+            let token = self.next().unwrap_or(Token::SemiColon);
+            parts.push(token_to_sql(&token)?);
+        }
+        Ok(parts.join(" "))
+    }
+
+    fn select(&mut self) -> Result<InfluxQLStatement> {
+        self.has_upper_time_bound = false;
+        let fill = self.take_fill()?;
+
+        // fields
+        let mut fields = vec![];
+        let mut has_aggregate = false;
+        let mut has_wildcard = false;
+        loop {
+            let (field, aggregate) = self.field(fill.as_ref())?;
+            has_wildcard |= field == "*";
+            fields.push(field);
+            has_aggregate |= aggregate;
+            if !self.consume(&Token::Comma) {
+                break;
+            }
+        }
+
+        self.expect_keyword("FROM")?;
+        let (table, name) = self.measurement()?;
+        if self.peek() == Some(&Token::Comma) {
+            return parser_err("selecting from multiple measurements is not supported");
+        }
+
+        let mut selection = None;
+        if self.parse_keyword("WHERE") {
+            selection = Some(self.expr()?);
+        }
+
+        // GROUP BY time(interval[, offset]), tags
+        let mut time_window = None;
+        let mut tags = vec![];
+        if self.parse_keywords(&["GROUP", "BY"]) {
+            loop {
+                if self.peek().map(|t| t.is_keyword("time")).unwrap_or(false)
+                    && self.tokens.get(self.pos + 1) == Some(&Token::LParen)
+                {
+                    self.pos += 2;
+                    let interval = self.duration()?;
+                    let offset = if self.consume(&Token::Comma) {
+                        Some(self.duration()?)
+                    } else {
+                        None
+                    };
+                    self.expect(&Token::RParen)?;
+                    time_window = Some((interval, offset));
+                } else if self.consume(&Token::Mul) {
+                    return parser_err("GROUP BY * is not supported");
+                } else {
+                    tags.push(self.raw_identifier()?);
+                }
+                if !self.consume(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let mut descending = false;
+        if self.parse_keywords(&["ORDER", "BY"]) {
+            if !self.parse_keyword("time") {
+                return parser_err("only ORDER BY time is supported");
+            }
+            if self.parse_keyword("DESC") {
+                descending = true;
+            } else {
+                self.parse_keyword("ASC");
+            }
+        }
+
+        let mut limit_offset = String::new();
+        if self.parse_keyword("LIMIT") {
+            limit_offset.push_str(&format!(" LIMIT {}", self.integer()?));
+        }
+        if self.parse_keyword("OFFSET") {
+            limit_offset.push_str(&format!(" OFFSET {}", self.integer()?));
+        }
+        if self
+            .peek()
+            .map(|t| t.is_keyword("SLIMIT") || t.is_keyword("SOFFSET") || t.is_keyword("tz"))
+            .unwrap_or(false)
+        {
+            return parser_err(format!("{:?} is not supported", self.peek()));
+        }
+
+        if fill.is_some() && time_window.is_none() {
+            return parser_err("fill() requires GROUP BY time()");
+        }
+        if time_window.is_some() && !has_aggregate {
+            return parser_err("GROUP BY time() requires an aggregate function");
+        }
+
+        // results are ordered by series, then time
+        let order_by_time = !has_aggregate || time_window.is_some();
+        let mut select_list = vec![];
+        let mut group_by = vec![];
+        match time_window {
+            Some((interval, offset)) => {
+                let fill = fill.unwrap_or(Fill::Null);
+                let window = time_window_expr(interval, offset, fill != Fill::None);
+                select_list.push(format!("{window} AS \"time\""));
+                group_by.push(window);
+                if fill != Fill::None && !self.has_upper_time_bound {
+                    selection = Some(match selection {
+                        Some(s) => format!("({s}) AND \"time\" <= now()"),
+                        None => "\"time\" <= now()".to_string(),
+                    });
+                }
+            }
+            None if !has_aggregate && !has_wildcard => select_list.push("\"time\"".to_string()),
+            None => {}
+        }
+        let quoted_tags = tags.iter().map(|t| quote_ident(t)).collect::<Vec<_>>();
+        select_list.extend(quoted_tags.iter().cloned());
+        select_list.extend(fields);
+        if has_aggregate {
+            group_by.extend(quoted_tags.iter().cloned());
+        }
+        let mut order_by = quoted_tags;
+        if order_by_time {
+            let order = if descending { " DESC" } else { "" };
+            order_by.push(format!("\"time\"{order}"));
+        }
+
+        let mut sql = format!("SELECT {} FROM {}", select_list.join(", "), table);
+        if let Some(selection) = selection {
+            sql.push_str(&format!(" WHERE {selection}"));
+        }
+        if !group_by.is_empty() {
+            sql.push_str(&format!(" GROUP BY {}", group_by.join(", ")));
+        }
+        if !order_by.is_empty() {
+            sql.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
+        }
+        sql.push_str(&limit_offset);
+
+        Ok(InfluxQLStatement {
+            sql,
+            name: Some(name),
+            tags,
+        })
+    }
+
+    /// Find and remove the `fill(...)` clause of the statement, since the fields before it
+    /// are translated according to it.
+    fn take_fill(&mut self) -> Result<Option<Fill>> {
+        let mut depth = 0;
+        let mut i = self.pos;
+        while let Some(token) = self.tokens.get(i) {
+            match token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                Token::SemiColon => break,
+                t if depth == 0
+                    && t.is_keyword("fill")
+                    && self.tokens.get(i + 1) == Some(&Token::LParen) =>
+                {
+                    let end = (i + 2..self.tokens.len())
+                        .find(|j| self.tokens[*j] == Token::RParen)
+                        .ok_or_else(|| ParserError::ParserError("unclosed fill(".to_string()))?;
+                    let fill = match &self.tokens[i + 2..end] {
+                        [t] if t.is_keyword("null") => Fill::Null,
+                        [t] if t.is_keyword("none") => Fill::None,
+                        [t] if t.is_keyword("previous") => Fill::Previous,
+                        [t] if t.is_keyword("linear") => Fill::Linear,
+                        [Token::Number(n)] => Fill::Value(n.clone()),
+                        [Token::Minus, Token::Number(n)] => Fill::Value(format!("-{n}")),
+                        other => return parser_err(format!("invalid fill option {other:?}")),
+                    };
+                    self.tokens.drain(i..=end);
+                    return Ok(Some(fill));
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        Ok(None)
+    }
+
+    /// Returns the translated field and whether it has an aggregate function.
+    fn field(&mut self, fill: Option<&Fill>) -> Result<(String, bool)> {
+        if self.consume(&Token::Mul) {
+            return Ok(("*".to_string(), false));
+        }
+        let mut has_aggregate = false;
+        let expr = self.expr_with_fill(fill, &mut has_aggregate)?;
+        if self.parse_keyword("AS") {
+            return Ok((format!("{expr} AS {}", self.identifier()?), has_aggregate));
+        }
+        Ok((expr, has_aggregate))
+    }
+
+    /// Returns the table of the measurement and the name of the measurement.
+    fn measurement(&mut self) -> Result<(String, String)> {
+        if let Some(Token::Regex(_)) = self.peek() {
+            return parser_err("regex measurement is not supported");
+        }
+        // [[database.]retention_policy.]measurement, the retention policy is ignored
+        let mut parts = vec![Some(self.raw_identifier()?)];
+        while self.consume(&Token::Dot) {
+            if self.peek() == Some(&Token::Dot) {
+                // default retention policy of `database..measurement`
+                parts.push(None);
+                continue;
+            }
+            parts.push(Some(self.raw_identifier()?));
+        }
+        match parts.as_slice() {
+            [.., Some(measurement)] if parts.len() <= 2 => {
+                Ok((quote_ident(measurement), measurement.clone()))
+            }
+            [Some(database), _, Some(measurement)] => Ok((
+                format!("{}.{}", quote_ident(database), quote_ident(measurement)),
+                measurement.clone(),
+            )),
+            _ => parser_err("invalid measurement"),
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        Ok(quote_ident(&self.raw_identifier()?))
+    }
+
+    /// The identifier as is, without quotes.
+    fn raw_identifier(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Ident { value, .. }) => Ok(value),
+            other => parser_err(format!("expected identifier, found {other:?}")),
+        }
+    }
+
+    fn integer(&mut self) -> Result<u64> {
+        match self.next() {
+            Some(Token::Number(n)) => n
+                .parse::<u64>()
+                .map_err(|_| ParserError::ParserError(format!("expected integer, found {n}"))),
+            other => parser_err(format!("expected integer, found {other:?}")),
+        }
+    }
+
+    fn duration(&mut self) -> Result<i64> {
+        match self.next() {
+            Some(Token::Duration(nanos)) if nanos > 0 => Ok(nanos),
+            other => parser_err(format!("expected duration, found {other:?}")),
+        }
+    }
+
+    fn expr(&mut self) -> Result<String> {
+        let mut has_aggregate = false;
+        self.expr_with_fill(None, &mut has_aggregate)
+    }
+
+    fn expr_with_fill(&mut self, fill: Option<&Fill>, has_aggregate: &mut bool) -> Result<String> {
+        let mut expr = self.and_expr(fill, has_aggregate)?;
+        while self.parse_keyword("OR") {
+            // an upper bound in OR branches doesn't bound the query
+            let has_upper_time_bound = self.has_upper_time_bound;
+            let right = self.and_expr(fill, has_aggregate)?;
+            self.has_upper_time_bound = has_upper_time_bound;
+            expr = format!("{expr} OR {right}");
+        }
+        Ok(expr)
+    }
+
+    fn and_expr(&mut self, fill: Option<&Fill>, has_aggregate: &mut bool) -> Result<String> {
+        let mut expr = self.comparison(fill, has_aggregate)?;
+        while self.parse_keyword("AND") {
+            let right = self.comparison(fill, has_aggregate)?;
+            expr = format!("{expr} AND {right}");
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self, fill: Option<&Fill>, has_aggregate: &mut bool) -> Result<String> {
+        let left = self.additive(fill, has_aggregate)?;
+        let op = match self.peek() {
+            Some(Token::Eq) => "=",
+            Some(Token::Neq) => "!=",
+            Some(Token::Lt) => "<",
+            Some(Token::LtEq) => "<=",
+            Some(Token::Gt) => ">",
+            Some(Token::GtEq) => ">=",
+            Some(Token::EqRegex) | Some(Token::NeqRegex) => {
+                let op = if self.next() == Some(Token::EqRegex) {
+                    "~"
+                } else {
+                    "!~"
+                };
+                return match self.next() {
+                    Some(Token::Regex(re)) => Ok(format!("{left} {op} {}", quote_string(&re))),
+                    other => parser_err(format!("expected regex, found {other:?}")),
+                };
+            }
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.additive(fill, has_aggregate)?;
+
+        const TIME: &str = "\"time\"";
+        if (left == TIME && matches!(op, "<" | "<=")) || (right == TIME && matches!(op, ">" | ">="))
+        {
+            self.has_upper_time_bound = true;
+        }
+        // integer timestamps are nanoseconds
+        let (left, right) = if left == TIME && is_integer(&right) {
+            (left, format!("CAST({right} AS TIMESTAMP)"))
+        } else if right == TIME && is_integer(&left) {
+            (format!("CAST({left} AS TIMESTAMP)"), right)
+        } else {
+            (left, right)
+        };
+
+        Ok(format!("{left} {op} {right}"))
+    }
+
+    fn additive(&mut self, fill: Option<&Fill>, has_aggregate: &mut bool) -> Result<String> {
+        let mut expr = self.multiplicative(fill, has_aggregate)?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => "+",
+                Some(Token::Minus) => "-",
+                _ => return Ok(expr),
+            };
+            self.pos += 1;
+            let right = self.multiplicative(fill, has_aggregate)?;
+            expr = format!("{expr} {op} {right}");
+        }
+    }
+
+    fn multiplicative(&mut self, fill: Option<&Fill>, has_aggregate: &mut bool) -> Result<String> {
+        let mut expr = self.unary(fill, has_aggregate)?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Mul) => "*",
+                Some(Token::Div) => "/",
+                Some(Token::Mod) => "%",
+                _ => return Ok(expr),
+            };
+            self.pos += 1;
+            let right = self.unary(fill, has_aggregate)?;
+            expr = format!("{expr} {op} {right}");
+        }
+    }
+
+    fn unary(&mut self, fill: Option<&Fill>, has_aggregate: &mut bool) -> Result<String> {
+        if self.consume(&Token::Minus) {
+            return Ok(format!("-{}", self.unary(fill, has_aggregate)?));
+        }
+        self.primary(fill, has_aggregate)
+    }
+
+    fn primary(&mut self, fill: Option<&Fill>, has_aggregate: &mut bool) -> Result<String> {
+        let token = match self.next() {
+            Some(token) => token,
+            None => return parser_err("unexpected end of statement"),
+        };
+        match token {
+            Token::LParen => {
+                let expr = self.expr_with_fill(fill, has_aggregate)?;
+                self.expect(&Token::RParen)?;
+                Ok(format!("({expr})"))
+            }
+            Token::Number(n) => Ok(n),
+            Token::String(s) => Ok(quote_string(&s)),
+            Token::Duration(nanos) => Ok(interval(nanos)),
+            Token::Ident {
+                value,
+                quoted: false,
+            } if self.peek() == Some(&Token::LParen) => {
+                self.pos += 1;
+                self.call(&value, fill, has_aggregate)
+            }
+            Token::Ident { value, quoted } => {
+                if !quoted
+                    && (value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false"))
+                {
+                    return Ok(value.to_lowercase());
+                }
+                // `field::type` casts are ignored
+                if self.consume(&Token::DoubleColon) {
+                    self.identifier()?;
+                }
+                Ok(quote_ident(&value))
+            }
+            other => parser_err(format!("unexpected {other:?} in expression")),
+        }
+    }
+
+    fn call(
+        &mut self,
+        name: &str,
+        fill: Option<&Fill>,
+        has_aggregate: &mut bool,
+    ) -> Result<String> {
+        let name = name.to_lowercase();
+        let mut args = vec![];
+        if !self.consume(&Token::RParen) {
+            loop {
+                if self.consume(&Token::Mul) {
+                    args.push("*".to_string());
+                } else {
+                    args.push(self.expr_with_fill(fill, has_aggregate)?);
+                }
+                if !self.consume(&Token::Comma) {
+                    break;
+                }
+            }
+            self.expect(&Token::RParen)?;
+        }
+
+        if !AGGREGATE_FUNCTIONS.contains(&name.as_str()) {
+            return Ok(format!("{name}({})", args.join(", ")));
+        }
+        *has_aggregate = true;
+        if args.iter().any(|a| a == "*") && name != "count" {
+            return parser_err(format!("{name}(*) is not supported"));
+        }
+
+        let aggregate = match (name.as_str(), args.as_slice()) {
+            ("mean", [field]) => format!("avg({field})"),
+            ("spread", [field]) => format!("(max({field}) - min({field}))"),
+            ("first" | "last", [field]) => format!("{name}(\"time\", {field})"),
+            ("percentile", [field, n]) => {
+                format!("approx_percentile_cont({field}, {n} / 100.0)")
+            }
+            ("percentile", _) => return parser_err("percentile() requires 2 arguments"),
+            (_, [field]) => format!("{name}({field})"),
+            _ => return parser_err(format!("{name}() requires 1 argument")),
+        };
+
+        Ok(match fill {
+            Some(Fill::Previous) => format!("locf({aggregate})"),
+            Some(Fill::Linear) => format!("interpolate({aggregate})"),
+            Some(Fill::Value(v)) => format!("coalesce({aggregate}, {v})"),
+            _ => aggregate,
+        })
+    }
+}
+
+fn time_window_expr(interval: i64, offset: Option<i64>, gapfill: bool) -> String {
+    let interval = self::interval(interval);
+    // the offset shifts the origin of windows
+    let origin = offset.map(|offset| {
+        quote_string(
+            &Utc.timestamp_nanos(offset)
+                .to_rfc3339_opts(SecondsFormat::Nanos, true),
+        )
+    });
+    match (gapfill, origin) {
+        (true, None) => format!("time_window_gapfill(\"time\", {interval})"),
+        (true, Some(origin)) => {
+            format!("time_window_gapfill(\"time\", {interval}, {interval}, {origin})")
+        }
+        (false, None) => format!("date_bin({interval}, \"time\")"),
+        (false, Some(origin)) => format!("date_bin({interval}, \"time\", TIMESTAMP {origin})"),
+    }
+}
+
+/// Interval literal of the nanoseconds in the largest exact unit.
+fn interval(nanos: i64) -> String {
+    const UNITS: [(&str, i64); 7] = [
+        ("day", 86_400_000_000_000),
+        ("hour", 3_600_000_000_000),
+        ("minute", 60_000_000_000),
+        ("second", 1_000_000_000),
+        ("millisecond", 1_000_000),
+        ("microsecond", 1_000),
+        ("nanosecond", 1),
+    ];
+    let (unit, n) = UNITS
+        .iter()
+        .find(|(_, n)| nanos % n == 0)
+        .copied()
+        .unwrap_or(("nanosecond", 1));
+    format!("INTERVAL '{} {unit}'", nanos / n)
+}
+
+fn token_to_sql(token: &Token) -> Result<String> {
+    let sql = match token {
+        Token::Ident {
+            value,
+            quoted: false,
+        } if is_keyword(value) => value.to_uppercase(),
+        Token::Ident { value, .. } => quote_ident(value),
+        Token::Number(n) => n.clone(),
+        Token::Duration(nanos) => interval(*nanos),
+        Token::String(s) => quote_string(s),
+        Token::Regex(_) | Token::EqRegex | Token::NeqRegex => {
+            return parser_err("regex is not supported in SHOW statements")
+        }
+        Token::LParen => "(".to_string(),
+        Token::RParen => ")".to_string(),
+        Token::Comma => ",".to_string(),
+        Token::Dot => ".".to_string(),
+        Token::SemiColon => ";".to_string(),
+        Token::DoubleColon => "::".to_string(),
+        Token::Eq => "=".to_string(),
+        Token::Neq => "!=".to_string(),
+        Token::Lt => "<".to_string(),
+        Token::LtEq => "<=".to_string(),
+        Token::Gt => ">".to_string(),
+        Token::GtEq => ">=".to_string(),
+        Token::Plus => "+".to_string(),
+        Token::Minus => "-".to_string(),
+        Token::Mul => "*".to_string(),
+        Token::Div => "/".to_string(),
+        Token::Mod => "%".to_string(),
+    };
+    Ok(sql)
+}
+
+/// Keywords of SHOW statements, other unquoted words are identifiers.
+fn is_keyword(word: &str) -> bool {
+    const KEYWORDS: [&str; 13] = [
+        "ON", "FROM", "WITH", "KEY", "IN", "WHERE", "AND", "OR", "NOT", "LIMIT", "OFFSET", "ORDER",
+        "BY",
+    ];
+    KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(word))
+}
+
+fn is_integer(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn parser_err<T>(msg: impl Into<String>) -> Result<T> {
+    Err(ParserError::ParserError(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{influxql_to_sql, translate_influxql, InfluxQLStatement};
+
+    #[test]
+    fn test_select() {
+        assert_eq!(
+            influxql_to_sql("SELECT v, host FROM m WHERE time > now() - 1h LIMIT 10").unwrap(),
+            "SELECT \"time\", \"v\", \"host\" FROM \"m\" \
+            WHERE \"time\" > now() - INTERVAL '1 hour' ORDER BY \"time\" LIMIT 10"
+        );
+        assert_eq!(
+            influxql_to_sql("SELECT count(v) FROM db..m WHERE host =~ /^a/ GROUP BY host").unwrap(),
+            "SELECT \"host\", count(\"v\") FROM \"db\".\"m\" \
+            WHERE \"host\" ~ '^a' GROUP BY \"host\" ORDER BY \"host\""
+        );
+        assert_eq!(
+            influxql_to_sql("SELECT * FROM m WHERE time >= 1000000000").unwrap(),
+            "SELECT * FROM \"m\" \
+            WHERE \"time\" >= CAST(1000000000 AS TIMESTAMP) ORDER BY \"time\""
+        );
+    }
+
+    #[test]
+    fn test_group_by_time() {
+        assert_eq!(
+            influxql_to_sql(
+                "SELECT mean(v) FROM m WHERE time > now()-1h GROUP BY time(1m), host fill(previous)"
+            )
+            .unwrap(),
+            "SELECT time_window_gapfill(\"time\", INTERVAL '1 minute') AS \"time\", \"host\", \
+            locf(avg(\"v\")) FROM \"m\" \
+            WHERE (\"time\" > now() - INTERVAL '1 hour') AND \"time\" <= now() \
+            GROUP BY time_window_gapfill(\"time\", INTERVAL '1 minute'), \"host\" \
+            ORDER BY \"host\", \"time\""
+        );
+        assert_eq!(
+            influxql_to_sql(
+                "SELECT last(v) AS v, spread(v) FROM m \
+                WHERE time > '2023-01-01T00:00:00Z' AND time < '2023-01-02T00:00:00Z' \
+                GROUP BY time(1d, 8h) fill(none) ORDER BY time DESC"
+            )
+            .unwrap(),
+            "SELECT date_bin(INTERVAL '1 day', \"time\", \
+            TIMESTAMP '1970-01-01T08:00:00.000000000Z') AS \"time\", \
+            last(\"time\", \"v\") AS \"v\", (max(\"v\") - min(\"v\")) FROM \"m\" \
+            WHERE \"time\" > '2023-01-01T00:00:00Z' AND \"time\" < '2023-01-02T00:00:00Z' \
+            GROUP BY date_bin(INTERVAL '1 day', \"time\", \
+            TIMESTAMP '1970-01-01T08:00:00.000000000Z') \
+            ORDER BY \"time\" DESC"
+        );
+        assert_eq!(
+            influxql_to_sql(
+                "SELECT percentile(v, 95) FROM m WHERE time > now() - 1h AND time < now() \
+                GROUP BY time(30s) fill(-1)"
+            )
+            .unwrap(),
+            "SELECT time_window_gapfill(\"time\", INTERVAL '30 second') AS \"time\", \
+            coalesce(approx_percentile_cont(\"v\", 95 / 100.0), -1) FROM \"m\" \
+            WHERE \"time\" > now() - INTERVAL '1 hour' AND \"time\" < now() \
+            GROUP BY time_window_gapfill(\"time\", INTERVAL '30 second') ORDER BY \"time\""
+        );
+    }
+
+    #[test]
+    fn test_show() {
+        assert_eq!(
+            influxql_to_sql("SHOW DATABASES; SHOW MEASUREMENTS").unwrap(),
+            "SHOW DATABASES;\nSHOW TABLES"
+        );
+        assert_eq!(
+            influxql_to_sql("SHOW TAG VALUES FROM m WITH KEY = \"host\" LIMIT 1").unwrap(),
+            "SHOW TAG VALUES FROM \"m\" WITH KEY = \"host\" LIMIT 1"
+        );
+        assert_eq!(
+            influxql_to_sql("SHOW FIELD KEYS FROM m").unwrap(),
+            "DESCRIBE TABLE \"m\""
+        );
+    }
+
+    #[test]
+    fn test_translate_statements() {
+        let statements =
+            translate_influxql("SELECT max(v) FROM db..m GROUP BY host, \"a b\"; SHOW DATABASES")
+                .unwrap();
+        assert_eq!(
+            statements,
+            vec![
+                InfluxQLStatement {
+                    sql: "SELECT \"host\", \"a b\", max(\"v\") FROM \"db\".\"m\" \
+                    GROUP BY \"host\", \"a b\" ORDER BY \"host\", \"a b\""
+                        .to_string(),
+                    name: Some("m".to_string()),
+                    tags: vec!["host".to_string(), "a b".to_string()],
+                },
+                InfluxQLStatement {
+                    sql: "SHOW DATABASES".to_string(),
+                    name: Some("databases".to_string()),
+                    tags: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert!(influxql_to_sql("SELECT v FROM /m.*/").is_err());
+        assert!(influxql_to_sql("SELECT v FROM a, b").is_err());
+        assert!(influxql_to_sql("SELECT v FROM m GROUP BY time(1m)").is_err());
+        assert!(influxql_to_sql("SELECT mean(v) FROM m GROUP BY *").is_err());
+        assert!(influxql_to_sql("SELECT mean(v) FROM m fill(previous)").is_err());
+        assert!(influxql_to_sql("DROP MEASUREMENT m").is_err());
+    }
+}
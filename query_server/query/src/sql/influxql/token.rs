@@ -0,0 +1,304 @@
+use datafusion::sql::sqlparser::parser::ParserError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    /// Identifier or keyword, `quoted` if it's in double quotes.
+    Ident {
+        value: String,
+        quoted: bool,
+    },
+    Number(String),
+    /// Duration literal like `1h30m`, in nanoseconds.
+    Duration(i64),
+    String(String),
+    Regex(String),
+    LParen,
+    RParen,
+    Comma,
+    Dot,
+    SemiColon,
+    DoubleColon,
+    Eq,
+    Neq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    EqRegex,
+    NeqRegex,
+    Plus,
+    Minus,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl Token {
+    /// Whether the token is the unquoted keyword, case insensitive.
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Ident { value, quoted: false } if value.eq_ignore_ascii_case(keyword))
+    }
+}
+
+const NANOS_PER_UNIT: [(&str, i64); 9] = [
+    ("ns", 1),
+    ("u", 1_000),
+    ("µ", 1_000),
+    ("ms", 1_000_000),
+    ("s", 1_000_000_000),
+    ("m", 60_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("d", 86_400_000_000_000),
+    ("w", 604_800_000_000_000),
+];
+
+pub fn tokenize(influxql: &str) -> Result<Vec<Token>, ParserError> {
+    let chars: Vec<char> = influxql.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '-' if next == Some('-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '.' if !next.map(|n| n.is_ascii_digit()).unwrap_or(false) => Token::Dot,
+            ';' => Token::SemiColon,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Mul,
+            '%' => Token::Mod,
+            ':' if next == Some(':') => {
+                i += 1;
+                Token::DoubleColon
+            }
+            '=' if next == Some('~') => {
+                i += 1;
+                Token::EqRegex
+            }
+            '=' => Token::Eq,
+            '!' if next == Some('=') => {
+                i += 1;
+                Token::Neq
+            }
+            '!' if next == Some('~') => {
+                i += 1;
+                Token::NeqRegex
+            }
+            '<' if next == Some('>') => {
+                i += 1;
+                Token::Neq
+            }
+            '<' if next == Some('=') => {
+                i += 1;
+                Token::LtEq
+            }
+            '<' => Token::Lt,
+            '>' if next == Some('=') => {
+                i += 1;
+                Token::GtEq
+            }
+            '>' => Token::Gt,
+            '/' if expect_regex(tokens.last()) => {
+                let (regex, end) = read_quoted(&chars, i, '/')?;
+                i = end;
+                tokens.push(Token::Regex(regex));
+                continue;
+            }
+            '/' => Token::Div,
+            '\'' => {
+                let (s, end) = read_quoted(&chars, i, '\'')?;
+                i = end;
+                tokens.push(Token::String(s));
+                continue;
+            }
+            '"' => {
+                let (value, end) = read_quoted(&chars, i, '"')?;
+                i = end;
+                tokens.push(Token::Ident {
+                    value,
+                    quoted: true,
+                });
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let (token, end) = read_number_or_duration(&chars, i)?;
+                i = end;
+                tokens.push(token);
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident {
+                    value: chars[start..i].iter().collect(),
+                    quoted: false,
+                });
+                continue;
+            }
+            c => {
+                return Err(ParserError::TokenizerError(format!(
+                    "unexpected character '{c}' in InfluxQL"
+                )))
+            }
+        };
+        tokens.push(token);
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// A `/` starts a regex after regex operators and `FROM`.
+fn expect_regex(prev: Option<&Token>) -> bool {
+    match prev {
+        Some(Token::EqRegex | Token::NeqRegex) => true,
+        Some(t) => t.is_keyword("FROM"),
+        None => false,
+    }
+}
+
+/// Read the content quoted by `quote` starting at `start`, `\` escapes the next char.
+/// Returns the content and the position after the closing quote.
+fn read_quoted(chars: &[char], start: usize, quote: char) -> Result<(String, usize), ParserError> {
+    let mut s = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                // regex keeps escapes except the escaped delimiter
+                if quote == '/' && chars[i + 1] != '/' {
+                    s.push('\\');
+                }
+                s.push(chars[i + 1]);
+                i += 2;
+            }
+            c if c == quote => return Ok((s, i + 1)),
+            c => {
+                s.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err(ParserError::TokenizerError(format!(
+        "unterminated quoted content starting with {quote}"
+    )))
+}
+
+fn read_number_or_duration(chars: &[char], start: usize) -> Result<(Token, usize), ParserError> {
+    let read_digits = |mut i: usize| {
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+            i += 1;
+        }
+        i
+    };
+
+    let mut i = read_digits(start);
+    let number: String = chars[start..i].iter().collect();
+    if i >= chars.len() || !chars[i].is_alphabetic() {
+        return Ok((Token::Number(number), i));
+    }
+
+    // duration like `1h30m`
+    let mut nanos = 0_i64;
+    let mut number = number;
+    loop {
+        let unit_start = i;
+        while i < chars.len() && chars[i].is_alphabetic() {
+            i += 1;
+        }
+        let unit: String = chars[unit_start..i].iter().collect();
+        let value = number
+            .parse::<i64>()
+            .map_err(|_| ParserError::TokenizerError(format!("invalid duration {number}{unit}")))?;
+        let nanos_per_unit = NANOS_PER_UNIT
+            .iter()
+            .find(|(u, _)| *u == unit)
+            .map(|(_, n)| *n)
+            .ok_or_else(|| {
+                ParserError::TokenizerError(format!("invalid duration unit '{unit}'"))
+            })?;
+        nanos = value
+            .checked_mul(nanos_per_unit)
+            .and_then(|v| v.checked_add(nanos))
+            .ok_or_else(|| ParserError::TokenizerError("duration overflow".to_string()))?;
+
+        if i >= chars.len() || !chars[i].is_ascii_digit() {
+            return Ok((Token::Duration(nanos), i));
+        }
+        let number_start = i;
+        i = read_digits(i);
+        number = chars[number_start..i].iter().collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, Token};
+
+    fn ident(value: &str, quoted: bool) -> Token {
+        Token::Ident {
+            value: value.to_string(),
+            quoted,
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize(
+            r#"SELECT "v"::field FROM m WHERE time > now() - 1h30m AND host =~ /a\/b/ AND t != 'it\'s'"#,
+        )
+        .unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                ident("SELECT", false),
+                ident("v", true),
+                Token::DoubleColon,
+                ident("field", false),
+                ident("FROM", false),
+                ident("m", false),
+                ident("WHERE", false),
+                ident("time", false),
+                Token::Gt,
+                ident("now", false),
+                Token::LParen,
+                Token::RParen,
+                Token::Minus,
+                Token::Duration(5_400_000_000_000),
+                ident("AND", false),
+                ident("host", false),
+                Token::EqRegex,
+                Token::Regex("a/b".to_string()),
+                ident("AND", false),
+                ident("t", false),
+                Token::Neq,
+                Token::String("it's".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            tokenize("1.5 / 2").unwrap(),
+            vec![
+                Token::Number("1.5".to_string()),
+                Token::Div,
+                Token::Number("2".to_string())
+            ]
+        );
+        assert!(tokenize("1y").is_err());
+        assert!(tokenize("'abc").is_err());
+    }
+}
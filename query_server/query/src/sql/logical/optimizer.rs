@@ -30,6 +30,7 @@ use spi::query::session::SessionCtx;
 use spi::Result;
 use trace::debug;
 
+use crate::extension::logical::optimizer_rule::push_down_time_bucket_aggregation::PushDownTimeBucketAggregation;
 use crate::extension::logical::optimizer_rule::rewrite_tag_scan::RewriteTagScan;
 use crate::sql::analyzer::DefaultAnalyzer;
 
@@ -98,6 +99,7 @@ impl Default for DefaultLogicalOptimizer {
             Arc::new(PushDownLimit::new()),
             // df default rules end
             // cnosdb rules
+            Arc::new(PushDownTimeBucketAggregation {}),
             Arc::new(RewriteTagScan {}),
         ];
 
@@ -1,6 +1,9 @@
 pub mod analyzer;
+pub mod influxql;
 pub mod logical;
 pub mod optimizer;
+pub mod param;
 pub mod parser;
 pub mod physical;
 pub mod planner;
+pub mod substrait;
@@ -371,11 +371,13 @@ mod test {
             test_plan(
                 plan,
                 "\
-                Aggregate: groupBy=[[]], aggr=[[MAX(?table?.value)]]\
-                \n  TableScan: ?table? projection=[value]",
+                Projection: MAX(MAX(?table?.value)) AS MAX(?table?.value)\
+                \n  Aggregate: groupBy=[[]], aggr=[[MAX(MAX(?table?.value))]]\
+                \n    TableScan: ?table?, grouping=[], agg=[AggregateFunction(AggregateFunction { fun: Max, args: [Column(Column { relation: Some(Bare { table: \"?table?\" }), name: \"value\" })], distinct: false, filter: None, order_by: None })]",
                 "\
-                AggregateExec: mode=Single, gby=[], aggr=[MAX(?table?.value)]\
-                \n  EmptyExec: produce_one_row=false\
+                ProjectionExec: expr=[MAX(MAX(?table?.value))@0 as MAX(?table?.value)]\
+                \n  AggregateExec: mode=Single, gby=[], aggr=[MAX(MAX(?table?.value))]\
+                \n    EmptyExec: produce_one_row=false\
                 \n",
             )
             .await?;
@@ -388,13 +390,15 @@ mod test {
         test_plan(
             plan,
             "\
-            Aggregate: groupBy=[[]], aggr=[[MAX(?table?.value)]]\
-            \n  TableScan: ?table? projection=[value]",
+            Projection: MAX(MAX(?table?.value)) AS MAX(?table?.value)\
+            \n  Aggregate: groupBy=[[]], aggr=[[MAX(MAX(?table?.value))]]\
+            \n    TableScan: ?table?, grouping=[], agg=[AggregateFunction(AggregateFunction { fun: Max, args: [Column(Column { relation: Some(Bare { table: \"?table?\" }), name: \"value\" })], distinct: false, filter: None, order_by: None })]",
             "\
-            AggregateExec: mode=Final, gby=[], aggr=[MAX(?table?.value)]\
-            \n  CoalescePartitionsExec\
-            \n    AggregateExec: mode=Partial, gby=[], aggr=[MAX(?table?.value)]\
-            \n      TskvExec: limit=None, predicate=ColumnDomains { column_to_domain: Some({}) }, split_num=8, projection=[value]\
+            ProjectionExec: expr=[MAX(MAX(?table?.value))@0 as MAX(?table?.value)]\
+            \n  AggregateExec: mode=Final, gby=[], aggr=[MAX(MAX(?table?.value))]\
+            \n    CoalescePartitionsExec\
+            \n      AggregateExec: mode=Partial, gby=[], aggr=[MAX(MAX(?table?.value))]\
+            \n        AggregateFilterTskvExec: agg=[[Max(\"value\")]], filter=[Predicate { pushed_down_domains: ColumnDomains { column_to_domain: Some({}) }, limit: None }]\
             \n",
         ).await
     }
@@ -451,11 +455,13 @@ mod test {
             test_plan(
                 plan,
                 "\
-                Aggregate: groupBy=[[]], aggr=[[MIN(?table?.value)]]\
-                \n  TableScan: ?table? projection=[value]",
+                Projection: MIN(MIN(?table?.value)) AS MIN(?table?.value)\
+                \n  Aggregate: groupBy=[[]], aggr=[[MIN(MIN(?table?.value))]]\
+                \n    TableScan: ?table?, grouping=[], agg=[AggregateFunction(AggregateFunction { fun: Min, args: [Column(Column { relation: Some(Bare { table: \"?table?\" }), name: \"value\" })], distinct: false, filter: None, order_by: None })]",
                 "\
-                AggregateExec: mode=Single, gby=[], aggr=[MIN(?table?.value)]\
-                \n  EmptyExec: produce_one_row=false\
+                ProjectionExec: expr=[MIN(MIN(?table?.value))@0 as MIN(?table?.value)]\
+                \n  AggregateExec: mode=Single, gby=[], aggr=[MIN(MIN(?table?.value))]\
+                \n    EmptyExec: produce_one_row=false\
                 \n",
             )
             .await?;
@@ -468,13 +474,15 @@ mod test {
         test_plan(
             plan,
             "\
-            Aggregate: groupBy=[[]], aggr=[[MIN(?table?.value)]]\
-            \n  TableScan: ?table? projection=[value]",
+            Projection: MIN(MIN(?table?.value)) AS MIN(?table?.value)\
+            \n  Aggregate: groupBy=[[]], aggr=[[MIN(MIN(?table?.value))]]\
+            \n    TableScan: ?table?, grouping=[], agg=[AggregateFunction(AggregateFunction { fun: Min, args: [Column(Column { relation: Some(Bare { table: \"?table?\" }), name: \"value\" })], distinct: false, filter: None, order_by: None })]",
             "\
-            AggregateExec: mode=Final, gby=[], aggr=[MIN(?table?.value)]\
-            \n  CoalescePartitionsExec\
-            \n    AggregateExec: mode=Partial, gby=[], aggr=[MIN(?table?.value)]\
-            \n      TskvExec: limit=None, predicate=ColumnDomains { column_to_domain: Some({}) }, split_num=8, projection=[value]\
+            ProjectionExec: expr=[MIN(MIN(?table?.value))@0 as MIN(?table?.value)]\
+            \n  AggregateExec: mode=Final, gby=[], aggr=[MIN(MIN(?table?.value))]\
+            \n    CoalescePartitionsExec\
+            \n      AggregateExec: mode=Partial, gby=[], aggr=[MIN(MIN(?table?.value))]\
+            \n        AggregateFilterTskvExec: agg=[[Min(\"value\")]], filter=[Predicate { pushed_down_domains: ColumnDomains { column_to_domain: Some({}) }, limit: None }]\
             \n",
         ).await
     }
@@ -531,11 +539,13 @@ mod test {
             test_plan(
                 plan,
                 "\
-                Aggregate: groupBy=[[]], aggr=[[SUM(?table?.value)]]\
-                \n  TableScan: ?table? projection=[value]",
+                Projection: SUM(SUM(?table?.value)) AS SUM(?table?.value)\
+                \n  Aggregate: groupBy=[[]], aggr=[[SUM(SUM(?table?.value))]]\
+                \n    TableScan: ?table?, grouping=[], agg=[AggregateFunction(AggregateFunction { fun: Sum, args: [Column(Column { relation: Some(Bare { table: \"?table?\" }), name: \"value\" })], distinct: false, filter: None, order_by: None })]",
                 "\
-                AggregateExec: mode=Single, gby=[], aggr=[SUM(?table?.value)]\
-                \n  EmptyExec: produce_one_row=false\
+                ProjectionExec: expr=[SUM(SUM(?table?.value))@0 as SUM(?table?.value)]\
+                \n  AggregateExec: mode=Single, gby=[], aggr=[SUM(SUM(?table?.value))]\
+                \n    EmptyExec: produce_one_row=false\
                 \n",
             )
             .await?;
@@ -548,13 +558,15 @@ mod test {
         test_plan(
             plan,
             "\
-            Aggregate: groupBy=[[]], aggr=[[SUM(?table?.value)]]\
-            \n  TableScan: ?table? projection=[value]",
+            Projection: SUM(SUM(?table?.value)) AS SUM(?table?.value)\
+            \n  Aggregate: groupBy=[[]], aggr=[[SUM(SUM(?table?.value))]]\
+            \n    TableScan: ?table?, grouping=[], agg=[AggregateFunction(AggregateFunction { fun: Sum, args: [Column(Column { relation: Some(Bare { table: \"?table?\" }), name: \"value\" })], distinct: false, filter: None, order_by: None })]",
             "\
-            AggregateExec: mode=Final, gby=[], aggr=[SUM(?table?.value)]\
-            \n  CoalescePartitionsExec\
-            \n    AggregateExec: mode=Partial, gby=[], aggr=[SUM(?table?.value)]\
-            \n      TskvExec: limit=None, predicate=ColumnDomains { column_to_domain: Some({}) }, split_num=8, projection=[value]\
+            ProjectionExec: expr=[SUM(SUM(?table?.value))@0 as SUM(?table?.value)]\
+            \n  AggregateExec: mode=Final, gby=[], aggr=[SUM(SUM(?table?.value))]\
+            \n    CoalescePartitionsExec\
+            \n      AggregateExec: mode=Partial, gby=[], aggr=[SUM(SUM(?table?.value))]\
+            \n        AggregateFilterTskvExec: agg=[[Sum(\"value\")]], filter=[Predicate { pushed_down_domains: ColumnDomains { column_to_domain: Some({}) }, limit: None }]\
             \n",
         ).await
     }
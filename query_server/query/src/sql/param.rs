@@ -0,0 +1,240 @@
+//! Parameters of sql statements.
+//!
+//! Placeholders are positional (`$1, $2...`), named (`$name`) or anonymous (`?`), the planner
+//! only knows positional ones, so the others are rewritten to positional ones before planning.
+
+use std::collections::HashMap;
+
+use datafusion::scalar::ScalarValue;
+use datafusion::sql::sqlparser::dialect::GenericDialect;
+use datafusion::sql::sqlparser::tokenizer::{Token, TokenWithLocation, Tokenizer};
+use spi::{QueryError, Result};
+
+/// Values of the placeholders of a statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValues {
+    /// Values of `$1, $2...` in order.
+    Positional(Vec<ScalarValue>),
+    /// Values of `$name` by name, without the `$` prefix.
+    Named(HashMap<String, ScalarValue>),
+}
+
+impl Default for ParamValues {
+    fn default() -> Self {
+        Self::Positional(vec![])
+    }
+}
+
+impl ParamValues {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Positional(values) => values.is_empty(),
+            Self::Named(values) => values.is_empty(),
+        }
+    }
+
+    /// Values in position order, `names` are the names of the placeholders
+    /// at each position returned by [`rewrite_named_placeholders`].
+    pub fn into_positional(self, names: &[String]) -> Result<Vec<ScalarValue>> {
+        match self {
+            Self::Positional(values) => {
+                if !names.is_empty() && !values.is_empty() {
+                    return Err(QueryError::Semantic {
+                        err: "named placeholders require named parameters".to_string(),
+                    });
+                }
+                Ok(values)
+            }
+            Self::Named(mut values) => {
+                if names.is_empty() && !values.is_empty() {
+                    return Err(QueryError::Semantic {
+                        err: "positional placeholders require positional parameters".to_string(),
+                    });
+                }
+                names
+                    .iter()
+                    .map(|name| {
+                        values.remove(name).ok_or_else(|| QueryError::Semantic {
+                            err: format!("no value for placeholder ${name}"),
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Rewrite the named placeholders of the sql to positional ones.
+///
+/// Returns the rewritten sql and the names of the placeholders in position order,
+/// the names are empty if the sql has no named placeholder.
+/// A name used more than once shares the same position.
+pub fn rewrite_named_placeholders(sql: &str) -> Result<(String, Vec<String>)> {
+    let mut names: Vec<String> = vec![];
+    let mut has_positional = false;
+    let rewritten = rewrite_placeholders(sql, |placeholder| {
+        let name = placeholder.strip_prefix('$')?;
+        if name.parse::<usize>().is_ok() {
+            has_positional = true;
+            return None;
+        }
+        let position = match names.iter().position(|n| n == name) {
+            Some(idx) => idx + 1,
+            None => {
+                names.push(name.to_string());
+                names.len()
+            }
+        };
+        Some(position)
+    })?;
+
+    if has_positional && !names.is_empty() {
+        return Err(QueryError::Semantic {
+            err: "can't mix positional and named placeholders".to_string(),
+        });
+    }
+
+    Ok((rewritten, names))
+}
+
+/// Rewrite the anonymous placeholders `?` (used by jdbc and odbc) of the sql
+/// to positional ones in the order they appear.
+pub fn rewrite_question_mark_placeholders(sql: &str) -> Result<String> {
+    let mut count = 0;
+    let mut has_positional = false;
+    let rewritten = rewrite_placeholders(sql, |placeholder| {
+        if placeholder != "?" {
+            has_positional = true;
+            return None;
+        }
+        count += 1;
+        Some(count)
+    })?;
+
+    if has_positional && count > 0 {
+        return Err(QueryError::Semantic {
+            err: "can't mix anonymous placeholders with other placeholders".to_string(),
+        });
+    }
+
+    Ok(rewritten)
+}
+
+/// Replace each placeholder token of the sql with `$<position>`,
+/// `position_of` returns None for placeholders to keep as is.
+fn rewrite_placeholders(
+    sql: &str,
+    mut position_of: impl FnMut(&str) -> Option<usize>,
+) -> Result<String> {
+    let dialect = GenericDialect {};
+    let tokens = Tokenizer::new(&dialect, sql)
+        .tokenize_with_location()
+        .map_err(|e| QueryError::Semantic { err: e.to_string() })?;
+
+    // byte offset of the start of each line
+    let mut line_offsets = vec![0];
+    line_offsets.extend(sql.match_indices('\n').map(|(i, _)| i + 1));
+
+    let mut rewritten = String::with_capacity(sql.len());
+    let mut copied = 0;
+    for TokenWithLocation { token, location } in tokens {
+        let placeholder = match &token {
+            Token::Placeholder(p) => p,
+            _ => continue,
+        };
+        let position = match position_of(placeholder) {
+            Some(position) => position,
+            None => continue,
+        };
+
+        // locations are 1-based lines and columns in chars
+        let line_offset = line_offsets[location.line as usize - 1];
+        let start = line_offset
+            + sql[line_offset..]
+                .chars()
+                .take(location.column as usize - 1)
+                .map(char::len_utf8)
+                .sum::<usize>();
+        rewritten.push_str(&sql[copied..start]);
+        rewritten.push_str(&format!("${position}"));
+        copied = start + placeholder.len();
+    }
+    rewritten.push_str(&sql[copied..]);
+
+    Ok(rewritten)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use datafusion::scalar::ScalarValue;
+
+    use super::{rewrite_named_placeholders, rewrite_question_mark_placeholders, ParamValues};
+
+    #[test]
+    fn test_rewrite_named_placeholders() {
+        let sql =
+            "SELECT * FROM cpu\nWHERE host = $host AND usage > $min AND host != 'it''s $host'";
+        let (rewritten, names) = rewrite_named_placeholders(sql).unwrap();
+        assert_eq!(
+            rewritten,
+            "SELECT * FROM cpu\nWHERE host = $1 AND usage > $2 AND host != 'it''s $host'"
+        );
+        assert_eq!(names, vec!["host".to_string(), "min".to_string()]);
+
+        let (rewritten, names) = rewrite_named_placeholders("SELECT $a, $b, $a FROM cpu").unwrap();
+        assert_eq!(rewritten, "SELECT $1, $2, $1 FROM cpu");
+        assert_eq!(names.len(), 2);
+
+        let sql = "SELECT * FROM cpu WHERE usage > $1";
+        let (rewritten, names) = rewrite_named_placeholders(sql).unwrap();
+        assert_eq!(rewritten, sql);
+        assert!(names.is_empty());
+
+        assert!(rewrite_named_placeholders("SELECT $1, $a FROM cpu").is_err());
+    }
+
+    #[test]
+    fn test_rewrite_question_mark_placeholders() {
+        let sql = "SELECT * FROM cpu WHERE host = ? AND usage > ? AND host != '?'";
+        assert_eq!(
+            rewrite_question_mark_placeholders(sql).unwrap(),
+            "SELECT * FROM cpu WHERE host = $1 AND usage > $2 AND host != '?'"
+        );
+
+        let sql = "INSERT INTO cpu (time, host, usage) VALUES ($1, $2, $3)";
+        assert_eq!(rewrite_question_mark_placeholders(sql).unwrap(), sql);
+
+        assert!(rewrite_question_mark_placeholders("SELECT ?, $1 FROM cpu").is_err());
+    }
+
+    #[test]
+    fn test_param_values_into_positional() {
+        let names = vec!["host".to_string(), "min".to_string()];
+        let values = ParamValues::Named(HashMap::from([
+            ("min".to_string(), ScalarValue::Float64(Some(0.5))),
+            ("host".to_string(), ScalarValue::Utf8(Some("a".to_string()))),
+        ]));
+        assert_eq!(
+            values.into_positional(&names).unwrap(),
+            vec![
+                ScalarValue::Utf8(Some("a".to_string())),
+                ScalarValue::Float64(Some(0.5))
+            ]
+        );
+
+        let values = ParamValues::Named(HashMap::from([(
+            "host".to_string(),
+            ScalarValue::Utf8(Some("a".to_string())),
+        )]));
+        assert!(values.into_positional(&names).is_err());
+
+        let values = ParamValues::Positional(vec![ScalarValue::Int64(Some(1))]);
+        assert!(values.clone().into_positional(&names).is_err());
+        assert_eq!(
+            values.into_positional(&[]).unwrap(),
+            vec![ScalarValue::Int64(Some(1))]
+        );
+    }
+}
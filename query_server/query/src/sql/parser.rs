@@ -6,7 +6,7 @@ use std::str::FromStr;
 use datafusion::common::parsers::CompressionTypeVariant;
 use datafusion::sql::parser::CreateExternalTable;
 use datafusion::sql::sqlparser::ast::{
-    DataType, Expr, Ident, ObjectName, Offset, OrderByExpr, SqlOption, TableFactor,
+    DataType, Expr, Ident, ObjectName, Offset, OrderByExpr, SqlOption, Statement, TableFactor,
 };
 use datafusion::sql::sqlparser::dialect::keywords::Keyword;
 use datafusion::sql::sqlparser::dialect::{Dialect, GenericDialect};
@@ -17,11 +17,12 @@ use models::meta_data::{NodeId, ReplicationSetId, VnodeId};
 use snafu::ResultExt;
 use spi::query::ast::{
     self, parse_string_value, Action, AlterDatabase, AlterTable, AlterTableAction, AlterTenant,
-    AlterTenantOperation, AlterUser, AlterUserOperation, ChecksumGroup, ColumnOption, CompactVnode,
-    CopyIntoLocation, CopyIntoTable, CopyTarget, CopyVnode, CreateDatabase, CreateRole,
-    CreateStream, CreateTable, CreateTenant, CreateUser, DatabaseOptions, DescribeDatabase,
-    DescribeTable, DropDatabaseObject, DropGlobalObject, DropTenantObject, DropVnode, Explain,
-    ExtStatement, GrantRevoke, MoveVnode, OutputMode, Privilege, RecoverDatabase, RecoverTenant,
+    AlterTenantOperation, AlterUser, AlterUserOperation, BackupDatabase, ChecksumGroup,
+    ColumnOption, CompactVnode, CopyIntoLocation, CopyIntoTable, CopyTarget, CopyVnode,
+    CreateDatabase, CreateRole, CreateRollupPolicy, CreateStream, CreateTable, CreateTenant,
+    CreateUser, DatabaseOptions, DescribeDatabase, DescribeTable, DropDatabaseObject,
+    DropGlobalObject, DropTenantObject, DropVnode, Explain, ExtStatement, GrantRevoke, MoveVnode,
+    OutputMode, Privilege, RecoverDatabase, RecoverTenant, RestoreDatabase, RollupAggregateExpr,
     ShowSeries, ShowTagBody, ShowTagValues, Trigger, UriLocation, With,
 };
 use spi::query::logical_planner::{DatabaseObjectType, GlobalObjectType, TenantObjectType};
@@ -56,6 +57,12 @@ enum CnosKeyWord {
     REPLICA,
     #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
     PRECISION,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    COLD_DURATION,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    COMPACTION_STRATEGY,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    COMPACTION_WINDOW,
 
     #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
     QUERIES,
@@ -112,6 +119,18 @@ enum CnosKeyWord {
     AFTER,
     #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
     RECOVER,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    ROLLUP,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    POLICY,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    AGGREGATES,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    BACKUP,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    RESTORE,
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    INCREMENTAL,
 }
 
 impl FromStr for CnosKeyWord {
@@ -127,6 +146,9 @@ impl FromStr for CnosKeyWord {
             "VNODE_DURATION" => Ok(CnosKeyWord::VNODE_DURATION),
             "REPLICA" => Ok(CnosKeyWord::REPLICA),
             "PRECISION" => Ok(CnosKeyWord::PRECISION),
+            "COLD_DURATION" => Ok(CnosKeyWord::COLD_DURATION),
+            "COMPACTION_STRATEGY" => Ok(CnosKeyWord::COMPACTION_STRATEGY),
+            "COMPACTION_WINDOW" => Ok(CnosKeyWord::COMPACTION_WINDOW),
             "DATABASES" => Ok(CnosKeyWord::DATABASES),
             "QUERIES" => Ok(CnosKeyWord::QUERIES),
             "TENANT" => Ok(CnosKeyWord::TENANT),
@@ -155,6 +177,12 @@ impl FromStr for CnosKeyWord {
             "UNSET" => Ok(CnosKeyWord::UNSET),
             "AFTER" => Ok(CnosKeyWord::AFTER),
             "RECOVER" => Ok(CnosKeyWord::RECOVER),
+            "ROLLUP" => Ok(CnosKeyWord::ROLLUP),
+            "POLICY" => Ok(CnosKeyWord::POLICY),
+            "AGGREGATES" => Ok(CnosKeyWord::AGGREGATES),
+            "BACKUP" => Ok(CnosKeyWord::BACKUP),
+            "RESTORE" => Ok(CnosKeyWord::RESTORE),
+            "INCREMENTAL" => Ok(CnosKeyWord::INCREMENTAL),
             _ => Err(ParserError::ParserError(format!(
                 "fail parse {} to CnosKeyWord",
                 s
@@ -302,6 +330,14 @@ impl<'a> ExtParser<'a> {
                                 self.parser.next_token();
                                 self.parse_recover()
                             }
+                            CnosKeyWord::BACKUP => {
+                                self.parser.next_token();
+                                self.parse_backup()
+                            }
+                            CnosKeyWord::RESTORE => {
+                                self.parser.next_token();
+                                self.parse_restore()
+                            }
                             _ => Ok(ExtStatement::SqlStatement(Box::new(
                                 self.parser.parse_statement()?,
                             ))),
@@ -899,6 +935,12 @@ impl<'a> ExtParser<'a> {
             options.replica = Some(self.parse_number::<u64>()?);
         } else if self.parse_cnos_keyword(CnosKeyWord::PRECISION) {
             options.precision = Some(self.parse_string_value()?);
+        } else if self.parse_cnos_keyword(CnosKeyWord::COLD_DURATION) {
+            options.cold_duration = Some(self.parse_string_value()?);
+        } else if self.parse_cnos_keyword(CnosKeyWord::COMPACTION_STRATEGY) {
+            options.compaction_strategy = Some(self.parse_string_value()?);
+        } else if self.parse_cnos_keyword(CnosKeyWord::COMPACTION_WINDOW) {
+            options.compaction_window = Some(self.parse_string_value()?);
         } else {
             return Ok(false);
         }
@@ -1191,6 +1233,56 @@ impl<'a> ExtParser<'a> {
         }))
     }
 
+    fn parse_create_rollup_policy(&mut self) -> Result<ExtStatement> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+
+        self.parser.expect_keyword(Keyword::ON)?;
+        let source_table = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::INTO)?;
+        let target_table = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::INTERVAL)?;
+        let interval = self.parse_string_value()?;
+
+        self.expect_cnos_keyword(CnosKeyWord::AGGREGATES)?;
+        self.parser.expect_token(&Token::LParen)?;
+        let aggregates = self.parser.parse_comma_separated(|parser| {
+            let function = parser.parse_identifier()?;
+            parser.expect_token(&Token::LParen)?;
+            let field = parser.parse_identifier()?;
+            parser.expect_token(&Token::RParen)?;
+            let alias = if parser.parse_keyword(Keyword::AS) {
+                Some(parser.parse_identifier()?)
+            } else {
+                None
+            };
+            Ok(RollupAggregateExpr {
+                function,
+                field,
+                alias,
+            })
+        })?;
+        self.parser.expect_token(&Token::RParen)?;
+
+        let ttl = if self.parse_cnos_keyword(CnosKeyWord::TTL) {
+            Some(self.parse_string_value()?)
+        } else {
+            None
+        };
+
+        Ok(ExtStatement::CreateRollupPolicy(CreateRollupPolicy {
+            if_not_exists,
+            name,
+            source_table,
+            target_table,
+            interval,
+            aggregates,
+            ttl,
+        }))
+    }
+
     /// Parse a SQL CREATE statement
     fn parse_create(&mut self) -> Result<ExtStatement> {
         // Currently only supports the creation of external tables
@@ -1211,11 +1303,68 @@ impl<'a> ExtParser<'a> {
             self.parse_create_role()
         } else if self.parse_cnos_keyword(CnosKeyWord::STREAM) {
             self.parse_create_stream()
+        } else if self.parse_cnos_keyword(CnosKeyWord::ROLLUP) {
+            self.expect_cnos_keyword(CnosKeyWord::POLICY)?;
+            self.parse_create_rollup_policy()
+        } else if self.parser.parse_keyword(Keyword::FUNCTION) {
+            self.parse_create_function()
+        } else if self.parser.parse_keywords(&[Keyword::OR, Keyword::REPLACE]) {
+            self.parse_create_view(true)
+        } else if matches!(self.parser.peek_token().token, Token::Word(ref w) if w.keyword == Keyword::VIEW)
+        {
+            self.parse_create_view(false)
         } else {
             self.expected("an object type after CREATE", self.parser.peek_token())
         }
     }
 
+    /// e.g.
+    /// CREATE FUNCTION [IF NOT EXISTS] c_to_f(c DOUBLE) RETURNS DOUBLE AS c * 1.8 + 32
+    fn parse_create_function(&mut self) -> Result<ExtStatement> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+
+        self.parser.expect_token(&Token::LParen)?;
+        let args = if self.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let args = self.parse_comma_separated(|parser| {
+                let name = parser.parser.parse_identifier()?;
+                let data_type = parser.parser.parse_data_type()?;
+                Ok((name, data_type))
+            })?;
+            self.parser.expect_token(&Token::RParen)?;
+            args
+        };
+
+        self.parser.expect_keyword(Keyword::RETURNS)?;
+        let return_type = self.parser.parse_data_type()?;
+        self.parser.expect_keyword(Keyword::AS)?;
+        let body = self.parser.parse_expr()?;
+
+        Ok(ExtStatement::CreateFunction(ast::CreateFunction {
+            if_not_exists,
+            name,
+            args,
+            return_type,
+            body,
+        }))
+    }
+
+    /// e.g.
+    /// CREATE [OR REPLACE] VIEW db.v AS SELECT ...
+    fn parse_create_view(&mut self, or_replace: bool) -> Result<ExtStatement> {
+        let view = self.parser.parse_create_view(or_replace)?;
+        if let Statement::CreateView { materialized, .. } = &view {
+            if *materialized {
+                return parser_err!("Materialized view is not supported");
+            }
+        }
+        Ok(ExtStatement::SqlStatement(Box::new(view)))
+    }
+
     /// Parse a copy statement
     fn parse_copy(&mut self) -> Result<ExtStatement> {
         if self.parse_cnos_keyword(CnosKeyWord::VNODE) {
@@ -1413,6 +1562,48 @@ impl<'a> ExtParser<'a> {
         Ok(ast)
     }
 
+    fn parse_backup(&mut self) -> Result<ExtStatement> {
+        self.parser.expect_keyword(Keyword::DATABASE)?;
+        let database_name = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::TO)?;
+        let path = self.parser.parse_literal_string()?;
+        let incremental = self.parse_cnos_keyword(CnosKeyWord::INCREMENTAL);
+        let connection_options = if self.parser.parse_keyword(Keyword::CONNECTION) {
+            self.parse_options()?
+        } else {
+            Default::default()
+        };
+
+        Ok(ExtStatement::BackupDatabase(BackupDatabase {
+            database_name,
+            location: UriLocation {
+                path,
+                connection_options,
+            },
+            incremental,
+        }))
+    }
+
+    fn parse_restore(&mut self) -> Result<ExtStatement> {
+        self.parser.expect_keyword(Keyword::DATABASE)?;
+        let database_name = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let path = self.parser.parse_literal_string()?;
+        let connection_options = if self.parser.parse_keyword(Keyword::CONNECTION) {
+            self.parse_options()?
+        } else {
+            Default::default()
+        };
+
+        Ok(ExtStatement::RestoreDatabase(RestoreDatabase {
+            database_name,
+            location: UriLocation {
+                path,
+                connection_options,
+            },
+        }))
+    }
+
     /// Parse a SQL DROP statement
     fn parse_drop(&mut self) -> Result<ExtStatement> {
         let ast = if self.parser.parse_keyword(Keyword::TABLE) {
@@ -1423,6 +1614,14 @@ impl<'a> ExtParser<'a> {
                 if_exist,
                 obj_type: DatabaseObjectType::Table,
             })
+        } else if self.parser.parse_keyword(Keyword::VIEW) {
+            let if_exist = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+            let object_name = self.parser.parse_object_name()?;
+            ExtStatement::DropDatabaseObject(DropDatabaseObject {
+                object_name,
+                if_exist,
+                obj_type: DatabaseObjectType::View,
+            })
         } else if self.parser.parse_keyword(Keyword::DATABASE) {
             let if_exist = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
             let object_name = self.parser.parse_identifier()?;
@@ -1474,9 +1673,18 @@ impl<'a> ExtParser<'a> {
             let if_exist = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
             let name = self.parser.parse_identifier()?;
             ExtStatement::DropStream(ast::DropStream { if_exist, name })
+        } else if self.parse_cnos_keyword(CnosKeyWord::ROLLUP) {
+            self.expect_cnos_keyword(CnosKeyWord::POLICY)?;
+            let if_exist = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+            let name = self.parser.parse_identifier()?;
+            ExtStatement::DropRollupPolicy(ast::DropRollupPolicy { if_exist, name })
+        } else if self.parser.parse_keyword(Keyword::FUNCTION) {
+            let if_exist = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+            let name = self.parser.parse_identifier()?;
+            ExtStatement::DropFunction(ast::DropFunction { if_exist, name })
         } else {
             return self.expected(
-                "TABLE,DATABASE,TENANT,USER,ROLE,VNODE,STREAM after DROP",
+                "TABLE,VIEW,DATABASE,TENANT,USER,ROLE,VNODE,STREAM,ROLLUP POLICY,FUNCTION after DROP",
                 self.parser.peek_token(),
             );
         };
@@ -1981,6 +2189,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_table_dictionary_codec() {
+        let sql = "CREATE TABLE test(status STRING CODEC(DICTIONARY), TAGS(host))";
+        let statements = ExtParser::parse_sql(sql).unwrap();
+        match &statements[0] {
+            ExtStatement::CreateTable(CreateTable { columns, .. }) => {
+                assert_eq!(
+                    columns[1],
+                    ColumnOption {
+                        name: Ident::from("status"),
+                        is_tag: false,
+                        data_type: DataType::String,
+                        encoding: Some(Encoding::Dictionary)
+                    }
+                );
+            }
+            _ => panic!("failed"),
+        }
+    }
+
+    #[test]
+    fn test_create_table_float_codec() {
+        let sql = "CREATE TABLE test(a DOUBLE CODEC(CHIMP), b DOUBLE CODEC(alp), TAGS(host))";
+        let statements = ExtParser::parse_sql(sql).unwrap();
+        match &statements[0] {
+            ExtStatement::CreateTable(CreateTable { columns, .. }) => {
+                assert_eq!(
+                    columns[..],
+                    [
+                        ColumnOption {
+                            name: Ident::from("host"),
+                            is_tag: true,
+                            data_type: DataType::String,
+                            encoding: None
+                        },
+                        ColumnOption {
+                            name: Ident::from("a"),
+                            is_tag: false,
+                            data_type: DataType::Double,
+                            encoding: Some(Encoding::Chimp)
+                        },
+                        ColumnOption {
+                            name: Ident::from("b"),
+                            is_tag: false,
+                            data_type: DataType::Double,
+                            encoding: Some(Encoding::Alp)
+                        }
+                    ]
+                );
+            }
+            _ => panic!("failed"),
+        }
+    }
+
     #[test]
     fn test_insert_values() {
         let sql = "insert public.test(TIME, ta, tb, fa, fb)
@@ -2115,12 +2377,26 @@ mod tests {
             ExtStatement::CreateDatabase(ref stmt) => {
                 let ans = format!("{:?}", stmt);
                 println!("{ans}");
-                let expectd = "CreateDatabase { name: Ident { value: \"test\", quote_style: None }, if_not_exists: false, options: DatabaseOptions { ttl: Some(\"10d\"), shard_num: Some(5), vnode_duration: Some(\"3d\"), replica: Some(10), precision: Some(\"us\") } }";
+                let expectd = "CreateDatabase { name: Ident { value: \"test\", quote_style: None }, if_not_exists: false, options: DatabaseOptions { ttl: Some(\"10d\"), shard_num: Some(5), vnode_duration: Some(\"3d\"), replica: Some(10), precision: Some(\"us\"), cold_duration: None, compaction_strategy: None, compaction_window: None } }";
                 assert_eq!(ans, expectd);
             }
             _ => panic!("impossible"),
         }
     }
+
+    #[test]
+    fn test_alter_database_cold_duration() {
+        let sql = "ALTER DATABASE test SET COLD_DURATION '30d';";
+        let statements = ExtParser::parse_sql(sql).unwrap();
+        assert_eq!(statements.len(), 1);
+        match statements[0] {
+            ExtStatement::AlterDatabase(ref stmt) => {
+                assert_eq!(stmt.options.cold_duration, Some("30d".to_string()));
+            }
+            _ => panic!("impossible"),
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_create_table_without_fields() {
@@ -2386,6 +2662,134 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn test_create_rollup_policy() {
+        let result = parse_sql(
+            "create rollup policy if not exists cpu_1m on cpu into cpu_1m interval '1m' \
+            aggregates (avg(usage), max(usage) as usage_max, last(status)) ttl '30d';",
+        );
+
+        let expected = ExtStatement::CreateRollupPolicy(ast::CreateRollupPolicy {
+            if_not_exists: true,
+            name: Ident::new("cpu_1m"),
+            source_table: Ident::new("cpu"),
+            target_table: Ident::new("cpu_1m"),
+            interval: "1m".to_string(),
+            aggregates: vec![
+                RollupAggregateExpr {
+                    function: Ident::new("avg"),
+                    field: Ident::new("usage"),
+                    alias: None,
+                },
+                RollupAggregateExpr {
+                    function: Ident::new("max"),
+                    field: Ident::new("usage"),
+                    alias: Some(Ident::new("usage_max")),
+                },
+                RollupAggregateExpr {
+                    function: Ident::new("last"),
+                    field: Ident::new("status"),
+                    alias: None,
+                },
+            ],
+            ttl: Some("30d".to_string()),
+        });
+        assert_eq!(expected, result);
+
+        let result = parse_sql("drop rollup policy if exists cpu_1m;");
+        let expected = ExtStatement::DropRollupPolicy(ast::DropRollupPolicy {
+            if_exist: true,
+            name: Ident::new("cpu_1m"),
+        });
+        assert_eq!(expected, result);
+
+        assert!(ExtParser::parse_sql(
+            "create rollup policy p on cpu into cpu_1m interval '1m' aggregates ();"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_create_and_drop_function() {
+        let result = parse_sql(
+            "create function if not exists c_to_f(c double) returns double as c * 1.8 + 32;",
+        );
+        match result {
+            ExtStatement::CreateFunction(ast::CreateFunction {
+                if_not_exists,
+                name,
+                args,
+                return_type,
+                body,
+            }) => {
+                assert!(if_not_exists);
+                assert_eq!(name, Ident::new("c_to_f"));
+                assert_eq!(args, vec![(Ident::new("c"), DataType::Double)]);
+                assert_eq!(return_type, DataType::Double);
+                assert_eq!(body.to_string(), "c * 1.8 + 32");
+            }
+            _ => panic!("expect CreateFunction"),
+        }
+
+        let result = parse_sql("create function one() returns bigint as 1;");
+        assert!(matches!(
+            result,
+            ExtStatement::CreateFunction(ast::CreateFunction { ref args, .. }) if args.is_empty()
+        ));
+
+        assert!(ExtParser::parse_sql("create function f(a double) as a + 1;").is_err());
+
+        let result = parse_sql("drop function if exists c_to_f;");
+        let expected = ExtStatement::DropFunction(ast::DropFunction {
+            if_exist: true,
+            name: Ident::new("c_to_f"),
+        });
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_backup_and_restore_database() {
+        let result = parse_sql("backup database db1 to '/tmp/backup/db1';");
+        let expected = ExtStatement::BackupDatabase(ast::BackupDatabase {
+            database_name: Ident::new("db1"),
+            location: UriLocation {
+                path: "/tmp/backup/db1".to_string(),
+                connection_options: vec![],
+            },
+            incremental: false,
+        });
+        assert_eq!(expected, result);
+
+        let result = parse_sql(
+            "backup database db1 to 's3://bucket/db1' incremental connection = (region = 'us-east-1');",
+        );
+        let expected = ExtStatement::BackupDatabase(ast::BackupDatabase {
+            database_name: Ident::new("db1"),
+            location: UriLocation {
+                path: "s3://bucket/db1".to_string(),
+                connection_options: vec![SqlOption {
+                    name: "region".into(),
+                    value: Value::SingleQuotedString("us-east-1".to_string()),
+                }],
+            },
+            incremental: true,
+        });
+        assert_eq!(expected, result);
+
+        let result = parse_sql("restore database db2 from '/tmp/backup/db1';");
+        let expected = ExtStatement::RestoreDatabase(ast::RestoreDatabase {
+            database_name: Ident::new("db2"),
+            location: UriLocation {
+                path: "/tmp/backup/db1".to_string(),
+                connection_options: vec![],
+            },
+        });
+        assert_eq!(expected, result);
+
+        assert!(ExtParser::parse_sql("backup database db1 '/tmp/backup/db1';").is_err());
+        assert!(ExtParser::parse_sql("restore database db1 to '/tmp/backup/db1';").is_err());
+    }
+
     #[test]
     fn test_show_streams() {
         let result = parse_sql("show streams verbose;");
@@ -2457,6 +2861,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_and_drop_view() {
+        let statement = parse_sql("CREATE OR REPLACE VIEW db.v AS SELECT a FROM t WHERE a > 1;");
+        match statement {
+            ExtStatement::SqlStatement(s) => match *s {
+                Statement::CreateView {
+                    or_replace,
+                    name,
+                    query,
+                    ..
+                } => {
+                    assert!(or_replace);
+                    assert_eq!("db.v", &name.to_string());
+                    assert_eq!("SELECT a FROM t WHERE a > 1", &query.to_string());
+                }
+                _ => panic!("expect CreateView"),
+            },
+            _ => panic!("expect SqlStatement"),
+        }
+
+        let statement = parse_sql("CREATE VIEW v AS SELECT * FROM t;");
+        assert!(matches!(
+            statement,
+            ExtStatement::SqlStatement(s)
+                if matches!(*s, Statement::CreateView { or_replace: false, .. })
+        ));
+
+        assert!(ExtParser::parse_sql("CREATE MATERIALIZED VIEW v AS SELECT * FROM t").is_err());
+
+        let statement = parse_sql("DROP VIEW IF EXISTS db.v");
+        match statement {
+            ExtStatement::DropDatabaseObject(DropDatabaseObject {
+                object_name,
+                if_exist,
+                obj_type,
+            }) => {
+                assert_eq!("db.v", &object_name.to_string());
+                assert!(if_exist);
+                assert_eq!(DatabaseObjectType::View, obj_type);
+            }
+            _ => panic!("expect DropDatabaseObject"),
+        }
+    }
+
     #[test]
     fn test_alter_table_rename_column() {
         let statement = parse_sql("ALTER TABLE TskvTable RENAME COLUMN tag1 to tag2;");
@@ -1,11 +1,12 @@
 use std::collections::{HashMap, HashSet};
 use std::option::Option;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::{iter, vec};
 
 use async_recursion::async_recursion;
 use async_trait::async_trait;
-use datafusion::arrow::datatypes::{DataType, SchemaRef, TimeUnit};
+use datafusion::arrow::datatypes::{DataType, Schema, SchemaRef, TimeUnit};
 use datafusion::arrow::error::ArrowError;
 use datafusion::common::parsers::CompressionTypeVariant;
 use datafusion::common::tree_node::TreeNode;
@@ -29,9 +30,10 @@ use datafusion::logical_expr::expr_rewriter::rewrite_preserving_name;
 use datafusion::logical_expr::logical_plan::Analyze;
 use datafusion::logical_expr::utils::expr_to_columns;
 use datafusion::logical_expr::{
-    lit, BinaryExpr, BuiltinScalarFunction, Case, CreateExternalTable as PlanCreateExternalTable,
-    EmptyRelation, Explain, Expr, Extension, LogicalPlan, LogicalPlanBuilder, Operator, PlanType,
-    SubqueryAlias, TableSource, ToStringifiedPlan, Union,
+    lit, AggregateFunction, BinaryExpr, BuiltinScalarFunction, Case,
+    CreateExternalTable as PlanCreateExternalTable, EmptyRelation, Explain, Expr, Extension,
+    LogicalPlan, LogicalPlanBuilder, Operator, PlanType, SubqueryAlias, TableSource,
+    ToStringifiedPlan, Union,
 };
 use datafusion::optimizer::analyzer::type_coercion::TypeCoercionRewriter;
 use datafusion::optimizer::simplify_expressions::ConstEvaluator;
@@ -41,9 +43,9 @@ use datafusion::scalar::ScalarValue;
 use datafusion::sql::parser::CreateExternalTable as AstCreateExternalTable;
 use datafusion::sql::planner::{object_name_to_table_reference, PlannerContext, SqlToRel};
 use datafusion::sql::sqlparser::ast::{
-    Assignment, DataType as SQLDataType, Expr as SQLExpr, Expr as ASTExpr, Ident, ObjectName,
-    Offset, OrderByExpr, Query, SqlOption, Statement, TableAlias, TableFactor, TableWithJoins,
-    TimezoneInfo,
+    Assignment, ColumnDef, DataType as SQLDataType, ExactNumberInfo, Expr as SQLExpr,
+    Expr as ASTExpr, Ident, ObjectName, Offset, OrderByExpr, Query, SqlOption, Statement,
+    TableAlias, TableFactor, TableWithJoins, TimezoneInfo,
 };
 use datafusion::sql::sqlparser::parser::ParserError;
 use datafusion::sql::TableReference;
@@ -58,11 +60,12 @@ use models::gis::data_type::{Geometry, GeometryType};
 use models::object_reference::{Resolve, ResolvedTable};
 use models::oid::{Identifier, Oid};
 use models::schema::{
-    ColumnType, DatabaseOptions, Duration, Precision, TableColumn, Tenant, TskvTableSchema,
+    ColumnType, CompactionStrategy, DatabaseOptions, Duration, Precision, RollupAggregate,
+    RollupPolicy, RollupProgress, SqlFunction, TableColumn, Tenant, TskvTableSchema,
     TskvTableSchemaRef, Watermark, DEFAULT_CATALOG, TIME_FIELD,
 };
 use models::utils::SeqIdGenerator;
-use models::{ColumnId, ValueType};
+use models::{ColumnId, ValueType, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE};
 use object_store::ObjectStore;
 use spi::query::ast;
 use spi::query::ast::{
@@ -81,16 +84,19 @@ use spi::query::logical_planner::{
     sql_option_to_alter_tenant_action, sql_options_to_map, sql_options_to_tenant_options,
     sql_options_to_user_options, unset_option_to_alter_tenant_action, AlterDatabase, AlterTable,
     AlterTableAction, AlterTenant, AlterTenantAction, AlterTenantAddUser, AlterTenantSetUser,
-    AlterUser, AlterUserAction, ChecksumGroup, CompactVnode, CopyOptions, CopyOptionsBuilder,
-    CopyVnode, CreateDatabase, CreateRole, CreateStreamTable, CreateTable, CreateTenant,
-    CreateUser, DDLPlan, DMLPlan, DatabaseObjectType, DeleteFromTable, DropDatabaseObject,
-    DropGlobalObject, DropTenantObject, DropVnode, FileFormatOptions, FileFormatOptionsBuilder,
+    AlterUser, AlterUserAction, BackupDatabase, ChecksumGroup, CompactVnode, CopyOptions,
+    CopyOptionsBuilder, CopyVnode, CreateDatabase, CreateFunction, CreateRole, CreateRollupPolicy,
+    CreateStreamTable, CreateTable, CreateTenant, CreateUser, CreateView, DDLPlan, DMLPlan,
+    DatabaseObjectType, DeleteFromTable, DropDatabaseObject, DropFunction, DropGlobalObject,
+    DropRollupPolicy, DropTenantObject, DropVnode, FileFormatOptions, FileFormatOptionsBuilder,
     GlobalObjectType, GrantRevoke, LogicalPlanner, MoveVnode, Plan, PlanWithPrivileges, QueryPlan,
-    RecoverDatabase, RecoverTenant, SYSPlan, TenantObjectType, TENANT_OPTION_LIMITER,
+    RecoverDatabase, RecoverTenant, RestoreDatabase, SYSPlan, TenantObjectType,
+    TENANT_OPTION_LIMITER,
 };
 use spi::query::session::SessionCtx;
 use spi::{QueryError, Result};
 use trace::{debug, warn};
+use tskv::backup::BackupLocation;
 use url::Url;
 
 use crate::data_source::source_downcast_adapter;
@@ -98,13 +104,15 @@ use crate::data_source::stream::{get_event_time_column, get_watermark_delay};
 use crate::data_source::table_source::{TableHandle, TableSourceAdapter, TEMP_LOCATION_TABLE_NAME};
 use crate::extension::logical::logical_plan_builder::LogicalPlanBuilderExt;
 use crate::extension::logical::plan_node::update::UpdateNode;
+use crate::function::sql_function::{create_sql_udf, FunctionContextProvider};
 use crate::metadata::{
-    is_system_database, ContextProviderExtension, DatabaseSet, COLUMNS_COLUMN_NAME,
-    COLUMNS_COLUMN_TYPE, COLUMNS_COMPRESSION_CODEC, COLUMNS_DATABASE_NAME, COLUMNS_DATA_TYPE,
-    COLUMNS_TABLE_NAME, DATABASES_DATABASE_NAME, DATABASES_PRECISION, DATABASES_REPLICA,
-    DATABASES_SHARD, DATABASES_TTL, DATABASES_VNODE_DURATION, INFORMATION_SCHEMA,
-    INFORMATION_SCHEMA_COLUMNS, INFORMATION_SCHEMA_DATABASES, INFORMATION_SCHEMA_QUERIES,
-    INFORMATION_SCHEMA_TABLES, TABLES_TABLE_DATABASE, TABLES_TABLE_NAME,
+    is_system_database, ContextProviderExtension, DatabaseSet, ViewContextProvider,
+    COLUMNS_COLUMN_NAME, COLUMNS_COLUMN_TYPE, COLUMNS_COMPRESSION_CODEC, COLUMNS_DATABASE_NAME,
+    COLUMNS_DATA_TYPE, COLUMNS_TABLE_NAME, DATABASES_DATABASE_NAME, DATABASES_PRECISION,
+    DATABASES_REPLICA, DATABASES_SHARD, DATABASES_TTL, DATABASES_VNODE_DURATION,
+    INFORMATION_SCHEMA, INFORMATION_SCHEMA_COLUMNS, INFORMATION_SCHEMA_DATABASES,
+    INFORMATION_SCHEMA_QUERIES, INFORMATION_SCHEMA_TABLES, TABLES_TABLE_DATABASE,
+    TABLES_TABLE_NAME,
 };
 
 /// CnosDB SQL query planner
@@ -204,8 +212,16 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
             ExtStatement::CreateStreamTable(stmt) => {
                 self.create_stream_table_to_plan(stmt, session)
             }
+            ExtStatement::CreateRollupPolicy(stmt) => {
+                self.create_rollup_policy_to_plan(stmt, session)
+            }
+            ExtStatement::DropRollupPolicy(stmt) => self.drop_rollup_policy_to_plan(stmt, session),
+            ExtStatement::CreateFunction(stmt) => self.create_function_to_plan(stmt, session),
+            ExtStatement::DropFunction(stmt) => self.drop_function_to_plan(stmt, session),
             ExtStatement::RecoverTenant(stmt) => self.recovertenant_to_plan(stmt),
             ExtStatement::RecoverDatabase(stmt) => self.recoverdatabase_to_plan(stmt, session),
+            ExtStatement::BackupDatabase(stmt) => self.backup_database_to_plan(stmt, session),
+            ExtStatement::RestoreDatabase(stmt) => self.restore_database_to_plan(stmt, session),
         }
     }
 
@@ -265,9 +281,25 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
 
                 self.delete_to_plan(session, from, selection)
             }
+            Statement::CreateView {
+                or_replace,
+                name,
+                columns,
+                query,
+                with_options,
+                ..
+            } => {
+                if !columns.is_empty() || !with_options.is_empty() {
+                    return Err(QueryError::NotImplemented {
+                        err: "Create view with columns or options".to_string(),
+                    });
+                }
+                self.create_view_to_plan(or_replace, name, *query, session)
+            }
             Statement::Kill { id, .. } => {
                 let plan = Plan::SYSTEM(SYSPlan::KillQuery(id.into()));
-                // TODO privileges
+                // the owner of the query is only known when executing,
+                // privileges are checked by KillQueryTask
                 Ok(PlanWithPrivileges {
                     plan,
                     privileges: vec![],
@@ -553,6 +585,45 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
         })
     }
 
+    fn create_view_to_plan(
+        &self,
+        or_replace: bool,
+        name: ObjectName,
+        query: Query,
+        session: &SessionCtx,
+    ) -> Result<PlanWithPrivileges> {
+        let view = object_name_to_resolved_table(session, name)?;
+        let database_name = view.database().to_string();
+        let sql = query.to_string();
+
+        // tables in the query are resolved against the database of the view,
+        // the same as when the view is referenced
+        let provider = ViewContextProvider::new(self.schema_provider, &database_name);
+        let df_plan =
+            SqlToRel::new(&provider).sql_statement_to_plan(Statement::Query(Box::new(query)))?;
+        let schema = Schema::from(df_plan.schema().as_ref());
+
+        // the creator must be able to read the tables referenced by the view
+        let access_databases = self.schema_provider.reset_access_databases();
+        let mut privileges = databases_privileges(
+            DatabasePrivilege::Read,
+            *session.tenant_id(),
+            access_databases,
+        );
+        privileges.push(Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Full, Some(database_name)),
+            Some(*session.tenant_id()),
+        ));
+
+        let plan = Plan::DDL(DDLPlan::CreateView(CreateView {
+            or_replace,
+            name: view,
+            query: sql,
+            schema,
+        }));
+        Ok(PlanWithPrivileges { plan, privileges })
+    }
+
     fn drop_database_object_to_plan(
         &self,
         stmt: ast::DropDatabaseObject,
@@ -567,14 +638,14 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
         let tenant_id = *session.tenant_id();
 
         let (plan, privilege) = match obj_type {
-            DatabaseObjectType::Table => {
+            DatabaseObjectType::Table | DatabaseObjectType::View => {
                 let table = object_name_to_resolved_table(session, object_name)?;
                 let database_name = table.database().to_string();
                 (
                     DDLPlan::DropDatabaseObject(DropDatabaseObject {
                         if_exist,
                         object_name: table,
-                        obj_type: DatabaseObjectType::Table,
+                        obj_type: obj_type.clone(),
                     }),
                     Privilege::TenantObject(
                         TenantObjectPrivilege::Database(
@@ -660,6 +731,269 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
         })
     }
 
+    fn create_rollup_policy_to_plan(
+        &self,
+        stmt: ast::CreateRollupPolicy,
+        session: &SessionCtx,
+    ) -> Result<PlanWithPrivileges> {
+        let ast::CreateRollupPolicy {
+            if_not_exists,
+            name,
+            source_table,
+            target_table,
+            interval,
+            aggregates,
+            ttl,
+        } = stmt;
+
+        let name = normalize_ident(name);
+        let source_table = normalize_ident(source_table);
+        let target_table = normalize_ident(target_table);
+        let database = session.default_database().to_string();
+
+        let interval = self.str_to_duration(&interval)?;
+        let interval_nanos = interval.to_nanoseconds();
+        if interval_nanos <= 0 || interval_nanos == i64::MAX {
+            return Err(QueryError::Parser {
+                source: ParserError::ParserError(
+                    "interval of rollup policy must be a finite duration greater than 0"
+                        .to_string(),
+                ),
+            });
+        }
+        let ttl = ttl.map(|e| self.str_to_duration(&e)).transpose()?;
+
+        let source_schema = self.get_tskv_schema(TableReference::bare(source_table.as_str()))?;
+        let target_schema = self.get_tskv_schema(TableReference::bare(target_table.as_str()))?;
+
+        for tag in source_schema
+            .columns()
+            .iter()
+            .filter(|c| c.column_type.is_tag())
+        {
+            if !target_schema
+                .column(&tag.name)
+                .is_some_and(|c| c.column_type.is_tag())
+            {
+                return Err(QueryError::Semantic {
+                    err: format!(
+                        "tag '{}' of table '{}' is not a tag of table '{}'",
+                        tag.name, source_table, target_table
+                    ),
+                });
+            }
+        }
+
+        let mut rollup_aggregates: Vec<RollupAggregate> = Vec::with_capacity(aggregates.len());
+        for ast::RollupAggregateExpr {
+            function,
+            field,
+            alias,
+        } in aggregates
+        {
+            let aggregate = RollupAggregate::new(
+                normalize_ident(field),
+                function.value,
+                alias.map(normalize_ident),
+            );
+            if !RollupAggregate::is_valid_function(&aggregate.function) {
+                return Err(QueryError::Semantic {
+                    err: format!(
+                        "unsupported rollup aggregate function '{}'",
+                        aggregate.function
+                    ),
+                });
+            }
+            for (table, schema, column) in [
+                (&source_table, &source_schema, &aggregate.field),
+                (&target_table, &target_schema, &aggregate.target),
+            ] {
+                if !schema
+                    .column(column)
+                    .is_some_and(|c| c.column_type.is_field())
+                {
+                    return Err(QueryError::Semantic {
+                        err: format!("field '{}' not found in table '{}'", column, table),
+                    });
+                }
+            }
+            if rollup_aggregates
+                .iter()
+                .any(|e| e.target == aggregate.target)
+            {
+                return Err(QueryError::Semantic {
+                    err: format!(
+                        "column '{}' of table '{}' is written by more than one aggregate",
+                        aggregate.target, target_table
+                    ),
+                });
+            }
+            rollup_aggregates.push(aggregate);
+        }
+
+        let policy = RollupPolicy {
+            tenant: session.tenant().to_string(),
+            database: database.clone(),
+            name,
+            source_table,
+            target_table,
+            interval,
+            aggregates: rollup_aggregates,
+            ttl,
+            owner: session.user().desc().clone(),
+            progress: RollupProgress::default(),
+        };
+
+        let privilege = Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Full, Some(database)),
+            Some(*session.tenant_id()),
+        );
+
+        Ok(PlanWithPrivileges {
+            plan: Plan::DDL(DDLPlan::CreateRollupPolicy(CreateRollupPolicy {
+                if_not_exists,
+                policy,
+            })),
+            privileges: vec![privilege],
+        })
+    }
+
+    fn drop_rollup_policy_to_plan(
+        &self,
+        stmt: ast::DropRollupPolicy,
+        session: &SessionCtx,
+    ) -> Result<PlanWithPrivileges> {
+        let ast::DropRollupPolicy { if_exist, name } = stmt;
+        let database = session.default_database().to_string();
+
+        let privilege = Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Full, Some(database.clone())),
+            Some(*session.tenant_id()),
+        );
+
+        Ok(PlanWithPrivileges {
+            plan: Plan::DDL(DDLPlan::DropRollupPolicy(DropRollupPolicy {
+                tenant_name: session.tenant().to_string(),
+                database,
+                name: normalize_ident(name),
+                if_exist,
+            })),
+            privileges: vec![privilege],
+        })
+    }
+
+    fn create_function_to_plan(
+        &self,
+        stmt: ast::CreateFunction,
+        session: &SessionCtx,
+    ) -> Result<PlanWithPrivileges> {
+        let ast::CreateFunction {
+            if_not_exists,
+            name,
+            args,
+            return_type,
+            body,
+        } = stmt;
+
+        let name = normalize_ident(name);
+        if BuiltinScalarFunction::from_str(&name).is_ok()
+            || AggregateFunction::from_str(&name).is_ok()
+            || self.schema_provider.get_aggregate_meta(&name).is_some()
+        {
+            return Err(QueryError::FunctionExists { name });
+        }
+        if self.schema_provider.get_function_meta(&name).is_some() && !if_not_exists {
+            return Err(QueryError::FunctionExists { name });
+        }
+
+        // the return type is planned as an extra column after the arguments
+        let columns = args
+            .into_iter()
+            .map(|(name, data_type)| ColumnDef {
+                name,
+                data_type,
+                collation: None,
+                options: vec![],
+            })
+            .chain(iter::once(ColumnDef {
+                name: Ident::new(format!("{name}_return")),
+                data_type: return_type,
+                collation: None,
+                options: vec![],
+            }))
+            .collect::<Vec<_>>();
+        let schema = self.df_planner.build_schema(columns)?;
+        let mut fields = schema.fields().iter().cloned().collect::<Vec<_>>();
+        let return_type = fields
+            .pop()
+            .map(|f| f.data_type().clone())
+            .unwrap_or(DataType::Null);
+
+        let mut args: Vec<(String, DataType)> = Vec::with_capacity(fields.len());
+        for field in fields {
+            if args.iter().any(|(arg, _)| arg == field.name()) {
+                return Err(QueryError::Semantic {
+                    err: format!(
+                        "argument '{}' of function '{}' is defined more than once",
+                        field.name(),
+                        name
+                    ),
+                });
+            }
+            args.push((field.name().clone(), field.data_type().clone()));
+        }
+
+        let function = SqlFunction {
+            tenant: session.tenant().to_string(),
+            name,
+            args,
+            return_type,
+            body: body.to_string(),
+        };
+        // make sure the body can be planned before persisting the function,
+        // in the same context as it's compiled for queries
+        let func_manager = self.schema_provider.func_manager();
+        create_sql_udf(
+            &function,
+            &FunctionContextProvider::new(func_manager.as_ref()),
+        )?;
+
+        let privilege = Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Full, None),
+            Some(*session.tenant_id()),
+        );
+
+        Ok(PlanWithPrivileges {
+            plan: Plan::DDL(DDLPlan::CreateFunction(CreateFunction {
+                if_not_exists,
+                function,
+            })),
+            privileges: vec![privilege],
+        })
+    }
+
+    fn drop_function_to_plan(
+        &self,
+        stmt: ast::DropFunction,
+        session: &SessionCtx,
+    ) -> Result<PlanWithPrivileges> {
+        let ast::DropFunction { if_exist, name } = stmt;
+
+        let privilege = Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Full, None),
+            Some(*session.tenant_id()),
+        );
+
+        Ok(PlanWithPrivileges {
+            plan: Plan::DDL(DDLPlan::DropFunction(DropFunction {
+                tenant_name: session.tenant().to_string(),
+                name: normalize_ident(name),
+                if_exist,
+            })),
+            privileges: vec![privilege],
+        })
+    }
+
     fn drop_global_object_to_plan(
         &self,
         stmt: ast::DropGlobalObject,
@@ -742,6 +1076,65 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
         })
     }
 
+    fn backup_database_to_plan(
+        &self,
+        stmt: ast::BackupDatabase,
+        session: &SessionCtx,
+    ) -> Result<PlanWithPrivileges> {
+        let ast::BackupDatabase {
+            database_name,
+            location,
+            incremental,
+        } = stmt;
+        let db_name = normalize_ident(database_name);
+        let location = build_backup_location(location)?;
+
+        let mut privileges = vec![Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Full, Some(db_name.clone())),
+            Some(*session.tenant_id()),
+        )];
+        privileges.extend(backup_location_privilege(&location));
+
+        Ok(PlanWithPrivileges {
+            plan: Plan::DDL(DDLPlan::BackupDatabase(BackupDatabase {
+                tenant_name: session.tenant().to_string(),
+                db_name,
+                location,
+                incremental,
+            })),
+            privileges,
+        })
+    }
+
+    fn restore_database_to_plan(
+        &self,
+        stmt: ast::RestoreDatabase,
+        session: &SessionCtx,
+    ) -> Result<PlanWithPrivileges> {
+        let ast::RestoreDatabase {
+            database_name,
+            location,
+        } = stmt;
+        let db_name = normalize_ident(database_name);
+        let location = build_backup_location(location)?;
+
+        // restore creates a new database, the same as CREATE DATABASE
+        let mut privileges = vec![Privilege::TenantObject(
+            TenantObjectPrivilege::Database(DatabasePrivilege::Full, None),
+            Some(*session.tenant_id()),
+        )];
+        privileges.extend(backup_location_privilege(&location));
+
+        Ok(PlanWithPrivileges {
+            plan: Plan::DDL(DDLPlan::RestoreDatabase(RestoreDatabase {
+                tenant_name: session.tenant().to_string(),
+                db_name,
+                location,
+            })),
+            privileges,
+        })
+    }
+
     fn recovertenant_to_plan(&self, stmt: ast::RecoverTenant) -> Result<PlanWithPrivileges> {
         let ast::RecoverTenant {
             object_name,
@@ -1500,6 +1893,22 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
         if let Some(vnode_duration) = options.vnode_duration {
             plan_options.with_vnode_duration(self.str_to_duration(&vnode_duration)?);
         }
+        if let Some(cold_duration) = options.cold_duration {
+            plan_options.with_cold_duration(self.str_to_duration(&cold_duration)?);
+        }
+        if let Some(compaction_window) = options.compaction_window {
+            plan_options.with_compaction_window(self.str_to_duration(&compaction_window)?);
+        }
+        if let Some(compaction_strategy) = options.compaction_strategy {
+            plan_options.with_compaction_strategy(
+                CompactionStrategy::new(&compaction_strategy).ok_or(QueryError::Parser {
+                    source: ParserError::ParserError(format!(
+                        "{} is not a valid compaction strategy, use like 'level', 'time_window'",
+                        compaction_strategy
+                    )),
+                })?,
+            );
+        }
         if let Some(precision) = options.precision {
             plan_options.with_precision(Precision::new(&precision).ok_or(QueryError::Parser {
                 source: ParserError::ParserError(format!(
@@ -1537,6 +1946,16 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
             SQLDataType::Double => Ok(ColumnType::Field(ValueType::Float)),
             SQLDataType::String => Ok(ColumnType::Field(ValueType::String)),
             SQLDataType::Boolean => Ok(ColumnType::Field(ValueType::Boolean)),
+            SQLDataType::Int(_) | SQLDataType::Integer(_) => {
+                Ok(ColumnType::Field(ValueType::Int32))
+            }
+            SQLDataType::Float(_) | SQLDataType::Real => Ok(ColumnType::Field(ValueType::Float32)),
+            SQLDataType::Binary(_) | SQLDataType::Varbinary(_) | SQLDataType::Bytea => {
+                Ok(ColumnType::Field(ValueType::Binary))
+            }
+            SQLDataType::Decimal(info) | SQLDataType::Numeric(info) => {
+                make_decimal_data_type(info).map_err(unsupport_type_err)
+            }
             SQLDataType::Custom(name, params) => {
                 make_custom_data_type(name, params).map_err(unsupport_type_err)
             }
@@ -1558,6 +1977,12 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
             SQLDataType::Double => encoding.is_double_encoding(),
             SQLDataType::String | SQLDataType::Custom(_, _) => encoding.is_string_encoding(),
             SQLDataType::Boolean => encoding.is_bool_encoding(),
+            SQLDataType::Int(_) | SQLDataType::Integer(_) => encoding.is_bigint_encoding(),
+            SQLDataType::Float(_) | SQLDataType::Real => encoding.is_double_encoding(),
+            SQLDataType::Binary(_) | SQLDataType::Varbinary(_) | SQLDataType::Bytea => {
+                encoding.is_string_encoding()
+            }
+            SQLDataType::Decimal(_) | SQLDataType::Numeric(_) => encoding.is_decimal_encoding(),
             _ => false,
         };
         if !is_ok {
@@ -1807,8 +2232,8 @@ impl<'a, S: ContextProviderExtension + Send + Sync + 'a> SqlPlanner<'a, S> {
             }
             AlterUserOperation::Set(sql_option) => {
                 let user_options = sql_options_to_user_options(vec![sql_option])?;
-                if user_options.granted_admin().is_some() {
-                    // 修改admin参数需要系统管理权限
+                if user_options.granted_admin().is_some() || user_options.has_query_quota() {
+                    // 修改admin参数和查询配额需要系统管理权限
                     privileges = vec![Privilege::Global(GlobalPrivilege::System)];
                 }
                 AlterUserAction::Set(user_options)
@@ -2345,6 +2770,53 @@ fn build_object_store(
     Ok(datasource::build_object_store(parsed_connection_options)?)
 }
 
+/// Build the location of a backup from a url like `s3://bucket/path`,
+/// a path without scheme is a local directory on the data nodes.
+fn build_backup_location(location: UriLocation) -> Result<BackupLocation> {
+    let UriLocation {
+        path,
+        connection_options,
+    } = location;
+
+    let (scheme, bucket, path) = match Url::parse(&path) {
+        Ok(url) => (
+            url.scheme().to_string(),
+            url.host_str().map(|s| s.to_string()),
+            url.path().to_string(),
+        ),
+        Err(_) => ("file".to_string(), None, path),
+    };
+
+    let uri_schema = UriSchema::from(scheme.as_str());
+    if matches!(uri_schema, UriSchema::Gcs) {
+        return Err(QueryError::Semantic {
+            err: "Backup location of gcs is not supported".to_string(),
+        });
+    }
+    // check the connection options
+    parse_connection_options(&uri_schema, bucket.as_deref(), connection_options.clone())?;
+    if matches!(uri_schema, UriSchema::Local) && path.is_empty() {
+        return Err(QueryError::Semantic {
+            err: "Backup location can't be empty".to_string(),
+        });
+    }
+
+    Ok(BackupLocation {
+        scheme: scheme.to_lowercase(),
+        bucket: bucket.unwrap_or_default(),
+        path,
+        options: sql_options_to_map(&connection_options),
+    })
+}
+
+/// A backup in a local directory reads and writes any path the server can access,
+/// only the system administrator is allowed to use it.
+fn backup_location_privilege(location: &BackupLocation) -> Option<Privilege<Oid>> {
+    location
+        .is_local()
+        .then_some(Privilege::Global(GlobalPrivilege::System))
+}
+
 async fn build_external_location_table_source(
     ctx: &SessionCtx,
     table_path: ListingTableUrl,
@@ -2588,7 +3060,7 @@ fn show_tag_value_projections(
     Ok(union_distinct)
 }
 
-fn check_privilege(user: &User, privileges: Vec<Privilege<Oid>>) -> Result<()> {
+pub(crate) fn check_privilege(user: &User, privileges: Vec<Privilege<Oid>>) -> Result<()> {
     let privileges_str = privileges
         .iter()
         .map(|e| format!("{:?}", e))
@@ -2606,7 +3078,7 @@ fn check_privilege(user: &User, privileges: Vec<Privilege<Oid>>) -> Result<()> {
     Ok(())
 }
 
-fn databases_privileges(
+pub(crate) fn databases_privileges(
     db_priv: DatabasePrivilege,
     tenant_id: Oid,
     databases: DatabaseSet,
@@ -2712,6 +3184,32 @@ fn make_custom_data_type(
     }
 }
 
+/// DECIMAL without precision is DECIMAL(38, 10), DECIMAL(p) is DECIMAL(p, 0).
+fn make_decimal_data_type(info: &ExactNumberInfo) -> std::result::Result<ColumnType, String> {
+    let (precision, scale) = match info {
+        ExactNumberInfo::None => (
+            DECIMAL128_MAX_PRECISION as u64,
+            DECIMAL_DEFAULT_SCALE as u64,
+        ),
+        ExactNumberInfo::Precision(precision) => (*precision, 0),
+        ExactNumberInfo::PrecisionAndScale(precision, scale) => (*precision, *scale),
+    };
+    if precision == 0 || precision > DECIMAL128_MAX_PRECISION as u64 {
+        return Err(format!(
+            "precision must be between 1 and {}",
+            DECIMAL128_MAX_PRECISION
+        ));
+    }
+    if scale > precision {
+        return Err("scale must not be greater than precision".to_string());
+    }
+
+    Ok(ColumnType::Field(ValueType::Decimal128(
+        precision as u8,
+        scale as i8,
+    )))
+}
+
 fn make_geometry_data_type(params: &[String]) -> std::result::Result<ColumnType, String> {
     if params.len() != 2 {
         return Err("format: GEOMETRY(<sub_type>, <srid>)".to_string());
@@ -2830,6 +3328,10 @@ mod tests {
             Ok(Precision::NS)
         }
 
+        fn func_manager(&self) -> spi::query::function::FuncMetaManagerRef {
+            unimplemented!()
+        }
+
         fn get_table_source(
             &self,
             name: TableReference,
@@ -2974,6 +3476,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_create_view() {
+        let sql = "create or replace view v as select field_int from test_tb where field_int > 1";
+        let mut statements = ExtParser::parse_sql(sql).unwrap();
+        assert_eq!(statements.len(), 1);
+        let test = MockContext {};
+        let planner = SqlPlanner::new(&test);
+        let plan = planner
+            .statement_to_plan(statements.pop_back().unwrap(), &session())
+            .await
+            .unwrap();
+        if let Plan::DDL(DDLPlan::CreateView(view)) = plan.plan {
+            assert!(view.or_replace);
+            assert_eq!(view.name.table(), "v");
+            assert_eq!(
+                view.query,
+                "SELECT field_int FROM test_tb WHERE field_int > 1"
+            );
+            assert_eq!(view.schema.fields().len(), 1);
+            assert_eq!(view.schema.field(0).name(), "field_int");
+        } else {
+            panic!("expected create view plan")
+        }
+    }
+
     #[tokio::test]
     async fn test_create_table() {
         let sql = "CREATE TABLE IF NOT EXISTS default_schema.test\
@@ -3056,6 +3583,50 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_create_table_with_extended_types() {
+        let sql = "CREATE TABLE test\
+            (column1 INT, column2 FLOAT CODEC(CHIMP), column3 DECIMAL(12, 2) CODEC(DELTA),\
+            column4 BINARY, TAGS(column5))";
+        let mut statements = ExtParser::parse_sql(sql).unwrap();
+        let test = MockContext {};
+        let planner = SqlPlanner::new(&test);
+        let plan = planner
+            .statement_to_plan(statements.pop_back().unwrap(), &session())
+            .await
+            .unwrap();
+        if let Plan::DDL(DDLPlan::CreateTable(create)) = plan.plan {
+            let column_types = create
+                .schema
+                .iter()
+                .map(|c| (c.name.as_str(), c.column_type.clone()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                column_types[2..],
+                [
+                    ("column1", ColumnType::Field(ValueType::Int32)),
+                    ("column2", ColumnType::Field(ValueType::Float32)),
+                    ("column3", ColumnType::Field(ValueType::Decimal128(12, 2))),
+                    ("column4", ColumnType::Field(ValueType::Binary)),
+                ]
+            );
+        } else {
+            panic!("expected create table plan")
+        }
+
+        for sql in [
+            "CREATE TABLE test(column1 DECIMAL(39, 2), TAGS(column2))",
+            "CREATE TABLE test(column1 DECIMAL(2, 3), TAGS(column2))",
+            "CREATE TABLE test(column1 DECIMAL CODEC(GORILLA), TAGS(column2))",
+        ] {
+            let mut statements = ExtParser::parse_sql(sql).unwrap();
+            let result = planner
+                .statement_to_plan(statements.pop_back().unwrap(), &session())
+                .await;
+            assert!(result.is_err(), "{sql}");
+        }
+    }
+
     #[tokio::test]
     async fn test_create_database() {
         let sql = "CREATE DATABASE test WITH TTL '10' SHARD 5 VNODE_DURATION '3d' REPLICA 10 PRECISION 'us';";
@@ -3070,13 +3641,35 @@ mod tests {
         if let Plan::DDL(DDLPlan::CreateDatabase(create)) = plan.plan {
             let ans = format!("{:?}", create);
             println!("{ans}");
-            let expected = r#"CreateDatabase { name: "test", if_not_exists: false, options: DatabaseOptions { ttl: Some(Duration { time_num: 10, unit: Day }), shard_num: Some(5), vnode_duration: Some(Duration { time_num: 3, unit: Day }), replica: Some(10), precision: Some(US), db_is_hidden: false } }"#;
+            let expected = r#"CreateDatabase { name: "test", if_not_exists: false, options: DatabaseOptions { ttl: Some(Duration { time_num: 10, unit: Day }), shard_num: Some(5), vnode_duration: Some(Duration { time_num: 3, unit: Day }), replica: Some(10), precision: Some(US), cold_duration: None, compaction_strategy: None, compaction_window: None, db_is_hidden: false } }"#;
             assert_eq!(ans, expected);
         } else {
             panic!("expected create table plan")
         }
     }
 
+    #[tokio::test]
+    async fn test_backup_location_privileges() {
+        let test = MockContext {};
+        let planner = SqlPlanner::new(&test);
+        let system = Privilege::Global(GlobalPrivilege::System);
+        for (sql, need_system) in [
+            ("BACKUP DATABASE test TO '/tmp/backup/test'", true),
+            ("RESTORE DATABASE test FROM 'file:///tmp/backup/test'", true),
+            (
+                "BACKUP DATABASE test TO 's3://bucket/test' CONNECTION = (region = 'us-east-1')",
+                false,
+            ),
+        ] {
+            let mut statements = ExtParser::parse_sql(sql).unwrap();
+            let plan = planner
+                .statement_to_plan(statements.pop_back().unwrap(), &session())
+                .await
+                .unwrap();
+            assert_eq!(plan.privileges.contains(&system), need_system, "{sql}");
+        }
+    }
+
     #[tokio::test]
     async fn test_create_table_filed_name_same() {
         let sql = "CREATE TABLE air (visibility DOUBLE,temperature DOUBLE,pressure DOUBLE,pressure DOUBLE,TAGS(station));";
@@ -0,0 +1,131 @@
+//! Planning of the [substrait](https://substrait.io) plans of queries,
+//! e.g. the query fragments pushed down by federated query engines.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::catalog::catalog::CatalogProvider;
+use datafusion::catalog::schema::SchemaProvider;
+use datafusion::datasource::TableProvider;
+use datafusion::execution::context::{SessionContext, SessionState};
+use datafusion::sql::TableReference;
+use datafusion_substrait::logical_plan::consumer::from_substrait_plan;
+use datafusion_substrait::serializer::deserialize_bytes;
+use models::auth::privilege::DatabasePrivilege;
+use spi::query::logical_planner::{Plan, QueryPlan};
+use spi::query::session::SessionCtx;
+use spi::Result;
+
+use crate::data_source::table_source::TableHandle;
+use crate::metadata::ContextProviderExtension;
+use crate::sql::planner::{check_privilege, databases_privileges};
+
+/// Create the logical plan of the serialized substrait plan.
+///
+/// The tables are resolved by the schema provider against the tenant and
+/// the default database of the session, the same as the tables of a sql query.
+pub async fn substrait_to_logical_plan<S>(
+    schema_provider: Arc<S>,
+    substrait_plan: &[u8],
+    session: &SessionCtx,
+) -> Result<Plan>
+where
+    S: ContextProviderExtension + Send + Sync + 'static,
+{
+    let substrait_plan = deserialize_bytes(substrait_plan.to_vec()).await?;
+
+    let config = session
+        .inner()
+        .config()
+        .clone()
+        .with_default_catalog_and_schema(session.tenant(), session.default_database());
+    let state = SessionState::with_config_rt(config, session.inner().runtime_env().clone());
+    let mut ctx = SessionContext::with_state(state);
+    ctx.register_catalog(
+        session.tenant(),
+        Arc::new(TenantCatalogProvider {
+            schema_provider: schema_provider.clone(),
+        }),
+    );
+
+    let df_plan = from_substrait_plan(&mut ctx, &substrait_plan).await?;
+
+    let privileges = databases_privileges(
+        DatabasePrivilege::Read,
+        *session.tenant_id(),
+        schema_provider.reset_access_databases(),
+    );
+    check_privilege(session.user(), privileges)?;
+
+    Ok(Plan::Query(QueryPlan { df_plan }))
+}
+
+/// Catalog of the tenant of the session, the databases are the schemas.
+struct TenantCatalogProvider<S> {
+    schema_provider: Arc<S>,
+}
+
+impl<S> CatalogProvider for TenantCatalogProvider<S>
+where
+    S: ContextProviderExtension + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn schema(&self, name: &str) -> Option<Arc<dyn SchemaProvider>> {
+        Some(Arc::new(DatabaseSchemaProvider {
+            schema_provider: self.schema_provider.clone(),
+            database: name.to_string(),
+        }))
+    }
+}
+
+struct DatabaseSchemaProvider<S> {
+    schema_provider: Arc<S>,
+    database: String,
+}
+
+impl<S> DatabaseSchemaProvider<S>
+where
+    S: ContextProviderExtension + Send + Sync + 'static,
+{
+    fn table_handle(&self, name: &str) -> Option<TableHandle> {
+        let table_ref = TableReference::partial(self.database.clone(), name.to_string());
+        let source = self.schema_provider.get_table_source(table_ref).ok()?;
+        Some(source.table_handle().clone())
+    }
+}
+
+#[async_trait]
+impl<S> SchemaProvider for DatabaseSchemaProvider<S>
+where
+    S: ContextProviderExtension + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    async fn table(&self, name: &str) -> Option<Arc<dyn TableProvider>> {
+        match self.table_handle(name)? {
+            TableHandle::TableProvider(t) => Some(t),
+            TableHandle::External(t) => Some(t),
+            TableHandle::Tskv(t) => Some(t),
+            // stream tables are only supported by sql
+            TableHandle::StreamProvider(_) => None,
+        }
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        self.table_handle(name).is_some()
+    }
+}
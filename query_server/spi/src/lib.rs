@@ -1,4 +1,5 @@
 use std::error;
+use std::time::Duration;
 
 use coordinator::errors::CoordinatorError;
 use datafusion::arrow::error::ArrowError;
@@ -526,6 +527,31 @@ pub enum QueryError {
     ForbiddenDropSystemRole {
         role: String,
     },
+
+    #[snafu(display("Invalid PromQL query, error: {}", reason))]
+    #[error_code(code = 77)]
+    InvalidPromQL {
+        reason: String,
+    },
+
+    #[snafu(display("Concurrent query limit of {} exceeded, limit: {}", owner, limit))]
+    #[error_code(code = 78)]
+    QueryQuotaExceeded {
+        owner: String,
+        limit: u32,
+    },
+
+    #[snafu(display("The query has been cancelled, it ran longer than {:?}", timeout))]
+    #[error_code(code = 79)]
+    QueryTimeout {
+        timeout: Duration,
+    },
+
+    #[snafu(display("Invalid backup: {}", reason))]
+    #[error_code(code = 80)]
+    InvalidBackup {
+        reason: String,
+    },
 }
 
 impl From<ParserError> for QueryError {
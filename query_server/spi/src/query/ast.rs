@@ -1,4 +1,5 @@
 use std::fmt;
+use std::time::Duration;
 
 use datafusion::sql::parser::CreateExternalTable;
 use datafusion::sql::sqlparser::ast::{
@@ -32,6 +33,12 @@ pub enum ExtStatement {
     DropStream(DropStream),
     ShowStreams(ShowStreams),
 
+    CreateRollupPolicy(CreateRollupPolicy),
+    DropRollupPolicy(DropRollupPolicy),
+
+    CreateFunction(CreateFunction),
+    DropFunction(DropFunction),
+
     DropDatabaseObject(DropDatabaseObject),
     DropTenantObject(DropTenantObject),
     DropGlobalObject(DropGlobalObject),
@@ -63,6 +70,10 @@ pub enum ExtStatement {
     // recover cmd
     RecoverTenant(RecoverTenant),
     RecoverDatabase(RecoverDatabase),
+
+    // backup cmd
+    BackupDatabase(BackupDatabase),
+    RestoreDatabase(RestoreDatabase),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -222,6 +233,21 @@ pub struct RecoverTenant {
     pub if_exist: bool,
 }
 
+/// BACKUP DATABASE db TO 'location' [INCREMENTAL] [CONNECTION = (...)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupDatabase {
+    pub database_name: Ident,
+    pub location: UriLocation,
+    pub incremental: bool,
+}
+
+/// RESTORE DATABASE db FROM 'location' [CONNECTION = (...)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreDatabase {
+    pub database_name: Ident,
+    pub location: UriLocation,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DescribeObject {
     pub object_name: ObjectName,
@@ -337,6 +363,12 @@ pub struct DatabaseOptions {
     pub replica: Option<u64>,
     // timestamp precision
     pub precision: Option<String>,
+    // data older than it will be moved to the cold storage
+    pub cold_duration: Option<String>,
+    // strategy to pick files for compaction
+    pub compaction_strategy: Option<String>,
+    // time window of the time window compaction strategy
+    pub compaction_window: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -434,6 +466,48 @@ pub struct ShowStreams {
     pub verbose: bool,
 }
 
+/// CREATE ROLLUP POLICY [IF NOT EXISTS] name ON source INTO target
+/// INTERVAL '1m' AGGREGATES (avg(f1), max(f1) AS f1_max) [TTL '30d']
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateRollupPolicy {
+    pub if_not_exists: bool,
+    pub name: Ident,
+    pub source_table: Ident,
+    pub target_table: Ident,
+    pub interval: String,
+    pub aggregates: Vec<RollupAggregateExpr>,
+    pub ttl: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RollupAggregateExpr {
+    pub function: Ident,
+    pub field: Ident,
+    pub alias: Option<Ident>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DropRollupPolicy {
+    pub if_exist: bool,
+    pub name: Ident,
+}
+
+/// CREATE FUNCTION [IF NOT EXISTS] name(arg type, ...) RETURNS type AS expr
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateFunction {
+    pub if_not_exists: bool,
+    pub name: Ident,
+    pub args: Vec<(Ident, DataType)>,
+    pub return_type: DataType,
+    pub body: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropFunction {
+    pub if_exist: bool,
+    pub name: Ident,
+}
+
 impl fmt::Display for ObjectType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(match self {
@@ -463,6 +537,25 @@ pub fn parse_string_value(value: Value) -> std::result::Result<String, ParserErr
     }
 }
 
+pub fn parse_u32_value(value: Value) -> std::result::Result<u32, ParserError> {
+    match value {
+        Value::Number(ref s, _) => s.parse::<u32>().map_err(|_| {
+            ParserError::ParserError(format!("expected u32 value, but found : {}", value))
+        }),
+        _ => Err(ParserError::ParserError(format!(
+            "expected u32 value, but found : {}",
+            value
+        ))),
+    }
+}
+
+/// Parse a duration string, e.g. '30s', '10m'
+pub fn parse_duration_value(value: Value) -> std::result::Result<Duration, ParserError> {
+    let text = parse_string_value(value)?;
+    duration_str::parse_std(&text)
+        .map_err(|_| ParserError::ParserError(format!("{} is not a valid duration", text)))
+}
+
 pub fn parse_char_value(value: Value) -> std::result::Result<char, ParserError> {
     let token = parse_string_value(value)?;
     match token.len() {
@@ -74,6 +74,26 @@ impl FromStr for StreamTriggerInterval {
     }
 }
 
+/// Query language of the statements in a query.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    #[default]
+    Sql,
+    InfluxQL,
+}
+
+impl FromStr for SqlDialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "sql" => Ok(SqlDialect::Sql),
+            "influxql" => Ok(SqlDialect::InfluxQL),
+            _ => Err(format!("unknown dialect '{s}', expect sql or influxql")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
@@ -36,6 +36,13 @@ pub trait QueryDispatcher: Send + Sync {
         query_state_machine: Arc<QueryStateMachine>,
     ) -> Result<Option<Plan>>;
 
+    /// Build the logical plan of a serialized substrait plan instead of the sql of the query.
+    async fn build_substrait_logical_plan(
+        &self,
+        query_state_machine: Arc<QueryStateMachine>,
+        substrait_plan: &[u8],
+    ) -> Result<Plan>;
+
     async fn execute_logical_plan(
         &self,
         logical_plan: Plan,
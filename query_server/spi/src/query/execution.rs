@@ -46,6 +46,8 @@ pub trait QueryExecution: Send + Sync {
     async fn start(&self) -> Result<Output>;
     // 停止
     fn cancel(&self) -> Result<()>;
+    // 等待空闲的并发配额
+    fn queued(&self) {}
     // query状态
     // 查询计划
     // 静态信息
@@ -239,6 +241,11 @@ impl QueryStateMachine {
         }
     }
 
+    /// Waiting for a free slot of the concurrent queries
+    pub fn begin_queue(&self) {
+        self.translate_to(Box::new(QueryState::QUEUED));
+    }
+
     pub fn begin_analyze(&self) {
         // TODO record time
         self.translate_to(Box::new(QueryState::RUNNING(RUNNING::ANALYZING)));
@@ -272,8 +279,8 @@ impl QueryStateMachine {
     }
 
     pub fn cancel(&self) {
-        // TODO
         self.translate_to(Box::new(QueryState::DONE(DONE::CANCELLED)));
+        self.session.cancellation_token().cancel();
     }
 
     pub fn fail(&self) {
@@ -285,6 +292,10 @@ impl QueryStateMachine {
         unsafe { &*self.state.load(Ordering::Relaxed) }
     }
 
+    pub fn is_cancelled(&self) -> bool {
+        self.session.cancellation_token().is_cancelled()
+    }
+
     pub fn duration(&self) -> Duration {
         self.start.elapsed()
     }
@@ -310,6 +321,7 @@ impl QueryStateMachine {
 #[derive(Debug, Clone)]
 pub enum QueryState {
     ACCEPTING,
+    QUEUED,
     RUNNING(RUNNING),
     DONE(DONE),
 }
@@ -318,6 +330,7 @@ impl AsRef<str> for QueryState {
     fn as_ref(&self) -> &str {
         match self {
             QueryState::ACCEPTING => "ACCEPTING",
+            QueryState::QUEUED => "QUEUED",
             QueryState::RUNNING(e) => e.as_ref(),
             QueryState::DONE(e) => e.as_ref(),
         }
@@ -16,6 +16,7 @@ use datafusion::logical_expr::{
 };
 use datafusion::physical_plan::functions::make_scalar_function;
 use datafusion::prelude::{col, Expr};
+use datafusion::scalar::ScalarValue;
 use datafusion::sql::sqlparser::ast::{Ident, ObjectName, SqlOption, Value};
 use datafusion::sql::sqlparser::parser::ParserError;
 use lazy_static::lazy_static;
@@ -26,12 +27,17 @@ use models::meta_data::{NodeId, ReplicationSetId, VnodeId};
 use models::object_reference::ResolvedTable;
 use models::oid::{Identifier, Oid};
 use models::schema::{
-    DatabaseOptions, Duration, TableColumn, Tenant, TenantOptions, TenantOptionsBuilder, Watermark,
+    DatabaseOptions, Duration, RollupPolicy, SqlFunction, TableColumn, Tenant, TenantOptions,
+    TenantOptionsBuilder, Watermark,
 };
 use snafu::ResultExt;
 use tempfile::NamedTempFile;
+use tskv::backup::BackupLocation;
 
-use super::ast::{parse_bool_value, parse_char_value, parse_string_value, ExtStatement};
+use super::ast::{
+    parse_bool_value, parse_char_value, parse_duration_value, parse_string_value, parse_u32_value,
+    ExtStatement,
+};
 use super::datasource::azure::{AzblobStorageConfig, AzblobStorageConfigBuilder};
 use super::datasource::gcs::{
     GcsStorageConfig, ServiceAccountCredentials, ServiceAccountCredentialsBuilder,
@@ -46,6 +52,9 @@ use crate::{ParserSnafu, QueryError, Result};
 pub const TENANT_OPTION_LIMITER: &str = "_limiter";
 pub const TENANT_OPTION_COMMENT: &str = "comment";
 pub const TENANT_OPTION_DROP_AFTER: &str = "drop_after";
+pub const TENANT_OPTION_MAX_CONCURRENT_QUERIES: &str = "max_concurrent_queries";
+pub const TENANT_OPTION_MAX_EXECUTION_TIME: &str = "max_execution_time";
+pub const TENANT_OPTION_QUERY_QUEUE_TIMEOUT: &str = "query_queue_timeout";
 
 lazy_static! {
     static ref TABLE_WRITE_UDF: Arc<ScalarUDF> = Arc::new(ScalarUDF::new(
@@ -93,6 +102,30 @@ impl Plan {
             Self::SYSTEM(p) => p.schema(),
         }
     }
+
+    /// Types of the placeholders `$1, $2...` in the plan, the type is `None` if it can't be inferred.
+    pub fn parameter_types(&self) -> Result<HashMap<String, Option<DataType>>> {
+        match self {
+            Self::Query(p) => Ok(p.df_plan.get_parameter_types()?),
+            _ => Ok(HashMap::new()),
+        }
+    }
+
+    /// Replace the placeholders `$1, $2...` in the plan with the values.
+    pub fn with_param_values(self, param_values: Vec<ScalarValue>) -> Result<Self> {
+        if param_values.is_empty() {
+            return Ok(self);
+        }
+
+        match self {
+            Self::Query(QueryPlan { df_plan }) => Ok(Self::Query(QueryPlan {
+                df_plan: df_plan.with_param_values(param_values)?,
+            })),
+            _ => Err(QueryError::Semantic {
+                err: "parameters are only supported by queries and dml statements".to_string(),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -122,6 +155,16 @@ pub enum DDLPlan {
 
     CreateStreamTable(CreateStreamTable),
 
+    CreateView(CreateView),
+
+    CreateRollupPolicy(CreateRollupPolicy),
+
+    DropRollupPolicy(DropRollupPolicy),
+
+    CreateFunction(CreateFunction),
+
+    DropFunction(DropFunction),
+
     CreateDatabase(CreateDatabase),
 
     CreateTenant(Box<CreateTenant>),
@@ -153,6 +196,10 @@ pub enum DDLPlan {
     RecoverDatabase(RecoverDatabase),
 
     RecoverTenant(RecoverTenant),
+
+    BackupDatabase(BackupDatabase),
+
+    RestoreDatabase(RestoreDatabase),
 }
 
 impl DDLPlan {
@@ -237,6 +284,7 @@ pub struct DropDatabaseObject {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DatabaseObjectType {
     Table,
+    View,
 }
 
 #[derive(Debug, Clone)]
@@ -281,6 +329,22 @@ pub struct RecoverDatabase {
     pub if_exist: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupDatabase {
+    pub tenant_name: String,
+    pub db_name: String,
+    pub location: BackupLocation,
+    /// Only upload data files that are not in the latest backup of the location.
+    pub incremental: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreDatabase {
+    pub tenant_name: String,
+    pub db_name: String,
+    pub location: BackupLocation,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RecoverTable {
     pub table: ResolvedTable,
@@ -317,6 +381,45 @@ pub struct CreateStreamTable {
     pub extra_options: HashMap<String, String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateView {
+    /// Replace the view if it already exists
+    pub or_replace: bool,
+    /// The view name
+    pub name: ResolvedTable,
+    /// The sql text of the query
+    pub query: String,
+    /// The output schema of the query
+    pub schema: Schema,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateRollupPolicy {
+    pub if_not_exists: bool,
+    pub policy: RollupPolicy,
+}
+
+#[derive(Debug, Clone)]
+pub struct DropRollupPolicy {
+    pub tenant_name: String,
+    pub database: String,
+    pub name: String,
+    pub if_exist: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateFunction {
+    pub if_not_exists: bool,
+    pub function: SqlFunction,
+}
+
+#[derive(Debug, Clone)]
+pub struct DropFunction {
+    pub tenant_name: String,
+    pub name: String,
+    pub if_exist: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CreateDatabase {
     pub name: String,
@@ -353,10 +456,23 @@ pub fn unset_option_to_alter_tenant_action(
             tenant_options_builder.unset_drop_after();
             Privilege::Global(GlobalPrivilege::Tenant(Some(tenant_id)))
         }
+        TENANT_OPTION_MAX_CONCURRENT_QUERIES => {
+            tenant_options_builder.unset_max_concurrent_queries();
+            Privilege::Global(GlobalPrivilege::System)
+        }
+        TENANT_OPTION_MAX_EXECUTION_TIME => {
+            tenant_options_builder.unset_max_execution_time();
+            Privilege::Global(GlobalPrivilege::System)
+        }
+        TENANT_OPTION_QUERY_QUEUE_TIMEOUT => {
+            tenant_options_builder.unset_query_queue_timeout();
+            Privilege::Global(GlobalPrivilege::System)
+        }
         _ => {
             return Err(QueryError::Parser {
                 source: ParserError::ParserError(format!(
-                "Expected option [{TENANT_OPTION_COMMENT}], [{TENANT_OPTION_LIMITER}], [{TENANT_OPTION_DROP_AFTER}] found [{}]",
+                "Expected option [{TENANT_OPTION_COMMENT}], [{TENANT_OPTION_LIMITER}], [{TENANT_OPTION_DROP_AFTER}], \
+                [{TENANT_OPTION_MAX_CONCURRENT_QUERIES}], [{TENANT_OPTION_MAX_EXECUTION_TIME}], [{TENANT_OPTION_QUERY_QUEUE_TIMEOUT}] found [{}]",
                 ident
             )),
             })
@@ -399,10 +515,24 @@ pub fn sql_option_to_alter_tenant_action(
             tenant_options_builder.drop_after(drop_after);
             Privilege::Global(GlobalPrivilege::Tenant(Some(tenant_id)))
         }
+        // the quotas of queries are limits of the tenant like the limiter
+        TENANT_OPTION_MAX_CONCURRENT_QUERIES => {
+            tenant_options_builder.max_concurrent_queries(parse_u32_value(value)?);
+            Privilege::Global(GlobalPrivilege::System)
+        }
+        TENANT_OPTION_MAX_EXECUTION_TIME => {
+            tenant_options_builder.max_execution_time(parse_duration_value(value)?);
+            Privilege::Global(GlobalPrivilege::System)
+        }
+        TENANT_OPTION_QUERY_QUEUE_TIMEOUT => {
+            tenant_options_builder.query_queue_timeout(parse_duration_value(value)?);
+            Privilege::Global(GlobalPrivilege::System)
+        }
         _ => {
             return Err(QueryError::Parser {
                 source: ParserError::ParserError(format!(
-                "Expected option [{TENANT_OPTION_COMMENT}], [{TENANT_OPTION_LIMITER}], [{TENANT_OPTION_DROP_AFTER}] found [{}]",
+                "Expected option [{TENANT_OPTION_COMMENT}], [{TENANT_OPTION_LIMITER}], [{TENANT_OPTION_DROP_AFTER}], \
+                [{TENANT_OPTION_MAX_CONCURRENT_QUERIES}], [{TENANT_OPTION_MAX_EXECUTION_TIME}], [{TENANT_OPTION_QUERY_QUEUE_TIMEOUT}] found [{}]",
                 name
             )),
             })
@@ -436,10 +566,20 @@ pub fn sql_options_to_tenant_options(options: Vec<SqlOption>) -> Result<TenantOp
                 })?;
                 builder.drop_after(drop_after);
             }
+            TENANT_OPTION_MAX_CONCURRENT_QUERIES => {
+                builder.max_concurrent_queries(parse_u32_value(value).context(ParserSnafu)?);
+            }
+            TENANT_OPTION_MAX_EXECUTION_TIME => {
+                builder.max_execution_time(parse_duration_value(value).context(ParserSnafu)?);
+            }
+            TENANT_OPTION_QUERY_QUEUE_TIMEOUT => {
+                builder.query_queue_timeout(parse_duration_value(value).context(ParserSnafu)?);
+            }
             _ => {
                 return Err(QueryError::Parser {
                     source: ParserError::ParserError(format!(
-                        "Expected option [{TENANT_OPTION_COMMENT}], [{TENANT_OPTION_LIMITER}], [{TENANT_OPTION_DROP_AFTER}] found [{}]",
+                        "Expected option [{TENANT_OPTION_COMMENT}], [{TENANT_OPTION_LIMITER}], [{TENANT_OPTION_DROP_AFTER}], \
+                [{TENANT_OPTION_MAX_CONCURRENT_QUERIES}], [{TENANT_OPTION_MAX_EXECUTION_TIME}], [{TENANT_OPTION_QUERY_QUEUE_TIMEOUT}] found [{}]",
                         name
                     )),
                 })
@@ -486,9 +626,18 @@ pub fn sql_options_to_user_options(
             "hash_password" => {
                 builder.hash_password(parse_string_value(value)?);
             }
+            "max_concurrent_queries" => {
+                builder.max_concurrent_queries(parse_u32_value(value)?);
+            }
+            "max_execution_time" => {
+                builder.max_execution_time(parse_duration_value(value)?);
+            }
+            "query_queue_timeout" => {
+                builder.query_queue_timeout(parse_duration_value(value)?);
+            }
             _ => {
                 return Err(ParserError::ParserError(format!(
-                "Expected option [password | rsa_public_key | comment | granted_admin], found [{}]",
+                "Expected option [password | rsa_public_key | comment | granted_admin | max_concurrent_queries | max_execution_time | query_queue_timeout], found [{}]",
                 name
             )))
             }
@@ -9,6 +9,7 @@ use datafusion::prelude::{SessionConfig, SessionContext};
 use datafusion::variable::VarType;
 use models::auth::user::User;
 use models::oid::Oid;
+use tokio_util::sync::CancellationToken;
 use trace::{SpanContext, SpanExt, SpanRecorder};
 
 use super::config::StreamTriggerInterval;
@@ -21,6 +22,7 @@ pub struct SessionCtx {
     desc: Arc<SessionCtxDesc>,
     inner: SessionState,
     span_ctx: Option<SpanContext>,
+    cancellation_token: CancellationToken,
 }
 
 impl SessionCtx {
@@ -53,9 +55,16 @@ impl SessionCtx {
             desc: self.desc.clone(),
             inner: self.inner.clone(),
             span_ctx,
+            cancellation_token: self.cancellation_token.clone(),
         }
     }
 
+    /// Cancelled when the query of this session is cancelled, it is also injected into
+    /// the datafusion session config so that the running scans can be stopped.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
     pub fn get_span_ctx(&self) -> Option<&SpanContext> {
         self.span_ctx.as_ref()
         // self.inner().config().get_extension::<SpanContext>();
@@ -104,11 +113,13 @@ impl SessionCtxFactory {
         memory_pool: Arc<dyn MemoryPool>,
         span_ctx: Option<SpanContext>,
     ) -> Result<SessionCtx> {
+        let cancellation_token = CancellationToken::new();
         let df_session_ctx = self.build_df_session_context(
             session_id,
             context.session_config().to_df_config(),
             memory_pool,
             &span_ctx,
+            &cancellation_token,
         )?;
 
         Ok(SessionCtx {
@@ -121,6 +132,7 @@ impl SessionCtxFactory {
             }),
             inner: df_session_ctx.state(),
             span_ctx,
+            cancellation_token,
         })
     }
 
@@ -130,12 +142,14 @@ impl SessionCtxFactory {
         config: &SessionConfig,
         memory_pool: Arc<dyn MemoryPool>,
         span_ctx: &Option<SpanContext>,
+        cancellation_token: &CancellationToken,
     ) -> Result<SessionContext> {
         let mut config = config.clone();
         if let Some(span_ctx) = span_ctx {
             // inject span context into datafusion session config, so that it can be used in execution
             config = config.with_extension(Arc::new(span_ctx.clone()))
         }
+        config = config.with_extension(Arc::new(cancellation_token.clone()));
 
         let rt_config = RuntimeConfig::new().with_memory_pool(memory_pool);
         let rt = RuntimeEnv::new(rt_config)?;
@@ -12,7 +12,7 @@ use crate::query::execution::{Output, QueryStateMachine, QueryStateMachineRef};
 use crate::query::logical_planner::Plan;
 use crate::query::recordbatch::RecordBatchStreamWrapper;
 use crate::service::protocol::{Query, QueryHandle, QueryId};
-use crate::Result;
+use crate::{QueryError, Result};
 
 pub type DBMSRef = Arc<dyn DatabaseManagerSystem + Send + Sync>;
 
@@ -34,6 +34,11 @@ pub trait DatabaseManagerSystem {
         &self,
         query_state_machine: QueryStateMachineRef,
     ) -> Result<Option<Plan>>;
+    async fn build_substrait_logical_plan(
+        &self,
+        query_state_machine: QueryStateMachineRef,
+        substrait_plan: &[u8],
+    ) -> Result<Plan>;
     async fn execute_logical_plan(
         &self,
         logical_plan: Plan,
@@ -116,6 +121,16 @@ impl DatabaseManagerSystem for DatabaseManagerSystemMock {
         Ok(None)
     }
 
+    async fn build_substrait_logical_plan(
+        &self,
+        _query_state_machine: QueryStateMachineRef,
+        _substrait_plan: &[u8],
+    ) -> Result<Plan> {
+        Err(QueryError::NotImplemented {
+            err: "mock substrait plan".to_string(),
+        })
+    }
+
     async fn execute_logical_plan(
         &self,
         _logical_plan: Plan,
@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
 use protocol_parser::Line;
 use protos::prompb::remote::WriteRequest;
+use serde::Serialize;
 use trace::SpanContext;
 
 use crate::service::protocol::Context;
@@ -23,4 +25,82 @@ pub trait PromRemoteServer {
     fn remote_write(&self, req: Bytes) -> Result<WriteRequest>;
 
     fn prom_write_request_to_lines<'a>(&self, req: &'a WriteRequest) -> Result<Vec<Line<'a>>>;
+
+    /// Evaluate a PromQL expression, at `start` for an instant query,
+    /// or at each step of `[start, end]` for a range query.
+    async fn query(
+        &self,
+        ctx: &Context,
+        req: PromQueryRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<PromQueryResult>;
+
+    /// Series matched by any of the selectors.
+    async fn series(
+        &self,
+        ctx: &Context,
+        req: PromSeriesRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<Vec<BTreeMap<String, String>>>;
+
+    /// Label names of the series matched by the selectors, of all series if there is no selector.
+    async fn label_names(
+        &self,
+        ctx: &Context,
+        req: PromSeriesRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<Vec<String>>;
+
+    /// Values of the label of the series matched by the selectors,
+    /// of all series if there is no selector.
+    async fn label_values(
+        &self,
+        ctx: &Context,
+        name: &str,
+        req: PromSeriesRequest,
+        span_ctx: Option<&SpanContext>,
+    ) -> Result<Vec<String>>;
+}
+
+/// A PromQL query, times are unix timestamps in nanoseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromQueryRequest {
+    pub query: String,
+    pub start: i64,
+    pub end: i64,
+    /// Interval of steps in nanoseconds, `None` for an instant query.
+    pub step: Option<i64>,
+}
+
+/// Series selectors and time range of the metadata apis, times are unix timestamps in
+/// nanoseconds, an absent time is unbounded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PromSeriesRequest {
+    pub matchers: Vec<String>,
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+}
+
+/// A sample of the http api of prometheus, `[<unix time in seconds>, "<value>"]`.
+pub type PromSample = (f64, String);
+
+/// `data` of the responses of `/api/v1/query` and `/api/v1/query_range`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "resultType", content = "result", rename_all = "lowercase")]
+pub enum PromQueryResult {
+    Matrix(Vec<PromRangeSeries>),
+    Vector(Vec<PromInstantSeries>),
+    Scalar(PromSample),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromRangeSeries {
+    pub metric: BTreeMap<String, String>,
+    pub values: Vec<PromSample>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromInstantSeries {
+    pub metric: BTreeMap<String, String>,
+    pub value: PromSample,
 }
@@ -6,7 +6,7 @@ use models::schema::{DEFAULT_CATALOG, DEFAULT_DATABASE, DEFAULT_PRECISION};
 use serde::{Deserialize, Serialize};
 use trace::{SpanRecorder, SpanRecorderExt};
 
-use crate::query::config::StreamTriggerInterval;
+use crate::query::config::{SqlDialect, StreamTriggerInterval};
 use crate::query::execution::Output;
 use crate::query::session::CnosSessionConfig;
 
@@ -72,6 +72,7 @@ pub struct Context {
     database: String,
     precision: String,
     chunked: bool,
+    dialect: SqlDialect,
     session_config: CnosSessionConfig,
 }
 
@@ -98,6 +99,10 @@ impl Context {
     pub fn chunked(&self) -> bool {
         self.chunked
     }
+
+    pub fn dialect(&self) -> SqlDialect {
+        self.dialect
+    }
 }
 
 impl SpanRecorderExt for Context {
@@ -117,6 +122,7 @@ pub struct ContextBuilder {
     database: String,
     precision: String,
     chunked: bool,
+    dialect: SqlDialect,
     session_config: CnosSessionConfig,
 }
 
@@ -128,6 +134,7 @@ impl ContextBuilder {
             tenant: DEFAULT_CATALOG.to_string(),
             database: DEFAULT_DATABASE.to_string(),
             chunked: Default::default(),
+            dialect: Default::default(),
             session_config: Default::default(),
         }
     }
@@ -176,6 +183,13 @@ impl ContextBuilder {
         self
     }
 
+    pub fn with_dialect(mut self, dialect: Option<SqlDialect>) -> Self {
+        if let Some(dialect) = dialect {
+            self.dialect = dialect;
+        }
+        self
+    }
+
     pub fn build(self) -> Context {
         Context {
             user_info: self.user_info,
@@ -183,6 +197,7 @@ impl ContextBuilder {
             database: self.database,
             precision: self.precision,
             chunked: self.chunked,
+            dialect: self.dialect,
             session_config: self.session_config,
         }
     }
@@ -13,7 +13,8 @@ use nom::IResult;
 
 use crate::error::SqlError;
 use crate::instance::{
-    run_lp_write, run_open_tsdb_json_write, run_open_tsdb_write, run_query, SqlClientOptions,
+    run_lp_write, run_open_tsdb_json_write, run_open_tsdb_write, run_prom_query, run_query,
+    SqlClientOptions,
 };
 
 type Result<T, E = SqlError> = std::result::Result<T, E>;
@@ -24,6 +25,7 @@ pub enum DBRequest {
     LineProtocol(String),
     OpenTSDBProtocol(String),
     OpenTSDBJson(String),
+    PromQL(String),
     Nothing,
 }
 
@@ -102,6 +104,11 @@ impl DBRequest {
                     &lines[i + 1..],
                     "OPENTSDB_JSON_END",
                 )?));
+            } else if instruction_match("PROMQL_BEGIN", line) {
+                return Ok(DBRequest::PromQL(parse_block(
+                    &lines[i + 1..],
+                    "PROMQL_END",
+                )?));
             } else if instruction_match("", line) {
                 options.parse_and_change(line)
             } else {
@@ -148,6 +155,10 @@ impl DBRequest {
                 run_open_tsdb_json_write(options, open_tsdb_json).await?;
                 Ok((Schema::empty(), vec![]))
             }
+            DBRequest::PromQL(params) => {
+                println!("[{}] Execute PromQL: \"{}\"", path.display(), params);
+                Ok(run_prom_query(options, params).await?)
+            }
             DBRequest::Nothing => {
                 println!("[{}] Execute Nothing", path.display());
                 Ok((Schema::empty(), vec![]))
@@ -1,8 +1,10 @@
 use std::path::PathBuf;
 use std::process::{Command, ExitStatus};
+use std::sync::Arc;
 use std::time::Duration;
 
-use arrow::datatypes::Schema;
+use arrow::array::StringArray;
+use arrow::datatypes::{DataType, Field, Schema};
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use arrow_flight::sql::client::FlightSqlServiceClient;
@@ -152,6 +154,39 @@ fn construct_opentsdb_json_url(option: &SqlClientOptions) -> Result<Url> {
     Ok(url)
 }
 
+/// Url of the prometheus query api, a range query if there is a `step` in the parameters,
+/// which are lines of `<name>=<value>`.
+fn construct_prom_query_url(option: &SqlClientOptions, params: &str) -> Result<Url> {
+    let SqlClientOptions {
+        http_host,
+        http_port,
+        tenant,
+        db,
+        ..
+    } = option;
+    let params = params
+        .lines()
+        .map(|line| {
+            line.trim().split_once('=').ok_or_else(|| SqlError::Other {
+                reason: format!("invalid PromQL parameter \"{line}\""),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let path = if params.iter().any(|(name, _)| *name == "step") {
+        "api/v1/query_range"
+    } else {
+        "api/v1/query"
+    };
+
+    let url = Url::parse(&format!("http://{}:{}", http_host, http_port))?;
+    let mut url = url.join(path)?;
+    url.query_pairs_mut()
+        .append_pair("db", db)
+        .append_pair("tenant", tenant)
+        .extend_pairs(params);
+    Ok(url)
+}
+
 fn build_http_write_request(option: &SqlClientOptions, url: Url, body: &str) -> Result<Request> {
     let request = Client::default()
         .request(Method::POST, url)
@@ -248,6 +283,31 @@ pub async fn run_open_tsdb_json_write(options: &SqlClientOptions, content: &str)
     }
 }
 
+/// Run a PromQL query, the result is the json body of the response in a row.
+pub async fn run_prom_query(
+    options: &SqlClientOptions,
+    params: &str,
+) -> Result<(Schema, Vec<RecordBatch>)> {
+    let request = Client::default()
+        .request(Method::GET, construct_prom_query_url(options, params)?)
+        .basic_auth::<&str, &str>(options.username.as_str(), None)
+        .build()?;
+    let client = Client::default();
+    let resp = client.execute(request).await?;
+    let status_code = resp.status();
+    let text = resp.text().await?;
+    if !status_code.is_success() {
+        return Err(SqlError::Http { err: text });
+    }
+
+    let schema = Schema::new(vec![Field::new("result", DataType::Utf8, false)]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.clone()),
+        vec![Arc::new(StringArray::from(vec![text]))],
+    )?;
+    Ok((schema, vec![batch]))
+}
+
 async fn flight_channel(host: &str, port: u16) -> Result<Channel> {
     let endpoint = Endpoint::new(format!("http://{}:{}", host, port))
         .map_err(|_| ArrowError::IoError("Cannot create endpoint".to_string()))?
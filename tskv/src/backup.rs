@@ -0,0 +1,464 @@
+//! Backup vnodes to an object store or a local directory, and restore them.
+//!
+//! Layout of files in a backup location:
+//! - `manifest.json`: the latest backup of the database, written by the coordinator.
+//! - `files/{vnode_id}/{tsm|delta}/{file}`: data files of the vnodes, they are immutable
+//!   so a data file is uploaded once and shared by the incremental backups of the vnode.
+//! - `{backup_id}/{vnode_id}/{tsm|delta}/{tombstone}`: tombstones of the data files.
+//! - `{backup_id}/{vnode_id}/index/{file}`: series index of the vnode.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path as StdPath, PathBuf};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::StreamExt;
+use models::meta_data::VnodeId;
+use object_store::aws::AmazonS3Builder;
+use object_store::azure::MicrosoftAzureBuilder;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use tokio::io::AsyncWriteExt;
+use trace::{debug, error};
+
+use crate::error::{self, Error, Result};
+use crate::file_system::file_manager::try_exists;
+use crate::kv_option::{DELTA_PATH, INDEX_PATH, TSM_PATH};
+use crate::summary::VersionEdit;
+use crate::vnode_store::VnodeStorage;
+use crate::{file_utils, ColumnFileId, EngineRef, VnodeSnapshot};
+
+pub const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+const BACKUP_FILES_PATH: &str = "files";
+
+/// Where a backup is stored, a local directory or a path in an object store.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupLocation {
+    /// `file`, `s3` or `azblob`.
+    pub scheme: String,
+    /// Bucket of s3, or container of azblob.
+    pub bucket: String,
+    /// The local directory, or the prefix of objects in the bucket.
+    pub path: String,
+    /// Connection options of the object store, named as the ones of `COPY INTO`.
+    pub options: HashMap<String, String>,
+}
+
+impl BackupLocation {
+    /// Whether the backup is in a local directory of the nodes.
+    pub fn is_local(&self) -> bool {
+        matches!(self.scheme.as_str(), "" | "file")
+    }
+}
+
+/// Backup of a vnode, taken from a snapshot of the vnode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VnodeBackup {
+    pub backup_id: String,
+    pub vnode_id: VnodeId,
+    /// Summary of the vnode in the snapshot, the data files are in the shared files directory.
+    pub summary: VersionEdit,
+    /// Tombstones and series index files of the backup, relative to the vnode directory.
+    pub mutable_files: Vec<String>,
+}
+
+impl VnodeBackup {
+    /// Ids of the data files of the vnode in this backup.
+    pub fn file_ids(&self) -> impl Iterator<Item = ColumnFileId> + '_ {
+        self.summary.add_files.iter().map(|f| f.file_id)
+    }
+
+    fn mutable_file_location(&self, relative: &str) -> String {
+        format!("{}/{}/{relative}", self.backup_id, self.vnode_id)
+    }
+}
+
+fn data_file_name(is_delta: bool, file_id: ColumnFileId) -> (&'static str, String) {
+    if is_delta {
+        (DELTA_PATH, file_utils::make_delta_file_name(file_id))
+    } else {
+        (TSM_PATH, file_utils::make_tsm_file_name(file_id))
+    }
+}
+
+fn data_file_location(vnode_id: VnodeId, is_delta: bool, file_id: ColumnFileId) -> String {
+    let (sub_dir, file_name) = data_file_name(is_delta, file_id);
+    format!("{BACKUP_FILES_PATH}/{vnode_id}/{sub_dir}/{file_name}")
+}
+
+pub struct BackupStore {
+    store: Arc<dyn ObjectStore>,
+    prefix: String,
+}
+
+impl BackupStore {
+    pub fn new(location: &BackupLocation) -> Result<Self> {
+        let options = &location.options;
+        let store: Arc<dyn ObjectStore> = match location.scheme.as_str() {
+            "s3" => {
+                let mut builder = AmazonS3Builder::new()
+                    .with_bucket_name(&location.bucket)
+                    .with_allow_http(true);
+                if let Some(region) = options.get("region") {
+                    builder = builder.with_region(region);
+                }
+                if let Some(endpoint_url) = options.get("endpoint_url") {
+                    builder = builder.with_endpoint(endpoint_url);
+                }
+                if let Some(access_key_id) = options.get("access_key_id") {
+                    builder = builder.with_access_key_id(access_key_id);
+                }
+                if let Some(secret_key) = options.get("secret_key") {
+                    builder = builder.with_secret_access_key(secret_key);
+                }
+                if let Some(token) = options.get("token") {
+                    builder = builder.with_token(token);
+                }
+                let virtual_hosted_style = options
+                    .get("virtual_hosted_style")
+                    .map_or(true, |v| v.eq_ignore_ascii_case("true"));
+                builder = builder.with_virtual_hosted_style_request(virtual_hosted_style);
+                Arc::new(builder.build().context(error::BackupStorageSnafu)?)
+            }
+            "azblob" => {
+                let use_emulator = options
+                    .get("use_emulator")
+                    .map_or(false, |v| v.eq_ignore_ascii_case("true"));
+                let mut builder = MicrosoftAzureBuilder::new()
+                    .with_container_name(&location.bucket)
+                    .with_use_emulator(use_emulator);
+                if let Some(account) = options.get("account") {
+                    builder = builder.with_account(account);
+                }
+                if let Some(access_key) = options.get("access_key") {
+                    builder = builder.with_access_key(access_key);
+                }
+                if let Some(bearer_token) = options.get("bearer_token") {
+                    builder = builder.with_bearer_token_authorization(bearer_token);
+                }
+                Arc::new(builder.build().context(error::BackupStorageSnafu)?)
+            }
+            "" | "file" => {
+                std::fs::create_dir_all(&location.path).context(error::IOSnafu)?;
+                let store = LocalFileSystem::new_with_prefix(&location.path)
+                    .context(error::BackupStorageSnafu)?;
+                return Ok(Self {
+                    store: Arc::new(store),
+                    prefix: String::new(),
+                });
+            }
+            scheme => {
+                return Err(Error::CommonError {
+                    reason: format!("unsupported scheme of backup location: {scheme}"),
+                })
+            }
+        };
+
+        Ok(Self {
+            store,
+            prefix: location.path.trim_matches('/').to_string(),
+        })
+    }
+
+    fn location(&self, relative: &str) -> Path {
+        if self.prefix.is_empty() {
+            Path::from(relative)
+        } else {
+            Path::from(format!("{}/{relative}", self.prefix))
+        }
+    }
+
+    /// Read an object, returns `None` if it does not exist.
+    pub async fn get(&self, relative: &str) -> Result<Option<Bytes>> {
+        match self.store.get(&self.location(relative)).await {
+            Ok(result) => Ok(Some(
+                result.bytes().await.context(error::BackupStorageSnafu)?,
+            )),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(Error::BackupStorage { source: e }),
+        }
+    }
+
+    pub async fn put(&self, relative: &str, data: Bytes) -> Result<()> {
+        self.store
+            .put(&self.location(relative), data)
+            .await
+            .context(error::BackupStorageSnafu)
+    }
+
+    /// Upload a local file as the object.
+    pub async fn upload(&self, path: impl AsRef<StdPath>, relative: &str) -> Result<()> {
+        let location = self.location(relative);
+        debug!(
+            "Backup: uploading {} to {location}.",
+            path.as_ref().display()
+        );
+        let mut file = tokio::fs::File::open(path.as_ref())
+            .await
+            .context(error::IOSnafu)?;
+        let (multipart_id, mut writer) = self
+            .store
+            .put_multipart(&location)
+            .await
+            .context(error::BackupStorageSnafu)?;
+
+        let res = async {
+            tokio::io::copy(&mut file, &mut writer).await?;
+            writer.shutdown().await
+        }
+        .await;
+        if let Err(e) = res {
+            if let Err(abort_err) = self.store.abort_multipart(&location, &multipart_id).await {
+                error!("Backup: failed to abort uploading '{location}': {abort_err}");
+            }
+            return Err(Error::IO { source: e });
+        }
+        Ok(())
+    }
+
+    /// Download the object to a local file.
+    pub async fn download(&self, relative: &str, path: impl AsRef<StdPath>) -> Result<()> {
+        let path = path.as_ref();
+        debug!(
+            "Backup: downloading {} to {}.",
+            self.location(relative),
+            path.display()
+        );
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .context(error::IOSnafu)?;
+        }
+        let mut stream = self
+            .store
+            .get(&self.location(relative))
+            .await
+            .context(error::BackupStorageSnafu)?
+            .into_stream();
+        let mut file = tokio::fs::File::create(path)
+            .await
+            .context(error::IOSnafu)?;
+        while let Some(bytes) = stream.next().await {
+            let bytes = bytes.context(error::BackupStorageSnafu)?;
+            file.write_all(&bytes).await.context(error::IOSnafu)?;
+        }
+        file.sync_all().await.context(error::IOSnafu)
+    }
+}
+
+/// Create a snapshot of the vnode and upload the files in it to the backup store,
+/// data files in `uploaded_files` are skipped, they were uploaded by a previous backup.
+pub async fn backup_vnode(
+    vnode: &VnodeStorage,
+    store: &BackupStore,
+    backup_id: &str,
+    uploaded_files: &HashSet<ColumnFileId>,
+) -> Result<VnodeBackup> {
+    let (snapshot, summary) = vnode.create_snapshot_with_summary().await?;
+    let snapshot_dir = vnode.ctx.options.storage.snapshot_sub_dir(
+        &models::schema::make_owner(&snapshot.tenant, &snapshot.database),
+        snapshot.vnode_id,
+        &snapshot.snapshot_id,
+    );
+
+    let result = upload_snapshot(
+        store,
+        backup_id,
+        &snapshot,
+        &snapshot_dir,
+        summary,
+        uploaded_files,
+    )
+    .await;
+    if let Err(e) = vnode.remove_snapshot(&snapshot).await {
+        error!(
+            "Backup: failed to remove snapshot {} of vnode {}: {e}",
+            snapshot.snapshot_id, snapshot.vnode_id
+        );
+    }
+    result
+}
+
+async fn upload_snapshot(
+    store: &BackupStore,
+    backup_id: &str,
+    snapshot: &VnodeSnapshot,
+    snapshot_dir: &StdPath,
+    summary: VersionEdit,
+    uploaded_files: &HashSet<ColumnFileId>,
+) -> Result<VnodeBackup> {
+    let mut backup = VnodeBackup {
+        backup_id: backup_id.to_string(),
+        vnode_id: snapshot.vnode_id,
+        summary,
+        mutable_files: vec![],
+    };
+
+    for f in backup.summary.add_files.iter() {
+        let (sub_dir, file_name) = data_file_name(f.is_delta, f.file_id);
+        let file_dir = snapshot_dir.join(sub_dir);
+        if !uploaded_files.contains(&f.file_id) {
+            store
+                .upload(
+                    file_dir.join(file_name),
+                    &data_file_location(backup.vnode_id, f.is_delta, f.file_id),
+                )
+                .await?;
+        }
+
+        let tombstone = file_utils::make_tsm_tombstone_file(&file_dir, f.file_id);
+        if try_exists(&tombstone) {
+            let relative = format!(
+                "{sub_dir}/{}",
+                file_utils::make_tsm_tombstone_file_name(f.file_id)
+            );
+            store
+                .upload(&tombstone, &backup.mutable_file_location(&relative))
+                .await?;
+            backup.mutable_files.push(relative);
+        }
+    }
+
+    let index_dir = snapshot_dir.join(INDEX_PATH);
+    if try_exists(&index_dir) {
+        for entry in walkdir::WalkDir::new(&index_dir) {
+            let entry = entry.map_err(|e| Error::IO { source: e.into() })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = match entry.path().strip_prefix(snapshot_dir) {
+                Ok(p) => p.to_string_lossy().to_string(),
+                Err(_) => continue,
+            };
+            store
+                .upload(entry.path(), &backup.mutable_file_location(&relative))
+                .await?;
+            backup.mutable_files.push(relative);
+        }
+    }
+
+    Ok(backup)
+}
+
+/// Download the files of the vnode backup into the move directory of the vnode,
+/// then build the vnode from the summary in the backup.
+pub async fn restore_vnode(
+    engine: &EngineRef,
+    tenant: &str,
+    database: &str,
+    vnode_id: VnodeId,
+    store: &BackupStore,
+    backup: VnodeBackup,
+) -> Result<()> {
+    let owner = models::schema::make_owner(tenant, database);
+    let move_dir = engine.get_storage_options().move_dir(&owner, vnode_id);
+    if let Err(e) = download_backup(store, &move_dir, &backup).await {
+        let _ = tokio::fs::remove_dir_all(&move_dir).await;
+        return Err(e);
+    }
+
+    let mut summary = backup.summary;
+    summary.tsf_id = vnode_id;
+    summary.tsf_name = owner;
+    for f in summary.add_files.iter_mut() {
+        f.tsf_id = vnode_id;
+    }
+    engine
+        .apply_vnode_summary(tenant, database, vnode_id, summary)
+        .await
+}
+
+async fn download_backup(
+    store: &BackupStore,
+    move_dir: &StdPath,
+    backup: &VnodeBackup,
+) -> Result<()> {
+    for f in backup.summary.add_files.iter() {
+        let (sub_dir, file_name) = data_file_name(f.is_delta, f.file_id);
+        store
+            .download(
+                &data_file_location(backup.vnode_id, f.is_delta, f.file_id),
+                move_dir.join(sub_dir).join(file_name),
+            )
+            .await?;
+    }
+    for relative in backup.mutable_files.iter() {
+        store
+            .download(
+                &backup.mutable_file_location(relative),
+                move_dir.join(relative),
+            )
+            .await?;
+    }
+    // The index directory is moved when building the vnode, it must exist.
+    tokio::fs::create_dir_all(move_dir.join(INDEX_PATH))
+        .await
+        .context(error::IOSnafu)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backup_file_locations() {
+        assert_eq!(
+            data_file_location(3, false, 12),
+            "files/3/tsm/_000012.tsm".to_string()
+        );
+        assert_eq!(
+            data_file_location(3, true, 12),
+            "files/3/delta/_000012.delta".to_string()
+        );
+
+        let backup = VnodeBackup {
+            backup_id: "20231017_120000_000".to_string(),
+            vnode_id: 3,
+            summary: VersionEdit::new(3),
+            mutable_files: vec![],
+        };
+        assert_eq!(
+            backup.mutable_file_location("tsm/_000012.tombstone"),
+            "20231017_120000_000/3/tsm/_000012.tombstone".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_backup_store() {
+        let dir = "/tmp/test/backup/local_store";
+        let _ = std::fs::remove_dir_all(dir);
+        let store = BackupStore::new(&BackupLocation {
+            scheme: "file".to_string(),
+            path: dir.to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(store.get(BACKUP_MANIFEST_FILE).await.unwrap().is_none());
+        store
+            .put(BACKUP_MANIFEST_FILE, Bytes::from_static(b"{}"))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get(BACKUP_MANIFEST_FILE).await.unwrap(),
+            Some(Bytes::from_static(b"{}"))
+        );
+
+        let downloaded = PathBuf::from(dir).join("downloaded").join("manifest");
+        store
+            .download(BACKUP_MANIFEST_FILE, &downloaded)
+            .await
+            .unwrap();
+        store
+            .upload(&downloaded, "uploaded/manifest")
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get("uploaded/manifest").await.unwrap(),
+            Some(Bytes::from_static(b"{}"))
+        );
+    }
+}
@@ -0,0 +1,731 @@
+//! Cold storage of tsm files.
+//!
+//! Tsm files of a vnode whose `max_ts` is older than the `COLD_DURATION` of the
+//! database are uploaded to an object store, then the local files are replaced by
+//! the uploaded ones through a `VersionEdit`. The tombstones of cold files stay in
+//! the local tsm directory, queries read cold files by ranged requests.
+
+use std::collections::HashMap;
+use std::path::Path as StdPath;
+use std::sync::Arc;
+
+use config::ColdStorageConfig;
+use futures::StreamExt;
+use models::Timestamp;
+use object_store::aws::AmazonS3Builder;
+use object_store::azure::MicrosoftAzureBuilder;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use once_cell::sync::OnceCell;
+use snafu::ResultExt;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{oneshot, RwLock};
+use trace::{error, info};
+use utils::BloomFilter;
+
+use crate::error::{self, Error, Result};
+use crate::file_system::file::object_file::ObjectFile;
+use crate::summary::{CompactMeta, SummaryTask, VersionEdit};
+use crate::tseries_family::{ColumnFile, TseriesFamily, Version};
+use crate::{ColumnFileId, TsKvContext, TseriesFamilyId};
+
+static COLD_STORE: OnceCell<ColdStore> = OnceCell::new();
+
+pub struct ColdStore {
+    store: Arc<dyn ObjectStore>,
+    prefix: String,
+}
+
+impl ColdStore {
+    pub fn new(config: &ColdStorageConfig) -> Result<Self> {
+        let prefix = config.path.trim_matches('/').to_string();
+        let store: Arc<dyn ObjectStore> = match config.kind.as_str() {
+            "s3" => {
+                let mut builder = AmazonS3Builder::new()
+                    .with_bucket_name(config.bucket.clone().unwrap_or_default())
+                    .with_virtual_hosted_style_request(config.virtual_hosted_style_request)
+                    .with_allow_http(true);
+                if let Some(region) = &config.region {
+                    builder = builder.with_region(region);
+                }
+                if let Some(endpoint_url) = &config.endpoint_url {
+                    builder = builder.with_endpoint(endpoint_url);
+                }
+                if let Some(access_key_id) = &config.access_key_id {
+                    builder = builder.with_access_key_id(access_key_id);
+                }
+                if let Some(secret_access_key) = &config.secret_access_key {
+                    builder = builder.with_secret_access_key(secret_access_key);
+                }
+                if let Some(security_token) = &config.security_token {
+                    builder = builder.with_token(security_token);
+                }
+                Arc::new(builder.build().context(error::ColdStorageSnafu)?)
+            }
+            "gcs" => {
+                let mut builder = GoogleCloudStorageBuilder::new()
+                    .with_bucket_name(config.bucket.clone().unwrap_or_default());
+                if let Some(path) = &config.service_account_path {
+                    builder = builder.with_service_account_path(path);
+                }
+                Arc::new(builder.build().context(error::ColdStorageSnafu)?)
+            }
+            "azblob" => {
+                let mut builder = MicrosoftAzureBuilder::new()
+                    .with_container_name(config.bucket.clone().unwrap_or_default());
+                if let Some(account_name) = &config.account_name {
+                    builder = builder.with_account(account_name);
+                }
+                if let Some(access_key) = &config.access_key {
+                    builder = builder.with_access_key(access_key);
+                }
+                Arc::new(builder.build().context(error::ColdStorageSnafu)?)
+            }
+            _ => {
+                std::fs::create_dir_all(&config.path).context(error::IOSnafu)?;
+                let store = LocalFileSystem::new_with_prefix(&config.path)
+                    .context(error::ColdStorageSnafu)?;
+                return Ok(Self {
+                    store: Arc::new(store),
+                    prefix: String::new(),
+                });
+            }
+        };
+
+        Ok(Self { store, prefix })
+    }
+
+    /// Location of a tsm file in the object store: `{prefix}/{owner}/{vnode_id}/_{file_id}.tsm`.
+    pub fn location(
+        &self,
+        owner: &str,
+        vnode_id: TseriesFamilyId,
+        file_id: ColumnFileId,
+    ) -> String {
+        let file_name = format!("_{:06}.tsm", file_id);
+        if self.prefix.is_empty() {
+            format!("{owner}/{vnode_id}/{file_name}")
+        } else {
+            format!("{}/{owner}/{vnode_id}/{file_name}", self.prefix)
+        }
+    }
+
+    pub async fn open_file(&self, location: &str) -> std::io::Result<ObjectFile> {
+        ObjectFile::open(self.store.clone(), Path::from(location)).await
+    }
+
+    /// Upload a local file to the location in the object store.
+    pub async fn upload(&self, path: impl AsRef<StdPath>, location: &str) -> Result<()> {
+        let location = Path::from(location);
+        let mut file = tokio::fs::File::open(path.as_ref())
+            .await
+            .context(error::IOSnafu)?;
+        let (multipart_id, mut writer) = self
+            .store
+            .put_multipart(&location)
+            .await
+            .context(error::ColdStorageSnafu)?;
+
+        let res = async {
+            tokio::io::copy(&mut file, &mut writer).await?;
+            writer.shutdown().await
+        }
+        .await;
+        if let Err(e) = res {
+            if let Err(abort_err) = self.store.abort_multipart(&location, &multipart_id).await {
+                error!("Failed to abort uploading '{location}': {abort_err}");
+            }
+            return Err(Error::IO { source: e });
+        }
+        Ok(())
+    }
+
+    /// Download the file at the location in the object store to a local file.
+    pub async fn download(&self, location: &str, path: impl AsRef<StdPath>) -> Result<()> {
+        let mut stream = self
+            .store
+            .get(&Path::from(location))
+            .await
+            .context(error::ColdStorageSnafu)?
+            .into_stream();
+        let mut file = tokio::fs::File::create(path.as_ref())
+            .await
+            .context(error::IOSnafu)?;
+        while let Some(bytes) = stream.next().await {
+            let bytes = bytes.context(error::ColdStorageSnafu)?;
+            file.write_all(&bytes).await.context(error::IOSnafu)?;
+        }
+        file.sync_all().await.context(error::IOSnafu)
+    }
+
+    pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        self.store
+            .copy(&Path::from(from), &Path::from(to))
+            .await
+            .context(error::ColdStorageSnafu)
+    }
+
+    pub async fn delete(&self, location: &str) -> Result<()> {
+        self.store
+            .delete(&Path::from(location))
+            .await
+            .context(error::ColdStorageSnafu)
+    }
+}
+
+/// Initialize the global cold store, does nothing if cold storage is not configured.
+pub fn init_cold_store(config: Option<&ColdStorageConfig>) -> Result<()> {
+    if let Some(config) = config {
+        COLD_STORE.get_or_try_init(|| ColdStore::new(config))?;
+    }
+    Ok(())
+}
+
+pub fn get_cold_store() -> Result<&'static ColdStore> {
+    COLD_STORE.get().ok_or_else(|| Error::CommonError {
+        reason: "cold storage is not configured".to_string(),
+    })
+}
+
+pub async fn open_file(location: &str) -> Result<ObjectFile> {
+    get_cold_store()?
+        .open_file(location)
+        .await
+        .map_err(|e| Error::OpenFile {
+            path: location.into(),
+            source: e,
+        })
+}
+
+pub async fn delete(location: &str) -> Result<()> {
+    get_cold_store()?.delete(location).await
+}
+
+/// Copy a tsm file in the cold store to the location of the file of another vnode,
+/// returns the new location. Each vnode owns the objects of it's files, so that
+/// compaction, TTL or dropping of one vnode doesn't delete the files of another.
+pub async fn copy_file(
+    from: &str,
+    owner: &str,
+    vnode_id: TseriesFamilyId,
+    file_id: ColumnFileId,
+) -> Result<String> {
+    let cold_store = get_cold_store()?;
+    let location = cold_store.location(owner, vnode_id, file_id);
+    cold_store.copy(from, &location).await?;
+    info!("Copied tsm file at '{from}' to '{location}'");
+    Ok(location)
+}
+
+/// Delete the tsm files of the version in the cold store, used when the vnode is removed.
+pub async fn delete_version_files(version: &Version) {
+    for file in version.levels_info().iter().flat_map(|l| l.files.iter()) {
+        if let Some(location) = file.cold_location() {
+            match delete(location).await {
+                Ok(()) => info!("Removed tsm file {} at '{location}'", file.file_id()),
+                Err(e) => error!(
+                    "Failed to remove tsm file {} at '{location}': {e}",
+                    file.file_id()
+                ),
+            }
+        }
+    }
+}
+
+/// Pick local tsm files in level 1-4 whose data are all older than `cold_ts`.
+fn pick_cold_files(version: &Version, cold_ts: Timestamp) -> Vec<Arc<ColumnFile>> {
+    let mut files = Vec::new();
+    for level in version.levels_info().iter().skip(1) {
+        for file in level.files.iter() {
+            if file.is_cold() || file.is_deleted() || file.time_range().max_ts >= cold_ts {
+                continue;
+            }
+            if !file.mark_compacting() {
+                continue;
+            }
+            files.push(file.clone());
+        }
+    }
+    files
+}
+
+/// Tsm files uploaded to the cold store, the local files are still marked compacting
+/// until the `VersionEdit` is written to summary.
+pub struct MovedColdFiles {
+    pub version_edit: VersionEdit,
+    pub file_metas: HashMap<ColumnFileId, Arc<BloomFilter>>,
+    pub files: Vec<Arc<ColumnFile>>,
+}
+
+impl MovedColdFiles {
+    /// Delete the uploaded objects and unmark the local files, used when the
+    /// `VersionEdit` failed to be written to summary.
+    pub async fn rollback(self) {
+        for meta in self.version_edit.add_files.iter() {
+            if let Some(location) = &meta.cold_location {
+                if let Err(e) = delete(location).await {
+                    error!("Failed to delete '{location}' from cold storage: {e}");
+                }
+            }
+        }
+        self.files.iter().for_each(|f| f.unmark_compacting());
+    }
+}
+
+/// Move cold tsm files of the vnode to the cold store, returns a `VersionEdit`
+/// that replaces the local files with the uploaded ones.
+pub async fn move_cold_files(
+    tsf: &Arc<RwLock<TseriesFamily>>,
+    cold_ts: Timestamp,
+) -> Result<Option<MovedColdFiles>> {
+    let (version, owner, tsf_id) = {
+        let tsf = tsf.read().await;
+        (tsf.version(), tsf.tenant_database(), tsf.tf_id())
+    };
+    let files = pick_cold_files(&version, cold_ts);
+    if files.is_empty() {
+        return Ok(None);
+    }
+    let cold_store = match get_cold_store() {
+        Ok(s) => s,
+        Err(e) => {
+            files.iter().for_each(|f| f.unmark_compacting());
+            return Err(e);
+        }
+    };
+
+    let mut version_edit = VersionEdit::new(tsf_id);
+    let mut file_metas = HashMap::new();
+    let mut moved_files = Vec::with_capacity(files.len());
+    for file in files {
+        let location = cold_store.location(&owner, tsf_id, file.file_id());
+        if let Err(e) = cold_store.upload(file.file_path(), &location).await {
+            error!(
+                "Failed to move tsm file '{}' to cold storage: {e}",
+                file.file_path().display()
+            );
+            file.unmark_compacting();
+            continue;
+        }
+        info!(
+            "Moved tsm file '{}' to cold storage '{location}'",
+            file.file_path().display()
+        );
+        let mut meta = CompactMeta::from(file.as_ref());
+        meta.tsf_id = tsf_id;
+        meta.cold_location = Some(location);
+        version_edit.del_file(file.level(), file.file_id(), file.is_delta());
+        version_edit.add_file(meta, version.max_level_ts());
+        file_metas.insert(file.file_id(), file.field_id_filter());
+        moved_files.push(file);
+    }
+    if version_edit.add_files.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(MovedColdFiles {
+        version_edit,
+        file_metas,
+        files: moved_files,
+    }))
+}
+
+/// Check all vnodes for cold tsm files, and write the `VersionEdit`s to summary.
+pub async fn run_cold_storage_check(ctx: &TsKvContext) {
+    let dbs: Vec<_> = ctx
+        .version_set
+        .read()
+        .await
+        .get_all_db()
+        .values()
+        .cloned()
+        .collect();
+    for db in dbs {
+        let (cold_ts, vnodes) = {
+            let db = db.read().await;
+            let cold_ts = match db.get_schema() {
+                Ok(schema) => schema.time_to_cold(),
+                Err(_) => None,
+            };
+            (
+                cold_ts,
+                db.ts_families().values().cloned().collect::<Vec<_>>(),
+            )
+        };
+        let cold_ts = match cold_ts {
+            Some(ts) => ts,
+            None => continue,
+        };
+        for vnode in vnodes {
+            match move_cold_files(&vnode, cold_ts).await {
+                Ok(Some(moved)) => {
+                    let (summary_tx, summary_rx) = oneshot::channel();
+                    let task = SummaryTask::new(
+                        vec![moved.version_edit.clone()],
+                        Some(moved.file_metas.clone()),
+                        None,
+                        summary_tx,
+                    );
+                    let res = match ctx.summary_task_sender.send(task).await {
+                        Ok(()) => summary_rx.await,
+                        Err(e) => {
+                            error!("Failed to send summary task for cold tsm files: {e}");
+                            moved.rollback().await;
+                            continue;
+                        }
+                    };
+                    match res {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => {
+                            error!("Failed to write summary for cold tsm files: {e}");
+                            moved.rollback().await;
+                        }
+                        // The summary may be written, keep the uploaded files.
+                        Err(e) => error!("Failed to receive result of summary task: {e}"),
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => error!("Failed to move tsm files to cold storage: {e}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use config::{ColdStorageConfig, Config};
+    use futures::TryStreamExt;
+    use memory_pool::GreedyMemoryPool;
+    use meta::model::meta_admin::AdminMeta;
+    use meta::model::MetaRef;
+    use metrics::metric_register::MetricsRegister;
+    use models::predicate::domain::TimeRanges;
+    use models::schema::{make_owner, DatabaseSchema, TenantOptions};
+    use models::{FieldId, ValueType};
+    use object_store::path::Path;
+    use tokio::runtime::{self, Runtime};
+    use tokio::sync::RwLock;
+    use utils::BloomFilter;
+
+    use super::{get_cold_store, move_cold_files};
+    use crate::kv_option::INDEX_PATH;
+    use crate::memcache::DataType;
+    use crate::reader::Level14TSDataStream;
+    use crate::summary::{CompactMeta, VersionEdit};
+    use crate::tseries_family::TseriesFamily;
+    use crate::tsm::codec::DataBlockEncoding;
+    use crate::tsm::{self, DataBlock};
+    use crate::{ColumnFileId, Engine, Options, TsKv, TseriesFamilyId};
+
+    /// The cold store is global, all tests use the same directory.
+    const COLD_STORAGE_DIR: &str = "/tmp/test/cold_storage/bucket";
+    const TENANT: &str = "cnosdb";
+    const FIELD_ID: FieldId = 1;
+
+    fn test_config(dir: &str) -> Config {
+        let _ = std::fs::remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let mut config = config::get_config_for_test();
+        config.storage.path = format!("{dir}/data");
+        config.wal.path = format!("{dir}/wal");
+        config.log.path = format!("{dir}/log");
+        config.storage.cold_storage = Some(ColdStorageConfig {
+            path: COLD_STORAGE_DIR.to_string(),
+            ..Default::default()
+        });
+        config
+    }
+
+    fn test_runtime() -> Arc<Runtime> {
+        Arc::new(
+            runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        )
+    }
+
+    async fn open_tskv(config: &Config, runtime: Arc<Runtime>, database: &str) -> TsKv {
+        let meta: MetaRef = AdminMeta::new(config.clone()).await;
+        meta.add_data_node().await.unwrap();
+        // Create tenant may get 'TenantAlreadyExists.
+        let _ = meta
+            .create_tenant(TENANT.to_string(), TenantOptions::default())
+            .await;
+        let meta_client = meta.tenant_meta(TENANT).await.unwrap();
+        let _ = meta_client.drop_db(database).await;
+        meta_client
+            .create_db(DatabaseSchema::new(TENANT, database))
+            .await
+            .unwrap();
+
+        TsKv::open(
+            meta,
+            Options::from(config),
+            runtime,
+            Arc::new(GreedyMemoryPool::default()),
+            Arc::new(MetricsRegister::default()),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn data_block() -> DataBlock {
+        DataBlock::I64 {
+            ts: vec![1, 2, 3],
+            val: vec![10, 20, 30],
+            enc: DataBlockEncoding::default(),
+        }
+    }
+
+    /// Add a vnode with a local tsm file of `data_block()` in level 1.
+    async fn add_vnode(
+        engine: &TsKv,
+        database: &str,
+        vnode_id: TseriesFamilyId,
+    ) -> Arc<RwLock<TseriesFamily>> {
+        let ctx = engine.context();
+        let db = engine
+            .get_db_or_else_create(TENANT, database)
+            .await
+            .unwrap();
+        let tsf = db
+            .write()
+            .await
+            .add_tsfamily(vnode_id, None, ctx.clone())
+            .await
+            .unwrap();
+
+        let tsm_dir = ctx
+            .options
+            .storage
+            .tsm_dir(&make_owner(TENANT, database), vnode_id);
+        std::fs::create_dir_all(&tsm_dir).unwrap();
+        let file_id = ctx.global_ctx.file_id_next();
+        let mut writer = tsm::new_tsm_writer(&tsm_dir, file_id, false, 0)
+            .await
+            .unwrap();
+        writer.write_block(FIELD_ID, &data_block()).await.unwrap();
+        writer.write_index().await.unwrap();
+        writer.finish().await.unwrap();
+
+        let mut version_edit = VersionEdit::new(vnode_id);
+        version_edit.add_file(
+            CompactMeta {
+                file_id,
+                file_size: writer.size(),
+                tsf_id: vnode_id,
+                level: 1,
+                min_ts: writer.min_ts(),
+                max_ts: writer.max_ts(),
+                ..Default::default()
+            },
+            writer.max_ts(),
+        );
+        let file_metas = HashMap::from([(file_id, Arc::new(writer.bloom_filter_cloned()))]);
+        apply_version_edit(&tsf, version_edit, file_metas).await;
+        tsf
+    }
+
+    async fn apply_version_edit(
+        tsf: &Arc<RwLock<TseriesFamily>>,
+        version_edit: VersionEdit,
+        mut file_metas: HashMap<ColumnFileId, Arc<BloomFilter>>,
+    ) {
+        let mut tsf = tsf.write().await;
+        let version =
+            tsf.version()
+                .copy_apply_version_edits(vec![version_edit], &mut file_metas, None);
+        tsf.new_version(version, None);
+    }
+
+    /// Move the tsm files of the vnode to the cold store.
+    async fn move_vnode_to_cold(tsf: &Arc<RwLock<TseriesFamily>>) {
+        let moved = move_cold_files(tsf, i64::MAX).await.unwrap().unwrap();
+        apply_version_edit(tsf, moved.version_edit, moved.file_metas).await;
+    }
+
+    /// Locations of the objects in the cold store under the prefix.
+    async fn cold_objects(prefix: &str) -> Vec<String> {
+        get_cold_store()
+            .unwrap()
+            .store
+            .list(Some(&Path::from(prefix)))
+            .await
+            .unwrap()
+            .map_ok(|meta| meta.location.to_string())
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    /// Read all values of `FIELD_ID` in level 1-4 of the vnode.
+    async fn scan(tsf: &Arc<RwLock<TseriesFamily>>) -> Vec<(i64, i64)> {
+        let version = tsf.read().await.version();
+        let time_ranges = Arc::new(TimeRanges::all());
+        let files = version
+            .get_level_files(&time_ranges, FIELD_ID)
+            .into_iter()
+            .skip(1)
+            .flatten()
+            .flatten()
+            .collect();
+        let mut stream = Level14TSDataStream::new(
+            version,
+            time_ranges,
+            files,
+            FIELD_ID,
+            ValueType::Integer,
+            None,
+        )
+        .await
+        .unwrap();
+        let mut values = vec![];
+        while let Some(data) = stream.next_data().await.unwrap() {
+            match data {
+                DataType::I64(ts, val) => values.push((ts, val)),
+                other => panic!("unexpected data {other}"),
+            }
+        }
+        values
+    }
+
+    #[test]
+    fn test_move_cold_files() {
+        let dir = "/tmp/test/cold_storage/move_cold_files";
+        let database = "test_move_cold_files";
+        let owner = make_owner(TENANT, database);
+        let vnode_id = 1;
+        let config = test_config(dir);
+        let rt = test_runtime();
+        rt.block_on(async {
+            let engine = open_tskv(&config, rt.clone(), database).await;
+            let tsf = add_vnode(&engine, database, vnode_id).await;
+            let local_file = tsf.read().await.version().levels_info()[1].files[0].clone();
+
+            // Files newer than the cold timestamp are not moved.
+            let cold_ts = local_file.time_range().max_ts;
+            assert!(move_cold_files(&tsf, cold_ts).await.unwrap().is_none());
+            assert!(!local_file.is_compacting());
+
+            let moved = move_cold_files(&tsf, cold_ts + 1).await.unwrap().unwrap();
+            assert!(local_file.is_compacting());
+            let location = format!("{owner}/{vnode_id}/_{:06}.tsm", local_file.file_id());
+            assert_eq!(moved.version_edit.del_files.len(), 1);
+            assert_eq!(moved.version_edit.add_files.len(), 1);
+            assert_eq!(
+                moved.version_edit.add_files[0].cold_location.as_deref(),
+                Some(location.as_str())
+            );
+            assert_eq!(cold_objects(&owner).await, vec![location.clone()]);
+
+            apply_version_edit(&tsf, moved.version_edit, moved.file_metas).await;
+            assert!(local_file.is_deleted());
+            let version = tsf.read().await.version();
+            let cold_file = &version.levels_info()[1].files[0];
+            assert_eq!(cold_file.file_id(), local_file.file_id());
+            assert_eq!(cold_file.cold_location(), Some(location.as_str()));
+
+            // Cold files are not moved again.
+            assert!(move_cold_files(&tsf, i64::MAX).await.unwrap().is_none());
+
+            engine.drop_database(TENANT, database).await.unwrap();
+            engine.close().await;
+        });
+    }
+
+    #[test]
+    fn test_scan_cold_files() {
+        let dir = "/tmp/test/cold_storage/scan_cold_files";
+        let database = "test_scan_cold_files";
+        let config = test_config(dir);
+        let rt = test_runtime();
+        rt.block_on(async {
+            let engine = open_tskv(&config, rt.clone(), database).await;
+            let tsf = add_vnode(&engine, database, 1).await;
+            let expected = vec![(1, 10), (2, 20), (3, 30)];
+            assert_eq!(scan(&tsf).await, expected);
+
+            move_vnode_to_cold(&tsf).await;
+            let local_file = tsf.read().await.version().levels_info()[1].files[0].clone();
+            assert!(local_file.is_cold());
+            assert_eq!(scan(&tsf).await, expected);
+
+            engine.drop_database(TENANT, database).await.unwrap();
+            engine.close().await;
+        });
+    }
+
+    #[test]
+    fn test_copy_cold_files() {
+        let dir = "/tmp/test/cold_storage/copy_cold_files";
+        let database = "test_copy_cold_files";
+        let owner = make_owner(TENANT, database);
+        let config = test_config(dir);
+        let rt = test_runtime();
+        rt.block_on(async {
+            let engine = open_tskv(&config, rt.clone(), database).await;
+            let ctx = engine.context();
+            let src_tsf = add_vnode(&engine, database, 1).await;
+            move_vnode_to_cold(&src_tsf).await;
+
+            // Copy vnode 1 to vnode 2 as COPY VNODE does, the local files are
+            // downloaded to the move directory.
+            let version_edit = src_tsf.read().await.build_version_edit(&mut HashMap::new());
+            std::fs::create_dir_all(ctx.options.storage.move_dir(&owner, 2).join(INDEX_PATH))
+                .unwrap();
+            let db = engine.get_db(TENANT, database).await.unwrap();
+            let dst_tsf = db
+                .write()
+                .await
+                .add_tsfamily(2, Some(version_edit), ctx.clone())
+                .await
+                .unwrap();
+
+            let version = dst_tsf.read().await.version();
+            let dst_file = &version.levels_info()[1].files[0];
+            let location = format!("{owner}/2/_{:06}.tsm", dst_file.file_id());
+            assert_eq!(dst_file.cold_location(), Some(location.as_str()));
+            assert_eq!(cold_objects(&format!("{owner}/2")).await, vec![location]);
+
+            // The copied vnode still has it's data after the source vnode is dropped.
+            engine.drop_vnode(1).await.unwrap();
+            assert!(cold_objects(&format!("{owner}/1")).await.is_empty());
+            assert_eq!(scan(&dst_tsf).await, vec![(1, 10), (2, 20), (3, 30)]);
+
+            engine.drop_database(TENANT, database).await.unwrap();
+            engine.close().await;
+        });
+    }
+
+    #[test]
+    fn test_drop_cold_files() {
+        let dir = "/tmp/test/cold_storage/drop_cold_files";
+        let database = "test_drop_cold_files";
+        let owner = make_owner(TENANT, database);
+        let config = test_config(dir);
+        let rt = test_runtime();
+        rt.block_on(async {
+            let engine = open_tskv(&config, rt.clone(), database).await;
+            for vnode_id in [1, 2] {
+                let tsf = add_vnode(&engine, database, vnode_id).await;
+                move_vnode_to_cold(&tsf).await;
+                assert_eq!(cold_objects(&format!("{owner}/{vnode_id}")).await.len(), 1);
+            }
+
+            engine.drop_vnode(1).await.unwrap();
+            assert!(cold_objects(&format!("{owner}/1")).await.is_empty());
+            assert_eq!(cold_objects(&format!("{owner}/2")).await.len(), 1);
+
+            engine.drop_database(TENANT, database).await.unwrap();
+            assert!(cold_objects(&owner).await.is_empty());
+
+            engine.close().await;
+        });
+    }
+}
@@ -1,6 +1,5 @@
 use std::cmp;
 use std::collections::HashMap;
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use blake3::Hasher;
@@ -16,7 +15,7 @@ use tokio::sync::RwLock;
 use super::CompactingBlockMeta;
 use crate::compaction::CompactIterator;
 use crate::error::{Error, Result};
-use crate::tseries_family::TseriesFamily;
+use crate::tseries_family::{ColumnFile, TseriesFamily};
 use crate::tsm::{DataBlock, TsmReader};
 use crate::TseriesFamilyId;
 
@@ -294,13 +293,13 @@ pub(crate) async fn vnode_hash_tree(
         (vnode_rlock.version(), vnode_rlock.tf_id())
     };
     let mut readers: Vec<Arc<TsmReader>> = Vec::new();
-    let tsm_paths: Vec<&PathBuf> = version
+    let column_files: Vec<&Arc<ColumnFile>> = version
         .levels_info()
         .iter()
-        .flat_map(|l| l.files.iter().map(|f| f.file_path()))
+        .flat_map(|l| l.files.iter())
         .collect();
-    for p in tsm_paths {
-        let r = version.get_tsm_reader(p).await?;
+    for f in column_files {
+        let r = version.get_column_file_reader(f).await?;
         readers.push(r);
     }
 
@@ -462,6 +461,48 @@ fn hash_partial_datablock(
             }
             Some((limit, after_max_ts))
         }
+        DataBlock::I128 { ts, val, .. } => {
+            let (max_ts_i, after_max_ts) = find_value_index_and_next(&ts[min_idx..], max_timestamp);
+            if max_ts_i == 0 {
+                return None;
+            }
+            let limit = min_idx + max_ts_i;
+            let ts_iter = ts[min_idx..limit].iter().map(|v| v.to_be_bytes());
+            let val_iter = val[min_idx..limit].iter().map(|v| v.to_be_bytes());
+            for (t, v) in ts_iter.zip(val_iter) {
+                hasher.update(&t);
+                hasher.update(&v);
+            }
+            Some((limit, after_max_ts))
+        }
+        DataBlock::I32 { ts, val, .. } => {
+            let (max_ts_i, after_max_ts) = find_value_index_and_next(&ts[min_idx..], max_timestamp);
+            if max_ts_i == 0 {
+                return None;
+            }
+            let limit = min_idx + max_ts_i;
+            let ts_iter = ts[min_idx..limit].iter().map(|v| v.to_be_bytes());
+            let val_iter = val[min_idx..limit].iter().map(|v| v.to_be_bytes());
+            for (t, v) in ts_iter.zip(val_iter) {
+                hasher.update(&t);
+                hasher.update(&v);
+            }
+            Some((limit, after_max_ts))
+        }
+        DataBlock::F32 { ts, val, .. } => {
+            let (max_ts_i, after_max_ts) = find_value_index_and_next(&ts[min_idx..], max_timestamp);
+            if max_ts_i == 0 {
+                return None;
+            }
+            let limit = min_idx + max_ts_i;
+            let ts_iter = ts[min_idx..limit].iter().map(|v| v.to_be_bytes());
+            let val_iter = val[min_idx..limit].iter().map(|v| v.to_be_bytes());
+            for (t, v) in ts_iter.zip(val_iter) {
+                hasher.update(&t);
+                hasher.update(&v);
+            }
+            Some((limit, after_max_ts))
+        }
     }
 }
 
@@ -802,6 +843,7 @@ mod test {
                         bool_vec.push((*t, if *v { vec![1_u8] } else { vec![0_u8] }));
                     }
                 }
+                DataBlock::I128 { .. } | DataBlock::I32 { .. } | DataBlock::F32 { .. } => {}
             }
         }
 
@@ -671,7 +671,7 @@ pub async fn run_compaction_job(
     let tsf_id = request.ts_family_id;
     let mut tsm_readers = Vec::new();
     for col_file in request.files.iter() {
-        let tsm_reader = request.version.get_tsm_reader(col_file.file_path()).await?;
+        let tsm_reader = request.version.get_column_file_reader(col_file).await?;
         tsm_readers.push(tsm_reader);
     }
 
@@ -885,6 +885,7 @@ fn new_compact_meta(
         high_seq: 0,
         low_seq: 0,
         is_delta: false,
+        cold_location: None,
     }
 }
 
@@ -1344,6 +1345,51 @@ pub mod test {
                     enc: DataBlockEncoding::default(),
                 }
             }
+            ValueType::Decimal => {
+                let mut ts_vec: Vec<Timestamp> = Vec::with_capacity(10000);
+                let mut val_vec: Vec<i128> = Vec::with_capacity(10000);
+                for (min_ts, max_ts) in data_descriptors {
+                    for ts in min_ts..max_ts + 1 {
+                        ts_vec.push(ts);
+                        val_vec.push(1);
+                    }
+                }
+                DataBlock::I128 {
+                    ts: ts_vec,
+                    val: val_vec,
+                    enc: DataBlockEncoding::default(),
+                }
+            }
+            ValueType::Integer32 => {
+                let mut ts_vec: Vec<Timestamp> = Vec::with_capacity(10000);
+                let mut val_vec: Vec<i32> = Vec::with_capacity(10000);
+                for (min_ts, max_ts) in data_descriptors {
+                    for ts in min_ts..max_ts + 1 {
+                        ts_vec.push(ts);
+                        val_vec.push(1);
+                    }
+                }
+                DataBlock::I32 {
+                    ts: ts_vec,
+                    val: val_vec,
+                    enc: DataBlockEncoding::default(),
+                }
+            }
+            ValueType::Float32 => {
+                let mut ts_vec: Vec<Timestamp> = Vec::with_capacity(10000);
+                let mut val_vec: Vec<f32> = Vec::with_capacity(10000);
+                for (min_ts, max_ts) in data_descriptors {
+                    for ts in min_ts..max_ts + 1 {
+                        ts_vec.push(ts);
+                        val_vec.push(1.0);
+                    }
+                }
+                DataBlock::F32 {
+                    ts: ts_vec,
+                    val: val_vec,
+                    enc: DataBlockEncoding::default(),
+                }
+            }
             ValueType::Unknown => {
                 panic!("value type is Unknown")
             }
@@ -303,8 +303,8 @@ struct WriterWrapper {
     /// Buffers of level-0 and level-1 data blocks:
     ///
     /// Each variant of DataBlock will be insert to a hard-coded index of buffers:
-    /// `[ [ Float, Integer, Unsigned, Boolean, Bytes ]; 2 ]`
-    buffers: [[DataBlock; 5]; 2],
+    /// `[ [ Float, Integer, Unsigned, Boolean, Bytes, Decimal, Integer32, Float32 ]; 2 ]`
+    buffers: [[DataBlock; 8]; 2],
     /// Pointer to leve-0 and level-1 TSM writers.
     writers: [Option<TsmWriter>; 2],
 }
@@ -321,6 +321,9 @@ impl WriterWrapper {
             DataBlock::new(0, ValueType::Unsigned),
             DataBlock::new(0, ValueType::Boolean),
             DataBlock::new(0, ValueType::String),
+            DataBlock::new(0, ValueType::Decimal),
+            DataBlock::new(0, ValueType::Integer32),
+            DataBlock::new(0, ValueType::Float32),
         ];
         Self {
             ts_family_id,
@@ -349,6 +352,9 @@ impl WriterWrapper {
             ValueType::Unsigned => 2,
             ValueType::Boolean => 3,
             ValueType::String => 4,
+            ValueType::Decimal => 5,
+            ValueType::Integer32 => 6,
+            ValueType::Float32 => 7,
             ValueType::Unknown => {
                 error!("Flush: Unknown value type for field: {}", field_id);
                 return Ok(());
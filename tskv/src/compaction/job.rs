@@ -4,12 +4,13 @@ use std::sync::{atomic, Arc};
 use std::time::{Duration, Instant};
 
 use flush::run_flush_memtable_job;
+use models::schema::split_owner;
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::{oneshot, RwLock, RwLockWriteGuard, Semaphore};
 use trace::{error, info};
 
-use crate::compaction::{flush, CompactTask, FlushReq, LevelCompactionPicker, Picker};
+use crate::compaction::{create_picker, flush, CompactTask, FlushReq};
 use crate::summary::SummaryTask;
 use crate::{TsKvContext, TseriesFamilyId};
 
@@ -173,8 +174,20 @@ impl CompactJobInner {
                             info!("forbidden compaction on moving vnode {}", vnode_id);
                             return;
                         }
-                        let picker = LevelCompactionPicker::new(ctx.options.storage.clone());
-                        let version = tsf.read().await.version();
+                        let (version, owner) = {
+                            let tsf_rlock = tsf.read().await;
+                            (tsf_rlock.version(), tsf_rlock.tenant_database())
+                        };
+                        let (tenant, database) = split_owner(&owner);
+                        let db_schema = ctx
+                            .version_set
+                            .read()
+                            .await
+                            .get_db_schema(tenant, database)
+                            .await
+                            .ok()
+                            .flatten();
+                        let picker = create_picker(db_schema.as_ref(), ctx.options.storage.clone());
                         let compact_req = picker.pick_compaction(version);
                         if let Some(req) = compact_req {
                             let database = req.database.clone();
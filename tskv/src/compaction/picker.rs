@@ -1,8 +1,10 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use models::predicate::domain::TimeRange;
+use models::schema::{CompactionStrategy, DatabaseSchema};
 use trace::{debug, info};
 
 use crate::compaction::CompactReq;
@@ -292,6 +294,133 @@ impl LevelCompactionPicker {
     }
 }
 
+/// Returns the compaction picker of the `CompactionStrategy` of the database,
+/// `LevelCompactionPicker` is used if the database schema is not found.
+pub fn create_picker(
+    db_schema: Option<&DatabaseSchema>,
+    storage_opt: Arc<StorageOptions>,
+) -> Box<dyn Picker> {
+    let db_options = match db_schema {
+        Some(schema) => schema.options(),
+        None => return Box::new(LevelCompactionPicker::new(storage_opt)),
+    };
+    match db_options.compaction_strategy_or_default() {
+        CompactionStrategy::Level => Box::new(LevelCompactionPicker::new(storage_opt)),
+        CompactionStrategy::TimeWindow => {
+            let window = db_options
+                .compaction_window_or_default()
+                .to_precision(*db_options.precision_or_default());
+            Box::new(TimeWindowCompactionPicker::new(storage_opt, window))
+        }
+    }
+}
+
+/// Compaction picker for picking files in the same time window.
+///
+/// Files are bucketed by the time window of their `max_ts`, only files in the
+/// same window will be compacted together:
+/// - Files in the newest window are compacted when the number of files reaches
+///   `compact_trigger_file_num`, the output is in level 1 or the highest level of
+///   the picked files.
+/// - Files in older windows will not receive new data, they are compacted into
+///   the max level as soon as there are more than one file in a window.
+#[derive(Debug)]
+pub struct TimeWindowCompactionPicker {
+    storage: Arc<StorageOptions>,
+    /// Time window in the precision of the database.
+    window: i64,
+}
+
+impl Picker for TimeWindowCompactionPicker {
+    fn pick_compaction(&self, version: Arc<Version>) -> Option<CompactReq> {
+        let storage_opt = version.storage_opt();
+        let mut windows: BTreeMap<i64, Vec<Arc<ColumnFile>>> = BTreeMap::new();
+        for lvl in version.levels_info().iter() {
+            for file in lvl.files.iter() {
+                if file.is_compacting() || file.is_deleted() || file.is_cold() {
+                    continue;
+                }
+                windows
+                    .entry(self.window_of(file.time_range().max_ts))
+                    .or_default()
+                    .push(file.clone());
+            }
+        }
+
+        let newest_window = *windows.keys().next_back()?;
+        let max_level = (storage_opt.max_level as LevelId).clamp(1, 4);
+        let trigger_file_num = (storage_opt.compact_trigger_file_num as usize).max(2);
+        for (window, mut files) in windows.into_iter().rev() {
+            let is_newest = window == newest_window;
+            if files.len() < 2 || (is_newest && files.len() < trigger_file_num) {
+                continue;
+            }
+            files.sort_by(LevelCompactionPicker::compare_column_file);
+            let mut picking_files = Vec::new();
+            let mut picking_files_size = 0_u64;
+            for file in files {
+                if picking_files.len() >= 2 && picking_files_size >= storage_opt.max_compact_size {
+                    break;
+                }
+                if !file.mark_compacting() {
+                    continue;
+                }
+                picking_files_size += file.size();
+                picking_files.push(file);
+            }
+            if picking_files.len() < 2 {
+                picking_files.iter().for_each(|f| f.unmark_compacting());
+                continue;
+            }
+
+            let out_level = if is_newest {
+                picking_files
+                    .iter()
+                    .map(|f| f.level())
+                    .max()
+                    .unwrap_or(1)
+                    .max(1)
+            } else {
+                max_level
+            };
+            info!(
+                "Picker(time window): picked window {} files: [ {} ] to level {}",
+                window,
+                picking_files
+                    .iter()
+                    .map(|f| format!("{{ Level-{}, file_id: {} }}", f.level(), f.file_id()))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                out_level,
+            );
+            return Some(CompactReq {
+                ts_family_id: version.tf_id(),
+                database: version.tenant_database(),
+                storage_opt: version.storage_opt(),
+                files: picking_files,
+                version: version.clone(),
+                out_level,
+            });
+        }
+
+        info!("Picker(time window): picked no files");
+        None
+    }
+}
+
+impl TimeWindowCompactionPicker {
+    pub fn new(storage_opt: Arc<StorageOptions>, window: i64) -> Self {
+        Self {
+            storage: storage_opt,
+            window: window.max(1),
+        }
+    }
+
+    fn window_of(&self, ts: i64) -> i64 {
+        ts.div_euclid(self.window)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::Arc;
@@ -302,7 +431,7 @@ mod test {
     use models::predicate::domain::TimeRange;
 
     use crate::compaction::test::create_options;
-    use crate::compaction::{LevelCompactionPicker, Picker};
+    use crate::compaction::{LevelCompactionPicker, Picker, TimeWindowCompactionPicker};
     use crate::file_utils::make_tsm_file;
     use crate::kv_option::Options;
     use crate::memcache::MemCache;
@@ -429,4 +558,39 @@ mod test {
         assert_eq!(compact_req.out_level, 2);
         assert_eq!(compact_req.files.len(), 2);
     }
+
+    #[test]
+    fn test_pick_time_window() {
+        //! There are 3 time windows of size 1000, the newest window (2) has only 1 file,
+        //! and window 0 has a file in compaction.
+        //! In this case, files of window 1 will be picked, and compact to the max level.
+        let dir = "/tmp/test/pick/time_window";
+        let opt = create_options(dir.to_string());
+
+        #[rustfmt::skip]
+        let levels_sketch: LevelsSketch = vec![
+            // vec![( level, Timestamp_Begin, Timestamp_end, vec![(file_id, Timestamp_Begin, Timestamp_end, size, being_compact)] )]
+            (0_u32, 2001_i64, 2500_i64, vec![
+                (6_u64, 2001_i64, 2500_i64, 1000_u64, false),
+            ]),
+            (1, 1, 1900, vec![
+                (1, 1, 900, 1000, false),
+                (2, 100, 999, 1000, true),
+                (3, 1001, 1500, 1000, false),
+                (4, 1600, 1900, 1000, false),
+            ]),
+        ];
+
+        let storage_opt = opt.storage.clone();
+        let tsf = create_tseries_family(Arc::new("dba".to_string()), opt, levels_sketch);
+        let picker = TimeWindowCompactionPicker::new(storage_opt.clone(), 1000);
+        let compact_req = picker.pick_compaction(tsf.version()).unwrap();
+        assert_eq!(
+            compact_req.out_level,
+            (storage_opt.max_level as u32).clamp(1, 4)
+        );
+        let mut file_ids: Vec<u64> = compact_req.files.iter().map(|f| f.file_id()).collect();
+        file_ids.sort();
+        assert_eq!(file_ids, vec![3, 4]);
+    }
 }
@@ -1,15 +1,20 @@
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 
-use models::predicate::domain::{TimeRange, TimeRanges};
+use models::predicate::domain::{PushedAggregateFunction, TimeRange, TimeRanges};
 use models::{utils as model_utils, ColumnId, FieldId, SeriesId, Timestamp};
+use snafu::ResultExt;
 use tokio::runtime::Runtime;
 use tokio::sync::Semaphore;
 use trace::trace;
 
+use crate::error::ReadTsmSnafu;
+use crate::memcache::FieldVal;
 use crate::tseries_family::{ColumnFile, SuperVersion};
-use crate::tsm::{self, BlockMeta, TsmReader};
-use crate::{Error, Result};
+use crate::tsm::{self, compare_field_val, BlockMeta, BlockStatistics, TsmReader};
+use crate::{ColumnFileId, Error, Result};
 
 #[derive(PartialEq, Eq)]
 pub enum TimeRangeCmp {
@@ -109,7 +114,10 @@ async fn count_non_null_values_inner(
                 // Only 1 grouped block, maybe no need to decode it.
                 let reader_blk_metas = std::mem::take(&mut grouped_reader_blk_metas);
                 let b = reader_blk_metas.first().unwrap();
-                if cached_time_range.overlaps(&b.time_range) || b.time_range_intersected {
+                if cached_time_range.overlaps(&b.time_range)
+                    || b.time_range_intersected
+                    || b.has_tombstone()
+                {
                     trace!(
                         "Length is 1 but cache overlapped, split it: {}",
                         &b.time_range
@@ -205,6 +213,8 @@ fn get_series_timestamps_in_caches(
 struct ReadTask {
     /// Reader for a file.
     tsm_reader: Arc<TsmReader>,
+    /// Is the file a delta file, data in delta files are newer than the others.
+    is_delta: bool,
     /// BlockMeta in a file.
     block_meta: Arc<BlockMeta>,
     /// Time range by BlockMeta::time_range() .
@@ -213,6 +223,23 @@ struct ReadTask {
     time_range_intersected: bool,
 }
 
+impl ReadTask {
+    /// Is there any tombstone overlaps with the block.
+    fn has_tombstone(&self) -> bool {
+        self.tsm_reader.has_tombstone()
+            && self
+                .tsm_reader
+                .get_block_tombstone_time_ranges(&self.block_meta)
+                .is_some()
+    }
+
+    /// Precedence of values of the same timestamp in different files,
+    /// values in delta files and files with greater id are newer.
+    fn precedence(&self) -> (bool, ColumnFileId) {
+        (self.is_delta, self.tsm_reader.file_id())
+    }
+}
+
 /// Filter block metas in files by time ranges, open files and then create file read tasks.
 async fn create_file_read_tasks<'a>(
     super_version: &SuperVersion,
@@ -223,8 +250,7 @@ async fn create_file_read_tasks<'a>(
     let mut read_tasks: Vec<ReadTask> = Vec::new();
 
     for cf in files {
-        let path = cf.file_path();
-        let reader = super_version.version.get_tsm_reader(&path).await?;
+        let reader = super_version.version.get_column_file_reader(cf).await?;
         let idx_meta_iter = match counting_object {
             CountingObject::Field(field_id) => {
                 if !cf.contains_field_id(*field_id) {
@@ -254,6 +280,7 @@ async fn create_file_read_tasks<'a>(
                 if tr_cmp != TimeRangeCmp::Exclude {
                     read_tasks.push(ReadTask {
                         tsm_reader: reader.clone(),
+                        is_delta: cf.is_delta(),
                         block_meta: Arc::new(blk_meta),
                         time_range: blk_tr,
                         time_range_intersected: tr_cmp == TimeRangeCmp::Intersect,
@@ -344,6 +371,332 @@ async fn count_non_null_values_in_files(
     Ok(count)
 }
 
+/// Aggregated values of a field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldAggregate {
+    /// Number of non-null values.
+    pub count: u64,
+    pub min: Option<FieldVal>,
+    pub max: Option<FieldVal>,
+    /// Sum of the numeric values, integers are wrapping added as arrow does.
+    pub sum: Option<FieldVal>,
+    /// Value of the minimum timestamp, with the timestamp.
+    pub first: Option<(Timestamp, FieldVal)>,
+    /// Value of the maximum timestamp, with the timestamp.
+    pub last: Option<(Timestamp, FieldVal)>,
+}
+
+impl FieldAggregate {
+    fn with_statistics(block_meta: &BlockMeta, statistics: BlockStatistics) -> Self {
+        Self {
+            count: block_meta.count() as u64,
+            min: Some(statistics.min),
+            max: Some(statistics.max),
+            sum: statistics.sum,
+            first: Some((block_meta.min_ts(), statistics.first)),
+            last: Some((block_meta.max_ts(), statistics.last)),
+        }
+    }
+
+    fn push(&mut self, ts: Timestamp, value: FieldVal) {
+        self.merge(Self {
+            count: 1,
+            min: Some(value.clone()),
+            max: Some(value.clone()),
+            sum: match value {
+                FieldVal::Float(_) | FieldVal::Integer(_) | FieldVal::Unsigned(_) => {
+                    Some(value.clone())
+                }
+                FieldVal::Boolean(_)
+                | FieldVal::Bytes(_)
+                | FieldVal::Decimal(_)
+                | FieldVal::Integer32(_)
+                | FieldVal::Float32(_) => None,
+            },
+            first: Some((ts, value.clone())),
+            last: Some((ts, value)),
+        });
+    }
+
+    /// Returns value of the aggregate function, count is an integer.
+    pub fn value(&self, function: &PushedAggregateFunction) -> Option<FieldVal> {
+        match function {
+            PushedAggregateFunction::Count(_) => Some(FieldVal::Integer(self.count as i64)),
+            PushedAggregateFunction::Max(_) => self.max.clone(),
+            PushedAggregateFunction::Min(_) => self.min.clone(),
+            PushedAggregateFunction::Sum(_) => self.sum.clone(),
+            PushedAggregateFunction::First(_) => self.first.as_ref().map(|(_, v)| v.clone()),
+            PushedAggregateFunction::Last(_) => self.last.as_ref().map(|(_, v)| v.clone()),
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.count += other.count;
+        self.min = pick(self.min.take(), other.min, |a, b| {
+            compare_field_val(a, b) == Ordering::Greater
+        });
+        self.max = pick(self.max.take(), other.max, |a, b| {
+            compare_field_val(a, b) == Ordering::Less
+        });
+        self.sum = match (self.sum.take(), other.sum) {
+            (Some(a), Some(b)) => Some(add_field_val(a, b)),
+            (a, b) => a.or(b),
+        };
+        self.first = pick(self.first.take(), other.first, |a, b| a.0 > b.0);
+        self.last = pick(self.last.take(), other.last, |a, b| a.0 < b.0);
+    }
+}
+
+/// Returns `b` if `a` is None or `replace(a, b)` is true, otherwise returns `a`.
+fn pick<T>(a: Option<T>, b: Option<T>, replace: impl Fn(&T, &T) -> bool) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            if replace(&a, &b) {
+                Some(b)
+            } else {
+                Some(a)
+            }
+        }
+        (a, b) => a.or(b),
+    }
+}
+
+fn add_field_val(a: FieldVal, b: FieldVal) -> FieldVal {
+    match (a, b) {
+        (FieldVal::Float(a), FieldVal::Float(b)) => FieldVal::Float(a + b),
+        (FieldVal::Integer(a), FieldVal::Integer(b)) => FieldVal::Integer(a.wrapping_add(b)),
+        (FieldVal::Unsigned(a), FieldVal::Unsigned(b)) => FieldVal::Unsigned(a.wrapping_add(b)),
+        (a, _) => a,
+    }
+}
+
+/// Compute pushed down aggregates of a field column:
+///
+/// `SELECT count(<field>), min(<field>), max(<field>), sum(<field>), first(time, <field>),
+/// last(time, <field>) FROM <table> WHERE <time_range_predicates>`
+///
+/// Blocks fully included in the time ranges, not overlapped with other blocks, cached data
+/// or tombstones are aggregated by the statistics in block metas, the others are decoded.
+pub async fn aggregate_field_values(
+    runtime: Arc<Runtime>,
+    super_version: Arc<SuperVersion>,
+    series_ids: Arc<Vec<SeriesId>>,
+    column_id: ColumnId,
+    time_ranges: Arc<TimeRanges>,
+) -> Result<FieldAggregate> {
+    let column_files = Arc::new(super_version.column_files(&time_ranges));
+    let mut jh_vec = Vec::with_capacity(series_ids.len());
+
+    // Limit the number of concurrent tasks.
+    let max_aggregate_tasks = num_cpus::get().min(series_ids.len());
+    let aggregate_tasks_limit = Arc::new(Semaphore::new(max_aggregate_tasks));
+    for series_id in series_ids.iter() {
+        let field_id = model_utils::unite_id(column_id, *series_id);
+        let sv_inner = super_version.clone();
+        let cfs_inner = column_files.clone();
+        let trs_inner = time_ranges.clone();
+
+        let permit = aggregate_tasks_limit.clone().acquire_owned().await.unwrap();
+        jh_vec.push(runtime.spawn(async move {
+            // All values are in the same bucket.
+            let ret =
+                aggregate_field_values_inner(sv_inner, field_id, cfs_inner, trs_inner, |_| 0).await;
+            drop(permit);
+            ret
+        }));
+    }
+
+    let mut aggregate = FieldAggregate::default();
+    for jh in jh_vec {
+        // JoinHandle returns JoinError if task was paniced.
+        let bucket_aggregates = jh.await.map_err(|e| Error::IO { source: e.into() })??;
+        for (_, bucket_aggregate) in bucket_aggregates {
+            aggregate.merge(bucket_aggregate);
+        }
+    }
+
+    Ok(aggregate)
+}
+
+/// Compute pushed down aggregates of a field column of a series in time buckets:
+///
+/// `SELECT date_bin(<stride>, time, <origin>), count(<field>), min(<field>), max(<field>),
+/// sum(<field>) FROM <table> WHERE <time_range_predicates> GROUP BY date_bin(...)`
+///
+/// `time_bucket` returns start of the bucket which a timestamp belongs to, returns
+/// aggregates of the buckets which have values, by start of the buckets.
+pub async fn aggregate_series_field_values_by_time_bucket(
+    super_version: Arc<SuperVersion>,
+    series_id: SeriesId,
+    column_id: ColumnId,
+    time_ranges: Arc<TimeRanges>,
+    time_bucket: impl Fn(Timestamp) -> Timestamp,
+) -> Result<BTreeMap<Timestamp, FieldAggregate>> {
+    let column_files = Arc::new(super_version.column_files(&time_ranges));
+    let field_id = model_utils::unite_id(column_id, series_id);
+    aggregate_field_values_inner(
+        super_version,
+        field_id,
+        column_files,
+        time_ranges,
+        time_bucket,
+    )
+    .await
+}
+
+/// Get aggregated values in time ranges of a field, by start of the time buckets.
+async fn aggregate_field_values_inner(
+    super_version: Arc<SuperVersion>,
+    field_id: FieldId,
+    column_files: Arc<Vec<Arc<ColumnFile>>>,
+    time_ranges: Arc<TimeRanges>,
+    time_bucket: impl Fn(Timestamp) -> Timestamp,
+) -> Result<BTreeMap<Timestamp, FieldAggregate>> {
+    let read_tasks = create_file_read_tasks(
+        &super_version,
+        &column_files,
+        &CountingObject::Field(field_id),
+        &time_ranges,
+    )
+    .await?;
+    let (cached_values, cached_time_range) =
+        get_field_values_in_caches(&super_version, field_id, &time_ranges);
+
+    let mut aggregates: BTreeMap<Timestamp, FieldAggregate> = BTreeMap::new();
+    for (ts, value) in cached_values.iter() {
+        aggregates
+            .entry(time_bucket(*ts))
+            .or_default()
+            .push(*ts, value.clone());
+    }
+
+    let mut grouped_tr = TimeRange::new(i64::MAX, i64::MIN);
+    let mut grouped_read_tasks: Vec<Vec<ReadTask>> = Vec::new();
+    for read_task in read_tasks {
+        match grouped_read_tasks.last_mut() {
+            Some(group) if grouped_tr.overlaps(&read_task.time_range) => {
+                grouped_tr.merge(&read_task.time_range);
+                group.push(read_task);
+            }
+            _ => {
+                grouped_tr = read_task.time_range;
+                grouped_read_tasks.push(vec![read_task]);
+            }
+        }
+    }
+
+    for group in grouped_read_tasks {
+        if let [read_task] = group.as_slice() {
+            let bucket = time_bucket(read_task.time_range.min_ts);
+            if !read_task.time_range_intersected
+                && !cached_time_range.overlaps(&read_task.time_range)
+                && !read_task.has_tombstone()
+                && bucket == time_bucket(read_task.time_range.max_ts)
+            {
+                if let Some(statistics) = read_task.block_meta.statistics() {
+                    trace!(
+                        "Aggregate by statistics of block: {}",
+                        &read_task.time_range
+                    );
+                    aggregates
+                        .entry(bucket)
+                        .or_default()
+                        .merge(FieldAggregate::with_statistics(
+                            &read_task.block_meta,
+                            statistics,
+                        ));
+                    continue;
+                }
+            }
+        }
+        trace!("Aggregate by decoding {} blocks", group.len());
+        aggregate_values_in_files(
+            group,
+            &time_ranges,
+            &cached_values,
+            &time_bucket,
+            &mut aggregates,
+        )
+        .await?;
+    }
+
+    Ok(aggregates)
+}
+
+/// Get values of a field in time ranges from all mutable and immutable caches,
+/// the newer value of a timestamp overrides the older one.
+fn get_field_values_in_caches(
+    super_version: &SuperVersion,
+    field_id: FieldId,
+    time_ranges: &TimeRanges,
+) -> (BTreeMap<Timestamp, FieldVal>, TimeRange) {
+    let time_predicate = |ts| time_ranges.is_boundless() || time_ranges.contains(ts);
+    let mut cached_values: BTreeMap<Timestamp, FieldVal> = BTreeMap::new();
+    let mut cached_time_range = TimeRange::new(i64::MAX, i64::MIN);
+    super_version.caches.read_field_data(
+        field_id,
+        time_predicate,
+        |_| true,
+        |d| {
+            let ts = d.timestamp();
+            cached_time_range.min_ts = cached_time_range.min_ts.min(ts);
+            cached_time_range.max_ts = cached_time_range.max_ts.max(ts);
+            cached_values.insert(ts, d.into_field_val());
+        },
+    );
+
+    (cached_values, cached_time_range)
+}
+
+/// Decode grouped read tasks and aggregate values in time ranges which are not cached.
+async fn aggregate_values_in_files(
+    read_tasks: Vec<ReadTask>,
+    time_ranges: &TimeRanges,
+    cached_values: &BTreeMap<Timestamp, FieldVal>,
+    time_bucket: &impl Fn(Timestamp) -> Timestamp,
+    aggregates: &mut BTreeMap<Timestamp, FieldAggregate>,
+) -> Result<()> {
+    let mut values: BTreeMap<Timestamp, ((bool, ColumnFileId), FieldVal)> = BTreeMap::new();
+    for read_task in read_tasks {
+        let blk = read_task
+            .tsm_reader
+            .get_data_block(&read_task.block_meta)
+            .await
+            .context(ReadTsmSnafu)?;
+        let precedence = read_task.precedence();
+        for i in 0..blk.len() {
+            let data = match blk.get(i) {
+                Some(data) => data,
+                None => break,
+            };
+            let ts = data.timestamp();
+            if !(time_ranges.is_boundless() || time_ranges.contains(ts))
+                || cached_values.contains_key(&ts)
+            {
+                continue;
+            }
+            match values.entry(ts) {
+                Entry::Vacant(e) => {
+                    e.insert((precedence, data.into_field_val()));
+                }
+                Entry::Occupied(mut e) => {
+                    if e.get().0 < precedence {
+                        e.insert((precedence, data.into_field_val()));
+                    }
+                }
+            }
+        }
+    }
+    for (ts, (_, value)) in values {
+        aggregates
+            .entry(time_bucket(ts))
+            .or_default()
+            .push(ts, value);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -357,9 +710,12 @@ mod test {
 
     use crate::compaction::flush_tests::default_table_schema;
     use crate::compaction::test::write_data_blocks_to_column_file;
-    use crate::compute::count::count_column_non_null_values;
+    use crate::compute::aggregate::{
+        aggregate_field_values, aggregate_series_field_values_by_time_bucket,
+        count_column_non_null_values, FieldAggregate,
+    };
     use crate::memcache::test::put_rows_to_cache;
-    use crate::memcache::MemCache;
+    use crate::memcache::{FieldVal, MemCache};
     use crate::tseries_family::test_tseries_family::build_version_by_column_files;
     use crate::tseries_family::{CacheGroup, SuperVersion};
     use crate::tsm::codec::DataBlockEncoding;
@@ -641,4 +997,121 @@ mod test {
             "skip_fmt"
         };
     }
+
+    #[test]
+    fn test_super_version_aggregate_file() {
+        let dir = "/tmp/test/ts_family/super_version_aggregate_file";
+        let mut global_config = config::get_config_for_test();
+        global_config.storage.path = dir.to_string();
+
+        #[rustfmt::skip]
+        let data = vec![
+            HashMap::from([
+                (model_utils::unite_id(1, 1), vec![DataBlock::I64 { ts: vec![1, 2, 3, 4], val: vec![5, -2, 7, 1], enc: DataBlockEncoding::default() }]),
+            ]),
+            HashMap::from([
+                (model_utils::unite_id(1, 1), vec![DataBlock::I64 { ts: vec![4, 5, 6], val: vec![10, 3, 4], enc: DataBlockEncoding::default() }]),
+            ]),
+            HashMap::from([
+                (model_utils::unite_id(1, 1), vec![DataBlock::I64 { ts: vec![7, 8, 9], val: vec![0, 9, -1], enc: DataBlockEncoding::default() }]),
+            ]),
+        ];
+
+        let opt = Arc::new(Options::from(&global_config));
+        let database = Arc::new("dba".to_string());
+        let ts_family_id = 1;
+        let dir = opt.storage.tsm_dir(&database, 1);
+        let runtime = Arc::new(
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        );
+
+        let (_, files) = runtime.block_on(write_data_blocks_to_column_file(&dir, data));
+        let version =
+            build_version_by_column_files(opt.storage.clone(), database, ts_family_id, files);
+        let pool: MemoryPoolRef = Arc::new(GreedyMemoryPool::default());
+        let super_version = Arc::new(SuperVersion::new(
+            ts_family_id,
+            opt.storage.clone(),
+            CacheGroup {
+                mut_cache: Arc::new(RwLock::new(MemCache::new(ts_family_id, 1, 2, 1, &pool))),
+                immut_cache: vec![],
+            },
+            Arc::new(version),
+            1,
+        ));
+        let run = |time_ranges: (i64, i64)| {
+            runtime
+                .block_on(aggregate_field_values(
+                    runtime.clone(),
+                    super_version.clone(),
+                    Arc::new(vec![1]),
+                    1,
+                    Arc::new(time_ranges.into()),
+                ))
+                .unwrap()
+        };
+
+        // Value of timestamp 4 in the latter file overwrites the former one.
+        assert_eq!(
+            run((i64::MIN, i64::MAX)),
+            FieldAggregate {
+                count: 9,
+                min: Some(FieldVal::Integer(-2)),
+                max: Some(FieldVal::Integer(10)),
+                sum: Some(FieldVal::Integer(35)),
+                first: Some((1, FieldVal::Integer(5))),
+                last: Some((9, FieldVal::Integer(-1))),
+            }
+        );
+        assert_eq!(
+            run((2, 4)),
+            FieldAggregate {
+                count: 3,
+                min: Some(FieldVal::Integer(-2)),
+                max: Some(FieldVal::Integer(10)),
+                sum: Some(FieldVal::Integer(15)),
+                first: Some((2, FieldVal::Integer(-2))),
+                last: Some((4, FieldVal::Integer(10))),
+            }
+        );
+        // The block is not overlapped, aggregated by the statistics.
+        assert_eq!(
+            run((7, 9)),
+            FieldAggregate {
+                count: 3,
+                min: Some(FieldVal::Integer(-1)),
+                max: Some(FieldVal::Integer(9)),
+                sum: Some(FieldVal::Integer(8)),
+                first: Some((7, FieldVal::Integer(0))),
+                last: Some((9, FieldVal::Integer(-1))),
+            }
+        );
+        assert_eq!(run((10, 20)), FieldAggregate::default());
+
+        // Aggregate by time buckets [1, 4), [4, 7), [7, 10).
+        let bucket_aggregates = runtime
+            .block_on(aggregate_series_field_values_by_time_bucket(
+                super_version.clone(),
+                1,
+                1,
+                Arc::new((i64::MIN, i64::MAX).into()),
+                |ts| ts - (ts - 1).rem_euclid(3),
+            ))
+            .unwrap();
+        let buckets: Vec<(i64, u64, Option<FieldVal>)> = bucket_aggregates
+            .into_iter()
+            .map(|(bucket, agg)| (bucket, agg.count, agg.sum))
+            .collect();
+        assert_eq!(
+            buckets,
+            vec![
+                (1, 3, Some(FieldVal::Integer(10))),
+                (4, 3, Some(FieldVal::Integer(17))),
+                (7, 3, Some(FieldVal::Integer(8))),
+            ]
+        );
+    }
 }
@@ -1 +1 @@
-pub mod count;
+pub mod aggregate;
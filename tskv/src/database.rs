@@ -27,7 +27,7 @@ use crate::schema::schemas::DBschemas;
 use crate::summary::{SummaryTask, VersionEdit};
 use crate::tseries_family::{LevelInfo, TseriesFamily, TsfFactory, Version};
 use crate::Error::{self};
-use crate::{file_utils, ColumnFileId, TsKvContext, TseriesFamilyId};
+use crate::{cold_storage, file_utils, ColumnFileId, TsKvContext, TseriesFamilyId};
 
 pub type FlatBufferTable<'a> = flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Table<'a>>>;
 
@@ -143,7 +143,27 @@ impl Database {
                     let file_path = f
                         .rename_file(&self.opt.storage, &self.owner, f.tsf_id, new_file_id)
                         .await?;
-                    let file_reader = crate::tsm::TsmReader::open(file_path).await?;
+                    let file_reader = match f.cold_location.take() {
+                        // The tsm file of the source vnode in the cold storage is
+                        // copied, the source vnode may delete it.
+                        Some(source) => {
+                            let location =
+                                cold_storage::copy_file(&source, &self.owner, tsf_id, new_file_id)
+                                    .await?;
+                            let tombstone_dir = file_path
+                                .parent()
+                                .unwrap_or_else(|| std::path::Path::new("/"));
+                            let reader = crate::tsm::TsmReader::open_cold(
+                                new_file_id,
+                                &location,
+                                tombstone_dir,
+                            )
+                            .await?;
+                            f.cold_location = Some(location);
+                            reader
+                        }
+                        None => crate::tsm::TsmReader::open(file_path).await?,
+                    };
                     file_metas.insert(new_file_id, file_reader.bloom_filter());
                 }
                 for f in ve.del_files.iter_mut() {
@@ -206,7 +226,14 @@ impl Database {
 
     pub async fn del_tsfamily(&mut self, tf_id: u32, summary_task_sender: Sender<SummaryTask>) {
         if let Some(tf) = self.ts_families.remove(&tf_id) {
-            tf.read().await.close();
+            let version = {
+                let tf = tf.read().await;
+                tf.close();
+                tf.version()
+            };
+            // Local files are removed with the vnode directory, but files in the
+            // cold storage are not.
+            cold_storage::delete_version_files(&version).await;
         }
 
         // TODO(zipper): If no ts_family recovered from summary, do not write summary.
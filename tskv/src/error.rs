@@ -269,6 +269,16 @@ pub enum Error {
 
     #[snafu(display("Columns of FlatBufferTable is missing"))]
     FlatBufColumnsMiss,
+
+    #[snafu(display("Cold storage error: {}", source))]
+    ColdStorage {
+        source: object_store::Error,
+    },
+
+    #[snafu(display("Backup storage error: {}", source))]
+    BackupStorage {
+        source: object_store::Error,
+    },
 }
 
 impl From<PointsError> for Error {
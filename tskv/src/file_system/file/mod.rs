@@ -1,5 +1,6 @@
 pub(crate) mod async_file;
 pub(crate) mod cursor;
+pub(crate) mod object_file;
 mod os;
 
 use std::io;
@@ -8,7 +9,7 @@ use std::io::IoSlice;
 use async_trait::async_trait;
 
 #[async_trait]
-pub trait IFile: Send {
+pub trait IFile: Send + Sync {
     async fn write_vec<'a>(&self, pos: u64, bufs: &'a mut [IoSlice<'a>]) -> io::Result<usize>;
     async fn write_at(&self, pos: u64, data: &[u8]) -> io::Result<usize>;
     async fn read_at(&self, pos: u64, data: &mut [u8]) -> io::Result<usize>;
@@ -0,0 +1,116 @@
+use std::io::{Error, ErrorKind, IoSlice, Result};
+use std::sync::Arc;
+
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+use crate::file_system::file::IFile;
+
+/// Read-only file stored in an object store, reads are done by ranged requests.
+pub struct ObjectFile {
+    store: Arc<dyn ObjectStore>,
+    location: Path,
+    size: u64,
+}
+
+impl ObjectFile {
+    pub async fn open(store: Arc<dyn ObjectStore>, location: Path) -> Result<Self> {
+        let meta = store.head(&location).await.map_err(object_store_error)?;
+        Ok(Self {
+            store,
+            location,
+            size: meta.size as u64,
+        })
+    }
+
+    pub fn location(&self) -> &Path {
+        &self.location
+    }
+}
+
+#[async_trait::async_trait]
+impl IFile for ObjectFile {
+    async fn write_vec<'a>(&self, _pos: u64, _bufs: &'a mut [IoSlice<'a>]) -> Result<usize> {
+        Err(read_only_error())
+    }
+
+    async fn write_at(&self, _pos: u64, _data: &[u8]) -> Result<usize> {
+        Err(read_only_error())
+    }
+
+    async fn read_at(&self, pos: u64, data: &mut [u8]) -> Result<usize> {
+        if pos >= self.size || data.is_empty() {
+            return Ok(0);
+        }
+        let end = self.size.min(pos + data.len() as u64);
+        let bytes = self
+            .store
+            .get_range(&self.location, pos as usize..end as usize)
+            .await
+            .map_err(object_store_error)?;
+        data[..bytes.len()].copy_from_slice(&bytes);
+        Ok(bytes.len())
+    }
+
+    async fn sync_data(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn truncate(&self, _size: u64) -> Result<()> {
+        Err(read_only_error())
+    }
+
+    fn len(&self) -> u64 {
+        self.size
+    }
+
+    fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+fn read_only_error() -> Error {
+    Error::new(ErrorKind::Unsupported, "object file is read only")
+}
+
+fn object_store_error(e: object_store::Error) -> Error {
+    match e {
+        object_store::Error::NotFound { .. } => Error::new(ErrorKind::NotFound, e),
+        _ => Error::new(ErrorKind::Other, e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use object_store::local::LocalFileSystem;
+    use object_store::path::Path;
+    use object_store::ObjectStore;
+
+    use crate::file_system::file::object_file::ObjectFile;
+    use crate::file_system::file::IFile;
+
+    #[tokio::test]
+    async fn test_read_at() {
+        let dir = "/tmp/test/object_file/1";
+        let _ = std::fs::remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let store: Arc<dyn ObjectStore> = Arc::new(LocalFileSystem::new_with_prefix(dir).unwrap());
+        let location = Path::from("a/b.tsm");
+        store
+            .put(&location, bytes::Bytes::from_static(b"hello world"))
+            .await
+            .unwrap();
+
+        let file = ObjectFile::open(store, location).await.unwrap();
+        assert_eq!(file.len(), 11);
+        let mut buf = [0_u8; 5];
+        assert_eq!(file.read_at(6, &mut buf).await.unwrap(), 5);
+        assert_eq!(&buf, b"world");
+        assert_eq!(file.read_at(8, &mut buf).await.unwrap(), 3);
+        assert_eq!(&buf[..3], b"rld");
+        assert_eq!(file.read_at(11, &mut buf).await.unwrap(), 0);
+        assert!(file.write_at(0, b"x").await.is_err());
+    }
+}
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-use config::Config;
+use config::{ColdStorageConfig, Config};
 use models::meta_data::VnodeId;
 
 use crate::TseriesFamilyId;
@@ -51,6 +51,7 @@ pub struct StorageOptions {
     pub max_compact_size: u64,
     pub max_concurrent_compaction: u16,
     pub strict_write: bool,
+    pub cold_storage: Option<ColdStorageConfig>,
 }
 
 // database/data/ts_family_id/tsm
@@ -149,6 +150,7 @@ impl From<&Config> for StorageOptions {
             max_compact_size: config.storage.max_compact_size,
             max_concurrent_compaction: config.storage.max_concurrent_compaction,
             strict_write: config.storage.strict_write,
+            cold_storage: config.storage.cold_storage.clone(),
         }
     }
 }
@@ -10,7 +10,7 @@ use metrics::metric_register::MetricsRegister;
 use models::codec::Encoding;
 use models::meta_data::{VnodeId, VnodeStatus};
 use models::predicate::domain::{ColumnDomains, ResolvedPredicate};
-use models::schema::{make_owner, DatabaseSchema, Precision};
+use models::schema::{make_owner, split_owner, DatabaseSchema, Precision};
 use models::{SeriesId, SeriesKey, TagKey, TagValue};
 use protos::kv_service::{WritePointsRequest, WritePointsResponse};
 use protos::models as fb_models;
@@ -23,7 +23,7 @@ use trace::{debug, error, info, warn, SpanContext, SpanExt, SpanRecorder};
 
 use crate::compaction::job::{CompactJob, FlushJob};
 use crate::compaction::{
-    self, check, run_flush_memtable_job, CompactTask, FlushReq, LevelCompactionPicker, Picker,
+    self, check, create_picker, run_flush_memtable_job, CompactTask, FlushReq,
 };
 use crate::database::Database;
 use crate::error::{self, Result};
@@ -39,7 +39,9 @@ use crate::vnode_store::VnodeStorage;
 use crate::wal::{
     self, Block, DeleteBlock, UpdateSeriesKeysBlock, WalDecoder, WalManager, WalTask,
 };
-use crate::{file_utils, Engine, Error, TsKvContext, TseriesFamilyId, UpdateSetValue};
+use crate::{
+    cold_storage, file_utils, Engine, Error, TsKvContext, TseriesFamilyId, UpdateSetValue,
+};
 
 // TODO: A small summay channel capacity can cause a block
 pub const COMPACT_REQ_CHANNEL_CAP: usize = 1024;
@@ -75,6 +77,7 @@ impl TsKv {
         let (summary_task_sender, summary_task_receiver) =
             mpsc::channel::<SummaryTask>(SUMMARY_REQ_CHANNEL_CAP);
         let (close_sender, _close_receiver) = broadcast::channel(1);
+        cold_storage::init_cold_store(shared_options.storage.cold_storage.as_ref())?;
         let (version_set, summary) = Self::recover_summary(
             runtime.clone(),
             memory_pool.clone(),
@@ -119,6 +122,7 @@ impl TsKv {
         core.compact_job.start_vnode_compaction_job().await;
         core.flush_job.start_vnode_flush_job(flush_task_receiver);
         core.run_wal_job(wal_manager, wal_receiver);
+        core.run_cold_storage_job();
         Ok(core)
     }
 
@@ -391,6 +395,30 @@ impl TsKv {
         info!("Summary task handler started");
     }
 
+    fn run_cold_storage_job(&self) {
+        let check_interval = match &self.ctx.options.storage.cold_storage {
+            Some(config) => config.check_interval.max(Duration::from_secs(1)),
+            None => return,
+        };
+        let ctx = self.ctx.clone();
+        let mut close_receiver = self.close_sender.subscribe();
+        self.runtime.spawn(async move {
+            info!("Job 'cold storage' started.");
+            let mut check_ticker = tokio::time::interval(check_interval);
+            loop {
+                tokio::select! {
+                    _ = check_ticker.tick() => {
+                        cold_storage::run_cold_storage_check(&ctx).await;
+                    }
+                    _ = close_receiver.recv() => {
+                        info!("Job 'cold storage' closed.");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
     pub async fn get_db(&self, tenant: &str, database: &str) -> Option<Arc<RwLock<Database>>> {
         self.ctx.version_set.read().await.get_db(tenant, database)
     }
@@ -1207,8 +1235,21 @@ impl Engine for TsKv {
                     }
                 }
 
-                let picker = LevelCompactionPicker::new(self.ctx.options.storage.clone());
-                let version = ts_family.read().await.version();
+                let (version, owner) = {
+                    let tsf_rlock = ts_family.read().await;
+                    (tsf_rlock.version(), tsf_rlock.tenant_database())
+                };
+                let (tenant, database) = split_owner(&owner);
+                let db_schema = self
+                    .ctx
+                    .version_set
+                    .read()
+                    .await
+                    .get_db_schema(tenant, database)
+                    .await
+                    .ok()
+                    .flatten();
+                let picker = create_picker(db_schema.as_ref(), self.ctx.options.storage.clone());
                 if let Some(req) = picker.pick_compaction(version) {
                     match compaction::run_compaction_job(req, self.ctx.global_ctx.clone()).await {
                         Ok(Some((version_edit, file_metas))) => {
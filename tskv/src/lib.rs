@@ -35,7 +35,9 @@ use crate::tseries_family::SuperVersion;
 pub use crate::tsm::print_tsm_statistics;
 pub use crate::wal::print_wal_statistics;
 
+pub mod backup;
 pub mod byte_utils;
+mod cold_storage;
 mod compaction;
 mod compute;
 mod context;
@@ -9,11 +9,12 @@ use memory_pool::{MemoryConsumer, MemoryPoolRef, MemoryReservation};
 use minivec::{mini_vec, MiniVec};
 use models::predicate::domain::{TimeRange, TimeRanges};
 use models::schema::{
-    timestamp_convert, Precision, TableColumn, TskvTableSchema, TskvTableSchemaRef,
+    timestamp_convert, ColumnType, Precision, TableColumn, TskvTableSchema, TskvTableSchemaRef,
 };
 use models::utils::split_id;
 use models::{
-    ColumnId, FieldId, PhysicalDType as ValueType, RwLockRef, SchemaId, SeriesId, Timestamp,
+    parse_decimal, ColumnId, FieldId, PhysicalDType as ValueType, RwLockRef, SchemaId, SeriesId,
+    Timestamp, ValueType as LogicalType, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE,
 };
 use parking_lot::RwLock;
 use protos::models::{Column, FieldType};
@@ -31,6 +32,9 @@ pub enum FieldVal {
     Unsigned(u64),
     Boolean(bool),
     Bytes(MiniVec<u8>),
+    Decimal(i128),
+    Integer32(i32),
+    Float32(f32),
 }
 
 impl FieldVal {
@@ -41,6 +45,9 @@ impl FieldVal {
             FieldVal::Unsigned(..) => ValueType::Unsigned,
             FieldVal::Boolean(..) => ValueType::Boolean,
             FieldVal::Bytes(..) => ValueType::String,
+            FieldVal::Decimal(..) => ValueType::Decimal,
+            FieldVal::Integer32(..) => ValueType::Integer32,
+            FieldVal::Float32(..) => ValueType::Float32,
         }
     }
 
@@ -51,6 +58,9 @@ impl FieldVal {
             FieldVal::Unsigned(val) => DataType::U64(ts, *val),
             FieldVal::Boolean(val) => DataType::Bool(ts, *val),
             FieldVal::Bytes(val) => DataType::Str(ts, val.clone()),
+            FieldVal::Decimal(val) => DataType::I128(ts, *val),
+            FieldVal::Integer32(val) => DataType::I32(ts, *val),
+            FieldVal::Float32(val) => DataType::F32(ts, *val),
         }
     }
 
@@ -76,6 +86,18 @@ impl FieldVal {
                 //let val = Vec::from(val);
                 FieldVal::Bytes(val)
             }
+            ValueType::Decimal => {
+                let val = byte_utils::decode_be_u128(&val) as i128;
+                FieldVal::Decimal(val)
+            }
+            ValueType::Integer32 => {
+                let val = byte_utils::decode_be_u32(&val) as i32;
+                FieldVal::Integer32(val)
+            }
+            ValueType::Float32 => {
+                let val = f32::from_bits(byte_utils::decode_be_u32(&val));
+                FieldVal::Float32(val)
+            }
             _ => todo!(),
         }
     }
@@ -97,6 +119,9 @@ impl Display for FieldVal {
             FieldVal::Float(val) => write!(f, "{}", val),
             FieldVal::Boolean(val) => write!(f, "{}", val),
             FieldVal::Bytes(val) => write!(f, "{:?})", val),
+            FieldVal::Decimal(val) => write!(f, "{}", val),
+            FieldVal::Integer32(val) => write!(f, "{}", val),
+            FieldVal::Float32(val) => write!(f, "{}", val),
         }
     }
 }
@@ -109,6 +134,9 @@ impl PartialEq for FieldVal {
             (FieldVal::Float(a), FieldVal::Float(b)) => a.eq(b),
             (FieldVal::Boolean(a), FieldVal::Boolean(b)) => a == b,
             (FieldVal::Bytes(a), FieldVal::Bytes(b)) => a == b,
+            (FieldVal::Decimal(a), FieldVal::Decimal(b)) => a == b,
+            (FieldVal::Integer32(a), FieldVal::Integer32(b)) => a == b,
+            (FieldVal::Float32(a), FieldVal::Float32(b)) => a.eq(b),
             _ => false,
         }
     }
@@ -241,6 +269,99 @@ impl RowData {
                             }
                         }
                     }
+                    FieldType::Int32 => {
+                        let len = column.int32_values_len()?;
+                        let column_nullbits =
+                            ImmutBitSet::new_without_check(len, column_nullbit.bytes());
+                        if !column_nullbits.get(row_count) {
+                            continue;
+                        }
+                        let val = column.int32_values()?.get(row_count);
+                        match schema.column(column_name) {
+                            None => {
+                                error!("column {} not found in schema", column_name);
+                            }
+                            Some(column) => {
+                                let field_id = column.id;
+                                let field_idx = fields_id.get(&field_id).unwrap();
+                                fields[*field_idx] = Some(FieldVal::Integer32(val));
+                                has_fields = true;
+                            }
+                        }
+                    }
+                    FieldType::Float32 => {
+                        let len = column.float32_values_len()?;
+                        let column_nullbits =
+                            ImmutBitSet::new_without_check(len, column_nullbit.bytes());
+                        if !column_nullbits.get(row_count) {
+                            continue;
+                        }
+                        let val = column.float32_values()?.get(row_count);
+                        match schema.column(column_name) {
+                            None => {
+                                error!("column {} not found in schema", column_name);
+                            }
+                            Some(column) => {
+                                let field_id = column.id;
+                                let field_idx = fields_id.get(&field_id).unwrap();
+                                fields[*field_idx] = Some(FieldVal::Float32(val));
+                                has_fields = true;
+                            }
+                        }
+                    }
+                    FieldType::Binary => {
+                        let len = column.binary_values_len()?;
+                        let column_nullbits =
+                            ImmutBitSet::new_without_check(len, column_nullbit.bytes());
+                        if !column_nullbits.get(row_count) {
+                            continue;
+                        }
+                        let val = column.binary_value(row_count)?;
+                        match schema.column(column_name) {
+                            None => {
+                                error!("column {} not found in schema", column_name);
+                            }
+                            Some(column) => {
+                                let field_id = column.id;
+                                let field_idx = fields_id.get(&field_id).unwrap();
+                                fields[*field_idx] = Some(FieldVal::Bytes(MiniVec::from(val)));
+                                has_fields = true;
+                            }
+                        }
+                    }
+                    FieldType::Decimal => {
+                        let len = column.string_values_len()?;
+                        let column_nullbits =
+                            ImmutBitSet::new_without_check(len, column_nullbit.bytes());
+                        if !column_nullbits.get(row_count) {
+                            continue;
+                        }
+                        let val = column.string_values()?.get(row_count);
+                        match schema.column(column_name) {
+                            None => {
+                                error!("column {} not found in schema", column_name);
+                            }
+                            Some(column) => {
+                                // Decimal values are sent as text and scaled by the column.
+                                let (precision, scale) = match column.column_type {
+                                    ColumnType::Field(LogicalType::Decimal128(p, s)) => (p, s),
+                                    _ => (DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE),
+                                };
+                                let val = parse_decimal(val, precision, scale).map_err(|e| {
+                                    Error::CommonError {
+                                        reason: format!(
+                                            "invalid decimal value of column {}: {}",
+                                            column_name, e
+                                        ),
+                                    }
+                                })?;
+                                let field_id = column.id;
+                                let field_idx = fields_id.get(&field_id).unwrap();
+                                fields[*field_idx] = Some(FieldVal::Decimal(val));
+                                has_fields = true;
+                            }
+                        }
+                    }
                     _ => {
                         error!("unsupported field type");
                     }
@@ -663,6 +784,9 @@ pub enum DataType {
     /// This variant is used for multiple Clone.
     /// If not, please use [`DataType::Str`].
     StrRef(i64, Arc<Vec<u8>>),
+    I128(i64, i128),
+    I32(i64, i32),
+    F32(i64, f32),
 }
 
 impl PartialEq for DataType {
@@ -694,6 +818,9 @@ impl DataType {
             ValueType::Float => DataType::F64(ts, 0.0),
             ValueType::Boolean => DataType::Bool(ts, false),
             ValueType::String => DataType::Str(ts, mini_vec![]),
+            ValueType::Decimal => DataType::I128(ts, 0),
+            ValueType::Integer32 => DataType::I32(ts, 0),
+            ValueType::Float32 => DataType::F32(ts, 0.0),
             _ => todo!(),
         }
     }
@@ -705,6 +832,9 @@ impl DataType {
             DataType::F64(ts, ..) => ts,
             DataType::Bool(ts, ..) => ts,
             DataType::StrRef(ts, ..) => ts,
+            DataType::I128(ts, ..) => ts,
+            DataType::I32(ts, ..) => ts,
+            DataType::F32(ts, ..) => ts,
         }
     }
 
@@ -715,6 +845,23 @@ impl DataType {
             FieldVal::Unsigned(val) => Self::U64(ts, val),
             FieldVal::Boolean(val) => Self::Bool(ts, val),
             FieldVal::Bytes(val) => Self::Str(ts, val),
+            FieldVal::Decimal(val) => Self::I128(ts, val),
+            FieldVal::Integer32(val) => Self::I32(ts, val),
+            FieldVal::Float32(val) => Self::F32(ts, val),
+        }
+    }
+
+    pub fn into_field_val(self) -> FieldVal {
+        match self {
+            DataType::U64(_, val) => FieldVal::Unsigned(val),
+            DataType::I64(_, val) => FieldVal::Integer(val),
+            DataType::Str(_, val) => FieldVal::Bytes(val),
+            DataType::F64(_, val) => FieldVal::Float(val),
+            DataType::Bool(_, val) => FieldVal::Boolean(val),
+            DataType::StrRef(_, val) => FieldVal::Bytes(MiniVec::from(&val[..])),
+            DataType::I128(_, val) => FieldVal::Decimal(val),
+            DataType::I32(_, val) => FieldVal::Integer32(val),
+            DataType::F32(_, val) => FieldVal::Float32(val),
         }
     }
 
@@ -759,6 +906,24 @@ impl DataType {
                 buf[8..buf_len].copy_from_slice(val);
                 buf
             }
+            DataType::I128(t, val) => {
+                let mut buf = vec![0; 24];
+                buf[0..8].copy_from_slice(t.to_be_bytes().as_slice());
+                buf[8..24].copy_from_slice(val.to_be_bytes().as_slice());
+                buf
+            }
+            DataType::I32(t, val) => {
+                let mut buf = vec![0; 12];
+                buf[0..8].copy_from_slice(t.to_be_bytes().as_slice());
+                buf[8..12].copy_from_slice(val.to_be_bytes().as_slice());
+                buf
+            }
+            DataType::F32(t, val) => {
+                let mut buf = vec![0; 12];
+                buf[0..8].copy_from_slice(t.to_be_bytes().as_slice());
+                buf[8..12].copy_from_slice(val.to_be_bytes().as_slice());
+                buf
+            }
         }
     }
 }
@@ -772,6 +937,9 @@ impl Display for DataType {
             DataType::F64(ts, val) => write!(f, "({}, {})", ts, val),
             DataType::Bool(ts, val) => write!(f, "({}, {})", ts, val),
             DataType::StrRef(ts, val) => write!(f, "({}, {:?})", ts, val),
+            DataType::I128(ts, val) => write!(f, "({}, {})", ts, val),
+            DataType::I32(ts, val) => write!(f, "({}, {})", ts, val),
+            DataType::F32(ts, val) => write!(f, "({}, {})", ts, val),
         }
     }
 }
@@ -1,25 +1,36 @@
 use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::sync::Arc;
 
 use datafusion::arrow::array::{
-    ArrayBuilder, ArrayRef, BooleanArray, BooleanBuilder, Float64Builder, Int64Builder,
-    PrimitiveArray, PrimitiveBuilder, StringArray, StringBuilder, TimestampMicrosecondBuilder,
+    ArrayBuilder, ArrayRef, BinaryBuilder, BooleanArray, BooleanBuilder, Decimal128Builder,
+    Float32Builder, Float64Builder, Int32Builder, Int64Builder, PrimitiveArray, PrimitiveBuilder,
+    StringArray, StringBuilder, StringDictionaryBuilder, TimestampMicrosecondBuilder,
     TimestampMillisecondBuilder, TimestampNanosecondBuilder, TimestampSecondBuilder, UInt64Builder,
 };
 use datafusion::arrow::datatypes::{
-    ArrowPrimitiveType, Float64Type, Int64Type, SchemaRef, TimeUnit, TimestampMicrosecondType,
-    TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt64Type,
+    ArrowPrimitiveType, Decimal128Type, Float32Type, Float64Type, Int32Type, Int64Type, SchemaRef,
+    TimeUnit, TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt64Type,
 };
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::physical_plan::metrics::{self, ExecutionPlanMetricsSet, MetricBuilder};
+use datafusion::scalar::ScalarValue;
 use futures::future::join_all;
 use models::meta_data::VnodeId;
-use models::predicate::domain::{self, QueryArgs, QueryExpr, TimeRanges};
+use models::predicate::domain::{
+    self, Domain, PushedAggregateFunction, PushedAggregateGrouping, QueryArgs, QueryExpr,
+    TimeRanges,
+};
 use models::predicate::PlacedSplit;
-use models::schema::{PhysicalCType as ColumnType, TableColumn, TskvTableSchemaRef};
+use models::schema::{
+    ColumnType as LogicalColumnType, PhysicalCType as ColumnType, TableColumn, TskvTableSchemaRef,
+};
 use models::utils::{min_num, unite_id};
-use models::{FieldId, PhysicalDType as ValueType, SeriesId, Timestamp};
+use models::{
+    ColumnId, FieldId, PhysicalDType as ValueType, SeriesId, Timestamp,
+    ValueType as LogicalValueType,
+};
 use protos::kv_service::QueryRecordBatchRequest;
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
@@ -27,7 +38,10 @@ use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 use trace::{debug, error, SpanRecorder};
 
-use crate::compute::count::count_column_non_null_values;
+use crate::compute::aggregate::{
+    aggregate_field_values, aggregate_series_field_values_by_time_bucket,
+    count_column_non_null_values, FieldAggregate,
+};
 use crate::error::Result;
 use crate::memcache::DataType;
 use crate::reader::Cursor;
@@ -52,6 +66,13 @@ impl ArrayBuilderPtr {
         self.ptr.as_any_mut().downcast_mut::<PrimitiveBuilder<T>>()
     }
 
+    /// Returns true if the builder is a `B`, the builders of Binary fields differ
+    /// from the builders of their physical types.
+    #[inline(always)]
+    fn is_builder<B: ArrayBuilder>(&self) -> bool {
+        self.ptr.as_any().is::<B>()
+    }
+
     pub fn append_primitive<T: ArrowPrimitiveType>(&mut self, t: T::Native) {
         if let Some(b) = self.builder::<T>() {
             b.append_value(t);
@@ -84,6 +105,11 @@ impl ArrayBuilderPtr {
                     reason: format!("unknown type of column '{}'", column_name),
                 });
             }
+            ValueType::String if self.is_builder::<BinaryBuilder>() => match value {
+                Some(DataType::Str(_, val)) => self.append_binary(val.as_slice()),
+                Some(DataType::StrRef(_, val)) => self.append_binary(val.as_slice()),
+                _ => self.append_null_binary(),
+            },
             ValueType::String => match value {
                 Some(DataType::Str(_, val)) => {
                     // Safety
@@ -127,6 +153,27 @@ impl ArrayBuilderPtr {
                     self.append_primitive_null::<UInt64Type>();
                 }
             }
+            ValueType::Decimal => {
+                if let Some(DataType::I128(_, val)) = value {
+                    self.append_primitive::<Decimal128Type>(val);
+                } else {
+                    self.append_primitive_null::<Decimal128Type>();
+                }
+            }
+            ValueType::Integer32 => {
+                if let Some(DataType::I32(_, val)) = value {
+                    self.append_primitive::<Int32Type>(val);
+                } else {
+                    self.append_primitive_null::<Int32Type>();
+                }
+            }
+            ValueType::Float32 => {
+                if let Some(DataType::F32(_, val)) = value {
+                    self.append_primitive::<Float32Type>(val);
+                } else {
+                    self.append_primitive_null::<Float32Type>();
+                }
+            }
         }
         Ok(())
     }
@@ -165,7 +212,10 @@ impl ArrayBuilderPtr {
     }
 
     pub fn append_string(&mut self, data: &str) {
-        if let Some(b) = self.ptr.as_any_mut().downcast_mut::<StringBuilder>() {
+        let builder = self.ptr.as_any_mut();
+        if let Some(b) = builder.downcast_mut::<StringBuilder>() {
+            b.append_value(data);
+        } else if let Some(b) = builder.downcast_mut::<StringDictionaryBuilder<Int32Type>>() {
             b.append_value(data);
         } else {
             error!(
@@ -176,7 +226,10 @@ impl ArrayBuilderPtr {
     }
 
     pub fn append_null_string(&mut self) {
-        if let Some(b) = self.ptr.as_any_mut().downcast_mut::<StringBuilder>() {
+        let builder = self.ptr.as_any_mut();
+        if let Some(b) = builder.downcast_mut::<StringBuilder>() {
+            b.append_null();
+        } else if let Some(b) = builder.downcast_mut::<StringDictionaryBuilder<Int32Type>>() {
             b.append_null();
         } else {
             error!(
@@ -186,6 +239,28 @@ impl ArrayBuilderPtr {
         }
     }
 
+    pub fn append_binary(&mut self, data: &[u8]) {
+        if let Some(b) = self.ptr.as_any_mut().downcast_mut::<BinaryBuilder>() {
+            b.append_value(data);
+        } else {
+            error!(
+                "Failed to get binary array builder to insert {:?} array",
+                self.column_type
+            );
+        }
+    }
+
+    pub fn append_null_binary(&mut self) {
+        if let Some(b) = self.ptr.as_any_mut().downcast_mut::<BinaryBuilder>() {
+            b.append_null();
+        } else {
+            error!(
+                "Failed to get binary array builder to insert {:?} array",
+                self.column_type
+            );
+        }
+    }
+
     fn extend_primitive_array<T: ArrowPrimitiveType>(&mut self, array: ArrayRef) {
         let builder = self.builder::<T>();
         let array = array.as_any().downcast_ref::<PrimitiveArray<T>>();
@@ -347,7 +422,9 @@ pub struct QueryOption {
     pub split: PlacedSplit,
     pub df_schema: SchemaRef,
     pub table_schema: TskvTableSchemaRef,
-    pub aggregates: Option<Vec<TableColumn>>, // TODO: Use PushedAggregateFunction
+    pub aggregates: Option<Vec<PushedAggregateFunction>>,
+    /// Grouping of the aggregates, only used if aggregates is set.
+    pub aggregate_grouping: Option<PushedAggregateGrouping>,
 }
 
 impl QueryOption {
@@ -355,7 +432,7 @@ impl QueryOption {
     pub fn new(
         batch_size: usize,
         split: PlacedSplit,
-        aggregates: Option<Vec<TableColumn>>, // TODO: Use PushedAggregateFunction
+        aggregates: Option<Vec<PushedAggregateFunction>>,
         df_schema: SchemaRef,
         table_schema: TskvTableSchemaRef,
     ) -> Self {
@@ -363,11 +440,17 @@ impl QueryOption {
             batch_size,
             split,
             aggregates,
+            aggregate_grouping: None,
             df_schema,
             table_schema,
         }
     }
 
+    pub fn with_aggregate_grouping(mut self, grouping: Option<PushedAggregateGrouping>) -> Self {
+        self.aggregate_grouping = grouping;
+        self
+    }
+
     pub fn tenant_name(&self) -> &str {
         &self.table_schema.tenant
     }
@@ -385,6 +468,7 @@ impl QueryOption {
             split: self.split.clone(),
             df_schema: self.df_schema.as_ref().clone(),
             table_schema: self.table_schema.clone(),
+            aggregate_grouping: self.aggregate_grouping.clone(),
         };
 
         let args_bytes = QueryArgs::encode(&args)?;
@@ -403,6 +487,7 @@ pub struct FieldFileLocation {
     reader: Arc<TsmReader>,
     block_meta_iter: BlockMetaIterator,
     time_ranges: Arc<TimeRanges>,
+    value_filter: Option<Arc<StringValueFilter>>,
 
     data_block_reader: DataBlockReader,
 }
@@ -462,8 +547,9 @@ async fn open_field_file_location(
     time_ranges: Arc<TimeRanges>,
     field_id: FieldId,
     value_type: ValueType,
+    value_filter: Option<Arc<StringValueFilter>>,
 ) -> Result<Vec<FieldFileLocation>> {
-    let tsm_reader = version.get_tsm_reader(column_file.file_path()).await?;
+    let tsm_reader = version.get_column_file_reader(&column_file).await?;
     let res = tsm_reader
         .index_iterator_opt(field_id)
         .map(move |index_meta| {
@@ -472,6 +558,7 @@ async fn open_field_file_location(
                 time_ranges.clone(),
                 index_meta.block_iterator_opt(time_ranges.clone()),
                 value_type,
+                value_filter.clone(),
             )
         })
         .collect();
@@ -485,6 +572,7 @@ impl Level14TSDataStream {
         column_files: Vec<Arc<ColumnFile>>,
         field_id: FieldId,
         value_type: ValueType,
+        value_filter: Option<Arc<StringValueFilter>>,
     ) -> Result<Self> {
         let file_location_futures = column_files.into_iter().map(move |f| {
             open_field_file_location(
@@ -493,6 +581,7 @@ impl Level14TSDataStream {
                 time_ranges.clone(),
                 field_id,
                 value_type,
+                value_filter.clone(),
             )
         });
         let file_locations = join_all(file_location_futures)
@@ -509,7 +598,7 @@ impl Level14TSDataStream {
         })
     }
 
-    async fn next_data(&mut self) -> Result<Option<DataType>> {
+    pub(crate) async fn next_data(&mut self) -> Result<Option<DataType>> {
         loop {
             match &mut self.peeked_file_locations {
                 None => match self.field_file_location.next() {
@@ -536,6 +625,7 @@ impl Level0TSDataStream {
         column_files: Vec<Arc<ColumnFile>>,
         field_id: FieldId,
         value_type: ValueType,
+        value_filter: Option<Arc<StringValueFilter>>,
     ) -> Result<Self> {
         let locations_future = column_files.into_iter().map(|f| {
             open_field_file_location(
@@ -544,6 +634,7 @@ impl Level0TSDataStream {
                 time_ranges.clone(),
                 field_id,
                 value_type,
+                value_filter.clone(),
             )
         });
         let field_file_locations = join_all(locations_future)
@@ -628,11 +719,13 @@ impl FieldFileLocation {
         time_ranges: Arc<TimeRanges>,
         block_meta_iter: BlockMetaIterator,
         vtype: ValueType,
+        value_filter: Option<Arc<StringValueFilter>>,
     ) -> Self {
         Self {
             reader,
             block_meta_iter,
             time_ranges,
+            value_filter,
             data_block_reader: DataBlockReader::new_uninit(vtype),
         }
     }
@@ -665,7 +758,14 @@ impl FieldFileLocation {
             // Check if the time range of the BlockMeta intersected with the given time ranges.
             if let Some(intersected_tr) = self.time_ranges.intersect(&time_range) {
                 // Load a DataBlock from reader by BlockMeta.
-                let block = self.reader.get_data_block(&meta).await?;
+                let block = match &self.value_filter {
+                    Some(filter) => {
+                        self.reader
+                            .get_data_block_matched(&meta, &|v| filter.matches(v))
+                            .await?
+                    }
+                    None => self.reader.get_data_block(&meta).await?,
+                };
                 let mut data_block_reader = DataBlockReader::new(block, intersected_tr);
                 if data_block_reader.has_next() {
                     return Ok(Some(data_block_reader));
@@ -751,10 +851,48 @@ impl Cursor for TagCursor {
     }
 }
 
+/// Values accepted by the equality filters of a dictionary-encoded string field.
+///
+/// Values of the dictionary-encoded blocks are matched once for each string in the
+/// dictionary, and the values not matched are read as empty strings, so the values
+/// merged from the caches and files are matched again by the [`FieldCursor`].
+#[derive(Debug)]
+pub struct StringValueFilter {
+    values: HashSet<Vec<u8>>,
+}
+
+impl StringValueFilter {
+    /// Returns None if the domain is not the equal values of strings, or the values
+    /// contains an empty string, which can't be told from the values not matched.
+    pub fn try_new(domain: &Domain) -> Option<Self> {
+        let mut values = HashSet::new();
+        for value in domain.equal_values()? {
+            let value = match value {
+                ScalarValue::Utf8(Some(v)) => v,
+                ScalarValue::Dictionary(_, v) => match v.as_ref() {
+                    ScalarValue::Utf8(Some(v)) => v,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            if value.is_empty() {
+                return None;
+            }
+            values.insert(value.as_bytes().to_vec());
+        }
+        Some(Self { values })
+    }
+
+    pub fn matches(&self, value: &[u8]) -> bool {
+        self.values.contains(value)
+    }
+}
+
 //-----------Field Cursor----------------
 pub struct FieldCursor {
     name: Arc<String>,
     value_type: ValueType,
+    value_filter: Option<Arc<StringValueFilter>>,
 
     cache_data: Box<dyn Iterator<Item = DataType> + 'static>,
     peeked_cache: Option<DataType>,
@@ -774,6 +912,7 @@ impl FieldCursor {
         Self {
             name,
             value_type,
+            value_filter: None,
             cache_data: Box::new(std::iter::empty()),
             peeked_cache: None,
             level14_data_stream: None,
@@ -786,6 +925,7 @@ impl FieldCursor {
     pub fn new(
         name: Arc<String>,
         value_type: ValueType,
+        value_filter: Option<Arc<StringValueFilter>>,
         cache_data: Box<dyn Iterator<Item = DataType> + 'static>,
         level0_data_stream: Option<Level0TSDataStream>,
         level14_data_stream: Option<Level14TSDataStream>,
@@ -793,6 +933,7 @@ impl FieldCursor {
         Self {
             name,
             value_type,
+            value_filter,
             cache_data,
             peeked_cache: None,
             level0_data_stream,
@@ -827,19 +968,10 @@ impl FieldCursor {
             },
         }
     }
-}
 
-#[async_trait::async_trait]
-impl Cursor for FieldCursor {
-    fn name(&self) -> &String {
-        &self.name
-    }
-
-    fn column_type(&self) -> ColumnType {
-        ColumnType::Field(self.value_type)
-    }
-
-    async fn next(&mut self) -> Result<Option<DataType>> {
+    /// Returns the next data merged from the caches and files, data in the caches
+    /// overwrites the data in files, data in level 0 overwrites data in level 1-4.
+    async fn next_merged_data(&mut self) -> Result<Option<DataType>> {
         if self.peeked_cache.is_none() {
             self.peeked_cache = self.cache_data.next();
         }
@@ -885,6 +1017,31 @@ impl Cursor for FieldCursor {
     }
 }
 
+#[async_trait::async_trait]
+impl Cursor for FieldCursor {
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn column_type(&self) -> ColumnType {
+        ColumnType::Field(self.value_type)
+    }
+
+    async fn next(&mut self) -> Result<Option<DataType>> {
+        loop {
+            let data = self.next_merged_data().await?;
+            let matched = match (&self.value_filter, &data) {
+                (Some(filter), Some(DataType::Str(_, val))) => filter.matches(val.as_slice()),
+                (Some(filter), Some(DataType::StrRef(_, val))) => filter.matches(val.as_slice()),
+                _ => true,
+            };
+            if matched {
+                return Ok(data);
+            }
+        }
+    }
+}
+
 pub struct RowIterator {
     runtime: Arc<Runtime>,
     engine: EngineRef,
@@ -1040,6 +1197,7 @@ impl RowIterator {
                 .child(format!("SeriesGroupRowIterator [{}, {})", start, end)),
             metrics: SeriesGroupRowIteratorMetrics::new(&self.metrics_set, start),
             row_cols,
+            value_filters: Self::string_value_filters(self.query_option.as_ref()),
         };
         let can_tok = self.series_iter_closer.clone();
         self.runtime.spawn(async move {
@@ -1063,15 +1221,53 @@ impl RowIterator {
         });
     }
 
+    /// Filters of the dictionary-encoded string fields with equality filters.
+    fn string_value_filters(query_option: &QueryOption) -> HashMap<String, Arc<StringValueFilter>> {
+        let domains = match query_option.split.fields_filter().domains() {
+            Some(domains) => domains,
+            None => return HashMap::new(),
+        };
+        domains
+            .iter()
+            .filter(|(name, _)| {
+                query_option
+                    .table_schema
+                    .column(name)
+                    .map(|c| c.is_dictionary_encoded())
+                    .unwrap_or(false)
+            })
+            .filter_map(|(name, domain)| {
+                StringValueFilter::try_new(domain).map(|f| (name.clone(), Arc::new(f)))
+            })
+            .collect()
+    }
+
     fn build_record_builders(query_option: &QueryOption) -> Result<Vec<ArrayBuilderPtr>> {
         // Get builders for aggregating.
         if let Some(aggregates) = query_option.aggregates.as_ref() {
             let mut builders: Vec<ArrayBuilderPtr> = Vec::with_capacity(aggregates.len());
-            for _ in 0..aggregates.len() {
-                builders.push(ArrayBuilderPtr::new(
-                    Box::new(Int64Builder::with_capacity(query_option.batch_size)),
-                    ColumnType::Field(ValueType::Integer),
-                ));
+            // Start of the time buckets and the tags go before the aggregates.
+            if let Some(grouping) = query_option.aggregate_grouping.as_ref() {
+                let mut kv_dts = vec![query_option
+                    .table_schema
+                    .time_column()
+                    .column_type
+                    .to_physical_type()];
+                kv_dts.extend(grouping.tags.iter().map(|_| ColumnType::Tag));
+                for kv_dt in kv_dts {
+                    let builder_item = Self::new_column_builder(&kv_dt, query_option.batch_size)?;
+                    builders.push(ArrayBuilderPtr::new(builder_item, kv_dt));
+                }
+            }
+            for agg in aggregates {
+                let kv_dt = match agg {
+                    PushedAggregateFunction::Count(_) => ColumnType::Field(ValueType::Integer),
+                    _ => Self::aggregate_column(query_option, agg)?
+                        .column_type
+                        .to_physical_type(),
+                };
+                let builder_item = Self::new_column_builder(&kv_dt, query_option.batch_size)?;
+                builders.push(ArrayBuilderPtr::new(builder_item, kv_dt));
             }
             return Ok(builders);
         }
@@ -1085,12 +1281,32 @@ impl RowIterator {
                 item.id, item.name
             );
             let kv_dt = item.column_type.to_physical_type();
-            let builder_item = Self::new_column_builder(&kv_dt, query_option.batch_size)?;
+            let builder_item: Box<dyn ArrayBuilder> = if item.is_dictionary_encoded() {
+                Box::new(StringDictionaryBuilder::<Int32Type>::new())
+            } else if let Some(builder) =
+                Self::new_logical_field_builder(&item.column_type, query_option.batch_size)?
+            {
+                builder
+            } else {
+                Self::new_column_builder(&kv_dt, query_option.batch_size)?
+            };
             builders.push(ArrayBuilderPtr::new(builder_item, kv_dt))
         }
         Ok(builders)
     }
 
+    fn aggregate_column<'a>(
+        query_option: &'a QueryOption,
+        agg: &PushedAggregateFunction,
+    ) -> Result<&'a TableColumn> {
+        query_option
+            .table_schema
+            .column(agg.column_name())
+            .ok_or_else(|| Error::CommonError {
+                reason: format!("column of aggregate {:?} not found", agg),
+            })
+    }
+
     fn new_column_builder(
         column_type: &ColumnType,
         batch_size: usize,
@@ -1117,6 +1333,9 @@ impl RowIterator {
                 ValueType::String => {
                     Box::new(StringBuilder::with_capacity(batch_size, batch_size * 32))
                 }
+                ValueType::Decimal => Box::new(Decimal128Builder::with_capacity(batch_size)),
+                ValueType::Integer32 => Box::new(Int32Builder::with_capacity(batch_size)),
+                ValueType::Float32 => Box::new(Float32Builder::with_capacity(batch_size)),
                 ValueType::Unknown => {
                     return Err(Error::CommonError {
                         reason: "failed to create column builder: unkown column type".to_string(),
@@ -1125,6 +1344,28 @@ impl RowIterator {
             },
         })
     }
+
+    /// Create builders of the fields whose arrow type is not the arrow type of
+    /// the physical type, returns None for the other columns.
+    fn new_logical_field_builder(
+        column_type: &LogicalColumnType,
+        batch_size: usize,
+    ) -> Result<Option<Box<dyn ArrayBuilder>>> {
+        let builder: Box<dyn ArrayBuilder> = match column_type {
+            LogicalColumnType::Field(LogicalValueType::Binary) => {
+                Box::new(BinaryBuilder::with_capacity(batch_size, batch_size * 32))
+            }
+            LogicalColumnType::Field(LogicalValueType::Decimal128(precision, scale)) => Box::new(
+                Decimal128Builder::with_capacity(batch_size)
+                    .with_precision_and_scale(*precision, *scale)
+                    .map_err(|e| Error::CommonError {
+                        reason: format!("failed to create decimal column builder: {}", e),
+                    })?,
+            ),
+            _ => return Ok(None),
+        };
+        Ok(Some(builder))
+    }
 }
 
 impl RowIterator {
@@ -1230,6 +1471,8 @@ struct SeriesGroupRowIterator {
     metrics: SeriesGroupRowIteratorMetrics,
     // row_cols_cache
     row_cols: Vec<Option<DataType>>,
+    /// Filters of the dictionary-encoded string fields, by names of the fields.
+    value_filters: HashMap<String, Arc<StringValueFilter>>,
 }
 
 impl SeriesGroupRowIterator {
@@ -1283,7 +1526,9 @@ impl SeriesGroupRowIterator {
     ///
     /// If there is no remaning data to fetch, return Ok(None), otherwise return Ok(Some(())).
     async fn fetch_next_row(&mut self, builder: &mut [ArrayBuilderPtr]) -> Result<Option<()>> {
-        if self.query_option.aggregates.is_some() {
+        if self.query_option.aggregate_grouping.is_some() {
+            self.collect_grouped_aggregate_row_data(builder).await
+        } else if self.query_option.aggregates.is_some() {
             self.collect_aggregate_row_data(builder).await
         } else {
             loop {
@@ -1359,6 +1604,7 @@ impl SeriesGroupRowIterator {
                                     unite_id(item.id, series_id),
                                     Arc::new(item.name.clone()),
                                     vtype,
+                                    self.value_filters.get(&item.name).cloned(),
                                 )
                                 .await?;
                             Box::new(cursor)
@@ -1384,6 +1630,7 @@ impl SeriesGroupRowIterator {
         time_ranges: Arc<TimeRanges>,
         field_id: FieldId,
         value_type: ValueType,
+        value_filter: Option<Arc<StringValueFilter>>,
     ) -> Result<(Option<Level0TSDataStream>, Option<Level14TSDataStream>)> {
         let mut level_files = version.get_level_files(&time_ranges, field_id);
 
@@ -1395,6 +1642,7 @@ impl SeriesGroupRowIterator {
                     fs,
                     field_id,
                     value_type,
+                    value_filter.clone(),
                 )
                 .await?,
             ),
@@ -1434,6 +1682,7 @@ impl SeriesGroupRowIterator {
                     fs,
                     field_id,
                     value_type,
+                    value_filter,
                 )
                 .await?,
             )
@@ -1447,6 +1696,7 @@ impl SeriesGroupRowIterator {
         field_id: FieldId,
         field_name: Arc<String>,
         field_type: ValueType,
+        value_filter: Option<Arc<StringValueFilter>>,
     ) -> Result<FieldCursor> {
         let super_version = match self.super_version {
             Some(ref v) => v.clone(),
@@ -1482,11 +1732,13 @@ impl SeriesGroupRowIterator {
                 time_ranges_ref.clone(),
                 field_id,
                 field_type,
+                value_filter.clone(),
             )
             .await?;
         let cursor = FieldCursor::new(
             field_name.clone(),
             field_type,
+            value_filter,
             Box::new(cache_data_iter),
             l0_stream,
             l14_stream,
@@ -1597,11 +1849,16 @@ impl SeriesGroupRowIterator {
             self.query_option.aggregates.as_ref(),
         ) {
             (Some(version), Some(aggregates)) => {
-                for (i, item) in aggregates.iter().enumerate() {
+                // Aggregates of the same field are computed once.
+                let mut field_aggregates: HashMap<ColumnId, FieldAggregate> = HashMap::new();
+                for (i, agg) in aggregates.iter().enumerate() {
+                    let item = RowIterator::aggregate_column(&self.query_option, agg)?;
                     let kv_dt = item.column_type.to_physical_type();
-                    match kv_dt {
-                        ColumnType::Tag => todo!("collect count for tag"),
-                        ColumnType::Time(_) => {
+                    let vtype = match (agg, kv_dt) {
+                        (PushedAggregateFunction::Count(_), ColumnType::Tag) => {
+                            todo!("collect count for tag")
+                        }
+                        (PushedAggregateFunction::Count(_), ColumnType::Time(_)) => {
                             let agg_ret = count_column_non_null_values(
                                 self.runtime.clone(),
                                 version.clone(),
@@ -1611,26 +1868,50 @@ impl SeriesGroupRowIterator {
                             )
                             .await?;
                             builder[i].append_primitive::<Int64Type>(agg_ret as i64);
+                            continue;
+                        }
+                        (_, ColumnType::Field(ValueType::Unknown)) => {
+                            return Err(Error::CommonError {
+                                reason: format!("unknown type of {}", item.name),
+                            });
+                        }
+                        (PushedAggregateFunction::Count(_), ColumnType::Field(_)) => {
+                            let agg_ret = count_column_non_null_values(
+                                self.runtime.clone(),
+                                version.clone(),
+                                self.series_ids.clone(),
+                                Some(item.id),
+                                self.query_option.split.time_ranges(),
+                            )
+                            .await?;
+                            builder[i].append_primitive::<Int64Type>(agg_ret as i64);
+                            continue;
+                        }
+                        (_, ColumnType::Field(vtype)) => vtype,
+                        _ => {
+                            return Err(Error::CommonError {
+                                reason: format!("unsupported aggregate {:?}", agg),
+                            });
                         }
-                        ColumnType::Field(vtype) => match vtype {
-                            ValueType::Unknown => {
-                                return Err(Error::CommonError {
-                                    reason: format!("unknown type of {}", item.name),
-                                });
-                            }
-                            _ => {
-                                let agg_ret = count_column_non_null_values(
-                                    self.runtime.clone(),
-                                    version.clone(),
-                                    self.series_ids.clone(),
-                                    Some(item.id),
-                                    self.query_option.split.time_ranges(),
-                                )
-                                .await?;
-                                builder[i].append_primitive::<Int64Type>(agg_ret as i64);
-                            }
-                        },
                     };
+
+                    if !field_aggregates.contains_key(&item.id) {
+                        let field_aggregate = aggregate_field_values(
+                            self.runtime.clone(),
+                            version.clone(),
+                            self.series_ids.clone(),
+                            item.id,
+                            self.query_option.split.time_ranges(),
+                        )
+                        .await?;
+                        field_aggregates.insert(item.id, field_aggregate);
+                    }
+                    let value = field_aggregates[&item.id].value(agg);
+                    builder[i].append_value(
+                        vtype,
+                        value.map(|v| DataType::with_field_val(0, v)),
+                        &item.name,
+                    )?;
                 }
 
                 Ok(Some(()))
@@ -1638,12 +1919,153 @@ impl SeriesGroupRowIterator {
             _ => Ok(None),
         }
     }
+
+    /// Collect aggregates of the next series by time buckets, a row for each
+    /// bucket which has values is appended.
+    async fn collect_grouped_aggregate_row_data(
+        &mut self,
+        builders: &mut [ArrayBuilderPtr],
+    ) -> Result<Option<()>> {
+        let (version, aggregates, grouping) = match (
+            self.super_version.as_ref(),
+            self.query_option.aggregates.as_ref(),
+            self.query_option.aggregate_grouping.as_ref(),
+        ) {
+            (Some(version), Some(aggregates), Some(grouping)) => (version, aggregates, grouping),
+            _ => return Ok(None),
+        };
+        if self.i >= self.end {
+            return Ok(None);
+        }
+        let series_id = self.series_ids[self.i];
+        self.i += 1;
+
+        let table_schema = &self.query_option.table_schema;
+        let series_key = match self
+            .engine
+            .get_series_key(
+                &table_schema.tenant,
+                &table_schema.db,
+                &table_schema.name,
+                self.vnode_id,
+                &[series_id],
+            )
+            .await?
+            .pop()
+        {
+            Some(key) => key,
+            None => return Ok(Some(())),
+        };
+        let mut tag_values = Vec::with_capacity(grouping.tags.len());
+        for tag in grouping.tags.iter() {
+            let column = table_schema.column(tag).ok_or_else(|| Error::CommonError {
+                reason: format!("tag column '{}' to group by not found", tag),
+            })?;
+            tag_values.push(series_key.tag_val(column.id.to_string().as_str()));
+        }
+        let time_unit = match table_schema.time_column().column_type.to_physical_type() {
+            ColumnType::Time(unit) => unit,
+            _ => {
+                return Err(Error::CommonError {
+                    reason: format!("time column of table '{}' not found", table_schema.name),
+                })
+            }
+        };
+
+        // Aggregates of the same field are computed once.
+        let mut columns: Vec<(&TableColumn, ValueType)> = Vec::with_capacity(aggregates.len());
+        let mut field_aggregates: HashMap<ColumnId, BTreeMap<Timestamp, FieldAggregate>> =
+            HashMap::new();
+        for agg in aggregates.iter() {
+            let item = RowIterator::aggregate_column(&self.query_option, agg)?;
+            let vtype = match (agg, item.column_type.to_physical_type()) {
+                (_, ColumnType::Field(ValueType::Unknown)) => {
+                    return Err(Error::CommonError {
+                        reason: format!("unknown type of {}", item.name),
+                    });
+                }
+                (PushedAggregateFunction::Count(_), ColumnType::Field(_)) => ValueType::Integer,
+                (_, ColumnType::Field(vtype)) => vtype,
+                _ => {
+                    return Err(Error::CommonError {
+                        reason: format!("unsupported grouped aggregate {:?}", agg),
+                    });
+                }
+            };
+            columns.push((item, vtype));
+            if !field_aggregates.contains_key(&item.id) {
+                let bucket_aggregates = aggregate_series_field_values_by_time_bucket(
+                    version.clone(),
+                    series_id,
+                    item.id,
+                    self.query_option.split.time_ranges(),
+                    |ts| grouping.time_bucket(ts),
+                )
+                .await?;
+                field_aggregates.insert(item.id, bucket_aggregates);
+            }
+        }
+
+        let buckets: BTreeSet<Timestamp> = field_aggregates
+            .values()
+            .flat_map(|bucket_aggregates| bucket_aggregates.keys().copied())
+            .collect();
+        let empty_aggregate = FieldAggregate::default();
+        for bucket in buckets {
+            builders[0].append_timestamp(&time_unit, bucket);
+            for ((builder, tag), tag_value) in builders[1..]
+                .iter_mut()
+                .zip(grouping.tags.iter())
+                .zip(tag_values.iter())
+            {
+                let value = tag_value.clone().map(|v| DataType::StrRef(0, Arc::new(v)));
+                builder.append_value(ValueType::String, value, tag)?;
+            }
+            let offset = 1 + grouping.tags.len();
+            for (i, (agg, (item, vtype))) in aggregates.iter().zip(columns.iter()).enumerate() {
+                let value = field_aggregates[&item.id]
+                    .get(&bucket)
+                    .unwrap_or(&empty_aggregate)
+                    .value(agg);
+                builders[offset + i].append_value(
+                    *vtype,
+                    value.map(|v| DataType::with_field_val(0, v)),
+                    &item.name,
+                )?;
+            }
+        }
+
+        Ok(Some(()))
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use datafusion::arrow::datatypes::DataType as ArrowDataType;
+    use datafusion::scalar::ScalarValue;
+    use models::predicate::domain::{Domain, Range};
+
+    use super::StringValueFilter;
+
     #[test]
     fn test_field_cursor() {
         // TODO: Test multi-level contains the same timestamp with different values.
     }
+
+    #[test]
+    fn test_string_value_filter() {
+        let ok = ScalarValue::Utf8(Some("ok".to_string()));
+        let domain = Domain::of_ranges(&[Range::eq(&ArrowDataType::Utf8, &ok)]).unwrap();
+        let filter = StringValueFilter::try_new(&domain).unwrap();
+        assert!(filter.matches(b"ok"));
+        assert!(!filter.matches(b"failed"));
+        assert!(!filter.matches(b""));
+
+        let empty = ScalarValue::Utf8(Some(String::new()));
+        let domain = Domain::of_values(&ArrowDataType::Utf8, true, &[&ok, &empty]);
+        assert!(StringValueFilter::try_new(&domain).is_none());
+
+        let domain = Domain::of_ranges(&[Range::gt(&ArrowDataType::Utf8, &ok)]).unwrap();
+        assert!(StringValueFilter::try_new(&domain).is_none());
+    }
 }
@@ -76,6 +76,8 @@ pub const READER_BUF_SIZE: usize = 1024 * 1024 * 64; //64MB
 #[repr(u8)]
 pub enum RecordDataVersion {
     V1 = 1,
+    /// Summary records with `CompactMeta::cold_location`.
+    V2 = 2,
 }
 
 #[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
@@ -42,6 +42,8 @@ pub struct CompactMeta {
     pub high_seq: u64,
     pub low_seq: u64,
     pub is_delta: bool,
+    /// Location of the file in the cold storage, `None` if the file is local.
+    pub cold_location: Option<String>,
 }
 
 impl Default for CompactMeta {
@@ -56,6 +58,7 @@ impl Default for CompactMeta {
             high_seq: u64::MIN,
             low_seq: u64::MIN,
             is_delta: false,
+            cold_location: None,
         }
     }
 }
@@ -73,6 +76,7 @@ impl From<&ColumnFile> for CompactMeta {
             min_ts: file.time_range().min_ts,
             max_ts: file.time_range().max_ts,
             is_delta: file.is_delta(),
+            cold_location: file.cold_location().map(|l| l.to_string()),
             ..Default::default()
         }
     }
@@ -118,13 +122,56 @@ impl CompactMeta {
             let base_dir = storage_opt.tsm_dir(database, ts_family_id);
             file_utils::make_tsm_file(base_dir, file_id)
         };
-        trace::info!("rename file from {:?} to {:?}", &old_name, &new_name);
-        file_utils::rename(old_name, &new_name).await?;
+        // Tsm file in the cold storage is not downloaded, only the tombstone is local.
+        if self.cold_location.is_none() {
+            trace::info!("rename file from {:?} to {:?}", &old_name, &new_name);
+            file_utils::rename(&old_name, &new_name).await?;
+        }
+
+        // The tombstone is stored beside the file, rename it if exists.
+        if let (Some(old_dir), Some(new_dir)) = (old_name.parent(), new_name.parent()) {
+            let old_tombstone = file_utils::make_tsm_tombstone_file(old_dir, self.file_id);
+            if try_exists(&old_tombstone) {
+                let new_tombstone = file_utils::make_tsm_tombstone_file(new_dir, file_id);
+                file_utils::rename(old_tombstone, new_tombstone).await?;
+            }
+        }
         self.file_id = file_id;
         Ok(new_name)
     }
 }
 
+/// `CompactMeta` of the summary records of `RecordDataVersion::V1`, before cold storage.
+#[derive(Serialize, Deserialize)]
+struct CompactMetaV1 {
+    file_id: ColumnFileId,
+    file_size: u64,
+    tsf_id: TseriesFamilyId,
+    level: LevelId,
+    min_ts: Timestamp,
+    max_ts: Timestamp,
+    high_seq: u64,
+    low_seq: u64,
+    is_delta: bool,
+}
+
+impl From<CompactMetaV1> for CompactMeta {
+    fn from(meta: CompactMetaV1) -> Self {
+        Self {
+            file_id: meta.file_id,
+            file_size: meta.file_size,
+            tsf_id: meta.tsf_id,
+            level: meta.level,
+            min_ts: meta.min_ts,
+            max_ts: meta.max_ts,
+            high_seq: meta.high_seq,
+            low_seq: meta.low_seq,
+            is_delta: meta.is_delta,
+            cold_location: None,
+        }
+    }
+}
+
 pub struct CompactMetaBuilder {
     pub ts_family_id: TseriesFamilyId,
 }
@@ -171,6 +218,41 @@ pub struct VersionEdit {
     pub tsf_name: String,
 }
 
+/// `VersionEdit` of the summary records of `RecordDataVersion::V1`.
+#[derive(Serialize, Deserialize)]
+struct VersionEditV1 {
+    has_seq_no: bool,
+    seq_no: u64,
+    has_file_id: bool,
+    file_id: u64,
+    max_level_ts: Timestamp,
+    add_files: Vec<CompactMetaV1>,
+    del_files: Vec<CompactMetaV1>,
+
+    del_tsf: bool,
+    add_tsf: bool,
+    tsf_id: TseriesFamilyId,
+    tsf_name: String,
+}
+
+impl From<VersionEditV1> for VersionEdit {
+    fn from(edit: VersionEditV1) -> Self {
+        Self {
+            has_seq_no: edit.has_seq_no,
+            seq_no: edit.seq_no,
+            has_file_id: edit.has_file_id,
+            file_id: edit.file_id,
+            max_level_ts: edit.max_level_ts,
+            add_files: edit.add_files.into_iter().map(CompactMeta::from).collect(),
+            del_files: edit.del_files.into_iter().map(CompactMeta::from).collect(),
+            del_tsf: edit.del_tsf,
+            add_tsf: edit.add_tsf,
+            tsf_id: edit.tsf_id,
+            tsf_name: edit.tsf_name,
+        }
+    }
+}
+
 impl Default for VersionEdit {
     fn default() -> Self {
         Self {
@@ -224,6 +306,19 @@ impl VersionEdit {
         bincode::deserialize(buf).map_err(|e| Error::RecordFileDecode { source: (e) })
     }
 
+    /// Decode a summary record by the `data_version` it was written with.
+    pub fn decode_summary_record(data_version: u8, buf: &[u8]) -> Result<Self> {
+        match RecordDataVersion::try_from(data_version) {
+            Ok(RecordDataVersion::V1) => bincode::deserialize::<VersionEditV1>(buf)
+                .map(Self::from)
+                .map_err(|e| Error::RecordFileDecode { source: (e) }),
+            Ok(RecordDataVersion::V2) => Self::decode(buf),
+            Err(_) => Err(Error::RecordFileIo {
+                reason: format!("unknown data version of summary record: {data_version}"),
+            }),
+        }
+    }
+
     pub fn encode_vec(data: &[Self]) -> Result<Vec<u8>> {
         let mut buf: Vec<u8> = Vec::with_capacity(data.len() * 32);
         for ve in data {
@@ -315,7 +410,7 @@ impl Summary {
         let buf = db.encode()?;
         let _ = w
             .write_record(
-                RecordDataVersion::V1.into(),
+                RecordDataVersion::V2.into(),
                 RecordDataType::Summary.into(),
                 &[&buf],
             )
@@ -412,7 +507,7 @@ impl Summary {
             let res = reader.read_record().await;
             match res {
                 Ok(result) => {
-                    let ed = VersionEdit::decode(&result.data)?;
+                    let ed = VersionEdit::decode_summary_record(result.data_version, &result.data)?;
                     if ed.add_tsf {
                         let db_ref = database_map
                             .entry(ed.tsf_name.clone())
@@ -475,7 +570,13 @@ impl Summary {
             for meta in files.into_values() {
                 let field_filter = if load_field_filter {
                     let tsm_path = meta.file_path(opt.storage.as_ref(), &database, tsf_id);
-                    let tsm_reader = TsmReader::open(tsm_path).await?;
+                    let tsm_reader = match &meta.cold_location {
+                        Some(location) => {
+                            let tombstone_dir = tsm_path.parent().unwrap_or_else(|| Path::new("/"));
+                            TsmReader::open_cold(meta.file_id, location, tombstone_dir).await?
+                        }
+                        None => TsmReader::open(tsm_path).await?,
+                    };
                     tsm_reader.bloom_filter()
                 } else {
                     Arc::new(BloomFilter::default())
@@ -543,7 +644,7 @@ impl Summary {
             let _ = self
                 .writer
                 .write_record(
-                    RecordDataVersion::V1.into(),
+                    RecordDataVersion::V2.into(),
                     RecordDataType::Summary.into(),
                     &[&buf],
                 )
@@ -641,7 +742,8 @@ pub async fn print_summary_statistics(path: impl AsRef<Path>) {
     loop {
         match reader.read_record().await {
             Ok(record) => {
-                let ve = VersionEdit::decode(&record.data).unwrap();
+                let ve =
+                    VersionEdit::decode_summary_record(record.data_version, &record.data).unwrap();
                 println!("VersionEdit #{}, vnode_id: {}", i, ve.tsf_id);
                 println!("------------------------------------------------------------");
                 i += 1;
@@ -817,7 +919,10 @@ mod test {
     use crate::file_system::file_manager;
     use crate::kv_option::Options;
     use crate::kvcore::{COMPACT_REQ_CHANNEL_CAP, SUMMARY_REQ_CHANNEL_CAP};
-    use crate::summary::{CompactMeta, Summary, SummaryTask, VersionEdit};
+    use crate::record_file::RecordDataVersion;
+    use crate::summary::{
+        CompactMeta, CompactMetaV1, Summary, SummaryTask, VersionEdit, VersionEditV1,
+    };
     use crate::{TsKvContext, TseriesFamilyId};
 
     #[test]
@@ -846,6 +951,48 @@ mod test {
         assert_eq!(ves, ves_2);
     }
 
+    #[test]
+    fn test_decode_summary_record_v1() {
+        let ve = VersionEditV1 {
+            has_seq_no: true,
+            seq_no: 1,
+            has_file_id: true,
+            file_id: 2,
+            max_level_ts: 100,
+            add_files: vec![CompactMetaV1 {
+                file_id: 2,
+                file_size: 1024,
+                tsf_id: 3,
+                level: 1,
+                min_ts: 1,
+                max_ts: 100,
+                high_seq: 1,
+                low_seq: 0,
+                is_delta: false,
+            }],
+            del_files: vec![],
+            del_tsf: false,
+            add_tsf: false,
+            tsf_id: 3,
+            tsf_name: "cnosdb.public".to_string(),
+        };
+        let ve_buf = bincode::serialize(&ve).unwrap();
+        let ve = VersionEdit::decode_summary_record(RecordDataVersion::V1.into(), &ve_buf).unwrap();
+        assert_eq!(ve.add_files.len(), 1);
+        assert_eq!(ve.add_files[0].file_size, 1024);
+        assert_eq!(ve.add_files[0].cold_location, None);
+        assert_eq!(ve.tsf_name, "cnosdb.public");
+
+        let mut ve2 = ve.clone();
+        ve2.add_files[0].cold_location = Some("cnosdb.public/3/_000002.tsm".to_string());
+        let ve2_buf = ve2.encode().unwrap();
+        assert_eq!(
+            VersionEdit::decode_summary_record(RecordDataVersion::V2.into(), &ve2_buf).unwrap(),
+            ve2
+        );
+        assert!(VersionEdit::decode_summary_record(u8::MAX, &ve2_buf).is_err());
+    }
+
     struct SummaryTestHelper {
         test_case_name: String,
         base_dir: String,
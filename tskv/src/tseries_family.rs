@@ -30,7 +30,7 @@ use crate::memcache::{DataType, FieldVal, MemCache, RowGroup};
 use crate::summary::{CompactMeta, VersionEdit};
 use crate::tsm::{self, TsmReader};
 use crate::Error::CommonError;
-use crate::{ColumnFileId, LevelId, Options, TseriesFamilyId};
+use crate::{cold_storage, ColumnFileId, LevelId, Options, TseriesFamilyId};
 
 #[derive(Debug)]
 pub struct ColumnFile {
@@ -42,8 +42,11 @@ pub struct ColumnFile {
     field_id_filter: Arc<BloomFilter>,
     deleted: AtomicBool,
     compacting: AtomicBool,
+    moved_to_cold: AtomicBool,
 
     path: PathBuf,
+    /// Location in the cold storage, the file is not in `path` if this is set.
+    cold_location: Option<String>,
     tsm_reader_cache: Weak<ShardedAsyncCache<String, Arc<TsmReader>>>,
 }
 
@@ -63,7 +66,9 @@ impl ColumnFile {
             field_id_filter,
             deleted: AtomicBool::new(false),
             compacting: AtomicBool::new(false),
+            moved_to_cold: AtomicBool::new(false),
             path: path.as_ref().into(),
+            cold_location: meta.cold_location.clone(),
             tsm_reader_cache,
         }
     }
@@ -92,6 +97,18 @@ impl ColumnFile {
         &self.path
     }
 
+    pub fn is_cold(&self) -> bool {
+        self.cold_location.is_some()
+    }
+
+    pub fn cold_location(&self) -> Option<&str> {
+        self.cold_location.as_deref()
+    }
+
+    pub fn field_id_filter(&self) -> Arc<BloomFilter> {
+        self.field_id_filter.clone()
+    }
+
     pub fn tombstone_path(&self) -> PathBuf {
         let mut path = self.path.clone();
         path.set_extension(tsm::TOMBSTONE_FILE_SUFFIX);
@@ -138,6 +155,13 @@ impl ColumnFile {
     pub fn unmark_compacting(&self) {
         self.compacting.store(false, Ordering::Release);
     }
+
+    /// Mark the local file deleted, but keep it's tombstone,
+    /// it's still used by the file in the cold storage.
+    pub fn mark_moved_to_cold(&self) {
+        self.moved_to_cold.store(true, Ordering::Release);
+        self.deleted.store(true, Ordering::Release);
+    }
 }
 
 impl Drop for ColumnFile {
@@ -149,12 +173,24 @@ impl Drop for ColumnFile {
         if self.is_deleted() {
             let path = self.file_path();
             if let Some(cache) = self.tsm_reader_cache.upgrade() {
-                let k = format!("{}", path.display());
+                let k = match &self.cold_location {
+                    Some(location) => location.clone(),
+                    None => format!("{}", path.display()),
+                };
                 tokio::spawn(async move {
                     cache.remove(&k).await;
                 });
             }
-            if let Err(e) = std::fs::remove_file(path) {
+            if let Some(location) = self.cold_location.clone() {
+                let file_id = self.file_id;
+                tokio::spawn(async move {
+                    if let Err(e) = cold_storage::delete(&location).await {
+                        error!("Failed to remove tsm file {file_id} at '{location}': {e}");
+                    } else {
+                        info!("Removed tsm file {file_id} at '{location}'");
+                    }
+                });
+            } else if let Err(e) = std::fs::remove_file(path) {
                 error!(
                     "Failed to remove tsm file {} at '{}': {e}",
                     self.file_id,
@@ -164,6 +200,9 @@ impl Drop for ColumnFile {
                 info!("Removed tsm file {} at '{}", self.file_id, path.display());
             }
 
+            if self.moved_to_cold.load(Ordering::Acquire) {
+                return;
+            }
             let tombstone_path = self.tombstone_path();
             if file_manager::try_exists(&tombstone_path) {
                 if let Err(e) = std::fs::remove_file(&tombstone_path) {
@@ -199,7 +238,9 @@ impl ColumnFile {
             field_id_filter: Arc::new(BloomFilter::default()),
             deleted: AtomicBool::new(false),
             compacting: AtomicBool::new(false),
+            moved_to_cold: AtomicBool::new(false),
             path: path.as_ref().into(),
+            cold_location: None,
             tsm_reader_cache: Weak::new(),
         }
     }
@@ -416,9 +457,13 @@ impl Version {
     ) -> Version {
         let mut added_files: Vec<Vec<CompactMeta>> = vec![vec![]; 5];
         let mut deleted_files: Vec<HashSet<ColumnFileId>> = vec![HashSet::new(); 5];
+        let mut cold_files: HashSet<ColumnFileId> = HashSet::new();
         for ve in version_edits.into_iter() {
             if !ve.add_files.is_empty() {
                 ve.add_files.into_iter().for_each(|f| {
+                    if f.cold_location.is_some() {
+                        cold_files.insert(f.file_id);
+                    }
                     added_files[f.level as usize].push(f);
                 });
             }
@@ -438,7 +483,11 @@ impl Version {
         for level in self.levels_info.iter() {
             for file in level.files.iter() {
                 if deleted_files[file.level as usize].contains(&file.file_id) {
-                    file.mark_deleted();
+                    if !file.is_cold() && cold_files.contains(&file.file_id) {
+                        file.mark_moved_to_cold();
+                    } else {
+                        file.mark_deleted();
+                    }
                     continue;
                 }
                 new_levels[level.level as usize].push_column_file(file.clone());
@@ -521,6 +570,27 @@ impl Version {
         Ok(tsm_reader)
     }
 
+    /// Get the `TsmReader` of a column file, the file may be in the cold storage.
+    pub async fn get_column_file_reader(&self, file: &ColumnFile) -> Result<Arc<TsmReader>> {
+        let location = match file.cold_location() {
+            Some(l) => l.to_string(),
+            None => return self.get_tsm_reader(file.file_path()).await,
+        };
+        let tsm_reader = match self.tsm_reader_cache.get(&location).await {
+            Some(val) => val,
+            None => {
+                let tombstone_dir = file.file_path().parent().unwrap_or_else(|| Path::new("/"));
+                let tsm_reader =
+                    Arc::new(TsmReader::open_cold(file.file_id(), &location, tombstone_dir).await?);
+                self.tsm_reader_cache
+                    .insert(location, tsm_reader.clone())
+                    .await;
+                tsm_reader
+            }
+        };
+        Ok(tsm_reader)
+    }
+
     // return: l0 , l1-l4 files
     pub fn get_level_files(
         &self,
@@ -1179,7 +1249,7 @@ impl ColumnFileToTsmReaderIterator {
     pub async fn next(&mut self) -> Option<Result<Arc<TsmReader>>> {
         loop {
             if let Some(f) = self.inner_iter.next() {
-                let tsm_reader = match self.version.get_tsm_reader(f.file_path()).await {
+                let tsm_reader = match self.version.get_column_file_reader(&f).await {
                     Ok(r) => r,
                     Err(e) => {
                         if e.is_file_not_found_error() {
@@ -1315,6 +1385,7 @@ pub mod test_tseries_family {
                 high_seq: 2,
                 low_seq: 2,
                 is_delta: false,
+                cold_location: None,
             },
             3100,
         );
@@ -1412,6 +1483,7 @@ pub mod test_tseries_family {
                 high_seq: 2,
                 low_seq: 2,
                 is_delta: false,
+                cold_location: None,
             },
             3150,
         );
@@ -1427,6 +1499,7 @@ pub mod test_tseries_family {
                 high_seq: 2,
                 low_seq: 2,
                 is_delta: false,
+                cold_location: None,
             },
             3150,
         );
@@ -9,9 +9,10 @@ use trace::error;
 
 use crate::memcache::DataType;
 use crate::tsm::codec::{
-    get_bool_codec, get_encoding, get_f64_codec, get_i64_codec, get_str_codec, get_ts_codec,
-    get_u64_codec, DataBlockEncoding,
+    get_bool_codec, get_encoding, get_f32_codec, get_f64_codec, get_i128_codec, get_i32_codec,
+    get_i64_codec, get_str_codec, get_ts_codec, get_u64_codec, DataBlockEncoding,
 };
+use crate::tsm::BlockStatistics;
 
 pub trait ByTimeRange {
     fn time_range(&self) -> Option<TimeRange>;
@@ -46,6 +47,21 @@ pub enum DataBlock {
         val: Vec<bool>,
         enc: DataBlockEncoding,
     },
+    I128 {
+        ts: Vec<i64>,
+        val: Vec<i128>,
+        enc: DataBlockEncoding,
+    },
+    I32 {
+        ts: Vec<i64>,
+        val: Vec<i32>,
+        enc: DataBlockEncoding,
+    },
+    F32 {
+        ts: Vec<i64>,
+        val: Vec<f32>,
+        enc: DataBlockEncoding,
+    },
 }
 
 impl PartialEq for DataBlock {
@@ -106,6 +122,39 @@ impl PartialEq for DataBlock {
                     false
                 }
             }
+            DataBlock::I128 {
+                ts: ts_other,
+                val: val_other,
+                ..
+            } => {
+                if let Self::I128 { ts, val, .. } = self {
+                    ts.eq(ts_other) && val.eq(val_other)
+                } else {
+                    false
+                }
+            }
+            DataBlock::I32 {
+                ts: ts_other,
+                val: val_other,
+                ..
+            } => {
+                if let Self::I32 { ts, val, .. } = self {
+                    ts.eq(ts_other) && val.eq(val_other)
+                } else {
+                    false
+                }
+            }
+            DataBlock::F32 {
+                ts: ts_other,
+                val: val_other,
+                ..
+            } => {
+                if let Self::F32 { ts, val, .. } = self {
+                    ts.eq(ts_other) && val.eq(val_other)
+                } else {
+                    false
+                }
+            }
         }
     }
 }
@@ -138,6 +187,21 @@ impl DataBlock {
                 val: Vec::with_capacity(size),
                 enc: DataBlockEncoding::default(),
             },
+            PhysicalDType::Decimal => Self::I128 {
+                ts: Vec::with_capacity(size),
+                val: Vec::with_capacity(size),
+                enc: DataBlockEncoding::default(),
+            },
+            PhysicalDType::Integer32 => Self::I32 {
+                ts: Vec::with_capacity(size),
+                val: Vec::with_capacity(size),
+                enc: DataBlockEncoding::default(),
+            },
+            PhysicalDType::Float32 => Self::F32 {
+                ts: Vec::with_capacity(size),
+                val: Vec::with_capacity(size),
+                enc: DataBlockEncoding::default(),
+            },
             PhysicalDType::Unknown => {
                 todo!()
             }
@@ -183,6 +247,24 @@ impl DataBlock {
                     val.push(MiniVec::from(val_in.as_slice()))
                 }
             }
+            DataType::I128(ts_in, val_in) => {
+                if let Self::I128 { ts, val, .. } = self {
+                    ts.push(ts_in);
+                    val.push(val_in);
+                }
+            }
+            DataType::I32(ts_in, val_in) => {
+                if let Self::I32 { ts, val, .. } = self {
+                    ts.push(ts_in);
+                    val.push(val_in);
+                }
+            }
+            DataType::F32(ts_in, val_in) => {
+                if let Self::F32 { ts, val, .. } = self {
+                    ts.push(ts_in);
+                    val.push(val_in);
+                }
+            }
         }
     }
 
@@ -208,6 +290,10 @@ impl DataBlock {
                 ts.clear();
                 val.clear();
             }
+            DataBlock::I128 { ts, val, .. } => {
+                ts.clear();
+                val.clear();
+            }
         }
     }
 
@@ -222,6 +308,9 @@ impl DataBlock {
             DataBlock::Str { ts, .. } => Some((ts[0].to_owned(), ts[end - 1].to_owned())),
             DataBlock::F64 { ts, .. } => Some((ts[0].to_owned(), ts[end - 1].to_owned())),
             DataBlock::Bool { ts, .. } => Some((ts[0].to_owned(), ts[end - 1].to_owned())),
+            DataBlock::I128 { ts, .. } => Some((ts[0].to_owned(), ts[end - 1].to_owned())),
+            DataBlock::I32 { ts, .. } => Some((ts[0].to_owned(), ts[end - 1].to_owned())),
+            DataBlock::F32 { ts, .. } => Some((ts[0].to_owned(), ts[end - 1].to_owned())),
         }
     }
 
@@ -234,6 +323,9 @@ impl DataBlock {
             DataBlock::Str { ts, .. } => (ts[start].to_owned(), ts[end - 1].to_owned()),
             DataBlock::F64 { ts, .. } => (ts[start].to_owned(), ts[end - 1].to_owned()),
             DataBlock::Bool { ts, .. } => (ts[start].to_owned(), ts[end - 1].to_owned()),
+            DataBlock::I128 { ts, .. } => (ts[start].to_owned(), ts[end - 1].to_owned()),
+            DataBlock::I32 { ts, .. } => (ts[start].to_owned(), ts[end - 1].to_owned()),
+            DataBlock::F32 { ts, .. } => (ts[start].to_owned(), ts[end - 1].to_owned()),
         }
     }
 
@@ -252,6 +344,9 @@ impl DataBlock {
             Self::F64 { enc, .. } => *enc,
             Self::Str { enc, .. } => *enc,
             Self::Bool { enc, .. } => *enc,
+            Self::I128 { enc, .. } => *enc,
+            Self::I32 { enc, .. } => *enc,
+            Self::F32 { enc, .. } => *enc,
         }
     }
 
@@ -263,6 +358,9 @@ impl DataBlock {
             Self::F64 { ts, .. } => ts.len(),
             Self::Str { ts, .. } => ts.len(),
             Self::Bool { ts, .. } => ts.len(),
+            Self::I128 { ts, .. } => ts.len(),
+            Self::I32 { ts, .. } => ts.len(),
+            Self::F32 { ts, .. } => ts.len(),
         }
     }
 
@@ -274,6 +372,9 @@ impl DataBlock {
             DataBlock::Str { .. } => PhysicalDType::String,
             DataBlock::F64 { .. } => PhysicalDType::Float,
             DataBlock::Bool { .. } => PhysicalDType::Boolean,
+            DataBlock::I128 { .. } => PhysicalDType::Decimal,
+            DataBlock::I32 { .. } => PhysicalDType::Integer32,
+            DataBlock::F32 { .. } => PhysicalDType::Float32,
         }
     }
 
@@ -285,6 +386,9 @@ impl DataBlock {
             DataBlock::Str { ts, .. } => ts.as_slice(),
             DataBlock::F64 { ts, .. } => ts.as_slice(),
             DataBlock::Bool { ts, .. } => ts.as_slice(),
+            DataBlock::I128 { ts, .. } => ts.as_slice(),
+            DataBlock::I32 { ts, .. } => ts.as_slice(),
+            DataBlock::F32 { ts, .. } => ts.as_slice(),
         }
     }
 
@@ -296,6 +400,9 @@ impl DataBlock {
             DataBlock::Str { ts, .. } => ts.is_empty(),
             DataBlock::F64 { ts, .. } => ts.is_empty(),
             DataBlock::Bool { ts, .. } => ts.is_empty(),
+            DataBlock::I128 { ts, .. } => ts.is_empty(),
+            DataBlock::I32 { ts, .. } => ts.is_empty(),
+            DataBlock::F32 { ts, .. } => ts.is_empty(),
         }
     }
 
@@ -337,6 +444,27 @@ impl DataBlock {
                     Some(DataType::Bool(ts[i], val[i]))
                 }
             }
+            DataBlock::I128 { ts, val, .. } => {
+                if ts.len() <= i {
+                    None
+                } else {
+                    Some(DataType::I128(ts[i], val[i]))
+                }
+            }
+            DataBlock::I32 { ts, val, .. } => {
+                if ts.len() <= i {
+                    None
+                } else {
+                    Some(DataType::I32(ts[i], val[i]))
+                }
+            }
+            DataBlock::F32 { ts, val, .. } => {
+                if ts.len() <= i {
+                    None
+                } else {
+                    Some(DataType::F32(ts[i], val[i]))
+                }
+            }
         }
     }
 
@@ -363,6 +491,18 @@ impl DataBlock {
                 ts[i] = ts_in;
                 val[i] = val_in;
             }
+            (DataBlock::I128 { ts, val, .. }, DataType::I128(ts_in, val_in)) => {
+                ts[i] = ts_in;
+                val[i] = val_in;
+            }
+            (DataBlock::I32 { ts, val, .. }, DataType::I32(ts_in, val_in)) => {
+                ts[i] = ts_in;
+                val[i] = val_in;
+            }
+            (DataBlock::F32 { ts, val, .. }, DataType::F32(ts_in, val_in)) => {
+                ts[i] = ts_in;
+                val[i] = val_in;
+            }
             _ => {}
         }
     }
@@ -384,6 +524,15 @@ impl DataBlock {
             DataBlock::Bool { enc, .. } => {
                 *enc = encoding;
             }
+            DataBlock::I128 { enc, .. } => {
+                *enc = encoding;
+            }
+            DataBlock::I32 { enc, .. } => {
+                *enc = encoding;
+            }
+            DataBlock::F32 { enc, .. } => {
+                *enc = encoding;
+            }
         }
     }
 
@@ -536,6 +685,18 @@ impl DataBlock {
                 exclude_fast(ts, min, max);
                 exclude_fast(val, min, max);
             }
+            DataBlock::I128 { ts, val, .. } => {
+                exclude_fast(ts, min, max);
+                exclude_fast(val, min, max);
+            }
+            DataBlock::I32 { ts, val, .. } => {
+                exclude_fast(ts, min, max);
+                exclude_fast(val, min, max);
+            }
+            DataBlock::F32 { ts, val, .. } => {
+                exclude_fast(ts, min, max);
+                exclude_fast(val, min, max);
+            }
         }
     }
 
@@ -629,6 +790,45 @@ impl DataBlock {
                     enc: *enc,
                 }
             }
+            DataBlock::I128 { ts, val, enc } => {
+                let mut new_ts = vec![];
+                let mut new_val = vec![];
+                indexs.into_iter().for_each(|(min, max)| {
+                    new_ts.extend_from_slice(&ts[min..=max]);
+                    new_val.extend_from_slice(&val[min..=max])
+                });
+                DataBlock::I128 {
+                    ts: new_ts,
+                    val: new_val,
+                    enc: *enc,
+                }
+            }
+            DataBlock::I32 { ts, val, enc } => {
+                let mut new_ts = vec![];
+                let mut new_val = vec![];
+                indexs.into_iter().for_each(|(min, max)| {
+                    new_ts.extend_from_slice(&ts[min..=max]);
+                    new_val.extend_from_slice(&val[min..=max])
+                });
+                DataBlock::I32 {
+                    ts: new_ts,
+                    val: new_val,
+                    enc: *enc,
+                }
+            }
+            DataBlock::F32 { ts, val, enc } => {
+                let mut new_ts = vec![];
+                let mut new_val = vec![];
+                indexs.into_iter().for_each(|(min, max)| {
+                    new_ts.extend_from_slice(&ts[min..=max]);
+                    new_val.extend_from_slice(&val[min..=max])
+                });
+                DataBlock::F32 {
+                    ts: new_ts,
+                    val: new_val,
+                    enc: *enc,
+                }
+            }
         }
     }
 
@@ -690,6 +890,18 @@ impl DataBlock {
                 ta.append(tb);
                 va.append(vb);
             }
+            (DataBlock::I128 { ts: ta, val: va, .. }, DataBlock::I128 { ts: tb, val: vb, .. }) => {
+                ta.append(tb);
+                va.append(vb);
+            }
+            (DataBlock::I32 { ts: ta, val: va, .. }, DataBlock::I32 { ts: tb, val: vb, .. }) => {
+                ta.append(tb);
+                va.append(vb);
+            }
+            (DataBlock::F32 { ts: ta, val: va, .. }, DataBlock::F32 { ts: tb, val: vb, .. }) => {
+                ta.append(tb);
+                va.append(vb);
+            }
             _ => {}
         }
     }
@@ -733,6 +945,21 @@ impl DataBlock {
                 let val_codec = get_f64_codec(val_enc);
                 val_codec.encode(&val[start..end], &mut data_buf)?
             }
+            DataBlock::I128 { ts, val, .. } => {
+                ts_codec.encode(&ts[start..end], &mut ts_buf)?;
+                let val_codec = get_i128_codec(val_enc);
+                val_codec.encode(&val[start..end], &mut data_buf)?
+            }
+            DataBlock::I32 { ts, val, .. } => {
+                ts_codec.encode(&ts[start..end], &mut ts_buf)?;
+                let val_codec = get_i32_codec(val_enc);
+                val_codec.encode(&val[start..end], &mut data_buf)?
+            }
+            DataBlock::F32 { ts, val, .. } => {
+                ts_codec.encode(&ts[start..end], &mut ts_buf)?;
+                let val_codec = get_f32_codec(val_enc);
+                val_codec.encode(&val[start..end], &mut data_buf)?
+            }
         }
         Ok((ts_buf, data_buf))
     }
@@ -809,6 +1036,42 @@ impl DataBlock {
                     enc: DataBlockEncoding::new(ts_encoding, val_encoding),
                 })
             }
+            PhysicalDType::Decimal => {
+                // values will be same length as time-stamps.
+                let mut decoded_val = Vec::with_capacity(count as usize);
+                let val_encoding = get_encoding(val);
+                let val_codec = get_i128_codec(val_encoding);
+                val_codec.decode(val, &mut decoded_val)?;
+                Ok(DataBlock::I128 {
+                    ts: decoded_ts,
+                    val: decoded_val,
+                    enc: DataBlockEncoding::new(ts_encoding, val_encoding),
+                })
+            }
+            PhysicalDType::Integer32 => {
+                // values will be same length as time-stamps.
+                let mut decoded_val = Vec::with_capacity(count as usize);
+                let val_encoding = get_encoding(val);
+                let val_codec = get_i32_codec(val_encoding);
+                val_codec.decode(val, &mut decoded_val)?;
+                Ok(DataBlock::I32 {
+                    ts: decoded_ts,
+                    val: decoded_val,
+                    enc: DataBlockEncoding::new(ts_encoding, val_encoding),
+                })
+            }
+            PhysicalDType::Float32 => {
+                // values will be same length as time-stamps.
+                let mut decoded_val = Vec::with_capacity(count as usize);
+                let val_encoding = get_encoding(val);
+                let val_codec = get_f32_codec(val_encoding);
+                val_codec.decode(val, &mut decoded_val)?;
+                Ok(DataBlock::F32 {
+                    ts: decoded_ts,
+                    val: decoded_val,
+                    enc: DataBlockEncoding::new(ts_encoding, val_encoding),
+                })
+            }
             _ => Err(format!(
                 "cannot decode block {:?} with no unknown value type",
                 field_type
@@ -890,6 +1153,49 @@ impl Display for DataBlock {
                     )
                 }
             }
+            DataBlock::I128 { ts, .. } => {
+                if !ts.is_empty() {
+                    write!(
+                        f,
+                        "I128 {{ len: {}, min_ts: {}, max_ts: {} }}",
+                        ts.len(),
+                        ts.first().unwrap(),
+                        ts.last().unwrap()
+                    )
+                } else {
+                    write!(
+                        f,
+                        "I128 {{ len: {}, min_ts: NONE, max_ts: NONE }}",
+                        ts.len()
+                    )
+                }
+            }
+            DataBlock::I32 { ts, .. } => {
+                if !ts.is_empty() {
+                    write!(
+                        f,
+                        "I32 {{ len: {}, min_ts: {}, max_ts: {} }}",
+                        ts.len(),
+                        ts.first().unwrap(),
+                        ts.last().unwrap()
+                    )
+                } else {
+                    write!(f, "I32 {{ len: {}, min_ts: NONE, max_ts: NONE }}", ts.len())
+                }
+            }
+            DataBlock::F32 { ts, .. } => {
+                if !ts.is_empty() {
+                    write!(
+                        f,
+                        "F32 {{ len: {}, min_ts: {}, max_ts: {} }}",
+                        ts.len(),
+                        ts.first().unwrap(),
+                        ts.last().unwrap()
+                    )
+                } else {
+                    write!(f, "F32 {{ len: {}, min_ts: NONE, max_ts: NONE }}", ts.len())
+                }
+            }
         }
     }
 }
@@ -1042,6 +1348,7 @@ pub struct EncodedDataBlock {
     pub count: u32,
     pub field_type: PhysicalDType,
     pub time_range: Option<TimeRange>,
+    pub statistics: Option<BlockStatistics>,
 }
 
 impl PartialEq for EncodedDataBlock {
@@ -1081,6 +1388,7 @@ impl EncodedDataBlock {
             count: (end - start) as u32,
             field_type: data_block.field_type(),
             time_range: Some(TimeRange::new(min_ts, max_ts)),
+            statistics: BlockStatistics::from_data_block(data_block, start, end),
         })
     }
 
@@ -1093,11 +1401,12 @@ impl EncodedDataBlock {
 pub mod test {
 
     use minivec::mini_vec;
+    use models::codec::Encoding;
     use models::predicate::domain::{TimeRange, TimeRanges};
     use models::PhysicalDType;
 
-    use super::DataBlockReader;
-    use crate::memcache::DataType;
+    use super::{DataBlockReader, EncodedDataBlock};
+    use crate::memcache::{DataType, FieldVal};
     use crate::tsm::codec::DataBlockEncoding;
     use crate::tsm::DataBlock;
 
@@ -1397,4 +1706,26 @@ pub mod test {
             assert_eq!(blk_reader.next(), None);
         }
     }
+
+    #[test]
+    fn test_decimal_block_encode_decode() {
+        let values: Vec<i128> = vec![12345, -12345, 0, i128::MAX, i128::MIN, 10_i128.pow(37)];
+        let ts: Vec<i64> = (1..=values.len() as i64).collect();
+        for val_encoding in [Encoding::Default, Encoding::Null, Encoding::Delta] {
+            let mut blk = DataBlock::new(values.len(), PhysicalDType::Decimal);
+            for (t, v) in ts.iter().zip(values.iter()) {
+                blk.insert(DataType::I128(*t, *v));
+            }
+            blk.set_encoding(DataBlockEncoding::new(Encoding::Default, val_encoding));
+            let encoded = EncodedDataBlock::encode(&blk, 0, blk.len()).unwrap();
+            assert_eq!(encoded.field_type, PhysicalDType::Decimal);
+            assert!(encoded.statistics.is_none());
+            let decoded = encoded.decode().unwrap();
+            assert_eq!(decoded, blk);
+            assert_eq!(
+                decoded.get(3).unwrap().into_field_val(),
+                FieldVal::Decimal(i128::MAX)
+            );
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use std::error::Error;
+
+use models::codec::Encoding;
+
+/// Encodes 128-bit decimals without compression, each value is stored as 16 big-endian bytes.
+pub fn i128_without_compress_encode(
+    src: &[i128],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+    dst.push(Encoding::Null as u8);
+
+    for i in src.iter() {
+        dst.extend_from_slice(i.to_be_bytes().as_slice());
+    }
+    Ok(())
+}
+
+pub fn i128_without_compress_decode(
+    src: &[u8],
+    dst: &mut Vec<i128>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let src = &src[1..];
+    if src.len() % 16 != 0 {
+        return Err(From::from("invalid length of decimal block"));
+    }
+    dst.reserve(src.len() / 16);
+    for chunk in src.chunks_exact(16) {
+        let mut buf = [0_u8; 16];
+        buf.copy_from_slice(chunk);
+        dst.push(i128::from_be_bytes(buf));
+    }
+    Ok(())
+}
+
+/// Encodes 128-bit decimals as zig-zag encoded deltas, each delta is written as a LEB128
+/// varint. Decimals of a column share the same scale, so the deltas are usually small.
+pub fn i128_zigzag_varint_encode(
+    src: &[i128],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+    dst.push(Encoding::Delta as u8);
+
+    let mut prev = 0_i128;
+    for v in src.iter() {
+        write_varint(zig_zag_encode(v.wrapping_sub(prev)), dst);
+        prev = *v;
+    }
+    Ok(())
+}
+
+pub fn i128_zigzag_varint_decode(
+    src: &[u8],
+    dst: &mut Vec<i128>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let mut src = &src[1..];
+    let mut prev = 0_i128;
+    while !src.is_empty() {
+        let (delta, n) = read_varint(src).ok_or("invalid varint in decimal block")?;
+        prev = prev.wrapping_add(zig_zag_decode(delta));
+        dst.push(prev);
+        src = &src[n..];
+    }
+    Ok(())
+}
+
+fn zig_zag_encode(v: i128) -> u128 {
+    ((v << 1) ^ (v >> 127)) as u128
+}
+
+fn zig_zag_decode(v: u128) -> i128 {
+    ((v >> 1) as i128) ^ -((v & 1) as i128)
+}
+
+fn write_varint(mut v: u128, dst: &mut Vec<u8>) {
+    while v >= 0x80 {
+        dst.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    dst.push(v as u8);
+}
+
+/// Returns the decoded value and the number of bytes read.
+fn read_varint(src: &[u8]) -> Option<(u128, usize)> {
+    let mut v = 0_u128;
+    for (i, b) in src.iter().enumerate() {
+        let shift = 7 * i as u32;
+        if shift >= 128 {
+            return None;
+        }
+        v |= ((b & 0x7f) as u128) << shift;
+        if b & 0x80 == 0 {
+            return Some((v, i + 1));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tsm::codec::get_encoding;
+
+    #[test]
+    fn test_zig_zag() {
+        for v in [0, 1, -1, 2, -2, i128::MAX, i128::MIN] {
+            assert_eq!(zig_zag_decode(zig_zag_encode(v)), v);
+        }
+        assert_eq!(zig_zag_encode(-1), 1);
+        assert_eq!(zig_zag_encode(1), 2);
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let src: Vec<i128> = vec![
+            0,
+            12345,
+            12350,
+            -99_999_999_999_999_999_999_999_999_999_999_999,
+            99_999_999_999_999_999_999_999_999_999_999_999,
+            i128::MIN,
+            i128::MAX,
+            7,
+        ];
+
+        let mut dst = vec![];
+        i128_zigzag_varint_encode(&src, &mut dst).unwrap();
+        assert_eq!(get_encoding(&dst), Encoding::Delta);
+        let mut got = vec![];
+        i128_zigzag_varint_decode(&dst, &mut got).unwrap();
+        assert_eq!(got, src);
+
+        let mut dst = vec![];
+        i128_without_compress_encode(&src, &mut dst).unwrap();
+        assert_eq!(get_encoding(&dst), Encoding::Null);
+        assert_eq!(dst.len(), 1 + 16 * src.len());
+        let mut got = vec![];
+        i128_without_compress_decode(&dst, &mut got).unwrap();
+        assert_eq!(got, src);
+    }
+
+    #[test]
+    fn test_encode_small_deltas() {
+        let src: Vec<i128> = (0..1000).map(|i| 1_000_000_000_000 + i * 25).collect();
+        let mut dst = vec![];
+        i128_zigzag_varint_encode(&src, &mut dst).unwrap();
+        assert!(dst.len() < 1000 * 2 + 16);
+        let mut got = vec![];
+        i128_zigzag_varint_decode(&dst, &mut got).unwrap();
+        assert_eq!(got, src);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let mut got = vec![];
+        assert!(i128_zigzag_varint_decode(&[Encoding::Delta as u8, 0x80], &mut got).is_err());
+        assert!(i128_without_compress_decode(&[Encoding::Null as u8, 1, 2], &mut got).is_err());
+    }
+}
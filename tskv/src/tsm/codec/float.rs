@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use integer_encoding::VarInt;
 use q_compress::{auto_compress, auto_decompress, DEFAULT_COMPRESSION_LEVEL};
 
 use crate::byte_utils::decode_be_f64;
@@ -273,6 +274,312 @@ pub fn f64_without_compress_encode(
     Ok(())
 }
 
+/// Writes bits into a byte vector, the most significant bit first.
+struct BitWriter<'a> {
+    dst: &'a mut Vec<u8>,
+    /// Number of unused bits in the last byte of dst.
+    free: u32,
+}
+
+impl<'a> BitWriter<'a> {
+    fn new(dst: &'a mut Vec<u8>) -> Self {
+        Self { dst, free: 0 }
+    }
+
+    /// Writes the lowest `n` bits of `v`, `n` must not be greater than 64.
+    fn write_bits(&mut self, v: u64, n: u32) {
+        let mut remaining = n;
+        while remaining > 0 {
+            if self.free == 0 {
+                self.dst.push(0);
+                self.free = 8;
+            }
+            let take = remaining.min(self.free);
+            let shift = remaining - take;
+            let bits = ((v >> shift) & ((1_u64 << take) - 1)) as u8;
+            let last = self.dst.len() - 1;
+            self.dst[last] |= bits << (self.free - take);
+            self.free -= take;
+            remaining = shift;
+        }
+    }
+}
+
+/// Reads bits written by `BitWriter`.
+struct BitReader<'a> {
+    src: &'a [u8],
+    /// Position of the next bit to read.
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(src: &'a [u8]) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    /// Reads `n` bits as the lowest bits of the returned value, `n` must not be greater than 64.
+    fn read_bits(&mut self, n: u32) -> Result<u64, Box<dyn Error + Send + Sync>> {
+        if self.pos + n as usize > self.src.len() * 8 {
+            return Err(From::from("unexpected end of block"));
+        }
+        let mut v = 0_u64;
+        let mut remaining = n;
+        while remaining > 0 {
+            let available = 8 - (self.pos % 8) as u32;
+            let take = remaining.min(available);
+            let bits =
+                (self.src[self.pos / 8] >> (available - take)) as u64 & ((1_u64 << take) - 1);
+            v = (v << take) | bits;
+            self.pos += take as usize;
+            remaining -= take;
+        }
+        Ok(v)
+    }
+}
+
+// Chimp128 keeps the last 128 values, and XORs each value with the previous value
+// that shares the most trailing zero bits with it.
+// https://www.vldb.org/pvldb/vol15/p3058-liakos.pdf
+const CHIMP_PREVIOUS_VALUES: usize = 128;
+const CHIMP_PREVIOUS_VALUES_LOG2: u32 = 7;
+const CHIMP_THRESHOLD: u32 = 6 + CHIMP_PREVIOUS_VALUES_LOG2;
+const CHIMP_SET_LSB: u64 = (1 << (CHIMP_THRESHOLD + 1)) - 1;
+/// Leading zero counts that can be represented by the 3 bits leading zero field.
+const CHIMP_LEADING_VALUES: [u32; 8] = [0, 8, 12, 16, 18, 20, 22, 24];
+
+/// Returns the index into `CHIMP_LEADING_VALUES` of the nearest representable
+/// leading zero count not greater than `leading_zeros`.
+fn chimp_leading_representation(leading_zeros: u32) -> usize {
+    match leading_zeros {
+        0..=7 => 0,
+        8..=11 => 1,
+        12..=15 => 2,
+        16..=17 => 3,
+        18..=19 => 4,
+        20..=21 => 5,
+        22..=23 => 6,
+        _ => 7,
+    }
+}
+
+/// Encodes floats with Chimp128, the number of values is stored as a varint
+/// after the encoding type, followed by the bit stream:
+///
+/// - The first value is stored as 64 bits.
+/// - `00` + 7 bits index: the value equals to the indexed previous value.
+/// - `01` + 7 bits index + 3 bits leading + 6 bits significant length + significant bits:
+///   the XOR with the indexed previous value has more than `CHIMP_THRESHOLD` trailing zeros.
+/// - `10` + XOR without leading zeros: the XOR with the last value has the same
+///   leading zeros as the last stored leading zeros.
+/// - `11` + 3 bits leading + XOR without leading zeros.
+pub fn f64_chimp_encode(
+    src: &[f64],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    dst.push(Encoding::Chimp as u8);
+    let mut buf = [0_u8; 10];
+    let n = (src.len() as u64).encode_var(&mut buf);
+    dst.extend_from_slice(&buf[..n]);
+
+    let mut writer = BitWriter::new(dst);
+    let first = src[0].to_bits();
+    writer.write_bits(first, 64);
+
+    let mut stored_values = [0_u64; CHIMP_PREVIOUS_VALUES];
+    stored_values[0] = first;
+    let mut indices = vec![0_usize; CHIMP_SET_LSB as usize + 1];
+    // u32::MAX means that no leading zeros is stored.
+    let mut stored_leading = u32::MAX;
+    for (index, v) in src.iter().enumerate().skip(1) {
+        let v = v.to_bits();
+        let key = (v & CHIMP_SET_LSB) as usize;
+        let last = (index - 1) % CHIMP_PREVIOUS_VALUES;
+        let candidate = indices[key];
+        let (previous, xor) = if index - candidate < CHIMP_PREVIOUS_VALUES {
+            let candidate = candidate % CHIMP_PREVIOUS_VALUES;
+            let xor = v ^ stored_values[candidate];
+            if xor.trailing_zeros() > CHIMP_THRESHOLD {
+                (candidate, xor)
+            } else {
+                (last, v ^ stored_values[last])
+            }
+        } else {
+            (last, v ^ stored_values[last])
+        };
+
+        if xor == 0 {
+            writer.write_bits(0b00, 2);
+            writer.write_bits(previous as u64, CHIMP_PREVIOUS_VALUES_LOG2);
+            stored_leading = u32::MAX;
+        } else {
+            let trailing = xor.trailing_zeros();
+            let representation = chimp_leading_representation(xor.leading_zeros());
+            let leading = CHIMP_LEADING_VALUES[representation];
+            if trailing > CHIMP_THRESHOLD {
+                let significant = 64 - leading - trailing;
+                writer.write_bits(0b01, 2);
+                writer.write_bits(previous as u64, CHIMP_PREVIOUS_VALUES_LOG2);
+                writer.write_bits(representation as u64, 3);
+                writer.write_bits(significant as u64, 6);
+                writer.write_bits(xor >> trailing, significant);
+                stored_leading = u32::MAX;
+            } else if leading == stored_leading {
+                writer.write_bits(0b10, 2);
+                writer.write_bits(xor, 64 - leading);
+            } else {
+                writer.write_bits(0b11, 2);
+                writer.write_bits(representation as u64, 3);
+                writer.write_bits(xor, 64 - leading);
+                stored_leading = leading;
+            }
+        }
+
+        stored_values[index % CHIMP_PREVIOUS_VALUES] = v;
+        indices[key] = index;
+    }
+    Ok(())
+}
+
+// ALP encodes floats which were decimals as integers `round(v * 10^e * 10^-f)`, the
+// values which can't be restored exactly are stored as exceptions.
+// https://dl.acm.org/doi/pdf/10.1145/3626717
+const ALP_MAX_EXPONENT: usize = 18;
+const ALP_F10: [f64; ALP_MAX_EXPONENT + 1] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18,
+];
+/// Scaled values must be less than 2^52 to be rounded exactly.
+const ALP_ENCODED_LIMIT: f64 = 4503599627370496.0;
+/// Max number of values used to find the best exponent and factor.
+const ALP_SAMPLES: usize = 64;
+
+fn alp_encode_value(v: f64, exponent: usize, factor: usize) -> Option<i64> {
+    let scaled = v * ALP_F10[exponent] / ALP_F10[factor];
+    if scaled.is_nan() || scaled.abs() >= ALP_ENCODED_LIMIT {
+        return None;
+    }
+    let encoded = scaled.round() as i64;
+    (alp_decode_value(encoded, exponent, factor).to_bits() == v.to_bits()).then_some(encoded)
+}
+
+/// Divides by the exact power of ten, so that decimals with no more than `exponent`
+/// fractional digits are restored to the same values as they are parsed.
+fn alp_decode_value(encoded: i64, exponent: usize, factor: usize) -> f64 {
+    encoded as f64 * ALP_F10[factor] / ALP_F10[exponent]
+}
+
+/// Returns the number of bits needed to store `max - min`.
+fn alp_bit_width(min: i64, max: i64) -> u32 {
+    64 - (max.wrapping_sub(min) as u64).leading_zeros()
+}
+
+/// Finds the exponent and factor that encodes the sampled values with the least size.
+fn alp_find_exponent_factor(src: &[f64]) -> (usize, usize) {
+    let step = (src.len() / ALP_SAMPLES).max(1);
+    let samples = src
+        .iter()
+        .step_by(step)
+        .take(ALP_SAMPLES)
+        .collect::<Vec<_>>();
+
+    let mut best = (0, 0);
+    let mut best_size = usize::MAX;
+    for exponent in 0..=ALP_MAX_EXPONENT {
+        for factor in 0..=exponent {
+            let mut exceptions = 0_usize;
+            let mut min = i64::MAX;
+            let mut max = i64::MIN;
+            for v in samples.iter() {
+                match alp_encode_value(**v, exponent, factor) {
+                    Some(encoded) => {
+                        min = min.min(encoded);
+                        max = max.max(encoded);
+                    }
+                    None => exceptions += 1,
+                }
+            }
+            let width = if min > max {
+                0
+            } else {
+                alp_bit_width(min, max)
+            };
+            // An exception costs 8 bytes value and about 2 bytes position.
+            let size = samples.len() * width as usize + exceptions * 80;
+            if size < best_size {
+                best = (exponent, factor);
+                best_size = size;
+            }
+        }
+    }
+    best
+}
+
+/// Encodes floats with ALP, the layout after the encoding type is:
+///
+/// exponent (1 byte), factor (1 byte), number of values (varint), min encoded value
+/// (varint), bit width (1 byte), bit-packed `encoded - min`, number of exceptions (varint),
+/// and for each exception the position (varint) and the value (8 bytes).
+///
+/// Blocks that are mostly not decimals are encoded with Chimp instead.
+pub fn f64_alp_encode(src: &[f64], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let (exponent, factor) = alp_find_exponent_factor(src);
+    let mut encoded = Vec::with_capacity(src.len());
+    let mut exceptions = vec![];
+    for (i, v) in src.iter().enumerate() {
+        match alp_encode_value(*v, exponent, factor) {
+            Some(e) => encoded.push(Some(e)),
+            None => {
+                encoded.push(None);
+                exceptions.push(i);
+            }
+        }
+    }
+    if exceptions.len() * 2 > src.len() {
+        return f64_chimp_encode(src, dst);
+    }
+
+    // Exceptions are filled by an encoded value so that they don't widen the bit width.
+    let placeholder = encoded.iter().flatten().next().copied().unwrap_or_default();
+    let encoded = encoded
+        .into_iter()
+        .map(|e| e.unwrap_or(placeholder))
+        .collect::<Vec<_>>();
+    let min = encoded.iter().copied().min().unwrap_or_default();
+    let max = encoded.iter().copied().max().unwrap_or_default();
+    let width = alp_bit_width(min, max);
+
+    dst.push(Encoding::Alp as u8);
+    dst.push(exponent as u8);
+    dst.push(factor as u8);
+    let mut buf = [0_u8; 10];
+    let n = (src.len() as u64).encode_var(&mut buf);
+    dst.extend_from_slice(&buf[..n]);
+    let n = min.encode_var(&mut buf);
+    dst.extend_from_slice(&buf[..n]);
+    dst.push(width as u8);
+    let mut writer = BitWriter::new(dst);
+    for e in encoded.iter() {
+        writer.write_bits(e.wrapping_sub(min) as u64, width);
+    }
+
+    let n = (exceptions.len() as u64).encode_var(&mut buf);
+    dst.extend_from_slice(&buf[..n]);
+    for i in exceptions {
+        let n = (i as u64).encode_var(&mut buf);
+        dst.extend_from_slice(&buf[..n]);
+        dst.extend_from_slice(&src[i].to_be_bytes());
+    }
+    Ok(())
+}
+
 // BIT_MASK contains a lookup table where the index is the number of bits
 // and the value is a mask. The table is always read by ANDing the index
 // with 0x3f, such that if the index is 64, position 0 will be read, which
@@ -397,6 +704,113 @@ pub fn f64_without_compress_decode(
     Ok(())
 }
 
+pub fn f64_chimp_decode(
+    src: &[u8],
+    dst: &mut Vec<f64>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let src = &src[1..];
+    let (count, n) = u64::decode_var(src).ok_or("invalid value count")?;
+    let count: usize = count.try_into()?;
+    if count == 0 {
+        return Ok(());
+    }
+
+    let mut reader = BitReader::new(&src[n..]);
+    let mut stored_values = [0_u64; CHIMP_PREVIOUS_VALUES];
+    stored_values[0] = reader.read_bits(64)?;
+    // Every value after the first one takes at least 2 bits.
+    dst.reserve(count.min(src.len() * 4));
+    dst.push(f64::from_bits(stored_values[0]));
+    let mut stored_leading = 0_u32;
+    for index in 1..count {
+        let last = stored_values[(index - 1) % CHIMP_PREVIOUS_VALUES];
+        let v = match reader.read_bits(2)? {
+            0b00 => {
+                let previous = reader.read_bits(CHIMP_PREVIOUS_VALUES_LOG2)? as usize;
+                stored_values[previous]
+            }
+            0b01 => {
+                let previous = reader.read_bits(CHIMP_PREVIOUS_VALUES_LOG2)? as usize;
+                let leading = CHIMP_LEADING_VALUES[reader.read_bits(3)? as usize];
+                let significant = reader.read_bits(6)? as u32;
+                if significant == 0 || leading + significant > 64 {
+                    return Err(From::from("invalid significant bits length"));
+                }
+                let trailing = 64 - leading - significant;
+                stored_values[previous] ^ (reader.read_bits(significant)? << trailing)
+            }
+            0b10 => last ^ reader.read_bits(64 - stored_leading)?,
+            _ => {
+                stored_leading = CHIMP_LEADING_VALUES[reader.read_bits(3)? as usize];
+                last ^ reader.read_bits(64 - stored_leading)?
+            }
+        };
+        stored_values[index % CHIMP_PREVIOUS_VALUES] = v;
+        dst.push(f64::from_bits(v));
+    }
+    Ok(())
+}
+
+pub fn f64_alp_decode(src: &[u8], dst: &mut Vec<f64>) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+    if src.len() < 3 {
+        return Err(From::from("unexpected end of block"));
+    }
+
+    let exponent = src[1] as usize;
+    let factor = src[2] as usize;
+    if exponent > ALP_MAX_EXPONENT || factor > exponent {
+        return Err(From::from("invalid exponent or factor"));
+    }
+    let mut i = 3;
+    let (count, n) = u64::decode_var(&src[i..]).ok_or("invalid value count")?;
+    let count: usize = count.try_into()?;
+    i += n;
+    let (min, n) = i64::decode_var(&src[i..]).ok_or("invalid min value")?;
+    i += n;
+    let width = *src.get(i).ok_or("unexpected end of block")? as u32;
+    if width > 64 {
+        return Err(From::from("invalid bit width"));
+    }
+    i += 1;
+
+    let packed_bits = count
+        .checked_mul(width as usize)
+        .ok_or("value count overflow")?;
+    let packed_len = packed_bits / 8 + usize::from(packed_bits % 8 > 0);
+    if i + packed_len > src.len() {
+        return Err(From::from("unexpected end of block"));
+    }
+    let mut reader = BitReader::new(&src[i..i + packed_len]);
+    let start = dst.len();
+    dst.reserve(count.min(src.len()));
+    for _ in 0..count {
+        let encoded = (reader.read_bits(width)? as i64).wrapping_add(min);
+        dst.push(alp_decode_value(encoded, exponent, factor));
+    }
+    i += packed_len;
+
+    let (exceptions, n) = u64::decode_var(&src[i..]).ok_or("invalid exception count")?;
+    i += n;
+    for _ in 0..exceptions {
+        let (position, n) = u64::decode_var(&src[i..]).ok_or("invalid exception position")?;
+        let position: usize = position.try_into()?;
+        i += n;
+        if position >= count || i + 8 > src.len() {
+            return Err(From::from("invalid exception"));
+        }
+        dst[start + position] = decode_be_f64(&src[i..i + 8]);
+        i += 8;
+    }
+    Ok(())
+}
+
 /// decode decodes a slice of bytes into a vector of floats.
 #[allow(clippy::many_single_char_names)]
 #[allow(clippy::useless_let_if_seq)]
@@ -580,9 +994,15 @@ fn decode_with_sentinel(
 mod tests {
     // use test_helpers::approximately_equal;
 
+    use integer_encoding::VarInt;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
     use crate::tsm::codec::float::{
-        f64_gorilla_decode, f64_gorilla_encode, f64_q_compress_decode, f64_q_compress_encode,
+        f64_alp_decode, f64_alp_encode, f64_chimp_decode, f64_chimp_encode, f64_gorilla_decode,
+        f64_gorilla_encode, f64_q_compress_decode, f64_q_compress_encode,
     };
+    use crate::tsm::codec::{get_encoding, Encoding};
 
     #[test]
     fn encode_no_values() {
@@ -1795,5 +2215,162 @@ mod tests {
             // verify got same values back
             assert_eq!(got, src, "{}", test.name);
         }
+
+        for test in tests.iter() {
+            let mut dst = vec![];
+            let src = test.input.clone();
+
+            f64_chimp_encode(&src, &mut dst).expect("failed to encode");
+
+            let mut got = vec![];
+            f64_chimp_decode(&dst, &mut got).expect("failed to decode");
+            // verify got same values back
+            assert_eq!(got, src, "{}", test.name);
+        }
+
+        for test in tests.iter() {
+            let mut dst = vec![];
+            let src = test.input.clone();
+
+            f64_alp_encode(&src, &mut dst).expect("failed to encode");
+
+            let mut got = vec![];
+            match get_encoding(&dst) {
+                Encoding::Alp => f64_alp_decode(&dst, &mut got).expect("failed to decode"),
+                _ => f64_chimp_decode(&dst, &mut got).expect("failed to decode"),
+            }
+            // verify got same values back
+            assert_eq!(got, src, "{}", test.name);
+        }
+    }
+
+    fn assert_bits_eq(got: &[f64], exp: &[f64]) {
+        assert_eq!(got.len(), exp.len());
+        for (i, (g, e)) in got.iter().zip(exp.iter()).enumerate() {
+            assert_eq!(g.to_bits(), e.to_bits(), "value {i}: {g} != {e}");
+        }
+    }
+
+    fn chimp_round_trip(src: &[f64]) {
+        let mut dst = vec![];
+        f64_chimp_encode(src, &mut dst).expect("failed to encode");
+        let mut got = vec![];
+        f64_chimp_decode(&dst, &mut got).expect("failed to decode");
+        assert_bits_eq(&got, src);
+    }
+
+    fn alp_round_trip(src: &[f64]) -> Encoding {
+        let mut dst = vec![];
+        f64_alp_encode(src, &mut dst).expect("failed to encode");
+        let encoding = get_encoding(&dst);
+        let mut got = vec![];
+        match encoding {
+            Encoding::Alp => f64_alp_decode(&dst, &mut got).expect("failed to decode"),
+            Encoding::Chimp => f64_chimp_decode(&dst, &mut got).expect("failed to decode"),
+            _ => panic!("unexpected encoding {:?}", encoding),
+        }
+        assert_bits_eq(&got, src);
+        encoding
+    }
+
+    #[test]
+    fn encode_special_values_chimp_alp() {
+        let src: Vec<f64> = vec![
+            100.0,
+            222.12,
+            f64::from_bits(0x7ff8000000000001),
+            45.324,
+            f64::NAN,
+            2453.023,
+            -1234.235312132,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            9123419329123.1234,
+            f64::from_bits(0x7ff0000000000002),
+            -19292929929292929292.22,
+            -0.0000000000000000000000000092,
+            0.0,
+            -0.0,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::MIN,
+        ];
+        chimp_round_trip(&src);
+        alp_round_trip(&src);
+
+        let mut dst = vec![];
+        f64_chimp_encode(&[], &mut dst).expect("failed to encode");
+        f64_alp_encode(&[], &mut dst).expect("failed to encode");
+        assert!(dst.is_empty());
+    }
+
+    #[test]
+    fn decode_corrupted_count_chimp_alp() {
+        let mut buf = [0_u8; 10];
+        let n = u64::MAX.encode_var(&mut buf);
+
+        let mut chimp = vec![Encoding::Chimp as u8];
+        chimp.extend_from_slice(&buf[..n]);
+        chimp.extend_from_slice(&1.5_f64.to_bits().to_be_bytes());
+        let mut got = vec![];
+        assert!(f64_chimp_decode(&chimp, &mut got).is_err());
+        assert!(got.capacity() <= chimp.len() * 4);
+
+        let mut alp = vec![Encoding::Alp as u8, 0, 0];
+        alp.extend_from_slice(&buf[..n]);
+        alp.extend_from_slice(&[0, 1, 0xff]);
+        let mut got = vec![];
+        assert!(f64_alp_decode(&alp, &mut got).is_err());
+    }
+
+    #[test]
+    fn encode_decimals_alp() {
+        let src = (0..1000)
+            .map(|i| (i * 37 % 1000 - 300) as f64 / 100.0)
+            .collect::<Vec<_>>();
+        assert_eq!(alp_round_trip(&src), Encoding::Alp);
+
+        let mut alp = vec![];
+        f64_alp_encode(&src, &mut alp).expect("failed to encode");
+        let mut gorilla = vec![];
+        f64_gorilla_encode(&src, &mut gorilla).expect("failed to encode");
+        assert!(alp.len() < gorilla.len());
+    }
+
+    #[test]
+    fn encode_round_trip_property() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let specials = [
+            0.0,
+            -0.0,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+        ];
+        for round in 0..200 {
+            let len = rng.gen_range(1..=1000);
+            let scale = 10_f64.powi(rng.gen_range(0..6));
+            let src = (0..len)
+                .map(|_| match round % 4 {
+                    // decimals
+                    0 => rng.gen_range(-1_000_000_i64..1_000_000) as f64 / scale,
+                    // decimals with a few exceptions
+                    1 if rng.gen_ratio(1, 20) => specials[rng.gen_range(0..specials.len())],
+                    1 => rng.gen_range(0_i64..100_000) as f64 / scale,
+                    // random bits
+                    2 => f64::from_bits(rng.gen()),
+                    // repeated values
+                    _ => rng.gen_range(0..4) as f64 * 0.5,
+                })
+                .collect::<Vec<_>>();
+
+            chimp_round_trip(&src);
+            let encoding = alp_round_trip(&src);
+            if round % 4 == 0 || round % 4 == 3 {
+                assert_eq!(encoding, Encoding::Alp, "round {round}");
+            }
+        }
     }
 }
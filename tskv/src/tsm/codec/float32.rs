@@ -0,0 +1,190 @@
+use std::error::Error;
+
+use q_compress::{auto_compress, auto_decompress, DEFAULT_COMPRESSION_LEVEL};
+
+use crate::tsm::codec::float::{
+    f64_alp_decode, f64_alp_encode, f64_chimp_decode, f64_chimp_encode, f64_gorilla_decode,
+    f64_gorilla_encode,
+};
+use crate::tsm::codec::Encoding;
+
+/// Encodes 32-bit floats without compression, each value is stored as 4 big-endian bytes.
+pub fn f32_without_compress_encode(
+    src: &[f32],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+    dst.push(Encoding::Null as u8);
+
+    for i in src.iter() {
+        dst.extend_from_slice(i.to_be_bytes().as_slice());
+    }
+    Ok(())
+}
+
+pub fn f32_without_compress_decode(
+    src: &[u8],
+    dst: &mut Vec<f32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let src = &src[1..];
+    if src.len() % 4 != 0 {
+        return Err(From::from("invalid length of float32 block"));
+    }
+    dst.reserve(src.len() / 4);
+    for chunk in src.chunks_exact(4) {
+        let mut buf = [0_u8; 4];
+        buf.copy_from_slice(chunk);
+        dst.push(f32::from_be_bytes(buf));
+    }
+    Ok(())
+}
+
+pub fn f32_q_compress_encode(
+    src: &[f32],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    dst.push(Encoding::Quantile as u8);
+
+    dst.append(&mut auto_compress(src, DEFAULT_COMPRESSION_LEVEL));
+    Ok(())
+}
+
+pub fn f32_q_compress_decode(
+    src: &[u8],
+    dst: &mut Vec<f32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let src = &src[1..];
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let mut decode: Vec<f32> = auto_decompress(src)?;
+    dst.append(&mut decode);
+    Ok(())
+}
+
+/// Encodes 32-bit floats with a 64-bit float encoding, widening a f32 is exact, and the
+/// low 29 bits of the widened mantissa are zero, which Gorilla and Chimp don't store.
+fn widen_encode(
+    src: &[f32],
+    dst: &mut Vec<u8>,
+    encode: fn(&[f64], &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let src = src.iter().map(|v| *v as f64).collect::<Vec<_>>();
+    encode(&src, dst)
+}
+
+fn narrow_decode(
+    src: &[u8],
+    dst: &mut Vec<f32>,
+    decode: fn(&[u8], &mut Vec<f64>) -> Result<(), Box<dyn Error + Send + Sync>>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut decoded = vec![];
+    decode(src, &mut decoded)?;
+    dst.extend(decoded.into_iter().map(|v| v as f32));
+    Ok(())
+}
+
+pub fn f32_gorilla_encode(
+    src: &[f32],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    widen_encode(src, dst, f64_gorilla_encode)
+}
+
+pub fn f32_gorilla_decode(
+    src: &[u8],
+    dst: &mut Vec<f32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    narrow_decode(src, dst, f64_gorilla_decode)
+}
+
+pub fn f32_chimp_encode(
+    src: &[f32],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    widen_encode(src, dst, f64_chimp_encode)
+}
+
+pub fn f32_chimp_decode(
+    src: &[u8],
+    dst: &mut Vec<f32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    narrow_decode(src, dst, f64_chimp_decode)
+}
+
+pub fn f32_alp_encode(src: &[f32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+    widen_encode(src, dst, f64_alp_encode)
+}
+
+pub fn f32_alp_decode(src: &[u8], dst: &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+    narrow_decode(src, dst, f64_alp_decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tsm::codec::get_encoding;
+
+    #[test]
+    fn test_encode_decode() {
+        let src: Vec<f32> = vec![
+            0.0,
+            1.5,
+            -1.5,
+            0.1,
+            12.375,
+            f32::MAX,
+            f32::MIN,
+            f32::MIN_POSITIVE,
+            100.25,
+        ];
+
+        type Codec = (
+            fn(&[f32], &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>>,
+            fn(&[u8], &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>>,
+        );
+        let codecs: [Codec; 5] = [
+            (f32_without_compress_encode, f32_without_compress_decode),
+            (f32_q_compress_encode, f32_q_compress_decode),
+            (f32_gorilla_encode, f32_gorilla_decode),
+            (f32_chimp_encode, f32_chimp_decode),
+            (f32_alp_encode, f32_alp_decode),
+        ];
+        for (encode, decode) in codecs {
+            let mut dst = vec![];
+            encode(&src, &mut dst).unwrap();
+            let mut got = vec![];
+            decode(&dst, &mut got).unwrap();
+            assert_eq!(
+                got.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+                src.iter().map(|v| v.to_bits()).collect::<Vec<_>>()
+            );
+        }
+
+        let mut dst = vec![];
+        f32_without_compress_encode(&src, &mut dst).unwrap();
+        assert_eq!(get_encoding(&dst), Encoding::Null);
+        assert_eq!(dst.len(), 1 + 4 * src.len());
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let mut got = vec![];
+        assert!(f32_without_compress_decode(&[Encoding::Null as u8, 1, 2], &mut got).is_err());
+    }
+}
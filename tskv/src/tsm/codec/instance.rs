@@ -7,18 +7,33 @@ use crate::tsm::codec::boolean::{
     bool_bitpack_decode, bool_bitpack_encode, bool_without_compress_decode,
     bool_without_compress_encode,
 };
+use crate::tsm::codec::decimal::{
+    i128_without_compress_decode, i128_without_compress_encode, i128_zigzag_varint_decode,
+    i128_zigzag_varint_encode,
+};
 use crate::tsm::codec::float::{
-    f64_gorilla_decode, f64_gorilla_encode, f64_q_compress_decode, f64_q_compress_encode,
-    f64_without_compress_decode, f64_without_compress_encode,
+    f64_alp_decode, f64_alp_encode, f64_chimp_decode, f64_chimp_encode, f64_gorilla_decode,
+    f64_gorilla_encode, f64_q_compress_decode, f64_q_compress_encode, f64_without_compress_decode,
+    f64_without_compress_encode,
+};
+use crate::tsm::codec::float32::{
+    f32_alp_decode, f32_alp_encode, f32_chimp_decode, f32_chimp_encode, f32_gorilla_decode,
+    f32_gorilla_encode, f32_q_compress_decode, f32_q_compress_encode, f32_without_compress_decode,
+    f32_without_compress_encode,
+};
+use crate::tsm::codec::int32::{
+    i32_q_compress_decode, i32_q_compress_encode, i32_without_compress_decode,
+    i32_without_compress_encode, i32_zigzag_simple8b_decode, i32_zigzag_simple8b_encode,
 };
 use crate::tsm::codec::integer::{
     i64_q_compress_decode, i64_q_compress_encode, i64_without_compress_decode,
     i64_without_compress_encode, i64_zigzag_simple8b_decode, i64_zigzag_simple8b_encode,
 };
 use crate::tsm::codec::string::{
-    str_bzip_decode, str_bzip_encode, str_gzip_decode, str_gzip_encode, str_snappy_decode,
-    str_snappy_encode, str_without_compress_decode, str_without_compress_encode, str_zlib_decode,
-    str_zlib_encode, str_zstd_decode, str_zstd_encode,
+    str_bzip_decode, str_bzip_encode, str_dictionary_decode, str_dictionary_encode,
+    str_gzip_decode, str_gzip_encode, str_snappy_decode, str_snappy_encode,
+    str_without_compress_decode, str_without_compress_encode, str_zlib_decode, str_zlib_encode,
+    str_zstd_decode, str_zstd_encode,
 };
 use crate::tsm::codec::timestamp::{
     ts_q_compress_decode, ts_q_compress_encode, ts_without_compress_decode,
@@ -152,6 +167,30 @@ impl FloatCodec for QuantileFloatCodec {
     }
 }
 
+struct ChimpFloatCodec();
+
+impl FloatCodec for ChimpFloatCodec {
+    fn encode(&self, src: &[f64], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f64_chimp_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<f64>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f64_chimp_decode(src, dst)
+    }
+}
+
+struct AlpFloatCodec();
+
+impl FloatCodec for AlpFloatCodec {
+    fn encode(&self, src: &[f64], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f64_alp_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<f64>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f64_alp_decode(src, dst)
+    }
+}
+
 pub trait UnsignedCodec {
     fn encode(&self, src: &[u64], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>>;
     fn decode(&self, src: &[u8], dst: &mut Vec<u64>) -> Result<(), Box<dyn Error + Send + Sync>>;
@@ -327,6 +366,157 @@ impl StringCodec for ZlibStringCodec {
     }
 }
 
+struct DictionaryStringCodec();
+
+impl StringCodec for DictionaryStringCodec {
+    fn encode(&self, src: &[&[u8]], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        str_dictionary_encode(src, dst)
+    }
+
+    fn decode(
+        &self,
+        src: &[u8],
+        dst: &mut Vec<MiniVec<u8>>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        str_dictionary_decode(src, dst)
+    }
+}
+
+pub trait DecimalCodec {
+    fn encode(&self, src: &[i128], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>>;
+    fn decode(&self, src: &[u8], dst: &mut Vec<i128>) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+struct NullDecimalCodec();
+
+impl DecimalCodec for NullDecimalCodec {
+    fn encode(&self, src: &[i128], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i128_without_compress_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<i128>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i128_without_compress_decode(src, dst)
+    }
+}
+
+struct DeltaDecimalCodec();
+
+impl DecimalCodec for DeltaDecimalCodec {
+    fn encode(&self, src: &[i128], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i128_zigzag_varint_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<i128>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i128_zigzag_varint_decode(src, dst)
+    }
+}
+
+pub trait Integer32Codec {
+    fn encode(&self, src: &[i32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>>;
+    fn decode(&self, src: &[u8], dst: &mut Vec<i32>) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+struct NullInteger32Codec();
+
+impl Integer32Codec for NullInteger32Codec {
+    fn encode(&self, src: &[i32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i32_without_compress_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<i32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i32_without_compress_decode(src, dst)
+    }
+}
+
+struct DeltaInteger32Codec();
+
+impl Integer32Codec for DeltaInteger32Codec {
+    fn encode(&self, src: &[i32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i32_zigzag_simple8b_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<i32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i32_zigzag_simple8b_decode(src, dst)
+    }
+}
+
+struct QuantileInteger32Codec();
+
+impl Integer32Codec for QuantileInteger32Codec {
+    fn encode(&self, src: &[i32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i32_q_compress_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<i32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        i32_q_compress_decode(src, dst)
+    }
+}
+
+pub trait Float32Codec {
+    fn encode(&self, src: &[f32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>>;
+    fn decode(&self, src: &[u8], dst: &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+struct NullFloat32Codec();
+
+impl Float32Codec for NullFloat32Codec {
+    fn encode(&self, src: &[f32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_without_compress_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_without_compress_decode(src, dst)
+    }
+}
+
+struct GorillaFloat32Codec();
+
+impl Float32Codec for GorillaFloat32Codec {
+    fn encode(&self, src: &[f32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_gorilla_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_gorilla_decode(src, dst)
+    }
+}
+
+struct QuantileFloat32Codec();
+
+impl Float32Codec for QuantileFloat32Codec {
+    fn encode(&self, src: &[f32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_q_compress_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_q_compress_decode(src, dst)
+    }
+}
+
+struct ChimpFloat32Codec();
+
+impl Float32Codec for ChimpFloat32Codec {
+    fn encode(&self, src: &[f32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_chimp_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_chimp_decode(src, dst)
+    }
+}
+
+struct AlpFloat32Codec();
+
+impl Float32Codec for AlpFloat32Codec {
+    fn encode(&self, src: &[f32], dst: &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_alp_encode(src, dst)
+    }
+
+    fn decode(&self, src: &[u8], dst: &mut Vec<f32>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        f32_alp_decode(src, dst)
+    }
+}
+
 pub fn get_encoding(src: &[u8]) -> Encoding {
     if src.is_empty() {
         return Encoding::Unknown;
@@ -366,6 +556,8 @@ pub fn get_f64_codec(algo: Encoding) -> Box<dyn FloatCodec + Send + Sync> {
         Encoding::Null => Box::new(NullFloatCodec()),
         Encoding::Gorilla => Box::new(GorillaFloatCodec()),
         Encoding::Quantile => Box::new(QuantileFloatCodec()),
+        Encoding::Chimp => Box::new(ChimpFloatCodec()),
+        Encoding::Alp => Box::new(AlpFloatCodec()),
         _ => Box::new(GorillaFloatCodec()),
     }
 }
@@ -378,6 +570,7 @@ pub fn get_str_codec(algo: Encoding) -> Box<dyn StringCodec + Send + Sync> {
         Encoding::Snappy => Box::new(SnappyStringCodec()),
         Encoding::Zstd => Box::new(ZstdStringCodec()),
         Encoding::Zlib => Box::new(ZlibStringCodec()),
+        Encoding::Dictionary => Box::new(DictionaryStringCodec()),
         _ => Box::new(SnappyStringCodec()),
     }
 }
@@ -389,3 +582,31 @@ pub fn get_bool_codec(algo: Encoding) -> Box<dyn BooleanCodec + Send + Sync> {
         _ => Box::new(BitPackBooleanCodec()),
     }
 }
+
+pub fn get_i128_codec(algo: Encoding) -> Box<dyn DecimalCodec + Send + Sync> {
+    match algo {
+        Encoding::Null => Box::new(NullDecimalCodec()),
+        Encoding::Delta => Box::new(DeltaDecimalCodec()),
+        _ => Box::new(DeltaDecimalCodec()),
+    }
+}
+
+pub fn get_i32_codec(algo: Encoding) -> Box<dyn Integer32Codec + Send + Sync> {
+    match algo {
+        Encoding::Null => Box::new(NullInteger32Codec()),
+        Encoding::Delta => Box::new(DeltaInteger32Codec()),
+        Encoding::Quantile => Box::new(QuantileInteger32Codec()),
+        _ => Box::new(DeltaInteger32Codec()),
+    }
+}
+
+pub fn get_f32_codec(algo: Encoding) -> Box<dyn Float32Codec + Send + Sync> {
+    match algo {
+        Encoding::Null => Box::new(NullFloat32Codec()),
+        Encoding::Gorilla => Box::new(GorillaFloat32Codec()),
+        Encoding::Quantile => Box::new(QuantileFloat32Codec()),
+        Encoding::Chimp => Box::new(ChimpFloat32Codec()),
+        Encoding::Alp => Box::new(AlpFloat32Codec()),
+        _ => Box::new(GorillaFloat32Codec()),
+    }
+}
@@ -0,0 +1,140 @@
+use std::error::Error;
+
+use q_compress::{auto_compress, auto_decompress, DEFAULT_COMPRESSION_LEVEL};
+
+use crate::tsm::codec::integer::{i64_zigzag_simple8b_decode, i64_zigzag_simple8b_encode};
+use crate::tsm::codec::Encoding;
+
+/// Encodes 32-bit integers without compression, each value is stored as 4 big-endian bytes.
+pub fn i32_without_compress_encode(
+    src: &[i32],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+    dst.push(Encoding::Null as u8);
+
+    for i in src.iter() {
+        dst.extend_from_slice(i.to_be_bytes().as_slice());
+    }
+    Ok(())
+}
+
+pub fn i32_without_compress_decode(
+    src: &[u8],
+    dst: &mut Vec<i32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let src = &src[1..];
+    if src.len() % 4 != 0 {
+        return Err(From::from("invalid length of int32 block"));
+    }
+    dst.reserve(src.len() / 4);
+    for chunk in src.chunks_exact(4) {
+        let mut buf = [0_u8; 4];
+        buf.copy_from_slice(chunk);
+        dst.push(i32::from_be_bytes(buf));
+    }
+    Ok(())
+}
+
+/// Encodes 32-bit integers as zig-zag encoded deltas packed by simple8b, the packed
+/// width only depends on the deltas, so it's the same as the 64-bit integer encoding.
+pub fn i32_zigzag_simple8b_encode(
+    src: &[i32],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let src = src.iter().map(|v| *v as i64).collect::<Vec<_>>();
+    i64_zigzag_simple8b_encode(&src, dst)
+}
+
+pub fn i32_zigzag_simple8b_decode(
+    src: &[u8],
+    dst: &mut Vec<i32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut decoded = vec![];
+    i64_zigzag_simple8b_decode(src, &mut decoded)?;
+    dst.reserve(decoded.len());
+    for v in decoded {
+        dst.push(i32::try_from(v).map_err(|_| format!("int32 value {v} overflows"))?);
+    }
+    Ok(())
+}
+
+pub fn i32_q_compress_encode(
+    src: &[i32],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    dst.push(Encoding::Quantile as u8);
+
+    dst.append(&mut auto_compress(src, DEFAULT_COMPRESSION_LEVEL));
+    Ok(())
+}
+
+pub fn i32_q_compress_decode(
+    src: &[u8],
+    dst: &mut Vec<i32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let src = &src[1..];
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let mut decode: Vec<i32> = auto_decompress(src)?;
+    dst.append(&mut decode);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tsm::codec::get_encoding;
+
+    #[test]
+    fn test_encode_decode() {
+        let src: Vec<i32> = vec![0, 1, -1, 100, 99, i32::MAX, i32::MIN, 7];
+
+        let mut dst = vec![];
+        i32_without_compress_encode(&src, &mut dst).unwrap();
+        assert_eq!(get_encoding(&dst), Encoding::Null);
+        assert_eq!(dst.len(), 1 + 4 * src.len());
+        let mut got = vec![];
+        i32_without_compress_decode(&dst, &mut got).unwrap();
+        assert_eq!(got, src);
+
+        let mut dst = vec![];
+        i32_zigzag_simple8b_encode(&src, &mut dst).unwrap();
+        let mut got = vec![];
+        i32_zigzag_simple8b_decode(&dst, &mut got).unwrap();
+        assert_eq!(got, src);
+
+        let mut dst = vec![];
+        i32_q_compress_encode(&src, &mut dst).unwrap();
+        assert_eq!(get_encoding(&dst), Encoding::Quantile);
+        let mut got = vec![];
+        i32_q_compress_decode(&dst, &mut got).unwrap();
+        assert_eq!(got, src);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let mut got = vec![];
+        assert!(i32_without_compress_decode(&[Encoding::Null as u8, 1, 2], &mut got).is_err());
+
+        let mut dst = vec![];
+        i64_zigzag_simple8b_encode(&[i32::MAX as i64 + 1], &mut dst).unwrap();
+        assert!(i32_zigzag_simple8b_decode(&dst, &mut got).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 mod boolean;
+mod decimal;
 mod float;
+mod float32;
 mod instance;
+mod int32;
 mod integer;
 mod simple8b;
 mod string;
@@ -9,6 +12,7 @@ mod unsigned;
 
 pub use instance::*;
 use models::codec::Encoding;
+pub(crate) use string::str_dictionary_decode_matched;
 
 /// Max number of bytes needed to store a varint-encoded 32-bit integer.
 const MAX_VAR_INT_32: usize = 5;
@@ -1,3 +1,5 @@
+use std::cmp::min;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::error::Error;
 use std::io::Write;
@@ -194,6 +196,64 @@ pub fn str_without_compress_encode(
     Ok(())
 }
 
+/// Encodes strings by a dictionary of the distinct strings and codes of the strings
+/// in the dictionary, which is suitable for low-cardinality strings.
+///
+/// ```text
+/// +----------+-----------------+----------------------------+------------+-------+
+/// | encoding | dictionary size | dictionary                 | code width | codes |
+/// | 1 byte   | varint          | (varint length, bytes) * N | 1 byte     | ...   |
+/// +----------+-----------------+----------------------------+------------+-------+
+/// ```
+///
+/// Codes are little-endian integers in `code width` bytes, which is 1, 2 or 4.
+pub fn str_dictionary_encode(
+    src: &[&[u8]],
+    dst: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let mut dictionary: Vec<&[u8]> = Vec::new();
+    let mut dictionary_codes: HashMap<&[u8], u32> = HashMap::new();
+    let mut codes = Vec::with_capacity(src.len());
+    for s in src {
+        let code = *dictionary_codes.entry(*s).or_insert_with(|| {
+            dictionary.push(*s);
+            (dictionary.len() - 1) as u32
+        });
+        codes.push(code);
+    }
+
+    dst.push(Encoding::Dictionary as u8);
+    let mut buf = [0_u8; super::MAX_VAR_INT_64];
+    let n = (dictionary.len() as u64).encode_var(&mut buf);
+    dst.extend_from_slice(&buf[..n]);
+    for s in dictionary.iter() {
+        let n = (s.len() as u64).encode_var(&mut buf);
+        dst.extend_from_slice(&buf[..n]);
+        dst.extend_from_slice(s);
+    }
+    let width = dictionary_code_width(dictionary.len());
+    dst.push(width as u8);
+    for code in codes {
+        dst.extend_from_slice(&code.to_le_bytes()[..width]);
+    }
+
+    Ok(())
+}
+
+fn dictionary_code_width(dictionary_size: usize) -> usize {
+    if dictionary_size <= 1 << 8 {
+        1
+    } else if dictionary_size <= 1 << 16 {
+        2
+    } else {
+        4
+    }
+}
+
 /// Decodes a slice of bytes representing Snappy-compressed data into a vector
 /// of vectors of bytes representing string data, which may or may not be valid
 /// UTF-8.
@@ -346,6 +406,77 @@ pub fn str_without_compress_decode(
     Ok(())
 }
 
+/// Splits data encoded by `str_dictionary_encode` (without the encoding byte)
+/// into the dictionary, the code width and the codes.
+fn split_dictionary(
+    data: &[u8],
+) -> Result<(Vec<&[u8]>, usize, &[u8]), Box<dyn Error + Send + Sync>> {
+    let (size, mut i) = u64::decode_var(data).ok_or("invalid dictionary size")?;
+    let size: usize = size.try_into()?;
+    let mut dictionary = Vec::with_capacity(min(size, data.len()));
+    for _ in 0..size {
+        let (length, num_bytes_read) =
+            u64::decode_var(&data[i..]).ok_or("invalid encoded string length")?;
+        let length: usize = length.try_into()?;
+        let lower = i + num_bytes_read;
+        let upper = lower.checked_add(length).ok_or("length overflow")?;
+        if upper > data.len() {
+            return Err("short buffer".into());
+        }
+        dictionary.push(&data[lower..upper]);
+        i = upper;
+    }
+
+    let width = *data.get(i).ok_or("short buffer")? as usize;
+    if !matches!(width, 1 | 2 | 4) {
+        return Err(format!("invalid dictionary code width {width}").into());
+    }
+    let codes = &data[i + 1..];
+    if codes.len() % width != 0 {
+        return Err("short buffer".into());
+    }
+
+    Ok((dictionary, width, codes))
+}
+
+pub fn str_dictionary_decode(
+    src: &[u8],
+    dst: &mut Vec<MiniVec<u8>>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    str_dictionary_decode_matched(src, dst, |_| true)
+}
+
+/// Decodes strings encoded by `str_dictionary_encode`, `matches` is evaluated once for
+/// each string in the dictionary, and the values whose codes are not matched are decoded
+/// as empty strings.
+pub fn str_dictionary_decode_matched(
+    src: &[u8],
+    dst: &mut Vec<MiniVec<u8>>,
+    matches: impl Fn(&[u8]) -> bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if src.is_empty() {
+        return Ok(());
+    }
+
+    let (dictionary, width, codes) = split_dictionary(&src[1..])?;
+    let dictionary = dictionary
+        .into_iter()
+        .map(|s| matches(s).then(|| MiniVec::from(s)))
+        .collect::<Vec<_>>();
+    dst.reserve(codes.len() / width);
+    for code in codes.chunks_exact(width) {
+        let mut buf = [0_u8; 4];
+        buf[..width].copy_from_slice(code);
+        let code = u32::from_le_bytes(buf) as usize;
+        match dictionary.get(code).ok_or("invalid dictionary code")? {
+            Some(value) => dst.push(value.clone()),
+            None => dst.push(MiniVec::new()),
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -369,6 +500,8 @@ mod tests {
         assert_eq!(dst.to_vec().len(), 0);
         str_without_compress_encode(&src, &mut dst).unwrap();
         assert_eq!(dst.to_vec().len(), 0);
+        str_dictionary_encode(&src, &mut dst).unwrap();
+        assert_eq!(dst.to_vec().len(), 0);
 
         // verify encoded no values.
     }
@@ -435,6 +568,8 @@ mod tests {
         assert_eq!(dst.to_vec().len(), 0);
         str_without_compress_decode(&src, &mut dst).unwrap();
         assert_eq!(dst.to_vec().len(), 0);
+        str_dictionary_decode(&src, &mut dst).unwrap();
+        assert_eq!(dst.to_vec().len(), 0);
     }
 
     #[test]
@@ -551,5 +686,53 @@ mod tests {
         str_without_compress_encode(&data, &mut dst).unwrap();
         str_without_compress_decode(&dst, &mut got).unwrap();
         assert_eq!(data_exp, got);
+        dst.clear();
+        got.clear();
+
+        str_dictionary_encode(&data, &mut dst).unwrap();
+        str_dictionary_decode(&dst, &mut got).unwrap();
+        assert_eq!(data_exp, got);
+    }
+
+    #[test]
+    fn test_dictionary_encode_decode() {
+        let data = (0..1000)
+            .map(|i| ALLSTR[i % 3].as_bytes())
+            .collect::<Vec<_>>();
+        let mut dst = vec![];
+        str_dictionary_encode(&data, &mut dst).unwrap();
+        // 3 distinct strings and 1 byte for each code.
+        let dictionary_len = 1 + ALLSTR[..3].iter().map(|s| 1 + s.len()).sum::<usize>();
+        assert_eq!(dst.len(), 1 + dictionary_len + 1 + 1000);
+
+        let mut got = vec![];
+        str_dictionary_decode(&dst, &mut got).unwrap();
+        assert_eq!(got.len(), 1000);
+        assert!(got.iter().zip(data.iter()).all(|(a, b)| a.as_slice() == *b));
+
+        // Values not matched are decoded as empty strings.
+        got.clear();
+        str_dictionary_decode_matched(&dst, &mut got, |s| s == b"shanghai").unwrap();
+        for (i, v) in got.iter().enumerate() {
+            if i % 3 == 1 {
+                assert_eq!(v.as_slice(), b"shanghai");
+            } else {
+                assert!(v.is_empty());
+            }
+        }
+
+        // Codes are stored in 2 bytes for more than 256 distinct strings.
+        let data_strings = (0..300).map(|i| format!("value {i}")).collect::<Vec<_>>();
+        let data = data_strings
+            .iter()
+            .map(|s| s.as_bytes())
+            .collect::<Vec<_>>();
+        dst.clear();
+        got.clear();
+        str_dictionary_encode(&data, &mut dst).unwrap();
+        str_dictionary_decode(&dst, &mut got).unwrap();
+        assert!(got.iter().zip(data.iter()).all(|(a, b)| a.as_slice() == *b));
+
+        assert!(str_dictionary_decode(&dst[..dst.len() - 1], &mut got).is_err());
     }
 }
@@ -8,7 +8,8 @@ use utils::BloomFilter;
 
 use crate::byte_utils::{decode_be_i64, decode_be_u16, decode_be_u32, decode_be_u64};
 use crate::tsm::{
-    BlockMetaIterator, DataBlock, WriteTsmError, WriteTsmResult, BLOCK_META_SIZE, INDEX_META_SIZE,
+    BlockMetaIterator, BlockStatistics, DataBlock, WriteTsmError, WriteTsmResult, BLOCK_META_SIZE,
+    BLOCK_META_SIZE_V1, BLOCK_STATISTICS_SIZE, INDEX_META_SIZE,
 };
 use crate::ColumnFileId;
 
@@ -16,6 +17,8 @@ use crate::ColumnFileId;
 pub struct Index {
     tsm_file_id: u64,
     bloom_filter: Arc<BloomFilter>,
+    /// Size of each block meta, it's smaller in the TSM files without block statistics.
+    block_meta_size: usize,
 
     /// In-memory index-block data
     ///
@@ -37,12 +40,14 @@ impl Index {
     pub fn new(
         tsm_file_id: u64,
         bloom_filter: Arc<BloomFilter>,
+        block_meta_size: usize,
         data: Vec<u8>,
         field_id_offs: Vec<(FieldId, usize)>,
     ) -> Self {
         Self {
             tsm_file_id,
             bloom_filter,
+            block_meta_size,
             data,
             field_id_offs,
         }
@@ -52,6 +57,10 @@ impl Index {
         self.bloom_filter.clone()
     }
 
+    pub fn block_meta_size(&self) -> usize {
+        self.block_meta_size
+    }
+
     pub fn data(&self) -> &[u8] {
         self.data.as_slice()
     }
@@ -118,7 +127,8 @@ impl IndexMeta {
         }
         let first_blk_beg = self.index_ref.field_id_offs()[self.index_idx].1 + INDEX_META_SIZE;
         let min_ts = decode_be_i64(&self.index_ref.data[first_blk_beg..first_blk_beg + 8]);
-        let last_blk_beg = first_blk_beg + BLOCK_META_SIZE * (self.block_count as usize - 1);
+        let last_blk_beg =
+            first_blk_beg + self.index_ref.block_meta_size * (self.block_count as usize - 1);
         let max_ts = decode_be_i64(&self.index_ref.data[last_blk_beg + 8..last_blk_beg + 16]);
         TimeRange::new(min_ts, max_ts)
     }
@@ -244,6 +254,19 @@ impl BlockMeta {
     pub fn tsm_file_id(&self) -> ColumnFileId {
         self.index_ref.tsm_file_id
     }
+
+    /// Returns statistics of the values in the block,
+    /// returns None if the TSM file or the block has no statistics.
+    pub fn statistics(&self) -> Option<BlockStatistics> {
+        if self.index_ref.block_meta_size < BLOCK_META_SIZE {
+            return None;
+        }
+        let off = self.block_offset + BLOCK_META_SIZE_V1;
+        BlockStatistics::decode(
+            &self.index_ref.data()[off..off + BLOCK_STATISTICS_SIZE],
+            self.field_type,
+        )
+    }
 }
 
 impl Display for BlockMeta {
@@ -282,7 +305,7 @@ pub(crate) fn get_data_block_meta_unchecked(
     field_id: FieldId,
     field_type: ValueType,
 ) -> BlockMeta {
-    let base = index_offset + INDEX_META_SIZE + block_idx * BLOCK_META_SIZE;
+    let base = index_offset + INDEX_META_SIZE + block_idx * index.block_meta_size;
     BlockMeta::new(index, field_id, field_type, base)
 }
 
@@ -329,6 +352,7 @@ pub(crate) struct BlockEntry {
     pub offset: u64,
     pub size: u64,
     pub val_offset: u64,
+    pub statistics: Option<BlockStatistics>,
 }
 
 impl BlockEntry {
@@ -341,6 +365,7 @@ impl BlockEntry {
             offset,
             size,
             val_offset: offset + ts_len,
+            statistics: block_meta.statistics(),
         }
     }
 
@@ -362,6 +387,7 @@ impl BlockEntry {
             size,
             // Encoded timestamps block need a 4-bytes crc checksum together.
             val_offset: offset + encoded_ts_size + 4,
+            statistics: BlockStatistics::from_data_block(data_block, 0, data_block.len()),
         })
     }
 
@@ -373,10 +399,21 @@ impl BlockEntry {
         buf[20..28].copy_from_slice(&self.offset.to_be_bytes()[..]);
         buf[28..36].copy_from_slice(&self.size.to_be_bytes()[..]);
         buf[36..44].copy_from_slice(&self.val_offset.to_be_bytes()[..]);
+        match &self.statistics {
+            Some(statistics) => statistics.encode(&mut buf[44..85]),
+            None => buf[44..85].fill(0),
+        }
     }
 
-    pub fn decode(data: &[u8]) -> Self {
-        assert!(data.len() >= BLOCK_META_SIZE);
+    /// Decode a block meta of the values in type `field_type`,
+    /// statistics are decoded if the data is long enough.
+    pub fn decode(data: &[u8], field_type: ValueType) -> Self {
+        assert!(data.len() >= BLOCK_META_SIZE_V1);
+        let statistics = if data.len() >= BLOCK_META_SIZE {
+            BlockStatistics::decode(&data[44..85], field_type)
+        } else {
+            None
+        };
         Self {
             min_ts: decode_be_i64(&data[0..8]),
             max_ts: decode_be_i64(&data[8..16]),
@@ -384,6 +421,7 @@ impl BlockEntry {
             offset: decode_be_u64(&data[20..28]),
             size: decode_be_u64(&data[28..36]),
             val_offset: decode_be_u64(&data[36..44]),
+            statistics,
         }
     }
 }
@@ -2,12 +2,14 @@ mod block;
 pub mod codec;
 mod index;
 mod reader;
+mod statistics;
 mod tombstone;
 mod writer;
 
 pub use block::*;
 pub use index::*;
 pub use reader::*;
+pub use statistics::*;
 pub use tombstone::{Tombstone, TsmTombstone, TOMBSTONE_FILE_SUFFIX};
 pub use writer::*;
 
@@ -16,11 +18,29 @@ pub(crate) const MAX_BLOCK_VALUES: u32 = 1000;
 
 const HEADER_SIZE: usize = 5;
 const INDEX_META_SIZE: usize = 11;
-const BLOCK_META_SIZE: usize = 44;
+/// Size of a block meta in TSM version 1, without block statistics.
+const BLOCK_META_SIZE_V1: usize = 44;
+const BLOCK_STATISTICS_SIZE: usize = 41;
+const BLOCK_META_SIZE: usize = BLOCK_META_SIZE_V1 + BLOCK_STATISTICS_SIZE; // 85
 const BLOOM_FILTER_SIZE: usize = 64;
 const BLOOM_FILTER_BITS: u64 = 512; // 64 * 8
 const FOOTER_SIZE: usize = BLOOM_FILTER_SIZE + 8; // 72
 
+/// Version of the TSM files without block statistics in the index.
+const TSM_VERSION_1: u8 = 1;
+/// Version of the TSM files to write, with block statistics in the index.
+const TSM_VERSION: u8 = 2;
+
+/// Returns size of the block metas in index of TSM files of the version,
+/// returns None if the version is unknown.
+fn block_meta_size(version: u8) -> Option<usize> {
+    match version {
+        TSM_VERSION_1 => Some(BLOCK_META_SIZE_V1),
+        TSM_VERSION => Some(BLOCK_META_SIZE),
+        _ => None,
+    }
+}
+
 pub trait BlockReader {
     fn decode(&mut self, block: &BlockMeta) -> crate::error::Result<DataBlock>;
 }
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 use std::path::Path;
 use std::sync::Arc;
 
+use models::codec::Encoding;
 use models::predicate::domain::{TimeRange, TimeRanges};
 use models::{FieldId, PhysicalDType as ValueType};
 use snafu::{ResultExt, Snafu};
@@ -9,20 +10,20 @@ use utils::BloomFilter;
 
 use crate::byte_utils::{self, decode_be_i64, decode_be_u16, decode_be_u64};
 use crate::error::{self, Result};
-use crate::file_system::file::async_file::AsyncFile;
 use crate::file_system::file::IFile;
 use crate::file_system::file_manager;
-use crate::file_utils;
 use crate::tsm::codec::{
-    get_bool_codec, get_encoding, get_f64_codec, get_i64_codec, get_str_codec, get_ts_codec,
-    get_u64_codec, DataBlockEncoding,
+    get_bool_codec, get_encoding, get_f32_codec, get_f64_codec, get_i128_codec, get_i32_codec,
+    get_i64_codec, get_str_codec, get_ts_codec, get_u64_codec, str_dictionary_decode_matched,
+    DataBlockEncoding,
 };
 use crate::tsm::tombstone::TsmTombstone;
 use crate::tsm::{
-    get_data_block_meta_unchecked, get_index_meta_unchecked, BlockEntry, BlockMeta, DataBlock,
-    Index, IndexEntry, IndexMeta, BLOCK_META_SIZE, BLOOM_FILTER_SIZE, FOOTER_SIZE, INDEX_META_SIZE,
-    MAX_BLOCK_VALUES,
+    block_meta_size, get_data_block_meta_unchecked, get_index_meta_unchecked, BlockEntry,
+    BlockMeta, DataBlock, Index, IndexEntry, IndexMeta, BLOCK_META_SIZE, BLOOM_FILTER_SIZE,
+    FOOTER_SIZE, HEADER_SIZE, INDEX_META_SIZE, MAX_BLOCK_VALUES,
 };
+use crate::{cold_storage, file_utils};
 
 pub type ReadTsmResult<T, E = ReadTsmError> = std::result::Result<T, E>;
 
@@ -49,20 +50,23 @@ pub enum ReadTsmError {
 
 /// Disk-based index reader
 pub struct IndexFile {
-    reader: Arc<AsyncFile>,
+    reader: Arc<dyn IFile>,
     bloom_filter: BloomFilter,
     idx_meta_buf: [u8; INDEX_META_SIZE],
     blk_meta_buf: [u8; BLOCK_META_SIZE],
+    block_meta_size: usize,
 
     index_offset: u64,
     pos: u64,
     end_pos: u64,
     index_block_idx: usize,
     index_block_count: usize,
+    index_field_type: ValueType,
 }
 
 impl IndexFile {
-    pub(crate) async fn open(reader: Arc<AsyncFile>) -> ReadTsmResult<Self> {
+    pub(crate) async fn open(reader: Arc<dyn IFile>) -> ReadTsmResult<Self> {
+        let block_meta_size = read_block_meta_size(reader.as_ref()).await?;
         let file_len = reader.len();
         let mut footer = [0_u8; FOOTER_SIZE];
         reader
@@ -76,11 +80,13 @@ impl IndexFile {
             bloom_filter,
             idx_meta_buf: [0_u8; INDEX_META_SIZE],
             blk_meta_buf: [0_u8; BLOCK_META_SIZE],
+            block_meta_size,
             index_offset,
             pos: index_offset,
             end_pos: file_len - FOOTER_SIZE as u64,
             index_block_idx: 0,
             index_block_count: 0,
+            index_field_type: ValueType::Unknown,
         })
     }
 
@@ -97,6 +103,7 @@ impl IndexFile {
         let (entry, blk_count) = IndexEntry::decode(&self.idx_meta_buf);
         self.index_block_idx = 0;
         self.index_block_count = blk_count as usize;
+        self.index_field_type = entry.field_type;
 
         Ok(Some(entry))
     }
@@ -106,12 +113,13 @@ impl IndexFile {
         if self.index_block_idx >= self.index_block_count {
             return Ok(None);
         }
+        let blk_meta_buf = &mut self.blk_meta_buf[..self.block_meta_size];
         self.reader
-            .read_at(self.pos, &mut self.blk_meta_buf[..])
+            .read_at(self.pos, blk_meta_buf)
             .await
             .context(ReadIOSnafu)?;
-        self.pos += BLOCK_META_SIZE as u64;
-        let entry = BlockEntry::decode(&self.blk_meta_buf);
+        self.pos += self.block_meta_size as u64;
+        let entry = BlockEntry::decode(blk_meta_buf, self.index_field_type);
         self.index_block_idx += 1;
 
         Ok(Some(entry))
@@ -175,16 +183,28 @@ pub async fn print_tsm_statistics(path: impl AsRef<Path>, show_tombstone: bool)
     println!("PointsCount: {}", points_cnt);
 }
 
-pub async fn load_index(tsm_file_id: u64, reader: Arc<AsyncFile>) -> ReadTsmResult<Index> {
+/// Read the version in header of a TSM file, returns the size of block metas in the index.
+async fn read_block_meta_size(reader: &dyn IFile) -> ReadTsmResult<usize> {
+    let mut header = [0_u8; HEADER_SIZE];
+    reader.read_at(0, &mut header).await.context(ReadIOSnafu)?;
+    let version = header[HEADER_SIZE - 1];
+    block_meta_size(version).ok_or_else(|| ReadTsmError::Invalid {
+        reason: format!("unknown TSM file version {}", version),
+    })
+}
+
+pub async fn load_index(tsm_file_id: u64, reader: Arc<dyn IFile>) -> ReadTsmResult<Index> {
     let len = reader.len();
-    if len < FOOTER_SIZE as u64 {
+    if len < (HEADER_SIZE + FOOTER_SIZE) as u64 {
         return Err(ReadTsmError::Invalid {
             reason: format!(
-                "TSM file ({}) size less than FOOTER_SIZE({})",
-                tsm_file_id, FOOTER_SIZE
+                "TSM file ({}) size less than HEADER_SIZE + FOOTER_SIZE({})",
+                tsm_file_id,
+                HEADER_SIZE + FOOTER_SIZE
             ),
         });
     }
+    let block_meta_size = read_block_meta_size(reader.as_ref()).await?;
     let mut buf = [0u8; FOOTER_SIZE];
 
     // Read index data offset
@@ -212,12 +232,12 @@ pub async fn load_index(tsm_file_id: u64, reader: Arc<AsyncFile>) -> ReadTsmResu
         .context(ReadIOSnafu)?;
 
     // Decode index data
-    let assumed_field_count = (data_len / (INDEX_META_SIZE + BLOCK_META_SIZE)) + 1;
+    let assumed_field_count = (data_len / (INDEX_META_SIZE + block_meta_size)) + 1;
     let mut field_id_offs: Vec<(FieldId, usize)> = Vec::with_capacity(assumed_field_count);
     let mut pos = 0_usize;
     while pos < data_len {
         field_id_offs.push((decode_be_u64(&data[pos..pos + 8]), pos));
-        pos += INDEX_META_SIZE + BLOCK_META_SIZE * decode_be_u16(&data[pos + 9..pos + 11]) as usize;
+        pos += INDEX_META_SIZE + block_meta_size * decode_be_u16(&data[pos + 9..pos + 11]) as usize;
     }
 
     // Sort by field id
@@ -227,6 +247,7 @@ pub async fn load_index(tsm_file_id: u64, reader: Arc<AsyncFile>) -> ReadTsmResu
     Ok(Index::new(
         tsm_file_id,
         Arc::new(bloom_filter),
+        block_meta_size,
         data,
         field_id_offs,
     ))
@@ -238,7 +259,7 @@ pub struct IndexReader {
 }
 
 impl IndexReader {
-    pub async fn open(tsm_file_id: u64, reader: Arc<AsyncFile>) -> Result<Self> {
+    pub async fn open(tsm_file_id: u64, reader: Arc<dyn IFile>) -> Result<Self> {
         let idx = load_index(tsm_file_id, reader)
             .await
             .context(error::ReadTsmSnafu)?;
@@ -365,15 +386,16 @@ impl BlockMetaIterator {
         debug_assert!(min_ts <= max_ts, "time_ranges invalid: {:#?}", time_ranges);
 
         self.time_ranges = Some(time_ranges);
+        let block_meta_size = self.index_ref.block_meta_size();
         let base = self.index_offset + INDEX_META_SIZE;
-        let sli = &self.index_ref.data()[base..base + self.block_count as usize * BLOCK_META_SIZE];
+        let sli = &self.index_ref.data()[base..base + self.block_count as usize * block_meta_size];
         let mut pos = 0_usize;
         let mut idx = 0_usize;
         // Find `idx` of index blocks that time_range.min_ts <= block.max_ts .
         while pos < sli.len() {
             if min_ts > decode_be_i64(&sli[pos + 8..pos + 16]) {
                 // If time_range.min_ts > block.max_ts, go on to check next block.
-                pos += BLOCK_META_SIZE;
+                pos += block_meta_size;
                 idx += 1;
             } else {
                 // If time_range.min_ts <= block.max_ts, This block may be the start block.
@@ -400,7 +422,7 @@ impl BlockMetaIterator {
             } else {
                 // If time_range.max_ts >= block.max_ts, go on to check next block.
                 self.block_meta_idx_end += 1;
-                pos += BLOCK_META_SIZE;
+                pos += block_meta_size;
             }
         }
     }
@@ -424,7 +446,7 @@ impl Iterator for BlockMetaIterator {
                     self.field_type,
                 );
                 self.block_meta_idx += 1;
-                self.block_offset += BLOCK_META_SIZE;
+                self.block_offset += self.index_ref.block_meta_size();
                 if time_ranges.overlaps(&(block_meta.min_ts(), block_meta.max_ts()).into()) {
                     ret = Some(block_meta);
                     break;
@@ -439,7 +461,7 @@ impl Iterator for BlockMetaIterator {
                 self.field_type,
             );
             self.block_meta_idx += 1;
-            self.block_offset += BLOCK_META_SIZE;
+            self.block_offset += self.index_ref.block_meta_size();
             ret = Some(block_meta);
         }
 
@@ -450,7 +472,7 @@ impl Iterator for BlockMetaIterator {
 #[derive(Clone)]
 pub struct TsmReader {
     tsm_file_id: u64,
-    reader: Arc<AsyncFile>,
+    reader: Arc<dyn IFile>,
     index_reader: Arc<IndexReader>,
     tombstone: Arc<TsmTombstone>,
 }
@@ -471,6 +493,24 @@ impl TsmReader {
         })
     }
 
+    /// Open a tsm file that was moved to the cold storage, the tombstone
+    /// of it is still in the local `tombstone_dir`.
+    pub async fn open_cold(
+        tsm_file_id: u64,
+        location: &str,
+        tombstone_dir: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let tsm: Arc<dyn IFile> = Arc::new(cold_storage::open_file(location).await?);
+        let tsm_idx = IndexReader::open(tsm_file_id, tsm.clone()).await?;
+        let tombstone = TsmTombstone::open(tombstone_dir, tsm_file_id).await?;
+        Ok(Self {
+            tsm_file_id,
+            reader: tsm,
+            index_reader: Arc::new(tsm_idx),
+            tombstone: Arc::new(tombstone),
+        })
+    }
+
     pub fn index_iterator(&self) -> IndexIterator {
         self.index_reader.iter()
     }
@@ -496,6 +536,29 @@ impl TsmReader {
         Ok(blk)
     }
 
+    /// Returns a DataBlock of strings without tombstone, if the strings are dictionary-encoded,
+    /// `matches` is evaluated on the dictionary and the values not matched are read as empty strings.
+    pub async fn get_data_block_matched(
+        &self,
+        block_meta: &BlockMeta,
+        matches: &(dyn Fn(&[u8]) -> bool + Send + Sync),
+    ) -> ReadTsmResult<DataBlock> {
+        let mut buf = vec![0_u8; block_meta.size() as usize];
+        self.reader
+            .read_at(block_meta.offset(), &mut buf)
+            .await
+            .context(ReadIOSnafu)?;
+        let mut blk = decode_data_block_matched(
+            &buf,
+            block_meta.field_type(),
+            block_meta.val_off() - block_meta.offset(),
+            Some(matches),
+        )?;
+        self.tombstone
+            .data_block_exclude_tombstones(block_meta.field_id(), &mut blk);
+        Ok(blk)
+    }
+
     // Reads raw data from file and returns the read data size.
     pub async fn get_raw_data(
         &self,
@@ -557,19 +620,19 @@ impl Debug for TsmReader {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TsmReader")
             .field("id", &self.file_id())
-            .field("fd", &self.reader.fd())
+            .field("size", &self.reader.len())
             .finish()
     }
 }
 
 pub struct ColumnReader {
-    reader: Arc<AsyncFile>,
+    reader: Arc<dyn IFile>,
     inner: BlockMetaIterator,
     buf: Vec<u8>,
 }
 
 impl ColumnReader {
-    pub fn new(reader: Arc<AsyncFile>, inner: BlockMetaIterator) -> Self {
+    pub fn new(reader: Arc<dyn IFile>, inner: BlockMetaIterator) -> Self {
         Self {
             reader,
             inner,
@@ -602,7 +665,7 @@ impl ColumnReader {
 }
 
 async fn read_data_block(
-    reader: Arc<AsyncFile>,
+    reader: Arc<dyn IFile>,
     buf: &mut [u8],
     field_type: ValueType,
     offset: u64,
@@ -616,6 +679,15 @@ pub fn decode_data_block(
     buf: &[u8],
     field_type: ValueType,
     val_off: u64,
+) -> ReadTsmResult<DataBlock> {
+    decode_data_block_matched(buf, field_type, val_off, None)
+}
+
+fn decode_data_block_matched(
+    buf: &[u8],
+    field_type: ValueType,
+    val_off: u64,
+    str_matches: Option<&(dyn Fn(&[u8]) -> bool + Send + Sync)>,
 ) -> ReadTsmResult<DataBlock> {
     debug_assert!(buf.len() >= 8);
     if buf.len() < 8 {
@@ -681,8 +753,13 @@ pub fn decode_data_block(
             // values will be same length as time-stamps.
             let mut val = Vec::with_capacity(ts.len());
             let val_encoding = get_encoding(data);
-            let val_codec = get_str_codec(val_encoding);
-            val_codec.decode(data, &mut val).context(DecodeSnafu)?;
+            match (val_encoding, str_matches) {
+                (Encoding::Dictionary, Some(matches)) => {
+                    str_dictionary_decode_matched(data, &mut val, matches)
+                }
+                _ => get_str_codec(val_encoding).decode(data, &mut val),
+            }
+            .context(DecodeSnafu)?;
             Ok(DataBlock::Str {
                 ts,
                 val,
@@ -701,6 +778,42 @@ pub fn decode_data_block(
                 enc: DataBlockEncoding::new(ts_encoding, val_encoding),
             })
         }
+        ValueType::Decimal => {
+            // values will be same length as time-stamps.
+            let mut val = Vec::with_capacity(ts.len());
+            let val_encoding = get_encoding(data);
+            let val_codec = get_i128_codec(val_encoding);
+            val_codec.decode(data, &mut val).context(DecodeSnafu)?;
+            Ok(DataBlock::I128 {
+                ts,
+                val,
+                enc: DataBlockEncoding::new(ts_encoding, val_encoding),
+            })
+        }
+        ValueType::Integer32 => {
+            // values will be same length as time-stamps.
+            let mut val = Vec::with_capacity(ts.len());
+            let val_encoding = get_encoding(data);
+            let val_codec = get_i32_codec(val_encoding);
+            val_codec.decode(data, &mut val).context(DecodeSnafu)?;
+            Ok(DataBlock::I32 {
+                ts,
+                val,
+                enc: DataBlockEncoding::new(ts_encoding, val_encoding),
+            })
+        }
+        ValueType::Float32 => {
+            // values will be same length as time-stamps.
+            let mut val = Vec::with_capacity(ts.len());
+            let val_encoding = get_encoding(data);
+            let val_codec = get_f32_codec(val_encoding);
+            val_codec.decode(data, &mut val).context(DecodeSnafu)?;
+            Ok(DataBlock::F32 {
+                ts,
+                val,
+                enc: DataBlockEncoding::new(ts_encoding, val_encoding),
+            })
+        }
         _ => Err(ReadTsmError::Decode {
             source: From::from(format!(
                 "cannot decode block {:?} with no unknown value type",
@@ -717,16 +830,23 @@ pub mod tsm_reader_tests {
     use std::path::{Path, PathBuf};
     use std::sync::Arc;
 
+    use models::codec::Encoding;
     use models::predicate::domain::{TimeRange, TimeRanges};
-    use models::{FieldId, Timestamp};
+    use models::{FieldId, PhysicalDType as ValueType, Timestamp};
     use snafu::ResultExt;
 
+    use crate::byte_utils::{decode_be_u16, decode_be_u64};
     use crate::error::{self, Error, Result};
     use crate::file_system::file_manager::{self};
     use crate::file_utils;
+    use crate::memcache::FieldVal;
     use crate::tsm::codec::DataBlockEncoding;
     use crate::tsm::tsm_writer_tests::write_to_tsm;
-    use crate::tsm::{BlockEntry, DataBlock, IndexEntry, IndexFile, TsmReader, TsmTombstone};
+    use crate::tsm::{
+        BlockEntry, BlockStatistics, DataBlock, IndexEntry, IndexFile, TsmReader, TsmTombstone,
+        BLOCK_META_SIZE, BLOCK_META_SIZE_V1, BLOOM_FILTER_SIZE, FOOTER_SIZE, HEADER_SIZE,
+        INDEX_META_SIZE, TSM_VERSION_1,
+    };
 
     async fn prepare(dir: impl AsRef<Path>) -> Result<(PathBuf, PathBuf)> {
         if file_manager::try_exists(&dir) {
@@ -825,6 +945,128 @@ pub mod tsm_reader_tests {
         read_and_check(&reader, &expected_data).await.unwrap();
     }
 
+    /// Rewrite the file in version 1, block metas in the index have no statistics.
+    fn rewrite_tsm_v1(tsm_file: &Path) {
+        let data = std::fs::read(tsm_file).unwrap();
+        let index_end = data.len() - FOOTER_SIZE;
+        let index_offset = decode_be_u64(&data[index_end + BLOOM_FILTER_SIZE..]) as usize;
+        let mut data_v1 = data[..index_offset].to_vec();
+        data_v1[HEADER_SIZE - 1] = TSM_VERSION_1;
+        let mut pos = index_offset;
+        while pos < index_end {
+            let block_count = decode_be_u16(&data[pos + 9..pos + 11]) as usize;
+            data_v1.extend_from_slice(&data[pos..pos + INDEX_META_SIZE]);
+            pos += INDEX_META_SIZE;
+            for _ in 0..block_count {
+                data_v1.extend_from_slice(&data[pos..pos + BLOCK_META_SIZE_V1]);
+                pos += BLOCK_META_SIZE;
+            }
+        }
+        data_v1.extend_from_slice(&data[index_end..]);
+        std::fs::write(tsm_file, data_v1).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_tsm_v1() {
+        let (tsm_file, _) = prepare("/tmp/test/tsm_reader/4").await.unwrap();
+        rewrite_tsm_v1(&tsm_file);
+
+        let reader = TsmReader::open(&tsm_file).await.unwrap();
+        for idx in reader.index_iterator() {
+            for blk in idx.block_iterator() {
+                assert!(blk.statistics().is_none());
+            }
+        }
+
+        #[rustfmt::skip]
+        let expected_data: HashMap<FieldId, Vec<DataBlock>> = HashMap::from([
+            (1, vec![DataBlock::U64 { ts: vec![1], val: vec![11], enc: DataBlockEncoding::default() }]
+            ),
+            (2, vec![
+                DataBlock::U64 { ts: vec![1, 2, 3, 4], val: vec![101, 102, 103, 104], enc: DataBlockEncoding::default() },
+                DataBlock::U64 { ts: vec![5, 6, 7, 8], val: vec![105, 106, 107, 108], enc: DataBlockEncoding::default() },
+                DataBlock::U64 { ts: vec![9, 10, 11, 12], val: vec![109, 110, 111, 112], enc: DataBlockEncoding::default() },
+            ]),
+            (3, vec![
+                DataBlock::U64 { ts: vec![5], val: vec![105], enc: DataBlockEncoding::default() },
+                DataBlock::U64 { ts: vec![9], val: vec![109], enc: DataBlockEncoding::default() },
+            ]),
+        ]);
+        read_and_check(&reader, &expected_data).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_tsm_32bit_fields() {
+        let dir = "/tmp/test/tsm_reader/6";
+        let _ = std::fs::remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let tsm_file = file_utils::make_tsm_file(dir, 1);
+
+        #[rustfmt::skip]
+        let data: HashMap<FieldId, Vec<DataBlock>> = HashMap::from([
+            (1, vec![
+                DataBlock::I32 { ts: vec![1, 2, 3], val: vec![i32::MIN, -1, i32::MAX], enc: DataBlockEncoding::default() },
+                DataBlock::I32 { ts: vec![4, 5], val: vec![4, 5], enc: DataBlockEncoding::new(Encoding::Default, Encoding::Null) },
+            ]),
+            (2, vec![
+                DataBlock::F32 { ts: vec![1, 2, 3], val: vec![0.1, -1.5, f32::MAX], enc: DataBlockEncoding::default() },
+                DataBlock::F32 { ts: vec![4, 5], val: vec![4.25, 5.5], enc: DataBlockEncoding::new(Encoding::Default, Encoding::Null) },
+            ]),
+            (3, vec![DataBlock::I64 { ts: vec![1, 2, 3], val: vec![1, 2, 3], enc: DataBlockEncoding::default() }]),
+        ]);
+        write_to_tsm(&tsm_file, &data).await.unwrap();
+
+        let reader = TsmReader::open(&tsm_file).await.unwrap();
+        for idx in reader.index_iterator_opt(1) {
+            assert_eq!(idx.field_type(), ValueType::Integer32);
+        }
+        for idx in reader.index_iterator_opt(2) {
+            assert_eq!(idx.field_type(), ValueType::Float32);
+        }
+        // Null encoded 32-bit values take 4 bytes each, after the crc and the encoding.
+        let blk = reader
+            .index_iterator_opt(1)
+            .flat_map(|idx| idx.block_iterator())
+            .nth(1)
+            .unwrap();
+        assert_eq!(blk.offset() + blk.size() - blk.val_off(), 4 + 1 + 4 * 2);
+        read_and_check(&reader, &data).await.unwrap();
+        drop(reader);
+
+        // 32-bit fields are also read from the files in version 1.
+        rewrite_tsm_v1(&tsm_file);
+        let reader = TsmReader::open(&tsm_file).await.unwrap();
+        for idx in reader.index_iterator() {
+            for blk in idx.block_iterator() {
+                assert!(blk.statistics().is_none());
+            }
+        }
+        read_and_check(&reader, &data).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_block_statistics() {
+        let (tsm_file, _) = prepare("/tmp/test/tsm_reader/5").await.unwrap();
+        let reader = TsmReader::open(&tsm_file).await.unwrap();
+
+        let statistics = reader
+            .index_iterator_opt(2)
+            .flat_map(|idx| idx.block_iterator())
+            .map(|blk| blk.statistics())
+            .collect::<Vec<_>>();
+        assert_eq!(statistics.len(), 3);
+        assert_eq!(
+            statistics[1],
+            Some(BlockStatistics {
+                min: FieldVal::Unsigned(105),
+                max: FieldVal::Unsigned(108),
+                sum: Some(FieldVal::Unsigned(426)),
+                first: FieldVal::Unsigned(105),
+                last: FieldVal::Unsigned(108),
+            })
+        );
+    }
+
     pub(crate) async fn read_opt_and_check(
         reader: &TsmReader,
         field_id: FieldId,
@@ -0,0 +1,278 @@
+use std::cmp::Ordering;
+
+use models::PhysicalDType as ValueType;
+
+use crate::byte_utils::{decode_be_i64, decode_be_u64};
+use crate::memcache::FieldVal;
+use crate::tsm::{DataBlock, BLOCK_STATISTICS_SIZE};
+
+/// Flag of the statistics: min, max, first and last values are set.
+const FLAG_VALUES: u8 = 1;
+/// Flag of the statistics: sum of values is set.
+const FLAG_SUM: u8 = 1 << 1;
+
+/// Statistics of the values in a data block, stored in the index since TSM version 2.
+///
+/// ```text
+/// +-------+---------+
+/// | flags | 1 byte  |
+/// | min   | 8 bytes |
+/// | max   | 8 bytes |
+/// | sum   | 8 bytes |
+/// | first | 8 bytes |
+/// | last  | 8 bytes |
+/// +-------+---------+
+/// ```
+///
+/// There are no null values in a data block, so the number of non-null values
+/// is `BlockMeta::count()`. String and decimal blocks have no statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockStatistics {
+    pub min: FieldVal,
+    pub max: FieldVal,
+    /// Sum of the numeric values, integers are wrapping added as arrow does.
+    /// None for the boolean values.
+    pub sum: Option<FieldVal>,
+    /// Value of the minimum timestamp.
+    pub first: FieldVal,
+    /// Value of the maximum timestamp.
+    pub last: FieldVal,
+}
+
+impl BlockStatistics {
+    /// Compute statistics of the values in `start..end` of the data block,
+    /// returns None if the range is empty or the values are strings or decimals.
+    pub fn from_data_block(data_block: &DataBlock, start: usize, end: usize) -> Option<Self> {
+        if start >= end || end > data_block.len() {
+            return None;
+        }
+        let statistics = match data_block {
+            DataBlock::U64 { val, .. } => {
+                let val = &val[start..end];
+                let sum = val.iter().fold(0_u64, |s, v| s.wrapping_add(*v));
+                values_statistics(val, u64::cmp, Some(FieldVal::Unsigned(sum)), |v| {
+                    FieldVal::Unsigned(*v)
+                })
+            }
+            DataBlock::I64 { val, .. } => {
+                let val = &val[start..end];
+                let sum = val.iter().fold(0_i64, |s, v| s.wrapping_add(*v));
+                values_statistics(val, i64::cmp, Some(FieldVal::Integer(sum)), |v| {
+                    FieldVal::Integer(*v)
+                })
+            }
+            DataBlock::F64 { val, .. } => {
+                let val = &val[start..end];
+                let sum = val.iter().sum::<f64>();
+                values_statistics(val, f64::total_cmp, Some(FieldVal::Float(sum)), |v| {
+                    FieldVal::Float(*v)
+                })
+            }
+            DataBlock::Bool { val, .. } => {
+                values_statistics(&val[start..end], bool::cmp, None, |v| FieldVal::Boolean(*v))
+            }
+            DataBlock::Str { .. }
+            | DataBlock::I128 { .. }
+            | DataBlock::I32 { .. }
+            | DataBlock::F32 { .. } => return None,
+        };
+
+        Some(statistics)
+    }
+
+    pub fn encode(&self, buf: &mut [u8]) {
+        assert!(buf.len() >= BLOCK_STATISTICS_SIZE);
+        buf[0] = FLAG_VALUES;
+        encode_value(&self.min, &mut buf[1..9]);
+        encode_value(&self.max, &mut buf[9..17]);
+        match &self.sum {
+            Some(sum) => {
+                buf[0] |= FLAG_SUM;
+                encode_value(sum, &mut buf[17..25]);
+            }
+            None => buf[17..25].fill(0),
+        }
+        encode_value(&self.first, &mut buf[25..33]);
+        encode_value(&self.last, &mut buf[33..41]);
+    }
+
+    /// Decode statistics of values in type `field_type`, returns None if the statistics is not set.
+    pub fn decode(data: &[u8], field_type: ValueType) -> Option<Self> {
+        assert!(data.len() >= BLOCK_STATISTICS_SIZE);
+        let flags = data[0];
+        if flags & FLAG_VALUES == 0 {
+            return None;
+        }
+        let sum = if flags & FLAG_SUM == 0 {
+            None
+        } else {
+            Some(decode_value(&data[17..25], field_type)?)
+        };
+        Some(Self {
+            min: decode_value(&data[1..9], field_type)?,
+            max: decode_value(&data[9..17], field_type)?,
+            sum,
+            first: decode_value(&data[25..33], field_type)?,
+            last: decode_value(&data[33..41], field_type)?,
+        })
+    }
+}
+
+/// Compare two values of the same type, floats are compared in total order,
+/// so NaN is greater than the other values as arrow does.
+pub fn compare_field_val(a: &FieldVal, b: &FieldVal) -> Ordering {
+    match (a, b) {
+        (FieldVal::Float(a), FieldVal::Float(b)) => a.total_cmp(b),
+        (FieldVal::Integer(a), FieldVal::Integer(b)) => a.cmp(b),
+        (FieldVal::Unsigned(a), FieldVal::Unsigned(b)) => a.cmp(b),
+        (FieldVal::Boolean(a), FieldVal::Boolean(b)) => a.cmp(b),
+        (FieldVal::Bytes(a), FieldVal::Bytes(b)) => a[..].cmp(&b[..]),
+        (FieldVal::Decimal(a), FieldVal::Decimal(b)) => a.cmp(b),
+        (FieldVal::Integer32(a), FieldVal::Integer32(b)) => a.cmp(b),
+        (FieldVal::Float32(a), FieldVal::Float32(b)) => a.total_cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+fn values_statistics<T>(
+    values: &[T],
+    cmp: impl Fn(&T, &T) -> Ordering,
+    sum: Option<FieldVal>,
+    to_field_val: impl Fn(&T) -> FieldVal,
+) -> BlockStatistics {
+    let (mut min, mut max) = (&values[0], &values[0]);
+    for v in &values[1..] {
+        if cmp(v, min) == Ordering::Less {
+            min = v;
+        }
+        if cmp(v, max) == Ordering::Greater {
+            max = v;
+        }
+    }
+
+    BlockStatistics {
+        min: to_field_val(min),
+        max: to_field_val(max),
+        sum,
+        first: to_field_val(&values[0]),
+        last: to_field_val(&values[values.len() - 1]),
+    }
+}
+
+fn encode_value(value: &FieldVal, buf: &mut [u8]) {
+    let bits = match value {
+        FieldVal::Float(v) => v.to_bits(),
+        FieldVal::Integer(v) => *v as u64,
+        FieldVal::Unsigned(v) => *v,
+        FieldVal::Boolean(v) => *v as u64,
+        FieldVal::Bytes(_)
+        | FieldVal::Decimal(_)
+        | FieldVal::Integer32(_)
+        | FieldVal::Float32(_) => 0,
+    };
+    buf[0..8].copy_from_slice(&bits.to_be_bytes());
+}
+
+fn decode_value(data: &[u8], field_type: ValueType) -> Option<FieldVal> {
+    let value = match field_type {
+        ValueType::Float => FieldVal::Float(f64::from_bits(decode_be_u64(data))),
+        ValueType::Integer => FieldVal::Integer(decode_be_i64(data)),
+        ValueType::Unsigned => FieldVal::Unsigned(decode_be_u64(data)),
+        ValueType::Boolean => FieldVal::Boolean(decode_be_u64(data) != 0),
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod test {
+    use minivec::mini_vec;
+    use models::PhysicalDType as ValueType;
+
+    use super::BlockStatistics;
+    use crate::memcache::FieldVal;
+    use crate::tsm::codec::DataBlockEncoding;
+    use crate::tsm::{DataBlock, BLOCK_STATISTICS_SIZE};
+
+    #[test]
+    fn test_block_statistics() {
+        let block = DataBlock::I64 {
+            ts: vec![1, 2, 3, 4, 5],
+            val: vec![3, -1, i64::MAX, 7, 2],
+            enc: DataBlockEncoding::default(),
+        };
+        let statistics = BlockStatistics::from_data_block(&block, 0, 5).unwrap();
+        assert_eq!(statistics.min, FieldVal::Integer(-1));
+        assert_eq!(statistics.max, FieldVal::Integer(i64::MAX));
+        assert_eq!(
+            statistics.sum,
+            Some(FieldVal::Integer(i64::MAX.wrapping_add(11)))
+        );
+        assert_eq!(statistics.first, FieldVal::Integer(3));
+        assert_eq!(statistics.last, FieldVal::Integer(2));
+
+        let statistics = BlockStatistics::from_data_block(&block, 3, 5).unwrap();
+        assert_eq!(statistics.min, FieldVal::Integer(2));
+        assert_eq!(statistics.sum, Some(FieldVal::Integer(9)));
+        assert_eq!(statistics.first, FieldVal::Integer(7));
+        assert!(BlockStatistics::from_data_block(&block, 2, 2).is_none());
+
+        let block = DataBlock::F64 {
+            ts: vec![1, 2, 3],
+            val: vec![1.5, f64::NAN, -2.0],
+            enc: DataBlockEncoding::default(),
+        };
+        let statistics = BlockStatistics::from_data_block(&block, 0, 3).unwrap();
+        assert_eq!(statistics.min, FieldVal::Float(-2.0));
+        assert!(matches!(statistics.max, FieldVal::Float(v) if v.is_nan()));
+
+        let block = DataBlock::Bool {
+            ts: vec![1, 2],
+            val: vec![true, false],
+            enc: DataBlockEncoding::default(),
+        };
+        let statistics = BlockStatistics::from_data_block(&block, 0, 2).unwrap();
+        assert_eq!(statistics.min, FieldVal::Boolean(false));
+        assert_eq!(statistics.sum, None);
+
+        let block = DataBlock::Str {
+            ts: vec![1],
+            val: vec![mini_vec![b'a']],
+            enc: DataBlockEncoding::default(),
+        };
+        assert!(BlockStatistics::from_data_block(&block, 0, 1).is_none());
+    }
+
+    #[test]
+    fn test_encode_decode_block_statistics() {
+        let statistics = BlockStatistics {
+            min: FieldVal::Float(-1.5),
+            max: FieldVal::Float(3.25),
+            sum: Some(FieldVal::Float(4.0)),
+            first: FieldVal::Float(3.25),
+            last: FieldVal::Float(-1.5),
+        };
+        let mut buf = [0_u8; BLOCK_STATISTICS_SIZE];
+        statistics.encode(&mut buf);
+        assert_eq!(
+            BlockStatistics::decode(&buf, ValueType::Float),
+            Some(statistics)
+        );
+
+        let statistics = BlockStatistics {
+            min: FieldVal::Boolean(false),
+            max: FieldVal::Boolean(true),
+            sum: None,
+            first: FieldVal::Boolean(true),
+            last: FieldVal::Boolean(true),
+        };
+        statistics.encode(&mut buf);
+        assert_eq!(
+            BlockStatistics::decode(&buf, ValueType::Boolean),
+            Some(statistics)
+        );
+
+        let buf = [0_u8; BLOCK_STATISTICS_SIZE];
+        assert_eq!(BlockStatistics::decode(&buf, ValueType::Integer), None);
+    }
+}
@@ -14,7 +14,7 @@ use crate::file_system::file_manager;
 use crate::file_utils;
 use crate::tsm::{
     BlockEntry, BlockMeta, DataBlock, IndexEntry, BLOCK_META_SIZE, BLOOM_FILTER_BITS,
-    INDEX_META_SIZE,
+    INDEX_META_SIZE, TSM_VERSION,
 };
 
 // A TSM file is composed for four sections: header, blocks, index and the footer.
@@ -47,6 +47,8 @@ use crate::tsm::{
 // │ 8 bytes │1 byte│2 bytes│ 8 bytes │ 8 bytes │4 bytes │8 bytes │8 bytes │8 bytes│
 // └─────────┴──────┴───────┴─────────┴─────────┴────────┴────────┴────────┴───────┘
 //
+// Since version 2, each block in index is followed by 41 bytes of BlockStatistics.
+//
 // ┌─────────────────────────┐
 // │ Footer                  │
 // ├───────────────┬─────────┤
@@ -56,7 +58,7 @@ use crate::tsm::{
 
 const HEADER_LEN: u64 = 5;
 const TSM_MAGIC: [u8; 4] = 0x01346613_u32.to_be_bytes();
-const VERSION: [u8; 1] = [1];
+const VERSION: [u8; 1] = [TSM_VERSION];
 
 pub type WriteTsmResult<T, E = WriteTsmError> = std::result::Result<T, E>;
 
@@ -504,6 +506,7 @@ async fn write_encoded_block_to(
             size: size as u64,
             // Encoded timestamps block need a 4-byte crc checksum together.
             val_offset: offset + block.ts.len() as u64 + 4,
+            statistics: block.statistics.clone(),
         },
     );
 
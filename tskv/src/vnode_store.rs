@@ -14,6 +14,7 @@ use trace::{debug, error, info, warn, SpanContext, SpanExt, SpanRecorder};
 
 use crate::database::Database;
 use crate::error::Result;
+use crate::file_system::file_manager::try_exists;
 use crate::index::ts_index::TSIndex;
 use crate::schema::error::SchemaError;
 use crate::summary::CompactMeta;
@@ -302,6 +303,15 @@ impl VnodeStorage {
     ///
     /// For one Vnode, multi snapshot may exist at a time.
     pub async fn create_snapshot(&self) -> Result<VnodeSnapshot> {
+        self.create_snapshot_with_summary()
+            .await
+            .map(|(snapshot, _)| snapshot)
+    }
+
+    /// Create a snapshot like `create_snapshot`, and return the summary of the Vnode
+    /// in the snapshot as well. Tsm files in cold storage are downloaded into the snapshot
+    /// directory, and tombstones of files are copied, so the snapshot is self-contained.
+    pub async fn create_snapshot_with_summary(&self) -> Result<(VnodeSnapshot, VersionEdit)> {
         debug!("Snapshot: create snapshot on vnode: {}", self.id);
 
         let vnode_id = self.id;
@@ -330,13 +340,18 @@ impl VnodeStorage {
             let snap_tsm_dir =
                 storage_opt.snapshot_tsm_dir(tenant_database.as_str(), vnode_id, &snapshot_id);
 
-            let (flush_req_optional, mut ve_summary_snapshot) = {
+            // Hold the version until cold files are downloaded, so they are not deleted.
+            let (flush_req_optional, mut ve_summary_snapshot, _version) = {
                 let mut vnode_wlock = vnode.write().await;
                 vnode_wlock.switch_to_immutable();
                 let flush_req_optional = vnode_wlock.build_flush_req(true);
                 let mut _file_metas = HashMap::new();
                 let ve_summary_snapshot = vnode_wlock.build_version_edit(&mut _file_metas);
-                (flush_req_optional, ve_summary_snapshot)
+                (
+                    flush_req_optional,
+                    ve_summary_snapshot,
+                    vnode_wlock.version(),
+                )
             };
 
             // Run force flush
@@ -365,7 +380,7 @@ impl VnodeStorage {
             };
 
             // Do snapshot, file system operations.
-            let files = {
+            let (files, cold_files) = {
                 let _vnode_rlock = vnode.read().await;
 
                 debug!(
@@ -409,21 +424,42 @@ impl VnodeStorage {
                 }
 
                 let mut files = Vec::with_capacity(ve_summary_snapshot.add_files.len());
-                for f in ve_summary_snapshot.add_files {
+                let mut cold_files = vec![];
+                for f in ve_summary_snapshot.add_files.iter_mut() {
                     // Get tsm/delta file path and snapshot file path
+                    let (file_dir, snapshot_file_dir) = if f.is_delta {
+                        (&delta_dir, &snap_delta_dir)
+                    } else {
+                        (&tsm_dir, &snap_tsm_dir)
+                    };
                     let (file_path, snapshot_path) = if f.is_delta {
                         (
-                            file_utils::make_delta_file(&delta_dir, f.file_id),
-                            file_utils::make_delta_file(&snap_delta_dir, f.file_id),
+                            file_utils::make_delta_file(file_dir, f.file_id),
+                            file_utils::make_delta_file(snapshot_file_dir, f.file_id),
                         )
                     } else {
                         (
-                            file_utils::make_tsm_file(&tsm_dir, f.file_id),
-                            file_utils::make_tsm_file(&snap_tsm_dir, f.file_id),
+                            file_utils::make_tsm_file(file_dir, f.file_id),
+                            file_utils::make_tsm_file(snapshot_file_dir, f.file_id),
                         )
                     };
 
-                    files.push(SnapshotFileMeta::from(&f));
+                    files.push(SnapshotFileMeta::from(&*f));
+
+                    // Copy tombstone of the file, tombstones are not immutable.
+                    let tombstone_path = file_utils::make_tsm_tombstone_file(file_dir, f.file_id);
+                    if try_exists(&tombstone_path) {
+                        let snapshot_tombstone_path =
+                            file_utils::make_tsm_tombstone_file(snapshot_file_dir, f.file_id);
+                        std::fs::copy(&tombstone_path, &snapshot_tombstone_path)
+                            .context(crate::error::IOSnafu)?;
+                    }
+
+                    // Tsm file in cold storage is downloaded after releasing the lock.
+                    if let Some(location) = f.cold_location.take() {
+                        cold_files.push((location, snapshot_path));
+                        continue;
+                    }
 
                     // Create hard link to tsm/delta file.
                     debug!(
@@ -443,9 +479,20 @@ impl VnodeStorage {
                     }
                 }
 
-                files
+                (files, cold_files)
             };
 
+            // Download tsm files in cold storage, they become local files in snapshot.
+            for (location, snapshot_path) in cold_files {
+                debug!(
+                    "Snapshot: downloading cold file {location} to {}.",
+                    snapshot_path.display()
+                );
+                crate::cold_storage::get_cold_store()?
+                    .download(&location, &snapshot_path)
+                    .await?;
+            }
+
             let (tenant, database) = models::schema::split_owner(tenant_database.as_str());
             let snapshot = VnodeSnapshot {
                 snapshot_id,
@@ -457,7 +504,7 @@ impl VnodeStorage {
                 last_seq_no,
             };
             debug!("Snapshot: created snapshot: {snapshot:?}");
-            Ok(snapshot)
+            Ok((snapshot, ve_summary_snapshot))
         } else {
             // Vnode not found
             warn!("Snapshot: vnode {vnode_id} not found.");
@@ -516,6 +563,7 @@ impl VnodeStorage {
                     high_seq: last_seq_no,
                     low_seq: 0,
                     is_delta: f.level == 0,
+                    cold_location: None,
                 })
                 .collect(),
             add_tsf: true,
@@ -542,6 +590,23 @@ impl VnodeStorage {
         Ok(())
     }
 
+    /// Delete the directory of a snapshot of the Vnode.
+    pub async fn remove_snapshot(&self, snapshot: &VnodeSnapshot) -> Result<()> {
+        let owner = models::schema::make_owner(&snapshot.tenant, &snapshot.database);
+        let snapshot_dir = self.ctx.options.storage.snapshot_sub_dir(
+            &owner,
+            snapshot.vnode_id,
+            &snapshot.snapshot_id,
+        );
+        debug!(
+            "Snapshot: removing snapshot directory {}.",
+            snapshot_dir.display()
+        );
+        tokio::fs::remove_dir_all(&snapshot_dir)
+            .await
+            .context(crate::error::DeleteFileSnafu { path: snapshot_dir })
+    }
+
     /// Delete the snapshot directory of a Vnode, all snapshots will be deleted.
     pub async fn delete_snapshot(&self) -> Result<()> {
         let vnode_id = self.id;